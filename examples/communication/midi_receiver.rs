@@ -0,0 +1,78 @@
+use nannou::prelude::*;
+use nannou_midi as midi;
+
+fn main() {
+    nannou::app(model).update(update).run();
+}
+
+struct Model {
+    receiver: midi::Receiver,
+    keys: Keys,
+}
+
+struct Keys {
+    // The notes currently held along with their velocity.
+    held: Vec<(u8, u8)>,
+    // The latest value of the mod wheel in the range `0.0..=1.0`.
+    mod_wheel: f32,
+}
+
+fn model(app: &App) -> Model {
+    app.new_window().size(720, 360).view(view).build().unwrap();
+
+    // List the available ports so that the user can find the name of their device.
+    println!("Available MIDI ports: {:#?}", midi::available_ports());
+
+    // Connect to the first available port. Use `.port("name")` to select a specific device.
+    let receiver = midi::InputBuilder::new().build_receiver().unwrap();
+    println!("Listening to \"{}\"", receiver.port_name());
+
+    Model {
+        receiver,
+        keys: Keys {
+            held: vec![],
+            mod_wheel: 0.0,
+        },
+    }
+}
+
+fn update(_app: &App, model: &mut Model, _update: Update) {
+    // Handle any pending messages.
+    model.receiver.dispatch(&mut model.keys, handle_message);
+}
+
+fn handle_message(keys: &mut Keys, msg: midi::MidiMessage) {
+    match msg {
+        midi::MidiMessage::NoteOn { note, velocity, .. } => {
+            println!(
+                "{} ({:.2}hz)",
+                midi::note_name(note),
+                midi::note_to_freq(note)
+            );
+            keys.held.push((note, velocity));
+        }
+        midi::MidiMessage::NoteOff { note, .. } => keys.held.retain(|&(n, _)| n != note),
+        midi::MidiMessage::ControlChange {
+            controller: 1,
+            value,
+            ..
+        } => keys.mod_wheel = value as f32 / 127.0,
+        _ => (),
+    }
+}
+
+fn view(app: &App, model: &Model, frame: Frame) {
+    let draw = app.draw();
+    draw.background().color(BLACK);
+
+    // Draw a circle for each held note, positioned by pitch and sized by velocity.
+    let win = app.window_rect();
+    for &(note, velocity) in &model.keys.held {
+        let x = map_range(note, 0, 127, win.left(), win.right());
+        let radius = map_range(velocity, 0, 127, 5.0, 60.0);
+        let hue = model.keys.mod_wheel;
+        draw.ellipse().x(x).radius(radius).hsv(hue, 0.8, 1.0);
+    }
+
+    draw.to_frame(app, &frame).unwrap();
+}
@@ -0,0 +1,58 @@
+//! Draw a grid of boxes in 3D via a perspective camera.
+//!
+//! Drag with the left mouse button to orbit the camera around the grid.
+use nannou::draw::ArcballCamera;
+use nannou::prelude::*;
+
+fn main() {
+    nannou::app(model).update(update).run();
+}
+
+struct Model {
+    arcball: ArcballCamera,
+}
+
+fn model(app: &App) -> Model {
+    app.new_window().size(1024, 768).view(view).build().unwrap();
+    let target = pt3(0.0, 0.0, 0.0);
+    let mut arcball = ArcballCamera::new(app.camera_3d(), target, 900.0);
+    arcball.pitch = 0.5;
+    Model { arcball }
+}
+
+fn update(app: &App, model: &mut Model, _update: Update) {
+    model.arcball.update(&app.mouse);
+}
+
+fn view(app: &App, model: &Model, frame: Frame) {
+    let draw = app.draw();
+    draw.background().color(BLACK);
+
+    let camera = model.arcball.camera();
+    let draw3d = draw.camera(&camera);
+    let n = 7;
+    let step = 80.0;
+    let offset = (n - 1) as f32 * step * 0.5;
+    for i in 0..n {
+        for j in 0..n {
+            let x = i as f32 * step - offset;
+            let z = j as f32 * step - offset;
+            let h = 20.0 + 100.0 * (0.5 + 0.5 * (app.time + (i + j) as f32 * 0.5).sin());
+            let hue = (i * n + j) as f32 / (n * n) as f32;
+            draw3d
+                .box_3d()
+                .x_y_z(x, h * 0.5, z)
+                .w_h_d(step * 0.6, h, step * 0.6)
+                .color(hsl(hue, 0.6, 0.5));
+        }
+    }
+
+    // Primitives drawn without the camera use the default 2D projection.
+    let win = app.window_rect();
+    draw.ellipse()
+        .xy(win.pad(30.0).bottom_left())
+        .radius(10.0)
+        .color(WHITE);
+
+    draw.to_frame(app, &frame).unwrap();
+}
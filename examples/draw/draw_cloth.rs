@@ -0,0 +1,55 @@
+use nannou::cloth::ClothSimulation;
+use nannou::prelude::*;
+
+const COLS: usize = 48;
+const ROWS: usize = 32;
+
+fn main() {
+    nannou::app(model).update(update).run();
+}
+
+struct Model {
+    cloth: ClothSimulation,
+}
+
+fn model(app: &App) -> Model {
+    app.new_window()
+        .size(1024, 768)
+        .view(view)
+        .mouse_pressed(mouse_pressed)
+        .build()
+        .unwrap();
+    let mut cloth = ClothSimulation::new(COLS, ROWS, 14.0);
+    for col in (0..COLS).step_by(6).chain(Some(COLS - 1)) {
+        cloth.pin(col, 0);
+    }
+    Model { cloth }
+}
+
+fn update(app: &App, model: &mut Model, update: Update) {
+    // Gusts of wind blowing back and forth.
+    let gust = (app.time * 0.7).sin() * 150.0;
+    model.cloth.wind(vec2(1.0, 0.0), gust);
+    model.cloth.update(update.since_last.secs() as f32);
+}
+
+// Tear the cloth along a vertical line beneath the mouse.
+fn mouse_pressed(app: &App, model: &mut Model, _button: MouseButton) {
+    let mouse = app.mouse.position();
+    let cloth = &mut model.cloth;
+    for row in 0..cloth.rows() {
+        for col in 0..cloth.cols() - 1 {
+            let (a, b) = (cloth.position(col, row), cloth.position(col + 1, row));
+            if a.x <= mouse.x && mouse.x < b.x {
+                cloth.cut_spring((col, row), (col + 1, row));
+            }
+        }
+    }
+}
+
+fn view(app: &App, model: &Model, frame: Frame) {
+    let draw = app.draw();
+    draw.background().color(BLACK);
+    model.cloth.draw_mesh(&draw).color(rgba(0.9, 0.4, 0.6, 0.8));
+    draw.to_frame(app, &frame).unwrap();
+}
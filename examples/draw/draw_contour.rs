@@ -0,0 +1,27 @@
+use nannou::prelude::*;
+
+fn main() {
+    nannou::sketch(view).run()
+}
+
+fn view(app: &App, frame: Frame) {
+    let draw = app.draw();
+    let win = app.window_rect();
+    draw.background().color(BLACK);
+
+    // A landscape of moving peaks and valleys.
+    let t = app.time;
+    let field = |p: Point2| {
+        let a = (p.x * 0.01 + t).sin() * (p.y * 0.012 - t * 0.7).cos();
+        let b = ((p.x + p.y) * 0.006 + t * 0.3).sin();
+        a + b * 0.5
+    };
+
+    // Sample the field once and draw a line for each elevation.
+    let levels: Vec<f32> = (-6..=6).map(|i| i as f32 * 0.2).collect();
+    draw.contours(field, win, 120, &levels)
+        .weight(1.5)
+        .color(WHITE);
+
+    draw.to_frame(app, &frame).unwrap();
+}
@@ -0,0 +1,73 @@
+//! A rippling surface drawn from a `GpuMesh`.
+//!
+//! The mesh's colours, texture coordinates and indices are uploaded to the GPU once. Each frame
+//! only the vertex positions are re-uploaded via `update_positions`.
+
+use nannou::draw::GpuMesh;
+use nannou::prelude::*;
+
+const COLS: usize = 64;
+const ROWS: usize = 64;
+const SPACING: f32 = 8.0;
+
+fn main() {
+    nannou::app(model).update(update).run();
+}
+
+struct Model {
+    mesh: GpuMesh,
+    positions: Vec<Point3>,
+}
+
+fn model(app: &App) -> Model {
+    app.new_window().size(1024, 768).view(view).build().unwrap();
+
+    // A grid of vertices coloured by their position.
+    let mut mesh = GpuMesh::new();
+    let normal = vec3(0.0, 0.0, 1.0);
+    for row in 0..ROWS {
+        for col in 0..COLS {
+            let u = col as f32 / (COLS - 1) as f32;
+            let v = row as f32 / (ROWS - 1) as f32;
+            let color = lin_srgba(u, v, 1.0 - u * v, 1.0);
+            mesh.push_vertex(grid_point(col, row, 0.0), normal, pt2(u, v), color);
+        }
+    }
+    let index = |col: usize, row: usize| (row * COLS + col) as u32;
+    for row in 0..ROWS - 1 {
+        for col in 0..COLS - 1 {
+            let (a, b) = (index(col, row), index(col + 1, row));
+            let (c, d) = (index(col + 1, row + 1), index(col, row + 1));
+            mesh.push_triangle(a, b, c);
+            mesh.push_triangle(a, c, d);
+        }
+    }
+    let positions = mesh.positions().to_vec();
+    Model { mesh, positions }
+}
+
+fn update(app: &App, model: &mut Model, _update: Update) {
+    let t = app.time;
+    for row in 0..ROWS {
+        for col in 0..COLS {
+            model.positions[row * COLS + col] = grid_point(col, row, t);
+        }
+    }
+    model.mesh.update_positions(&model.positions);
+}
+
+fn view(app: &App, model: &Model, frame: Frame) {
+    let draw = app.draw();
+    draw.background().color(BLACK);
+    draw.gpu_mesh(&model.mesh);
+    draw.to_frame(app, &frame).unwrap();
+}
+
+// A point on the grid, rippling out from the centre over time.
+fn grid_point(col: usize, row: usize, t: f32) -> Point3 {
+    let x = (col as f32 - (COLS - 1) as f32 * 0.5) * SPACING;
+    let y = (row as f32 - (ROWS - 1) as f32 * 0.5) * SPACING;
+    let d = (x * x + y * y).sqrt();
+    let offset = (d * 0.04 - t * 3.0).sin() * 6.0;
+    pt3(x + offset, y + offset, 0.0)
+}
@@ -0,0 +1,40 @@
+//! Draw ten thousand rotating squares with a single instanced draw call.
+//!
+//! The square is only tessellated once. Each instance provides its own position, rotation, scale
+//! and colour.
+use nannou::prelude::*;
+
+const COUNT: usize = 10_000;
+
+fn main() {
+    nannou::sketch(view).run();
+}
+
+fn view(app: &App, frame: Frame) {
+    let draw = app.draw();
+    draw.background().color(BLACK);
+
+    let win = app.window_rect();
+    let t = app.time;
+
+    // Arrange the instances along a spiral.
+    let positions = (0..COUNT).map(|i| {
+        let f = i as f32 / COUNT as f32;
+        let angle = f * TAU * 40.0 + t * 0.2;
+        let radius = f * win.w().min(win.h()) * 0.5;
+        pt3(angle.cos() * radius, angle.sin() * radius, 0.0)
+    });
+    let rotations = (0..COUNT).map(|i| i as f32 * 0.01 + t);
+    let scales = (0..COUNT).map(|i| 1.0 + (i as f32 * 0.05 + t * 2.0).sin() * 0.5);
+    let colors = (0..COUNT).map(|i| hsla(i as f32 / COUNT as f32, 1.0, 0.5, 0.8));
+
+    draw.instanced(COUNT as u32)
+        .positions(positions)
+        .rotations(rotations)
+        .scales(scales)
+        .colors(colors)
+        .rect()
+        .w_h(4.0, 4.0);
+
+    draw.to_frame(app, &frame).unwrap();
+}
@@ -0,0 +1,42 @@
+use nannou::lsystem::{self, LSystem, Turtle};
+use nannou::prelude::*;
+
+fn main() {
+    nannou::app(model).run();
+}
+
+struct Model {
+    plant: String,
+}
+
+fn model(app: &App) -> Model {
+    app.new_window().size(720, 720).view(view).build().unwrap();
+    // A fractal plant, as described in "The Algorithmic Beauty of Plants".
+    let mut system = LSystem::new("X");
+    system.add_rule('X', "F+[[X]-X]-F[-FX]+X");
+    system.add_rule('F', "FF");
+    let plant = system.nth(6);
+    Model { plant }
+}
+
+fn view(app: &App, model: &Model, frame: Frame) {
+    let draw = app.draw();
+    draw.background().color(BLACK);
+
+    // Sway the plant by varying the branching angle over time.
+    let angle = 25.0 + (app.time * 0.5).sin() * 3.0;
+    let commands = lsystem::default_commands(2.5, angle);
+
+    let win = app.window_rect();
+    let mut turtle = Turtle::new();
+    turtle.pen_up();
+    turtle.goto(pt2(0.0, win.bottom()));
+    turtle.pen_down();
+    turtle.set_heading(-10.0);
+    turtle.pen_color(PALEGREEN);
+    turtle.hide();
+    turtle.interpret(&model.plant, &commands);
+    turtle.draw(&draw);
+
+    draw.to_frame(app, &frame).unwrap();
+}
@@ -0,0 +1,51 @@
+use nannou::particle::Force;
+use nannou::prelude::*;
+
+fn main() {
+    nannou::app(model).update(update).run();
+}
+
+struct Model {
+    particles: ParticleSystem,
+}
+
+fn model(app: &App) -> Model {
+    app.new_window().size(1024, 768).view(view).build().unwrap();
+    let particles = ParticleSystem::new(100_000);
+    Model { particles }
+}
+
+fn update(app: &App, model: &mut Model, update: Update) {
+    let dt = update.since_last.secs() as f32;
+
+    // Spray particles from the mouse, coloured by the time at which they were emitted.
+    let hue = (app.time * 0.1).fract();
+    for _ in 0..200 {
+        let angle = random_range(0.0, TAU);
+        let speed = random_range(50.0, 250.0);
+        let velocity = vec2(angle.cos(), angle.sin()) * speed;
+        let lifetime = random_range(2.0, 6.0);
+        let color = hsla(hue, 0.8, 0.6, 0.5);
+        model
+            .particles
+            .emit(app.mouse.position(), velocity, lifetime, color);
+    }
+
+    // Particles fall while being pulled toward the centre of the window.
+    let forces = [
+        Force::Gravity(vec2(0.0, -60.0)),
+        Force::Drag(0.3),
+        Force::Attractor {
+            pos: pt2(0.0, 0.0),
+            strength: 200_000.0,
+        },
+    ];
+    model.particles.update(dt, &forces);
+}
+
+fn view(app: &App, model: &Model, frame: Frame) {
+    let draw = app.draw();
+    draw.background().color(BLACK);
+    draw.blend(BLEND_ADD).particle_system(&model.particles);
+    draw.to_frame(app, &frame).unwrap();
+}
@@ -0,0 +1,55 @@
+//! Draw each of the 3D solid primitives, slowly rotating.
+//!
+//! Solids are lit by a fixed directional light using their vertex normals. Use `.lit(false)` to
+//! disable lighting.
+use nannou::prelude::*;
+
+fn main() {
+    nannou::sketch(view).run();
+}
+
+fn view(app: &App, frame: Frame) {
+    let draw = app.draw();
+    draw.background().color(BLACK);
+
+    let t = app.time;
+    let win = app.window_rect();
+    let step = win.w() / 6.0;
+    let x = |i: f32| win.left() + step * (i + 1.0);
+
+    draw.sphere()
+        .x(x(0.0))
+        .radius(step * 0.35)
+        .color(STEELBLUE)
+        .roll(t)
+        .pitch(t * 0.5);
+    draw.box_3d()
+        .x(x(1.0))
+        .w_h_d(step * 0.6, step * 0.6, step * 0.6)
+        .color(CORAL)
+        .roll(t)
+        .pitch(t * 0.5);
+    draw.cylinder()
+        .x(x(2.0))
+        .radius(step * 0.25)
+        .height(step * 0.7)
+        .color(SEAGREEN)
+        .roll(t)
+        .pitch(t * 0.5);
+    draw.cone()
+        .x(x(3.0))
+        .radius(step * 0.3)
+        .height(step * 0.7)
+        .color(GOLD)
+        .roll(t)
+        .pitch(t * 0.5);
+    draw.torus()
+        .x(x(4.0))
+        .radius(step * 0.3)
+        .tube_radius(step * 0.1)
+        .color(ORCHID)
+        .roll(t)
+        .pitch(t * 0.5);
+
+    draw.to_frame(app, &frame).unwrap();
+}
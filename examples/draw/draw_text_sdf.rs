@@ -0,0 +1,32 @@
+//! Text drawn via signed distance fields remains sharp as it is scaled and may be outlined or
+//! given a glow, all within the shader.
+//!
+//! Compare with `draw_text.rs` which uses the default coverage rendering.
+
+use nannou::draw::TextRenderMode;
+use nannou::prelude::*;
+
+fn main() {
+    nannou::sketch(view).run()
+}
+
+fn view(app: &App, frame: Frame) {
+    let draw = app.draw();
+    draw.background().color(BLACK);
+
+    // Slowly scale the text up and down to show that the glyphs remain sharp.
+    let scale = 1.0 + (app.time * 0.5).sin() * 0.75;
+
+    draw.scale(scale)
+        .text("SDF")
+        .render_mode(TextRenderMode::Sdf)
+        .font_size(96)
+        .color(WHITE)
+        .outline_width(3.0)
+        .outline_color(DEEPPINK)
+        .glow_radius(6.0)
+        .glow_color(CYAN)
+        .w_h(400.0, 200.0);
+
+    draw.to_frame(app, &frame).unwrap();
+}
@@ -0,0 +1,37 @@
+use nannou::draw::ArrowHead;
+use nannou::prelude::*;
+
+fn main() {
+    nannou::sketch(view).run()
+}
+
+fn view(app: &App, frame: Frame) {
+    let draw = app.draw();
+    let win = app.window_rect();
+    draw.background().color(BLACK);
+
+    // Sample a swirling field on a regular grid.
+    let step = 30.0;
+    let t = app.time;
+    let mut positions = vec![];
+    let mut vectors = vec![];
+    let mut y = win.bottom() + step * 0.5;
+    while y < win.top() {
+        let mut x = win.left() + step * 0.5;
+        while x < win.right() {
+            let angle = (x * 0.01 + t).sin() + (y * 0.01 - t).cos();
+            let mag = 0.5 + 0.5 * (x * 0.005 + y * 0.005 + t).sin();
+            positions.push(pt2(x, y));
+            vectors.push(vec2(angle.cos(), angle.sin()) * mag);
+            x += step;
+        }
+        y += step;
+    }
+
+    draw.vector_field(&positions, &vectors, step * 0.9)
+        .head_style(ArrowHead::Open)
+        .weight(0.06)
+        .color(WHITE);
+
+    draw.to_frame(app, &frame).unwrap();
+}
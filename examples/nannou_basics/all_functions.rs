@@ -47,6 +47,7 @@ fn event(_app: &App, _model: &mut Model, event: Event) {
         } => {}
         Event::DeviceEvent(_device_id, _event) => {}
         Event::Update(_dt) => {}
+        Event::Asset(_event) => {}
         Event::Suspended => {}
         Event::Resumed => {}
     }
@@ -1,5 +1,4 @@
-//! A simple example demonstrating the behaviour of the three `LoopMode` variants supported by
-//! nannou.
+//! A simple example demonstrating the behaviour of the `LoopMode` variants supported by nannou.
 //!
 //! The `LoopMode` determines how the nannou application loop is driven.
 //!
@@ -41,7 +40,9 @@ fn key_pressed(app: &App, _model: &mut Model, _key: Key) {
         LoopMode::Wait { .. } => app.set_loop_mode(LoopMode::RefreshSync),
         LoopMode::RefreshSync { .. } => app.set_loop_mode(LoopMode::rate_fps(60.0)),
         LoopMode::Rate { .. } => app.set_loop_mode(LoopMode::loop_once()),
-        LoopMode::NTimes { .. } => app.set_loop_mode(LoopMode::Wait),
+        LoopMode::NTimes { .. } => app.set_loop_mode(LoopMode::fixed_step(30.0)),
+        LoopMode::FixedStep { .. } => app.set_loop_mode(LoopMode::on_event()),
+        LoopMode::OnEvent { .. } => app.set_loop_mode(LoopMode::Wait),
     }
     println!("Loop mode switched to: {:?}", app.loop_mode());
     let title = format!("`LoopMode` Demonstration - `{:?}`", app.loop_mode());
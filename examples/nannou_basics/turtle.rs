@@ -0,0 +1,51 @@
+use nannou::prelude::*;
+
+fn main() {
+    nannou::app(model).update(update).simple_window(view).run();
+}
+
+struct Model;
+
+fn model(app: &App) -> Model {
+    // Two independent turtles, each accessed by name. Only one may be borrowed at a time.
+    {
+        let mut ada = app.turtle("ada");
+        ada.pen_color(ORANGE);
+        ada.pen_width(2.0);
+    }
+    {
+        let mut grace = app.turtle("grace");
+        grace.pen_color(SKYBLUE);
+        grace.pen_up();
+        grace.goto(pt2(-200.0, -200.0));
+        grace.pen_down();
+    }
+    Model
+}
+
+fn update(app: &App, _model: &mut Model, _update: Update) {
+    // Each update, both turtles take another step of their spirals.
+    let step = app.elapsed_frames() as f32;
+    if step > 360.0 {
+        return;
+    }
+    {
+        let mut ada = app.turtle("ada");
+        ada.forward(step * 0.5);
+        ada.turn_right(91.0);
+    }
+    let mut grace = app.turtle("grace");
+    grace.forward(4.0);
+    grace.turn_left(3.0);
+    if step as u32 % 30 == 0 {
+        grace.stamp();
+    }
+}
+
+fn view(app: &App, _model: &Model, frame: Frame) {
+    let draw = app.draw();
+    draw.background().color(BLACK);
+    app.turtle("ada").draw(&draw);
+    app.turtle("grace").draw(&draw);
+    draw.to_frame(app, &frame).unwrap();
+}
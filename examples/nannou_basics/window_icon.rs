@@ -0,0 +1,33 @@
+//! Set the icon displayed within the window's titlebar and the taskbar.
+//!
+//! Window icons are supported on Windows and Linux (X11). On macOS, the icon is specified by the
+//! app bundle instead and `set_icon` has no effect.
+use nannou::prelude::*;
+
+fn main() {
+    nannou::app(model).run();
+}
+
+struct Model;
+
+fn model(app: &App) -> Model {
+    let window = app
+        .new_window()
+        .size(512, 512)
+        .title("nannou")
+        .view(view)
+        .build()
+        .unwrap();
+
+    // Load the nannou logo from the assets directory and use it as the window's icon.
+    let assets = app.assets_path().unwrap();
+    let icon_path = assets.join("images").join("nannou.png");
+    let icon = app.new_icon_from_file(icon_path).unwrap();
+    app.window(window).unwrap().set_icon(&icon);
+
+    Model
+}
+
+fn view(_app: &App, _model: &Model, frame: Frame) {
+    frame.clear(CORNFLOWERBLUE);
+}
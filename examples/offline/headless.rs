@@ -0,0 +1,50 @@
+// Render a short animation without a window, writing each frame to `headless_frames/`.
+//
+// As time advances by a fixed interval and the random number generator is seeded, every run
+// produces the same images, e.g. for comparison against reference images in CI.
+use nannou::app::headless;
+use nannou::prelude::*;
+
+struct Model {
+    points: Vec<Point2>,
+}
+
+fn main() {
+    let config = headless::Config::new()
+        .size(640, 360)
+        .frames(60)
+        .seed(42)
+        .output_dir("headless_frames");
+    if let Err(err) = App::run_headless(model, update, view, config) {
+        eprintln!("failed to run headless: {}", err);
+    }
+}
+
+fn model(app: &App) -> Model {
+    let rect = app.window_rect();
+    let points = (0..100)
+        .map(|_| {
+            pt2(
+                random_range(rect.left(), rect.right()),
+                random_range(rect.bottom(), rect.top()),
+            )
+        })
+        .collect();
+    Model { points }
+}
+
+fn update(app: &App, model: &mut Model, _update: Update) {
+    let t = app.time;
+    for (i, p) in model.points.iter_mut().enumerate() {
+        p.y += (t + i as f32).sin();
+    }
+}
+
+fn view(app: &App, model: &Model, frame: Frame) {
+    let draw = app.draw();
+    draw.background().color(BLACK);
+    for p in &model.points {
+        draw.ellipse().xy(*p).radius(4.0).color(WHITE);
+    }
+    draw.to_frame(app, &frame).unwrap();
+}
@@ -0,0 +1,160 @@
+//! A particle simulation running in a compute shader.
+//!
+//! Demonstrates dispatching a compute shader via `frame.begin_compute_pass()`. The builder takes
+//! care of creating the bind group layout and the pipeline, caching the pipeline between frames.
+//!
+//! Each frame the simulation is advanced on the GPU, after which the particle buffer is read back
+//! to the CPU where the particles are drawn. The particles are attracted to the mouse.
+
+use nannou::prelude::*;
+use std::sync::{Arc, Mutex};
+
+struct Model {
+    particle_buffer: wgpu::Buffer,
+    uniform_buffer: wgpu::Buffer,
+    particles: Arc<Mutex<Vec<Particle>>>,
+    threadpool: futures::executor::ThreadPool,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct Particle {
+    position: [f32; 2],
+    velocity: [f32; 2],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct Uniforms {
+    attractor: [f32; 2],
+    dt: f32,
+    particle_count: u32,
+}
+
+const PARTICLE_COUNT: u32 = 4096;
+// Must match the `local_size_x` of the compute shader.
+const WORKGROUP_SIZE: u32 = 64;
+
+const PARTICLES_SIZE: wgpu::BufferAddress =
+    (PARTICLE_COUNT as usize * std::mem::size_of::<Particle>()) as wgpu::BufferAddress;
+const UNIFORMS_SIZE: wgpu::BufferAddress = std::mem::size_of::<Uniforms>() as wgpu::BufferAddress;
+
+fn main() {
+    nannou::app(model).run();
+}
+
+fn model(app: &App) -> Model {
+    let w_id = app.new_window().size(1024, 768).view(view).build().unwrap();
+    let window = app.window(w_id).unwrap();
+    let device = window.swap_chain_device();
+    let rect = window.rect();
+
+    // Scatter the particles across the window.
+    let particles: Vec<Particle> = (0..PARTICLE_COUNT)
+        .map(|_| Particle {
+            position: [
+                random_range(rect.left(), rect.right()),
+                random_range(rect.bottom(), rect.top()),
+            ],
+            velocity: [random_range(-50.0, 50.0), random_range(-50.0, 50.0)],
+        })
+        .collect();
+
+    let particles_bytes = unsafe { wgpu::bytes::from_slice(&particles) };
+    let usage = wgpu::BufferUsage::STORAGE | wgpu::BufferUsage::COPY_SRC;
+    let particle_buffer = device.create_buffer_with_data(particles_bytes, usage);
+
+    let uniforms = create_uniforms(app);
+    let uniforms_bytes = unsafe { wgpu::bytes::from(&uniforms) };
+    let usage = wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST;
+    let uniform_buffer = device.create_buffer_with_data(uniforms_bytes, usage);
+
+    let particles = Arc::new(Mutex::new(particles));
+    let threadpool = futures::executor::ThreadPool::new().unwrap();
+
+    Model {
+        particle_buffer,
+        uniform_buffer,
+        particles,
+        threadpool,
+    }
+}
+
+fn view(app: &App, model: &Model, frame: Frame) {
+    let device = frame.device_queue_pair().device();
+
+    // Update the uniforms with the latest mouse position and timestep.
+    let uniforms = create_uniforms(app);
+    let uniforms_bytes = unsafe { wgpu::bytes::from(&uniforms) };
+    let new_uniform_buffer =
+        device.create_buffer_with_data(uniforms_bytes, wgpu::BufferUsage::COPY_SRC);
+    frame.command_encoder().copy_buffer_to_buffer(
+        &new_uniform_buffer,
+        0,
+        &model.uniform_buffer,
+        0,
+        UNIFORMS_SIZE,
+    );
+
+    // Advance the simulation.
+    let workgroups = (PARTICLE_COUNT + WORKGROUP_SIZE - 1) / WORKGROUP_SIZE;
+    frame
+        .begin_compute_pass()
+        .pipeline_from_spirv(include_bytes!("shaders/comp.spv"))
+        .bind_buffer(0, &model.particle_buffer, 0..PARTICLES_SIZE)
+        .bind_uniform_buffer(1, &model.uniform_buffer, 0..UNIFORMS_SIZE)
+        .dispatch(workgroups, 1, 1);
+
+    // Read the particles back so that they may be drawn next frame.
+    let read_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("read_particles"),
+        size: PARTICLES_SIZE,
+        usage: wgpu::BufferUsage::MAP_READ | wgpu::BufferUsage::COPY_DST,
+    });
+    frame.command_encoder().copy_buffer_to_buffer(
+        &model.particle_buffer,
+        0,
+        &read_buffer,
+        0,
+        PARTICLES_SIZE,
+    );
+    let particles = model.particles.clone();
+    let future = async move {
+        let result = read_buffer.map_read(0, PARTICLES_SIZE).await;
+        if let Ok(mapping) = result {
+            if let Ok(mut particles) = particles.lock() {
+                let bytes = mapping.as_slice();
+                let len = bytes.len() / std::mem::size_of::<Particle>();
+                let ptr = bytes.as_ptr() as *const Particle;
+                let slice = unsafe { std::slice::from_raw_parts(ptr, len) };
+                particles.copy_from_slice(slice);
+            }
+        }
+    };
+    model.threadpool.spawn_ok(future);
+
+    // Draw the most recently read particles.
+    frame.clear(BLACK);
+    let draw = app.draw();
+    if let Ok(particles) = model.particles.lock() {
+        for p in particles.iter() {
+            let [x, y] = p.position;
+            let [vx, vy] = p.velocity;
+            let speed = (vx * vx + vy * vy).sqrt();
+            let hue = map_range(speed.min(400.0), 0.0, 400.0, 0.6, 0.0);
+            draw.rect()
+                .x_y(x, y)
+                .w_h(2.0, 2.0)
+                .color(hsla(hue, 1.0, 0.6, 0.8));
+        }
+    }
+    draw.to_frame(app, &frame).unwrap();
+}
+
+fn create_uniforms(app: &App) -> Uniforms {
+    Uniforms {
+        attractor: [app.mouse.x, app.mouse.y],
+        dt: app.duration.since_prev_update.secs() as f32,
+        particle_count: PARTICLE_COUNT,
+    }
+}
@@ -77,7 +77,7 @@ fn view(app: &App, model: &Model, frame: Frame) {
     frame.clear(BLACK);
 
     let win = app.window_rect();
-    let noise = nannou::noise::Fbm::new()
+    let noise = nannou::noise::noise::Fbm::new()
         .set_seed(model.noise_random_seed)
         .set_octaves(model.octaves)
         .set_persistence(model.falloff as f64);
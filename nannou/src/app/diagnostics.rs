@@ -0,0 +1,287 @@
+//! Producing the structured report returned by `App::diagnostics` and `app::diagnostics`.
+
+use crate::app::App;
+use crate::wgpu;
+use std::fmt::Write;
+
+/// Produce the diagnostics report, including the monitors and active GPU devices of the given app.
+///
+/// Without an app, the report may be produced before the app is created, e.g. at the top of
+/// `main`, at the cost of omitting the monitors.
+pub(crate) fn report(app: Option<&App>) -> String {
+    let mut s = String::new();
+    heading(&mut s, "nannou diagnostics", '=');
+    let system = vec![
+        vec!["nannou".to_string(), env!("CARGO_PKG_VERSION").to_string()],
+        vec!["os".to_string(), std::env::consts::OS.to_string()],
+        vec!["arch".to_string(), std::env::consts::ARCH.to_string()],
+    ];
+    table(&mut s, &["Item", "Value"], &system);
+
+    heading(&mut s, "GPU adapters", '-');
+    table(
+        &mut s,
+        &[
+            "Name",
+            "Vendor",
+            "Device",
+            "Type",
+            "Backend",
+            "Active devices",
+        ],
+        &adapters(app),
+    );
+
+    heading(&mut s, "Monitors", '-');
+    table(
+        &mut s,
+        &["Index", "Name", "Size (points)", "Scale", "Primary"],
+        &monitors(app),
+    );
+
+    heading(&mut s, "Audio devices", '-');
+    table(
+        &mut s,
+        &["Direction", "Name", "Max channels"],
+        &audio_devices(),
+    );
+
+    heading(&mut s, "MIDI ports", '-');
+    table(&mut s, &["Direction", "Name"], &midi_ports());
+
+    heading(&mut s, "Gamepads", '-');
+    table(&mut s, &["Name", "Connected"], &gamepads(app));
+    s
+}
+
+/// Print the given report to stdout and write it to `App::DIAGNOSTICS_FILE_NAME`.
+pub(crate) fn print(report: &str) {
+    println!("{}", report);
+    if let Err(err) = std::fs::write(App::DIAGNOSTICS_FILE_NAME, report) {
+        eprintln!("failed to write `{}`: {}", App::DIAGNOSTICS_FILE_NAME, err);
+    }
+}
+
+// Every adapter available on any backend, along with the number of devices the app has active on
+// each.
+fn adapters(app: Option<&App>) -> Vec<Vec<String>> {
+    let active: Vec<_> = app
+        .map(|app| app.wgpu_adapters().adapters())
+        .unwrap_or_default();
+    wgpu::Adapter::enumerate(wgpu::BackendBit::all())
+        .iter()
+        .map(|adapter| {
+            let info = adapter.get_info();
+            let active_devices = match app {
+                None => "n/a".to_string(),
+                Some(_) => active
+                    .iter()
+                    .filter(|active| {
+                        let active = active.info();
+                        active.name == info.name
+                            && active.vendor == info.vendor
+                            && active.device == info.device
+                            && active.backend == info.backend
+                    })
+                    .map(|active| active.device_descriptors().len())
+                    .sum::<usize>()
+                    .to_string(),
+            };
+            vec![
+                info.name,
+                format!("{:#06x}", info.vendor),
+                format!("{:#06x}", info.device),
+                format!("{:?}", info.device_type),
+                format!("{:?}", info.backend),
+                active_devices,
+            ]
+        })
+        .collect()
+}
+
+fn monitors(app: Option<&App>) -> Vec<Vec<String>> {
+    let app = match app {
+        Some(app) => app,
+        None => return note("(requires a running app)", 5),
+    };
+    app.monitors()
+        .iter()
+        .enumerate()
+        .map(|(i, monitor)| {
+            let (w, h) = monitor.rect().w_h();
+            vec![
+                i.to_string(),
+                monitor.name().unwrap_or("unknown").to_string(),
+                format!("{}x{}", w, h),
+                format!("{:.2}", monitor.scale_factor()),
+                yes_no(monitor.is_primary()),
+            ]
+        })
+        .collect()
+}
+
+#[cfg(feature = "audio")]
+fn audio_devices() -> Vec<Vec<String>> {
+    let host = nannou_audio::Host::new();
+    let mut rows = vec![];
+    if let Ok(devices) = host.input_devices() {
+        for device in devices {
+            let name = device.name().unwrap_or_else(|_| "unknown".to_string());
+            let channels = device.max_supported_input_channels().to_string();
+            rows.push(vec!["input".to_string(), name, channels]);
+        }
+    }
+    if let Ok(devices) = host.output_devices() {
+        for device in devices {
+            let name = device.name().unwrap_or_else(|_| "unknown".to_string());
+            let channels = device.max_supported_output_channels().to_string();
+            rows.push(vec!["output".to_string(), name, channels]);
+        }
+    }
+    rows
+}
+
+#[cfg(not(feature = "audio"))]
+fn audio_devices() -> Vec<Vec<String>> {
+    unavailable("audio", 3)
+}
+
+#[cfg(feature = "midi")]
+fn midi_ports() -> Vec<Vec<String>> {
+    let inputs = nannou_midi::available_ports()
+        .into_iter()
+        .map(|name| vec!["input".to_string(), name]);
+    let outputs = nannou_midi::available_output_ports()
+        .into_iter()
+        .map(|name| vec!["output".to_string(), name]);
+    inputs.chain(outputs).collect()
+}
+
+#[cfg(not(feature = "midi"))]
+fn midi_ports() -> Vec<Vec<String>> {
+    unavailable("midi", 2)
+}
+
+#[cfg(feature = "gamepad")]
+fn gamepads(app: Option<&App>) -> Vec<Vec<String>> {
+    let gamepads = match app {
+        Some(app) => app.gamepads().collect(),
+        None => crate::gamepad::Context::new().gamepads(),
+    };
+    gamepads
+        .into_iter()
+        .map(|gamepad| vec![gamepad.name(), yes_no(gamepad.is_connected())])
+        .collect()
+}
+
+#[cfg(not(feature = "gamepad"))]
+fn gamepads(_app: Option<&App>) -> Vec<Vec<String>> {
+    unavailable("gamepad", 2)
+}
+
+// A single row noting that the given feature must be enabled to list the section.
+#[allow(dead_code)]
+fn unavailable(feature: &str, columns: usize) -> Vec<Vec<String>> {
+    note(&format!("(enable the `{}` feature)", feature), columns)
+}
+
+// A single row containing the given note in its first column.
+fn note(note: &str, columns: usize) -> Vec<Vec<String>> {
+    let mut row = vec![note.to_string()];
+    row.resize(columns, String::new());
+    vec![row]
+}
+
+fn yes_no(b: bool) -> String {
+    match b {
+        true => "yes".to_string(),
+        false => "no".to_string(),
+    }
+}
+
+fn heading(s: &mut String, title: &str, underline: char) {
+    if !s.is_empty() {
+        s.push('\n');
+    }
+    let rule: String = std::iter::repeat(underline).take(title.len()).collect();
+    writeln!(s, "{}\n{}", title, rule).unwrap();
+}
+
+// Write the given rows as a table with aligned columns.
+fn table(s: &mut String, headers: &[&str], rows: &[Vec<String>]) {
+    if rows.is_empty() {
+        writeln!(s, "(none)").unwrap();
+        return;
+    }
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = std::cmp::max(*width, cell.chars().count());
+        }
+    }
+    writeln!(s, "{}", line(headers.iter().cloned(), &widths)).unwrap();
+    let rule: Vec<String> = widths.iter().map(|&w| "-".repeat(w)).collect();
+    writeln!(s, "{}", rule.join("-+-")).unwrap();
+    for row in rows {
+        writeln!(s, "{}", line(row.iter().map(|c| &c[..]), &widths)).unwrap();
+    }
+}
+
+// A single row of the table with each cell padded to the width of its column.
+//
+// Trailing empty cells are omitted along with their separators.
+fn line<'a, I>(cells: I, widths: &[usize]) -> String
+where
+    I: Iterator<Item = &'a str>,
+{
+    let mut cells: Vec<_> = cells.zip(widths).collect();
+    while cells
+        .last()
+        .map(|&(cell, _)| cell.is_empty())
+        .unwrap_or(false)
+    {
+        cells.pop();
+    }
+    let cells: Vec<_> = cells
+        .into_iter()
+        .map(|(cell, &width)| format!("{:width$}", cell, width = width))
+        .collect();
+    cells.join(" | ").trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{note, table};
+
+    #[test]
+    fn test_table() {
+        let mut s = String::new();
+        let rows = vec![
+            vec!["a".to_string(), "long value".to_string()],
+            vec!["longer key".to_string(), "b".to_string()],
+        ];
+        table(&mut s, &["Key", "Value"], &rows);
+        let expected = "\
+Key        | Value
+-----------+-----------
+a          | long value
+longer key | b
+";
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn test_table_empty() {
+        let mut s = String::new();
+        table(&mut s, &["Key", "Value"], &[]);
+        assert_eq!(s, "(none)\n");
+    }
+
+    #[test]
+    fn test_table_note() {
+        // Empty trailing cells are trimmed rather than padded.
+        let mut s = String::new();
+        table(&mut s, &["A", "B", "C"], &note("(note)", 3));
+        assert_eq!(s, "A      | B | C\n-------+---+--\n(note)\n");
+    }
+}
@@ -0,0 +1,296 @@
+//! Items related to running an app without a display via `App::run_headless`.
+//!
+//! Headless apps render each frame to an off-screen texture rather than to a window, making it
+//! possible to render on servers without a display and to test visual output within CI.
+
+use super::{App, Builder, LoopState, ModelFn, Proxy, UpdateFn, ViewFn};
+use crate::event::Event;
+use crate::frame::{self, Frame, RawFrame};
+use crate::geom;
+use crate::wgpu;
+use crate::window;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use std::{fmt, io};
+
+/// Describes how a headless app should be run.
+#[derive(Clone, Debug)]
+pub struct Config {
+    size: [u32; 2],
+    frames: u64,
+    output_dir: Option<PathBuf>,
+    fps: f64,
+    msaa_samples: u32,
+    seed: Option<u64>,
+}
+
+/// Errors that might occur while running a headless app.
+#[derive(Debug)]
+pub enum Error {
+    /// No wgpu adapter was available for rendering.
+    NoAvailableAdapter,
+    /// Failed to create the directory to which frames are written.
+    OutputDir(io::Error),
+    /// Timed out while waiting for captured frames to be written.
+    CaptureTimeout,
+}
+
+// State tracked by the `App` while running headless.
+#[derive(Debug)]
+pub(crate) struct State {
+    pub(crate) rect: geom::Rect,
+    pub(crate) frame_count: u64,
+}
+
+impl Config {
+    /// The number of frames rendered by default.
+    pub const DEFAULT_FRAMES: u64 = 1;
+    /// The rate at which time advances between frames by default.
+    pub const DEFAULT_FPS: f64 = 60.0;
+
+    /// The default configuration.
+    ///
+    /// Renders a single frame of `window::DEFAULT_DIMENSIONS` without writing it to disk.
+    pub fn new() -> Self {
+        let size = [
+            window::DEFAULT_DIMENSIONS.width as u32,
+            window::DEFAULT_DIMENSIONS.height as u32,
+        ];
+        Config {
+            size,
+            frames: Self::DEFAULT_FRAMES,
+            output_dir: None,
+            fps: Self::DEFAULT_FPS,
+            msaa_samples: Frame::DEFAULT_MSAA_SAMPLES,
+            seed: None,
+        }
+    }
+
+    /// The size of each frame in pixels.
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.size = [width, height];
+        self
+    }
+
+    /// The number of frames to render before returning.
+    pub fn frames(mut self, frames: u64) -> Self {
+        self.frames = frames;
+        self
+    }
+
+    /// A directory to which each frame will be written as a PNG file.
+    ///
+    /// Frames are named by their index, e.g. `00000.png`, `00001.png` and so on. The directory is
+    /// created if it does not yet exist.
+    pub fn output_dir<P>(mut self, path: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        self.output_dir = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// The rate in frames per second at which time advances between frames.
+    ///
+    /// This determines the `Update` delivered before each frame and in turn `app.time`. By
+    /// default, this is `DEFAULT_FPS`.
+    ///
+    /// **Panics** if `fps` is not greater than `0.0`.
+    pub fn fps(mut self, fps: f64) -> Self {
+        assert!(fps > 0.0, "`fps` must be greater than `0.0`");
+        self.fps = fps;
+        self
+    }
+
+    /// The number of MSAA samples used by the frame's intermediary texture.
+    ///
+    /// By default, this is `Frame::DEFAULT_MSAA_SAMPLES`.
+    pub fn msaa_samples(mut self, msaa_samples: u32) -> Self {
+        self.msaa_samples = msaa_samples;
+        self
+    }
+
+    /// Seed the app's random number generator before calling the model function.
+    ///
+    /// See `App::seed_rng`.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// The implementation of `App::run_headless`.
+pub(crate) fn run<M>(
+    model_fn: ModelFn<M>,
+    update_fn: UpdateFn<M>,
+    view_fn: ViewFn<M>,
+    config: Config,
+) -> Result<(), Error>
+where
+    M: 'static,
+{
+    let Config {
+        size,
+        frames,
+        output_dir,
+        fps,
+        msaa_samples,
+        seed,
+    } = config;
+
+    assert!(
+        Frame::SUPPORTED_MSAA_SAMPLES.contains(&msaa_samples),
+        "unsupported `msaa_samples` value {}: expected one of {:?}",
+        msaa_samples,
+        Frame::SUPPORTED_MSAA_SAMPLES,
+    );
+
+    if let Some(ref dir) = output_dir {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    // There is no event loop to wake.
+    let event_loop_proxy = Proxy {
+        event_loop_proxy: None,
+        event_loop_is_asleep: Arc::new(AtomicBool::new(false)),
+        redraw_requested: Default::default(),
+    };
+    let max_capture_frame_jobs = num_cpus::get() as u32;
+    let capture_frame_timeout = Some(Builder::<(), Event>::DEFAULT_CAPTURE_FRAME_TIMEOUT);
+    let mut app = App::new(
+        event_loop_proxy,
+        None,
+        None,
+        max_capture_frame_jobs,
+        capture_frame_timeout,
+    );
+    let rect = geom::Rect::from_w_h(size[0] as f32, size[1] as f32);
+    let frame_count = 0;
+    app.headless = Some(State { rect, frame_count });
+
+    // Request a device without a surface.
+    let request_adapter_opts = wgpu::RequestAdapterOptions {
+        power_preference: wgpu::DEFAULT_POWER_PREFERENCE,
+        compatible_surface: None,
+    };
+    let adapter = app
+        .wgpu_adapters()
+        .get_or_request(request_adapter_opts, wgpu::DEFAULT_BACKENDS)
+        .ok_or(Error::NoAvailableAdapter)?;
+    let device_queue_pair = adapter.get_or_request_device(wgpu::default_device_descriptor());
+    let device = device_queue_pair.device();
+
+    // The off-screen texture takes the place of the swap chain texture.
+    let texture = wgpu::TextureBuilder::new()
+        .size(size)
+        .format(TEXTURE_FORMAT)
+        .usage(wgpu::TextureUsage::OUTPUT_ATTACHMENT)
+        .build(device);
+    let texture_view = texture.view().build();
+    let render = frame::RenderData::new(device, size, TEXTURE_FORMAT, msaa_samples);
+    let capture = frame::CaptureData::new(max_capture_frame_jobs, capture_frame_timeout);
+
+    // Safe, as the ID is never used to refer to a winit window.
+    let window_id = unsafe { window::Id::dummy() };
+
+    if let Some(seed) = seed {
+        app.seed_rng(seed);
+    }
+    let mut model = model_fn(&app);
+
+    let loop_start = Instant::now();
+    let mut loop_state = LoopState {
+        updates_since_event: 0,
+        loop_start,
+        last_update: loop_start,
+        total_updates: 0,
+    };
+
+    for nth in 0..frames {
+        // Advance by a fixed interval rather than the wall clock so that runs are reproducible.
+        let now = loop_start + Duration::from_secs_f64(nth as f64 / fps);
+        // There is no event function, so complete loaded assets without delivering events.
+        app.assets.poll(&app);
+        super::apply_update::<M, Event>(
+            &mut app,
+            &mut model,
+            None,
+            Some(update_fn),
+            &mut loop_state,
+            now,
+            0.0,
+        );
+
+        if let Some(ref dir) = output_dir {
+            let path = dir.join(format!("{:05}", nth)).with_extension("png");
+            if let Ok(mut guard) = capture.next_frame_path.lock() {
+                *guard = Some(path);
+            }
+        }
+
+        let raw_frame = RawFrame::new_empty(
+            device_queue_pair.clone(),
+            window_id,
+            nth,
+            now,
+            loop_start,
+            &texture_view,
+            TEXTURE_FORMAT,
+            rect,
+            1.0,
+        );
+        let frame = Frame::new_empty(raw_frame, &render, &capture);
+        app.frame_times.borrow_mut().record(Instant::now());
+        view_fn(&app, &model, frame);
+
+        if let Some(ref mut headless) = app.headless {
+            headless.frame_count += 1;
+        }
+        device.poll(wgpu::Maintain::Poll);
+        app.wgpu_adapters().end_frame_buffer_pools();
+    }
+
+    // Wait for all captured frames to be written before returning.
+    capture
+        .texture_capturer
+        .await_active_snapshots(device)
+        .map_err(|_| Error::CaptureTimeout)?;
+
+    Ok(())
+}
+
+// The format of the off-screen texture to which each frame is written.
+const TEXTURE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::OutputDir(e)
+    }
+}
+
+impl std::error::Error for Error {
+    fn cause(&self) -> Option<&dyn std::error::Error> {
+        match *self {
+            Error::OutputDir(ref e) => Some(e),
+            Error::NoAvailableAdapter | Error::CaptureTimeout => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::NoAvailableAdapter => write!(f, "no available wgpu adapter detected"),
+            Error::OutputDir(ref e) => write!(f, "failed to create output directory: {}", e),
+            Error::CaptureTimeout => write!(f, "timed out while writing captured frames"),
+        }
+    }
+}
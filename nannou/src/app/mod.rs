@@ -7,19 +7,26 @@
 //! - [**Proxy**](./struct.Proxy.html) - a handle to an **App** that may be used from a non-main
 //!   thread.
 //! - [**LoopMode**](./enum.LoopMode.html) - describes the behaviour of the application event loop.
+//! - [**headless**](./headless/index.html) - items for running an app without a display.
 
+use crate::asset;
+#[cfg(feature = "dialog")]
+use crate::dialog;
 use crate::draw;
-use crate::event::{self, Event, Key, LoopEvent, Update};
-use crate::frame::{Frame, RawFrame};
+use crate::event::{self, Event, Key, KeyboardShortcut, LoopEvent, ModifiersState, Update};
+use crate::frame::{Frame, RawFrame, VideoRecorder};
 use crate::geom;
+use crate::net;
+use crate::rand;
 use crate::state;
-use crate::time::DurationF64;
+use crate::time::{self, DurationF64};
+use crate::turtle::Turtle;
 use crate::ui;
 use crate::wgpu;
 use crate::window::{self, Window};
 use find_folder;
 use std;
-use std::cell::{RefCell, RefMut};
+use std::cell::{Ref, RefCell, RefMut};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::atomic::{self, AtomicBool};
@@ -28,6 +35,9 @@ use std::time::{Duration, Instant};
 use winit;
 use winit::event_loop::ControlFlow;
 
+mod diagnostics;
+pub mod headless;
+
 /// The user function type for initialising their model.
 pub type ModelFn<Model> = fn(&App) -> Model;
 
@@ -46,6 +56,9 @@ pub type SketchViewFn = fn(&App, Frame);
 /// The user function type allowing them to consume the `model` when the application exits.
 pub type ExitFn<Model> = fn(&App, Model);
 
+/// The function type for app-wide key press handlers registered via `App::on_key_press`.
+pub type KeyPressFn = fn(&App);
+
 /// The **App**'s view function.
 enum View<Model = ()> {
     /// A view function allows for viewing the user's model.
@@ -65,6 +78,8 @@ pub struct Builder<M = (), E = Event> {
     default_window_size: Option<DefaultWindowSize>,
     capture_frame_timeout: Option<Option<Duration>>,
     max_capture_frame_jobs: Option<u32>,
+    key_press_fns: Vec<(Key, KeyPressFn)>,
+    shortcuts: Vec<(KeyboardShortcut, event::ShortcutFn<M>)>,
 }
 
 /// A nannou `Sketch` builder.
@@ -148,6 +163,31 @@ pub struct App {
     /// the number becomes higher. Instead, we recommend using `app.duration.since_start` or
     /// `app.duration.since_prev_update` to access a more precise form of app time.
     pub time: DrawScalar,
+    /// A clock shared with timers created via `create_timer`, kept in sync with
+    /// `duration.since_start`.
+    clock: time::Clock,
+    // One-shot wakeups registered via `wake_on_timer`.
+    wake_timers: RefCell<Vec<Instant>>,
+    // The fraction of a step remaining after the last update when in `FixedStep` mode.
+    accumulator_alpha: f32,
+    assets: asset::AssetServer,
+    key_press_fns: RefCell<Vec<(Key, KeyPressFn)>>,
+    shortcuts: RefCell<Vec<KeyboardShortcut>>,
+    // Files hovered over or dropped onto windows since the last update.
+    file_drops: RefCell<event::FileDropQueue>,
+    // URL schemes, control servers and sockets registered by the user.
+    pub(crate) net_handlers: RefCell<net::Handlers>,
+    // Frame timing recorded at the start of each of the focused window's `view` calls.
+    frame_times: RefCell<state::time::FrameTimes>,
+    // Turtles accessed by name via `App::turtle`.
+    turtles: RefCell<HashMap<String, Turtle>>,
+    // Serial ports registered via `Port::on_data`, each alongside a `DataFn<M>`.
+    #[cfg(feature = "serial")]
+    pub(crate) serial_ports: RefCell<Vec<(crate::serial::WeakPort, Arc<dyn std::any::Any>)>>,
+    #[cfg(feature = "gamepad")]
+    gamepads: crate::gamepad::Context,
+    // `Some` while running via `App::run_headless`.
+    headless: Option<headless::State>,
 }
 
 /// Miscellaneous app configuration parameters.
@@ -175,7 +215,8 @@ pub type DrawScalar = geom::scalar::Default;
 /// **App**'s inner event loop.
 #[derive(Clone)]
 pub struct Proxy {
-    event_loop_proxy: winit::event_loop::EventLoopProxy<()>,
+    // `None` when running headless, in which case there is no event loop to wake.
+    event_loop_proxy: Option<winit::event_loop::EventLoopProxy<()>>,
     // Indicates whether or not the events loop is currently asleep.
     //
     // This is set to `true` each time the events loop is ready to return and the `LoopMode` is
@@ -183,6 +224,8 @@ pub struct Proxy {
     //
     // This value is set back to `false` each time the events loop receives any kind of event.
     event_loop_is_asleep: Arc<AtomicBool>,
+    // Set by `request_redraw` and taken by the event loop in `OnEvent` mode.
+    redraw_requested: Arc<AtomicBool>,
 }
 
 // State related specifically to the application loop, shared between loop modes.
@@ -231,6 +274,39 @@ pub enum LoopMode {
         /// The number of updates that must be emited regardless of non-update events
         number_of_updates: usize,
     },
+
+    /// Sleeps until an event arrives, then emits a single `Update` and redraws.
+    ///
+    /// Unlike **Wait**, no additional updates are emitted following each event, so the app uses
+    /// no CPU while idle. This is ideal for mostly static GUIs and data visualisation tools.
+    ///
+    /// The app may also be woken via `App::request_redraw`, `Proxy::request_redraw` from other
+    /// threads, or after some duration via `App::wake_on_timer`.
+    OnEvent {
+        /// If `Some`, the maximum duration to sleep before emitting an update regardless of
+        /// whether or not any events have arrived.
+        max_wait: Option<Duration>,
+    },
+
+    /// Emits `Update` events at a fixed rate, independent of the rate at which frames are drawn.
+    ///
+    /// Time is accumulated between frames and consumed in steps of exactly `1.0 / update_rate`
+    /// seconds, so that simulations behave the same regardless of the display's refresh rate.
+    /// Each update's `since_last` is equal to the step interval.
+    ///
+    /// As frames are generally not aligned with steps, some fraction of a step remains after each
+    /// frame's updates. This is delivered via `Update::accumulator_alpha` and
+    /// `App::accumulator_alpha`, allowing for interpolating between the previous and current
+    /// state within `view` for smooth display.
+    FixedStep {
+        /// The number of updates emitted per second.
+        update_rate: f64,
+        /// The maximum number of updates emitted per frame.
+        ///
+        /// If updates take longer than the step interval to process, the accumulated time would
+        /// otherwise grow without bound. Any time beyond this number of steps is discarded.
+        max_frame_skip: u32,
+    },
 }
 
 impl<M> Builder<M, Event>
@@ -259,6 +335,8 @@ where
             default_window_size: None,
             max_capture_frame_jobs: None,
             capture_frame_timeout: None,
+            key_press_fns: vec![],
+            shortcuts: vec![],
         }
     }
 
@@ -283,6 +361,8 @@ where
             default_window_size,
             max_capture_frame_jobs,
             capture_frame_timeout,
+            key_press_fns,
+            shortcuts,
             ..
         } = self;
         Builder {
@@ -295,6 +375,8 @@ where
             default_window_size,
             max_capture_frame_jobs,
             capture_frame_timeout,
+            key_press_fns,
+            shortcuts,
         }
     }
 }
@@ -414,6 +496,39 @@ where
         self
     }
 
+    /// Call the given function whenever the given key is pressed within any window.
+    ///
+    /// This is useful for registering app-wide shortcuts from `main`, e.g.
+    /// `.on_key_press(Key::S, |app| app.screenshot_to_clipboard())`. See `App::on_key_press`.
+    pub fn on_key_press(mut self, key: Key, f: KeyPressFn) -> Self {
+        self.key_press_fns.push((key, f));
+        self
+    }
+
+    /// Register a shortcut that calls `handler` with the model whenever `key` is pressed within
+    /// any of the app's windows while exactly the given `modifiers` are held.
+    ///
+    /// Shortcut handlers are called after any `on_key_press` functions and prior to any
+    /// window-specific `key_pressed` function. The `description` is used when listing shortcuts
+    /// via `App::shortcuts` or `App::print_shortcuts`, e.g.
+    ///
+    /// ```ignore
+    /// nannou::app(model)
+    ///     .shortcut(Key::S, ModifiersState::CTRL, "Save the current parameters", save)
+    ///     .run();
+    /// ```
+    pub fn shortcut(
+        mut self,
+        key: Key,
+        modifiers: ModifiersState,
+        description: &str,
+        handler: event::ShortcutFn<M>,
+    ) -> Self {
+        let shortcut = KeyboardShortcut::new(key, modifiers, description);
+        self.shortcuts.push((shortcut, handler));
+        self
+    }
+
     /// Build and run an `App` with the specified parameters.
     ///
     /// This function will not return until the application has exited.
@@ -429,8 +544,9 @@ where
         let event_loop_proxy = event_loop.create_proxy();
         let event_loop_is_asleep = Arc::new(AtomicBool::new(false));
         let event_loop_proxy = Proxy {
-            event_loop_proxy,
+            event_loop_proxy: Some(event_loop_proxy),
             event_loop_is_asleep,
+            redraw_requested: Default::default(),
         };
 
         // Initialise the app.
@@ -448,6 +564,11 @@ where
             max_capture_frame_jobs,
             capture_frame_timeout,
         );
+        for (key, f) in self.key_press_fns {
+            app.on_key_press(key, f);
+        }
+        let (shortcuts, shortcut_fns): (Vec<_>, Vec<_>) = self.shortcuts.into_iter().unzip();
+        *app.shortcuts.borrow_mut() = shortcuts;
 
         // Create the default window if necessary
         if self.create_default_window {
@@ -461,6 +582,13 @@ where
         // Call the user's model function.
         let model = (self.model)(&app);
 
+        if std::env::var(App::DEBUG_ENV_VAR)
+            .map(|v| v == "1")
+            .unwrap_or(false)
+        {
+            app.print_info();
+        }
+
         // If there is not yet some default window in "focus" check to see if one has been created.
         if app.focused_window.borrow().is_none() {
             if let Some(id) = app.windows.borrow().keys().next() {
@@ -475,6 +603,7 @@ where
             self.update,
             self.default_view,
             self.exit,
+            shortcut_fns,
         );
     }
 }
@@ -489,6 +618,12 @@ where
         self
     }
 
+    /// Call the given function whenever the given key is pressed within the sketch window.
+    pub fn on_key_press(mut self, key: Key, f: KeyPressFn) -> Self {
+        self.builder = self.builder.on_key_press(key, f);
+        self
+    }
+
     /// Build and run a `Sketch` with the specified parameters.
     ///
     /// This calls `App::run` internally. See that method for details!
@@ -526,6 +661,8 @@ impl LoopMode {
     /// The minimum number of updates that will be emitted after an event is triggered in Wait
     /// mode.
     pub const UPDATES_PER_WAIT_EVENT: u32 = 3;
+    /// The default maximum number of updates emitted per frame in **FixedStep** mode.
+    pub const DEFAULT_MAX_FRAME_SKIP: u32 = 5;
 
     /// A simplified constructor for the default `RefreshSync` loop mode.
     ///
@@ -557,6 +694,22 @@ impl LoopMode {
     pub fn loop_once() -> Self {
         Self::loop_ntimes(1)
     }
+
+    /// Specify the **OnEvent** mode, sleeping indefinitely between events.
+    pub fn on_event() -> Self {
+        LoopMode::OnEvent { max_wait: None }
+    }
+
+    /// Specify the **FixedStep** mode with the given number of updates per second.
+    ///
+    /// Up to `DEFAULT_MAX_FRAME_SKIP` updates are emitted per frame.
+    pub fn fixed_step(update_rate: f64) -> Self {
+        let max_frame_skip = Self::DEFAULT_MAX_FRAME_SKIP;
+        LoopMode::FixedStep {
+            update_rate,
+            max_frame_skip,
+        }
+    }
 }
 
 impl Default for LoopMode {
@@ -582,6 +735,10 @@ impl App {
     pub const ASSETS_DIRECTORY_NAME: &'static str = "assets";
     pub const DEFAULT_EXIT_ON_ESCAPE: bool = true;
     pub const DEFAULT_FULLSCREEN_ON_SHORTCUT: bool = true;
+    /// Setting this environment variable to `1` prints the `diagnostics` report upon startup.
+    pub const DEBUG_ENV_VAR: &'static str = "NANNOU_DEBUG";
+    /// The file within the working directory to which `print_info` writes the report.
+    pub const DIAGNOSTICS_FILE_NAME: &'static str = "nannou-diagnostics.txt";
 
     // Create a new `App`.
     fn new(
@@ -603,6 +760,8 @@ impl App {
         let keys = state::Keys::default();
         let duration = state::Time::default();
         let time = duration.since_start.secs() as _;
+        let clock = time::Clock::default();
+        let assets = asset::AssetServer::new(event_loop_proxy.clone());
         let app = App {
             event_loop_proxy,
             event_loop_window_target,
@@ -619,6 +778,21 @@ impl App {
             keys,
             duration,
             time,
+            clock,
+            wake_timers: RefCell::new(vec![]),
+            accumulator_alpha: 0.0,
+            assets,
+            key_press_fns: RefCell::new(vec![]),
+            shortcuts: RefCell::new(vec![]),
+            file_drops: RefCell::new(Default::default()),
+            net_handlers: RefCell::new(Default::default()),
+            frame_times: RefCell::new(Default::default()),
+            turtles: RefCell::new(HashMap::new()),
+            #[cfg(feature = "serial")]
+            serial_ports: RefCell::new(vec![]),
+            #[cfg(feature = "gamepad")]
+            gamepads: crate::gamepad::Context::new(),
+            headless: None,
         };
         app
     }
@@ -639,6 +813,22 @@ impl App {
         }
     }
 
+    /// Information about each of the monitors available on the system.
+    ///
+    /// The index of each monitor may be given to `window::Builder::monitor` to create a window on
+    /// that monitor.
+    pub fn monitors(&self) -> Vec<window::Monitor> {
+        let available = self.available_monitors();
+        if available.is_empty() {
+            return vec![];
+        }
+        let primary = self.primary_monitor();
+        available
+            .into_iter()
+            .map(|handle| window::Monitor::new(handle, &primary))
+            .collect()
+    }
+
     /// Returns the primary monitor of the system.
     pub fn primary_monitor(&self) -> winit::monitor::MonitorHandle {
         match self.event_loop_window_target {
@@ -656,6 +846,30 @@ impl App {
         }
     }
 
+    /// Run an app without a display, rendering each frame to an off-screen texture.
+    ///
+    /// No window or event loop is created, making this suitable for rendering on servers and for
+    /// visual regression tests within CI. The model is updated and viewed once for each of the
+    /// frames specified by the `config`. If an `output_dir` is specified, each frame is written
+    /// to a numbered PNG file within it.
+    ///
+    /// Time advances by a fixed interval each frame rather than with the wall clock, so two runs
+    /// with the same `seed` produce identical images.
+    ///
+    /// As there are no windows, `app.window_rect()` describes the off-screen frame while methods
+    /// that refer to a window, e.g. `app.main_window()`, will panic.
+    pub fn run_headless<M>(
+        model: ModelFn<M>,
+        update: UpdateFn<M>,
+        view: ViewFn<M>,
+        config: headless::Config,
+    ) -> Result<(), headless::Error>
+    where
+        M: 'static,
+    {
+        headless::run(model, update, view, config)
+    }
+
     /// Find and return the absolute path to the project's `assets` directory.
     ///
     /// This method looks for the assets directory in the following order:
@@ -667,6 +881,29 @@ impl App {
         find_assets_path()
     }
 
+    /// Load an image for use as a window icon via `Window::set_icon`.
+    ///
+    /// Icons are usually small, square images, e.g. 32x32 pixels.
+    pub fn new_icon_from_file<P>(&self, path: P) -> image::ImageResult<image::DynamicImage>
+    where
+        P: AsRef<std::path::Path>,
+    {
+        image::open(path)
+    }
+
+    /// Compile the GLSL shader at the given path and watch it for changes, recompiling it each
+    /// time the file is modified.
+    ///
+    /// Call `module(device)` on the returned handle (e.g. within `view`) to retrieve the latest
+    /// successfully compiled module. See the **ShaderHandle** docs for details.
+    #[cfg(feature = "hotglsl")]
+    pub fn watch_shader<P>(&self, path: P) -> wgpu::ShaderHandle
+    where
+        P: AsRef<std::path::Path>,
+    {
+        wgpu::ShaderHandle::new(path)
+    }
+
     /// The path to the current project directory.
     ///
     /// The current project directory is considered to be the directory containing the cargo
@@ -682,7 +919,7 @@ impl App {
     pub fn new_window(&self) -> window::Builder {
         let builder = window::Builder::new(self);
         let builder = match self.default_window_size {
-            Some(DefaultWindowSize::Fullscreen) => builder.fullscreen(),
+            Some(DefaultWindowSize::Fullscreen) => builder.fullscreen(Default::default()),
             Some(DefaultWindowSize::Logical(size)) => builder.size(size.width, size.height),
             None => builder,
         };
@@ -726,8 +963,13 @@ impl App {
     ///
     /// The **Rect** coords are described in "points" (pixels divided by the hidpi factor).
     ///
+    /// When running headless, this is the rect of the off-screen frame.
+    ///
     /// **Panics** if there are no windows or if no window is in focus.
     pub fn window_rect(&self) -> geom::Rect<DrawScalar> {
+        if let Some(ref headless) = self.headless {
+            return headless.rect;
+        }
         self.main_window().rect()
     }
 
@@ -744,6 +986,20 @@ impl App {
             .expect("no window for focused id")
     }
 
+    /// The feedback texture of the window with the given ID to which the current frame is written.
+    ///
+    /// The first call for a window enables feedback, allocating a pair of textures matching the
+    /// size and format of the window's swap chain. The textures are swapped before each call to
+    /// `view`, such that the image written during the previous frame may be read via
+    /// `RawFrame::feedback_texture_view`, e.g. for sampling within a custom render pass to produce
+    /// trails. Both textures are initialised to transparent black.
+    ///
+    /// Returns `None` if there is no window for the given ID.
+    pub fn feedback_texture(&self, window_id: window::Id) -> Option<wgpu::Texture> {
+        self.window(window_id)
+            .map(|window| window.feedback_texture())
+    }
+
     /// Access to the **App**'s inner map of wgpu adapters representing access to physical GPU
     /// devices.
     ///
@@ -758,6 +1014,17 @@ impl App {
         &self.adapters
     }
 
+    /// The pool of reusable buffers associated with the device of the main window.
+    ///
+    /// Buffers requested from the pool must be created with the same device, i.e.
+    /// `app.main_window().swap_chain_device()`. See the **BufferPool** docs for details.
+    pub fn wgpu_buffer_pool(&self) -> wgpu::BufferPool {
+        self.main_window()
+            .swap_chain_device_queue_pair()
+            .buffer_pool()
+            .clone()
+    }
+
     /// Return whether or not the `App` is currently set to exit when the `Escape` key is pressed.
     pub fn exit_on_escape(&self) -> bool {
         self.config.borrow().exit_on_escape
@@ -790,6 +1057,127 @@ impl App {
         self.config.borrow_mut().fullscreen_on_shortcut = b;
     }
 
+    /// Call the given function whenever the given key is pressed within any of the app's windows.
+    ///
+    /// Functions are called in the order in which they were registered, prior to any
+    /// window-specific `key_pressed` function.
+    pub fn on_key_press(&self, key: Key, f: KeyPressFn) {
+        self.key_press_fns.borrow_mut().push((key, f));
+    }
+
+    /// All shortcuts registered via `Builder::shortcut` in the order in which they were
+    /// registered.
+    ///
+    /// This is useful for displaying an on-screen list of shortcuts from within `view`.
+    pub fn shortcuts(&self) -> Ref<[KeyboardShortcut]> {
+        Ref::map(self.shortcuts.borrow(), |s| &s[..])
+    }
+
+    /// Print all registered shortcuts to stdout, one per line.
+    pub fn print_shortcuts(&self) {
+        for shortcut in self.shortcuts().iter() {
+            println!("{}", shortcut);
+        }
+    }
+
+    /// Register a custom URL scheme, e.g. `"nannou"` for URLs like `nannou://play?bpm=120`, calling
+    /// `handler` with the model for each URL of the scheme delivered to the app.
+    ///
+    /// The current executable is registered with the OS as the scheme's handler, such that opening
+    /// a URL of the scheme launches the app with the URL or, if the app is already running,
+    /// delivers the URL to it. Pending URLs are delivered before the next update. The handler
+    /// remains registered even if registration with the OS fails, in which case the error is
+    /// returned. See the `net::url` module for platform-specific details.
+    ///
+    /// On Linux and Windows, if another instance of the app is already running and this instance
+    /// was launched with URLs of the scheme, the URLs are forwarded to the running instance and
+    /// this process **exits**.
+    ///
+    /// The `Model` type must match the app's model type, otherwise the app will **panic!** upon
+    /// delivering a URL.
+    pub fn register_url_scheme<Model>(
+        &self,
+        scheme: &str,
+        handler: net::UrlFn<Model>,
+    ) -> std::io::Result<()>
+    where
+        Model: 'static,
+    {
+        let mut scheme = net::UrlScheme::new(scheme);
+        let registered = scheme.register_with_os();
+        let forwarded = scheme.listen(self.create_proxy());
+        if let Ok(true) = forwarded {
+            std::process::exit(0);
+        }
+        self.net_handlers
+            .borrow_mut()
+            .add_url_scheme(scheme, handler);
+        registered.and(forwarded.map(|_| ()))
+    }
+
+    /// Run a minimal HTTP server on the given port, calling `handler` with the model for each
+    /// request received.
+    ///
+    /// The handler receives the path of each request along with its query parameters, e.g. a
+    /// request to `http://<ip>:<port>/play?bpm=120` produces the path `/play` and the parameter
+    /// `bpm` of `120`. Requests are delivered before the next update. This allows for controlling
+    /// an installation from any device with a browser on the same network.
+    ///
+    /// The `Model` type must match the app's model type, otherwise the app will **panic!** upon
+    /// delivering a request.
+    pub fn http_control_server<Model>(
+        &self,
+        port: u16,
+        handler: net::HttpControlFn<Model>,
+    ) -> std::io::Result<std::net::SocketAddr>
+    where
+        Model: 'static,
+    {
+        let proxy = self.create_proxy();
+        let server = net::ControlServer::bind_with_proxy(port, proxy)?;
+        let addr = server.addr();
+        self.net_handlers
+            .borrow_mut()
+            .add_control_server(server, handler);
+        Ok(addr)
+    }
+
+    /// Capture the next frame of the main window and copy it to the system clipboard.
+    ///
+    /// See `Window::screenshot_to_clipboard` for details. Requires the `clipboard` feature.
+    #[cfg(feature = "clipboard")]
+    pub fn screenshot_to_clipboard(&self) {
+        self.main_window().screenshot_to_clipboard();
+    }
+
+    /// The fraction of a step that has accumulated since the last update, within the range
+    /// `0.0..1.0`.
+    ///
+    /// This is only meaningful in `LoopMode::FixedStep`, where it may be used within `view` to
+    /// interpolate between the previous and current state. It is always `0.0` in other modes.
+    pub fn accumulator_alpha(&self) -> f32 {
+        self.accumulator_alpha
+    }
+
+    /// Request that the app emits an update and redraws its windows, waking the event loop if it
+    /// is asleep.
+    ///
+    /// This is primarily useful in `LoopMode::OnEvent`, where the app otherwise only redraws in
+    /// response to events. Use `Proxy::request_redraw` to request a redraw from other threads.
+    pub fn request_redraw(&self) {
+        self.event_loop_proxy.request_redraw();
+    }
+
+    /// Emit an update and redraw once the given duration has elapsed.
+    ///
+    /// This is a one-shot wakeup, primarily useful in `LoopMode::OnEvent`, e.g. for refreshing a
+    /// visualisation of some periodically changing data.
+    pub fn wake_on_timer(&self, duration: Duration) {
+        self.wake_timers
+            .borrow_mut()
+            .push(Instant::now() + duration);
+    }
+
     /// Returns the **App**'s current **LoopMode**.
     ///
     /// The default loop mode is `LoopMode::RefreshSync`.
@@ -832,15 +1220,232 @@ impl App {
         draw.clone()
     }
 
+    /// The **Turtle** with the given name, created at the origin upon first access.
+    ///
+    /// Named turtles persist for the lifetime of the app, e.g. `app.turtle("ada").forward(50.0)`.
+    ///
+    /// **Panics** if a turtle returned by a previous call is still borrowed. Independent turtles
+    /// may also be stored within the model via `Turtle::new`.
+    pub fn turtle(&self, name: &str) -> RefMut<Turtle> {
+        let turtles = self.turtles.borrow_mut();
+        RefMut::map(turtles, |turtles| {
+            turtles.entry(name.to_string()).or_insert_with(Turtle::new)
+        })
+    }
+
+    /// A handle to the seedable, thread-local random number generator used by nannou's random
+    /// helper functions.
+    ///
+    /// The handle implements `Rng`, e.g. `app.rng().gen_range(0.0, 1.0)`.
+    pub fn rng(&self) -> rand::AppRng {
+        rand::rng()
+    }
+
+    /// Reset the random number generator with the given seed so that a run may be reproduced.
+    ///
+    /// This affects `app.rng()` along with all of the `nannou::rand` helper functions.
+    pub fn seed_rng(&self, seed: u64) {
+        rand::seed_rng(seed);
+    }
+
+    /// The seed with which the random number generator was last reset.
+    ///
+    /// A random seed is chosen when the app starts. Printing it allows for revisiting a "good" run
+    /// via `seed_rng`, e.g. `println!("seed: {}", app.rng_seed())`.
+    pub fn rng_seed(&self) -> u64 {
+        rand::rng_seed()
+    }
+
+    /// Produce a perspective **Camera3D** that may be used to draw 3D geometry via
+    /// `draw.camera(&camera)`.
+    ///
+    /// The camera uses the `DEFAULT_FOV`, `DEFAULT_NEAR` and `DEFAULT_FAR` of **Camera3D**. Until
+    /// positioned via `look_at`, the camera looks along the negative *z* axis such that the
+    /// *z = 0* plane matches the window's usual 2D coordinate space.
+    pub fn camera_3d(&self) -> draw::Camera3D {
+        draw::Camera3D::perspective(
+            draw::Camera3D::DEFAULT_FOV,
+            draw::Camera3D::DEFAULT_NEAR,
+            draw::Camera3D::DEFAULT_FAR,
+        )
+    }
+
+    /// Create a texture that may be used as the target of `draw.to_texture(..)` or
+    /// `frame.draw_to_texture(..)` and later sampled via `draw.texture(..)`.
+    ///
+    /// The `size` is specified in pixels. The texture uses the same format as the frame's
+    /// intermediary texture (`Frame::TEXTURE_FORMAT`) and is created on the device associated with
+    /// the main window.
+    pub fn create_render_texture(&self, size: geom::Vector2) -> wgpu::Texture {
+        let window = self.main_window();
+        let device = window.swap_chain_device();
+        wgpu::TextureBuilder::new()
+            .size([size.x.max(1.0) as u32, size.y.max(1.0) as u32])
+            .format(Frame::TEXTURE_FORMAT)
+            .usage(
+                wgpu::TextureUsage::OUTPUT_ATTACHMENT
+                    | wgpu::TextureUsage::SAMPLED
+                    | wgpu::TextureUsage::COPY_SRC
+                    | wgpu::TextureUsage::COPY_DST,
+            )
+            .build(device)
+    }
+
     /// The number of times the focused window's **view** function has been called since the start
     /// of the program.
+    ///
+    /// When running headless, this is the number of off-screen frames rendered so far.
     pub fn elapsed_frames(&self) -> u64 {
+        if let Some(ref headless) = self.headless {
+            return headless.frame_count;
+        }
         self.main_window().frame_count
     }
 
-    /// The number of frames that can currently be displayed a second
-    pub fn fps(&self) -> f32 {
-        self.duration.updates_per_second()
+    /// Begin recording a video to the given path, encoded by an `ffmpeg` child process.
+    ///
+    /// `fps` is the rate at which the recorded frames are played back. Feed frames to the
+    /// returned recorder via `recorder.feed_frame(&frame)` at the end of `view` and call
+    /// `recorder.finish()` once done, e.g. within the `exit` function. See the **VideoRecorder**
+    /// docs for details.
+    ///
+    /// The codec is chosen based on the file extension via `video::Codec::from_path`. Frames are
+    /// read back from the GPU using the same `max_capture_frame_jobs` and
+    /// `capture_frame_timeout` as `Window::capture_frame`.
+    pub fn record_video<P>(&self, path: P, fps: f64) -> VideoRecorder
+    where
+        P: AsRef<std::path::Path>,
+    {
+        VideoRecorder::new(
+            path.as_ref().to_path_buf(),
+            fps,
+            self.max_capture_frame_jobs,
+            self.capture_frame_timeout,
+        )
+    }
+
+    /// Create a timer of the given duration in seconds that advances with the app's time.
+    ///
+    /// As the timer is driven by `app.duration.since_start`, it only progresses between updates
+    /// and will remain consistent throughout each call to `update` and `view`.
+    pub fn create_timer(&self, duration_secs: f32) -> time::Timer {
+        time::Timer::from_clock(duration_secs, self.clock.clone())
+    }
+
+    /// The app's **AssetServer**, for loading assets on background threads.
+    pub fn assets(&self) -> &asset::AssetServer {
+        &self.assets
+    }
+
+    /// Begin loading the asset at the given path on a background thread.
+    ///
+    /// This is short-hand for `app.assets().load(path)`. The returned handle yields the asset once
+    /// loading completes, at which point an `Event::Asset` is delivered to the app's event
+    /// function.
+    pub fn load_asset<T, P>(&self, path: P) -> asset::Handle<T>
+    where
+        T: asset::Asset,
+        P: AsRef<std::path::Path>,
+    {
+        self.assets.load(path)
+    }
+
+    /// All currently connected gamepads.
+    ///
+    /// Requires the `gamepad` feature.
+    #[cfg(feature = "gamepad")]
+    pub fn gamepads(&self) -> impl Iterator<Item = crate::gamepad::Gamepad> {
+        self.gamepads.gamepads().into_iter()
+    }
+
+    /// A structured text report describing the GPU adapters, monitors, audio devices, MIDI ports
+    /// and gamepads available to the app, suitable for pasting into a bug report.
+    ///
+    /// Audio devices and MIDI ports are only listed when the `audio` and `midi` features are
+    /// enabled, while gamepads require the `gamepad` feature.
+    ///
+    /// See the `app::diagnostics` function for producing the report without an **App**.
+    pub fn diagnostics(&self) -> String {
+        diagnostics::report(Some(self))
+    }
+
+    /// Print the `diagnostics` report to stdout and write it to `nannou-diagnostics.txt` within
+    /// the working directory.
+    ///
+    /// This is called automatically once the model has been created if the `NANNOU_DEBUG`
+    /// environment variable is set to `1`.
+    pub fn print_info(&self) {
+        diagnostics::print(&self.diagnostics());
+    }
+
+    /// Open a native dialog for choosing an existing file, blocking until the dialog is closed.
+    ///
+    /// Only files matching one of the given filters may be chosen, while an empty slice allows
+    /// for choosing any file. Returns `None` if the dialog was cancelled.
+    ///
+    /// Requires the `dialog` feature.
+    #[cfg(feature = "dialog")]
+    pub fn pick_file(&self, title: &str, filters: &[dialog::FileFilter]) -> Option<PathBuf> {
+        dialog::pick_file(title, filters)
+    }
+
+    /// Open a native dialog for choosing an existing directory, blocking until the dialog is
+    /// closed.
+    ///
+    /// Returns `None` if the dialog was cancelled.
+    ///
+    /// Requires the `dialog` feature.
+    #[cfg(feature = "dialog")]
+    pub fn pick_directory(&self, title: &str) -> Option<PathBuf> {
+        dialog::pick_directory(title)
+    }
+
+    /// Open a native dialog for choosing where to save a file, e.g. when exporting an image,
+    /// blocking until the dialog is closed.
+    ///
+    /// The dialog's file name is initialised with `default_name`. Returns `None` if the dialog was
+    /// cancelled.
+    ///
+    /// Requires the `dialog` feature.
+    #[cfg(feature = "dialog")]
+    pub fn save_file_dialog(
+        &self,
+        title: &str,
+        default_name: &str,
+        filters: &[dialog::FileFilter],
+    ) -> Option<PathBuf> {
+        dialog::save_file(title, default_name, filters)
+    }
+
+    /// The duration in seconds between the starts of the two most recent frames.
+    ///
+    /// Frames are timed at the start of each call to the focused window's **view** function, or
+    /// to each off-screen **view** call when running headless.
+    /// Returns `0.0` until at least two frames have been presented.
+    pub fn delta_time(&self) -> f32 {
+        self.frame_times.borrow().delta().secs() as f32
+    }
+
+    /// The instantaneous frame rate, derived from the duration between the two most recent
+    /// frames.
+    pub fn fps(&self) -> f64 {
+        self.frame_times.borrow().fps()
+    }
+
+    /// The frame rate smoothed via an exponential moving average over roughly the last 60
+    /// frames.
+    ///
+    /// This is more useful than `fps` for on-screen display as it does not jitter.
+    pub fn fps_smoothed(&self) -> f64 {
+        self.frame_times.borrow().fps_smoothed()
+    }
+
+    /// The durations of the most recent `n` frames, oldest first.
+    ///
+    /// Useful for plotting frame timing jitter. At most
+    /// `state::time::FrameTimes::HISTORY_LEN` durations are retained.
+    pub fn frame_time_history(&self, n: usize) -> Ref<[Duration]> {
+        Ref::map(self.frame_times.borrow(), |t| t.history(n))
     }
 
     /// The name of the nannou executable that is currently running.
@@ -862,18 +1467,37 @@ impl Proxy {
     ///
     /// The `app::Proxy` stores a flag in order to track whether or not the `EventLoop` is
     /// currently blocking and waiting for events. This method will only call the underlying
-    /// `winit::event_loop::EventLoopProxy::send_event` method if this flag is set to true and will
-    /// immediately set the flag to false afterwards. This makes it safe to call the `wakeup`
-    /// method as frequently as necessary across methods without causing any underlying OS methods
-    /// to be called more than necessary.
+    /// `winit::event_loop::EventLoopProxy::send_event` method if this flag is set to true, clearing
+    /// the flag atomically in the same step. This makes it safe to call the `wakeup` method as
+    /// frequently as necessary from any number of threads without causing any underlying OS
+    /// methods to be called more than necessary.
     pub fn wakeup(&self) -> Result<(), winit::event_loop::EventLoopClosed<()>> {
-        if self.event_loop_is_asleep.load(atomic::Ordering::Relaxed) {
-            self.event_loop_proxy.send_event(())?;
-            self.event_loop_is_asleep
-                .store(false, atomic::Ordering::Relaxed);
+        let event_loop_proxy = match self.event_loop_proxy {
+            Some(ref proxy) => proxy,
+            None => return Ok(()),
+        };
+        // Only the caller that observes the sleeping event loop sends the wakeup event.
+        let was_asleep = self.event_loop_is_asleep.compare_exchange(
+            true,
+            false,
+            atomic::Ordering::AcqRel,
+            atomic::Ordering::Acquire,
+        );
+        if was_asleep.is_ok() {
+            event_loop_proxy.send_event(())?;
         }
         Ok(())
     }
+
+    /// Request that the app emits an update and redraws its windows, waking the event loop if it
+    /// is asleep.
+    ///
+    /// See `App::request_redraw` for details.
+    pub fn request_redraw(&self) {
+        self.redraw_requested.store(true, atomic::Ordering::SeqCst);
+        // The event loop has already closed if this fails, so there is nothing to redraw.
+        self.wakeup().ok();
+    }
 }
 
 impl draw::Draw {
@@ -882,20 +1506,18 @@ impl draw::Draw {
     /// The **App** stores a unique render.
     pub fn to_frame(&self, app: &App, frame: &Frame) -> Result<(), draw::renderer::DrawError> {
         let window_id = frame.window_id();
-        let window = app
-            .window(window_id)
-            .expect("no window to draw to for `Draw`'s window_id");
+        let device = frame.device_queue_pair().device();
+        let scale_factor = frame.scale_factor();
 
         // Retrieve a renderer for this window.
         let renderers = app.draw_state.renderers.borrow_mut();
         let renderer = RefMut::map(renderers, |renderers| {
             renderers.entry(window_id).or_insert_with(|| {
-                let device = window.swap_chain_device();
-                let frame_dims: [u32; 2] = window.tracked_state.physical_size.into();
-                let scale_factor = window.tracked_state.scale_factor as f32;
-                let msaa_samples = window.msaa_samples();
+                let frame_dims = frame.texture_size();
+                let msaa_samples = frame.texture_msaa_samples();
                 let target_format = crate::frame::Frame::TEXTURE_FORMAT;
-                let renderer = draw::RendererBuilder::new().build(
+                let buffer_pool = frame.device_queue_pair().buffer_pool().clone();
+                let renderer = draw::RendererBuilder::new().buffer_pool(buffer_pool).build(
                     device,
                     frame_dims,
                     scale_factor,
@@ -906,13 +1528,33 @@ impl draw::Draw {
             })
         });
 
-        let scale_factor = window.tracked_state.scale_factor as _;
+        // Queue any child draws targeting textures to be rendered ahead of the frame.
+        for (texture, draw) in self.drain_texture_draws() {
+            frame.push_texture_draw(texture, draw);
+        }
+
         let mut renderer = renderer.borrow_mut();
-        renderer.render_to_frame(window.swap_chain_device(), self, scale_factor, frame);
+        renderer.render_to_frame(device, self, scale_factor, frame);
         Ok(())
     }
 }
 
+/// A structured text report describing the GPU adapters, audio devices, MIDI ports and gamepads
+/// available to the system, suitable for pasting into a bug report.
+///
+/// Unlike `App::diagnostics`, this does not require an **App** and so may be called at the top of
+/// `main`, e.g. to investigate an app that fails to start. Monitors are only listed by
+/// `App::diagnostics`, as querying them requires the app's event loop.
+pub fn diagnostics() -> String {
+    diagnostics::report(None)
+}
+
+/// Print the `diagnostics` report to stdout and write it to `nannou-diagnostics.txt` within the
+/// working directory.
+pub fn print_info() {
+    diagnostics::print(&diagnostics());
+}
+
 /// Attempt to find the assets directory path relative to the executable location.
 pub fn find_assets_path() -> Result<PathBuf, find_folder::Error> {
     let exe_path = std::env::current_exe()?;
@@ -991,6 +1633,7 @@ fn run_loop<M, E>(
     update_fn: Option<UpdateFn<M>>,
     default_view: Option<View<M>>,
     exit_fn: Option<ExitFn<M>>,
+    shortcut_fns: Vec<event::ShortcutFn<M>>,
 ) where
     M: 'static,
     E: LoopEvent,
@@ -1028,15 +1671,88 @@ fn run_loop<M, E>(
         match event {
             // Check to see if we need to emit an update and request a redraw.
             winit::event::Event::MainEventsCleared => {
+                // Complete any loaded assets, delivering their events before the update.
+                let asset_events = app.assets.poll(&app);
+                if let (Some(model), Some(event_fn)) = (model.as_mut(), event_fn) {
+                    for event in asset_events {
+                        if let Some(event) = E::from_asset_event(event) {
+                            event_fn(&app, model, event);
+                        }
+                    }
+                }
+
+                // Deliver the files hovered over or dropped onto windows before the update.
+                let file_drops = app.file_drops.borrow_mut().drain();
+                if let Some(model) = model.as_mut() {
+                    for (window_id, drop) in file_drops {
+                        emit_file_drop(&app, model, event_fn, window_id, drop);
+                    }
+                }
+
+                // Deliver any URLs and control requests received from the OS or network.
+                let received = app.net_handlers.borrow_mut().poll();
+                if let Some(model) = model.as_mut() {
+                    for received in received {
+                        received.handle(model);
+                    }
+                }
+
+                // Deliver any lines received by registered serial ports.
+                #[cfg(feature = "serial")]
+                {
+                    if let Some(model) = model.as_mut() {
+                        let ports = app.serial_ports.borrow().clone();
+                        crate::serial::deliver(&ports, model);
+                    }
+                }
+
+                // Deliver any pending gamepad events before the update.
+                #[cfg(feature = "gamepad")]
+                {
+                    if let (Some(model), Some(event_fn)) = (model.as_mut(), event_fn) {
+                        for event in app.gamepads.poll() {
+                            if let Some(event) = E::from_gamepad_event(event) {
+                                event_fn(&app, model, event);
+                            }
+                        }
+                    }
+                }
                 if let Some(model) = model.as_mut() {
                     let loop_mode = app.loop_mode();
                     let now = Instant::now();
+                    let redraw_requested = app
+                        .event_loop_proxy
+                        .redraw_requested
+                        .swap(false, atomic::Ordering::SeqCst);
+                    let timer_expired = take_expired_wake_timers(&app, now);
                     let mut do_update = |loop_state: &mut LoopState| {
-                        apply_update(&mut app, model, event_fn, update_fn, loop_state, now);
+                        apply_update(&mut app, model, event_fn, update_fn, loop_state, now, 0.0);
                     };
                     match loop_mode {
                         LoopMode::NTimes { number_of_updates }
                             if loop_state.total_updates >= number_of_updates as u64 => {}
+                        LoopMode::OnEvent { max_wait } => {
+                            let waited = max_wait
+                                .map(|max| now.duration_since(loop_state.last_update) >= max)
+                                .unwrap_or(false);
+                            let event_arrived = loop_state.updates_since_event == 0;
+                            if event_arrived || redraw_requested || timer_expired || waited {
+                                do_update(&mut loop_state);
+                            }
+                        }
+                        LoopMode::FixedStep {
+                            update_rate,
+                            max_frame_skip,
+                        } => apply_fixed_step_updates(
+                            &mut app,
+                            model,
+                            event_fn,
+                            update_fn,
+                            &mut loop_state,
+                            now,
+                            update_rate,
+                            max_frame_skip,
+                        ),
                         _ => do_update(&mut loop_state),
                     }
                 }
@@ -1049,7 +1765,7 @@ fn run_loop<M, E>(
             winit::event::Event::RedrawRequested(window_id) => {
                 // Take the render data and swapchain.
                 // We'll replace them before the end of this block.
-                let (mut swap_chain, nth_frame) = {
+                let (mut swap_chain, nth_frame, timestamp, first_timestamp) = {
                     let mut windows = app.windows.borrow_mut();
 
                     let window = windows
@@ -1062,7 +1778,15 @@ fn run_loop<M, E>(
                         .expect("missing swap chain");
                     let nth_frame = window.frame_count;
                     window.frame_count += 1;
-                    (swap_chain, nth_frame)
+                    let timestamp = Instant::now();
+                    let first_timestamp = *window.first_frame_at.get_or_insert(timestamp);
+                    // Time the focused window's frames, or every frame if no window has focus.
+                    let focused = *app.focused_window.borrow();
+                    if focused.map(|id| id == window_id).unwrap_or(true) {
+                        app.frame_times.borrow_mut().record(timestamp);
+                    }
+                    window.swap_feedback();
+                    (swap_chain, nth_frame, timestamp, first_timestamp)
                 };
 
                 if let Some(model) = model.as_ref() {
@@ -1087,14 +1811,19 @@ fn run_loop<M, E>(
                         .to_logical::<f32>(sf)
                         .into();
                     let window_rect = geom::Rect::from_w_h(w, h);
-                    let raw_frame = RawFrame::new_empty(
+                    let mut raw_frame = RawFrame::new_empty(
                         window.swap_chain_device_queue_pair().clone(),
                         window_id,
                         nth_frame,
+                        timestamp,
+                        first_timestamp,
                         swap_chain_texture,
                         window.swap_chain.descriptor.format,
                         window_rect,
+                        sf as f32,
                     );
+                    let feedback = window.feedback_views(&mut *raw_frame.command_encoder());
+                    raw_frame.set_feedback(feedback);
 
                     // If the user specified a view function specifically for this window, use it.
                     // Otherwise, use the fallback, default view passed to the app if there was one.
@@ -1104,11 +1833,13 @@ fn run_loop<M, E>(
                         Some(window::View::Sketch(view)) => {
                             let data = frame_data.as_ref().expect("missing `frame_data`");
                             let frame = Frame::new_empty(raw_frame, &data.render, &data.capture);
+                            set_frame_cursor_image(&app, window, &frame);
                             view(&app, frame);
                         }
                         Some(window::View::WithModel(view)) => {
                             let data = frame_data.as_ref().expect("missing `frame_data`");
                             let frame = Frame::new_empty(raw_frame, &data.render, &data.capture);
+                            set_frame_cursor_image(&app, window, &frame);
                             let view = view
                                 .to_fn_ptr::<M>()
                                 .expect("unexpected model argument given to window view function");
@@ -1125,12 +1856,14 @@ fn run_loop<M, E>(
                                 let data = frame_data.as_ref().expect("missing `frame_data`");
                                 let frame =
                                     Frame::new_empty(raw_frame, &data.render, &data.capture);
+                                set_frame_cursor_image(&app, window, &frame);
                                 view(&app, frame);
                             }
                             Some(View::WithModel(view)) => {
                                 let data = frame_data.as_ref().expect("missing `frame_data`");
                                 let frame =
                                     Frame::new_empty(raw_frame, &data.render, &data.capture);
+                                set_frame_cursor_image(&app, window, &frame);
                                 view(&app, &model, frame);
                             }
                             None => raw_frame.submit(),
@@ -1150,6 +1883,7 @@ fn run_loop<M, E>(
             // Clear any inactive adapters and devices and poll those remaining.
             winit::event::Event::RedrawEventsCleared => {
                 app.wgpu_adapters().clear_inactive_adapters_and_devices();
+                app.wgpu_adapters().end_frame_buffer_pools();
                 // TODO: This seems to cause some glitching and slows down macOS drastically.
                 // While not necessary, this would be nice to have to automatically process async
                 // read/write callbacks submitted by users who aren't aware that they need to poll
@@ -1200,7 +1934,13 @@ fn run_loop<M, E>(
 
         // Process the event with the users functions and see if we need to exit.
         if let Some(model) = model.as_mut() {
-            exit |= process_and_emit_winit_event::<M, E>(&mut app, model, event_fn, &event);
+            exit |= process_and_emit_winit_event::<M, E>(
+                &mut app,
+                model,
+                event_fn,
+                &shortcut_fns,
+                &event,
+            );
         }
 
         // Set the control flow based on the loop mode.
@@ -1221,9 +1961,31 @@ fn run_loop<M, E>(
             {
                 ControlFlow::Wait
             }
+            LoopMode::OnEvent { max_wait } => {
+                // Sleep until the earliest of the next timer and the maximum wait.
+                let next_timer = app.wake_timers.borrow().iter().min().cloned();
+                let max_wait = max_wait.map(|max| loop_state.last_update + max);
+                let deadline = match (next_timer, max_wait) {
+                    (Some(a), Some(b)) => Some(a.min(b)),
+                    (a, b) => a.or(b),
+                };
+                match deadline {
+                    Some(deadline) => ControlFlow::WaitUntil(deadline),
+                    None => ControlFlow::Wait,
+                }
+            }
             _ => ControlFlow::Poll,
         };
 
+        // Allow for waking the event loop via the app's proxy while it sleeps.
+        let asleep = match *control_flow {
+            ControlFlow::Wait | ControlFlow::WaitUntil(_) => true,
+            _ => false,
+        };
+        app.event_loop_proxy
+            .event_loop_is_asleep
+            .store(asleep, atomic::Ordering::Release);
+
         // If we need to exit, call the user's function and update control flow.
         if exit {
             if let Some(model) = model.take() {
@@ -1255,6 +2017,7 @@ fn apply_update<M, E>(
     update_fn: Option<UpdateFn<M>>,
     loop_state: &mut LoopState,
     now: Instant,
+    accumulator_alpha: f32,
 ) where
     M: 'static,
     E: LoopEvent,
@@ -1264,10 +2027,13 @@ fn apply_update<M, E>(
     let since_start = now.duration_since(loop_state.loop_start);
     app.duration.since_prev_update = since_last;
     app.duration.since_start = since_start;
+    app.clock.set(since_start);
     app.time = since_start.secs() as _;
+    app.accumulator_alpha = accumulator_alpha;
     let update = crate::event::Update {
         since_start,
         since_last,
+        accumulator_alpha,
     };
     // User event function.
     if let Some(event_fn) = event_fn {
@@ -1288,6 +2054,79 @@ fn apply_update<M, E>(
     }
 }
 
+// Provide the frame with the window's cursor image, if any, while the mouse is over the window.
+fn set_frame_cursor_image(app: &App, window: &Window, frame: &Frame) {
+    if app.mouse.window != Some(frame.window_id()) {
+        return;
+    }
+    if let Some(cursor) = window.cursor_image() {
+        frame.set_cursor_image(cursor, app.mouse.position());
+    }
+}
+
+// Remove all wake timers that have expired by `now`, returning whether or not any were removed.
+fn take_expired_wake_timers(app: &App, now: Instant) -> bool {
+    let mut timers = app.wake_timers.borrow_mut();
+    let len = timers.len();
+    timers.retain(|&timer| timer > now);
+    timers.len() < len
+}
+
+// Apply an update for each fixed step that has accumulated since the last update.
+//
+// The time of the last update acts as the accumulator. Each update advances it by exactly one
+// step, so that the fraction of a step remaining is carried over to the next frame.
+fn apply_fixed_step_updates<M, E>(
+    app: &mut App,
+    model: &mut M,
+    event_fn: Option<EventFn<M, E>>,
+    update_fn: Option<UpdateFn<M>>,
+    loop_state: &mut LoopState,
+    now: Instant,
+    update_rate: f64,
+    max_frame_skip: u32,
+) where
+    M: 'static,
+    E: LoopEvent,
+{
+    let step = update_interval(update_rate);
+    let step_secs = step.secs();
+    let accumulated = now.duration_since(loop_state.last_update).secs();
+    let total_steps = (accumulated / step_secs).floor();
+    let remainder = accumulated - total_steps * step_secs;
+    let steps = total_steps.min(max_frame_skip as f64) as u32;
+
+    // Discard any time beyond `max_frame_skip` steps to avoid falling further and further behind.
+    if total_steps > steps as f64 {
+        let behind = steps as f64 * step_secs + remainder;
+        loop_state.last_update = now - Duration::from_secs_f64(behind);
+    }
+
+    let accumulator_alpha = (remainder / step_secs).min(1.0).max(0.0) as f32;
+    for _ in 0..steps {
+        let step_end = loop_state.last_update + step;
+        apply_update(
+            app,
+            model,
+            event_fn,
+            update_fn,
+            loop_state,
+            step_end,
+            accumulator_alpha,
+        );
+    }
+
+    // Frames are drawn regardless of whether or not a step completed, allowing for `view` to
+    // interpolate using the new `accumulator_alpha`.
+    app.accumulator_alpha = accumulator_alpha;
+    if steps == 0 {
+        let windows = app.windows.borrow();
+        for window in windows.values() {
+            window.window.request_redraw();
+        }
+    }
+}
+
 // Whether or not the given event should toggle fullscreen.
 fn should_toggle_fullscreen(
     winit_event: &winit::event::WindowEvent,
@@ -1328,6 +2167,39 @@ fn should_toggle_fullscreen(
     false
 }
 
+// Deliver a batch of hovered or dropped files to the `event_fn` and, in the case of dropped files,
+// to the window's `on_file_drop` function.
+fn emit_file_drop<M, E>(
+    app: &App,
+    model: &mut M,
+    event_fn: Option<EventFn<M, E>>,
+    window_id: window::Id,
+    drop: event::FileDrop,
+) where
+    M: 'static,
+    E: LoopEvent,
+{
+    let file_drop_fn = match drop {
+        event::FileDrop::Dropped(_) => app
+            .windows
+            .borrow()
+            .get(&window_id)
+            .and_then(|w| w.user_functions.file_drop.clone()),
+        _ => None,
+    };
+    if let Some(file_drop_fn) = file_drop_fn {
+        let file_drop_fn = file_drop_fn
+            .to_fn_ptr::<M>()
+            .expect("unexpected model argument given to file_drop function");
+        (*file_drop_fn)(model, drop.paths().to_vec());
+    }
+    if let Some(event_fn) = event_fn {
+        if let Some(event) = E::from_file_drop(window_id, drop) {
+            event_fn(app, model, event);
+        }
+    }
+}
+
 // Event handling boilerplate shared between the loop modes.
 //
 // 1. Checks for exit on escape.
@@ -1338,6 +2210,7 @@ fn process_and_emit_winit_event<'a, M, E>(
     app: &mut App,
     model: &mut M,
     event_fn: Option<EventFn<M, E>>,
+    shortcut_fns: &[event::ShortcutFn<M>],
     winit_event: &winit::event::Event<'a, ()>,
 ) -> bool
 where
@@ -1385,9 +2258,9 @@ where
                     if app.fullscreen_on_shortcut() {
                         if should_toggle_fullscreen(event, &app.keys.mods) {
                             if win.is_fullscreen() {
-                                win.set_fullscreen(false);
+                                win.exit_fullscreen();
                             } else {
-                                win.set_fullscreen(true);
+                                win.set_fullscreen(Default::default());
                             }
                         }
                     }
@@ -1570,7 +2443,25 @@ where
 
             // Check for more specific event functions.
             match simple {
-                event::WindowEvent::KeyPressed(key) => call_user_function!(key_pressed, key),
+                event::WindowEvent::KeyPressed(key) => {
+                    // Collect the handlers first, as handlers may register more handlers.
+                    let key_press_fns: Vec<_> = app
+                        .key_press_fns
+                        .borrow()
+                        .iter()
+                        .filter(|&&(k, _)| k == key)
+                        .map(|&(_, f)| f)
+                        .collect();
+                    for f in key_press_fns {
+                        f(&app);
+                    }
+                    for (shortcut, handler) in app.shortcuts.borrow().iter().zip(shortcut_fns) {
+                        if shortcut.matches(key, app.keys.mods) {
+                            handler(model);
+                        }
+                    }
+                    call_user_function!(key_pressed, key)
+                }
                 event::WindowEvent::KeyReleased(key) => call_user_function!(key_released, key),
                 event::WindowEvent::MouseMoved(pos) => call_user_function!(mouse_moved, pos),
                 event::WindowEvent::MousePressed(button) => {
@@ -1586,15 +2477,35 @@ where
                 }
                 event::WindowEvent::Moved(pos) => call_user_function!(moved, pos),
                 event::WindowEvent::Resized(size) => call_user_function!(resized, size),
-                event::WindowEvent::Touch(touch) => call_user_function!(touch, touch),
+                event::WindowEvent::Touch(touch) => {
+                    call_user_function!(touch, touch);
+                    // Only run gesture recognition for windows that handle gestures.
+                    let gestures = app
+                        .windows
+                        .borrow_mut()
+                        .get_mut(&window_id)
+                        .filter(|w| w.user_functions.gesture.is_some())
+                        .map(|w| w.gesture_recogniser.touch(&touch))
+                        .unwrap_or_default();
+                    for gesture in gestures {
+                        call_user_function!(gesture, gesture);
+                    }
+                }
                 event::WindowEvent::TouchPressure(pressure) => {
                     call_user_function!(touchpad_pressure, pressure)
                 }
-                event::WindowEvent::HoveredFile(path) => call_user_function!(hovered_file, path),
+                event::WindowEvent::HoveredFile(path) => {
+                    app.file_drops.borrow_mut().hovered(window_id, path.clone());
+                    call_user_function!(hovered_file, path)
+                }
                 event::WindowEvent::HoveredFileCancelled => {
+                    app.file_drops.borrow_mut().cancelled(window_id);
                     call_user_function!(hovered_file_cancelled)
                 }
-                event::WindowEvent::DroppedFile(path) => call_user_function!(dropped_file, path),
+                event::WindowEvent::DroppedFile(path) => {
+                    app.file_drops.borrow_mut().dropped(window_id, path.clone());
+                    call_user_function!(dropped_file, path)
+                }
                 event::WindowEvent::Focused => call_user_function!(focused),
                 event::WindowEvent::Unfocused => call_user_function!(unfocused),
                 event::WindowEvent::Closed => call_user_function!(closed),
@@ -0,0 +1,409 @@
+//! Loading assets such as images, textures, fonts and audio files on background threads.
+//!
+//! Call `app.load_asset(path)` or `app.assets().load(path)` to begin loading an asset. A
+//! **Handle** is returned immediately, which yields the asset once loading completes. Loading is
+//! completed by the app between updates, at which point an `Event::Asset` is delivered to the
+//! app's event function.
+//!
+//! Custom asset types may be loaded by implementing the **Asset** trait.
+
+use crate::text;
+use crate::wgpu;
+use crate::App;
+use std::cell::{Cell, Ref, RefCell};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::mpsc;
+use std::{fmt, io};
+
+/// A type that may be loaded via the **AssetServer**.
+///
+/// Loading happens in two stages. First, `load` reads the asset's data on a background thread.
+/// Then, `from_data` produces the asset on the main thread, e.g. for uploading an image to the
+/// GPU.
+pub trait Asset: Sized + 'static {
+    /// The data produced on the background thread.
+    type Data: Send + 'static;
+
+    /// Read the asset's data from the file at the given path.
+    fn load(path: &Path) -> Result<Self::Data, Error>;
+
+    /// Produce the asset from its data.
+    fn from_data(data: Self::Data, app: &App) -> Result<Self, Error>;
+}
+
+/// Loads assets on a thread pool, completing them between updates of the app.
+///
+/// Access the app's asset server via `app.assets()`.
+pub struct AssetServer {
+    proxy: crate::app::Proxy,
+    next_id: Cell<u64>,
+    pending: RefCell<Vec<Box<dyn PendingAsset>>>,
+    // Only spawned upon the first load.
+    thread_pool: RefCell<Option<rayon::ThreadPool>>,
+}
+
+/// A handle to an asset that may still be loading.
+///
+/// Handles are cheap to clone and all clones refer to the same asset.
+pub struct Handle<T> {
+    id: HandleId,
+    path: Rc<PathBuf>,
+    state: Rc<RefCell<State<T>>>,
+}
+
+/// A unique identifier for a handle produced by the **AssetServer**.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HandleId(u64);
+
+/// An event emitted by the **AssetServer** upon completing a load.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AssetEvent {
+    /// The asset was loaded and is available via `Handle::get`.
+    Loaded(HandleId),
+    /// Loading failed. The error is available via `Handle::error`.
+    Failed(HandleId),
+}
+
+/// Errors that might occur while loading an asset.
+#[derive(Debug)]
+pub enum Error {
+    /// Failed to read the asset's file.
+    Io(io::Error),
+    /// Failed to decode an image.
+    Image(image::ImageError),
+    /// Failed to load a font.
+    Font(text::font::Error),
+    /// Failed to decode an audio file.
+    #[cfg(feature = "audio")]
+    Audio(crate::audio::file::Error),
+    /// Failed to spawn the thread pool on which assets are loaded.
+    ThreadPool(rayon::ThreadPoolBuildError),
+    /// The background thread stopped before the asset was loaded.
+    Cancelled,
+}
+
+// The loading state of an asset.
+enum State<T> {
+    Loading,
+    Loaded(T),
+    Failed(Error),
+}
+
+// An asset whose data is being loaded on the thread pool.
+trait PendingAsset {
+    // Complete the asset if its data is ready, returning the resulting event.
+    fn poll(&mut self, app: &App) -> Option<AssetEvent>;
+}
+
+struct Pending<T: Asset> {
+    id: HandleId,
+    rx: mpsc::Receiver<Result<T::Data, Error>>,
+    state: Rc<RefCell<State<T>>>,
+}
+
+impl AssetServer {
+    // Create the asset server. Used by `App::new`.
+    pub(crate) fn new(proxy: crate::app::Proxy) -> Self {
+        AssetServer {
+            proxy,
+            next_id: Cell::new(0),
+            pending: RefCell::new(vec![]),
+            thread_pool: RefCell::new(None),
+        }
+    }
+
+    /// Begin loading the asset at the given path on a background thread.
+    ///
+    /// The returned handle yields the asset once loading completes. Relative paths are relative to
+    /// the current working directory, so consider joining paths onto `app.assets_path()`.
+    pub fn load<T, P>(&self, path: P) -> Handle<T>
+    where
+        T: Asset,
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref().to_path_buf();
+        let id = HandleId(self.next_id.get());
+        self.next_id.set(id.0 + 1);
+        let state = Rc::new(RefCell::new(State::Loading));
+        let (tx, rx) = mpsc::channel();
+
+        let thread_path = path.clone();
+        let proxy = self.proxy.clone();
+        let load = move || {
+            tx.send(T::load(&thread_path)).ok();
+            // Wake the app in case it is waiting for events.
+            proxy.wakeup().ok();
+        };
+        match self.spawn(load) {
+            Ok(()) => (),
+            Err(err) => *state.borrow_mut() = State::Failed(Error::ThreadPool(err)),
+        }
+
+        let pending = Pending {
+            id,
+            rx,
+            state: state.clone(),
+        };
+        self.pending.borrow_mut().push(Box::new(pending));
+        Handle {
+            id,
+            path: Rc::new(path),
+            state,
+        }
+    }
+
+    /// The number of assets that are still loading.
+    pub fn pending(&self) -> usize {
+        self.pending.borrow().len()
+    }
+
+    // Complete all assets whose data is ready, returning an event for each. Called by the app
+    // before each update.
+    pub(crate) fn poll(&self, app: &App) -> Vec<AssetEvent> {
+        // Take the pending assets, as loads may begin while completing others.
+        let mut pending = std::mem::replace(&mut *self.pending.borrow_mut(), vec![]);
+        let mut events = vec![];
+        let mut i = 0;
+        while i < pending.len() {
+            match pending[i].poll(app) {
+                Some(event) => {
+                    events.push(event);
+                    pending.remove(i);
+                }
+                None => i += 1,
+            }
+        }
+        let mut guard = self.pending.borrow_mut();
+        pending.extend(guard.drain(..));
+        *guard = pending;
+        events
+    }
+
+    // Spawn the given task on the thread pool, spawning the pool upon the first call.
+    fn spawn<F>(&self, task: F) -> Result<(), rayon::ThreadPoolBuildError>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let mut guard = self.thread_pool.borrow_mut();
+        if guard.is_none() {
+            let thread_pool = rayon::ThreadPoolBuilder::new()
+                .thread_name(|i| format!("nannou_asset_loader_{}", i))
+                .build()?;
+            *guard = Some(thread_pool);
+        }
+        guard.as_ref().expect("no thread pool").spawn(task);
+        Ok(())
+    }
+}
+
+impl<T> Handle<T> {
+    /// The unique identifier of the handle, matching that delivered via **AssetEvent**s.
+    pub fn id(&self) -> HandleId {
+        self.id
+    }
+
+    /// The path from which the asset is loaded.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The asset, or `None` if it is still loading or loading failed.
+    pub fn get(&self) -> Option<Ref<T>> {
+        if !self.is_loaded() {
+            return None;
+        }
+        let asset = Ref::map(self.state.borrow(), |state| match *state {
+            State::Loaded(ref asset) => asset,
+            _ => unreachable!(),
+        });
+        Some(asset)
+    }
+
+    /// Whether or not the asset has finished loading successfully.
+    pub fn is_loaded(&self) -> bool {
+        match *self.state.borrow() {
+            State::Loaded(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Whether or not the asset is still loading.
+    pub fn is_loading(&self) -> bool {
+        match *self.state.borrow() {
+            State::Loading => true,
+            _ => false,
+        }
+    }
+
+    /// Whether or not loading the asset failed.
+    pub fn is_failed(&self) -> bool {
+        match *self.state.borrow() {
+            State::Failed(_) => true,
+            _ => false,
+        }
+    }
+
+    /// The error that occurred while loading the asset, if any.
+    pub fn error(&self) -> Option<Ref<Error>> {
+        if !self.is_failed() {
+            return None;
+        }
+        let error = Ref::map(self.state.borrow(), |state| match *state {
+            State::Failed(ref err) => err,
+            _ => unreachable!(),
+        });
+        Some(error)
+    }
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        Handle {
+            id: self.id,
+            path: self.path.clone(),
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for Handle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Handle")
+            .field("id", &self.id)
+            .field("path", &self.path)
+            .field("is_loaded", &self.is_loaded())
+            .finish()
+    }
+}
+
+impl<T> PendingAsset for Pending<T>
+where
+    T: Asset,
+{
+    fn poll(&mut self, app: &App) -> Option<AssetEvent> {
+        // Loading fails immediately if the thread pool could not be spawned.
+        if let State::Failed(_) = *self.state.borrow() {
+            return Some(AssetEvent::Failed(self.id));
+        }
+        let result = match self.rx.try_recv() {
+            Ok(result) => result.and_then(|data| T::from_data(data, app)),
+            Err(mpsc::TryRecvError::Empty) => return None,
+            Err(mpsc::TryRecvError::Disconnected) => Err(Error::Cancelled),
+        };
+        let (state, event) = match result {
+            Ok(asset) => (State::Loaded(asset), AssetEvent::Loaded(self.id)),
+            Err(err) => (State::Failed(err), AssetEvent::Failed(self.id)),
+        };
+        *self.state.borrow_mut() = state;
+        Some(event)
+    }
+}
+
+impl Asset for image::DynamicImage {
+    type Data = Self;
+
+    fn load(path: &Path) -> Result<Self::Data, Error> {
+        Ok(image::open(path)?)
+    }
+
+    fn from_data(data: Self::Data, _app: &App) -> Result<Self, Error> {
+        Ok(data)
+    }
+}
+
+impl Asset for wgpu::Texture {
+    type Data = image::DynamicImage;
+
+    fn load(path: &Path) -> Result<Self::Data, Error> {
+        Ok(image::open(path)?)
+    }
+
+    fn from_data(data: Self::Data, app: &App) -> Result<Self, Error> {
+        Ok(wgpu::Texture::from_image(app, &data))
+    }
+}
+
+impl Asset for text::Font {
+    type Data = Self;
+
+    fn load(path: &Path) -> Result<Self::Data, Error> {
+        Ok(text::font::from_file(path)?)
+    }
+
+    fn from_data(data: Self::Data, _app: &App) -> Result<Self, Error> {
+        Ok(data)
+    }
+}
+
+#[cfg(feature = "audio")]
+impl Asset for crate::audio::File {
+    type Data = Self;
+
+    fn load(path: &Path) -> Result<Self::Data, Error> {
+        Ok(crate::audio::File::open(path)?)
+    }
+
+    fn from_data(data: Self::Data, _app: &App) -> Result<Self, Error> {
+        Ok(data)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<image::ImageError> for Error {
+    fn from(err: image::ImageError) -> Self {
+        Error::Image(err)
+    }
+}
+
+impl From<text::font::Error> for Error {
+    fn from(err: text::font::Error) -> Self {
+        Error::Font(err)
+    }
+}
+
+#[cfg(feature = "audio")]
+impl From<crate::audio::file::Error> for Error {
+    fn from(err: crate::audio::file::Error) -> Self {
+        Error::Audio(err)
+    }
+}
+
+impl From<rayon::ThreadPoolBuildError> for Error {
+    fn from(err: rayon::ThreadPoolBuildError) -> Self {
+        Error::ThreadPool(err)
+    }
+}
+
+impl std::error::Error for Error {
+    fn cause(&self) -> Option<&dyn std::error::Error> {
+        match *self {
+            Error::Io(ref err) => Some(err),
+            Error::Image(ref err) => Some(err),
+            Error::Font(ref err) => Some(err),
+            #[cfg(feature = "audio")]
+            Error::Audio(ref err) => Some(err),
+            Error::ThreadPool(ref err) => Some(err),
+            Error::Cancelled => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Io(ref err) => err.fmt(f),
+            Error::Image(ref err) => err.fmt(f),
+            Error::Font(ref err) => write!(f, "failed to load font: {}", err),
+            #[cfg(feature = "audio")]
+            Error::Audio(ref err) => err.fmt(f),
+            Error::ThreadPool(ref err) => write!(f, "failed to spawn the asset loader: {}", err),
+            Error::Cancelled => write!(f, "the asset loader stopped before loading completed"),
+        }
+    }
+}
@@ -0,0 +1,393 @@
+//! A spring-mesh cloth simulation integrated via Verlet integration.
+//!
+//! A **ClothSimulation** is a grid of nodes connected to their horizontal and vertical neighbours
+//! by springs. Nodes may be pinned in place, pushed by wind and torn apart by cutting springs:
+//!
+//! ```ignore
+//! let mut cloth = ClothSimulation::new(40, 30, 12.0);
+//! for col in (0..40).step_by(4) {
+//!     cloth.pin(col, 0);
+//! }
+//! cloth.wind(vec2(1.0, 0.0), 200.0);
+//!
+//! // Within `update`.
+//! cloth.update(update.since_last.secs() as f32);
+//!
+//! // Within `view`.
+//! cloth.draw_mesh(&draw).color(PLUM);
+//! ```
+//!
+//! Simulations created via `new` are constrained to the *xy* plane, while those created via
+//! `new_3d` are free to billow along the *z* axis, in which case wind pushes against the surface
+//! of the cloth. Wind blowing along the surface of a 3D cloth only drags it a little, so a flat
+//! cloth facing along the *z* axis is best blown by wind with a *z* component.
+
+use crate::draw::primitive::mesh::DrawingMesh;
+use crate::geom::{pt2, pt3, vec3, Point2, Point3, Vector3};
+use crate::wgpu;
+use crate::Draw;
+
+/// A grid of nodes connected by springs, simulated via Verlet integration.
+///
+/// Nodes are addressed by column and row, with row `0` along the top of the cloth. The cloth
+/// begins centred on the origin within the *xy* plane.
+#[derive(Clone, Debug)]
+pub struct ClothSimulation {
+    cols: usize,
+    rows: usize,
+    spacing: f32,
+    three_d: bool,
+    positions: Vec<Point3>,
+    previous: Vec<Point3>,
+    pinned: Vec<bool>,
+    // Whether the spring to the right of and below each node remains intact.
+    right: Vec<bool>,
+    down: Vec<bool>,
+    gravity: Vector3,
+    wind: Vector3,
+    damping: f32,
+    iterations: usize,
+}
+
+// The fraction of the wind blowing along the surface of a 3D cloth that is applied to its nodes.
+const TANGENTIAL_WIND: f32 = 0.1;
+
+// A spring identified by the index of the node to its left or above it.
+enum Spring {
+    Right(usize),
+    Down(usize),
+}
+
+impl ClothSimulation {
+    /// The default acceleration applied to every node.
+    pub const DEFAULT_GRAVITY: Vector3 = Vector3 {
+        x: 0.0,
+        y: -500.0,
+        z: 0.0,
+    };
+
+    /// The default fraction of each node's velocity lost per update.
+    pub const DEFAULT_DAMPING: f32 = 0.01;
+
+    /// The default number of times the springs are relaxed per update.
+    pub const DEFAULT_ITERATIONS: usize = 8;
+
+    /// A cloth of `cols` by `rows` nodes separated by `spacing`, constrained to the *xy* plane.
+    ///
+    /// **Panics** if `cols` or `rows` is less than `2`.
+    pub fn new(cols: usize, rows: usize, spacing: f32) -> Self {
+        Self::with_mode(cols, rows, spacing, false)
+    }
+
+    /// A cloth of `cols` by `rows` nodes separated by `spacing`, free to move in three dimensions.
+    ///
+    /// **Panics** if `cols` or `rows` is less than `2`.
+    pub fn new_3d(cols: usize, rows: usize, spacing: f32) -> Self {
+        Self::with_mode(cols, rows, spacing, true)
+    }
+
+    fn with_mode(cols: usize, rows: usize, spacing: f32, three_d: bool) -> Self {
+        assert!(
+            cols >= 2 && rows >= 2,
+            "a cloth requires at least 2 columns and rows"
+        );
+        let w = (cols - 1) as f32 * spacing;
+        let h = (rows - 1) as f32 * spacing;
+        let positions: Vec<Point3> = (0..cols * rows)
+            .map(|i| {
+                let (col, row) = ((i % cols) as f32, (i / cols) as f32);
+                pt3(col * spacing - w * 0.5, h * 0.5 - row * spacing, 0.0)
+            })
+            .collect();
+        let n = positions.len();
+        let right = (0..n).map(|i| i % cols < cols - 1).collect();
+        let down = (0..n).map(|i| i / cols < rows - 1).collect();
+        ClothSimulation {
+            cols,
+            rows,
+            spacing,
+            three_d,
+            previous: positions.clone(),
+            positions,
+            pinned: vec![false; n],
+            right,
+            down,
+            gravity: Self::DEFAULT_GRAVITY,
+            wind: vec3(0.0, 0.0, 0.0),
+            damping: Self::DEFAULT_DAMPING,
+            iterations: Self::DEFAULT_ITERATIONS,
+        }
+    }
+
+    /// The number of columns of nodes.
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// The number of rows of nodes.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Whether or not the cloth may move along the *z* axis.
+    pub fn is_3d(&self) -> bool {
+        self.three_d
+    }
+
+    /// The position of the node at the given column and row.
+    pub fn position(&self, col: usize, row: usize) -> Point3 {
+        self.positions[self.index(col, row)]
+    }
+
+    /// Move the node at the given column and row, e.g. to drag the cloth with the mouse.
+    ///
+    /// The node keeps its velocity.
+    pub fn set_position(&mut self, col: usize, row: usize, position: Point3) {
+        let i = self.index(col, row);
+        let position = self.constrain(position);
+        let velocity = self.positions[i] - self.previous[i];
+        self.positions[i] = position;
+        self.previous[i] = position - velocity;
+    }
+
+    /// Fix the node at the given column and row in place.
+    pub fn pin(&mut self, col: usize, row: usize) {
+        let i = self.index(col, row);
+        self.pinned[i] = true;
+    }
+
+    /// Allow the node at the given column and row to move freely.
+    pub fn unpin(&mut self, col: usize, row: usize) {
+        let i = self.index(col, row);
+        self.pinned[i] = false;
+        self.previous[i] = self.positions[i];
+    }
+
+    /// The acceleration applied to every node.
+    pub fn set_gravity<V>(&mut self, gravity: V)
+    where
+        V: Into<Vector3>,
+    {
+        self.gravity = self.constrain(gravity.into());
+    }
+
+    /// Blow wind in the given direction with the given strength during every update.
+    ///
+    /// Within 3D simulations, each node is pushed along its surface normal in proportion to how
+    /// directly the wind strikes it, while the portion of the wind blowing along the surface only
+    /// drags the node slightly. A `strength` of `0.0` or a zero `direction` stops the wind.
+    pub fn wind<V>(&mut self, direction: V, strength: f32)
+    where
+        V: Into<Vector3>,
+    {
+        self.wind = normalize_or_zero(self.constrain(direction.into())) * strength;
+    }
+
+    /// The fraction of each node's velocity lost per update within the range `0.0..=1.0`.
+    pub fn set_damping(&mut self, damping: f32) {
+        self.damping = damping;
+    }
+
+    /// The number of times the springs are relaxed per update. Higher values produce stiffer
+    /// cloth at a greater cost.
+    pub fn set_iterations(&mut self, iterations: usize) {
+        self.iterations = iterations;
+    }
+
+    /// Cut the spring between the adjacent nodes `a` and `b`, each given as `(col, row)`.
+    ///
+    /// Returns `false` if the nodes are not horizontally or vertically adjacent or the spring was
+    /// already cut.
+    pub fn cut_spring(&mut self, a: (usize, usize), b: (usize, usize)) -> bool {
+        match self.spring(a, b) {
+            Some(Spring::Right(i)) => std::mem::replace(&mut self.right[i], false),
+            Some(Spring::Down(i)) => std::mem::replace(&mut self.down[i], false),
+            None => false,
+        }
+    }
+
+    /// Whether or not the spring between the adjacent nodes `a` and `b` is intact.
+    pub fn has_spring(&self, a: (usize, usize), b: (usize, usize)) -> bool {
+        match self.spring(a, b) {
+            Some(Spring::Right(i)) => self.right[i],
+            Some(Spring::Down(i)) => self.down[i],
+            None => false,
+        }
+    }
+
+    /// Advance the simulation by `dt` seconds.
+    pub fn update(&mut self, dt: f32) {
+        let normals = match self.three_d && self.wind != vec3(0.0, 0.0, 0.0) {
+            true => Some(self.normals()),
+            false => None,
+        };
+        let retain = 1.0 - self.damping;
+        for i in 0..self.positions.len() {
+            if self.pinned[i] {
+                continue;
+            }
+            let wind = match normals {
+                Some(ref normals) => {
+                    let normal = normals[i] * normals[i].dot(self.wind);
+                    normal + (self.wind - normal) * TANGENTIAL_WIND
+                }
+                None => self.wind,
+            };
+            let acceleration = self.gravity + wind;
+            let position = self.positions[i];
+            let velocity = (position - self.previous[i]) * retain;
+            self.previous[i] = position;
+            self.positions[i] = self.constrain(position + velocity + acceleration * dt * dt);
+        }
+        for _ in 0..self.iterations {
+            self.relax();
+        }
+    }
+
+    /// Draw the cloth as a single mesh of triangles.
+    ///
+    /// Cells with cut springs are omitted. The returned **Drawing** may be used to colour the
+    /// mesh.
+    pub fn draw_mesh<'a>(&self, draw: &'a Draw) -> DrawingMesh<'a, f32> {
+        draw.mesh()
+            .indexed(self.positions.iter().cloned(), self.indices())
+    }
+
+    /// Draw the cloth as a single mesh of triangles, textured with the given view.
+    ///
+    /// The texture is stretched across the entire cloth, with the top left node at texture
+    /// coordinates `[0.0, 0.0]` and the bottom right node at `[1.0, 1.0]`.
+    pub fn draw_mesh_textured<'a>(
+        &self,
+        draw: &'a Draw,
+        view: &dyn wgpu::ToTextureView,
+    ) -> DrawingMesh<'a, f32> {
+        let points = self.positions.iter().cloned().zip(self.tex_coords());
+        draw.mesh().indexed_textured(view, points, self.indices())
+    }
+
+    /// The texture coordinates of each node, row by row from the top left.
+    pub fn tex_coords(&self) -> Vec<Point2> {
+        let (cols, rows) = (self.cols, self.rows);
+        (0..cols * rows)
+            .map(|i| {
+                let u = (i % cols) as f32 / (cols - 1) as f32;
+                let v = (i / cols) as f32 / (rows - 1) as f32;
+                pt2(u, v)
+            })
+            .collect()
+    }
+
+    /// The indices of the triangles describing the intact cloth, each trio indexing the nodes
+    /// row by row from the top left.
+    ///
+    /// Each cell is split into two triangles, each of which is present while both of its springs
+    /// along the edges of the cell are intact.
+    pub fn indices(&self) -> Vec<usize> {
+        let cols = self.cols;
+        let mut indices = vec![];
+        for row in 0..self.rows - 1 {
+            for col in 0..cols - 1 {
+                let tl = row * cols + col;
+                let (tr, bl, br) = (tl + 1, tl + cols, tl + cols + 1);
+                if self.right[tl] && self.down[tl] {
+                    indices.extend_from_slice(&[tl, tr, bl]);
+                }
+                if self.down[tr] && self.right[bl] {
+                    indices.extend_from_slice(&[tr, br, bl]);
+                }
+            }
+        }
+        indices
+    }
+
+    // The spring between the given nodes, if they are adjacent.
+    fn spring(&self, a: (usize, usize), b: (usize, usize)) -> Option<Spring> {
+        let (a, b) = (self.index(a.0, a.1), self.index(b.0, b.1));
+        let (a, b) = (a.min(b), a.max(b));
+        if b == a + 1 && b % self.cols != 0 {
+            Some(Spring::Right(a))
+        } else if b == a + self.cols {
+            Some(Spring::Down(a))
+        } else {
+            None
+        }
+    }
+
+    fn index(&self, col: usize, row: usize) -> usize {
+        assert!(
+            col < self.cols && row < self.rows,
+            "no cloth node at column {} and row {}",
+            col,
+            row
+        );
+        row * self.cols + col
+    }
+
+    // Discard the `z` component of 2D simulations.
+    fn constrain(&self, mut v: Vector3) -> Vector3 {
+        if !self.three_d {
+            v.z = 0.0;
+        }
+        v
+    }
+
+    // Move each pair of connected nodes toward the rest length of their spring.
+    fn relax(&mut self) {
+        let cols = self.cols;
+        for i in 0..self.positions.len() {
+            if self.right[i] {
+                self.relax_spring(i, i + 1);
+            }
+            if self.down[i] {
+                self.relax_spring(i, i + cols);
+            }
+        }
+    }
+
+    fn relax_spring(&mut self, a: usize, b: usize) {
+        let (pinned_a, pinned_b) = (self.pinned[a], self.pinned[b]);
+        if pinned_a && pinned_b {
+            return;
+        }
+        let delta = self.positions[b] - self.positions[a];
+        let distance = delta.magnitude();
+        if distance == 0.0 {
+            return;
+        }
+        let correction = delta * ((distance - self.spacing) / distance);
+        match (pinned_a, pinned_b) {
+            (true, _) => self.positions[b] -= correction,
+            (_, true) => self.positions[a] += correction,
+            _ => {
+                self.positions[a] += correction * 0.5;
+                self.positions[b] -= correction * 0.5;
+            }
+        }
+    }
+
+    // The approximate surface normal at each node, via the differences between its neighbours.
+    //
+    // The normal is zero where the neighbours are degenerate, e.g. where the cloth is folded flat.
+    fn normals(&self) -> Vec<Vector3> {
+        let (cols, rows) = (self.cols, self.rows);
+        (0..cols * rows)
+            .map(|i| {
+                let (col, row) = (i % cols, i / cols);
+                let left = self.positions[row * cols + col.saturating_sub(1)];
+                let right = self.positions[row * cols + (col + 1).min(cols - 1)];
+                let up = self.positions[row.saturating_sub(1) * cols + col];
+                let down = self.positions[(row + 1).min(rows - 1) * cols + col];
+                normalize_or_zero((right - left).cross(up - down))
+            })
+            .collect()
+    }
+}
+
+// Normalize the given vector, or produce a zero vector if it has no length.
+fn normalize_or_zero(v: Vector3) -> Vector3 {
+    match v.magnitude2() > 0.0 {
+        true => v.normalize(),
+        false => vec3(0.0, 0.0, 0.0),
+    }
+}
@@ -4,9 +4,13 @@
 //! See the [**named**](./named/index.html) module for a set of provided color constants.
 
 pub mod conv;
+pub mod oklab;
+pub mod swatch;
 
 pub use self::conv::IntoLinSrgba;
 pub use self::named::*;
+pub use self::oklab::{oklch_lerp, Oklab, Oklch};
+pub use self::swatch::Palette;
 #[doc(inline)]
 pub use palette::*;
 
@@ -112,6 +116,18 @@ where
     LinSrgba::new(r, g, b, a)
 }
 
+/// A short-hand constructor for `Oklab::new`.
+pub fn oklab(l: f32, a: f32, b: f32) -> Oklab {
+    Oklab::new(l, a, b)
+}
+
+/// A short-hand constructor for `Oklch::new`.
+///
+/// The given hue is in degrees.
+pub fn oklch(l: f32, chroma: f32, hue_degrees: f32) -> Oklch {
+    Oklch::new(l, chroma, hue_degrees)
+}
+
 /// Create a new color from a hexadecimal int literal
 #[inline]
 pub fn rgb_u32(c: u32) -> Rgb<u8> {
@@ -0,0 +1,149 @@
+//! The [Oklab](https://bottosson.github.io/posts/oklab/) perceptual color space and its polar
+//! form, Oklch.
+//!
+//! Oklab improves upon CIELAB's perceptual uniformity, particularly for blues, making it well
+//! suited to generating gradients and palettes where equal steps should appear equally different.
+
+use crate::color::{Component, IntoLinSrgba, LinSrgb, LinSrgba};
+use crate::math::num_traits::Float;
+
+/// A color within the Oklab color space.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Oklab {
+    /// The perceived lightness in the range `0.0` (black) to `1.0` (white).
+    pub l: f32,
+    /// The green to red axis, roughly within the range `-0.4..=0.4`.
+    pub a: f32,
+    /// The blue to yellow axis, roughly within the range `-0.4..=0.4`.
+    pub b: f32,
+}
+
+/// A color within the Oklab color space, described by its lightness, chroma and hue.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Oklch {
+    /// The perceived lightness in the range `0.0` (black) to `1.0` (white).
+    pub l: f32,
+    /// The colorfulness, where `0.0` is gray. Colors within the sRGB gamut have a chroma of at most
+    /// roughly `0.37`.
+    pub chroma: f32,
+    /// The hue angle in degrees.
+    pub hue: f32,
+}
+
+impl Oklab {
+    /// Create a new Oklab color.
+    pub fn new(l: f32, a: f32, b: f32) -> Self {
+        Oklab { l, a, b }
+    }
+
+    /// Convert a linear sRGB color to Oklab.
+    pub fn from_lin_srgb(rgb: LinSrgb) -> Self {
+        let (r, g, b) = (rgb.red, rgb.green, rgb.blue);
+        let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+        let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+        let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+        Oklab {
+            l: 0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+            a: 1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+            b: 0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+        }
+    }
+
+    /// Convert to linear sRGB.
+    ///
+    /// Colors outside of the sRGB gamut produce components outside of the range `0.0..=1.0`.
+    pub fn to_lin_srgb(&self) -> LinSrgb {
+        let Oklab { l, a, b } = *self;
+        let l_ = l + 0.3963377774 * a + 0.2158037573 * b;
+        let m_ = l - 0.1055613458 * a - 0.0638541728 * b;
+        let s_ = l - 0.0894841775 * a - 1.2914855480 * b;
+        let (l, m, s) = (l_ * l_ * l_, m_ * m_ * m_, s_ * s_ * s_);
+        LinSrgb::new(
+            4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s,
+            -1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s,
+            -0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s,
+        )
+    }
+}
+
+impl Oklch {
+    /// Create a new Oklch color with the given hue in degrees.
+    pub fn new(l: f32, chroma: f32, hue_degrees: f32) -> Self {
+        Oklch {
+            l,
+            chroma,
+            hue: hue_degrees,
+        }
+    }
+
+    /// Convert a linear sRGB color to Oklch.
+    pub fn from_lin_srgb(rgb: LinSrgb) -> Self {
+        Oklab::from_lin_srgb(rgb).into()
+    }
+
+    /// Convert to linear sRGB.
+    ///
+    /// Colors outside of the sRGB gamut produce components outside of the range `0.0..=1.0`.
+    pub fn to_lin_srgb(&self) -> LinSrgb {
+        Oklab::from(*self).to_lin_srgb()
+    }
+
+    /// The same color with its hue rotated by the given number of degrees.
+    pub fn shift_hue(self, degrees: f32) -> Self {
+        let hue = (self.hue + degrees).rem_euclid(360.0);
+        Oklch { hue, ..self }
+    }
+}
+
+impl From<Oklch> for Oklab {
+    fn from(lch: Oklch) -> Self {
+        let (sin, cos) = lch.hue.to_radians().sin_cos();
+        Oklab::new(lch.l, lch.chroma * cos, lch.chroma * sin)
+    }
+}
+
+impl From<Oklab> for Oklch {
+    fn from(lab: Oklab) -> Self {
+        let chroma = (lab.a * lab.a + lab.b * lab.b).sqrt();
+        let hue = lab.b.atan2(lab.a).to_degrees().rem_euclid(360.0);
+        Oklch::new(lab.l, chroma, hue)
+    }
+}
+
+impl<S> IntoLinSrgba<S> for Oklab
+where
+    S: Component + Float,
+{
+    fn into_lin_srgba(self) -> LinSrgba<S> {
+        // Clamp to the sRGB gamut so that out of gamut colors do not produce invalid components.
+        let rgb = self.to_lin_srgb();
+        let clamp = |c: f32| c.max(0.0).min(1.0);
+        LinSrgb::new(clamp(rgb.red), clamp(rgb.green), clamp(rgb.blue)).into_lin_srgba()
+    }
+}
+
+impl<S> IntoLinSrgba<S> for Oklch
+where
+    S: Component + Float,
+{
+    fn into_lin_srgba(self) -> LinSrgba<S> {
+        Oklab::from(self).into_lin_srgba()
+    }
+}
+
+/// Interpolate between two Oklch colors, where `t` is in the range `0.0..=1.0`.
+///
+/// The hue is interpolated along the shorter arc between the two hues. If one of the colors is
+/// gray, i.e. has a chroma of `0.0`, the hue of the other is used throughout.
+pub fn oklch_lerp(a: Oklch, b: Oklch, t: f32) -> Oklch {
+    let lerp = |a: f32, b: f32| a + (b - a) * t;
+    let hue = if a.chroma == 0.0 {
+        b.hue
+    } else if b.chroma == 0.0 {
+        a.hue
+    } else {
+        let delta = (b.hue - a.hue + 180.0).rem_euclid(360.0) - 180.0;
+        (a.hue + delta * t).rem_euclid(360.0)
+    };
+    Oklch::new(lerp(a.l, b.l), lerp(a.chroma, b.chroma), hue)
+}
@@ -0,0 +1,350 @@
+//! Items related to the **Palette** type, a named, indexed collection of colors.
+
+use crate::color::{self, IntoLinSrgba, LinSrgba, Oklch};
+use std::collections::HashMap;
+use std::ops::Index;
+
+/// A named, indexed collection of colors.
+///
+/// Colors may be accessed by index, e.g. `palette[3]`, or by name, e.g. `palette["primary"]`. Both
+/// forms of indexing panic if the color does not exist. See `get` and `get_named` for
+/// non-panicking alternatives.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Palette {
+    colors: Vec<LinSrgba>,
+    names: HashMap<String, usize>,
+}
+
+/// Errors that might occur while loading a **Palette**.
+#[derive(Debug)]
+pub enum Error {
+    /// A string could not be parsed as a hex color.
+    InvalidHex(String),
+    /// Some error occurred while reading the palette.
+    Io(std::io::Error),
+    /// The palette could not be fetched from the given URL.
+    #[cfg(feature = "lospec")]
+    Fetch(String),
+}
+
+impl Palette {
+    /// An empty palette.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a palette from the given colors.
+    pub fn from_colors<I>(colors: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: IntoLinSrgba<f32>,
+    {
+        let colors = colors.into_iter().map(|c| c.into_lin_srgba()).collect();
+        let names = HashMap::new();
+        Palette { colors, names }
+    }
+
+    /// Create a palette from the given sRGB hex strings, e.g. `["#ff8800", "3af", "#00000080"]`.
+    ///
+    /// The leading `#` is optional. Both the 3 and 6 digit forms are supported, along with an
+    /// optional alpha component as the 4th or last two digits respectively.
+    pub fn from_hex_array(hexes: &[&str]) -> Result<Self, Error> {
+        let colors = hexes
+            .iter()
+            .map(|hex| parse_hex(hex))
+            .collect::<Result<Vec<_>, _>>()?;
+        let names = HashMap::new();
+        Ok(Palette { colors, names })
+    }
+
+    /// Create a palette from the contents of a Lospec `.hex` file, where each line contains a
+    /// single hex color. Empty lines are skipped.
+    pub fn from_lospec_hex(text: &str) -> Result<Self, Error> {
+        let hexes: Vec<_> = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect();
+        Self::from_hex_array(&hexes)
+    }
+
+    /// Fetch a palette from [Lospec](https://lospec.com/palette-list).
+    ///
+    /// The URL may refer to either the palette's page, e.g.
+    /// `https://lospec.com/palette-list/sweetie-16`, or its `.hex` file.
+    ///
+    /// This blocks the current thread until the request completes.
+    #[cfg(feature = "lospec")]
+    pub fn from_lospec_url(url: &str) -> Result<Self, Error> {
+        let url = url.trim_end_matches('/');
+        let url = if url.ends_with(".hex") {
+            url.to_string()
+        } else {
+            format!("{}.hex", url)
+        };
+        let response = ureq::get(&url).call();
+        if let Some(err) = response.synthetic_error() {
+            return Err(Error::Fetch(err.to_string()));
+        }
+        if !response.ok() {
+            let msg = format!("{} responded with status {}", url, response.status());
+            return Err(Error::Fetch(msg));
+        }
+        let text = response.into_string()?;
+        Self::from_lospec_hex(&text)
+    }
+
+    /// The base color followed by the color on the opposite side of the hue wheel.
+    pub fn complementary(base: Oklch) -> Self {
+        Self::from_colors(vec![base, base.shift_hue(180.0)])
+    }
+
+    /// `count` colors with hues spread evenly across `spread_degrees`, centered on the base hue.
+    pub fn analogous(base: Oklch, count: usize, spread_degrees: f32) -> Self {
+        let colors = (0..count).map(|i| {
+            let offset = if count > 1 {
+                spread_degrees * (i as f32 / (count - 1) as f32 - 0.5)
+            } else {
+                0.0
+            };
+            base.shift_hue(offset)
+        });
+        Self::from_colors(colors)
+    }
+
+    /// `count` colors of the base hue and chroma, decreasing in lightness from the base towards
+    /// black.
+    pub fn shades(base: Oklch, count: usize) -> Self {
+        let colors = (0..count).map(|i| {
+            let l = base.l * (1.0 - i as f32 / count as f32);
+            Oklch { l, ..base }
+        });
+        Self::from_colors(colors)
+    }
+
+    /// The number of colors in the palette.
+    pub fn len(&self) -> usize {
+        self.colors.len()
+    }
+
+    /// Whether or not the palette contains no colors.
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+
+    /// The colors of the palette in order.
+    pub fn colors(&self) -> &[LinSrgba] {
+        &self.colors
+    }
+
+    /// Iterate over the colors of the palette in order.
+    pub fn iter(&self) -> std::slice::Iter<LinSrgba> {
+        self.colors.iter()
+    }
+
+    /// The color at the given index.
+    pub fn get(&self, index: usize) -> Option<LinSrgba> {
+        self.colors.get(index).cloned()
+    }
+
+    /// The color with the given name.
+    pub fn get_named(&self, name: &str) -> Option<LinSrgba> {
+        self.names.get(name).and_then(|&ix| self.get(ix))
+    }
+
+    /// The name of the color at the given index, if it has one.
+    pub fn name(&self, index: usize) -> Option<&str> {
+        self.names
+            .iter()
+            .find(|&(_, &ix)| ix == index)
+            .map(|(name, _)| &name[..])
+    }
+
+    /// Append the given color and return its index.
+    pub fn push<C>(&mut self, color: C) -> usize
+    where
+        C: IntoLinSrgba<f32>,
+    {
+        self.colors.push(color.into_lin_srgba());
+        self.colors.len() - 1
+    }
+
+    /// Append the given color with the given name and return its index.
+    ///
+    /// If the name is already in use, it will refer to the new color.
+    pub fn push_named<C>(&mut self, name: &str, color: C) -> usize
+    where
+        C: IntoLinSrgba<f32>,
+    {
+        let index = self.push(color);
+        self.names.insert(name.to_string(), index);
+        index
+    }
+
+    /// Name the color at the given index.
+    ///
+    /// **Panics** if the index is out of bounds.
+    pub fn set_name(&mut self, name: &str, index: usize) {
+        assert!(index < self.colors.len(), "no color at index {}", index);
+        self.names.insert(name.to_string(), index);
+    }
+
+    /// Builder style alternative to `set_name`.
+    pub fn with_name(mut self, name: &str, index: usize) -> Self {
+        self.set_name(name, index);
+        self
+    }
+
+    /// The palette as a CSS rule declaring a custom property for each color.
+    ///
+    /// Named colors are declared with their name, while unnamed colors are declared as `color-N`
+    /// where `N` is the index.
+    pub fn to_css(&self) -> String {
+        let mut css = String::from(":root {\n");
+        for (i, &color) in self.colors.iter().enumerate() {
+            let hex = to_hex(color);
+            match self.name(i) {
+                Some(name) => css.push_str(&format!("  --{}: {};\n", name, hex)),
+                None => css.push_str(&format!("  --color-{}: {};\n", i, hex)),
+            }
+        }
+        css.push_str("}\n");
+        css
+    }
+
+    /// The palette encoded as an Adobe Swatch Exchange (`.ase`) file.
+    ///
+    /// Each color is written as an sRGB swatch. Alpha is not supported by the format and is
+    /// discarded.
+    pub fn to_ase(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        bytes.extend_from_slice(b"ASEF");
+        bytes.extend_from_slice(&1u16.to_be_bytes());
+        bytes.extend_from_slice(&0u16.to_be_bytes());
+        bytes.extend_from_slice(&(self.colors.len() as u32).to_be_bytes());
+        for (i, &color) in self.colors.iter().enumerate() {
+            let name = match self.name(i) {
+                Some(name) => name.to_string(),
+                None => to_hex(color),
+            };
+            // The name is UTF-16 with a null terminator.
+            let name: Vec<u16> = name.encode_utf16().chain(Some(0)).collect();
+            let len = 2 + name.len() * 2 + 4 + 3 * 4 + 2;
+            bytes.extend_from_slice(&0x0001u16.to_be_bytes());
+            bytes.extend_from_slice(&(len as u32).to_be_bytes());
+            bytes.extend_from_slice(&(name.len() as u16).to_be_bytes());
+            for unit in name {
+                bytes.extend_from_slice(&unit.to_be_bytes());
+            }
+            bytes.extend_from_slice(b"RGB ");
+            for &c in &[color.red, color.green, color.blue] {
+                let c = encode_srgb(c) as f32 / 255.0;
+                bytes.extend_from_slice(&c.to_bits().to_be_bytes());
+            }
+            // The "normal" color type, as opposed to "global" or "spot".
+            bytes.extend_from_slice(&2u16.to_be_bytes());
+        }
+        bytes
+    }
+}
+
+impl Index<usize> for Palette {
+    type Output = LinSrgba;
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.colors[index]
+    }
+}
+
+impl<'a> Index<&'a str> for Palette {
+    type Output = LinSrgba;
+    fn index(&self, name: &'a str) -> &Self::Output {
+        match self.names.get(name) {
+            Some(&index) => &self.colors[index],
+            None => panic!("no color named \"{}\" in palette", name),
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a Palette {
+    type Item = &'a LinSrgba;
+    type IntoIter = std::slice::Iter<'a, LinSrgba>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.colors.iter()
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl std::error::Error for Error {
+    fn cause(&self) -> Option<&dyn std::error::Error> {
+        match *self {
+            Error::Io(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match *self {
+            Error::InvalidHex(ref s) => write!(f, "invalid hex color: \"{}\"", s),
+            Error::Io(ref e) => std::fmt::Display::fmt(e, f),
+            #[cfg(feature = "lospec")]
+            Error::Fetch(ref s) => write!(f, "failed to fetch palette: {}", s),
+        }
+    }
+}
+
+// Parse an sRGB hex string into a linear color.
+fn parse_hex(hex: &str) -> Result<LinSrgba, Error> {
+    let err = || Error::InvalidHex(hex.to_string());
+    let digits = hex.trim().trim_start_matches('#');
+    if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(err());
+    }
+    let channel = |i: usize, n: usize| -> Result<u8, Error> {
+        let v = u8::from_str_radix(&digits[i * n..i * n + n], 16).map_err(|_| err())?;
+        // Expand short form digits, e.g. `f` to `ff`.
+        Ok(if n == 1 { v * 17 } else { v })
+    };
+    let (n, has_alpha) = match digits.len() {
+        3 => (1, false),
+        4 => (1, true),
+        6 => (2, false),
+        8 => (2, true),
+        _ => return Err(err()),
+    };
+    let a = if has_alpha { channel(3, n)? } else { 255 };
+    let srgba = color::srgba8(channel(0, n)?, channel(1, n)?, channel(2, n)?, a);
+    Ok(srgba.into_lin_srgba())
+}
+
+// Encode a linear color component as an 8-bit sRGB component.
+fn encode_srgb(c: f32) -> u8 {
+    let c = c.max(0.0).min(1.0);
+    let s = if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    };
+    (s * 255.0).round() as u8
+}
+
+// Format a linear color as an sRGB hex string, including alpha only if not fully opaque.
+fn to_hex(color: LinSrgba) -> String {
+    let (r, g, b) = (
+        encode_srgb(color.red),
+        encode_srgb(color.green),
+        encode_srgb(color.blue),
+    );
+    let a = (color.alpha.max(0.0).min(1.0) * 255.0).round() as u8;
+    if a == 255 {
+        format!("#{:02x}{:02x}{:02x}", r, g, b)
+    } else {
+        format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, a)
+    }
+}
@@ -0,0 +1,5 @@
+//! Items that ease the transition to nannou from other creative coding environments.
+//!
+//! - [**p5**](./p5/index.html): free functions named after their p5.js counterparts.
+
+pub mod p5;
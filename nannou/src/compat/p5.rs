@@ -0,0 +1,247 @@
+//! Free functions named after their [p5.js](https://p5js.org/reference/) counterparts.
+//!
+//! The drawing functions operate on an implicit, thread-local **Draw** context. The context is
+//! usually provided by applying the `#[use_p5_compat]` attribute (available via the `p5` feature)
+//! to a sketch's `view` function:
+//!
+//! ```ignore
+//! use nannou::compat::p5::*;
+//! use nannou::prelude::*;
+//!
+//! #[use_p5_compat]
+//! fn view(app: &App, frame: Frame) {
+//!     background(30.0, 30.0, 40.0);
+//!     fill(220.0, 80.0, 80.0, 255.0);
+//!     strokeWeight(4.0);
+//!     ellipse(0.0, 0.0, 100.0, 100.0);
+//! }
+//! ```
+//!
+//! Alternatively, the context may be installed manually via `begin` and removed via `end`.
+//!
+//! As in p5.js, color channels are specified within the range `0.0..=255.0`. Coordinates follow
+//! nannou's conventions however: the origin is at the centre of the window and the *y* axis points
+//! upwards. Functions anchored to the top-left corner of a shape in p5.js, such as `rect`, remain
+//! anchored to the top-left corner, which lies at the greatest *y* of the shape.
+
+#![allow(non_snake_case)]
+
+use crate::color::{self, IntoLinSrgba, LinSrgba};
+use crate::draw::properties::ColorScalar;
+use crate::draw::Draw;
+use crate::geom::pt2;
+use crate::math;
+use crate::noise::{NoiseSource, PerlinNoise};
+use crate::{App, Frame};
+use std::cell::RefCell;
+
+#[cfg(feature = "p5")]
+pub use nannou_p5_macros::use_p5_compat;
+
+// The implicit drawing context along with the current styles.
+struct Context {
+    draw: Draw,
+    fill: Option<LinSrgba<ColorScalar>>,
+    stroke: Option<LinSrgba<ColorScalar>>,
+    stroke_weight: f32,
+}
+
+/// Installs the app's **Draw** as the implicit context for the lifetime of a `view` function.
+///
+/// Created by the code generated by `#[use_p5_compat]`. Upon drop, including upon an early return
+/// from the `view`, the context is removed and its drawing is submitted to the frame. The drawing
+/// is not submitted if the thread is panicking.
+#[doc(hidden)]
+pub struct ViewGuard<'a, 'swap_chain> {
+    app: &'a App,
+    frame: &'a Frame<'swap_chain>,
+    draw: Draw,
+}
+
+thread_local! {
+    static CONTEXT: RefCell<Option<Context>> = RefCell::new(None);
+    static NOISE: PerlinNoise = PerlinNoise::new();
+}
+
+impl<'a, 'swap_chain> ViewGuard<'a, 'swap_chain> {
+    /// Install the app's **Draw** as the implicit context until the guard is dropped.
+    pub fn new(app: &'a App, frame: &'a Frame<'swap_chain>) -> Self {
+        let draw = app.draw();
+        begin(&draw);
+        ViewGuard { app, frame, draw }
+    }
+}
+
+impl<'a, 'swap_chain> Drop for ViewGuard<'a, 'swap_chain> {
+    fn drop(&mut self) {
+        end();
+        if !std::thread::panicking() {
+            self.draw
+                .to_frame(self.app, self.frame)
+                .expect("failed to submit the p5 draw context to the frame");
+        }
+    }
+}
+
+/// Install the given **Draw** as the implicit context for the drawing functions on this thread.
+///
+/// The styles are reset to the p5.js defaults: a white fill, a black stroke and a stroke weight of
+/// `1.0`.
+pub fn begin(draw: &Draw) {
+    let context = Context {
+        draw: draw.clone(),
+        fill: Some(color::WHITE.into_lin_srgba()),
+        stroke: Some(color::BLACK.into_lin_srgba()),
+        stroke_weight: 1.0,
+    };
+    CONTEXT.with(|ctx| *ctx.borrow_mut() = Some(context));
+}
+
+/// Remove the implicit context installed via `begin`, returning its **Draw**.
+pub fn end() -> Option<Draw> {
+    CONTEXT.with(|ctx| ctx.borrow_mut().take().map(|ctx| ctx.draw))
+}
+
+/// The **Draw** of the implicit context, useful for mixing nannou's API with the p5.js functions.
+///
+/// **Panics** if no context has been installed.
+pub fn draw() -> Draw {
+    with_context(|ctx| ctx.draw.clone())
+}
+
+// Drawing.
+
+/// Clear the background with the given color.
+pub fn background(r: f32, g: f32, b: f32) {
+    with_context(|ctx| {
+        ctx.draw.background().color(rgba(r, g, b, 255.0));
+    })
+}
+
+/// Set the color used to fill shapes.
+pub fn fill(r: f32, g: f32, b: f32, a: f32) {
+    with_context(|ctx| ctx.fill = Some(rgba(r, g, b, a)))
+}
+
+/// Disable filling shapes.
+pub fn noFill() {
+    with_context(|ctx| ctx.fill = None)
+}
+
+/// Set the color used to draw lines and the outlines of shapes.
+pub fn stroke(r: f32, g: f32, b: f32, a: f32) {
+    with_context(|ctx| ctx.stroke = Some(rgba(r, g, b, a)))
+}
+
+/// Disable drawing lines and the outlines of shapes.
+pub fn noStroke() {
+    with_context(|ctx| ctx.stroke = None)
+}
+
+/// Set the width of lines and the outlines of shapes.
+pub fn strokeWeight(weight: f32) {
+    with_context(|ctx| ctx.stroke_weight = weight)
+}
+
+/// Draw an ellipse centred on `(x, y)` with the given width and height.
+pub fn ellipse(x: f32, y: f32, w: f32, h: f32) {
+    with_context(|ctx| {
+        if ctx.fill.is_none() && ctx.stroke.is_none() {
+            return;
+        }
+        let drawing = ctx.draw.ellipse().x_y(x, y).w_h(w, h);
+        let drawing = match ctx.fill {
+            Some(color) => drawing.color(color),
+            None => drawing.no_fill(),
+        };
+        if let Some(color) = ctx.stroke {
+            drawing.stroke(color).stroke_weight(ctx.stroke_weight);
+        }
+    })
+}
+
+/// Draw a rectangle with the given width and height.
+///
+/// As in p5.js, `(x, y)` describes the top-left corner of the rectangle, with the rectangle
+/// extending downwards along the *y* axis from `y`.
+pub fn rect(x: f32, y: f32, w: f32, h: f32) {
+    with_context(|ctx| {
+        if ctx.fill.is_none() && ctx.stroke.is_none() {
+            return;
+        }
+        let drawing = ctx.draw.rect().x_y(x + w * 0.5, y - h * 0.5).w_h(w, h);
+        let drawing = match ctx.fill {
+            Some(color) => drawing.color(color),
+            None => drawing.no_fill(),
+        };
+        if let Some(color) = ctx.stroke {
+            drawing.stroke(color).stroke_weight(ctx.stroke_weight);
+        }
+    })
+}
+
+/// Draw a line from `(x1, y1)` to `(x2, y2)` with the current stroke.
+pub fn line(x1: f32, y1: f32, x2: f32, y2: f32) {
+    with_context(|ctx| {
+        if let Some(color) = ctx.stroke {
+            ctx.draw
+                .line()
+                .start(pt2(x1, y1))
+                .end(pt2(x2, y2))
+                .weight(ctx.stroke_weight)
+                .color(color);
+        }
+    })
+}
+
+/// Draw the given text centred on `(x, y)` with the current fill.
+pub fn text(s: &str, x: f32, y: f32) {
+    with_context(|ctx| {
+        if let Some(color) = ctx.fill {
+            ctx.draw.text(s).x_y(x, y).color(color);
+        }
+    })
+}
+
+// Math.
+
+/// Perlin noise at the given point, within the range `0.0..=1.0`.
+///
+/// Unlike the drawing functions, this does not require an implicit context.
+pub fn noise(x: f32, y: f32, z: f32) -> f32 {
+    NOISE.with(|noise| (noise.get_3d(x, y, z) * 0.5 + 0.5).max(0.0).min(1.0))
+}
+
+/// Map the given value from the input range to the output range.
+pub fn map(value: f32, lo1: f32, hi1: f32, lo2: f32, hi2: f32) -> f32 {
+    math::map_range(value, lo1, hi1, lo2, hi2)
+}
+
+/// Constrain the given value to the range `lo..=hi`.
+pub fn constrain(value: f32, lo: f32, hi: f32) -> f32 {
+    math::clamp(value, lo, hi)
+}
+
+/// The distance between `(x1, y1)` and `(x2, y2)`.
+pub fn dist(x1: f32, y1: f32, x2: f32, y2: f32) -> f32 {
+    (pt2(x2, y2) - pt2(x1, y1)).magnitude()
+}
+
+// Call the given function with the implicit context.
+fn with_context<F, T>(f: F) -> T
+where
+    F: FnOnce(&mut Context) -> T,
+{
+    CONTEXT.with(|ctx| {
+        let mut ctx = ctx.borrow_mut();
+        let ctx = ctx.as_mut().expect(
+            "no implicit p5 draw context - apply `#[use_p5_compat]` to `view` or call `p5::begin`",
+        );
+        f(ctx)
+    })
+}
+
+// Convert color channels within the range `0.0..=255.0` to a linear color.
+fn rgba(r: f32, g: f32, b: f32, a: f32) -> LinSrgba<ColorScalar> {
+    color::Srgba::new(r / 255.0, g / 255.0, b / 255.0, a / 255.0).into_lin_srgba()
+}
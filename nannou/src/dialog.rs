@@ -0,0 +1,64 @@
+//! Native file dialogs, provided by the [rfd](https://docs.rs/rfd) crate.
+//!
+//! Requires the `dialog` feature. Dialogs are usually opened via `App::pick_file`,
+//! `App::pick_directory` and `App::save_file_dialog`. All dialogs are synchronous, blocking the
+//! calling thread and in turn the app's event loop until the user closes the dialog.
+
+use std::path::PathBuf;
+
+pub use rfd;
+
+/// Restricts the files that may be chosen within a dialog to those with the given extensions.
+///
+/// ```ignore
+/// let images = FileFilter::new("Images", &["png", "jpg", "jpeg"]);
+/// let path = app.pick_file("Load image", &[images]);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FileFilter<'a> {
+    /// The name of the filter displayed within the dialog, e.g. `"Images"`.
+    pub label: &'a str,
+    /// The extensions of the files matched by the filter without the leading `.`, e.g. `"png"`.
+    pub extensions: &'a [&'a str],
+}
+
+impl<'a> FileFilter<'a> {
+    /// A filter matching files with the given extensions.
+    pub fn new(label: &'a str, extensions: &'a [&'a str]) -> Self {
+        FileFilter { label, extensions }
+    }
+}
+
+/// Open a dialog for choosing an existing file, blocking until the dialog is closed.
+///
+/// Returns `None` if the dialog was cancelled.
+pub fn pick_file(title: &str, filters: &[FileFilter]) -> Option<PathBuf> {
+    dialog(title, filters).pick_file()
+}
+
+/// Open a dialog for choosing an existing directory, blocking until the dialog is closed.
+///
+/// Returns `None` if the dialog was cancelled.
+pub fn pick_directory(title: &str) -> Option<PathBuf> {
+    dialog(title, &[]).pick_folder()
+}
+
+/// Open a dialog for choosing the path at which a file should be saved, blocking until the
+/// dialog is closed.
+///
+/// The file name field of the dialog is initialised with `default_name`. Returns `None` if the
+/// dialog was cancelled.
+pub fn save_file(title: &str, default_name: &str, filters: &[FileFilter]) -> Option<PathBuf> {
+    dialog(title, filters)
+        .set_file_name(default_name)
+        .save_file()
+}
+
+// A dialog with the given title and filters.
+fn dialog(title: &str, filters: &[FileFilter]) -> rfd::FileDialog {
+    filters
+        .iter()
+        .fold(rfd::FileDialog::new().set_title(title), |dialog, filter| {
+            dialog.add_filter(filter.label, filter.extensions)
+        })
+}
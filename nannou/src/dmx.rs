@@ -0,0 +1,398 @@
+//! DMX512 lighting control output via [Art-Net](https://art-net.org.uk/).
+//!
+//! An **ArtNetSender** transmits universes of 512 channels to an Art-Net node over UDP, while a
+//! **DmxAnimator** stores the state of a single universe and only transmits it upon change,
+//! leaving the app free to set channels every frame:
+//!
+//! ```no_run
+//! use nannou::dmx::{ArtNetSender, DmxAnimator};
+//!
+//! let sender = ArtNetSender::new("2.0.0.10").unwrap();
+//! let mut animator = DmxAnimator::from_patch_file("patch.csv").unwrap();
+//! if let Some(fixture) = animator.fixture("wash-left") {
+//!     let channel = fixture.channel;
+//!     animator.set_rgb(channel, 255, 120, 0);
+//! }
+//! animator.flush(&sender).unwrap();
+//! ```
+//!
+//! ## Patch files
+//!
+//! A patch is a CSV file describing a fixture per line in the form
+//! `channel, fixture-type, fixture-name`, where `channel` is the fixture's 1-based start address.
+//! Blank lines and lines beginning with `#` are ignored, as is a leading header row.
+//!
+//! ```text
+//! channel,fixture-type,fixture-name
+//! 1,rgb-par,wash-left
+//! 4,rgb-par,wash-right
+//! ```
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket};
+use std::path::Path;
+use std::sync::atomic::{self, AtomicU8};
+
+/// The UDP port on which Art-Net nodes listen.
+pub const ARTNET_PORT: u16 = 6454;
+
+/// The number of channels within a DMX512 universe.
+pub const UNIVERSE_SIZE: usize = 512;
+
+/// The Art-Net protocol revision implemented by the sender.
+pub const PROTOCOL_VERSION: u16 = 14;
+
+/// The size in bytes of the header preceding the channel data within an ArtDmx packet.
+pub const HEADER_SIZE: usize = 18;
+
+// The identifier beginning every Art-Net packet.
+const ID: &[u8; 8] = b"Art-Net\0";
+
+// The OpCode of an ArtDmx packet.
+const OP_DMX: u16 = 0x5000;
+
+/// Sends DMX512 universes to an Art-Net node over UDP.
+#[derive(Debug)]
+pub struct ArtNetSender {
+    socket: UdpSocket,
+    target: SocketAddr,
+    sequence: AtomicU8,
+}
+
+/// Stores the state of a single DMX512 universe, only sending it when changed.
+#[derive(Clone)]
+pub struct DmxAnimator {
+    universe: u16,
+    data: [u8; UNIVERSE_SIZE],
+    changed: bool,
+    fixtures: Vec<Fixture>,
+}
+
+/// A fixture described by a patch file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fixture {
+    /// The 1-based DMX address of the fixture's first channel.
+    pub channel: u16,
+    /// The type of the fixture, e.g. `rgb-par`.
+    pub kind: String,
+    /// The unique name of the fixture.
+    pub name: String,
+}
+
+/// Errors that might occur while loading a patch file.
+#[derive(Debug)]
+pub enum PatchError {
+    /// The patch file could not be read.
+    Io(io::Error),
+    /// A line of the patch could not be parsed.
+    InvalidLine {
+        /// The 1-based number of the line.
+        line: usize,
+        /// A description of the problem.
+        reason: String,
+    },
+}
+
+impl ArtNetSender {
+    /// Create a sender targeting the Art-Net node at the given address.
+    ///
+    /// The address may optionally specify a port, e.g. `"2.0.0.10:6454"`, otherwise the standard
+    /// `ARTNET_PORT` is used. Broadcast addresses such as `"2.255.255.255"` are supported.
+    pub fn new(target_ip: &str) -> io::Result<Self> {
+        let target = parse_target(target_ip)?;
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?;
+        socket.set_broadcast(true)?;
+        socket.set_nonblocking(true)?;
+        Ok(ArtNetSender {
+            socket,
+            target,
+            sequence: AtomicU8::new(0),
+        })
+    }
+
+    /// The address to which packets are sent.
+    pub fn target(&self) -> SocketAddr {
+        self.target
+    }
+
+    /// Send the given channel data for the given universe without blocking.
+    ///
+    /// The universe is the 15-bit *Port-Address* of the node's output, combining the Art-Net
+    /// *Net* and *Sub-Net* within the upper bits. Returns an error of kind `InvalidInput` if the
+    /// universe exceeds `0x7FFF`.
+    pub fn send_universe(&self, universe: u16, data: &[u8; UNIVERSE_SIZE]) -> io::Result<()> {
+        if universe > 0x7FFF {
+            let msg = format!("universe {} exceeds the maximum of 32767", universe);
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, msg));
+        }
+        let sequence = self.next_sequence();
+        let packet = dmx_packet(sequence, universe, data);
+        self.socket.send_to(&packet, self.target)?;
+        Ok(())
+    }
+
+    // Sequence numbers cycle through `1..=255`, as `0` disables re-ordering on the receiver.
+    fn next_sequence(&self) -> u8 {
+        let mut prev = self.sequence.load(atomic::Ordering::Relaxed);
+        loop {
+            let next = match prev {
+                255 => 1,
+                n => n + 1,
+            };
+            let ordering = atomic::Ordering::Relaxed;
+            match self
+                .sequence
+                .compare_exchange_weak(prev, next, ordering, ordering)
+            {
+                Ok(_) => return next,
+                Err(actual) => prev = actual,
+            }
+        }
+    }
+}
+
+impl DmxAnimator {
+    /// A new animator for universe `0` with all channels set to `0`.
+    pub fn new() -> Self {
+        DmxAnimator {
+            universe: 0,
+            data: [0; UNIVERSE_SIZE],
+            // Ensure the initial state is sent on the first `flush`.
+            changed: true,
+            fixtures: vec![],
+        }
+    }
+
+    /// Load an animator with the fixtures described by the CSV patch file at the given path.
+    ///
+    /// See the [module documentation](./index.html#patch-files) for the format.
+    pub fn from_patch_file<P>(path: P) -> Result<Self, PatchError>
+    where
+        P: AsRef<Path>,
+    {
+        let patch = fs::read_to_string(path)?;
+        Self::from_patch_str(&patch)
+    }
+
+    /// Load an animator with the fixtures described by the given CSV patch.
+    pub fn from_patch_str(patch: &str) -> Result<Self, PatchError> {
+        let mut animator = Self::new();
+        animator.fixtures = parse_patch(patch)?;
+        Ok(animator)
+    }
+
+    /// The universe to which the state is sent.
+    pub fn universe(&self) -> u16 {
+        self.universe
+    }
+
+    /// Specify the universe to which the state is sent.
+    pub fn set_universe(&mut self, universe: u16) {
+        if self.universe != universe {
+            self.universe = universe;
+            self.changed = true;
+        }
+    }
+
+    /// The fixtures loaded from the patch.
+    pub fn fixtures(&self) -> &[Fixture] {
+        &self.fixtures
+    }
+
+    /// The fixture with the given name.
+    pub fn fixture(&self, name: &str) -> Option<&Fixture> {
+        self.fixtures.iter().find(|f| f.name == name)
+    }
+
+    /// The value of the channel at the given 1-based DMX address.
+    ///
+    /// Returns `0` for addresses outside of `1..=512`.
+    pub fn channel(&self, ch: u16) -> u8 {
+        match channel_index(ch) {
+            Some(ix) => self.data[ix],
+            None => 0,
+        }
+    }
+
+    /// The state of all channels within the universe.
+    pub fn data(&self) -> &[u8; UNIVERSE_SIZE] {
+        &self.data
+    }
+
+    /// Whether or not the state has changed since the last `flush`.
+    pub fn is_changed(&self) -> bool {
+        self.changed
+    }
+
+    /// Set the channel at the given 1-based DMX address.
+    ///
+    /// Addresses outside of `1..=512` are ignored.
+    pub fn set_channel(&mut self, ch: u16, value: u8) {
+        if let Some(ix) = channel_index(ch) {
+            if self.data[ix] != value {
+                self.data[ix] = value;
+                self.changed = true;
+            }
+        }
+    }
+
+    /// Set the three consecutive channels of an RGB fixture starting at the given 1-based address.
+    pub fn set_rgb(&mut self, fixture_offset: u16, r: u8, g: u8, b: u8) {
+        self.set_channel(fixture_offset, r);
+        self.set_channel(fixture_offset.saturating_add(1), g);
+        self.set_channel(fixture_offset.saturating_add(2), b);
+    }
+
+    /// Set all channels to `0`.
+    pub fn clear(&mut self) {
+        if self.data.iter().any(|&v| v != 0) {
+            self.data = [0; UNIVERSE_SIZE];
+            self.changed = true;
+        }
+    }
+
+    /// Send the state via the given sender if it has changed since the last `flush`.
+    ///
+    /// Returns whether or not the state was sent.
+    pub fn flush(&mut self, sender: &ArtNetSender) -> io::Result<bool> {
+        if !self.changed {
+            return Ok(false);
+        }
+        sender.send_universe(self.universe, &self.data)?;
+        self.changed = false;
+        Ok(true)
+    }
+}
+
+/// Construct an ArtDmx packet carrying the given channel data for the given universe.
+///
+/// A `sequence` of `0` disables re-ordering on the receiving node.
+pub fn dmx_packet(sequence: u8, universe: u16, data: &[u8; UNIVERSE_SIZE]) -> Vec<u8> {
+    let mut packet = Vec::with_capacity(HEADER_SIZE + UNIVERSE_SIZE);
+    packet.extend_from_slice(ID);
+    // The OpCode is little-endian while all other multi-byte fields are big-endian.
+    packet.extend_from_slice(&OP_DMX.to_le_bytes());
+    packet.extend_from_slice(&PROTOCOL_VERSION.to_be_bytes());
+    packet.push(sequence);
+    // The physical input port from which the data originated, informational only.
+    packet.push(0);
+    // The low byte holds the Sub-Net and Universe while the high byte holds the Net.
+    packet.push((universe & 0xFF) as u8);
+    packet.push(((universe >> 8) & 0x7F) as u8);
+    packet.extend_from_slice(&(UNIVERSE_SIZE as u16).to_be_bytes());
+    packet.extend_from_slice(data);
+    packet
+}
+
+/// Parse the fixtures described by the given CSV patch.
+pub fn parse_patch(patch: &str) -> Result<Vec<Fixture>, PatchError> {
+    let mut fixtures: Vec<Fixture> = vec![];
+    let mut first_row = true;
+    for (i, line) in patch.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let invalid = |reason: String| PatchError::InvalidLine {
+            line: i + 1,
+            reason,
+        };
+        let mut fields = line.splitn(3, ',').map(str::trim);
+        let (channel, kind, name) = match (fields.next(), fields.next(), fields.next()) {
+            (Some(c), Some(k), Some(n)) => (c, k, n),
+            _ => {
+                let reason = "expected `channel, fixture-type, fixture-name`".to_string();
+                return Err(invalid(reason));
+            }
+        };
+        let is_header = first_row;
+        first_row = false;
+        let channel: u16 = match channel.parse() {
+            Ok(channel) => channel,
+            Err(_) if is_header => continue,
+            Err(_) => return Err(invalid(format!("invalid channel `{}`", channel))),
+        };
+        if channel_index(channel).is_none() {
+            let reason = format!("channel {} is outside of the range 1..=512", channel);
+            return Err(invalid(reason));
+        }
+        if name.is_empty() {
+            return Err(invalid("missing fixture name".to_string()));
+        }
+        if fixtures.iter().any(|f| f.name == name) {
+            return Err(invalid(format!("duplicate fixture name `{}`", name)));
+        }
+        fixtures.push(Fixture {
+            channel,
+            kind: kind.to_string(),
+            name: name.to_string(),
+        });
+    }
+    Ok(fixtures)
+}
+
+// The index into the universe's data for the given 1-based DMX address.
+fn channel_index(ch: u16) -> Option<usize> {
+    match ch >= 1 && ch as usize <= UNIVERSE_SIZE {
+        true => Some(ch as usize - 1),
+        false => None,
+    }
+}
+
+// Parse the target address, defaulting to the standard Art-Net port.
+fn parse_target(target: &str) -> io::Result<SocketAddr> {
+    if let Ok(addr) = target.parse::<SocketAddr>() {
+        return Ok(addr);
+    }
+    match target.parse::<IpAddr>() {
+        Ok(ip) => Ok(SocketAddr::new(ip, ARTNET_PORT)),
+        Err(_) => {
+            let msg = format!("`{}` is not a valid Art-Net node address", target);
+            Err(io::Error::new(io::ErrorKind::InvalidInput, msg))
+        }
+    }
+}
+
+impl Default for DmxAnimator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for DmxAnimator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DmxAnimator")
+            .field("universe", &self.universe)
+            .field("changed", &self.changed)
+            .field("fixtures", &self.fixtures)
+            .finish()
+    }
+}
+
+impl From<io::Error> for PatchError {
+    fn from(err: io::Error) -> Self {
+        PatchError::Io(err)
+    }
+}
+
+impl std::error::Error for PatchError {
+    fn cause(&self) -> Option<&dyn std::error::Error> {
+        match *self {
+            PatchError::Io(ref err) => Some(err),
+            PatchError::InvalidLine { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PatchError::Io(ref err) => err.fmt(f),
+            PatchError::InvalidLine { line, ref reason } => {
+                write!(f, "invalid patch on line {}: {}", line, reason)
+            }
+        }
+    }
+}
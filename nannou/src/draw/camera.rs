@@ -0,0 +1,265 @@
+//! 3D cameras for use with the **Draw** API.
+//!
+//! See the **Camera3D** type, the `Draw::camera` method and the **ArcballCamera** controller.
+
+use crate::geom::{Point3, Vector2, Vector3};
+use crate::math::{Matrix4, Rad, SquareMatrix};
+use crate::serde_derive::{Deserialize, Serialize};
+use crate::state;
+
+/// The way in which a **Camera3D** projects the scene onto the output.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Projection {
+    /// A perspective projection, where further objects appear smaller.
+    Perspective {
+        /// The vertical field of view in radians.
+        fov: f32,
+        /// The distance from the camera to the near clipping plane.
+        near: f32,
+        /// The distance from the camera to the far clipping plane.
+        far: f32,
+    },
+    /// An orthographic projection, where objects appear the same size regardless of distance.
+    Orthographic {
+        /// The height of the visible volume in world units. The width is determined by the aspect
+        /// ratio of the output.
+        size: f32,
+        /// The distance from the camera to the near clipping plane.
+        near: f32,
+        /// The distance from the camera to the far clipping plane.
+        far: f32,
+    },
+}
+
+/// A camera describing the view and projection used to draw 3D geometry.
+///
+/// Set the camera used by a **Draw** instance via `draw.camera(&camera)`. All primitives drawn
+/// with the resulting **Draw** instance are rendered with the camera's view-projection matrix,
+/// while those drawn with the original instance continue to use the default 2D projection.
+///
+/// ```ignore
+/// let camera = Camera3D::perspective(PI / 3.0, 1.0, 10_000.0).look_at(
+///     pt3(300.0, 200.0, 600.0),
+///     pt3(0.0, 0.0, 0.0),
+///     vec3(0.0, 1.0, 0.0),
+/// );
+/// draw.camera(&camera).box_3d().w_h_d(100.0, 100.0, 100.0);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Camera3D {
+    /// The projection of the camera.
+    pub projection: Projection,
+    /// The view transform of the camera, or `None` for the default view.
+    ///
+    /// By default, a perspective camera looks along the negative *z* axis from a distance at
+    /// which the *z = 0* plane matches the default 2D coordinate space of the output, i.e. one
+    /// unit is one logical pixel. An orthographic camera looks along the negative *z* axis from
+    /// the origin.
+    pub view: Option<Matrix4<f32>>,
+}
+
+/// A controller that orbits a **Camera3D** around a target point in response to mouse drags.
+///
+/// Store the **ArcballCamera** within the app's model, update it via `update` within the `update`
+/// function and draw via `draw.camera(&model.arcball.camera())`.
+///
+/// ```ignore
+/// fn update(app: &App, model: &mut Model, _update: Update) {
+///     model.arcball.update(&app.mouse);
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArcballCamera {
+    /// The camera providing the projection. Its view is replaced by the orbit.
+    pub camera: Camera3D,
+    /// The point around which the camera orbits.
+    pub target: Point3,
+    /// The distance from the target to the camera.
+    pub distance: f32,
+    /// The rotation of the camera around the *y* axis in radians.
+    pub yaw: f32,
+    /// The elevation of the camera above the *xz* plane in radians.
+    pub pitch: f32,
+    /// The rotation in radians per logical pixel of mouse drag.
+    pub sensitivity: f32,
+    last_drag_position: Option<Vector2>,
+}
+
+impl Camera3D {
+    /// The default vertical field of view used by `App::camera_3d`, 60 degrees.
+    pub const DEFAULT_FOV: f32 = std::f32::consts::PI / 3.0;
+    /// The default near clipping distance used by `App::camera_3d`.
+    pub const DEFAULT_NEAR: f32 = 1.0;
+    /// The default far clipping distance used by `App::camera_3d`.
+    pub const DEFAULT_FAR: f32 = 10_000.0;
+
+    /// A camera with a perspective projection.
+    ///
+    /// The `fov` is the vertical field of view in radians.
+    pub fn perspective(fov: f32, near: f32, far: f32) -> Self {
+        let projection = Projection::Perspective { fov, near, far };
+        Camera3D {
+            projection,
+            view: None,
+        }
+    }
+
+    /// A camera with an orthographic projection where the visible volume is `size` units high.
+    ///
+    /// The near and far clipping planes default to `-size` and `size` respectively. Use
+    /// `clip_planes` to change them.
+    pub fn orthographic(size: f32) -> Self {
+        let projection = Projection::Orthographic {
+            size,
+            near: -size,
+            far: size,
+        };
+        Camera3D {
+            projection,
+            view: None,
+        }
+    }
+
+    /// Position the camera at `eye`, looking towards `target` with the given `up` direction.
+    pub fn look_at(mut self, eye: Point3, target: Point3, up: Vector3) -> Self {
+        let eye = cgmath::Point3::new(eye.x, eye.y, eye.z);
+        let target = cgmath::Point3::new(target.x, target.y, target.z);
+        let up = cgmath::Vector3::new(up.x, up.y, up.z);
+        self.view = Some(Matrix4::look_at(eye, target, up));
+        self
+    }
+
+    /// Specify the distances to the near and far clipping planes.
+    pub fn clip_planes(mut self, near: f32, far: f32) -> Self {
+        match self.projection {
+            Projection::Perspective {
+                near: ref mut n,
+                far: ref mut f,
+                ..
+            }
+            | Projection::Orthographic {
+                near: ref mut n,
+                far: ref mut f,
+                ..
+            } => {
+                *n = near;
+                *f = far;
+            }
+        }
+        self
+    }
+
+    /// The view matrix for an output of the given size in logical pixels.
+    pub fn view_matrix(&self, output_size: Vector2) -> Matrix4<f32> {
+        if let Some(view) = self.view {
+            return view;
+        }
+        match self.projection {
+            Projection::Perspective { fov, .. } => {
+                let distance = output_size.y * 0.5 / (fov * 0.5).tan();
+                Matrix4::from_translation(cgmath::Vector3::new(0.0, 0.0, -distance))
+            }
+            Projection::Orthographic { .. } => Matrix4::identity(),
+        }
+    }
+
+    /// The projection matrix for an output of the given size in logical pixels.
+    ///
+    /// Depth is mapped to the range `0.0` to `1.0` as expected by wgpu.
+    pub fn projection_matrix(&self, output_size: Vector2) -> Matrix4<f32> {
+        let aspect = output_size.x / output_size.y.max(std::f32::EPSILON);
+        let proj = match self.projection {
+            Projection::Perspective { fov, near, far } => {
+                cgmath::perspective(Rad(fov), aspect, near, far)
+            }
+            Projection::Orthographic { size, near, far } => {
+                let top = size * 0.5;
+                let right = top * aspect;
+                cgmath::ortho(-right, right, -top, top, near, far)
+            }
+        };
+        // Both projections map z values to the range -1.0 to 1.0. Scale and translate the z axis
+        // so that it is in the range of 0.0 to 1.0.
+        let trans = Matrix4::from_translation(cgmath::Vector3::new(0.0, 0.0, 1.0));
+        let scale = Matrix4::from_nonuniform_scale(1.0, 1.0, 0.5);
+        scale * trans * proj
+    }
+
+    /// The combined view-projection matrix for an output of the given size in logical pixels.
+    ///
+    /// This is the matrix uploaded to the vertex shader by the **draw::Renderer**.
+    pub fn view_projection(&self, output_size: Vector2) -> Matrix4<f32> {
+        self.projection_matrix(output_size) * self.view_matrix(output_size)
+    }
+}
+
+impl ArcballCamera {
+    /// The default rotation in radians per logical pixel of mouse drag.
+    pub const DEFAULT_SENSITIVITY: f32 = 0.01;
+
+    // Keep the pitch just short of the poles where the view would become degenerate.
+    const MAX_PITCH: f32 = std::f32::consts::FRAC_PI_2 - 0.001;
+
+    /// Orbit the given camera around `target` at the given distance.
+    ///
+    /// The camera begins on the positive *z* axis relative to the target, looking towards it.
+    pub fn new(camera: Camera3D, target: Point3, distance: f32) -> Self {
+        ArcballCamera {
+            camera,
+            target,
+            distance,
+            yaw: 0.0,
+            pitch: 0.0,
+            sensitivity: Self::DEFAULT_SENSITIVITY,
+            last_drag_position: None,
+        }
+    }
+
+    /// Orbit the camera by the given mouse drag delta in logical pixels.
+    ///
+    /// Dragging right or up rotates the scene right or up respectively, as though grabbing it.
+    pub fn drag(&mut self, delta: Vector2) {
+        self.yaw -= delta.x * self.sensitivity;
+        let pitch = self.pitch - delta.y * self.sensitivity;
+        self.pitch = pitch.max(-Self::MAX_PITCH).min(Self::MAX_PITCH);
+    }
+
+    /// Move the camera towards the target by the given factor.
+    ///
+    /// Factors greater than `1.0` move the camera closer while factors less than `1.0` move it
+    /// further away.
+    pub fn zoom(&mut self, factor: f32) {
+        if factor > 0.0 {
+            self.distance /= factor;
+        }
+    }
+
+    /// Orbit the camera while the left mouse button is dragged.
+    ///
+    /// This should be called once per update with the app's mouse state, i.e. `&app.mouse`.
+    pub fn update(&mut self, mouse: &state::Mouse) {
+        if mouse.buttons.left().is_down() {
+            let position = mouse.position();
+            if let Some(last) = self.last_drag_position {
+                self.drag(position - last);
+            }
+            self.last_drag_position = Some(position);
+        } else {
+            self.last_drag_position = None;
+        }
+    }
+
+    /// The position of the camera.
+    pub fn eye(&self) -> Point3 {
+        let (sin_yaw, cos_yaw) = self.yaw.sin_cos();
+        let (sin_pitch, cos_pitch) = self.pitch.sin_cos();
+        let direction = Vector3::new(cos_pitch * sin_yaw, sin_pitch, cos_pitch * cos_yaw);
+        self.target + direction * self.distance
+    }
+
+    /// The camera looking at the target from the current position in the orbit.
+    pub fn camera(&self) -> Camera3D {
+        let up = Vector3::new(0.0, 1.0, 0.0);
+        self.camera.look_at(self.eye(), self.target, up)
+    }
+}
@@ -0,0 +1,305 @@
+//! A stateful drawing API modelled after the HTML Canvas 2D context.
+//!
+//! See the **Canvas2D** type for details.
+
+use crate::color::{self, IntoLinSrgba, LinSrgba};
+use crate::draw::properties::ColorScalar;
+use crate::draw::Draw;
+use crate::geom::{self, pt2, vec3, Point2};
+use std::f32::consts::PI;
+
+/// A stateful drawing context that closely mirrors the HTML Canvas 2D API.
+///
+/// The **Canvas2D** is intended to ease the transition for those familiar with sketching in
+/// JavaScript. Rather than building each primitive, paths are described via `begin_path`,
+/// `move_to`, `line_to`, `arc`, etc and then submitted to the inner **Draw** via `fill` or `stroke`
+/// using the current fill and stroke styles.
+///
+/// Note that coordinates follow nannou's conventions rather than those of the HTML canvas. That
+/// is, the origin is at the centre of the window, the *y* axis points upwards and positive angles
+/// describe a counter-clockwise rotation.
+///
+/// ```
+/// # use nannou::prelude::*;
+/// # let draw = nannou::Draw::new();
+/// let mut ctx = draw.canvas_2d();
+/// ctx.fill_style(STEELBLUE);
+/// ctx.fill_rect(-50.0, -50.0, 100.0, 100.0);
+/// ctx.begin_path();
+/// ctx.arc(0.0, 0.0, 80.0, 0.0, PI);
+/// ctx.stroke_style(PLUM);
+/// ctx.line_width(4.0);
+/// ctx.stroke();
+/// ```
+#[derive(Clone, Debug)]
+pub struct Canvas2D {
+    state: State,
+    saved: Vec<State>,
+    subpaths: Vec<Subpath>,
+}
+
+// The portion of the context that is saved and restored via `save` and `restore`.
+#[derive(Clone, Debug)]
+struct State {
+    draw: Draw,
+    fill: LinSrgba<ColorScalar>,
+    stroke: LinSrgba<ColorScalar>,
+    line_width: f32,
+}
+
+#[derive(Clone, Debug)]
+struct Subpath {
+    points: Vec<Point2>,
+    closed: bool,
+}
+
+/// The maximum distance between an arc and the line segments used to approximate it.
+const ARC_TOLERANCE: f32 = 0.1;
+
+impl Canvas2D {
+    /// Begin a new context drawing to the given **Draw**.
+    ///
+    /// The fill and stroke styles default to opaque black and the line width defaults to `1.0`,
+    /// matching the HTML canvas.
+    pub fn new(draw: &Draw) -> Self {
+        let black = color::BLACK.into_lin_srgba();
+        let state = State {
+            draw: draw.clone(),
+            fill: black,
+            stroke: black,
+            line_width: 1.0,
+        };
+        Canvas2D {
+            state,
+            saved: vec![],
+            subpaths: vec![],
+        }
+    }
+
+    /// The **Draw** instance to which the context submits drawings, including the current
+    /// transform.
+    pub fn draw(&self) -> &Draw {
+        &self.state.draw
+    }
+
+    // Styles.
+
+    /// Set the color used by `fill` and `fill_rect`.
+    pub fn fill_style<C>(&mut self, color: C)
+    where
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        self.state.fill = color.into_lin_srgba();
+    }
+
+    /// Set the color used by `stroke` and `stroke_rect`.
+    pub fn stroke_style<C>(&mut self, color: C)
+    where
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        self.state.stroke = color.into_lin_srgba();
+    }
+
+    /// Set the width of lines drawn via `stroke` and `stroke_rect`.
+    pub fn line_width(&mut self, width: f32) {
+        self.state.line_width = width;
+    }
+
+    // State.
+
+    /// Push the current styles and transform onto a stack.
+    pub fn save(&mut self) {
+        self.saved.push(self.state.clone());
+    }
+
+    /// Restore the styles and transform most recently saved via `save`.
+    ///
+    /// Does nothing if there are no saved states. The current path is unaffected.
+    pub fn restore(&mut self) {
+        if let Some(state) = self.saved.pop() {
+            self.state = state;
+        }
+    }
+
+    // Transforms.
+
+    /// Translate the origin of all following drawings.
+    pub fn translate(&mut self, x: f32, y: f32) {
+        self.state.draw = self.state.draw.translate(vec3(x, y, 0.0));
+    }
+
+    /// Rotate all following drawings by the given angle in radians.
+    pub fn rotate(&mut self, radians: f32) {
+        self.state.draw = self.state.draw.rotate(radians);
+    }
+
+    /// Scale all following drawings along each axis.
+    pub fn scale(&mut self, x: f32, y: f32) {
+        self.state.draw = self.state.draw.scale_axes(vec3(x, y, 1.0));
+    }
+
+    // Paths.
+
+    /// Clear the current path in preparation for describing a new one.
+    pub fn begin_path(&mut self) {
+        self.subpaths.clear();
+    }
+
+    /// Begin a new sub-path at the given point.
+    pub fn move_to(&mut self, x: f32, y: f32) {
+        let points = vec![pt2(x, y)];
+        self.subpaths.push(Subpath {
+            points,
+            closed: false,
+        });
+    }
+
+    /// Add a straight line from the end of the current sub-path to the given point.
+    ///
+    /// Begins a new sub-path at the point if there is no current sub-path.
+    pub fn line_to(&mut self, x: f32, y: f32) {
+        match self.subpaths.last_mut() {
+            Some(subpath) if !subpath.closed => subpath.points.push(pt2(x, y)),
+            _ => self.move_to(x, y),
+        }
+    }
+
+    /// Close the current sub-path with a straight line back to its first point.
+    ///
+    /// Following `line_to` calls begin a new sub-path from the first point.
+    pub fn close_path(&mut self) {
+        let start = match self.subpaths.last_mut() {
+            Some(subpath) if !subpath.closed => {
+                subpath.closed = true;
+                subpath.points[0]
+            }
+            _ => return,
+        };
+        self.move_to(start.x, start.y);
+    }
+
+    /// Add a circular arc centred at `(x, y)` with the given radius to the current sub-path.
+    ///
+    /// The arc sweeps counter-clockwise from the `start` angle to the `end` angle, both in
+    /// radians. A straight line connects the end of the current sub-path to the start of the arc.
+    pub fn arc(&mut self, x: f32, y: f32, radius: f32, start: f32, end: f32) {
+        let sweep = if end - start >= 2.0 * PI {
+            2.0 * PI
+        } else {
+            (end - start).rem_euclid(2.0 * PI)
+        };
+        let radius = radius.abs();
+        let step = match radius > ARC_TOLERANCE {
+            true => 2.0 * (1.0 - ARC_TOLERANCE / radius).acos(),
+            false => PI / 4.0,
+        };
+        let segments = ((sweep / step).ceil() as usize).max(1);
+        let centre = pt2(x, y);
+        for i in 0..=segments {
+            let angle = start + sweep * i as f32 / segments as f32;
+            let p = centre + pt2(angle.cos(), angle.sin()) * radius;
+            self.line_to(p.x, p.y);
+        }
+    }
+
+    /// Add a closed rectangular sub-path to the current path.
+    ///
+    /// `(x, y)` describes the bottom-left corner of the rectangle.
+    pub fn rect(&mut self, x: f32, y: f32, w: f32, h: f32) {
+        self.move_to(x, y);
+        self.line_to(x + w, y);
+        self.line_to(x + w, y + h);
+        self.line_to(x, y + h);
+        self.close_path();
+    }
+
+    /// Fill the current path with the current fill style.
+    ///
+    /// Overlapping sub-paths are filled according to the non-zero winding rule.
+    pub fn fill(&self) {
+        let path = match self.path(true) {
+            None => return,
+            Some(path) => path,
+        };
+        self.state
+            .draw
+            .path()
+            .fill()
+            .rule(lyon::tessellation::FillRule::NonZero)
+            .color(self.state.fill)
+            .events(path.iter());
+    }
+
+    /// Stroke the current path with the current stroke style and line width.
+    pub fn stroke(&self) {
+        let path = match self.path(false) {
+            None => return,
+            Some(path) => path,
+        };
+        self.state
+            .draw
+            .path()
+            .stroke()
+            .weight(self.state.line_width)
+            .color(self.state.stroke)
+            .events(path.iter());
+    }
+
+    // Immediate drawing.
+
+    /// Fill a rectangle with the current fill style.
+    ///
+    /// `(x, y)` describes the bottom-left corner of the rectangle. The current path is unaffected.
+    pub fn fill_rect(&self, x: f32, y: f32, w: f32, h: f32) {
+        self.state
+            .draw
+            .rect()
+            .x_y(x + w * 0.5, y + h * 0.5)
+            .w_h(w, h)
+            .color(self.state.fill);
+    }
+
+    /// Stroke the outline of a rectangle with the current stroke style and line width.
+    ///
+    /// `(x, y)` describes the bottom-left corner of the rectangle. The current path is unaffected.
+    pub fn stroke_rect(&self, x: f32, y: f32, w: f32, h: f32) {
+        let corners = [pt2(x, y), pt2(x + w, y), pt2(x + w, y + h), pt2(x, y + h)];
+        self.state
+            .draw
+            .path()
+            .stroke()
+            .weight(self.state.line_width)
+            .color(self.state.stroke)
+            .points_closed(corners.iter().cloned());
+    }
+
+    /// Draw the given text centred on `(x, y)` with the current fill style.
+    pub fn fill_text(&self, text: &str, x: f32, y: f32) {
+        self.state.draw.text(text).x_y(x, y).color(self.state.fill);
+    }
+
+    // Build the current path, returning `None` if it contains no edges.
+    //
+    // Filling implicitly closes all open sub-paths.
+    fn path(&self, close_all: bool) -> Option<geom::Path> {
+        let mut builder = geom::path();
+        let mut has_edges = false;
+        for subpath in &self.subpaths {
+            if subpath.points.len() < 2 {
+                continue;
+            }
+            has_edges = true;
+            builder = builder.move_to(subpath.points[0]);
+            for &p in &subpath.points[1..] {
+                builder = builder.line_to(p);
+            }
+            if subpath.closed || close_all {
+                builder = builder.close();
+            }
+        }
+        match has_edges {
+            true => Some(builder.build()),
+            false => None,
+        }
+    }
+}
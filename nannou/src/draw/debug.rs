@@ -0,0 +1,227 @@
+//! Helpers for visualising coordinate systems while debugging, i.e. `Draw::grid`, `Draw::axes`
+//! and `Draw::axes_3d`.
+//!
+//! Requires the `debug` feature, allowing for these helpers to be compiled out of release builds
+//! by disabling the feature.
+
+use crate::color::conv::IntoLinSrgba;
+use crate::color::LinSrgba;
+use crate::draw::properties::ColorScalar;
+use crate::draw::Draw;
+use crate::geom::{pt2, Point2, Rect};
+use crate::text;
+
+/// Draws reference lines at regular intervals, produced via `Draw::grid`.
+///
+/// The grid is drawn when the **Grid** is dropped. Lines are aligned with the origin, so the
+/// lines through `x = 0` and `y = 0` are always present within the grid's rect and are drawn with
+/// twice the stroke weight.
+pub struct Grid<'a> {
+    draw: &'a Draw,
+    spacing: f32,
+    color: LinSrgba,
+    stroke_weight: f32,
+    rect: Rect,
+    font: Option<text::Font>,
+    font_size: text::FontSize,
+}
+
+/// Draws the axes of the coordinate system at the origin, produced via `Draw::axes` or
+/// `Draw::axes_3d`.
+///
+/// The *x*, *y* and *z* axes are drawn in red, green and blue respectively when the **Axes** are
+/// dropped.
+#[derive(Debug)]
+pub struct Axes<'a> {
+    draw: &'a Draw,
+    length: f32,
+    stroke_weight: f32,
+    z: bool,
+}
+
+impl Draw {
+    /// Draw a grid of reference lines.
+    ///
+    /// By default, lines are `Grid::DEFAULT_SPACING` apart covering a rect of
+    /// `Grid::DEFAULT_SIZE` centred on the origin. Values are labelled along the axes if a font
+    /// is specified via `Grid::font`.
+    pub fn grid(&self) -> Grid {
+        Grid {
+            draw: self,
+            spacing: Grid::DEFAULT_SPACING,
+            color: LinSrgba::new(0.5, 0.5, 0.5, 0.5),
+            stroke_weight: 1.0,
+            rect: Rect::from_w_h(Grid::DEFAULT_SIZE, Grid::DEFAULT_SIZE),
+            font: None,
+            font_size: Grid::DEFAULT_FONT_SIZE,
+        }
+    }
+
+    /// Draw the *x* and *y* axes from the origin.
+    pub fn axes(&self) -> Axes {
+        Axes::new(self, false)
+    }
+
+    /// Draw the *x*, *y* and *z* axes from the origin.
+    ///
+    /// The *z* axis is only visible when drawn with a 3D camera, e.g. via `Draw::camera`.
+    pub fn axes_3d(&self) -> Axes {
+        Axes::new(self, true)
+    }
+}
+
+impl<'a> Grid<'a> {
+    /// The default distance between grid lines.
+    pub const DEFAULT_SPACING: f32 = 50.0;
+    /// The default width and height of the area covered by the grid.
+    pub const DEFAULT_SIZE: f32 = 1_000.0;
+    /// The default font size of labels.
+    pub const DEFAULT_FONT_SIZE: text::FontSize = 10;
+
+    /// The distance between grid lines.
+    pub fn spacing(mut self, spacing: f32) -> Self {
+        assert!(spacing > 0.0, "grid spacing must be greater than `0.0`");
+        self.spacing = spacing;
+        self
+    }
+
+    /// The colour of the grid lines and labels.
+    pub fn color<C>(mut self, color: C) -> Self
+    where
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        self.color = color.into_lin_srgba();
+        self
+    }
+
+    /// The stroke weight of the grid lines.
+    pub fn stroke_weight(mut self, weight: f32) -> Self {
+        self.stroke_weight = weight;
+        self
+    }
+
+    /// The area covered by the grid.
+    pub fn rect(mut self, rect: Rect) -> Self {
+        self.rect = rect;
+        self
+    }
+
+    /// Label the value of each line along the axes using the given font.
+    pub fn font(mut self, font: text::Font) -> Self {
+        self.font = Some(font);
+        self
+    }
+
+    /// The font size of labels.
+    pub fn font_size(mut self, size: text::FontSize) -> Self {
+        self.font_size = size;
+        self
+    }
+
+    // The values at which lines are drawn within the given range.
+    fn values(&self, start: f32, end: f32) -> impl Iterator<Item = f32> {
+        let spacing = self.spacing;
+        let first = (start / spacing).ceil() as i64;
+        let last = (end / spacing).floor() as i64;
+        (first..=last).map(move |i| i as f32 * spacing)
+    }
+
+    // Draw a single line, doubling the weight if it passes through the origin.
+    fn line(&self, start: Point2, end: Point2, value: f32) {
+        let weight = if value == 0.0 {
+            self.stroke_weight * 2.0
+        } else {
+            self.stroke_weight
+        };
+        self.draw
+            .line()
+            .points(start, end)
+            .weight(weight)
+            .color(self.color);
+    }
+
+    // Label a line with its value at the given position.
+    fn label(&self, font: &text::Font, position: Point2, value: f32) {
+        let value = (value * 1_000.0).round() / 1_000.0;
+        self.draw
+            .text(&value.to_string())
+            .font(font.clone())
+            .font_size(self.font_size)
+            .xy(position)
+            .color(self.color);
+    }
+}
+
+impl<'a> Axes<'a> {
+    /// The default length of each axis.
+    pub const DEFAULT_LENGTH: f32 = 100.0;
+
+    fn new(draw: &'a Draw, z: bool) -> Self {
+        Axes {
+            draw,
+            length: Self::DEFAULT_LENGTH,
+            stroke_weight: 2.0,
+            z,
+        }
+    }
+
+    /// The length of each axis.
+    pub fn length(mut self, length: f32) -> Self {
+        self.length = length;
+        self
+    }
+
+    /// The stroke weight of each axis.
+    pub fn stroke_weight(mut self, weight: f32) -> Self {
+        self.stroke_weight = weight;
+        self
+    }
+
+    // Draw a single axis along the *x* axis of the given draw.
+    fn axis(&self, draw: &Draw, color: LinSrgba) {
+        draw.arrow()
+            .points(pt2(0.0, 0.0), pt2(self.length, 0.0))
+            .weight(self.stroke_weight)
+            .color(color);
+    }
+}
+
+impl<'a> Drop for Grid<'a> {
+    fn drop(&mut self) {
+        let r = self.rect;
+        for x in self.values(r.left(), r.right()) {
+            self.line(pt2(x, r.bottom()), pt2(x, r.top()), x);
+        }
+        for y in self.values(r.bottom(), r.top()) {
+            self.line(pt2(r.left(), y), pt2(r.right(), y), y);
+        }
+
+        // Label values along the axes, or along the edges if the axes are outside the rect.
+        let font = match self.font {
+            None => return,
+            Some(ref font) => font,
+        };
+        let offset = self.font_size as f32;
+        let label_y = 0.0f32.max(r.bottom()).min(r.top()) - offset;
+        let label_x = 0.0f32.max(r.left()).min(r.right()) + offset;
+        for x in self.values(r.left(), r.right()).filter(|&x| x != 0.0) {
+            self.label(font, pt2(x, label_y), x);
+        }
+        for y in self.values(r.bottom(), r.top()) {
+            self.label(font, pt2(label_x, y), y);
+        }
+    }
+}
+
+impl<'a> Drop for Axes<'a> {
+    fn drop(&mut self) {
+        self.axis(self.draw, LinSrgba::new(1.0, 0.0, 0.0, 1.0));
+        let y_axis = self.draw.z_radians(std::f32::consts::FRAC_PI_2);
+        self.axis(&y_axis, LinSrgba::new(0.0, 1.0, 0.0, 1.0));
+        if self.z {
+            // Rotating the *x* axis around *y* by -90 degrees points it along *z*.
+            let z_axis = self.draw.y_radians(-std::f32::consts::FRAC_PI_2);
+            self.axis(&z_axis, LinSrgba::new(0.0, 0.0, 1.0, 1.0));
+        }
+    }
+}
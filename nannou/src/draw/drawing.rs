@@ -7,6 +7,7 @@ use crate::draw::properties::{
 use crate::draw::{self, Draw};
 use crate::geom::{self, Point2, Point3, Vector2, Vector3};
 use crate::math::{Angle, BaseFloat, Euler, Quaternion, Rad};
+use crate::particle::ParticleSystem;
 use lyon::path::PathEvent;
 use lyon::tessellation::{FillOptions, LineCap, LineJoin, StrokeOptions};
 use std::marker::PhantomData;
@@ -120,6 +121,56 @@ where
         self.finish_inner()
     }
 
+    /// Colour the primitive with the given gradient.
+    ///
+    /// The gradient is evaluated per fragment via a lookup table of its colours, so it remains
+    /// smooth regardless of the tessellation of the primitive. Textured primitives and text are
+    /// instead coloured per vertex.
+    ///
+    /// **Panics** if the state of the parent **Draw** is already borrowed.
+    pub fn gradient(self, gradient: draw::Gradient<S>) -> Self {
+        self.draw
+            .state
+            .try_borrow_mut()
+            .expect("drawing failed to borrow state to set the gradient")
+            .gradients
+            .insert(self.index, gradient);
+        self
+    }
+
+    /// Draw the primitive once for each live particle of the given **ParticleSystem**.
+    ///
+    /// The primitive is tessellated once and used as a sprite, centred on the position of each
+    /// particle with its colour multiplied by the colour of the particle. The transform of the
+    /// **Draw** is applied to the whole system.
+    ///
+    /// See **Draw::particle_system** for drawing each particle as a circle.
+    ///
+    /// **Panics** if the state of the parent **Draw** is already borrowed.
+    pub fn particles(self, system: &ParticleSystem) -> Self {
+        self.draw
+            .state
+            .try_borrow_mut()
+            .expect("drawing failed to borrow state to set the particle system")
+            .particle_systems
+            .insert(self.index, system.handle());
+        self
+    }
+
+    // Draw the primitive once for each of the given instances.
+    //
+    // Used by the **InstancedDraw** type. Panics if the state of the parent **Draw** is already
+    // borrowed.
+    pub(crate) fn instanced(self, instances: Vec<draw::primitive::Instance<S>>) -> Self {
+        self.draw
+            .state
+            .try_borrow_mut()
+            .expect("drawing failed to borrow state to set the instances")
+            .instances
+            .insert(self.index, instances);
+        self
+    }
+
     // Map the given function onto the primitive stored within **Draw** at `index`.
     //
     // The functionn is only applied if the node has not yet been **Drawn**.
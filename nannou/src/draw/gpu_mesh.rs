@@ -0,0 +1,370 @@
+//! A user-constructible triangle mesh whose vertex data is retained on the GPU between frames.
+
+use crate::color::LinSrgba;
+use crate::draw::renderer::VertexMode;
+use crate::geom::{Point2, Point3, Vector3};
+use crate::wgpu;
+use std::sync::{Arc, Mutex};
+
+/// A triangle mesh of arbitrary topology whose vertex data is retained on the GPU.
+///
+/// Unlike the meshes described via `draw.mesh()`, whose vertices are submitted anew each frame,
+/// the vertex and index data of a **GpuMesh** is uploaded once when the mesh is first drawn via
+/// `draw.gpu_mesh(&mesh)` and reused for all following frames.
+///
+/// Adding vertices or triangles marks the mesh for re-uploading automatically. Changes made via
+/// the `*_mut` methods are only uploaded once `mark_dirty` is called. For animated meshes,
+/// `update_positions` re-uploads only the vertex positions, leaving the colours, texture
+/// coordinates and indices untouched.
+///
+/// Vertices are transformed on the GPU by the transform of the **Draw** instance with which the
+/// mesh is drawn. Normals are not used by the renderer, though they are retained for use in
+/// e.g. computing per-vertex colours.
+#[derive(Debug)]
+pub struct GpuMesh {
+    data: Arc<Data>,
+    // Incremented each time the mesh requires re-uploading in full.
+    revision: u64,
+    // Incremented each time only the positions require re-uploading.
+    positions_revision: u64,
+    gpu: Arc<Mutex<GpuState>>,
+}
+
+/// The vertex and index data of a **GpuMesh** as of the moment it was drawn.
+#[derive(Clone, Debug)]
+pub(crate) struct Snapshot {
+    pub(crate) data: Arc<Data>,
+    revision: u64,
+    positions_revision: u64,
+    gpu: Arc<Mutex<GpuState>>,
+}
+
+/// The channels of a **GpuMesh**.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Data {
+    pub(crate) positions: Vec<Point3>,
+    pub(crate) normals: Vec<Vector3>,
+    pub(crate) tex_coords: Vec<Point2>,
+    pub(crate) colors: Vec<LinSrgba>,
+    pub(crate) indices: Vec<u32>,
+}
+
+// The buffers of a mesh uploaded to the GPU, if any.
+#[derive(Debug, Default)]
+struct GpuState {
+    buffers: Option<Buffers>,
+}
+
+#[derive(Debug)]
+struct Buffers {
+    revision: u64,
+    positions_revision: u64,
+    vertex_count: usize,
+    positions: Arc<wgpu::Buffer>,
+    colors: Arc<wgpu::Buffer>,
+    tex_coords: Arc<wgpu::Buffer>,
+    indices: Arc<wgpu::Buffer>,
+    index_count: u32,
+    // The vertex mode channels for coloured and textured drawing, created as required.
+    color_modes: Option<Arc<wgpu::Buffer>>,
+    texture_modes: Option<Arc<wgpu::Buffer>>,
+}
+
+/// The buffers required to draw a **GpuMesh** within a render pass.
+pub(crate) struct DrawBuffers {
+    pub(crate) positions: Arc<wgpu::Buffer>,
+    pub(crate) colors: Arc<wgpu::Buffer>,
+    pub(crate) tex_coords: Arc<wgpu::Buffer>,
+    pub(crate) modes: Arc<wgpu::Buffer>,
+    pub(crate) indices: Arc<wgpu::Buffer>,
+    pub(crate) index_count: u32,
+}
+
+impl GpuMesh {
+    /// An empty mesh.
+    pub fn new() -> Self {
+        GpuMesh {
+            data: Default::default(),
+            revision: 0,
+            positions_revision: 0,
+            gpu: Default::default(),
+        }
+    }
+
+    /// Add a vertex to the mesh, returning its index for use with `push_triangle`.
+    pub fn push_vertex(
+        &mut self,
+        position: Point3,
+        normal: Vector3,
+        tex_coords: Point2,
+        color: LinSrgba,
+    ) -> u32 {
+        let index = self.data.positions.len() as u32;
+        let data = Arc::make_mut(&mut self.data);
+        data.positions.push(position);
+        data.normals.push(normal);
+        data.tex_coords.push(tex_coords);
+        data.colors.push(color);
+        self.mark_dirty();
+        index
+    }
+
+    /// Add a triangle described by the indices of three vertices.
+    ///
+    /// Triangles are counter-clockwise when viewed from their front face.
+    ///
+    /// **Panics** if any of the indices refers to a vertex that does not exist.
+    pub fn push_triangle(&mut self, a: u32, b: u32, c: u32) {
+        let len = self.data.positions.len() as u32;
+        assert!(
+            a < len && b < len && c < len,
+            "triangle index out of range of the mesh's vertices"
+        );
+        Arc::make_mut(&mut self.data).indices.extend(&[a, b, c]);
+        self.mark_dirty();
+    }
+
+    /// The number of vertices in the mesh.
+    pub fn vertex_count(&self) -> usize {
+        self.data.positions.len()
+    }
+
+    /// The number of triangles in the mesh.
+    pub fn triangle_count(&self) -> usize {
+        self.data.indices.len() / 3
+    }
+
+    /// The position of each vertex.
+    pub fn positions(&self) -> &[Point3] {
+        &self.data.positions
+    }
+
+    /// The normal of each vertex.
+    pub fn normals(&self) -> &[Vector3] {
+        &self.data.normals
+    }
+
+    /// The texture coordinates of each vertex.
+    pub fn tex_coords(&self) -> &[Point2] {
+        &self.data.tex_coords
+    }
+
+    /// The colour of each vertex.
+    pub fn colors(&self) -> &[LinSrgba] {
+        &self.data.colors
+    }
+
+    /// The vertex indices of each triangle.
+    pub fn indices(&self) -> &[u32] {
+        &self.data.indices
+    }
+
+    /// Mutable access to the normal of each vertex.
+    pub fn normals_mut(&mut self) -> &mut [Vector3] {
+        &mut Arc::make_mut(&mut self.data).normals
+    }
+
+    /// Mutable access to the texture coordinates of each vertex.
+    ///
+    /// Call `mark_dirty` for the changes to be uploaded.
+    pub fn tex_coords_mut(&mut self) -> &mut [Point2] {
+        &mut Arc::make_mut(&mut self.data).tex_coords
+    }
+
+    /// Mutable access to the colour of each vertex.
+    ///
+    /// Call `mark_dirty` for the changes to be uploaded.
+    pub fn colors_mut(&mut self) -> &mut [LinSrgba] {
+        &mut Arc::make_mut(&mut self.data).colors
+    }
+
+    /// Replace the position of each vertex, re-uploading only the position buffer the next time
+    /// the mesh is drawn.
+    ///
+    /// **Panics** if the number of positions differs from the number of vertices.
+    pub fn update_positions(&mut self, positions: &[Point3]) {
+        assert_eq!(
+            positions.len(),
+            self.data.positions.len(),
+            "the number of positions must match the number of vertices"
+        );
+        Arc::make_mut(&mut self.data)
+            .positions
+            .copy_from_slice(positions);
+        self.positions_revision += 1;
+    }
+
+    /// Re-upload all of the mesh's data the next time it is drawn.
+    pub fn mark_dirty(&mut self) {
+        self.revision += 1;
+    }
+
+    /// Compute a normal for each vertex by averaging the normals of the triangles that share it,
+    /// weighted by their area.
+    ///
+    /// This produces smooth shading across shared vertices.
+    pub fn compute_smooth_normals(&mut self) {
+        let data = Arc::make_mut(&mut self.data);
+        for n in &mut data.normals {
+            *n = Vector3::new(0.0, 0.0, 0.0);
+        }
+        for tri in data.indices.chunks_exact(3) {
+            // The cross product's magnitude is twice the triangle's area.
+            let normal = face_normal(&data.positions, tri);
+            for &i in tri {
+                data.normals[i as usize] = data.normals[i as usize] + normal;
+            }
+        }
+        for n in &mut data.normals {
+            *n = n.normalize();
+        }
+    }
+
+    /// Give each triangle its own vertices with a normal perpendicular to its face.
+    ///
+    /// This produces faceted shading. Vertices shared between triangles are duplicated, so the
+    /// indices of vertices returned by `push_vertex` are no longer valid afterwards.
+    pub fn compute_flat_normals(&mut self) {
+        let old = (*self.data).clone();
+        let data = Arc::make_mut(&mut self.data);
+        data.positions.clear();
+        data.normals.clear();
+        data.tex_coords.clear();
+        data.colors.clear();
+        data.indices.clear();
+        for tri in old.indices.chunks_exact(3) {
+            let normal = face_normal(&old.positions, tri).normalize();
+            for &i in tri {
+                let i = i as usize;
+                data.indices.push(data.positions.len() as u32);
+                data.positions.push(old.positions[i]);
+                data.normals.push(normal);
+                data.tex_coords.push(old.tex_coords[i]);
+                data.colors.push(old.colors[i]);
+            }
+        }
+        self.mark_dirty();
+    }
+
+    // The state of the mesh to be drawn.
+    pub(crate) fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            data: self.data.clone(),
+            revision: self.revision,
+            positions_revision: self.positions_revision,
+            gpu: self.gpu.clone(),
+        }
+    }
+}
+
+impl Snapshot {
+    /// Upload the mesh to the GPU if it has not yet been uploaded or has changed since, returning
+    /// the buffers required for drawing it with the given vertex mode.
+    pub(crate) fn buffers(
+        &self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        vertex_mode: VertexMode,
+    ) -> DrawBuffers {
+        let mut gpu = self.gpu.lock().expect("failed to acquire GPU mesh lock");
+        let stale = match gpu.buffers {
+            None => true,
+            Some(ref buffers) => buffers.revision != self.revision,
+        };
+        if stale {
+            gpu.buffers = Some(Buffers::new(device, self));
+        }
+        let buffers = gpu.buffers.as_mut().expect("no GPU mesh buffers");
+
+        // Only the positions have changed, so copy them into the existing buffer.
+        if buffers.positions_revision != self.positions_revision {
+            let bytes = unsafe { wgpu::bytes::from_slice(&self.data.positions) };
+            let staging = device.create_buffer_with_data(bytes, wgpu::BufferUsage::COPY_SRC);
+            let size = bytes.len() as wgpu::BufferAddress;
+            encoder.copy_buffer_to_buffer(&staging, 0, &buffers.positions, 0, size);
+            buffers.positions_revision = self.positions_revision;
+        }
+
+        let vertex_count = buffers.vertex_count;
+        let modes = match vertex_mode {
+            VertexMode::Texture => &mut buffers.texture_modes,
+            _ => &mut buffers.color_modes,
+        };
+        let modes = modes
+            .get_or_insert_with(|| {
+                let modes = vec![vertex_mode; vertex_count];
+                let bytes = unsafe { wgpu::bytes::from_slice(&modes) };
+                Arc::new(device.create_buffer_with_data(bytes, wgpu::BufferUsage::VERTEX))
+            })
+            .clone();
+
+        DrawBuffers {
+            positions: buffers.positions.clone(),
+            colors: buffers.colors.clone(),
+            tex_coords: buffers.tex_coords.clone(),
+            modes,
+            indices: buffers.indices.clone(),
+            index_count: buffers.index_count,
+        }
+    }
+}
+
+impl Buffers {
+    fn new(device: &wgpu::Device, snapshot: &Snapshot) -> Self {
+        let data = &snapshot.data;
+        let vertex = wgpu::BufferUsage::VERTEX;
+        let create = |bytes: &[u8], usage| Arc::new(device.create_buffer_with_data(bytes, usage));
+        let (positions, colors, tex_coords, indices) = unsafe {
+            (
+                create(
+                    wgpu::bytes::from_slice(&data.positions),
+                    vertex | wgpu::BufferUsage::COPY_DST,
+                ),
+                create(wgpu::bytes::from_slice(&data.colors), vertex),
+                create(wgpu::bytes::from_slice(&data.tex_coords), vertex),
+                create(
+                    wgpu::bytes::from_slice(&data.indices),
+                    wgpu::BufferUsage::INDEX,
+                ),
+            )
+        };
+        Buffers {
+            revision: snapshot.revision,
+            positions_revision: snapshot.positions_revision,
+            vertex_count: data.positions.len(),
+            positions,
+            colors,
+            tex_coords,
+            indices,
+            index_count: data.indices.len() as u32,
+            color_modes: None,
+            texture_modes: None,
+        }
+    }
+}
+
+impl Clone for GpuMesh {
+    /// The clone shares the mesh data, but is uploaded to the GPU separately.
+    fn clone(&self) -> Self {
+        GpuMesh {
+            data: self.data.clone(),
+            revision: 0,
+            positions_revision: 0,
+            gpu: Default::default(),
+        }
+    }
+}
+
+impl Default for GpuMesh {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// The normal of the given triangle with a magnitude of twice its area.
+fn face_normal(positions: &[Point3], tri: &[u32]) -> Vector3 {
+    let a = positions[tri[0] as usize];
+    let b = positions[tri[1] as usize];
+    let c = positions[tri[2] as usize];
+    (b - a).cross(c - a)
+}
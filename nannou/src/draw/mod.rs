@@ -2,8 +2,10 @@
 //!
 //! See the [**Draw** type](./struct.Draw.html) for more details.
 
-use crate::geom::{self, Point2};
-use crate::math::{deg_to_rad, turns_to_rad, BaseFloat, Matrix4, SquareMatrix};
+use crate::geom::{self, Point2, Point3, Vector2};
+use crate::math::{deg_to_rad, turns_to_rad, BaseFloat, Matrix4, NumCast, SquareMatrix};
+use crate::particle::{self, ParticleSystem};
+use crate::serde_derive::{Deserialize, Serialize};
 use crate::wgpu;
 use lyon::path::PathEvent;
 use std::cell::RefCell;
@@ -12,19 +14,33 @@ use std::mem;
 use std::rc::Rc;
 
 pub use self::background::Background;
+pub use self::camera::{ArcballCamera, Camera3D};
+pub use self::canvas_2d::Canvas2D;
 pub use self::drawing::{Drawing, DrawingContext};
+pub use self::gpu_mesh::GpuMesh;
 use self::mesh::vertex::Color;
 pub use self::mesh::Mesh;
-use self::primitive::Primitive;
+pub use self::obj::{ObjError, ObjGroup, ObjMesh};
+pub use self::primitive::{ArrowHead, Gradient, TextRenderMode};
+use self::primitive::{GradientFill, Instance, Instanced, Particles, Primitive};
+pub use self::recording::DrawRecording;
 pub use self::renderer::{Builder as RendererBuilder, Renderer};
 pub use self::theme::Theme;
 
 pub mod background;
+pub mod camera;
+pub mod canvas_2d;
+#[cfg(feature = "debug")]
+pub mod debug;
 mod drawing;
+pub mod gpu_mesh;
 pub mod mesh;
+pub mod obj;
 pub mod primitive;
 pub mod properties;
+pub mod recording;
 pub mod renderer;
+pub mod svg;
 pub mod theme;
 
 /// A simple API for drawing 2D and 3D graphics.
@@ -58,9 +74,12 @@ where
     state: Rc<RefCell<State<S>>>,
     /// The current context of this **Draw** instance.
     context: Context<S>,
+    /// Transforms saved via `push_transform`, restored in reverse order via `pop_transform`.
+    transform_stack: Vec<Matrix4<S>>,
 }
 
-/// The current **Transform**, alpha **BlendDescriptor** and **Scissor** of a **Draw** instance.
+/// The current **Transform**, alpha **BlendDescriptor**, **Scissor** and **Camera3D** of a **Draw**
+/// instance.
 #[derive(Clone, Debug, PartialEq)]
 pub struct Context<S = geom::scalar::Default> {
     pub transform: Matrix4<S>,
@@ -69,6 +88,13 @@ pub struct Context<S = geom::scalar::Default> {
     pub scissor: Scissor<S>,
     pub topology: wgpu::PrimitiveTopology,
     pub sampler: wgpu::SamplerDescriptor,
+    /// The layer used to order primitives before rendering. See **Draw::z_layer**.
+    pub z_layer: S,
+    /// Whether or not primitives are hinted as fully opaque. See **Draw::opaque**.
+    pub opaque: bool,
+    /// The camera used to project primitives, or `None` for the default 2D projection. See
+    /// **Draw::camera**.
+    pub camera: Option<Camera3D>,
 }
 
 /// Commands generated by drawings.
@@ -84,7 +110,7 @@ pub enum DrawCommand<S = geom::scalar::Default> {
 }
 
 /// The scissor for a **Draw**'s render context.
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum Scissor<S = geom::scalar::Default> {
     /// The extent of the scissor matches the bounds of the target texture.
     Full,
@@ -94,6 +120,28 @@ pub enum Scissor<S = geom::scalar::Default> {
     NoOverlap,
 }
 
+/// A set of common blend modes that may be used with **Draw::blend_mode**.
+///
+/// Note that there is no `Overlay` mode, as overlay blending depends on the destination colour in
+/// a manner that cannot be expressed via the fixed-function GPU blend state.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BlendMode {
+    /// Regular alpha blending. This is the default.
+    Normal,
+    /// The source colour is weighted by its alpha and added to the destination. Useful for
+    /// glowing particles and light effects.
+    Add,
+    /// The source colour is multiplied with the destination, darkening the result.
+    Multiply,
+    /// The inverse of the source and destination are multiplied, lightening the result.
+    Screen,
+    /// Custom color and alpha blend descriptors.
+    Custom {
+        color: wgpu::BlendDescriptor,
+        alpha: wgpu::BlendDescriptor,
+    },
+}
+
 /// The inner state of the **Draw** type.
 ///
 /// The **Draw** type stores its **State** behind a **RefCell** - a type used for moving mutability
@@ -114,6 +162,22 @@ where
     ///
     /// Keys are indices into the `draw_commands` Vec.
     drawing: HashMap<usize, Primitive<S>>,
+    /// Gradients specified for primitives that are in the process of being drawn.
+    ///
+    /// Keys are indices into the `draw_commands` Vec.
+    gradients: HashMap<usize, Gradient<S>>,
+    /// Instances specified for primitives that are in the process of being drawn.
+    ///
+    /// Keys are indices into the `draw_commands` Vec.
+    instances: HashMap<usize, Vec<Instance<S>>>,
+    /// Particle systems specified for primitives that are in the process of being drawn.
+    ///
+    /// Keys are indices into the `draw_commands` Vec.
+    particle_systems: HashMap<usize, particle::Handle>,
+    /// Child **Draw**s whose commands target textures rather than the frame.
+    ///
+    /// See **Draw::to_texture**.
+    texture_draws: Vec<(wgpu::Texture, Draw<S>)>,
     /// The list of recorded draw commands.
     ///
     /// An element may be `None` if it is a primitive in the process of being drawn.
@@ -139,6 +203,29 @@ pub struct IntermediaryState<S> {
     text_buffer: String,
 }
 
+impl BlendMode {
+    /// The color and alpha blend descriptors associated with the blend mode.
+    pub fn blend_descriptors(&self) -> (wgpu::BlendDescriptor, wgpu::BlendDescriptor) {
+        let alpha = wgpu::RenderPipelineBuilder::DEFAULT_ALPHA_BLEND;
+        match *self {
+            BlendMode::Normal => (wgpu::RenderPipelineBuilder::DEFAULT_COLOR_BLEND, alpha),
+            BlendMode::Add => (wgpu::blend::ADDITIVE, alpha),
+            BlendMode::Multiply => (wgpu::blend::MULTIPLY, alpha),
+            BlendMode::Screen => (wgpu::blend::SCREEN, alpha),
+            BlendMode::Custom {
+                ref color,
+                ref alpha,
+            } => (color.clone(), alpha.clone()),
+        }
+    }
+}
+
+impl Default for BlendMode {
+    fn default() -> Self {
+        BlendMode::Normal
+    }
+}
+
 impl<S> IntermediaryState<S> {
     pub fn reset(&mut self) {
         self.intermediary_mesh.clear();
@@ -158,6 +245,10 @@ where
         self.background_color = None;
         self.last_draw_context = None;
         self.drawing.clear();
+        self.gradients.clear();
+        self.instances.clear();
+        self.particle_systems.clear();
+        self.texture_draws.clear();
         self.draw_commands.clear();
         self.intermediary_state.borrow_mut().reset();
     }
@@ -179,7 +270,22 @@ where
     }
 
     // Insert the draw primitive command at the given index.
-    fn insert_draw_command(&mut self, index: usize, prim: Primitive<S>) {
+    fn insert_draw_command(&mut self, index: usize, mut prim: Primitive<S>) {
+        if let Some(gradient) = self.gradients.remove(&index) {
+            prim = GradientFill::new(prim, gradient).into();
+        }
+        if let Some(instances) = self.instances.remove(&index) {
+            let primitive = Box::new(prim);
+            prim = Instanced {
+                primitive,
+                instances,
+            }
+            .into();
+        }
+        if let Some(system) = self.particle_systems.remove(&index) {
+            let primitive = Box::new(prim);
+            prim = Particles { primitive, system }.into();
+        }
         if let Some(elem) = self.draw_commands.get_mut(index) {
             *elem = Some(DrawCommand::Primitive(prim));
         }
@@ -202,6 +308,38 @@ where
         self.state.borrow_mut().reset();
     }
 
+    /// Produce a child **Draw** whose commands are rendered to the given texture rather than to
+    /// the frame.
+    ///
+    /// The child begins with the same transform, blend mode, scissor and theme as `self`, however
+    /// the commands of the two are stored separately.
+    ///
+    /// When `self` is drawn to a frame via `to_frame`, the child is rendered to the texture upon
+    /// submission of the frame. Texture draws are always rendered before the frame's own commands
+    /// and children are rendered before their parents, so the texture may be used by `self`
+    /// (e.g. via `draw.texture(&texture)`) within the same frame.
+    ///
+    /// The texture must have the `OUTPUT_ATTACHMENT` usage. See `App::create_render_texture`.
+    pub fn to_texture(&self, texture: &wgpu::Texture) -> Self {
+        let mut state = State::default();
+        state.theme = self.state.borrow().theme.clone();
+        let child = Draw {
+            state: Rc::new(RefCell::new(state)),
+            context: self.context.clone(),
+            transform_stack: vec![],
+        };
+        self.state
+            .borrow_mut()
+            .texture_draws
+            .push((texture.clone(), child.clone()));
+        child
+    }
+
+    /// Drain the child **Draw**s produced via `to_texture` along with their target textures.
+    pub(crate) fn drain_texture_draws(&self) -> Vec<(wgpu::Texture, Self)> {
+        mem::replace(&mut self.state.borrow_mut().texture_draws, vec![])
+    }
+
     // Context changes.
 
     /// Produce a new **Draw** instance transformed by the given transform matrix.
@@ -214,6 +352,60 @@ where
         self.context(context)
     }
 
+    /// The transform currently applied to all drawings of this **Draw** instance.
+    pub fn current_transform(&self) -> Matrix4<S> {
+        self.context.transform
+    }
+
+    /// Save the current transform so that it may be restored via `pop_transform`.
+    ///
+    /// The saved transforms are carried by **Draw** instances produced from this one, allowing
+    /// transforms to be accumulated by reassigning and later undone:
+    ///
+    /// ```
+    /// # use nannou::prelude::*;
+    /// # let mut draw = nannou::Draw::new();
+    /// draw.push_transform();
+    /// draw = draw.translate(vec3(100.0, 0.0, 0.0)).rotate(PI / 4.0);
+    /// draw.rect().w_h(20.0, 20.0);
+    /// draw.pop_transform();
+    /// assert_eq!(draw.current_transform(), nannou::Draw::new().current_transform());
+    /// ```
+    pub fn push_transform(&mut self) {
+        self.transform_stack.push(self.context.transform);
+    }
+
+    /// Restore the transform most recently saved via `push_transform`.
+    ///
+    /// Does nothing if there are no saved transforms.
+    pub fn pop_transform(&mut self) {
+        if let Some(transform) = self.transform_stack.pop() {
+            self.context.transform = transform;
+        }
+    }
+
+    /// Call the given function between a `push_transform` and a `pop_transform`.
+    ///
+    /// Any transforms applied to the **Draw** within the function by reassigning it are undone
+    /// once the function returns.
+    ///
+    /// ```
+    /// # use nannou::prelude::*;
+    /// # let mut draw = nannou::Draw::new();
+    /// draw.with_transform(|draw| {
+    ///     *draw = draw.scale(2.0);
+    ///     draw.ellipse().radius(10.0);
+    /// });
+    /// ```
+    pub fn with_transform<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        self.push_transform();
+        f(self);
+        self.pop_transform();
+    }
+
     /// Translate the position of the origin by the given translation vector.
     pub fn translate(&self, v: geom::Vector3<S>) -> Self {
         self.transform(Matrix4::from_translation(v.into()))
@@ -419,6 +611,23 @@ where
         self.color_blend(blend_descriptor)
     }
 
+    /// Produce a new **Draw** instance that will draw with the given **BlendMode**.
+    ///
+    /// This sets both the color and alpha blend descriptors. The **draw::Renderer** creates one
+    /// render pipeline per unique blend state and only switches pipelines when the blend state
+    /// changes between consecutive primitives.
+    ///
+    /// Note that primitives are still drawn in submission order. Blend modes such as `Multiply`
+    /// and `Screen` are sensitive to the colour already present within the target, so overlapping
+    /// primitives may produce different results depending on the order in which they are drawn.
+    pub fn blend_mode(&self, mode: BlendMode) -> Self {
+        let (color_blend, alpha_blend) = mode.blend_descriptors();
+        let mut context = self.context.clone();
+        context.color_blend = color_blend;
+        context.alpha_blend = alpha_blend;
+        self.context(context)
+    }
+
     /// Produce a new **Draw** instance that will be cropped to the given rectangle.
     ///
     /// If the current **Draw** instance already contains a scissor, the result will be the overlap
@@ -480,6 +689,52 @@ where
         self.context(context)
     }
 
+    /// Produce a new **Draw** instance whose primitives will be drawn on the given layer.
+    ///
+    /// When the **Draw**'s commands are drained for rendering, primitives are sorted by layer so
+    /// that those on lower layers are drawn first, i.e. further "back". Primitives on the same
+    /// layer retain their submission order. This allows for correctly layering semi-transparent
+    /// primitives regardless of the order in which they were submitted.
+    ///
+    /// Note that this only affects draw order and does not affect the *z* position of vertices,
+    /// unless primitives are hinted as opaque via **Draw::opaque**. The default layer is `0.0`.
+    pub fn z_layer(&self, z_layer: S) -> Self {
+        let mut context = self.context.clone();
+        context.z_layer = z_layer;
+        self.context(context)
+    }
+
+    /// Produce a new **Draw** instance whose primitives are hinted as fully opaque.
+    ///
+    /// Opaque primitives are drawn before all others, sorted front to back by layer so that the
+    /// depth test may reject the hidden fragments of the primitives behind them. Semi-transparent
+    /// primitives are then drawn back to front as usual.
+    ///
+    /// When drawing with the default 2D projection and any primitive is hinted as opaque, the
+    /// layer of every primitive is applied as an offset along the *z* axis so that the depth test
+    /// keeps nearer layers in front. Primitives drawn via a **Draw::camera** are expected to
+    /// provide their own depth.
+    pub fn opaque(&self, opaque: bool) -> Self {
+        let mut context = self.context.clone();
+        context.opaque = opaque;
+        self.context(context)
+    }
+
+    /// Produce a new **Draw** instance whose primitives will be projected via the given camera.
+    ///
+    /// The **draw::Renderer** uploads the camera's view-projection matrix as a uniform and uses it
+    /// for all primitives drawn with the new instance. A new uniform is only uploaded when the
+    /// camera changes between consecutive primitives.
+    ///
+    /// The **Draw** instance's transform is applied to primitives before the camera's view. By
+    /// default, a perspective camera preserves the scale of the *z = 0* plane, so 2D primitives
+    /// drawn there appear at their usual size.
+    pub fn camera(&self, camera: &Camera3D) -> Self {
+        let mut context = self.context.clone();
+        context.camera = Some(*camera);
+        self.context(context)
+    }
+
     /// Specify the primitive topology to use within the render pipeline.
     ///
     /// This method is shared between the `line_mode`, `point_mode` and `triangle_mode` methods.
@@ -492,7 +747,12 @@ where
     /// Produce a new **Draw** instance with the given context.
     fn context(&self, context: Context<S>) -> Self {
         let state = self.state.clone();
-        Draw { state, context }
+        let transform_stack = self.transform_stack.clone();
+        Draw {
+            state,
+            context,
+            transform_stack,
+        }
     }
 
     // Primitives.
@@ -547,6 +807,59 @@ where
         self.a(Default::default())
     }
 
+    /// Draw the given cubic Bézier curve.
+    ///
+    /// The curve is stroked and is flattened adaptively during tessellation. The accuracy of the
+    /// flattening may be adjusted via the `stroke_tolerance` method.
+    pub fn bezier(&self, bezier: geom::Bezier<Point2<S>>) -> Drawing<primitive::Bezier<S>, S> {
+        self.a(primitive::Bezier::new(bezier))
+    }
+
+    /// Draw a smooth curve passing through each of the given points.
+    ///
+    /// The tension of the curve may be specified via the `tension` method and the curve may be
+    /// closed via the `looped` method.
+    pub fn catmull_rom(&self, points: &[Point2<S>]) -> Drawing<primitive::CatmullRom<S>, S> {
+        self.a(primitive::CatmullRom::new(points))
+    }
+
+    /// Draw the line along which the given scalar field is equal to `level`.
+    ///
+    /// The field is sampled on a `resolution` x `resolution` grid of points spanning `bounds`, from
+    /// which the contour is produced via marching squares. Crossing positions are interpolated
+    /// between samples by default, see `interpolate`.
+    ///
+    /// **Panics** if `resolution` is less than `2`.
+    pub fn contour<F>(
+        &self,
+        field: F,
+        bounds: geom::Rect<S>,
+        resolution: u32,
+        level: S,
+    ) -> Drawing<primitive::Contour<S>, S>
+    where
+        F: Fn(Point2<S>) -> S,
+    {
+        self.contours(field, bounds, resolution, &[level])
+    }
+
+    /// Draw the lines along which the given scalar field is equal to each of the given levels.
+    ///
+    /// The same as `contour`, but the field is only sampled once for all levels.
+    pub fn contours<F>(
+        &self,
+        field: F,
+        bounds: geom::Rect<S>,
+        resolution: u32,
+        levels: &[S],
+    ) -> Drawing<primitive::Contour<S>, S>
+    where
+        F: Fn(Point2<S>) -> S,
+    {
+        let grid = geom::ScalarGrid::sample(field, bounds, resolution);
+        self.a(primitive::Contour::new(grid, levels))
+    }
+
     /// Begin drawing a **Quad**.
     pub fn quad(&self) -> Drawing<primitive::Quad<S>, S> {
         self.a(Default::default())
@@ -562,6 +875,31 @@ where
         self.a(Default::default())
     }
 
+    /// Begin drawing a **Sphere**.
+    pub fn sphere(&self) -> Drawing<primitive::Sphere<S>, S> {
+        self.a(Default::default())
+    }
+
+    /// Begin drawing a **Box3d**.
+    pub fn box_3d(&self) -> Drawing<primitive::Box3d<S>, S> {
+        self.a(Default::default())
+    }
+
+    /// Begin drawing a **Cylinder**.
+    pub fn cylinder(&self) -> Drawing<primitive::Cylinder<S>, S> {
+        self.a(Default::default())
+    }
+
+    /// Begin drawing a **Cone**.
+    pub fn cone(&self) -> Drawing<primitive::Cone<S>, S> {
+        self.a(Default::default())
+    }
+
+    /// Begin drawing a **Torus**.
+    pub fn torus(&self) -> Drawing<primitive::Torus<S>, S> {
+        self.a(Default::default())
+    }
+
     /// Begin drawing a **Polygon**.
     pub fn polygon(&self) -> Drawing<primitive::PolygonInit<S>, S> {
         self.a(Default::default())
@@ -572,6 +910,104 @@ where
         self.a(Default::default())
     }
 
+    /// Draw a textured mesh described by the given triangle list.
+    ///
+    /// Each vertex is a `(point, tex_coords)` pair and every three consecutive vertices describe a
+    /// single triangle. Texture coordinates range from `0.0` to `1.0`.
+    ///
+    /// This is short-hand for `draw.mesh().points_textured(view, vertices)`. The sampler used to
+    /// sample the texture may be specified via `draw.sampler(desc)`. The renderer caches one bind
+    /// group per unique texture view and sampler pair, so the same texture may be drawn many
+    /// times without allocating new GPU resources each frame.
+    pub fn texture_mesh(
+        &self,
+        view: &dyn wgpu::ToTextureView,
+        vertices: &[(Point2<S>, Point2<S>)],
+    ) -> Drawing<primitive::Mesh<S>, S> {
+        self.mesh().points_textured(view, vertices.iter().cloned())
+    }
+
+    /// Draw the given **GpuMesh** with its vertex colours.
+    ///
+    /// The mesh is uploaded to the GPU the first time it is drawn and only re-uploaded once it
+    /// has changed. See the **GpuMesh** docs for details.
+    pub fn gpu_mesh(&self, mesh: &GpuMesh) -> Drawing<primitive::GpuMesh, S> {
+        self.a(primitive::GpuMesh::new(mesh, None))
+    }
+
+    /// Draw the given **GpuMesh** with the given texture, sampled via the mesh's texture
+    /// coordinates.
+    pub fn gpu_mesh_textured(
+        &self,
+        mesh: &GpuMesh,
+        view: &dyn wgpu::ToTextureView,
+    ) -> Drawing<primitive::GpuMesh, S> {
+        let view = Some(view.to_texture_view());
+        self.a(primitive::GpuMesh::new(mesh, view))
+    }
+
+    /// Begin drawing a primitive that will be rendered `count` times via a single instanced draw
+    /// call.
+    ///
+    /// The position, rotation, scale, colour or transform of each instance may be specified via
+    /// the returned **InstancedDraw** before beginning a `rect`, `ellipse`, `arrow` or `mesh`.
+    /// This is far more efficient than drawing many copies of the same primitive individually, as
+    /// the primitive is only tessellated once.
+    pub fn instanced(&self, count: u32) -> primitive::InstancedDraw<S> {
+        primitive::InstancedDraw::new(self, count)
+    }
+
+    /// Draw an arrow along each of the given vectors, starting from the position of the same index.
+    ///
+    /// Each vector is multiplied by `scale`. All arrows are drawn via a single instanced draw
+    /// call, so large fields remain cheap to draw.
+    ///
+    /// The returned **Drawing** describes a single arrow from `[0.0, 0.0]` to `[1.0, 0.0]` which
+    /// is rotated and scaled to each vector. As a result, the weight and head size of the arrow
+    /// are proportional to the length of each vector, e.g. `head_length(0.5)` produces heads half
+    /// the length of each arrow. By default, the weight is `0.04` with a head length of `0.25` and
+    /// a head width of `0.1`.
+    ///
+    /// Only as many arrows as the shorter of the two slices are drawn.
+    pub fn vector_field(
+        &self,
+        positions: &[Point2<S>],
+        vectors: &[Vector2<S>],
+        scale: S,
+    ) -> Drawing<primitive::Arrow<S>, S> {
+        let count = positions.len().min(vectors.len());
+        let positions = positions.iter().map(|p| Point3::new(p.x, p.y, S::zero()));
+        let rotations = vectors.iter().map(|v| v.y.atan2(v.x));
+        let scales = vectors.iter().map(|v| v.magnitude() * scale);
+        let s = |f: f64| -> S { NumCast::from(f).unwrap() };
+        self.instanced(count as u32)
+            .positions(positions)
+            .rotations(rotations)
+            .scales(scales)
+            .arrow()
+            .points(
+                Point2::new(S::zero(), S::zero()),
+                Point2::new(S::one(), S::zero()),
+            )
+            .weight(0.04)
+            .head_length(s(0.25))
+            .head_width(s(0.1))
+    }
+
+    /// Draw each live particle of the given **ParticleSystem** as a circle.
+    ///
+    /// The particles are simulated and drawn on the GPU via a single instanced draw call. The
+    /// returned **Drawing** describes the sprite drawn for every particle, centred on the
+    /// particle's position and multiplied by its colour. By default each sprite is a white circle
+    /// with a diameter of `4.0`.
+    ///
+    /// Any other primitive may be used as the sprite via `Drawing::particles`, e.g.
+    /// `draw.texture(&sprite).w_h(8.0, 8.0).particles(&system)`.
+    pub fn particle_system(&self, system: &ParticleSystem) -> Drawing<primitive::Ellipse<S>, S> {
+        let d = NumCast::from(4.0).unwrap();
+        self.ellipse().w_h(d, d).particles(system)
+    }
+
     /// Begin drawing a **Polyline**.
     ///
     /// Note that this is simply short-hand for `draw.path().stroke()`
@@ -579,6 +1015,14 @@ where
         self.path().stroke()
     }
 
+    /// Begin drawing a dashed **Polyline** or stroked **Path**.
+    ///
+    /// Each element of the pattern is a `(dash_length, gap_length)` pair. Note that this is simply
+    /// short-hand for `draw.path().stroke().dashes(pattern)`.
+    pub fn path_dashed(&self, pattern: &[(f32, f32)]) -> Drawing<primitive::PathStroke<S>, S> {
+        self.path().stroke().dashes(pattern)
+    }
+
     /// Begin drawing a **Text**.
     pub fn text(&self, s: &str) -> Drawing<primitive::Text<S>, S> {
         let text = {
@@ -595,6 +1039,34 @@ where
         self.a(primitive::Texture::new(view))
     }
 
+    /// Begin drawing the given **Image** without first uploading it to a texture.
+    ///
+    /// The image is uploaded to a texture by the **Renderer**. Textures are cached by a hash of
+    /// the image content, so an image that is drawn every frame is only uploaded again if its
+    /// content changes. Textures that are no longer drawn are freed once the renderer's image
+    /// cache capacity is exceeded. See `RendererBuilder::image_cache_capacity`.
+    pub fn image(&self, image: &crate::image::DynamicImage) -> Drawing<primitive::Image<S>, S> {
+        let data = primitive::image::ImageData::from_image(image);
+        self.a(primitive::Image::new(data))
+    }
+
+    /// Begin drawing an image from raw texture data.
+    ///
+    /// Unlike `image`, the data is not hashed and is instead uploaded every frame. This is better
+    /// suited to images that are updated at a high frequency, e.g. video or generated data.
+    ///
+    /// **Panics** if the length of `data` does not match the given dimensions and format.
+    pub fn image_raw(
+        &self,
+        data: &[u8],
+        width: u32,
+        height: u32,
+        format: wgpu::TextureFormat,
+    ) -> Drawing<primitive::Image<S>, S> {
+        let data = primitive::image::ImageData::from_raw(data, width, height, format);
+        self.a(primitive::Image::new(data))
+    }
+
     /// Finish any drawings-in-progress and produce an iterator draining the inner draw commands
     /// and yielding them by value.
     pub fn drain_commands(&self) -> impl Iterator<Item = DrawCommand<S>> {
@@ -604,7 +1076,7 @@ where
             let empty = Vec::with_capacity(state.draw_commands.len());
             std::mem::replace(&mut state.draw_commands, empty)
         };
-        cmds.into_iter().filter_map(|opt| opt)
+        sort_by_z_layer(cmds.into_iter().filter_map(|opt| opt))
     }
 
     /// Drain any remaining `drawing`s and convert them to draw commands.
@@ -613,6 +1085,16 @@ where
     }
 }
 
+impl Draw {
+    /// Begin a stateful **Canvas2D** context that draws to this **Draw** instance.
+    ///
+    /// The **Canvas2D** closely mirrors the HTML Canvas 2D API and may be a friendlier starting
+    /// point for those coming from JavaScript.
+    pub fn canvas_2d(&self) -> Canvas2D {
+        Canvas2D::new(self)
+    }
+}
+
 impl<S> Default for IntermediaryState<S> {
     fn default() -> Self {
         let intermediary_mesh = Default::default();
@@ -639,12 +1121,20 @@ where
         let background_color = Default::default();
         let draw_commands = Default::default();
         let drawing = Default::default();
+        let gradients = Default::default();
+        let instances = Default::default();
+        let particle_systems = Default::default();
+        let texture_draws = Default::default();
         let intermediary_state = RefCell::new(Default::default());
         let theme = Default::default();
         State {
             last_draw_context,
             draw_commands,
             drawing,
+            gradients,
+            instances,
+            particle_systems,
+            texture_draws,
             intermediary_state,
             theme,
             background_color,
@@ -659,7 +1149,12 @@ where
     fn default() -> Self {
         let state: Rc<RefCell<State<S>>> = Rc::new(RefCell::new(Default::default()));
         let context = Default::default();
-        Draw { state, context }
+        let transform_stack = vec![];
+        Draw {
+            state,
+            context,
+            transform_stack,
+        }
     }
 }
 
@@ -675,6 +1170,70 @@ where
             scissor: Scissor::Full,
             topology: wgpu::RenderPipelineBuilder::DEFAULT_PRIMITIVE_TOPOLOGY,
             sampler: wgpu::SamplerBuilder::new().into_descriptor(),
+            z_layer: S::zero(),
+            opaque: false,
+            camera: None,
+        }
+    }
+}
+
+// Stable sort the given draw commands by the `z_layer` of the context in which each primitive is
+// drawn, inserting new context commands where necessary.
+//
+// Primitives hinted as opaque are drawn first from front to back, followed by all others from back
+// to front. See `Draw::opaque`.
+pub(crate) fn sort_by_z_layer<S>(
+    cmds: impl Iterator<Item = DrawCommand<S>>,
+) -> std::vec::IntoIter<DrawCommand<S>>
+where
+    S: BaseFloat,
+{
+    // Each primitive refers to the context in which it was drawn by index.
+    let mut contexts = vec![Context::default()];
+    let mut layered = vec![];
+    let mut requires_sort = false;
+    let mut any_opaque = false;
+    for cmd in cmds {
+        match cmd {
+            DrawCommand::Context(ctxt) => contexts.push(ctxt),
+            DrawCommand::Primitive(prim) => {
+                let ctxt = &contexts[contexts.len() - 1];
+                requires_sort |= ctxt.z_layer != S::zero() || ctxt.opaque;
+                any_opaque |= ctxt.opaque;
+                layered.push((contexts.len() - 1, prim));
+            }
+        }
+    }
+    if requires_sort {
+        layered.sort_by(|&(a, _), &(b, _)| {
+            let (a, b) = (&contexts[a], &contexts[b]);
+            let layer = match (a.opaque, b.opaque) {
+                (true, true) => b.z_layer.partial_cmp(&a.z_layer),
+                (false, false) => a.z_layer.partial_cmp(&b.z_layer),
+                (true, false) => Some(std::cmp::Ordering::Less),
+                (false, true) => Some(std::cmp::Ordering::Greater),
+            };
+            layer.unwrap_or(std::cmp::Ordering::Equal)
+        });
+    }
+    if any_opaque {
+        for ctxt in contexts.iter_mut().filter(|ctxt| ctxt.camera.is_none()) {
+            let offset = geom::Vector3::new(S::zero(), S::zero(), ctxt.z_layer);
+            ctxt.transform = Matrix4::from_translation(offset.into()) * ctxt.transform;
+        }
+    }
+    let mut sorted = Vec::with_capacity(layered.len() * 2);
+    let mut last_ctxt = None;
+    for (ctxt, prim) in layered {
+        let changed = match last_ctxt {
+            None => true,
+            Some(last) => last != ctxt && contexts[last] != contexts[ctxt],
+        };
+        if changed {
+            sorted.push(DrawCommand::Context(contexts[ctxt].clone()));
+            last_ctxt = Some(ctxt);
         }
+        sorted.push(DrawCommand::Primitive(prim));
     }
+    sorted.into_iter()
 }
@@ -0,0 +1,259 @@
+//! Loading and drawing meshes described by Wavefront OBJ files.
+//!
+//! See the **ObjMesh** type and the `Draw::mesh_obj` and `Draw::obj_mesh` methods.
+
+use crate::color::LinSrgba;
+use crate::draw;
+use crate::draw::primitive::solid;
+use crate::geom::{pt2, pt3, Point2, Point3, Vector3};
+use crate::wgpu;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// A mesh loaded from an OBJ file along with its MTL materials.
+///
+/// Each group (or object) within the file is loaded as a separate **ObjGroup** with its own
+/// material. Loading an **ObjMesh** once and storing it within the app's model avoids parsing the
+/// file every frame. Draw it via `draw.obj_mesh(&mesh)`.
+///
+/// Vertex normals are loaded from the file if present. Otherwise, flat normals are generated for
+/// each face. Normals are used to shade groups that are not textured.
+#[derive(Debug)]
+pub struct ObjMesh {
+    groups: Vec<ObjGroup>,
+}
+
+/// A single group within an **ObjMesh** sharing a single material.
+#[derive(Debug)]
+pub struct ObjGroup {
+    /// The name of the group within the OBJ file.
+    pub name: String,
+    /// The position of each vertex.
+    pub points: Vec<Point3>,
+    /// The normal of each vertex.
+    pub normals: Vec<Vector3>,
+    /// The texture coordinates of each vertex, or empty if the file has none.
+    pub tex_coords: Vec<Point2>,
+    /// Each set of three consecutive indices describes a triangle.
+    pub indices: Vec<usize>,
+    /// The diffuse colour of the group's material, or white if the group has no material.
+    pub color: LinSrgba,
+    /// The diffuse texture of the group's material, if any was loaded.
+    pub texture: Option<wgpu::Texture>,
+}
+
+/// Errors that might occur while loading an **ObjMesh**.
+#[derive(Debug)]
+pub enum ObjError {
+    /// An error occurred while loading the OBJ or MTL file.
+    Load(tobj::LoadError),
+    /// An error occurred while loading a material's texture.
+    Texture(image::ImageError),
+}
+
+impl ObjMesh {
+    /// Load the OBJ file at the given path along with its materials and their diffuse textures.
+    ///
+    /// Textures are uploaded via the given `device` and `queue` and are shared between all groups
+    /// that use them. Texture paths are resolved relative to the directory of the OBJ file.
+    pub fn from_obj<P>(
+        path: P,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) -> Result<Self, ObjError>
+    where
+        P: AsRef<Path>,
+    {
+        load(path.as_ref(), Some((device, queue)))
+    }
+
+    /// Load the geometry and material colours of the OBJ file at the given path.
+    ///
+    /// Unlike `from_obj`, no textures are loaded and in turn no device is required.
+    pub fn load<P>(path: P) -> Result<Self, ObjError>
+    where
+        P: AsRef<Path>,
+    {
+        load(path.as_ref(), None)
+    }
+
+    /// The groups of the mesh.
+    pub fn groups(&self) -> &[ObjGroup] {
+        &self.groups
+    }
+}
+
+impl draw::Draw {
+    /// Load the OBJ file at the given path and draw it with the current transform.
+    ///
+    /// Each group within the file is drawn with the diffuse colour of its material. Textures are
+    /// not loaded as no device is available to the **Draw** API. Note that the file is parsed on
+    /// every call. To reuse a mesh across frames or to draw its textures, load an **ObjMesh** via
+    /// `ObjMesh::from_obj` and draw it via `obj_mesh` instead.
+    pub fn mesh_obj<P>(&self, path: P) -> Result<(), ObjError>
+    where
+        P: AsRef<Path>,
+    {
+        let mesh = ObjMesh::load(path)?;
+        self.obj_mesh(&mesh);
+        Ok(())
+    }
+
+    /// Draw each group of the given **ObjMesh** with the current transform.
+    ///
+    /// Textured groups are drawn with their texture. All other groups are drawn with the colour of
+    /// their material, shaded by their normals.
+    pub fn obj_mesh(&self, mesh: &ObjMesh) {
+        let normal_transform = solid::normal_transform(&self.context.transform);
+        for group in mesh.groups() {
+            let points = group.points.iter().cloned();
+            let indices = group.indices.iter().cloned();
+            match group.texture {
+                Some(ref texture) if !group.tex_coords.is_empty() => {
+                    let points = points.zip(group.tex_coords.iter().cloned());
+                    self.mesh().indexed_textured(texture, points, indices);
+                }
+                _ => {
+                    let colors = group.normals.iter().map(|n| {
+                        let n = cgmath::Vector3::new(n.x, n.y, n.z);
+                        let n = cgmath::Transform::transform_vector(&normal_transform, n);
+                        solid::shade(group.color, n.into())
+                    });
+                    self.mesh().indexed_colored(points.zip(colors), indices);
+                }
+            }
+        }
+    }
+}
+
+impl From<tobj::LoadError> for ObjError {
+    fn from(e: tobj::LoadError) -> Self {
+        ObjError::Load(e)
+    }
+}
+
+impl From<image::ImageError> for ObjError {
+    fn from(e: image::ImageError) -> Self {
+        ObjError::Texture(e)
+    }
+}
+
+impl std::error::Error for ObjError {
+    fn cause(&self) -> Option<&dyn std::error::Error> {
+        match *self {
+            ObjError::Load(ref e) => Some(e),
+            ObjError::Texture(ref e) => Some(e),
+        }
+    }
+}
+
+impl std::fmt::Display for ObjError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match *self {
+            ObjError::Load(ref e) => std::fmt::Display::fmt(e, f),
+            ObjError::Texture(ref e) => std::fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+// Load the mesh, loading textures only if a device and queue are given.
+fn load(
+    path: &Path,
+    device_queue: Option<(&wgpu::Device, &wgpu::Queue)>,
+) -> Result<ObjMesh, ObjError> {
+    let triangulate = true;
+    let (models, materials) = tobj::load_obj(path, triangulate)?;
+    let dir = path.parent().unwrap_or_else(|| Path::new(""));
+    let mut textures: HashMap<String, wgpu::Texture> = HashMap::new();
+    let mut groups = Vec::with_capacity(models.len());
+    for model in models {
+        let tobj::Mesh {
+            positions,
+            normals,
+            texcoords,
+            indices,
+            material_id,
+            ..
+        } = model.mesh;
+
+        let mut points: Vec<_> = positions.chunks(3).map(|p| pt3(p[0], p[1], p[2])).collect();
+        // OBJ texture coordinates begin at the bottom left, while wgpu's begin at the top left.
+        let mut tex_coords: Vec<_> = if texcoords.len() / 2 == points.len() {
+            texcoords.chunks(2).map(|t| pt2(t[0], 1.0 - t[1])).collect()
+        } else {
+            vec![]
+        };
+        let mut indices: Vec<_> = indices.into_iter().map(|i| i as usize).collect();
+        let normals = if normals.len() == positions.len() {
+            normals
+                .chunks(3)
+                .map(|n| Vector3::new(n[0], n[1], n[2]))
+                .collect()
+        } else {
+            flat_normals(&mut points, &mut tex_coords, &mut indices)
+        };
+
+        // Retrieve the material colour and texture.
+        let material = material_id.and_then(|id| materials.get(id));
+        let color = match material {
+            None => LinSrgba::new(1.0, 1.0, 1.0, 1.0),
+            Some(m) => {
+                let [r, g, b] = m.diffuse;
+                LinSrgba::new(r, g, b, m.dissolve)
+            }
+        };
+        let mut texture = None;
+        if let (Some(m), Some((device, queue))) = (material, device_queue) {
+            if !m.diffuse_texture.is_empty() {
+                if !textures.contains_key(&m.diffuse_texture) {
+                    let usage = wgpu::TextureBuilder::default_image_texture_usage();
+                    let texture_path = dir.join(&m.diffuse_texture);
+                    let tex = wgpu::Texture::load_from_path(device, queue, usage, texture_path)?;
+                    textures.insert(m.diffuse_texture.clone(), tex);
+                }
+                texture = textures.get(&m.diffuse_texture).cloned();
+            }
+        }
+
+        groups.push(ObjGroup {
+            name: model.name,
+            points,
+            normals,
+            tex_coords,
+            indices,
+            color,
+            texture,
+        });
+    }
+    Ok(ObjMesh { groups })
+}
+
+// Split the vertices so that each triangle has its own vertices, producing the flat normal of
+// each triangle for each of its vertices.
+fn flat_normals(
+    points: &mut Vec<Point3>,
+    tex_coords: &mut Vec<Point2>,
+    indices: &mut Vec<usize>,
+) -> Vec<Vector3> {
+    let mut new_points = Vec::with_capacity(indices.len());
+    let mut new_tex_coords = Vec::with_capacity(indices.len());
+    let mut normals = Vec::with_capacity(indices.len());
+    for tri in indices.chunks(3) {
+        if tri.len() < 3 {
+            break;
+        }
+        let (a, b, c) = (points[tri[0]], points[tri[1]], points[tri[2]]);
+        let normal = (b - a).cross(c - a).normalize();
+        for &i in tri {
+            new_points.push(points[i]);
+            if !tex_coords.is_empty() {
+                new_tex_coords.push(tex_coords[i]);
+            }
+            normals.push(normal);
+        }
+    }
+    *indices = (0..new_points.len()).collect();
+    *points = new_points;
+    *tex_coords = new_tex_coords;
+    normals
+}
@@ -11,12 +11,32 @@ use lyon::tessellation::StrokeOptions;
 
 /// A path containing only two points - a start and end.
 ///
-/// A triangle is drawn on the end to indicate direction.
+/// A head is drawn on the end to indicate direction. See **ArrowHead** for the available styles.
 #[derive(Clone, Debug)]
 pub struct Arrow<S = geom::scalar::Default> {
     line: Line<S>,
     head_length: Option<S>,
     head_width: Option<S>,
+    head_style: ArrowHead,
+}
+
+/// The style of the head drawn at the end of an **Arrow**.
+///
+/// The shaft and head of an arrow are tessellated as a single path, so translucent arrows do not
+/// appear darker where the two overlap.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ArrowHead {
+    /// A filled triangle. This is the default.
+    ///
+    /// The shaft is filled along with the head, so the stroke caps of the arrow are ignored.
+    Filled,
+    /// Two strokes forming a chevron at the end of the shaft, drawn with the shaft's stroke.
+    Open,
+    /// A filled circle centred on the end of the shaft.
+    ///
+    /// The `head_width` is the radius of the circle, while the `head_length` is unused. As with
+    /// `Filled`, the stroke caps of the arrow are ignored.
+    Circle,
 }
 
 /// The drawing context for a line.
@@ -66,6 +86,14 @@ impl<S> Arrow<S> {
         self
     }
 
+    /// The style of the arrow head.
+    ///
+    /// By default, this is `ArrowHead::Filled`.
+    pub fn head_style(mut self, style: ArrowHead) -> Self {
+        self.head_style = style;
+        self
+    }
+
     // Map the inner `PathStroke<S>` using the given function.
     fn map_line<F>(self, map: F) -> Self
     where
@@ -75,12 +103,14 @@ impl<S> Arrow<S> {
             line,
             head_length,
             head_width,
+            head_style,
         } = self;
         let line = map(line);
         Arrow {
             line,
             head_length,
             head_width,
+            head_style,
         }
     }
 }
@@ -129,6 +159,13 @@ where
     pub fn head_width(self, width: S) -> Self {
         self.map_ty(|ty| ty.head_width(width))
     }
+
+    /// The style of the arrow head.
+    ///
+    /// By default, this is `ArrowHead::Filled`.
+    pub fn head_style(self, style: ArrowHead) -> Self {
+        self.map_ty(|ty| ty.head_style(style))
+    }
 }
 
 impl<S> SetStroke for Arrow<S> {
@@ -180,6 +217,7 @@ impl draw::renderer::RenderPrimitive for Arrow<f32> {
             line,
             head_length,
             head_width,
+            head_style,
         } = self;
         let start = line.start.unwrap_or(pt2(0.0, 0.0));
         let end = line.end.unwrap_or(pt2(0.0, 0.0));
@@ -187,67 +225,89 @@ impl draw::renderer::RenderPrimitive for Arrow<f32> {
             return draw::renderer::PrimitiveRender::default();
         }
 
-        // Calculate the arrow head points.
-        let line_w_2 = line.path.opts.line_width * 2.0;
+        // Calculate the arrow head dimensions.
+        let line_w = line.path.opts.line_width;
+        let line_w_2 = line_w * 2.0;
         let line_w_4 = line_w_2 * 2.0;
         let head_width = head_width.unwrap_or(line_w_2);
         let head_length = head_length.unwrap_or(line_w_4);
         let line_dir = end - start;
         let line_dir_mag = line_dir.magnitude();
-        let tri_len = head_length.min(line_dir_mag);
-        let tri_dir_norm = line_dir.with_magnitude(tri_len);
-        let tri_start = end - tri_dir_norm;
-        let tri_end = end;
-        let line_start = start;
-        let line_end = tri_start;
-        let tri_a = tri_end;
-        let tri_w_dir = vec2(-tri_dir_norm.y, tri_dir_norm.x).with_magnitude(head_width);
-        let tri_b = tri_start + tri_w_dir;
-        let tri_c = tri_start - tri_w_dir;
-        // The line should only be drawn if there is space after drawing the triangle.
-        let draw_line = line_dir_mag > tri_len;
+        let dir = line_dir / line_dir_mag;
+        let normal = vec2(-dir.y, dir.x);
+
+        // Build the shaft and head as a single path.
+        let mut builder = lyon::path::Path::builder();
+        let shaft = normal * (line_w / 2.0);
+        let options = match head_style {
+            ArrowHead::Filled => {
+                // The shaft is only drawn if there is space after drawing the head.
+                let head_len = head_length.min(line_dir_mag);
+                let head_start = end - dir * head_len;
+                let head = normal * head_width;
+                let draw_shaft = line_dir_mag > head_len;
+                let mut outline = vec![];
+                if draw_shaft {
+                    outline.extend_from_slice(&[start - shaft, head_start - shaft]);
+                }
+                outline.extend_from_slice(&[head_start - head, end, head_start + head]);
+                if draw_shaft {
+                    outline.extend_from_slice(&[head_start + shaft, start + shaft]);
+                }
+                add_polygon(&mut builder, &outline);
+                path::Options::Fill(Default::default())
+            }
+            ArrowHead::Open => {
+                let head_start = end - dir * head_length.min(line_dir_mag);
+                let head = normal * head_width;
+                builder.move_to(lyon_point(start));
+                builder.line_to(lyon_point(end));
+                builder.move_to(lyon_point(head_start + head));
+                builder.line_to(lyon_point(end));
+                builder.line_to(lyon_point(head_start - head));
+                path::Options::Stroke(line.path.opts)
+            }
+            ArrowHead::Circle => {
+                let radius = head_width;
+                if line_dir_mag > radius {
+                    let shaft_end = end - dir * radius;
+                    let outline = [
+                        start - shaft,
+                        shaft_end - shaft,
+                        shaft_end + shaft,
+                        start + shaft,
+                    ];
+                    add_polygon(&mut builder, &outline);
+                }
+                let radii = lyon::math::vector(radius, radius);
+                let sweep_angle = lyon::math::Angle::radians(std::f32::consts::PI * 2.0);
+                let x_rotation = lyon::math::Angle::radians(0.0);
+                builder.move_to(lyon_point(end + vec2(radius, 0.0)));
+                builder.arc(lyon_point(end), radii, sweep_angle, x_rotation);
+                builder.close();
+                path::Options::Fill(Default::default())
+            }
+        };
+        let arrow_path = builder.build();
 
         // Determine the transform to apply to all points.
         let global_transform = ctxt.transform;
         let local_transform = line.path.position.transform() * line.path.orientation.transform();
         let transform = global_transform * local_transform;
 
-        // Draw the tri.
-        let tri_points = [tri_a, tri_b, tri_c];
-        let tri_points = tri_points.iter().cloned().map(Into::into);
-        let close_tri = true;
-        let tri_events = lyon::path::iterator::FromPolyline::new(close_tri, tri_points);
         path::render_path_events(
-            tri_events,
+            arrow_path.iter(),
             line.path.color,
             transform,
-            path::Options::Fill(Default::default()),
+            options,
             &ctxt.theme,
             &draw::theme::Primitive::Arrow,
             &mut ctxt.fill_tessellator,
             &mut ctxt.stroke_tessellator,
+            ctxt.svg.as_deref_mut(),
             mesh,
         );
 
-        // Draw the line.
-        if draw_line {
-            let line_points = [line_start, line_end];
-            let line_points = line_points.iter().cloned().map(Into::into);
-            let close_line = false;
-            let line_events = lyon::path::iterator::FromPolyline::new(close_line, line_points);
-            path::render_path_events(
-                line_events,
-                line.path.color,
-                transform,
-                path::Options::Stroke(line.path.opts),
-                &ctxt.theme,
-                &draw::theme::Primitive::Arrow,
-                &mut ctxt.fill_tessellator,
-                &mut ctxt.stroke_tessellator,
-                mesh,
-            );
-        }
-
         draw::renderer::PrimitiveRender::default()
     }
 }
@@ -260,10 +320,34 @@ where
         let line = Default::default();
         let head_length = Default::default();
         let head_width = Default::default();
+        let head_style = Default::default();
         Arrow {
             line,
             head_length,
             head_width,
+            head_style,
+        }
+    }
+}
+
+impl Default for ArrowHead {
+    fn default() -> Self {
+        ArrowHead::Filled
+    }
+}
+
+// Add a closed sub-path with the given points to the path.
+fn add_polygon(builder: &mut lyon::path::Builder, points: &[Point2]) {
+    let mut points = points.iter().cloned().map(lyon_point);
+    if let Some(first) = points.next() {
+        builder.move_to(first);
+        for point in points {
+            builder.line_to(point);
         }
+        builder.close();
     }
 }
+
+fn lyon_point(p: Point2) -> lyon::math::Point {
+    lyon::math::point(p.x, p.y)
+}
@@ -0,0 +1,163 @@
+use crate::color::LinSrgba;
+use crate::draw::primitive::path;
+use crate::draw::primitive::{PathStroke, Primitive};
+use crate::draw::properties::spatial::{orientation, position};
+use crate::draw::properties::{ColorScalar, SetColor, SetOrientation, SetPosition, SetStroke};
+use crate::draw::{self, Drawing};
+use crate::geom::{self, Point2};
+use crate::math::{BaseFloat, Zero};
+use lyon::tessellation::StrokeOptions;
+
+/// A cubic Bézier curve, stroked along its length.
+///
+/// The curve is flattened adaptively during tessellation according to the `stroke_tolerance`.
+#[derive(Clone, Debug)]
+pub struct Bezier<S = geom::scalar::Default> {
+    pub path: PathStroke<S>,
+    pub bezier: Option<geom::Bezier<Point2<S>>>,
+}
+
+/// The drawing context for a Bézier curve.
+pub type DrawingBezier<'a, S = geom::scalar::Default> = Drawing<'a, Bezier<S>, S>;
+
+impl<S> Bezier<S> {
+    /// Create a new **Bezier** primitive from the given curve.
+    pub fn new(bezier: geom::Bezier<Point2<S>>) -> Self
+    where
+        S: Zero,
+    {
+        let path = Default::default();
+        let bezier = Some(bezier);
+        Bezier { path, bezier }
+    }
+
+    /// Short-hand for the `stroke_weight` method.
+    pub fn weight(self, weight: f32) -> Self {
+        self.map_path(|p| p.stroke_weight(weight))
+    }
+
+    /// Short-hand for the `stroke_tolerance` method.
+    pub fn tolerance(self, tolerance: f32) -> Self {
+        self.map_path(|p| p.stroke_tolerance(tolerance))
+    }
+
+    /// Specify the curve to be drawn.
+    pub fn curve(mut self, bezier: geom::Bezier<Point2<S>>) -> Self {
+        self.bezier = Some(bezier);
+        self
+    }
+
+    // Map the inner `PathStroke<S>` using the given function.
+    fn map_path<F>(mut self, map: F) -> Self
+    where
+        F: FnOnce(PathStroke<S>) -> PathStroke<S>,
+    {
+        self.path = map(self.path);
+        self
+    }
+}
+
+impl<'a, S> DrawingBezier<'a, S>
+where
+    S: BaseFloat,
+{
+    /// Short-hand for the `stroke_weight` method.
+    pub fn weight(self, weight: f32) -> Self {
+        self.map_ty(|ty| ty.weight(weight))
+    }
+
+    /// Short-hand for the `stroke_tolerance` method.
+    pub fn tolerance(self, tolerance: f32) -> Self {
+        self.map_ty(|ty| ty.tolerance(tolerance))
+    }
+
+    /// Specify the curve to be drawn.
+    pub fn curve(self, bezier: geom::Bezier<Point2<S>>) -> Self {
+        self.map_ty(|ty| ty.curve(bezier))
+    }
+}
+
+impl<S> SetStroke for Bezier<S> {
+    fn stroke_options_mut(&mut self) -> &mut StrokeOptions {
+        SetStroke::stroke_options_mut(&mut self.path)
+    }
+}
+
+impl<S> SetOrientation<S> for Bezier<S> {
+    fn properties(&mut self) -> &mut orientation::Properties<S> {
+        SetOrientation::properties(&mut self.path)
+    }
+}
+
+impl<S> SetPosition<S> for Bezier<S> {
+    fn properties(&mut self) -> &mut position::Properties<S> {
+        SetPosition::properties(&mut self.path)
+    }
+}
+
+impl<S> SetColor<ColorScalar> for Bezier<S> {
+    fn rgba_mut(&mut self) -> &mut Option<LinSrgba> {
+        SetColor::rgba_mut(&mut self.path)
+    }
+}
+
+impl<S> From<Bezier<S>> for Primitive<S> {
+    fn from(prim: Bezier<S>) -> Self {
+        Primitive::Bezier(prim)
+    }
+}
+
+impl<S> Into<Option<Bezier<S>>> for Primitive<S> {
+    fn into(self) -> Option<Bezier<S>> {
+        match self {
+            Primitive::Bezier(prim) => Some(prim),
+            _ => None,
+        }
+    }
+}
+
+impl draw::renderer::RenderPrimitive for Bezier<f32> {
+    fn render_primitive(
+        self,
+        mut ctxt: draw::renderer::RenderContext,
+        mesh: &mut draw::Mesh,
+    ) -> draw::renderer::PrimitiveRender {
+        let Bezier { path, bezier } = self;
+        let bezier = match bezier {
+            None => return draw::renderer::PrimitiveRender::default(),
+            Some(bezier) => bezier,
+        };
+
+        // Determine the transform to apply to all points.
+        let global_transform = ctxt.transform;
+        let local_transform = path.position.transform() * path.orientation.transform();
+        let transform = global_transform * local_transform;
+
+        path::render_path_events(
+            bezier.path_events(),
+            path.color,
+            transform,
+            path::Options::Stroke(path.opts),
+            &ctxt.theme,
+            &draw::theme::Primitive::Path,
+            &mut ctxt.fill_tessellator,
+            &mut ctxt.stroke_tessellator,
+            ctxt.svg.as_deref_mut(),
+            mesh,
+        );
+
+        draw::renderer::PrimitiveRender::default()
+    }
+}
+
+impl<S> Default for Bezier<S>
+where
+    S: Zero,
+{
+    fn default() -> Self {
+        Bezier {
+            path: Default::default(),
+            bezier: None,
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use crate::draw::primitive::solid::{SetSolid, SolidOptions};
+use crate::draw::primitive::Primitive;
+use crate::draw::properties::spatial::{dimension, orientation, position};
+use crate::draw::properties::{
+    ColorScalar, LinSrgba, SetColor, SetDimensions, SetOrientation, SetPosition,
+};
+use crate::draw::{self, Drawing};
+use crate::geom;
+use crate::math::BaseFloat;
+
+/// Properties related to drawing a **Box3d**.
+///
+/// The box is triangulated with flat vertex normals and dimensions specified via `w_h_d`.
+#[derive(Clone, Debug)]
+pub struct Box3d<S = geom::scalar::Default> {
+    dimensions: dimension::Properties<S>,
+    solid: SolidOptions<S>,
+}
+
+/// The drawing context for a **Box3d**.
+pub type DrawingBox3d<'a, S = geom::scalar::Default> = Drawing<'a, Box3d<S>, S>;
+
+// Trait implementations.
+
+impl draw::renderer::RenderPrimitive for Box3d<f32> {
+    fn render_primitive(
+        self,
+        ctxt: draw::renderer::RenderContext,
+        mesh: &mut draw::Mesh,
+    ) -> draw::renderer::PrimitiveRender {
+        let Box3d { dimensions, solid } = self;
+        let w = dimensions.x.map(f32::abs).unwrap_or(100.0);
+        let h = dimensions.y.map(f32::abs).unwrap_or(100.0);
+        let d = dimensions.z.map(f32::abs).unwrap_or(100.0);
+        let box_3d = geom::solid::box_3d(w, h, d);
+        solid.render(box_3d, ctxt, &draw::theme::Primitive::Box3d, mesh)
+    }
+}
+
+impl<S> Default for Box3d<S>
+where
+    S: BaseFloat,
+{
+    fn default() -> Self {
+        Box3d {
+            dimensions: Default::default(),
+            solid: Default::default(),
+        }
+    }
+}
+
+impl<S> SetDimensions<S> for Box3d<S> {
+    fn properties(&mut self) -> &mut dimension::Properties<S> {
+        SetDimensions::properties(&mut self.dimensions)
+    }
+}
+
+impl<S> SetOrientation<S> for Box3d<S> {
+    fn properties(&mut self) -> &mut orientation::Properties<S> {
+        SetOrientation::properties(&mut self.solid)
+    }
+}
+
+impl<S> SetPosition<S> for Box3d<S> {
+    fn properties(&mut self) -> &mut position::Properties<S> {
+        SetPosition::properties(&mut self.solid)
+    }
+}
+
+impl<S> SetColor<ColorScalar> for Box3d<S> {
+    fn rgba_mut(&mut self) -> &mut Option<LinSrgba> {
+        SetColor::rgba_mut(&mut self.solid)
+    }
+}
+
+impl<S> SetSolid<S> for Box3d<S> {
+    fn solid_options_mut(&mut self) -> &mut SolidOptions<S> {
+        SetSolid::solid_options_mut(&mut self.solid)
+    }
+}
+
+// Primitive conversions.
+
+impl<S> From<Box3d<S>> for Primitive<S> {
+    fn from(prim: Box3d<S>) -> Self {
+        Primitive::Box3d(prim)
+    }
+}
+
+impl<S> Into<Option<Box3d<S>>> for Primitive<S> {
+    fn into(self) -> Option<Box3d<S>> {
+        match self {
+            Primitive::Box3d(prim) => Some(prim),
+            _ => None,
+        }
+    }
+}
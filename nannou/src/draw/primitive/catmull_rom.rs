@@ -0,0 +1,195 @@
+use crate::color::LinSrgba;
+use crate::draw::primitive::path;
+use crate::draw::primitive::{PathStroke, Primitive};
+use crate::draw::properties::spatial::{orientation, position};
+use crate::draw::properties::{ColorScalar, SetColor, SetOrientation, SetPosition, SetStroke};
+use crate::draw::{self, Drawing};
+use crate::geom::{self, Point2};
+use crate::math::{BaseFloat, Zero};
+use lyon::tessellation::StrokeOptions;
+
+/// A smooth curve passing through each of its points, stroked along its length.
+///
+/// The spline is converted to a sequence of cubic Bézier segments which are flattened adaptively
+/// during tessellation according to the `stroke_tolerance`.
+#[derive(Clone, Debug)]
+pub struct CatmullRom<S = geom::scalar::Default> {
+    pub path: PathStroke<S>,
+    pub spline: geom::CatmullRom<Point2<S>>,
+}
+
+/// The drawing context for a Catmull-Rom spline.
+pub type DrawingCatmullRom<'a, S = geom::scalar::Default> = Drawing<'a, CatmullRom<S>, S>;
+
+impl<S> CatmullRom<S> {
+    /// Create a new **CatmullRom** primitive passing through the given points.
+    pub fn new(points: &[Point2<S>]) -> Self
+    where
+        S: Copy + Zero,
+    {
+        let path = Default::default();
+        let spline = geom::CatmullRom::new(points.iter().cloned());
+        CatmullRom { path, spline }
+    }
+
+    /// Short-hand for the `stroke_weight` method.
+    pub fn weight(self, weight: f32) -> Self {
+        self.map_path(|p| p.stroke_weight(weight))
+    }
+
+    /// Short-hand for the `stroke_tolerance` method.
+    pub fn tolerance(self, tolerance: f32) -> Self {
+        self.map_path(|p| p.stroke_tolerance(tolerance))
+    }
+
+    /// Specify the tension of the spline.
+    ///
+    /// `0.0` is loose, `0.5` is a standard Catmull-Rom spline and `1.0` is linear.
+    pub fn tension(mut self, tension: f32) -> Self {
+        self.spline.tension = tension;
+        self
+    }
+
+    /// Join the last point back to the first, producing a closed loop.
+    pub fn looped(mut self) -> Self {
+        self.spline.looped = true;
+        self
+    }
+
+    // Map the inner `PathStroke<S>` using the given function.
+    fn map_path<F>(mut self, map: F) -> Self
+    where
+        F: FnOnce(PathStroke<S>) -> PathStroke<S>,
+    {
+        self.path = map(self.path);
+        self
+    }
+}
+
+impl<'a, S> DrawingCatmullRom<'a, S>
+where
+    S: BaseFloat,
+{
+    /// Short-hand for the `stroke_weight` method.
+    pub fn weight(self, weight: f32) -> Self {
+        self.map_ty(|ty| ty.weight(weight))
+    }
+
+    /// Short-hand for the `stroke_tolerance` method.
+    pub fn tolerance(self, tolerance: f32) -> Self {
+        self.map_ty(|ty| ty.tolerance(tolerance))
+    }
+
+    /// Specify the tension of the spline.
+    ///
+    /// `0.0` is loose, `0.5` is a standard Catmull-Rom spline and `1.0` is linear.
+    pub fn tension(self, tension: f32) -> Self {
+        self.map_ty(|ty| ty.tension(tension))
+    }
+
+    /// Join the last point back to the first, producing a closed loop.
+    pub fn looped(self) -> Self {
+        self.map_ty(|ty| ty.looped())
+    }
+}
+
+impl<S> SetStroke for CatmullRom<S> {
+    fn stroke_options_mut(&mut self) -> &mut StrokeOptions {
+        SetStroke::stroke_options_mut(&mut self.path)
+    }
+}
+
+impl<S> SetOrientation<S> for CatmullRom<S> {
+    fn properties(&mut self) -> &mut orientation::Properties<S> {
+        SetOrientation::properties(&mut self.path)
+    }
+}
+
+impl<S> SetPosition<S> for CatmullRom<S> {
+    fn properties(&mut self) -> &mut position::Properties<S> {
+        SetPosition::properties(&mut self.path)
+    }
+}
+
+impl<S> SetColor<ColorScalar> for CatmullRom<S> {
+    fn rgba_mut(&mut self) -> &mut Option<LinSrgba> {
+        SetColor::rgba_mut(&mut self.path)
+    }
+}
+
+impl<S> From<CatmullRom<S>> for Primitive<S> {
+    fn from(prim: CatmullRom<S>) -> Self {
+        Primitive::CatmullRom(prim)
+    }
+}
+
+impl<S> Into<Option<CatmullRom<S>>> for Primitive<S> {
+    fn into(self) -> Option<CatmullRom<S>> {
+        match self {
+            Primitive::CatmullRom(prim) => Some(prim),
+            _ => None,
+        }
+    }
+}
+
+impl draw::renderer::RenderPrimitive for CatmullRom<f32> {
+    fn render_primitive(
+        self,
+        mut ctxt: draw::renderer::RenderContext,
+        mesh: &mut draw::Mesh,
+    ) -> draw::renderer::PrimitiveRender {
+        let CatmullRom { path, spline } = self;
+        let segments = spline.to_bezier_segments();
+        if segments.is_empty() {
+            return draw::renderer::PrimitiveRender::default();
+        }
+        let to_lyon = |p: Point2| lyon::math::point(p.x, p.y);
+        let first = to_lyon(segments[0].from);
+        let begin = lyon::path::PathEvent::Begin { at: first };
+        let cubics = segments.iter().map(|s| lyon::path::PathEvent::Cubic {
+            from: to_lyon(s.from),
+            ctrl1: to_lyon(s.ctrl1),
+            ctrl2: to_lyon(s.ctrl2),
+            to: to_lyon(s.to),
+        });
+        let last = to_lyon(segments[segments.len() - 1].to);
+        let end = lyon::path::PathEvent::End {
+            last,
+            first,
+            close: spline.looped,
+        };
+        let events = Some(begin).into_iter().chain(cubics).chain(Some(end));
+
+        // Determine the transform to apply to all points.
+        let global_transform = ctxt.transform;
+        let local_transform = path.position.transform() * path.orientation.transform();
+        let transform = global_transform * local_transform;
+
+        path::render_path_events(
+            events,
+            path.color,
+            transform,
+            path::Options::Stroke(path.opts),
+            &ctxt.theme,
+            &draw::theme::Primitive::Path,
+            &mut ctxt.fill_tessellator,
+            &mut ctxt.stroke_tessellator,
+            ctxt.svg.as_deref_mut(),
+            mesh,
+        );
+
+        draw::renderer::PrimitiveRender::default()
+    }
+}
+
+impl<S> Default for CatmullRom<S>
+where
+    S: Zero,
+{
+    fn default() -> Self {
+        CatmullRom {
+            path: Default::default(),
+            spline: geom::CatmullRom::new(None),
+        }
+    }
+}
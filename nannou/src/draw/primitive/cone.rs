@@ -0,0 +1,145 @@
+use crate::draw::primitive::solid::{SetSolid, SolidOptions};
+use crate::draw::primitive::Primitive;
+use crate::draw::properties::spatial::{orientation, position};
+use crate::draw::properties::{ColorScalar, LinSrgba, SetColor, SetOrientation, SetPosition};
+use crate::draw::{self, Drawing};
+use crate::geom;
+use crate::math::BaseFloat;
+
+/// Properties related to drawing a **Cone**.
+///
+/// The cone is triangulated with vertex normals and aligned with the *y* axis.
+#[derive(Clone, Debug)]
+pub struct Cone<S = geom::scalar::Default> {
+    radius: Option<S>,
+    height: Option<S>,
+    resolution: Option<u32>,
+    solid: SolidOptions<S>,
+}
+
+/// The drawing context for a **Cone**.
+pub type DrawingCone<'a, S = geom::scalar::Default> = Drawing<'a, Cone<S>, S>;
+
+// Cone-specific methods.
+
+impl<S> Cone<S>
+where
+    S: BaseFloat,
+{
+    /// Specify the radius of the base of the cone.
+    pub fn radius(mut self, radius: S) -> Self {
+        self.radius = Some(radius);
+        self
+    }
+
+    /// Specify the height of the cone along its *y* axis, from the base to the apex.
+    pub fn height(mut self, height: S) -> Self {
+        self.height = Some(height);
+        self
+    }
+
+    /// The number of segments around the base of the cone.
+    pub fn resolution(mut self, resolution: u32) -> Self {
+        self.resolution = Some(resolution);
+        self
+    }
+}
+
+// Trait implementations.
+
+impl draw::renderer::RenderPrimitive for Cone<f32> {
+    fn render_primitive(
+        self,
+        ctxt: draw::renderer::RenderContext,
+        mesh: &mut draw::Mesh,
+    ) -> draw::renderer::PrimitiveRender {
+        let Cone {
+            radius,
+            height,
+            resolution,
+            solid,
+        } = self;
+        let radius = radius.unwrap_or(50.0);
+        let height = height.unwrap_or(100.0);
+        let resolution = resolution.unwrap_or(32);
+        let cone = geom::solid::cone(radius, height, resolution);
+        solid.render(cone, ctxt, &draw::theme::Primitive::Cone, mesh)
+    }
+}
+
+impl<S> Default for Cone<S>
+where
+    S: BaseFloat,
+{
+    fn default() -> Self {
+        Cone {
+            radius: None,
+            height: None,
+            resolution: None,
+            solid: Default::default(),
+        }
+    }
+}
+
+impl<S> SetOrientation<S> for Cone<S> {
+    fn properties(&mut self) -> &mut orientation::Properties<S> {
+        SetOrientation::properties(&mut self.solid)
+    }
+}
+
+impl<S> SetPosition<S> for Cone<S> {
+    fn properties(&mut self) -> &mut position::Properties<S> {
+        SetPosition::properties(&mut self.solid)
+    }
+}
+
+impl<S> SetColor<ColorScalar> for Cone<S> {
+    fn rgba_mut(&mut self) -> &mut Option<LinSrgba> {
+        SetColor::rgba_mut(&mut self.solid)
+    }
+}
+
+impl<S> SetSolid<S> for Cone<S> {
+    fn solid_options_mut(&mut self) -> &mut SolidOptions<S> {
+        SetSolid::solid_options_mut(&mut self.solid)
+    }
+}
+
+// Primitive conversions.
+
+impl<S> From<Cone<S>> for Primitive<S> {
+    fn from(prim: Cone<S>) -> Self {
+        Primitive::Cone(prim)
+    }
+}
+
+impl<S> Into<Option<Cone<S>>> for Primitive<S> {
+    fn into(self) -> Option<Cone<S>> {
+        match self {
+            Primitive::Cone(prim) => Some(prim),
+            _ => None,
+        }
+    }
+}
+
+// Drawing methods.
+
+impl<'a, S> DrawingCone<'a, S>
+where
+    S: BaseFloat,
+{
+    /// Specify the radius of the base of the cone.
+    pub fn radius(self, radius: S) -> Self {
+        self.map_ty(|ty| ty.radius(radius))
+    }
+
+    /// Specify the height of the cone along its *y* axis, from the base to the apex.
+    pub fn height(self, height: S) -> Self {
+        self.map_ty(|ty| ty.height(height))
+    }
+
+    /// The number of segments around the base of the cone.
+    pub fn resolution(self, resolution: u32) -> Self {
+        self.map_ty(|ty| ty.resolution(resolution))
+    }
+}
@@ -0,0 +1,180 @@
+use crate::color::LinSrgba;
+use crate::draw::primitive::path;
+use crate::draw::primitive::{PathStroke, Primitive};
+use crate::draw::properties::spatial::{orientation, position};
+use crate::draw::properties::{ColorScalar, SetColor, SetOrientation, SetPosition, SetStroke};
+use crate::draw::{self, Drawing};
+use crate::geom::{self, Point2};
+use crate::math::BaseFloat;
+use lyon::path::PathEvent;
+use lyon::tessellation::StrokeOptions;
+
+/// The iso-contours of a sampled scalar field, stroked along their length.
+///
+/// The field is sampled upon creation, while the contour lines for each level are produced via
+/// marching squares during rendering. See `geom::contour` for details.
+#[derive(Clone, Debug)]
+pub struct Contour<S = geom::scalar::Default> {
+    pub path: PathStroke<S>,
+    pub grid: geom::ScalarGrid<S>,
+    pub levels: Vec<S>,
+}
+
+/// The drawing context for the contours of a scalar field.
+pub type DrawingContour<'a, S = geom::scalar::Default> = Drawing<'a, Contour<S>, S>;
+
+impl<S> Contour<S>
+where
+    S: BaseFloat,
+{
+    /// Create a new **Contour** primitive drawing the given levels of the sampled field.
+    pub fn new(grid: geom::ScalarGrid<S>, levels: &[S]) -> Self {
+        let path = Default::default();
+        let levels = levels.to_vec();
+        Contour { path, grid, levels }
+    }
+
+    /// Short-hand for the `stroke_weight` method.
+    pub fn weight(self, weight: f32) -> Self {
+        self.map_path(|p| p.stroke_weight(weight))
+    }
+
+    /// Short-hand for the `stroke_tolerance` method.
+    pub fn tolerance(self, tolerance: f32) -> Self {
+        self.map_path(|p| p.stroke_tolerance(tolerance))
+    }
+
+    /// Whether or not crossing positions are interpolated between samples.
+    ///
+    /// Enabled by default. When disabled, lines follow the grid producing jagged edges.
+    pub fn interpolate(mut self, interpolate: bool) -> Self {
+        self.grid = self.grid.interpolate(interpolate);
+        self
+    }
+
+    // Map the inner `PathStroke<S>` using the given function.
+    fn map_path<F>(mut self, map: F) -> Self
+    where
+        F: FnOnce(PathStroke<S>) -> PathStroke<S>,
+    {
+        self.path = map(self.path);
+        self
+    }
+}
+
+impl<'a, S> DrawingContour<'a, S>
+where
+    S: BaseFloat,
+{
+    /// Short-hand for the `stroke_weight` method.
+    pub fn weight(self, weight: f32) -> Self {
+        self.map_ty(|ty| ty.weight(weight))
+    }
+
+    /// Short-hand for the `stroke_tolerance` method.
+    pub fn tolerance(self, tolerance: f32) -> Self {
+        self.map_ty(|ty| ty.tolerance(tolerance))
+    }
+
+    /// Whether or not crossing positions are interpolated between samples.
+    ///
+    /// Enabled by default. When disabled, lines follow the grid producing jagged edges.
+    pub fn interpolate(self, interpolate: bool) -> Self {
+        self.map_ty(|ty| ty.interpolate(interpolate))
+    }
+}
+
+impl<S> SetStroke for Contour<S> {
+    fn stroke_options_mut(&mut self) -> &mut StrokeOptions {
+        SetStroke::stroke_options_mut(&mut self.path)
+    }
+}
+
+impl<S> SetOrientation<S> for Contour<S> {
+    fn properties(&mut self) -> &mut orientation::Properties<S> {
+        SetOrientation::properties(&mut self.path)
+    }
+}
+
+impl<S> SetPosition<S> for Contour<S> {
+    fn properties(&mut self) -> &mut position::Properties<S> {
+        SetPosition::properties(&mut self.path)
+    }
+}
+
+impl<S> SetColor<ColorScalar> for Contour<S> {
+    fn rgba_mut(&mut self) -> &mut Option<LinSrgba> {
+        SetColor::rgba_mut(&mut self.path)
+    }
+}
+
+impl<S> From<Contour<S>> for Primitive<S> {
+    fn from(prim: Contour<S>) -> Self {
+        Primitive::Contour(prim)
+    }
+}
+
+impl<S> Into<Option<Contour<S>>> for Primitive<S> {
+    fn into(self) -> Option<Contour<S>> {
+        match self {
+            Primitive::Contour(prim) => Some(prim),
+            _ => None,
+        }
+    }
+}
+
+impl draw::renderer::RenderPrimitive for Contour<f32> {
+    fn render_primitive(
+        self,
+        mut ctxt: draw::renderer::RenderContext,
+        mesh: &mut draw::Mesh,
+    ) -> draw::renderer::PrimitiveRender {
+        let Contour { path, grid, levels } = self;
+        let lines: Vec<_> = levels
+            .iter()
+            .flat_map(|&level| grid.contour(level))
+            .filter(|line| line.points.len() >= 2)
+            .collect();
+        if lines.is_empty() {
+            return draw::renderer::PrimitiveRender::default();
+        }
+
+        // Each line becomes a sub-path of a single path.
+        let to_lyon = |p: &Point2| lyon::math::point(p.x, p.y);
+        let events = lines.iter().flat_map(|line| {
+            let first = to_lyon(&line.points[0]);
+            let last = to_lyon(&line.points[line.points.len() - 1]);
+            let begin = PathEvent::Begin { at: first };
+            let segments = line.points.windows(2).map(move |w| PathEvent::Line {
+                from: to_lyon(&w[0]),
+                to: to_lyon(&w[1]),
+            });
+            let end = PathEvent::End {
+                last,
+                first,
+                close: line.closed,
+            };
+            Some(begin).into_iter().chain(segments).chain(Some(end))
+        });
+
+        // Determine the transform to apply to all points.
+        let global_transform = ctxt.transform;
+        let local_transform = path.position.transform() * path.orientation.transform();
+        let transform = global_transform * local_transform;
+
+        path::render_path_events(
+            events,
+            path.color,
+            transform,
+            path::Options::Stroke(path.opts),
+            &ctxt.theme,
+            &draw::theme::Primitive::Path,
+            &mut ctxt.fill_tessellator,
+            &mut ctxt.stroke_tessellator,
+            ctxt.svg.as_deref_mut(),
+            mesh,
+        );
+
+        draw::renderer::PrimitiveRender::default()
+    }
+}
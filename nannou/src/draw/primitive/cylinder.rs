@@ -0,0 +1,145 @@
+use crate::draw::primitive::solid::{SetSolid, SolidOptions};
+use crate::draw::primitive::Primitive;
+use crate::draw::properties::spatial::{orientation, position};
+use crate::draw::properties::{ColorScalar, LinSrgba, SetColor, SetOrientation, SetPosition};
+use crate::draw::{self, Drawing};
+use crate::geom;
+use crate::math::BaseFloat;
+
+/// Properties related to drawing a **Cylinder**.
+///
+/// The cylinder is triangulated with vertex normals and aligned with the *y* axis.
+#[derive(Clone, Debug)]
+pub struct Cylinder<S = geom::scalar::Default> {
+    radius: Option<S>,
+    height: Option<S>,
+    resolution: Option<u32>,
+    solid: SolidOptions<S>,
+}
+
+/// The drawing context for a **Cylinder**.
+pub type DrawingCylinder<'a, S = geom::scalar::Default> = Drawing<'a, Cylinder<S>, S>;
+
+// Cylinder-specific methods.
+
+impl<S> Cylinder<S>
+where
+    S: BaseFloat,
+{
+    /// Specify the radius of the cylinder.
+    pub fn radius(mut self, radius: S) -> Self {
+        self.radius = Some(radius);
+        self
+    }
+
+    /// Specify the height of the cylinder along its *y* axis.
+    pub fn height(mut self, height: S) -> Self {
+        self.height = Some(height);
+        self
+    }
+
+    /// The number of segments around the circumference of the cylinder.
+    pub fn resolution(mut self, resolution: u32) -> Self {
+        self.resolution = Some(resolution);
+        self
+    }
+}
+
+// Trait implementations.
+
+impl draw::renderer::RenderPrimitive for Cylinder<f32> {
+    fn render_primitive(
+        self,
+        ctxt: draw::renderer::RenderContext,
+        mesh: &mut draw::Mesh,
+    ) -> draw::renderer::PrimitiveRender {
+        let Cylinder {
+            radius,
+            height,
+            resolution,
+            solid,
+        } = self;
+        let radius = radius.unwrap_or(50.0);
+        let height = height.unwrap_or(100.0);
+        let resolution = resolution.unwrap_or(32);
+        let cylinder = geom::solid::cylinder(radius, height, resolution);
+        solid.render(cylinder, ctxt, &draw::theme::Primitive::Cylinder, mesh)
+    }
+}
+
+impl<S> Default for Cylinder<S>
+where
+    S: BaseFloat,
+{
+    fn default() -> Self {
+        Cylinder {
+            radius: None,
+            height: None,
+            resolution: None,
+            solid: Default::default(),
+        }
+    }
+}
+
+impl<S> SetOrientation<S> for Cylinder<S> {
+    fn properties(&mut self) -> &mut orientation::Properties<S> {
+        SetOrientation::properties(&mut self.solid)
+    }
+}
+
+impl<S> SetPosition<S> for Cylinder<S> {
+    fn properties(&mut self) -> &mut position::Properties<S> {
+        SetPosition::properties(&mut self.solid)
+    }
+}
+
+impl<S> SetColor<ColorScalar> for Cylinder<S> {
+    fn rgba_mut(&mut self) -> &mut Option<LinSrgba> {
+        SetColor::rgba_mut(&mut self.solid)
+    }
+}
+
+impl<S> SetSolid<S> for Cylinder<S> {
+    fn solid_options_mut(&mut self) -> &mut SolidOptions<S> {
+        SetSolid::solid_options_mut(&mut self.solid)
+    }
+}
+
+// Primitive conversions.
+
+impl<S> From<Cylinder<S>> for Primitive<S> {
+    fn from(prim: Cylinder<S>) -> Self {
+        Primitive::Cylinder(prim)
+    }
+}
+
+impl<S> Into<Option<Cylinder<S>>> for Primitive<S> {
+    fn into(self) -> Option<Cylinder<S>> {
+        match self {
+            Primitive::Cylinder(prim) => Some(prim),
+            _ => None,
+        }
+    }
+}
+
+// Drawing methods.
+
+impl<'a, S> DrawingCylinder<'a, S>
+where
+    S: BaseFloat,
+{
+    /// Specify the radius of the cylinder.
+    pub fn radius(self, radius: S) -> Self {
+        self.map_ty(|ty| ty.radius(radius))
+    }
+
+    /// Specify the height of the cylinder along its *y* axis.
+    pub fn height(self, height: S) -> Self {
+        self.map_ty(|ty| ty.height(height))
+    }
+
+    /// The number of segments around the circumference of the cylinder.
+    pub fn resolution(self, resolution: u32) -> Self {
+        self.map_ty(|ty| ty.resolution(resolution))
+    }
+}
@@ -0,0 +1,71 @@
+use crate::draw::gpu_mesh::Snapshot;
+use crate::draw::primitive::Primitive;
+use crate::draw::{self, GpuMesh as GpuMeshData};
+use crate::geom;
+use crate::wgpu;
+
+/// A **GpuMesh** drawn from the vertex data retained on the GPU.
+///
+/// Produced via `Draw::gpu_mesh` or `Draw::gpu_mesh_textured`.
+#[derive(Clone, Debug)]
+pub struct GpuMesh {
+    pub(crate) mesh: Snapshot,
+    pub(crate) texture_view: Option<wgpu::TextureView>,
+}
+
+impl GpuMesh {
+    pub(crate) fn new(mesh: &GpuMeshData, texture_view: Option<wgpu::TextureView>) -> Self {
+        let mesh = mesh.snapshot();
+        GpuMesh { mesh, texture_view }
+    }
+
+    /// The way in which the mesh is rendered, used by the **Renderer**.
+    pub(crate) fn primitive_render(&self) -> draw::renderer::PrimitiveRender {
+        match self.texture_view {
+            Some(ref view) => draw::renderer::PrimitiveRender::texture(view.clone()),
+            None => draw::renderer::PrimitiveRender::color(),
+        }
+    }
+}
+
+impl draw::renderer::RenderPrimitive for GpuMesh {
+    // The **Renderer** draws the mesh from its retained GPU buffers. This is used for recording
+    // the mesh in cases where the vertices must be available on the CPU, e.g. for SVG export.
+    fn render_primitive(
+        self,
+        ctxt: draw::renderer::RenderContext,
+        mesh: &mut draw::Mesh,
+    ) -> draw::renderer::PrimitiveRender {
+        let render = self.primitive_render();
+        let data = &self.mesh.data;
+        let transform = ctxt.transform;
+        let transform_point = |p: geom::Point3| -> geom::Point3 {
+            let p = cgmath::Point3::new(p.x, p.y, p.z);
+            let p = cgmath::Transform::transform_point(transform, p);
+            p.into()
+        };
+        let start = mesh.raw_vertex_count() as u32;
+        let vertices = (0..data.positions.len()).map(|i| {
+            let point = transform_point(data.positions[i]);
+            ((point, data.colors[i]), data.tex_coords[i]).into()
+        });
+        let indices = data.indices.iter().map(|&i| start + i);
+        mesh.extend(vertices, indices);
+        render
+    }
+}
+
+impl<S> From<GpuMesh> for Primitive<S> {
+    fn from(prim: GpuMesh) -> Self {
+        Primitive::GpuMesh(prim)
+    }
+}
+
+impl<S> Into<Option<GpuMesh>> for Primitive<S> {
+    fn into(self) -> Option<GpuMesh> {
+        match self {
+            Primitive::GpuMesh(prim) => Some(prim),
+            _ => None,
+        }
+    }
+}
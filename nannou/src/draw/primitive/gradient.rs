@@ -0,0 +1,266 @@
+use crate::color::{LinSrgba, Srgba};
+use crate::draw;
+use crate::draw::primitive::image::{ImageData, ImageKey};
+use crate::draw::primitive::Primitive;
+use crate::draw::renderer::{PrimitiveRender, RenderPrimitive, VertexMode};
+use crate::geom::{self, Point2};
+use crate::math::{Matrix4, SquareMatrix};
+use crate::wgpu;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+/// The number of colours in the lookup table produced by **Gradient::lut**.
+pub const LUT_WIDTH: u32 = 256;
+
+/// A gradient with which the vertices of a primitive may be coloured.
+///
+/// Gradient coordinates are described in drawing space, i.e. the same space in which the position
+/// of the primitive is described, and respect the transform of the **Draw** instance used to draw
+/// the primitive.
+///
+/// Each stop is a position along the gradient from `0.0` to `1.0` along with the colour at that
+/// position. Stops are expected to be ordered by position.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Gradient<S = geom::scalar::Default> {
+    /// A gradient from `start` to `end`.
+    Linear {
+        start: Point2<S>,
+        end: Point2<S>,
+        stops: Vec<(f32, LinSrgba)>,
+    },
+    /// A gradient outward from `center`, ending at `radius`.
+    Radial {
+        center: Point2<S>,
+        radius: S,
+        stops: Vec<(f32, LinSrgba)>,
+    },
+}
+
+/// A primitive whose colours are produced by a gradient.
+///
+/// Produced upon finishing a **Drawing** for which `gradient` was specified.
+#[derive(Clone, Debug)]
+pub struct GradientFill<S = geom::scalar::Default> {
+    pub primitive: Box<Primitive<S>>,
+    pub gradient: Gradient<S>,
+    /// The texture view of the gradient's lookup table, set by the **Renderer** once uploaded.
+    pub(crate) lut: Option<wgpu::TextureView>,
+}
+
+impl<S> Gradient<S> {
+    /// The stops describing the colours along the gradient.
+    pub fn stops(&self) -> &[(f32, LinSrgba)] {
+        match *self {
+            Gradient::Linear { ref stops, .. } | Gradient::Radial { ref stops, .. } => stops,
+        }
+    }
+}
+
+impl<S> GradientFill<S> {
+    /// Fill the given primitive with the gradient.
+    pub fn new(primitive: Primitive<S>, gradient: Gradient<S>) -> Self {
+        GradientFill {
+            primitive: Box::new(primitive),
+            gradient,
+            lut: None,
+        }
+    }
+}
+
+impl Gradient<f32> {
+    /// The position along the gradient at the given point in drawing space.
+    ///
+    /// The result is clamped to the range `0.0` to `1.0`.
+    pub fn position_at(&self, point: Point2) -> f32 {
+        let position = match *self {
+            Gradient::Linear { start, end, .. } => linear_position(start, end, point),
+            Gradient::Radial { center, radius, .. } => {
+                let d = radial_coords(center, radius, point);
+                (d.x * d.x + d.y * d.y).sqrt()
+            }
+        };
+        position.max(0.0).min(1.0)
+    }
+
+    /// The colour of the gradient at the given point in drawing space.
+    pub fn color_at(&self, point: Point2) -> LinSrgba {
+        color_at_position(self.stops(), self.position_at(point))
+    }
+
+    /// A lookup table of the colours along the gradient.
+    ///
+    /// The table is a single row of `LUT_WIDTH` sRGB encoded pixels, where the centre of each
+    /// pixel describes the colour at the respective position along the gradient. The **Renderer**
+    /// samples this table per fragment so that the gradient remains smooth regardless of the
+    /// tessellation of the primitive. The data is keyed by its content, so the table is only
+    /// uploaded once for as long as the stops remain unchanged.
+    pub fn lut(&self) -> ImageData {
+        let stops = self.stops();
+        let c = |c: f32| (c.max(0.0).min(1.0) * 255.0).round() as u8;
+        let bytes: Vec<u8> = (0..LUT_WIDTH)
+            .flat_map(|i| {
+                let position = (i as f32 + 0.5) / LUT_WIDTH as f32;
+                let srgba = Srgba::from_linear(color_at_position(stops, position));
+                vec![c(srgba.red), c(srgba.green), c(srgba.blue), c(srgba.alpha)]
+            })
+            .collect();
+        let mut hasher = DefaultHasher::new();
+        bytes.hash(&mut hasher);
+        ImageData {
+            key: ImageKey::Content(hasher.finish()),
+            width: LUT_WIDTH,
+            height: 1,
+            format: ImageData::IMAGE_FORMAT,
+            bytes: Arc::new(bytes),
+        }
+    }
+}
+
+impl RenderPrimitive for GradientFill<f32> {
+    fn render_primitive(
+        self,
+        ctxt: draw::renderer::RenderContext,
+        mesh: &mut draw::Mesh,
+    ) -> PrimitiveRender {
+        let GradientFill {
+            primitive,
+            gradient,
+            lut,
+        } = self;
+
+        // Render the primitive into its own mesh so that its vertices may be recoloured.
+        let inverse_transform = ctxt.transform.invert().unwrap_or_else(Matrix4::identity);
+        let mut primitive_mesh = draw::Mesh::default();
+        let render = (*primitive).render_primitive(ctxt, &mut primitive_mesh);
+
+        let local_points: Vec<_> = primitive_mesh
+            .points()
+            .iter()
+            .map(|p| {
+                let local = cgmath::Transform::transform_point(&inverse_transform, (*p).into());
+                Point2 {
+                    x: local.x,
+                    y: local.y,
+                }
+            })
+            .collect();
+        let offset = mesh.points().len() as u32;
+        let indices: Vec<_> = primitive_mesh
+            .indices()
+            .iter()
+            .map(|i| i + offset)
+            .collect();
+
+        // Primitives that are already textured or rendered as text retain their vertex mode, in
+        // which case the gradient is evaluated per vertex. Otherwise the gradient's lookup table
+        // is sampled per fragment using texture coordinates that vary linearly across each
+        // triangle.
+        match (lut, render.vertex_mode, render.sdf.is_some()) {
+            (Some(lut), VertexMode::Color, false) => {
+                let (tex_coords, vertex_mode): (Vec<_>, _) = match gradient {
+                    Gradient::Linear { start, end, .. } => {
+                        let tex_coords = local_points
+                            .iter()
+                            .map(|&p| Point2 {
+                                x: linear_position(start, end, p),
+                                y: 0.5,
+                            })
+                            .collect();
+                        (tex_coords, VertexMode::Texture)
+                    }
+                    Gradient::Radial { center, radius, .. } => {
+                        let tex_coords = local_points
+                            .iter()
+                            .map(|&p| radial_coords(center, radius, p))
+                            .collect();
+                        (tex_coords, VertexMode::RadialGradient)
+                    }
+                };
+                let colors = vec![draw::mesh::vertex::DEFAULT_VERTEX_COLOR; tex_coords.len()];
+                mesh.extend_from_slices(primitive_mesh.points(), &indices, &colors, &tex_coords);
+                PrimitiveRender {
+                    texture_view: Some(lut),
+                    vertex_mode,
+                    sdf: None,
+                }
+            }
+            _ => {
+                let colors: Vec<_> = local_points.iter().map(|&p| gradient.color_at(p)).collect();
+                mesh.extend_from_slices(
+                    primitive_mesh.points(),
+                    &indices,
+                    &colors,
+                    primitive_mesh.tex_coords(),
+                );
+                render
+            }
+        }
+    }
+}
+
+impl<S> From<GradientFill<S>> for Primitive<S> {
+    fn from(prim: GradientFill<S>) -> Self {
+        Primitive::GradientFill(prim)
+    }
+}
+
+impl<S> Into<Option<GradientFill<S>>> for Primitive<S> {
+    fn into(self) -> Option<GradientFill<S>> {
+        match self {
+            Primitive::GradientFill(prim) => Some(prim),
+            _ => None,
+        }
+    }
+}
+
+// The unclamped position of the point along the linear gradient from `start` to `end`.
+fn linear_position(start: Point2, end: Point2, point: Point2) -> f32 {
+    let direction = end - start;
+    let magnitude2 = direction.x * direction.x + direction.y * direction.y;
+    if magnitude2 == 0.0 {
+        return 0.0;
+    }
+    let d = point - start;
+    (d.x * direction.x + d.y * direction.y) / magnitude2
+}
+
+// The offset of the point from the center of the radial gradient, normalised by its radius.
+fn radial_coords(center: Point2, radius: f32, point: Point2) -> Point2 {
+    if radius == 0.0 {
+        return Point2 { x: 0.0, y: 0.0 };
+    }
+    (point - center) / radius
+}
+
+// Linearly interpolate between the stops at the given position.
+fn color_at_position(stops: &[(f32, LinSrgba)], position: f32) -> LinSrgba {
+    let mut stops = stops.iter();
+    let mut prev = match stops.next() {
+        None => return draw::mesh::vertex::DEFAULT_VERTEX_COLOR,
+        Some(&stop) => stop,
+    };
+    if position <= prev.0 {
+        return prev.1;
+    }
+    for &next in stops {
+        if position <= next.0 {
+            let range = next.0 - prev.0;
+            let t = if range > 0.0 {
+                (position - prev.0) / range
+            } else {
+                0.0
+            };
+            let lerp = |a: f32, b: f32| a + (b - a) * t;
+            let (a, b) = (prev.1, next.1);
+            return LinSrgba::new(
+                lerp(a.red, b.red),
+                lerp(a.green, b.green),
+                lerp(a.blue, b.blue),
+                lerp(a.alpha, b.alpha),
+            );
+        }
+        prev = next;
+    }
+    prev.1
+}
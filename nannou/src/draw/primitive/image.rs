@@ -0,0 +1,200 @@
+use crate::draw::primitive::{Primitive, Texture};
+use crate::draw::properties::spatial::{self, dimension, orientation, position};
+use crate::draw::properties::{SetDimensions, SetOrientation, SetPosition};
+use crate::draw::Drawing;
+use crate::geom;
+use crate::image;
+use crate::math::BaseFloat;
+use crate::wgpu;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+/// Properties related to drawing an **Image** that has not yet been uploaded to the GPU.
+///
+/// The image data is uploaded to a texture by the **Renderer** upon rendering. Textures are
+/// cached between frames so that images whose content does not change are only uploaded once.
+#[derive(Clone, Debug)]
+pub struct Image<S = geom::scalar::Default> {
+    data: ImageData,
+    spatial: spatial::Properties<S>,
+    area: geom::Rect,
+}
+
+/// The raw image data along with the information required to upload it to a texture.
+#[derive(Clone, Debug)]
+pub struct ImageData {
+    /// Describes how the texture for this data should be cached.
+    pub key: ImageKey,
+    /// The width of the image in pixels.
+    pub width: u32,
+    /// The height of the image in pixels.
+    pub height: u32,
+    /// The format of the texture to which the data will be uploaded.
+    pub format: wgpu::TextureFormat,
+    /// The raw bytes of the image.
+    pub bytes: Arc<Vec<u8>>,
+}
+
+/// Describes how the texture for an image should be cached by the **Renderer**.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ImageKey {
+    /// The texture is cached by a hash of the image content and only uploaded when the content of
+    /// the image changes.
+    Content(u64),
+    /// The texture is re-uploaded every frame. The texture itself is re-used between frames
+    /// where possible.
+    Raw,
+}
+
+/// The drawing context for an **Image**.
+pub type DrawingImage<'a, S = geom::scalar::Default> = Drawing<'a, Image<S>, S>;
+
+impl ImageData {
+    /// The texture format used for images loaded via `from_image`.
+    pub const IMAGE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+    /// Convert the given image to RGBA and key it by a hash of its content.
+    pub fn from_image(image: &image::DynamicImage) -> Self {
+        let rgba = image.to_rgba();
+        let (width, height) = rgba.dimensions();
+        let bytes = rgba.into_raw();
+        let mut hasher = DefaultHasher::new();
+        (width, height).hash(&mut hasher);
+        bytes.hash(&mut hasher);
+        let key = ImageKey::Content(hasher.finish());
+        let format = Self::IMAGE_FORMAT;
+        let bytes = Arc::new(bytes);
+        ImageData {
+            key,
+            width,
+            height,
+            format,
+            bytes,
+        }
+    }
+
+    /// Raw data that should be uploaded every frame.
+    ///
+    /// **Panics** if the length of `data` does not match the size of a texture with the given
+    /// dimensions and format.
+    pub fn from_raw(data: &[u8], width: u32, height: u32, format: wgpu::TextureFormat) -> Self {
+        let size_bytes =
+            width as usize * height as usize * wgpu::texture_format_size_bytes(format) as usize;
+        assert_eq!(
+            data.len(),
+            size_bytes,
+            "raw image data does not match the given dimensions and format"
+        );
+        let key = ImageKey::Raw;
+        let bytes = Arc::new(data.to_vec());
+        ImageData {
+            key,
+            width,
+            height,
+            format,
+            bytes,
+        }
+    }
+}
+
+impl<S> Image<S>
+where
+    S: BaseFloat,
+{
+    pub(crate) fn new(data: ImageData) -> Self {
+        let w = S::from(data.width).unwrap();
+        let h = S::from(data.height).unwrap();
+        let spatial = spatial::Properties::default().w_h(w, h);
+        let x = geom::Range {
+            start: 0.0,
+            end: 1.0,
+        };
+        let y = geom::Range {
+            start: 0.0,
+            end: 1.0,
+        };
+        let area = geom::Rect { x, y };
+        Self {
+            data,
+            spatial,
+            area,
+        }
+    }
+}
+
+impl<S> Image<S> {
+    /// Specify the area of the image to draw.
+    ///
+    /// The bounds of the rectangle should represent the desired area as texture coordinates of the
+    /// image, ranging from (0.0, 0.0) in the bottom left to (1.0, 1.0) in the top right.
+    ///
+    /// By default, the area represents the full extent of the image.
+    pub fn area(mut self, rect: geom::Rect) -> Self {
+        self.area = rect;
+        self
+    }
+
+    /// The image data that will be uploaded during rendering.
+    pub fn data(&self) -> &ImageData {
+        &self.data
+    }
+
+    /// Convert the image into a **Texture** primitive that refers to the given view.
+    ///
+    /// This is used by the **Renderer** once the image has been uploaded.
+    pub(crate) fn into_texture(self, texture_view: wgpu::TextureView) -> Texture<S> {
+        let Image { spatial, area, .. } = self;
+        Texture::from_parts(texture_view, spatial, area)
+    }
+}
+
+impl<'a, S> DrawingImage<'a, S>
+where
+    S: BaseFloat,
+{
+    /// Specify the area of the image to draw.
+    ///
+    /// The bounds of the rectangle should represent the desired area as texture coordinates of the
+    /// image, ranging from (0.0, 0.0) in the bottom left to (1.0, 1.0) in the top right.
+    ///
+    /// By default, the area represents the full extent of the image.
+    pub fn area(self, rect: geom::Rect) -> Self {
+        self.map_ty(|ty| ty.area(rect))
+    }
+}
+
+impl<S> SetOrientation<S> for Image<S> {
+    fn properties(&mut self) -> &mut orientation::Properties<S> {
+        SetOrientation::properties(&mut self.spatial)
+    }
+}
+
+impl<S> SetPosition<S> for Image<S> {
+    fn properties(&mut self) -> &mut position::Properties<S> {
+        SetPosition::properties(&mut self.spatial)
+    }
+}
+
+impl<S> SetDimensions<S> for Image<S> {
+    fn properties(&mut self) -> &mut dimension::Properties<S> {
+        SetDimensions::properties(&mut self.spatial)
+    }
+}
+
+// Primitive conversions.
+
+impl<S> From<Image<S>> for Primitive<S> {
+    fn from(prim: Image<S>) -> Self {
+        Primitive::Image(prim)
+    }
+}
+
+impl<S> Into<Option<Image<S>>> for Primitive<S> {
+    fn into(self) -> Option<Image<S>> {
+        match self {
+            Primitive::Image(prim) => Some(prim),
+            _ => None,
+        }
+    }
+}
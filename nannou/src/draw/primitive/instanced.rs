@@ -0,0 +1,202 @@
+use crate::color::{IntoLinSrgba, LinSrgba};
+use crate::draw::primitive::{self, Primitive};
+use crate::draw::properties::ColorScalar;
+use crate::draw::{self, Drawing};
+use crate::geom::{self, Point3};
+use crate::math::{BaseFloat, Matrix4, Rad, SquareMatrix};
+
+/// The properties of a single instance of an **Instanced** primitive.
+///
+/// The final transform of the instance is `translation * rotation * scale * transform`, applied
+/// on top of the transform of the **Draw** instance used to draw the primitive.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Instance<S = geom::scalar::Default> {
+    /// The position of the instance.
+    pub position: Point3<S>,
+    /// The rotation of the instance around the z axis in radians.
+    pub rotation: S,
+    /// The uniform scale of the instance.
+    pub scale: S,
+    /// An additional transform, applied before the position, rotation and scale.
+    pub transform: Matrix4<S>,
+    /// Multiplied with the colours of the primitive's vertices.
+    pub color: LinSrgba,
+}
+
+/// A primitive that is drawn once for each of its instances via a single instanced draw call.
+///
+/// Produced upon finishing a **Drawing** begun via an **InstancedDraw**.
+#[derive(Clone, Debug)]
+pub struct Instanced<S = geom::scalar::Default> {
+    pub primitive: Box<Primitive<S>>,
+    pub instances: Vec<Instance<S>>,
+}
+
+/// A **Draw** wrapper for drawing a single primitive many times via GPU instancing.
+///
+/// Rather than tessellating the primitive once per instance, the primitive is tessellated once
+/// and drawn for every instance with a single draw call. Each instance may override its position,
+/// rotation, scale and colour, or be given an arbitrary transform.
+///
+/// Produced via `Draw::instanced`. The primitive is begun via one of the `rect`, `ellipse`,
+/// `arrow` or `mesh` methods, which return the **Drawing** used to describe the primitive as a whole.
+///
+/// ```ignore
+/// draw.instanced(positions.len() as u32)
+///     .positions(positions.iter().cloned())
+///     .colors(colors.iter().cloned())
+///     .rect()
+///     .w_h(4.0, 4.0);
+/// ```
+#[derive(Debug)]
+pub struct InstancedDraw<'a, S = geom::scalar::Default>
+where
+    S: 'a + BaseFloat,
+{
+    draw: &'a draw::Draw<S>,
+    instances: Vec<Instance<S>>,
+}
+
+impl<S> Instance<S>
+where
+    S: BaseFloat,
+{
+    /// The transform of the instance.
+    pub fn matrix(&self) -> Matrix4<S> {
+        let translation = Matrix4::from_translation(self.position);
+        let rotation = Matrix4::from_angle_z(Rad(self.rotation));
+        let scale = Matrix4::from_scale(self.scale);
+        translation * rotation * scale * self.transform
+    }
+}
+
+impl<'a, S> InstancedDraw<'a, S>
+where
+    S: BaseFloat,
+{
+    pub(crate) fn new(draw: &'a draw::Draw<S>, count: u32) -> Self {
+        let instances = (0..count).map(|_| Default::default()).collect();
+        InstancedDraw { draw, instances }
+    }
+
+    /// The number of instances that will be drawn.
+    pub fn count(&self) -> usize {
+        self.instances.len()
+    }
+
+    /// Specify the transform of each instance.
+    ///
+    /// Instances beyond the length of the iterator retain their current transform.
+    pub fn transforms<I>(self, transforms: I) -> Self
+    where
+        I: IntoIterator<Item = Matrix4<S>>,
+    {
+        self.map_instances(transforms, |instance, transform| {
+            instance.transform = transform
+        })
+    }
+
+    /// Specify the position of each instance.
+    pub fn positions<I>(self, positions: I) -> Self
+    where
+        I: IntoIterator<Item = Point3<S>>,
+    {
+        self.map_instances(positions, |instance, position| instance.position = position)
+    }
+
+    /// Specify the rotation of each instance around the z axis in radians.
+    pub fn rotations<I>(self, rotations: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+    {
+        self.map_instances(rotations, |instance, rotation| instance.rotation = rotation)
+    }
+
+    /// Specify the uniform scale of each instance.
+    pub fn scales<I>(self, scales: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+    {
+        self.map_instances(scales, |instance, scale| instance.scale = scale)
+    }
+
+    /// Specify the colour of each instance.
+    ///
+    /// The colour is multiplied with the colour of the primitive's vertices, so the primitive
+    /// should usually be left white.
+    pub fn colors<I>(self, colors: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: IntoLinSrgba<ColorScalar>,
+    {
+        self.map_instances(colors, |instance, color| {
+            instance.color = color.into_lin_srgba()
+        })
+    }
+
+    /// Begin drawing a **Rect** for each instance.
+    pub fn rect(self) -> Drawing<'a, primitive::Rect<S>, S> {
+        let InstancedDraw { draw, instances } = self;
+        draw.rect().instanced(instances)
+    }
+
+    /// Begin drawing an **Ellipse** for each instance.
+    pub fn ellipse(self) -> Drawing<'a, primitive::Ellipse<S>, S> {
+        let InstancedDraw { draw, instances } = self;
+        draw.ellipse().instanced(instances)
+    }
+
+    /// Begin drawing an **Arrow** for each instance.
+    pub fn arrow(self) -> Drawing<'a, primitive::Arrow<S>, S> {
+        let InstancedDraw { draw, instances } = self;
+        draw.arrow().instanced(instances)
+    }
+
+    /// Begin drawing a **Mesh** for each instance.
+    pub fn mesh(self) -> Drawing<'a, primitive::mesh::Vertexless, S> {
+        let InstancedDraw { draw, instances } = self;
+        draw.mesh().instanced(instances)
+    }
+
+    // Apply the given function to each instance paired with an element of the given iterator.
+    fn map_instances<I, F>(mut self, iter: I, mut f: F) -> Self
+    where
+        I: IntoIterator,
+        F: FnMut(&mut Instance<S>, I::Item),
+    {
+        for (instance, elem) in self.instances.iter_mut().zip(iter) {
+            f(instance, elem);
+        }
+        self
+    }
+}
+
+impl<S> Default for Instance<S>
+where
+    S: BaseFloat,
+{
+    fn default() -> Self {
+        Instance {
+            position: Point3::new(S::zero(), S::zero(), S::zero()),
+            rotation: S::zero(),
+            scale: S::one(),
+            transform: Matrix4::identity(),
+            color: LinSrgba::new(1.0, 1.0, 1.0, 1.0),
+        }
+    }
+}
+
+impl<S> From<Instanced<S>> for Primitive<S> {
+    fn from(prim: Instanced<S>) -> Self {
+        Primitive::Instanced(prim)
+    }
+}
+
+impl<S> Into<Option<Instanced<S>>> for Primitive<S> {
+    fn into(self) -> Option<Instanced<S>> {
+        match self {
+            Primitive::Instanced(prim) => Some(prim),
+            _ => None,
+        }
+    }
+}
@@ -17,6 +17,10 @@ pub struct Line<S = geom::scalar::Default> {
     pub path: PathStroke<S>,
     pub start: Option<Point2<S>>,
     pub end: Option<Point2<S>>,
+    /// A sequence of `(dash_length, gap_length)` pairs. If empty, the line is solid.
+    pub dash_pattern: Vec<(f32, f32)>,
+    /// The distance along the pattern at which the first dash begins.
+    pub dash_offset: f32,
 }
 
 /// The drawing context for a line.
@@ -50,14 +54,32 @@ impl<S> Line<S> {
         self.start(start).end(end)
     }
 
+    /// Draw the line as a series of dashes.
+    ///
+    /// Each element of the pattern is a `(dash_length, gap_length)` pair, specified in the
+    /// drawing's coordinate space. The pattern is tiled along the length of the line. Each dash is
+    /// stroked individually, so the line's cap style applies to both ends of every dash.
+    pub fn dashes(mut self, pattern: &[(f32, f32)]) -> Self {
+        self.dash_pattern.clear();
+        self.dash_pattern.extend_from_slice(pattern);
+        self
+    }
+
+    /// Offset the phase of the dash pattern by the given distance along the line.
+    ///
+    /// Increasing the offset over time causes the dashes to move along the line toward the start.
+    pub fn dash_offset(mut self, offset: f32) -> Self {
+        self.dash_offset = offset;
+        self
+    }
+
     // Map the inner `PathStroke<S>` using the given function.
-    fn map_path<F>(self, map: F) -> Self
+    fn map_path<F>(mut self, map: F) -> Self
     where
         F: FnOnce(PathStroke<S>) -> PathStroke<S>,
     {
-        let Line { path, start, end } = self;
-        let path = map(path);
-        Line { path, start, end }
+        self.path = map(self.path);
+        self
     }
 }
 
@@ -89,6 +111,16 @@ where
     pub fn points(self, start: Point2<S>, end: Point2<S>) -> Self {
         self.map_ty(|ty| ty.points(start, end))
     }
+
+    /// Draw the line as a series of dashes described by `(dash_length, gap_length)` pairs.
+    pub fn dashes(self, pattern: &[(f32, f32)]) -> Self {
+        self.map_ty(|ty| ty.dashes(pattern))
+    }
+
+    /// Offset the phase of the dash pattern by the given distance along the line.
+    pub fn dash_offset(self, offset: f32) -> Self {
+        self.map_ty(|ty| ty.dash_offset(offset))
+    }
 }
 
 impl<S> SetStroke for Line<S> {
@@ -136,33 +168,55 @@ impl draw::renderer::RenderPrimitive for Line<f32> {
         mut ctxt: draw::renderer::RenderContext,
         mesh: &mut draw::Mesh,
     ) -> draw::renderer::PrimitiveRender {
-        let Line { path, start, end } = self;
+        let Line {
+            path,
+            start,
+            end,
+            dash_pattern,
+            dash_offset,
+        } = self;
         let start = start.unwrap_or(pt2(0.0, 0.0));
         let end = end.unwrap_or(pt2(0.0, 0.0));
         if start == end {
             return draw::renderer::PrimitiveRender::default();
         }
-        let close = false;
-        let points = [start, end];
-        let points = points.iter().cloned().map(Into::into);
-        let events = lyon::path::iterator::FromPolyline::new(close, points);
 
         // Determine the transform to apply to all points.
         let global_transform = ctxt.transform;
         let local_transform = path.position.transform() * path.orientation.transform();
         let transform = global_transform * local_transform;
 
-        path::render_path_events(
-            events,
-            path.color,
-            transform,
-            path::Options::Stroke(path.opts),
-            &ctxt.theme,
-            &draw::theme::Primitive::Line,
-            &mut ctxt.fill_tessellator,
-            &mut ctxt.stroke_tessellator,
-            mesh,
-        );
+        let mut render_segment = |a: Point2, b: Point2| {
+            let close = false;
+            let points = [a, b];
+            let points = points.iter().cloned().map(Into::into);
+            let events = lyon::path::iterator::FromPolyline::new(close, points);
+            path::render_path_events(
+                events,
+                path.color,
+                transform,
+                path::Options::Stroke(path.opts),
+                &ctxt.theme,
+                &draw::theme::Primitive::Line,
+                &mut ctxt.fill_tessellator,
+                &mut ctxt.stroke_tessellator,
+                ctxt.svg.as_deref_mut(),
+                mesh,
+            );
+        };
+
+        let line = end - start;
+        let len = line.magnitude();
+        let dashes = dash_ranges(&dash_pattern, dash_offset, len);
+        match dashes {
+            None => render_segment(start, end),
+            Some(dashes) => {
+                let dir = line / len;
+                for (a, b) in dashes {
+                    render_segment(start + dir * a, start + dir * b);
+                }
+            }
+        }
 
         draw::renderer::PrimitiveRender::default()
     }
@@ -177,6 +231,101 @@ where
             path: Default::default(),
             start: Default::default(),
             end: Default::default(),
+            dash_pattern: Default::default(),
+            dash_offset: 0.0,
         }
     }
 }
+
+/// Split the given polyline into the polylines covered by each dash of the pattern.
+///
+/// Each point carries an attribute, e.g. a color, which is interpolated via `lerp` at the ends of
+/// each dash. Points within a dash are retained so that joins still apply at its corners. If the
+/// pattern describes a solid line, the whole polyline is returned as a single dash.
+pub(crate) fn dash_polyline<T, F>(
+    points: &[(Point2, T)],
+    pattern: &[(f32, f32)],
+    offset: f32,
+    lerp: F,
+) -> Vec<Vec<(Point2, T)>>
+where
+    T: Copy,
+    F: Fn(T, T, f32) -> T,
+{
+    if points.len() < 2 {
+        return vec![];
+    }
+    let mut dists = Vec::with_capacity(points.len());
+    let mut total = 0.0;
+    dists.push(total);
+    for w in points.windows(2) {
+        total += (w[1].0 - w[0].0).magnitude();
+        dists.push(total);
+    }
+    let ranges = match dash_ranges(pattern, offset, total) {
+        None => return vec![points.to_vec()],
+        Some(ranges) => ranges,
+    };
+    // The point at distance `d` along the segment beginning at point `seg`.
+    let at = |d: f32, seg: usize| {
+        let ((a, a_attr), (b, b_attr)) = (points[seg], points[seg + 1]);
+        let len = dists[seg + 1] - dists[seg];
+        let t = match len > 0.0 {
+            true => ((d - dists[seg]) / len).max(0.0).min(1.0),
+            false => 0.0,
+        };
+        (a + (b - a) * t, lerp(a_attr, b_attr, t))
+    };
+    let last_seg = points.len() - 2;
+    let mut dashes = vec![];
+    let mut seg = 0;
+    for (start, end) in ranges {
+        while seg < last_seg && dists[seg + 1] < start {
+            seg += 1;
+        }
+        let mut dash = vec![at(start, seg)];
+        let mut end_seg = seg;
+        while end_seg < last_seg && dists[end_seg + 1] < end {
+            end_seg += 1;
+            dash.push(points[end_seg]);
+        }
+        dash.push(at(end, end_seg));
+        dashes.push(dash);
+    }
+    dashes
+}
+
+/// Produce the `(start, end)` distances of each dash along a line of the given length.
+///
+/// Returns `None` if the pattern describes a solid line, i.e. it is empty or has no length.
+pub fn dash_ranges(
+    pattern: &[(f32, f32)],
+    offset: f32,
+    len: f32,
+) -> Option<impl Iterator<Item = (f32, f32)> + '_> {
+    let pattern_len: f32 = pattern.iter().map(|&(d, g)| d.max(0.0) + g.max(0.0)).sum();
+    if pattern.is_empty() || pattern_len <= 0.0 || !pattern_len.is_finite() {
+        return None;
+    }
+    // Begin one pattern before the line so that dashes crossing the start are included.
+    let mut dist = -offset.rem_euclid(pattern_len);
+    let iter = pattern
+        .iter()
+        .cycle()
+        .map(move |&(dash, gap)| {
+            let (dash, gap) = (dash.max(0.0), gap.max(0.0));
+            let range = (dist, dist + dash);
+            dist += dash + gap;
+            range
+        })
+        .take_while(move |&(a, _)| a < len)
+        .filter_map(move |(a, b)| {
+            let (a, b) = (a.max(0.0), b.min(len));
+            if b > a {
+                Some((a, b))
+            } else {
+                None
+            }
+        });
+    Some(iter)
+}
@@ -345,6 +345,24 @@ where
             texture_view,
         }
     }
+
+    // Initialise a `Mesh` from the vertices of an already tessellated mesh, e.g. those baked by a
+    // `DrawRecording`, appending them to the intermediary mesh.
+    pub(crate) fn from_baked(
+        inner_mesh: &mut draw::Mesh<S>,
+        baked: &draw::Mesh<S>,
+        vertex_mode: draw::renderer::VertexMode,
+        texture_view: Option<wgpu::TextureView>,
+    ) -> Self {
+        let v_start = inner_mesh.points().len();
+        let i_start = inner_mesh.indices().len();
+        let offset = v_start as u32;
+        let indices: Vec<u32> = baked.indices().iter().map(|&i| offset + i).collect();
+        inner_mesh.extend_from_slices(baked.points(), &indices, baked.colors(), baked.tex_coords());
+        let v_end = inner_mesh.points().len();
+        let i_end = inner_mesh.indices().len();
+        Mesh::new(v_start..v_end, i_start..i_end, vertex_mode, texture_view)
+    }
 }
 
 impl<'a, S> Drawing<'a, Vertexless, S>
@@ -566,6 +584,7 @@ impl draw::renderer::RenderPrimitive for Mesh<f32> {
         draw::renderer::PrimitiveRender {
             texture_view,
             vertex_mode,
+            sdf: None,
         }
     }
 }
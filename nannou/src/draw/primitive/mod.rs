@@ -1,27 +1,55 @@
 pub mod arrow;
+pub mod bezier;
+pub mod box_3d;
+pub mod catmull_rom;
+pub mod cone;
+pub mod contour;
+pub mod cylinder;
 pub mod ellipse;
+pub mod gpu_mesh;
+pub mod gradient;
+pub mod image;
+pub mod instanced;
 pub mod line;
 pub mod mesh;
+pub mod particles;
 pub mod path;
 pub mod polygon;
 pub mod quad;
 pub mod rect;
+pub mod solid;
+pub mod sphere;
 pub mod text;
 pub mod texture;
+pub mod torus;
 pub mod tri;
 
 use crate::geom;
 
-pub use self::arrow::Arrow;
+pub use self::arrow::{Arrow, ArrowHead};
+pub use self::bezier::Bezier;
+pub use self::box_3d::Box3d;
+pub use self::catmull_rom::CatmullRom;
+pub use self::cone::Cone;
+pub use self::contour::Contour;
+pub use self::cylinder::Cylinder;
 pub use self::ellipse::Ellipse;
+pub use self::gpu_mesh::GpuMesh;
+pub use self::gradient::{Gradient, GradientFill};
+pub use self::image::Image;
+pub use self::instanced::{Instance, Instanced, InstancedDraw};
 pub use self::line::Line;
 pub use self::mesh::Mesh;
+pub use self::particles::Particles;
 pub use self::path::{Path, PathFill, PathInit, PathStroke};
 pub use self::polygon::{Polygon, PolygonInit};
 pub use self::quad::Quad;
 pub use self::rect::Rect;
-pub use self::text::Text;
+pub use self::solid::{SetSolid, SolidOptions};
+pub use self::sphere::Sphere;
+pub use self::text::{Text, TextRenderMode};
 pub use self::texture::Texture;
+pub use self::torus::Torus;
 pub use self::tri::Tri;
 
 /// A wrapper around all primitive sets of properties so that they may be stored within the
@@ -32,10 +60,21 @@ pub use self::tri::Tri;
 #[derive(Clone, Debug)]
 pub enum Primitive<S = geom::scalar::Default> {
     Arrow(Arrow<S>),
+    Bezier(Bezier<S>),
+    Box3d(Box3d<S>),
+    CatmullRom(CatmullRom<S>),
+    Cone(Cone<S>),
+    Contour(Contour<S>),
+    Cylinder(Cylinder<S>),
     Ellipse(Ellipse<S>),
+    GpuMesh(GpuMesh),
+    GradientFill(GradientFill<S>),
+    Image(Image<S>),
+    Instanced(Instanced<S>),
     Line(Line<S>),
     MeshVertexless(mesh::Vertexless),
     Mesh(Mesh<S>),
+    Particles(Particles<S>),
     PathInit(PathInit<S>),
     PathFill(PathFill<S>),
     PathStroke(PathStroke<S>),
@@ -44,7 +83,9 @@ pub enum Primitive<S = geom::scalar::Default> {
     Polygon(Polygon<S>),
     Quad(Quad<S>),
     Rect(Rect<S>),
+    Sphere(Sphere<S>),
     Text(Text<S>),
     Texture(Texture<S>),
+    Torus(Torus<S>),
     Tri(Tri<S>),
 }
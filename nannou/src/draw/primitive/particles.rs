@@ -0,0 +1,27 @@
+use crate::draw::primitive::Primitive;
+use crate::geom;
+use crate::particle;
+
+/// A primitive drawn as a sprite for each live particle of a **ParticleSystem**.
+///
+/// Produced upon finishing a **Drawing** for which `particles` was specified.
+#[derive(Clone, Debug)]
+pub struct Particles<S = geom::scalar::Default> {
+    pub primitive: Box<Primitive<S>>,
+    pub(crate) system: particle::Handle,
+}
+
+impl<S> From<Particles<S>> for Primitive<S> {
+    fn from(prim: Particles<S>) -> Self {
+        Primitive::Particles(prim)
+    }
+}
+
+impl<S> Into<Option<Particles<S>>> for Primitive<S> {
+    fn into(self) -> Option<Particles<S>> {
+        match self {
+            Primitive::Particles(prim) => Some(prim),
+            _ => None,
+        }
+    }
+}
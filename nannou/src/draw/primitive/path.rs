@@ -1,7 +1,7 @@
 use crate::color::conv::IntoLinSrgba;
 use crate::color::LinSrgba;
 use crate::draw::mesh::vertex::{Color, TexCoords};
-use crate::draw::primitive::Primitive;
+use crate::draw::primitive::{line, Primitive};
 use crate::draw::properties::spatial::{orientation, position};
 use crate::draw::properties::{
     ColorScalar, SetColor, SetFill, SetOrientation, SetPosition, SetStroke,
@@ -61,6 +61,14 @@ pub struct PathOptions<T, S = geom::scalar::Default> {
     pub(crate) color: Option<LinSrgba>,
     pub(crate) position: position::Properties<S>,
     pub(crate) orientation: orientation::Properties<S>,
+    pub(crate) dashes: Dashes,
+}
+
+/// A dash pattern applied to a stroked path. See **PathStroke::dashes**.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct Dashes {
+    pattern: Vec<(f32, f32)>,
+    offset: f32,
 }
 
 /// Mutable access to stroke and fill tessellators.
@@ -85,6 +93,7 @@ pub struct Path<S = geom::scalar::Default> {
     options: Options,
     vertex_mode: draw::renderer::VertexMode,
     texture_view: Option<wgpu::TextureView>,
+    dashes: Dashes,
 }
 
 /// The initial drawing context for a path.
@@ -142,11 +151,13 @@ where
         let orientation = Default::default();
         let position = Default::default();
         let color = Default::default();
+        let dashes = Default::default();
         PathOptions {
             opts,
             orientation,
             position,
             color,
+            dashes,
         }
     }
 }
@@ -179,6 +190,26 @@ impl<S> PathStroke<S> {
     pub fn tolerance(self, tolerance: f32) -> Self {
         self.stroke_tolerance(tolerance)
     }
+
+    /// Stroke the path as a series of dashes.
+    ///
+    /// Each element of the pattern is a `(dash_length, gap_length)` pair, specified in the
+    /// drawing's coordinate space. The pattern is tiled along the arc length of each sub-path,
+    /// restarting at the beginning of each. Each dash is stroked individually, so the path's join
+    /// style applies to the corners within a dash and its cap style to both ends of every dash.
+    pub fn dashes(mut self, pattern: &[(f32, f32)]) -> Self {
+        self.dashes.pattern.clear();
+        self.dashes.pattern.extend_from_slice(pattern);
+        self
+    }
+
+    /// Offset the phase of the dash pattern by the given distance along the path.
+    ///
+    /// Increasing the offset over time causes the dashes to move along the path toward the start.
+    pub fn dash_offset(mut self, offset: f32) -> Self {
+        self.dashes.offset = offset;
+        self
+    }
 }
 
 impl<T, S> PathOptions<T, S>
@@ -205,6 +236,7 @@ where
             self.opts.into_options(),
             draw::renderer::VertexMode::Color,
             None,
+            self.dashes,
         )
     }
 
@@ -334,6 +366,7 @@ where
             self.opts.into_options(),
             draw::renderer::VertexMode::Color,
             None,
+            self.dashes,
         )
     }
 
@@ -371,6 +404,7 @@ where
             self.opts.into_options(),
             draw::renderer::VertexMode::Texture,
             Some(texture_view),
+            self.dashes,
         )
     }
 }
@@ -384,19 +418,26 @@ pub(crate) fn render_path_events<I>(
     theme_prim: &draw::theme::Primitive,
     fill_tessellator: &mut lyon::tessellation::FillTessellator,
     stroke_tessellator: &mut lyon::tessellation::StrokeTessellator,
+    svg: Option<&mut draw::svg::Recorder>,
     mesh: &mut draw::Mesh,
 ) where
     I: IntoIterator<Item = lyon::path::PathEvent>,
 {
+    let color = color.unwrap_or_else(|| match options {
+        Options::Fill(_) => theme.fill_lin_srgba(theme_prim),
+        Options::Stroke(_) => theme.stroke_lin_srgba(theme_prim),
+    });
+
+    // When exporting to SVG, the path is recorded as-is rather than tessellated.
+    if let Some(svg) = svg {
+        svg.path(events, &transform, &options, color);
+        return;
+    }
+
+    let mut mesh_builder = draw::mesh::MeshBuilder::single_color(mesh, transform, color);
     let res = match options {
-        Options::Fill(options) => {
-            let color = color.unwrap_or_else(|| theme.fill_lin_srgba(theme_prim));
-            let mut mesh_builder = draw::mesh::MeshBuilder::single_color(mesh, transform, color);
-            fill_tessellator.tessellate(events, &options, &mut mesh_builder)
-        }
+        Options::Fill(options) => fill_tessellator.tessellate(events, &options, &mut mesh_builder),
         Options::Stroke(options) => {
-            let color = color.unwrap_or_else(|| theme.stroke_lin_srgba(theme_prim));
-            let mut mesh_builder = draw::mesh::MeshBuilder::single_color(mesh, transform, color);
             stroke_tessellator.tessellate(events, &options, &mut mesh_builder)
         }
     };
@@ -412,10 +453,22 @@ pub(crate) fn render_path_points_colored<I>(
     options: Options,
     fill_tessellator: &mut lyon::tessellation::FillTessellator,
     stroke_tessellator: &mut lyon::tessellation::StrokeTessellator,
+    svg: Option<&mut draw::svg::Recorder>,
     mesh: &mut draw::Mesh,
 ) where
     I: IntoIterator<Item = (Point2, Color)>,
 {
+    // SVG paths have a single colour, so the colour of the first point is used.
+    if let Some(svg) = svg {
+        let points: Vec<_> = points_colored.into_iter().collect();
+        if let Some(&(_, color)) = points.first() {
+            let points = points.iter().map(|&(p, _)| p.into());
+            let events = lyon::path::iterator::FromPolyline::new(close, points);
+            svg.path(events, &transform, &options, color);
+        }
+        return;
+    }
+
     let path = match points_colored_to_lyon_path(points_colored, close) {
         None => return,
         Some(p) => p,
@@ -493,6 +546,7 @@ pub(crate) fn render_path_source(
     theme_prim: &draw::theme::Primitive,
     fill_tessellator: &mut lyon::tessellation::FillTessellator,
     stroke_tessellator: &mut lyon::tessellation::StrokeTessellator,
+    svg: Option<&mut draw::svg::Recorder>,
     mesh: &mut draw::Mesh,
 ) {
     match path_src {
@@ -505,6 +559,7 @@ pub(crate) fn render_path_source(
             theme_prim,
             fill_tessellator,
             stroke_tessellator,
+            svg,
             mesh,
         ),
         PathEventSourceIter::ColoredPoints { points, close } => render_path_points_colored(
@@ -514,6 +569,7 @@ pub(crate) fn render_path_source(
             options,
             fill_tessellator,
             stroke_tessellator,
+            svg,
             mesh,
         ),
         PathEventSourceIter::TexturedPoints { points, close } => render_path_points_textured(
@@ -542,6 +598,7 @@ impl draw::renderer::RenderPrimitive for Path<f32> {
             options,
             vertex_mode,
             texture_view,
+            dashes,
         } = self;
 
         // Determine the transform to apply to all points.
@@ -549,69 +606,178 @@ impl draw::renderer::RenderPrimitive for Path<f32> {
         let local_transform = position.transform() * orientation.transform();
         let transform = global_transform * local_transform;
 
+        // Only stroked paths may be dashed, in which case curves are flattened with the stroke's
+        // tolerance before the path is split at dash boundaries.
+        let dash_tolerance = match options {
+            Options::Stroke(ref opts) if !dashes.pattern.is_empty() => Some(opts.tolerance),
+            _ => None,
+        };
+
         // A function for rendering the path.
-        let render =
-            |src: PathEventSourceIter,
-             theme: &draw::Theme,
-             fill_tessellator: &mut lyon::tessellation::FillTessellator,
-             stroke_tessellator: &mut lyon::tessellation::StrokeTessellator| {
-                render_path_source(
-                    src,
-                    color,
-                    transform,
-                    options,
-                    theme,
-                    &draw::theme::Primitive::Path,
-                    fill_tessellator,
-                    stroke_tessellator,
-                    mesh,
-                )
-            };
+        let mut render = |src: PathEventSourceIter,
+                          theme: &draw::Theme,
+                          fill_tessellator: &mut lyon::tessellation::FillTessellator,
+                          stroke_tessellator: &mut lyon::tessellation::StrokeTessellator,
+                          svg: Option<&mut draw::svg::Recorder>| {
+            render_path_source(
+                src,
+                color,
+                transform,
+                options.clone(),
+                theme,
+                &draw::theme::Primitive::Path,
+                fill_tessellator,
+                stroke_tessellator,
+                svg,
+                &mut *mesh,
+            )
+        };
 
         match path_event_src {
             PathEventSource::Buffered(range) => {
-                let mut events = ctxt.path_event_buffer[range].iter().cloned();
-                let src = PathEventSourceIter::Events(&mut events);
-                render(
-                    src,
-                    &ctxt.theme,
-                    &mut ctxt.fill_tessellator,
-                    &mut ctxt.stroke_tessellator,
-                );
+                let events = ctxt.path_event_buffer[range].iter().cloned();
+                match dash_tolerance {
+                    None => {
+                        let mut events = events;
+                        let src = PathEventSourceIter::Events(&mut events);
+                        render(
+                            src,
+                            &ctxt.theme,
+                            &mut ctxt.fill_tessellator,
+                            &mut ctxt.stroke_tessellator,
+                            ctxt.svg.as_deref_mut(),
+                        );
+                    }
+                    Some(tolerance) => {
+                        for (points, close) in geom::path::flatten_events(events, tolerance) {
+                            let points = points.into_iter().map(|p| (p, ())).collect();
+                            let points = close_polyline(points, close);
+                            let (pattern, offset) = (&dashes.pattern, dashes.offset);
+                            for dash in line::dash_polyline(&points, pattern, offset, |_, _, _| ())
+                            {
+                                let points = dash.iter().map(|&(p, _)| p.into());
+                                let mut events =
+                                    lyon::path::iterator::FromPolyline::new(false, points);
+                                let src = PathEventSourceIter::Events(&mut events);
+                                render(
+                                    src,
+                                    &ctxt.theme,
+                                    &mut ctxt.fill_tessellator,
+                                    &mut ctxt.stroke_tessellator,
+                                    ctxt.svg.as_deref_mut(),
+                                );
+                            }
+                        }
+                    }
+                }
             }
             PathEventSource::ColoredPoints { range, close } => {
-                let mut points_colored = ctxt.path_points_colored_buffer[range].iter().cloned();
-                let src = PathEventSourceIter::ColoredPoints {
-                    points: &mut points_colored,
-                    close,
-                };
-                render(
-                    src,
-                    &ctxt.theme,
-                    &mut ctxt.fill_tessellator,
-                    &mut ctxt.stroke_tessellator,
-                );
+                let points = &ctxt.path_points_colored_buffer[range];
+                let dashed = dash_tolerance.map(|_| {
+                    let points = close_polyline(points.to_vec(), close);
+                    line::dash_polyline(&points, &dashes.pattern, dashes.offset, lerp_color)
+                });
+                match dashed {
+                    None => {
+                        let mut points_colored = points.iter().cloned();
+                        let src = PathEventSourceIter::ColoredPoints {
+                            points: &mut points_colored,
+                            close,
+                        };
+                        render(
+                            src,
+                            &ctxt.theme,
+                            &mut ctxt.fill_tessellator,
+                            &mut ctxt.stroke_tessellator,
+                            ctxt.svg.as_deref_mut(),
+                        );
+                    }
+                    Some(dashes) => {
+                        for dash in dashes {
+                            let mut points_colored = dash.into_iter();
+                            let src = PathEventSourceIter::ColoredPoints {
+                                points: &mut points_colored,
+                                close: false,
+                            };
+                            render(
+                                src,
+                                &ctxt.theme,
+                                &mut ctxt.fill_tessellator,
+                                &mut ctxt.stroke_tessellator,
+                                ctxt.svg.as_deref_mut(),
+                            );
+                        }
+                    }
+                }
             }
             PathEventSource::TexturedPoints { range, close } => {
-                let mut points_textured = ctxt.path_points_textured_buffer[range].iter().cloned();
-                let src = PathEventSourceIter::TexturedPoints {
-                    points: &mut points_textured,
-                    close,
-                };
-                render(
-                    src,
-                    &ctxt.theme,
-                    &mut ctxt.fill_tessellator,
-                    &mut ctxt.stroke_tessellator,
-                );
+                let points = &ctxt.path_points_textured_buffer[range];
+                let dashed = dash_tolerance.map(|_| {
+                    let points = close_polyline(points.to_vec(), close);
+                    let lerp = |a: TexCoords, b: TexCoords, t: f32| a + (b - a) * t;
+                    line::dash_polyline(&points, &dashes.pattern, dashes.offset, lerp)
+                });
+                match dashed {
+                    None => {
+                        let mut points_textured = points.iter().cloned();
+                        let src = PathEventSourceIter::TexturedPoints {
+                            points: &mut points_textured,
+                            close,
+                        };
+                        render(
+                            src,
+                            &ctxt.theme,
+                            &mut ctxt.fill_tessellator,
+                            &mut ctxt.stroke_tessellator,
+                            ctxt.svg.as_deref_mut(),
+                        );
+                    }
+                    Some(dashes) => {
+                        for dash in dashes {
+                            let mut points_textured = dash.into_iter();
+                            let src = PathEventSourceIter::TexturedPoints {
+                                points: &mut points_textured,
+                                close: false,
+                            };
+                            render(
+                                src,
+                                &ctxt.theme,
+                                &mut ctxt.fill_tessellator,
+                                &mut ctxt.stroke_tessellator,
+                                ctxt.svg.as_deref_mut(),
+                            );
+                        }
+                    }
+                }
             }
         }
 
         draw::renderer::PrimitiveRender {
             texture_view,
             vertex_mode,
+            sdf: None,
+        }
+    }
+}
+
+// Return to the first point at the end of a closed polyline so that it may be dashed.
+fn close_polyline<T: Copy>(mut points: Vec<(Point2, T)>, close: bool) -> Vec<(Point2, T)> {
+    if close {
+        if let Some(&first) = points.first() {
+            points.push(first);
         }
     }
+    points
+}
+
+fn lerp_color(a: Color, b: Color, t: f32) -> Color {
+    let lerp = |a: f32, b: f32| a + (b - a) * t;
+    Color::new(
+        lerp(a.red, b.red),
+        lerp(a.green, b.green),
+        lerp(a.blue, b.blue),
+        lerp(a.alpha, b.alpha),
+    )
 }
 
 /// Create a lyon path for the given iterator of colored points.
@@ -691,6 +857,7 @@ where
         options: Options,
         vertex_mode: draw::renderer::VertexMode,
         texture_view: Option<wgpu::TextureView>,
+        dashes: Dashes,
     ) -> Self {
         Path {
             color,
@@ -700,6 +867,7 @@ where
             options,
             vertex_mode,
             texture_view,
+            dashes,
         }
     }
 }
@@ -753,6 +921,18 @@ where
     pub fn tolerance(self, tolerance: f32) -> Self {
         self.map_ty(|ty| ty.stroke_tolerance(tolerance))
     }
+
+    /// Stroke the path as a series of `(dash_length, gap_length)` dashes.
+    ///
+    /// See the **PathStroke::dashes** method for details.
+    pub fn dashes(self, pattern: &[(f32, f32)]) -> Self {
+        self.map_ty(|ty| ty.dashes(pattern))
+    }
+
+    /// Offset the phase of the dash pattern by the given distance along the path.
+    pub fn dash_offset(self, offset: f32) -> Self {
+        self.map_ty(|ty| ty.dash_offset(offset))
+    }
 }
 
 impl<'a, T, S> DrawingPathOptions<'a, T, S>
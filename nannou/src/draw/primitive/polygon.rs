@@ -204,24 +204,25 @@ pub fn render_events_themed<F, I>(
     let transform = global_transform * local_transform;
 
     // A function for rendering the path.
-    let mut render =
-        |opts: path::Options,
-         color: Option<LinSrgba>,
-         theme: &draw::Theme,
-         fill_tessellator: &mut lyon::tessellation::FillTessellator,
-         stroke_tessellator: &mut lyon::tessellation::StrokeTessellator| {
-            path::render_path_events(
-                events(),
-                color,
-                transform,
-                opts,
-                theme,
-                theme_primitive,
-                fill_tessellator,
-                stroke_tessellator,
-                mesh,
-            )
-        };
+    let mut render = |opts: path::Options,
+                      color: Option<LinSrgba>,
+                      theme: &draw::Theme,
+                      fill_tessellator: &mut lyon::tessellation::FillTessellator,
+                      stroke_tessellator: &mut lyon::tessellation::StrokeTessellator,
+                      svg: Option<&mut draw::svg::Recorder>| {
+        path::render_path_events(
+            events(),
+            color,
+            transform,
+            opts,
+            theme,
+            theme_primitive,
+            fill_tessellator,
+            stroke_tessellator,
+            svg,
+            mesh,
+        )
+    };
 
     // Do the fill tessellation first.
     if !no_fill {
@@ -232,6 +233,7 @@ pub fn render_events_themed<F, I>(
             &ctxt.theme,
             &mut ctxt.fill_tessellator,
             &mut ctxt.stroke_tessellator,
+            ctxt.svg.as_deref_mut(),
         );
     }
 
@@ -245,6 +247,7 @@ pub fn render_events_themed<F, I>(
             &ctxt.theme,
             &mut ctxt.fill_tessellator,
             &mut ctxt.stroke_tessellator,
+            ctxt.svg.as_deref_mut(),
         );
     }
 }
@@ -295,6 +298,7 @@ impl Polygon<f32> {
             path_points_textured_buffer,
             transform,
             theme,
+            mut svg,
             ..
         } = ctxt;
 
@@ -304,25 +308,26 @@ impl Polygon<f32> {
         let transform = global_transform * local_transform;
 
         // A function for rendering the path.
-        let mut render =
-            |src: path::PathEventSourceIter,
-             opts: path::Options,
-             color: Option<LinSrgba>,
-             theme: &draw::Theme,
-             fill_tessellator: &mut lyon::tessellation::FillTessellator,
-             stroke_tessellator: &mut lyon::tessellation::StrokeTessellator| {
-                path::render_path_source(
-                    src,
-                    color,
-                    transform,
-                    opts,
-                    theme,
-                    theme_primitive,
-                    fill_tessellator,
-                    stroke_tessellator,
-                    mesh,
-                )
-            };
+        let mut render = |src: path::PathEventSourceIter,
+                          opts: path::Options,
+                          color: Option<LinSrgba>,
+                          theme: &draw::Theme,
+                          fill_tessellator: &mut lyon::tessellation::FillTessellator,
+                          stroke_tessellator: &mut lyon::tessellation::StrokeTessellator,
+                          svg: Option<&mut draw::svg::Recorder>| {
+            path::render_path_source(
+                src,
+                color,
+                transform,
+                opts,
+                theme,
+                theme_primitive,
+                fill_tessellator,
+                stroke_tessellator,
+                svg,
+                mesh,
+            )
+        };
 
         // Do the fill tessellation first.
         if !no_fill {
@@ -338,6 +343,7 @@ impl Polygon<f32> {
                         theme,
                         fill_tessellator,
                         stroke_tessellator,
+                        svg.as_deref_mut(),
                     );
                 }
                 PathEventSource::ColoredPoints { ref range, close } => {
@@ -354,6 +360,7 @@ impl Polygon<f32> {
                         theme,
                         fill_tessellator,
                         stroke_tessellator,
+                        svg.as_deref_mut(),
                     );
                 }
                 PathEventSource::TexturedPoints { ref range, close } => {
@@ -370,6 +377,7 @@ impl Polygon<f32> {
                         theme,
                         fill_tessellator,
                         stroke_tessellator,
+                        svg.as_deref_mut(),
                     );
                 }
             }
@@ -389,6 +397,7 @@ impl Polygon<f32> {
                         theme,
                         fill_tessellator,
                         stroke_tessellator,
+                        svg.as_deref_mut(),
                     );
                 }
                 PathEventSource::ColoredPoints { range, close } => {
@@ -409,6 +418,7 @@ impl Polygon<f32> {
                         theme,
                         fill_tessellator,
                         stroke_tessellator,
+                        svg.as_deref_mut(),
                     );
                 }
                 PathEventSource::TexturedPoints { range, close } => {
@@ -424,6 +434,7 @@ impl Polygon<f32> {
                         theme,
                         fill_tessellator,
                         stroke_tessellator,
+                        svg.as_deref_mut(),
                     );
                 }
             }
@@ -434,6 +445,7 @@ impl Polygon<f32> {
             Some(texture_view) => draw::renderer::PrimitiveRender {
                 texture_view: Some(texture_view),
                 vertex_mode: draw::renderer::VertexMode::Texture,
+                sdf: None,
             },
         }
     }
@@ -0,0 +1,172 @@
+use crate::color::LinSrgba;
+use crate::draw::primitive::Primitive;
+use crate::draw::properties::spatial::{orientation, position};
+use crate::draw::properties::{ColorScalar, SetColor, SetOrientation, SetPosition};
+use crate::draw::{self, Drawing};
+use crate::geom::{self, Point2, Vector3};
+use crate::math::{BaseFloat, Matrix, Matrix4, SquareMatrix};
+
+/// The direction from which solids are lit when lighting is enabled.
+///
+/// Points from the surface towards the light, i.e. up, to the left and towards the viewer.
+pub const LIGHT_DIRECTION: [f32; 3] = [-0.4, 0.6, 0.7];
+
+/// The amount of light received by surfaces facing away from the light.
+pub const AMBIENT_LIGHT: f32 = 0.25;
+
+/// A trait implemented for all 3D solid draw primitives.
+pub trait SetSolid<S>: Sized {
+    /// Access to the solid builder parameters.
+    fn solid_options_mut(&mut self) -> &mut SolidOptions<S>;
+
+    /// Specify whether or not the solid should be shaded by its normals.
+    ///
+    /// When `true` (the default), the colour of each vertex is scaled by the diffuse lighting
+    /// received from `LIGHT_DIRECTION`. When `false`, the solid is filled with a flat colour.
+    fn lit(mut self, lit: bool) -> Self {
+        self.solid_options_mut().lit = lit;
+        self
+    }
+}
+
+/// Properties shared by all 3D solid primitives.
+#[derive(Clone, Debug)]
+pub struct SolidOptions<S = geom::scalar::Default> {
+    pub position: position::Properties<S>,
+    pub orientation: orientation::Properties<S>,
+    pub color: Option<LinSrgba>,
+    pub lit: bool,
+}
+
+impl SolidOptions<f32> {
+    /// Render the given solid into the mesh.
+    ///
+    /// Normals are transformed along with the solid's vertices and, if the solid is lit, used to
+    /// shade the colour of each vertex. The draw pipeline has no normal vertex channel, so lighting
+    /// is evaluated per vertex and interpolated across each triangle.
+    pub(crate) fn render(
+        self,
+        solid: geom::Solid<f32>,
+        ctxt: draw::renderer::RenderContext,
+        theme_primitive: &draw::theme::Primitive,
+        mesh: &mut draw::Mesh,
+    ) -> draw::renderer::PrimitiveRender {
+        let SolidOptions {
+            position,
+            orientation,
+            color,
+            lit,
+        } = self;
+
+        // Determine the transform to apply to vertices and the transform to apply to normals.
+        let global_transform = *ctxt.transform;
+        let local_transform = position.transform() * orientation.transform();
+        let transform = global_transform * local_transform;
+        let normal_transform = normal_transform(&transform);
+
+        let color = color.unwrap_or_else(|| ctxt.theme.fill_lin_srgba(theme_primitive));
+        let tex_coords = Point2 { x: 0.0, y: 0.0 };
+
+        let geom::Solid {
+            points,
+            normals,
+            indices,
+        } = solid;
+        let vertices = points.into_iter().zip(normals).map(|(p, n)| {
+            let p = cgmath::Point3::new(p.x, p.y, p.z);
+            let p: geom::Point3 = cgmath::Transform::transform_point(&transform, p).into();
+            let color = if lit {
+                let n = cgmath::Vector3::new(n.x, n.y, n.z);
+                let n = cgmath::Transform::transform_vector(&normal_transform, n);
+                shade(color, n.into())
+            } else {
+                color
+            };
+            ((p, color), tex_coords).into()
+        });
+        let offset = mesh.raw_vertex_count() as u32;
+        let indices = indices.into_iter().map(|i| offset + i);
+        mesh.extend(vertices, indices);
+
+        draw::renderer::PrimitiveRender::default()
+    }
+}
+
+/// The transform to apply to normals given the transform applied to points.
+///
+/// This is the inverse transpose of the upper 3x3 of the transform, falling back to the transform
+/// itself in the case that it is not invertible.
+pub(crate) fn normal_transform(transform: &Matrix4<f32>) -> Matrix4<f32> {
+    transform
+        .invert()
+        .map(|m| m.transpose())
+        .unwrap_or(*transform)
+}
+
+/// Scale the given colour by the light received from `LIGHT_DIRECTION` by a surface with the given
+/// normal.
+pub(crate) fn shade(color: LinSrgba, normal: Vector3) -> LinSrgba {
+    let [lx, ly, lz] = LIGHT_DIRECTION;
+    let light = Vector3::new(lx, ly, lz).normalize();
+    let diffuse = normal.normalize().dot(light).max(0.0);
+    let shade = AMBIENT_LIGHT + (1.0 - AMBIENT_LIGHT) * diffuse;
+    LinSrgba::new(
+        color.red * shade,
+        color.green * shade,
+        color.blue * shade,
+        color.alpha,
+    )
+}
+
+impl<'a, S, T> Drawing<'a, T, S>
+where
+    S: BaseFloat,
+    T: SetSolid<S> + Into<Primitive<S>>,
+    Primitive<S>: Into<Option<T>>,
+{
+    /// Specify whether or not the solid should be shaded by its normals.
+    ///
+    /// When `true` (the default), the colour of each vertex is scaled by the diffuse lighting
+    /// received from `LIGHT_DIRECTION`. When `false`, the solid is filled with a flat colour.
+    pub fn lit(self, lit: bool) -> Self {
+        self.map_ty(|ty| ty.lit(lit))
+    }
+}
+
+impl<S> Default for SolidOptions<S>
+where
+    S: BaseFloat,
+{
+    fn default() -> Self {
+        SolidOptions {
+            position: Default::default(),
+            orientation: Default::default(),
+            color: None,
+            lit: true,
+        }
+    }
+}
+
+impl<S> SetSolid<S> for SolidOptions<S> {
+    fn solid_options_mut(&mut self) -> &mut SolidOptions<S> {
+        self
+    }
+}
+
+impl<S> SetOrientation<S> for SolidOptions<S> {
+    fn properties(&mut self) -> &mut orientation::Properties<S> {
+        SetOrientation::properties(&mut self.orientation)
+    }
+}
+
+impl<S> SetPosition<S> for SolidOptions<S> {
+    fn properties(&mut self) -> &mut position::Properties<S> {
+        SetPosition::properties(&mut self.position)
+    }
+}
+
+impl<S> SetColor<ColorScalar> for SolidOptions<S> {
+    fn rgba_mut(&mut self) -> &mut Option<LinSrgba> {
+        SetColor::rgba_mut(&mut self.color)
+    }
+}
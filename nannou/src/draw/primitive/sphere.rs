@@ -0,0 +1,134 @@
+use crate::draw::primitive::solid::{SetSolid, SolidOptions};
+use crate::draw::primitive::Primitive;
+use crate::draw::properties::spatial::{orientation, position};
+use crate::draw::properties::{ColorScalar, LinSrgba, SetColor, SetOrientation, SetPosition};
+use crate::draw::{self, Drawing};
+use crate::geom;
+use crate::math::BaseFloat;
+
+/// Properties related to drawing a **Sphere**.
+///
+/// The sphere is triangulated with vertex normals and centred on its position.
+#[derive(Clone, Debug)]
+pub struct Sphere<S = geom::scalar::Default> {
+    radius: Option<S>,
+    resolution: Option<u32>,
+    solid: SolidOptions<S>,
+}
+
+/// The drawing context for a **Sphere**.
+pub type DrawingSphere<'a, S = geom::scalar::Default> = Drawing<'a, Sphere<S>, S>;
+
+// Sphere-specific methods.
+
+impl<S> Sphere<S>
+where
+    S: BaseFloat,
+{
+    /// Specify the radius of the sphere.
+    pub fn radius(mut self, radius: S) -> Self {
+        self.radius = Some(radius);
+        self
+    }
+
+    /// The number of segments around the equator of the sphere.
+    ///
+    /// The number of rings from pole to pole is half of the resolution.
+    pub fn resolution(mut self, resolution: u32) -> Self {
+        self.resolution = Some(resolution);
+        self
+    }
+}
+
+// Trait implementations.
+
+impl draw::renderer::RenderPrimitive for Sphere<f32> {
+    fn render_primitive(
+        self,
+        ctxt: draw::renderer::RenderContext,
+        mesh: &mut draw::Mesh,
+    ) -> draw::renderer::PrimitiveRender {
+        let Sphere {
+            radius,
+            resolution,
+            solid,
+        } = self;
+        let radius = radius.unwrap_or(50.0);
+        let resolution = resolution.unwrap_or(32);
+        let sphere = geom::solid::sphere(radius, resolution);
+        solid.render(sphere, ctxt, &draw::theme::Primitive::Sphere, mesh)
+    }
+}
+
+impl<S> Default for Sphere<S>
+where
+    S: BaseFloat,
+{
+    fn default() -> Self {
+        Sphere {
+            radius: None,
+            resolution: None,
+            solid: Default::default(),
+        }
+    }
+}
+
+impl<S> SetOrientation<S> for Sphere<S> {
+    fn properties(&mut self) -> &mut orientation::Properties<S> {
+        SetOrientation::properties(&mut self.solid)
+    }
+}
+
+impl<S> SetPosition<S> for Sphere<S> {
+    fn properties(&mut self) -> &mut position::Properties<S> {
+        SetPosition::properties(&mut self.solid)
+    }
+}
+
+impl<S> SetColor<ColorScalar> for Sphere<S> {
+    fn rgba_mut(&mut self) -> &mut Option<LinSrgba> {
+        SetColor::rgba_mut(&mut self.solid)
+    }
+}
+
+impl<S> SetSolid<S> for Sphere<S> {
+    fn solid_options_mut(&mut self) -> &mut SolidOptions<S> {
+        SetSolid::solid_options_mut(&mut self.solid)
+    }
+}
+
+// Primitive conversions.
+
+impl<S> From<Sphere<S>> for Primitive<S> {
+    fn from(prim: Sphere<S>) -> Self {
+        Primitive::Sphere(prim)
+    }
+}
+
+impl<S> Into<Option<Sphere<S>>> for Primitive<S> {
+    fn into(self) -> Option<Sphere<S>> {
+        match self {
+            Primitive::Sphere(prim) => Some(prim),
+            _ => None,
+        }
+    }
+}
+
+// Drawing methods.
+
+impl<'a, S> DrawingSphere<'a, S>
+where
+    S: BaseFloat,
+{
+    /// Specify the radius of the sphere.
+    pub fn radius(self, radius: S) -> Self {
+        self.map_ty(|ty| ty.radius(radius))
+    }
+
+    /// The number of segments around the equator of the sphere.
+    ///
+    /// The number of rings from pole to pole is half of the resolution.
+    pub fn resolution(self, resolution: u32) -> Self {
+        self.map_ty(|ty| ty.resolution(resolution))
+    }
+}
@@ -1,3 +1,4 @@
+use crate::color::IntoLinSrgba;
 use crate::draw::drawing::DrawingContext;
 use crate::draw::primitive::Primitive;
 use crate::draw::properties::spatial::{self, dimension, orientation, position};
@@ -23,11 +24,38 @@ pub struct Text<S = geom::scalar::Default> {
 pub struct Style {
     pub color: Option<LinSrgba>,
     pub layout: text::layout::Builder,
+    pub render_mode: TextRenderMode,
+    pub outline_width: Scalar,
+    pub outline_color: Option<LinSrgba>,
+    pub glow_radius: Scalar,
+    pub glow_color: Option<LinSrgba>,
+}
+
+/// The way in which the glyphs of a **Text** primitive are rendered.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum TextRenderMode {
+    /// Glyphs are rasterised to a coverage mask at the size at which they are drawn.
+    ///
+    /// This is the default and produces the crispest results for unscaled text, but glyphs must be
+    /// re-rasterised for each size and blur when scaled via the transform.
+    Coverage,
+    /// Glyphs are drawn from multi-channel signed distance fields generated once per glyph.
+    ///
+    /// Text remains sharp at any scale and may be outlined or given a glow via the
+    /// `outline_width` and `glow_radius` methods. Very small text may appear slightly softer than
+    /// with `Coverage`.
+    Sdf,
 }
 
 /// The drawing context for the **Text** primitive.
 pub type DrawingText<'a, S = geom::scalar::Default> = Drawing<'a, Text<S>, S>;
 
+impl Default for TextRenderMode {
+    fn default() -> Self {
+        TextRenderMode::Coverage
+    }
+}
+
 impl<S> Text<S> {
     /// Begin drawing some text.
     pub fn new(ctxt: DrawingContext<S>, text: &str) -> Self
@@ -56,6 +84,18 @@ impl<S> Text<S> {
         self
     }
 
+    // The text of the primitive within the given text buffer.
+    pub(crate) fn text_from_buffer<'b>(&self, text_buffer: &'b str) -> &'b str {
+        &text_buffer[self.text.clone()]
+    }
+
+    // Append the text to the given text buffer, pointing the primitive at the appended text.
+    pub(crate) fn move_to_buffer(&mut self, text: &str, text_buffer: &mut String) {
+        let start = text_buffer.len();
+        text_buffer.push_str(text);
+        self.text = start..text_buffer.len();
+    }
+
     /// The font size to use for the text.
     pub fn font_size(self, size: FontSize) -> Self {
         self.map_layout(|l| l.font_size(size))
@@ -124,6 +164,32 @@ impl<S> Text<S> {
         self.map_layout(|l| l.y_align(align))
     }
 
+    /// Specify how the text should be aligned along the x axis of its bounding rectangle.
+    ///
+    /// `Align::Start`, `Align::Middle` and `Align::End` align the text to the left, centre and
+    /// right respectively.
+    pub fn align_x(self, align: Align) -> Self {
+        self.map_layout(|l| l.align_x(align))
+    }
+
+    /// Specify how the text should be aligned along the y axis of its bounding rectangle.
+    ///
+    /// `Align::Start`, `Align::Middle` and `Align::End` align the text to the bottom, middle and
+    /// top respectively.
+    pub fn align_y(self, align: Align) -> Self {
+        self.map_layout(|l| l.align_y(align))
+    }
+
+    /// Wrap the text at the given width using the Unicode line breaking rules.
+    ///
+    /// This sets the width of the text's bounding rectangle along with `Wrap::Unicode`. Lines are
+    /// only broken at line break opportunities, e.g. between words, unless a single word exceeds
+    /// the width.
+    pub fn max_width(mut self, width: S) -> Self {
+        self.spatial.dimensions.x = Some(width);
+        self.line_wrap(Some(Wrap::Unicode))
+    }
+
     /// Align the top edge of the text with the top edge of its bounding rectangle.
     pub fn align_top(self) -> Self {
         self.map_layout(|l| l.align_top())
@@ -151,6 +217,51 @@ impl<S> Text<S> {
         self.style = style;
         self
     }
+
+    /// Specify the way in which the glyphs should be rendered.
+    ///
+    /// The default is `TextRenderMode::Coverage`.
+    pub fn render_mode(mut self, mode: TextRenderMode) -> Self {
+        self.style.render_mode = mode;
+        self
+    }
+
+    /// Outline the glyphs by the given width in points.
+    ///
+    /// Only applies to `TextRenderMode::Sdf`. Outlines are limited to the range of the distance
+    /// field, i.e. `text::sdf::SPREAD` pixels at `text::sdf::EM_SIZE`, scaled by the font size.
+    pub fn outline_width(mut self, width: Scalar) -> Self {
+        self.style.outline_width = width;
+        self
+    }
+
+    /// The colour of the outline. Black by default.
+    pub fn outline_color<C>(mut self, color: C) -> Self
+    where
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        self.style.outline_color = Some(color.into_lin_srgba());
+        self
+    }
+
+    /// Surround the glyphs and their outline with a glow that fades over the given radius in
+    /// points.
+    ///
+    /// Only applies to `TextRenderMode::Sdf`. Like the outline, the glow is limited to the range
+    /// of the distance field.
+    pub fn glow_radius(mut self, radius: Scalar) -> Self {
+        self.style.glow_radius = radius;
+        self
+    }
+
+    /// The colour of the glow. Defaults to the colour of the text.
+    pub fn glow_color<C>(mut self, color: C) -> Self
+    where
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        self.style.glow_color = Some(color.into_lin_srgba());
+        self
+    }
 }
 
 impl<'a, S> DrawingText<'a, S>
@@ -217,6 +328,29 @@ where
         self.map_ty(|ty| ty.y_align(align))
     }
 
+    /// Specify how the text should be aligned along the x axis of its bounding rectangle.
+    ///
+    /// `Align::Start`, `Align::Middle` and `Align::End` align the text to the left, centre and
+    /// right respectively.
+    pub fn align_x(self, align: Align) -> Self {
+        self.map_ty(|ty| ty.align_x(align))
+    }
+
+    /// Specify how the text should be aligned along the y axis of its bounding rectangle.
+    ///
+    /// `Align::Start`, `Align::Middle` and `Align::End` align the text to the bottom, middle and
+    /// top respectively.
+    pub fn align_y(self, align: Align) -> Self {
+        self.map_ty(|ty| ty.align_y(align))
+    }
+
+    /// Wrap the text at the given width using the Unicode line breaking rules.
+    ///
+    /// This sets the width of the text's bounding rectangle along with `Wrap::Unicode`.
+    pub fn max_width(self, width: S) -> Self {
+        self.map_ty(|ty| ty.max_width(width))
+    }
+
     /// Align the top edge of the text with the top edge of its bounding rectangle.
     pub fn align_text_top(self) -> Self {
         self.map_ty(|ty| ty.align_top())
@@ -238,6 +372,44 @@ where
     pub fn layout(self, layout: &Layout) -> Self {
         self.map_ty(|ty| ty.layout(layout))
     }
+
+    /// Specify the way in which the glyphs should be rendered.
+    ///
+    /// The default is `TextRenderMode::Coverage`.
+    pub fn render_mode(self, mode: TextRenderMode) -> Self {
+        self.map_ty(|ty| ty.render_mode(mode))
+    }
+
+    /// Outline the glyphs by the given width in points.
+    ///
+    /// Only applies to `TextRenderMode::Sdf`.
+    pub fn outline_width(self, width: text::Scalar) -> Self {
+        self.map_ty(|ty| ty.outline_width(width))
+    }
+
+    /// The colour of the outline. Black by default.
+    pub fn outline_color<C>(self, color: C) -> Self
+    where
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        self.map_ty(|ty| ty.outline_color(color))
+    }
+
+    /// Surround the glyphs and their outline with a glow that fades over the given radius in
+    /// points.
+    ///
+    /// Only applies to `TextRenderMode::Sdf`.
+    pub fn glow_radius(self, radius: text::Scalar) -> Self {
+        self.map_ty(|ty| ty.glow_radius(radius))
+    }
+
+    /// The colour of the glow. Defaults to the colour of the text.
+    pub fn glow_color<C>(self, color: C) -> Self
+    where
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        self.map_ty(|ty| ty.glow_color(color))
+    }
 }
 
 impl draw::renderer::RenderPrimitive for Text<f32> {
@@ -251,7 +423,15 @@ impl draw::renderer::RenderPrimitive for Text<f32> {
             style,
             text,
         } = self;
-        let Style { color, layout } = style;
+        let Style {
+            color,
+            layout,
+            render_mode,
+            outline_width,
+            outline_color,
+            glow_radius,
+            glow_color,
+        } = style;
         let layout = layout.build();
         let (maybe_x, maybe_y, maybe_z) = (
             spatial.dimensions.x,
@@ -274,7 +454,7 @@ impl draw::renderer::RenderPrimitive for Text<f32> {
         let text_str = &ctxt.text_buffer[text.clone()];
         let text = text::text(text_str).layout(&layout).build(rect);
 
-        // Queue the glyphs to be cached
+        // Collect the glyphs to be cached.
         let font_id = text::font::id(text.font());
         let positioned_glyphs: Vec<_> = text
             .rt_glyphs(
@@ -282,104 +462,112 @@ impl draw::renderer::RenderPrimitive for Text<f32> {
                 ctxt.output_attachment_scale_factor,
             )
             .collect();
-        for glyph in positioned_glyphs.iter() {
-            ctxt.glyph_cache.queue_glyph(font_id.index(), glyph.clone());
-        }
-
-        // Cache the enqueued glyphs within the pixel buffer.
-        let (glyph_cache_w, _) = ctxt.glyph_cache.dimensions();
-        {
-            let draw::renderer::RenderContext {
-                glyph_cache:
-                    &mut draw::renderer::GlyphCache {
-                        ref mut cache,
-                        ref mut pixel_buffer,
-                        ref mut requires_upload,
-                        ..
-                    },
-                ..
-            } = ctxt;
-            let glyph_cache_w = glyph_cache_w as usize;
-            let res = cache.cache_queued(|rect, data| {
-                let width = (rect.max.x - rect.min.x) as usize;
-                let height = (rect.max.y - rect.min.y) as usize;
-                let mut dst_ix = rect.min.y as usize * glyph_cache_w + rect.min.x as usize;
-                let mut src_ix = 0;
-                for _ in 0..height {
-                    let dst_range = dst_ix..dst_ix + width;
-                    let src_range = src_ix..src_ix + width;
-                    let dst_slice = &mut pixel_buffer[dst_range];
-                    let src_slice = &data[src_range];
-                    dst_slice.copy_from_slice(src_slice);
-                    dst_ix += glyph_cache_w;
-                    src_ix += width;
-                }
-                *requires_upload = true;
-            });
-            if let Err(err) = res {
-                eprintln!("failed to cache queued glyphs: {}", err);
-            }
-        }
 
         // Determine the transform to apply to all points.
         let global_transform = ctxt.transform;
         let local_transform = spatial.position.transform() * spatial.orientation.transform();
         let transform = global_transform * local_transform;
 
-        // A function for converting RustType rects to nannou rects.
+        // When exporting to SVG, the glyphs are recorded as outlines rather than cached.
+        if let Some(svg) = ctxt.svg {
+            svg.text(&text, &transform, color);
+            return draw::renderer::PrimitiveRender::default();
+        }
+
+        // A function for converting screen-space (physical pixel) rects to nannou rects.
         let scale_factor = ctxt.output_attachment_scale_factor;
         let (out_w, out_h) = ctxt.output_attachment_size.into();
         let [half_out_w, half_out_h] = [out_w as f32 / 2.0, out_h as f32 / 2.0];
-        let to_nannou_rect = |screen_rect: text::rt::Rect<i32>| {
-            let l = screen_rect.min.x as f32 / scale_factor - half_out_w;
-            let r = screen_rect.max.x as f32 / scale_factor - half_out_w;
-            let t = -(screen_rect.min.y as f32 / scale_factor - half_out_h);
-            let b = -(screen_rect.max.y as f32 / scale_factor - half_out_h);
+        let to_nannou_rect = |min: [f32; 2], max: [f32; 2]| {
+            let l = min[0] / scale_factor - half_out_w;
+            let r = max[0] / scale_factor - half_out_w;
+            let t = -(min[1] / scale_factor - half_out_h);
+            let b = -(max[1] / scale_factor - half_out_h);
             geom::Rect::from_corners(geom::pt2(l, b), geom::pt2(r, t))
         };
 
+        // Create a mesh-compatible vertex from the position and tex_coords.
+        let v = |position: geom::Point2, tex_coords: [f32; 2]| -> draw::mesh::Vertex {
+            let p = geom::Point3::from(position);
+            let p = cgmath::Transform::transform_point(&transform, p.into());
+            let point = draw::mesh::vertex::Point::from(p);
+            draw::mesh::vertex::new(point, color, tex_coords.into())
+        };
+
+        if let TextRenderMode::Sdf = render_mode {
+            // Distances are scaled by the transform in addition to the scale factor.
+            let transform_scale = {
+                let (x, y) = (transform.x, transform.y);
+                (x.x * y.y - x.y * y.x).abs().sqrt()
+            };
+            let px_scale = scale_factor * transform_scale;
+            let outline_color = outline_color.unwrap_or(LinSrgba::new(0.0, 0.0, 0.0, 1.0));
+            let glow_color = glow_color.unwrap_or(color);
+            // Scale the entries from the size at which they were generated to the glyphs' size.
+            let glyph_scale = positioned_glyphs
+                .first()
+                .map(|g| g.scale().y)
+                .unwrap_or(0.0);
+            let k = glyph_scale / text::sdf::EM_SIZE;
+            for g in positioned_glyphs {
+                let entry = match ctxt.sdf_atlas.glyph(font_id.index(), text.font(), g.id()) {
+                    None => continue,
+                    Some(entry) => entry,
+                };
+                let origin = g.position();
+                let min = [origin.x + entry.left * k, origin.y - entry.top * k];
+                let max = [
+                    min[0] + entry.width as f32 * k,
+                    min[1] + entry.height as f32 * k,
+                ];
+                let rect = to_nannou_rect(min, max);
+                let uv = ctxt.sdf_atlas.uv_rect(&entry);
+                push_glyph_quad(mesh, &v, rect, uv);
+            }
+            let c = |c: LinSrgba| [c.red, c.green, c.blue, c.alpha];
+            let px_per_unit = 2.0 * text::sdf::SPREAD * k * transform_scale;
+            let sdf = draw::renderer::SdfVertex {
+                outline_color: c(outline_color),
+                glow_color: c(glow_color),
+                params: [
+                    px_per_unit,
+                    outline_width * px_scale,
+                    glow_radius * px_scale,
+                    0.0,
+                ],
+            };
+            return draw::renderer::PrimitiveRender::sdf_text(sdf);
+        }
+
+        // Cache the enqueued glyphs within the pixel buffer, growing the cache if there is not
+        // enough room for them.
+        loop {
+            for glyph in positioned_glyphs.iter() {
+                ctxt.glyph_cache.queue_glyph(font_id.index(), glyph.clone());
+            }
+            match cache_queued(ctxt.glyph_cache) {
+                Ok(()) => break,
+                Err(text::rt::gpu_cache::CacheWriteErr::NoRoomForWholeQueue)
+                    if ctxt.glyph_cache.grow() =>
+                {
+                    continue
+                }
+                Err(err) => {
+                    eprintln!("failed to cache queued glyphs: {}", err);
+                    break;
+                }
+            }
+        }
+
         // Extend the mesh with a rect for each displayed glyph.
         for g in positioned_glyphs {
             if let Ok(Some((uv_rect, screen_rect))) = ctxt.glyph_cache.rect_for(font_id.index(), &g)
             {
-                let rect = to_nannou_rect(screen_rect);
-
-                // Create a mesh-compatible vertex from the position and tex_coords.
-                let v = |position, tex_coords: [f32; 2]| -> draw::mesh::Vertex {
-                    let p = geom::Point3::from(position);
-                    let p = cgmath::Transform::transform_point(&transform, p.into());
-                    let point = draw::mesh::vertex::Point::from(p);
-                    draw::mesh::vertex::new(point, color, tex_coords.into())
-                };
-
-                // The sides of the UV rect.
-                let uv_l = uv_rect.min.x;
-                let uv_t = uv_rect.min.y;
-                let uv_r = uv_rect.max.x;
-                let uv_b = uv_rect.max.y;
-
-                // Insert the vertices.
-                let bottom_left = v(rect.bottom_left(), [uv_l, uv_b]);
-                let bottom_right = v(rect.bottom_right(), [uv_r, uv_b]);
-                let top_left = v(rect.top_left(), [uv_l, uv_t]);
-                let top_right = v(rect.top_right(), [uv_r, uv_t]);
-                let start_ix = mesh.points().len() as u32;
-                mesh.push_vertex(top_left);
-                mesh.push_vertex(bottom_left);
-                mesh.push_vertex(bottom_right);
-                mesh.push_vertex(top_right);
-
-                // Now the indices.
-                let tl_ix = start_ix;
-                let bl_ix = start_ix + 1;
-                let br_ix = start_ix + 2;
-                let tr_ix = start_ix + 3;
-                mesh.push_index(tl_ix);
-                mesh.push_index(bl_ix);
-                mesh.push_index(br_ix);
-                mesh.push_index(tl_ix);
-                mesh.push_index(br_ix);
-                mesh.push_index(tr_ix);
+                let min = [screen_rect.min.x as f32, screen_rect.min.y as f32];
+                let max = [screen_rect.max.x as f32, screen_rect.max.y as f32];
+                let rect = to_nannou_rect(min, max);
+                let uv = [uv_rect.min.x, uv_rect.min.y, uv_rect.max.x, uv_rect.max.y];
+                push_glyph_quad(mesh, &v, rect, uv);
             }
         }
 
@@ -387,6 +575,65 @@ impl draw::renderer::RenderPrimitive for Text<f32> {
     }
 }
 
+// Extend the mesh with a quad for a single glyph with the given `[left, top, right, bottom]` UVs.
+fn push_glyph_quad<F>(mesh: &mut draw::Mesh, v: &F, rect: geom::Rect, uv: [f32; 4])
+where
+    F: Fn(geom::Point2, [f32; 2]) -> draw::mesh::Vertex,
+{
+    let [uv_l, uv_t, uv_r, uv_b] = uv;
+
+    // Insert the vertices.
+    let bottom_left = v(rect.bottom_left(), [uv_l, uv_b]);
+    let bottom_right = v(rect.bottom_right(), [uv_r, uv_b]);
+    let top_left = v(rect.top_left(), [uv_l, uv_t]);
+    let top_right = v(rect.top_right(), [uv_r, uv_t]);
+    let start_ix = mesh.points().len() as u32;
+    mesh.push_vertex(top_left);
+    mesh.push_vertex(bottom_left);
+    mesh.push_vertex(bottom_right);
+    mesh.push_vertex(top_right);
+
+    // Now the indices.
+    let tl_ix = start_ix;
+    let bl_ix = start_ix + 1;
+    let br_ix = start_ix + 2;
+    let tr_ix = start_ix + 3;
+    mesh.push_index(tl_ix);
+    mesh.push_index(bl_ix);
+    mesh.push_index(br_ix);
+    mesh.push_index(tl_ix);
+    mesh.push_index(br_ix);
+    mesh.push_index(tr_ix);
+}
+
+// Cache the queued glyphs within the glyph cache's pixel buffer.
+fn cache_queued(
+    glyph_cache: &mut draw::renderer::GlyphCache,
+) -> Result<(), text::rt::gpu_cache::CacheWriteErr> {
+    let draw::renderer::GlyphCache {
+        ref mut cache,
+        ref mut pixel_buffer,
+        ref mut requires_upload,
+    } = *glyph_cache;
+    let glyph_cache_w = cache.dimensions().0 as usize;
+    cache.cache_queued(|rect, data| {
+        let width = (rect.max.x - rect.min.x) as usize;
+        let height = (rect.max.y - rect.min.y) as usize;
+        let mut dst_ix = rect.min.y as usize * glyph_cache_w + rect.min.x as usize;
+        let mut src_ix = 0;
+        for _ in 0..height {
+            let dst_range = dst_ix..dst_ix + width;
+            let src_range = src_ix..src_ix + width;
+            let dst_slice = &mut pixel_buffer[dst_range];
+            let src_slice = &data[src_range];
+            dst_slice.copy_from_slice(src_slice);
+            dst_ix += glyph_cache_w;
+            src_ix += width;
+        }
+        *requires_upload = true;
+    })
+}
+
 impl<S> SetOrientation<S> for Text<S> {
     fn properties(&mut self) -> &mut orientation::Properties<S> {
         SetOrientation::properties(&mut self.spatial)
@@ -48,6 +48,18 @@ where
 }
 
 impl<S> Texture<S> {
+    pub(crate) fn from_parts(
+        texture_view: wgpu::TextureView,
+        spatial: spatial::Properties<S>,
+        area: geom::Rect,
+    ) -> Self {
+        Self {
+            texture_view,
+            spatial,
+            area,
+        }
+    }
+
     /// Specify the area of the texture to draw.
     ///
     /// The bounds of the rectangle should represent the desired area as texture coordinates of the
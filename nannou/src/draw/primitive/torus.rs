@@ -0,0 +1,160 @@
+use crate::draw::primitive::solid::{SetSolid, SolidOptions};
+use crate::draw::primitive::Primitive;
+use crate::draw::properties::spatial::{orientation, position};
+use crate::draw::properties::{ColorScalar, LinSrgba, SetColor, SetOrientation, SetPosition};
+use crate::draw::{self, Drawing};
+use crate::geom;
+use crate::math::BaseFloat;
+
+/// Properties related to drawing a **Torus**.
+///
+/// The torus is triangulated with vertex normals and aligned with the *y* axis.
+#[derive(Clone, Debug)]
+pub struct Torus<S = geom::scalar::Default> {
+    radius: Option<S>,
+    tube_radius: Option<S>,
+    resolution: Option<u32>,
+    tube_resolution: Option<u32>,
+    solid: SolidOptions<S>,
+}
+
+/// The drawing context for a **Torus**.
+pub type DrawingTorus<'a, S = geom::scalar::Default> = Drawing<'a, Torus<S>, S>;
+
+// Torus-specific methods.
+
+impl<S> Torus<S>
+where
+    S: BaseFloat,
+{
+    /// Specify the distance from the centre of the torus to the centre of its tube.
+    pub fn radius(mut self, radius: S) -> Self {
+        self.radius = Some(radius);
+        self
+    }
+
+    /// Specify the radius of the tube.
+    pub fn tube_radius(mut self, tube_radius: S) -> Self {
+        self.tube_radius = Some(tube_radius);
+        self
+    }
+
+    /// The number of segments around the torus.
+    pub fn resolution(mut self, resolution: u32) -> Self {
+        self.resolution = Some(resolution);
+        self
+    }
+
+    /// The number of segments around the tube.
+    pub fn tube_resolution(mut self, tube_resolution: u32) -> Self {
+        self.tube_resolution = Some(tube_resolution);
+        self
+    }
+}
+
+// Trait implementations.
+
+impl draw::renderer::RenderPrimitive for Torus<f32> {
+    fn render_primitive(
+        self,
+        ctxt: draw::renderer::RenderContext,
+        mesh: &mut draw::Mesh,
+    ) -> draw::renderer::PrimitiveRender {
+        let Torus {
+            radius,
+            tube_radius,
+            resolution,
+            tube_resolution,
+            solid,
+        } = self;
+        let radius = radius.unwrap_or(50.0);
+        let tube_radius = tube_radius.unwrap_or(15.0);
+        let resolution = resolution.unwrap_or(32);
+        let tube_resolution = tube_resolution.unwrap_or(16);
+        let torus = geom::solid::torus(radius, tube_radius, resolution, tube_resolution);
+        solid.render(torus, ctxt, &draw::theme::Primitive::Torus, mesh)
+    }
+}
+
+impl<S> Default for Torus<S>
+where
+    S: BaseFloat,
+{
+    fn default() -> Self {
+        Torus {
+            radius: None,
+            tube_radius: None,
+            resolution: None,
+            tube_resolution: None,
+            solid: Default::default(),
+        }
+    }
+}
+
+impl<S> SetOrientation<S> for Torus<S> {
+    fn properties(&mut self) -> &mut orientation::Properties<S> {
+        SetOrientation::properties(&mut self.solid)
+    }
+}
+
+impl<S> SetPosition<S> for Torus<S> {
+    fn properties(&mut self) -> &mut position::Properties<S> {
+        SetPosition::properties(&mut self.solid)
+    }
+}
+
+impl<S> SetColor<ColorScalar> for Torus<S> {
+    fn rgba_mut(&mut self) -> &mut Option<LinSrgba> {
+        SetColor::rgba_mut(&mut self.solid)
+    }
+}
+
+impl<S> SetSolid<S> for Torus<S> {
+    fn solid_options_mut(&mut self) -> &mut SolidOptions<S> {
+        SetSolid::solid_options_mut(&mut self.solid)
+    }
+}
+
+// Primitive conversions.
+
+impl<S> From<Torus<S>> for Primitive<S> {
+    fn from(prim: Torus<S>) -> Self {
+        Primitive::Torus(prim)
+    }
+}
+
+impl<S> Into<Option<Torus<S>>> for Primitive<S> {
+    fn into(self) -> Option<Torus<S>> {
+        match self {
+            Primitive::Torus(prim) => Some(prim),
+            _ => None,
+        }
+    }
+}
+
+// Drawing methods.
+
+impl<'a, S> DrawingTorus<'a, S>
+where
+    S: BaseFloat,
+{
+    /// Specify the distance from the centre of the torus to the centre of its tube.
+    pub fn radius(self, radius: S) -> Self {
+        self.map_ty(|ty| ty.radius(radius))
+    }
+
+    /// Specify the radius of the tube.
+    pub fn tube_radius(self, tube_radius: S) -> Self {
+        self.map_ty(|ty| ty.tube_radius(tube_radius))
+    }
+
+    /// The number of segments around the torus.
+    pub fn resolution(self, resolution: u32) -> Self {
+        self.map_ty(|ty| ty.resolution(resolution))
+    }
+
+    /// The number of segments around the tube.
+    pub fn tube_resolution(self, tube_resolution: u32) -> Self {
+        self.map_ty(|ty| ty.tube_resolution(tube_resolution))
+    }
+}
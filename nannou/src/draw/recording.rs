@@ -0,0 +1,562 @@
+//! Items related to recording the commands of a **Draw** for replaying them in later frames.
+//!
+//! See **Draw::record** for details.
+
+use crate::color::LinSrgba;
+use crate::draw::primitive::{self, Primitive};
+use crate::draw::renderer::{GlyphCache, RenderContext, RenderPrimitive, VertexMode};
+use crate::draw::{self, Camera3D, Context, Draw, DrawCommand, Scissor};
+use crate::geom::{Point2, Point3, Vector2};
+use crate::math::{Matrix4, SquareMatrix};
+use crate::serde_derive::{Deserialize, Serialize};
+use crate::text;
+use crate::wgpu;
+use lyon::tessellation::{FillTessellator, StrokeTessellator};
+use serde::{Deserializer, Serializer};
+use std::cell::RefCell;
+use std::ops::Deref;
+
+/// A recording of draw commands whose geometry is tessellated once and may be replayed into a
+/// **Draw** every frame.
+///
+/// Produced via **Draw::record**. Commands are drawn to the recording as they would be to any
+/// other **Draw**, as the recording dereferences to its own inner **Draw** instance.
+///
+/// The first call to `replay` *bakes* the recorded commands: primitives are tessellated with
+/// their transforms applied, producing vertices with absolute positions, and consecutive
+/// primitives sharing the same blend mode, scissor, topology, camera and texture are merged into
+/// a single mesh. Subsequent replays only copy these vertices into the target **Draw**'s mesh.
+///
+/// Text and images depend on the caches of the **Renderer** and so are not baked. Instead, they are
+/// re-submitted as regular primitives upon each replay.
+///
+/// Once baked, commands drawn to the recording are ignored until `invalidate` is called.
+///
+/// Recordings implement **Serialize** and **Deserialize**, allowing baked geometry to be saved to
+/// and loaded from disk, e.g. via `nannou::io::save_to_json`. Serializing bakes the recording if
+/// necessary. As text, images and textures depend on GPU resources, recordings containing them
+/// fail to serialize.
+#[derive(Debug)]
+pub struct DrawRecording {
+    draw: Draw,
+    baked: RefCell<Option<Baked>>,
+}
+
+// The commands of a recording after baking.
+#[derive(Debug)]
+struct Baked {
+    background_color: Option<LinSrgba>,
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug)]
+enum Segment {
+    // Tessellated geometry with absolute vertex positions. The context's transform is unused.
+    Mesh {
+        context: Context,
+        mesh: draw::Mesh,
+        vertex_mode: VertexMode,
+        texture_view: Option<wgpu::TextureView>,
+    },
+    // A primitive that is re-submitted upon each replay, along with its text if any.
+    Primitive {
+        context: Context,
+        primitive: Primitive,
+        text: String,
+    },
+}
+
+// The serializable form of the baked commands of a recording.
+#[derive(Serialize, Deserialize)]
+struct BakedState {
+    background_color: Option<[f32; 4]>,
+    meshes: Vec<MeshState>,
+}
+
+// A baked mesh segment along with the parts of its context that affect rendering.
+//
+// The sampler only applies to textured meshes, which cannot be serialized, and so is omitted.
+#[derive(Serialize, Deserialize)]
+struct MeshState {
+    #[serde(with = "BlendDescriptorDef")]
+    alpha_blend: wgpu::BlendDescriptor,
+    #[serde(with = "BlendDescriptorDef")]
+    color_blend: wgpu::BlendDescriptor,
+    scissor: Scissor,
+    #[serde(with = "PrimitiveTopologyDef")]
+    topology: wgpu::PrimitiveTopology,
+    z_layer: f32,
+    opaque: bool,
+    camera: Option<Camera3D>,
+    vertex_mode: VertexMode,
+    points: Vec<Point3>,
+    indices: Vec<u32>,
+    colors: Vec<[f32; 4]>,
+    tex_coords: Vec<Point2>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "wgpu::BlendDescriptor")]
+struct BlendDescriptorDef {
+    #[serde(with = "BlendFactorDef")]
+    src_factor: wgpu::BlendFactor,
+    #[serde(with = "BlendFactorDef")]
+    dst_factor: wgpu::BlendFactor,
+    #[serde(with = "BlendOperationDef")]
+    operation: wgpu::BlendOperation,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "wgpu::BlendFactor")]
+enum BlendFactorDef {
+    Zero,
+    One,
+    SrcColor,
+    OneMinusSrcColor,
+    SrcAlpha,
+    OneMinusSrcAlpha,
+    DstColor,
+    OneMinusDstColor,
+    DstAlpha,
+    OneMinusDstAlpha,
+    SrcAlphaSaturated,
+    BlendColor,
+    OneMinusBlendColor,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "wgpu::BlendOperation")]
+enum BlendOperationDef {
+    Add,
+    Subtract,
+    ReverseSubtract,
+    Min,
+    Max,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "wgpu::PrimitiveTopology")]
+enum PrimitiveTopologyDef {
+    PointList,
+    LineList,
+    LineStrip,
+    TriangleList,
+    TriangleStrip,
+}
+
+// The resources required to tessellate primitives while baking.
+struct Resources<'a> {
+    intermediary_state: &'a draw::IntermediaryState<f32>,
+    theme: &'a draw::Theme,
+    glyph_cache: GlyphCache,
+    sdf_atlas: text::sdf::SdfAtlas,
+    fill_tessellator: FillTessellator,
+    stroke_tessellator: StrokeTessellator,
+}
+
+impl Draw<f32> {
+    /// Begin a recording of draw commands that may be replayed in later frames without being
+    /// tessellated again.
+    ///
+    /// The recording begins with the same transform, blend mode, scissor and theme as `self`,
+    /// however the commands of the two are stored separately. The recording is intended to be
+    /// stored within the model, e.g. for caching a complex static background:
+    ///
+    /// ```ignore
+    /// fn view(app: &App, model: &Model, frame: Frame) {
+    ///     let draw = app.draw();
+    ///     if !model.background.is_baked() {
+    ///         draw_background(&model.background);
+    ///     }
+    ///     model.background.replay(&draw);
+    ///     // ...
+    /// }
+    /// ```
+    ///
+    /// See **DrawRecording** for details.
+    pub fn record(&self) -> DrawRecording {
+        let mut state = draw::State::default();
+        state.theme = self.state.borrow().theme.clone();
+        let draw = Draw {
+            state: std::rc::Rc::new(RefCell::new(state)),
+            context: self.context.clone(),
+            transform_stack: vec![],
+        };
+        DrawRecording {
+            draw,
+            baked: RefCell::new(None),
+        }
+    }
+}
+
+impl DrawRecording {
+    /// Whether or not the recorded commands have been baked by a call to `replay`.
+    ///
+    /// Commands drawn to the recording while it is baked are ignored.
+    pub fn is_baked(&self) -> bool {
+        self.baked.borrow().is_some()
+    }
+
+    /// Submit the recorded commands to the given **Draw**.
+    ///
+    /// The commands are baked upon the first replay following the creation of the recording or a
+    /// call to `invalidate`. The transform of `draw` is applied on top of the baked geometry,
+    /// allowing for the same recording to be replayed at different positions.
+    pub fn replay(&self, draw: &Draw) {
+        if !self.is_baked() {
+            let baked = self.bake();
+            *self.baked.borrow_mut() = Some(baked);
+        }
+        let baked = self.baked.borrow();
+        let baked = baked.as_ref().expect("recording was not baked");
+
+        if let Some(color) = baked.background_color {
+            draw.state.borrow_mut().background_color = Some(color);
+        }
+
+        for segment in &baked.segments {
+            match *segment {
+                Segment::Mesh {
+                    ref context,
+                    ref mesh,
+                    vertex_mode,
+                    ref texture_view,
+                } => {
+                    let context = Context {
+                        transform: draw.context.transform,
+                        ..context.clone()
+                    };
+                    let mesh = {
+                        let state = draw.state.borrow();
+                        let mut intermediary_state = state.intermediary_state.borrow_mut();
+                        primitive::Mesh::from_baked(
+                            &mut intermediary_state.intermediary_mesh,
+                            mesh,
+                            vertex_mode,
+                            texture_view.clone(),
+                        )
+                    };
+                    with_context(draw, context).a(mesh);
+                }
+                Segment::Primitive {
+                    ref context,
+                    ref primitive,
+                    ref text,
+                } => {
+                    let context = Context {
+                        transform: draw.context.transform * context.transform,
+                        ..context.clone()
+                    };
+                    let mut primitive = primitive.clone();
+                    if let Primitive::Text(ref mut t) = primitive {
+                        let state = draw.state.borrow();
+                        let mut intermediary_state = state.intermediary_state.borrow_mut();
+                        t.move_to_buffer(text, &mut intermediary_state.text_buffer);
+                    }
+                    with_context(draw, context).a(primitive);
+                }
+            }
+        }
+    }
+
+    /// Discard the baked commands along with all commands drawn to the recording.
+    ///
+    /// New commands may then be drawn to the recording, which will be baked upon the next replay.
+    pub fn invalidate(&self) {
+        *self.baked.borrow_mut() = None;
+        self.draw.reset();
+    }
+
+    // Drain the recorded commands, tessellating all primitives that do not depend on the caches of
+    // the renderer.
+    fn bake(&self) -> Baked {
+        let cmds: Vec<_> = self.draw.drain_commands().collect();
+        let baked = {
+            let state = self.draw.state.borrow();
+            let intermediary_state = state.intermediary_state.borrow();
+            let mut resources = Resources {
+                intermediary_state: &intermediary_state,
+                theme: &state.theme,
+                // Text is not baked, so the glyph caches remain unused.
+                glyph_cache: GlyphCache::new([1, 1], 0.1, 0.1),
+                sdf_atlas: text::sdf::SdfAtlas::new(1),
+                fill_tessellator: FillTessellator::new(),
+                stroke_tessellator: StrokeTessellator::new(),
+            };
+            let mut segments = vec![];
+            let mut context = Context::default();
+            for cmd in cmds {
+                match cmd {
+                    DrawCommand::Context(ctxt) => context = ctxt,
+                    DrawCommand::Primitive(prim) => {
+                        let transform = context.transform;
+                        let res = &mut resources;
+                        bake_primitive(prim, &context, &transform, None, res, &mut segments);
+                    }
+                }
+            }
+            Baked {
+                background_color: state.background_color,
+                segments,
+            }
+        };
+        // The recorded commands are no longer required.
+        self.draw.reset();
+        baked
+    }
+}
+
+impl BakedState {
+    // Produce the serializable form of the given baked commands.
+    //
+    // Returns `None` if any segment depends on GPU resources.
+    fn from_baked(baked: &Baked) -> Option<Self> {
+        let background_color = baked.background_color.map(to_array);
+        let mut meshes = vec![];
+        for segment in &baked.segments {
+            let (context, mesh, vertex_mode) = match *segment {
+                Segment::Mesh {
+                    ref context,
+                    ref mesh,
+                    vertex_mode,
+                    texture_view: None,
+                } => (context, mesh, vertex_mode),
+                _ => return None,
+            };
+            meshes.push(MeshState {
+                alpha_blend: context.alpha_blend.clone(),
+                color_blend: context.color_blend.clone(),
+                scissor: context.scissor,
+                topology: context.topology,
+                z_layer: context.z_layer,
+                opaque: context.opaque,
+                camera: context.camera,
+                vertex_mode,
+                points: mesh.points().to_vec(),
+                indices: mesh.indices().to_vec(),
+                colors: mesh.colors().iter().cloned().map(to_array).collect(),
+                tex_coords: mesh.tex_coords().to_vec(),
+            });
+        }
+        Some(BakedState {
+            background_color,
+            meshes,
+        })
+    }
+
+    // Convert back into baked commands.
+    fn into_baked(self) -> Baked {
+        let background_color = self.background_color.map(from_array);
+        let segments = self
+            .meshes
+            .into_iter()
+            .map(|m| {
+                let context = Context {
+                    alpha_blend: m.alpha_blend,
+                    color_blend: m.color_blend,
+                    scissor: m.scissor,
+                    topology: m.topology,
+                    z_layer: m.z_layer,
+                    opaque: m.opaque,
+                    camera: m.camera,
+                    ..Context::default()
+                };
+                let colors: Vec<_> = m.colors.into_iter().map(from_array).collect();
+                let mut mesh = draw::Mesh::default();
+                mesh.extend_from_slices(&m.points, &m.indices, &colors, &m.tex_coords);
+                Segment::Mesh {
+                    context,
+                    mesh,
+                    vertex_mode: m.vertex_mode,
+                    texture_view: None,
+                }
+            })
+            .collect();
+        Baked {
+            background_color,
+            segments,
+        }
+    }
+}
+
+/// Serializes the baked geometry of the recording, baking it first if necessary.
+impl serde::Serialize for DrawRecording {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if !self.is_baked() {
+            let baked = self.bake();
+            *self.baked.borrow_mut() = Some(baked);
+        }
+        let baked = self.baked.borrow();
+        let baked = baked.as_ref().expect("recording was not baked");
+        match BakedState::from_baked(baked) {
+            Some(state) => serde::Serialize::serialize(&state, serializer),
+            None => Err(serde::ser::Error::custom(
+                "recordings containing text, images or textures cannot be serialized",
+            )),
+        }
+    }
+}
+
+/// Deserializes a baked recording, ready to be replayed.
+impl<'de> serde::Deserialize<'de> for DrawRecording {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let state: BakedState = serde::Deserialize::deserialize(deserializer)?;
+        Ok(DrawRecording {
+            draw: Draw::new(),
+            baked: RefCell::new(Some(state.into_baked())),
+        })
+    }
+}
+
+impl Deref for DrawRecording {
+    type Target = Draw;
+    fn deref(&self) -> &Self::Target {
+        &self.draw
+    }
+}
+
+// A **Draw** sharing the state of the given **Draw** with the given context.
+fn with_context(draw: &Draw, context: Context) -> Draw {
+    Draw {
+        state: draw.state.clone(),
+        context,
+        transform_stack: vec![],
+    }
+}
+
+// Tessellate the given primitive with the transform applied, appending it to the segments.
+//
+// Instances are tessellated individually with their colours multiplied by `tint`.
+fn bake_primitive(
+    prim: Primitive,
+    context: &Context,
+    transform: &Matrix4<f32>,
+    tint: Option<LinSrgba>,
+    res: &mut Resources,
+    segments: &mut Vec<Segment>,
+) {
+    match prim {
+        Primitive::Instanced(instanced) => {
+            let primitive::Instanced {
+                primitive,
+                instances,
+            } = instanced;
+            for instance in instances {
+                let transform = *transform * instance.matrix();
+                let tint = Some(match tint {
+                    None => instance.color,
+                    Some(tint) => multiply(tint, instance.color),
+                });
+                let prim = (*primitive).clone();
+                bake_primitive(prim, context, &transform, tint, res, segments);
+            }
+        }
+        prim @ Primitive::Text(_) | prim @ Primitive::Image(_) | prim @ Primitive::Particles(_) => {
+            let text = match prim {
+                Primitive::Text(ref t) => {
+                    let text_buffer = &res.intermediary_state.text_buffer;
+                    t.text_from_buffer(text_buffer).to_string()
+                }
+                _ => String::new(),
+            };
+            segments.push(Segment::Primitive {
+                context: context.clone(),
+                primitive: prim,
+                text,
+            });
+        }
+        prim => {
+            let state = res.intermediary_state;
+            let mut mesh = draw::Mesh::default();
+            let ctxt = RenderContext {
+                transform,
+                intermediary_mesh: &state.intermediary_mesh,
+                path_event_buffer: &state.path_event_buffer,
+                path_points_colored_buffer: &state.path_points_colored_buffer,
+                path_points_textured_buffer: &state.path_points_textured_buffer,
+                text_buffer: &state.text_buffer,
+                theme: res.theme,
+                glyph_cache: &mut res.glyph_cache,
+                sdf_atlas: &mut res.sdf_atlas,
+                fill_tessellator: &mut res.fill_tessellator,
+                stroke_tessellator: &mut res.stroke_tessellator,
+                output_attachment_size: Vector2::new(0.0, 0.0),
+                output_attachment_scale_factor: 1.0,
+                svg: None,
+            };
+            let render = prim.render_primitive(ctxt, &mut mesh);
+            if mesh.indices().is_empty() {
+                return;
+            }
+            let colors: Vec<_> = match tint {
+                None => mesh.colors().to_vec(),
+                Some(tint) => mesh.colors().iter().map(|&c| multiply(c, tint)).collect(),
+            };
+            let vertex_mode = render.vertex_mode;
+            let texture_view = render.texture_view;
+            let target = segment_mesh(segments, context, vertex_mode, texture_view);
+            let offset = target.points().len() as u32;
+            let indices: Vec<u32> = mesh.indices().iter().map(|&i| offset + i).collect();
+            target.extend_from_slices(mesh.points(), &indices, &colors, mesh.tex_coords());
+        }
+    }
+}
+
+// The mesh of the last segment if it matches the given context, vertex mode and texture.
+// Otherwise, begins a new mesh segment.
+fn segment_mesh<'a>(
+    segments: &'a mut Vec<Segment>,
+    context: &Context,
+    vertex_mode: VertexMode,
+    texture_view: Option<wgpu::TextureView>,
+) -> &'a mut draw::Mesh {
+    let context = Context {
+        transform: Matrix4::identity(),
+        ..context.clone()
+    };
+    let texture_id = texture_view.as_ref().map(|v| v.id());
+    let matches = match segments.last() {
+        Some(Segment::Mesh {
+            context: c,
+            vertex_mode: m,
+            texture_view: v,
+            ..
+        }) => *c == context && *m == vertex_mode && v.as_ref().map(|v| v.id()) == texture_id,
+        _ => false,
+    };
+    if !matches {
+        segments.push(Segment::Mesh {
+            context,
+            mesh: Default::default(),
+            vertex_mode,
+            texture_view,
+        });
+    }
+    match segments.last_mut() {
+        Some(Segment::Mesh { mesh, .. }) => mesh,
+        _ => unreachable!(),
+    }
+}
+
+fn multiply(a: LinSrgba, b: LinSrgba) -> LinSrgba {
+    LinSrgba::new(
+        a.red * b.red,
+        a.green * b.green,
+        a.blue * b.blue,
+        a.alpha * b.alpha,
+    )
+}
+
+fn to_array(c: LinSrgba) -> [f32; 4] {
+    [c.red, c.green, c.blue, c.alpha]
+}
+
+fn from_array([r, g, b, a]: [f32; 4]) -> LinSrgba {
+    LinSrgba::new(r, g, b, a)
+}
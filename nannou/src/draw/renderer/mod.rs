@@ -2,7 +2,9 @@ use crate::draw;
 use crate::draw::mesh::vertex::Color;
 use crate::frame::Frame;
 use crate::geom::{self, Point2, Rect, Vector2};
-use crate::math::{map_range, Matrix4};
+use crate::math::{map_range, Matrix4, SquareMatrix};
+use crate::particle;
+use crate::serde_derive::{Deserialize, Serialize};
 use crate::text;
 use crate::wgpu;
 use lyon::path::PathEvent;
@@ -11,6 +13,7 @@ use std::collections::HashMap;
 use std::fmt;
 use std::hash::{Hash, Hasher};
 use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
 
 /// Draw API primitives that may be rendered via the **Renderer** type.
 pub trait RenderPrimitive {
@@ -28,6 +31,9 @@ pub struct PrimitiveRender {
     pub texture_view: Option<wgpu::TextureView>,
     /// The way in which vertices should be coloured in the fragment shader.
     pub vertex_mode: VertexMode,
+    /// If `Some`, the primitive's vertices are rendered via the SDF text pipeline with the given
+    /// parameters, sampling the **Renderer**'s SDF atlas.
+    pub sdf: Option<SdfVertex>,
 }
 
 /// The context provided to primitives to assist with the rendering process.
@@ -40,10 +46,15 @@ pub struct RenderContext<'a> {
     pub text_buffer: &'a str,
     pub theme: &'a draw::Theme,
     pub glyph_cache: &'a mut GlyphCache,
+    pub sdf_atlas: &'a mut text::sdf::SdfAtlas,
     pub fill_tessellator: &'a mut FillTessellator,
     pub stroke_tessellator: &'a mut StrokeTessellator,
     pub output_attachment_size: Vector2, // logical coords
     pub output_attachment_scale_factor: f32,
+    /// If `Some`, primitives are recorded as SVG elements where possible rather than tessellated.
+    ///
+    /// See **Draw::to_svg**.
+    pub svg: Option<&'a mut draw::svg::Recorder>,
 }
 
 pub struct GlyphCache {
@@ -55,8 +66,21 @@ pub struct GlyphCache {
     pub requires_upload: bool,
 }
 
+/// The per-vertex parameters of text rendered via the SDF text pipeline.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct SdfVertex {
+    /// The colour of the outline.
+    pub outline_color: [f32; 4],
+    /// The colour of the glow.
+    pub glow_color: [f32; 4],
+    /// The number of output pixels per unit of the distance field, followed by the outline width
+    /// and glow radius in output pixels. The last value is unused.
+    pub params: [f32; 4],
+}
+
 /// A top-level indicator of whether or not
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[repr(u32)]
 pub enum VertexMode {
     /// Use the color values and ignore the texture coordinates.
@@ -67,17 +91,28 @@ pub enum VertexMode {
     ///
     /// Uses the color values, but multiplies the alpha by the glyph cache texture's red value.
     Text = 2,
+    /// A special mode used by radial gradients.
+    ///
+    /// Samples the texture at the distance of the texture coordinates from the origin, where the
+    /// texture describes the colours along the gradient.
+    RadialGradient = 3,
 }
 
 /// A helper type aimed at simplifying the rendering of conrod primitives via wgpu.
 #[derive(Debug)]
 pub struct Renderer {
     glyph_cache: GlyphCache,
+    sdf_atlas: text::sdf::SdfAtlas,
     vs_mod: wgpu::ShaderModule,
+    vs_instanced_mod: wgpu::ShaderModule,
+    vs_particles_mod: wgpu::ShaderModule,
+    vs_sdf_mod: wgpu::ShaderModule,
     fs_mod: wgpu::ShaderModule,
+    fs_sdf_mod: wgpu::ShaderModule,
     // One pipeline per unique Pipeline ID (combination of blend, topology and component type).
     pipelines: HashMap<PipelineId, wgpu::RenderPipeline>,
     glyph_cache_texture: wgpu::Texture,
+    sdf_atlas_texture: wgpu::Texture,
     depth_texture: wgpu::Texture,
     depth_texture_view: wgpu::TextureView,
     default_texture: wgpu::Texture,
@@ -86,16 +121,34 @@ pub struct Renderer {
     uniform_bind_group: wgpu::BindGroup,
     text_bind_group_layout: wgpu::BindGroupLayout,
     text_bind_group: wgpu::BindGroup,
+    sdf_bind_group: wgpu::BindGroup,
     texture_samplers: HashMap<SamplerId, wgpu::Sampler>,
     texture_bind_group_layouts: HashMap<wgpu::TextureComponentType, wgpu::BindGroupLayout>,
     texture_bind_groups: HashMap<BindGroupId, wgpu::BindGroup>,
     output_color_format: wgpu::TextureFormat,
     sample_count: u32,
-    scale_factor: f32,
     render_commands: Vec<RenderCommand>,
     mesh: draw::Mesh,
     vertex_mode_buffer: Vec<VertexMode>,
+    // Per-vertex SDF parameters. Only filled once an SDF primitive is drawn during the frame.
+    sdf_vertex_buffer: Vec<SdfVertex>,
+    // Per-instance data for all **Instanced** primitives.
+    instance_buffer: Vec<InstanceData>,
+    // Provides the buffer to which `instance_buffer` is uploaded each frame. Either shared with
+    // the device via `Builder::buffer_pool`, or owned by the renderer.
+    buffer_pool: wgpu::BufferPool,
+    // Whether the renderer owns `buffer_pool`, in which case the renderer advances its frame.
+    owns_buffer_pool: bool,
+    // The particle systems drawn during the frame, indexed by `RenderCommand::DrawParticles`.
+    particle_systems: Vec<particle::Handle>,
+    // The GPU meshes drawn during the frame, indexed by `RenderCommand::DrawGpuMesh`.
+    gpu_meshes: Vec<draw::primitive::GpuMesh>,
+    // The uniforms for the default projection followed by those of each camera used in the frame.
+    uniforms: Vec<Uniforms>,
     uniform_buffer: wgpu::Buffer,
+    // The number of **Uniforms** for which there is room within the `uniform_buffer`.
+    uniform_buffer_capacity: usize,
+    image_cache: ImageCache,
 }
 
 /// A type aimed at simplifying construction of a `draw::Renderer`.
@@ -105,6 +158,8 @@ pub struct Builder {
     pub glyph_cache_size: [u32; 2],
     pub glyph_cache_scale_tolerance: f32,
     pub glyph_cache_position_tolerance: f32,
+    pub image_cache_capacity: usize,
+    pub buffer_pool: Option<wgpu::BufferPool>,
 }
 
 /// Commands that map to wgpu encodable commands.
@@ -114,13 +169,34 @@ enum RenderCommand {
     SetPipeline(PipelineId),
     /// Change bind group for a new image.
     SetBindGroup(BindGroupId),
+    /// Change the uniforms to those at the given index for a new camera.
+    SetUniforms(usize),
     /// Set the rectangular scissor.
     SetScissor(Scissor),
-    /// Draw the given vertex range.
+    /// Draw the given vertex range for the given range of instances.
     DrawIndexed {
         start_vertex: i32,
         index_range: std::ops::Range<u32>,
+        instance_range: std::ops::Range<u32>,
+    },
+    /// Draw the given index range once for each particle of the particle system at the given
+    /// index, reading the instance data from the system's particle buffer.
+    DrawParticles {
+        system: usize,
+        index_range: std::ops::Range<u32>,
+        instance_count: u32,
     },
+    /// Draw the GPU mesh at the given index from its own retained buffers.
+    DrawGpuMesh(usize),
+}
+
+// The buffers to which the frame's mesh is uploaded.
+struct MeshBuffers {
+    points: wgpu::Buffer,
+    colors: wgpu::Buffer,
+    tex_coords: wgpu::Buffer,
+    modes: wgpu::Buffer,
+    indices: wgpu::Buffer,
 }
 
 /// The position and dimensions of the scissor.
@@ -135,6 +211,39 @@ pub struct Scissor {
 #[derive(Debug)]
 pub struct DrawError;
 
+/// Textures uploaded for **Image** primitives, cached between frames.
+#[derive(Debug)]
+struct ImageCache {
+    // The maximum number of textures to keep around once they are no longer drawn.
+    capacity: usize,
+    // Incremented each time the renderer is filled.
+    frame: u64,
+    // The number of raw images drawn during the current frame.
+    raw_count: usize,
+    entries: HashMap<ImageCacheKey, CachedImage>,
+    // Data to be uploaded to their respective textures upon encoding the render pass.
+    pending_uploads: Vec<(wgpu::Texture, Arc<Vec<u8>>)>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+enum ImageCacheKey {
+    Content(u64),
+    // Raw images are identified by their order within the frame, allowing their textures to be
+    // re-used between frames.
+    Raw {
+        index: usize,
+        size: [u32; 2],
+        format: wgpu::TextureFormat,
+    },
+}
+
+#[derive(Debug)]
+struct CachedImage {
+    texture: wgpu::Texture,
+    texture_view: wgpu::TextureView,
+    last_used: u64,
+}
+
 #[repr(C)]
 #[derive(Copy, Clone, Debug)]
 struct Uniforms {
@@ -149,9 +258,44 @@ struct Uniforms {
     /// - x is transformed from (-half_logical_win_w, half_logical_win_w) to (-1, 1).
     /// - y is transformed from (-half_logical_win_h, half_logical_win_h) to (1, -1).
     /// - z is transformed from (-max_logical_win_side, max_logical_win_side) to (0, 1).
+    ///
+    /// When drawing with a **Camera3D**, this is the camera's view-projection matrix instead.
     proj: Matrix4<f32>,
 }
 
+// The distance between each **Uniforms** within the uniform buffer, allowing each to be selected
+// via a dynamic offset.
+const UNIFORMS_STRIDE: wgpu::BufferAddress = wgpu::BIND_BUFFER_ALIGNMENT;
+
+// The per-instance attributes read from the particle buffer of a particle system, matching
+// `shader_particles.vert`. The velocity of each particle is skipped.
+const PARTICLE_ATTRIBUTES: [wgpu::VertexAttributeDescriptor; 3] = [
+    wgpu::VertexAttributeDescriptor {
+        offset: 0,
+        format: wgpu::VertexFormat::Float2,
+        shader_location: 4,
+    },
+    wgpu::VertexAttributeDescriptor {
+        offset: 16,
+        format: wgpu::VertexFormat::Float4,
+        shader_location: 5,
+    },
+    wgpu::VertexAttributeDescriptor {
+        offset: 32,
+        format: wgpu::VertexFormat::Float2,
+        shader_location: 6,
+    },
+];
+
+/// The data uploaded to the instance buffer for each instance of an **Instanced** primitive.
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+struct InstanceData {
+    /// The columns of the instance's transform.
+    transform: [[f32; 4]; 4],
+    color: [f32; 4],
+}
+
 type SamplerId = u64;
 type BindGroupId = (SamplerId, wgpu::TextureViewId);
 type BlendId = u64;
@@ -165,6 +309,9 @@ struct PipelineId {
     alpha_id: AlphaId,
     topology: wgpu::PrimitiveTopology,
     texture_component_type: wgpu::TextureComponentType,
+    instanced: bool,
+    particles: bool,
+    sdf: bool,
 }
 
 impl Default for PrimitiveRender {
@@ -177,16 +324,26 @@ impl RenderPrimitive for draw::Primitive {
     fn render_primitive(self, ctxt: RenderContext, mesh: &mut draw::Mesh) -> PrimitiveRender {
         match self {
             draw::Primitive::Arrow(prim) => prim.render_primitive(ctxt, mesh),
+            draw::Primitive::Bezier(prim) => prim.render_primitive(ctxt, mesh),
+            draw::Primitive::Box3d(prim) => prim.render_primitive(ctxt, mesh),
+            draw::Primitive::CatmullRom(prim) => prim.render_primitive(ctxt, mesh),
+            draw::Primitive::Cone(prim) => prim.render_primitive(ctxt, mesh),
+            draw::Primitive::Contour(prim) => prim.render_primitive(ctxt, mesh),
+            draw::Primitive::Cylinder(prim) => prim.render_primitive(ctxt, mesh),
             draw::Primitive::Mesh(prim) => prim.render_primitive(ctxt, mesh),
             draw::Primitive::Path(prim) => prim.render_primitive(ctxt, mesh),
             draw::Primitive::Polygon(prim) => prim.render_primitive(ctxt, mesh),
             draw::Primitive::Tri(prim) => prim.render_primitive(ctxt, mesh),
             draw::Primitive::Ellipse(prim) => prim.render_primitive(ctxt, mesh),
+            draw::Primitive::GpuMesh(prim) => prim.render_primitive(ctxt, mesh),
+            draw::Primitive::GradientFill(prim) => prim.render_primitive(ctxt, mesh),
             draw::Primitive::Quad(prim) => prim.render_primitive(ctxt, mesh),
             draw::Primitive::Rect(prim) => prim.render_primitive(ctxt, mesh),
+            draw::Primitive::Sphere(prim) => prim.render_primitive(ctxt, mesh),
             draw::Primitive::Line(prim) => prim.render_primitive(ctxt, mesh),
             draw::Primitive::Text(prim) => prim.render_primitive(ctxt, mesh),
             draw::Primitive::Texture(prim) => prim.render_primitive(ctxt, mesh),
+            draw::Primitive::Torus(prim) => prim.render_primitive(ctxt, mesh),
             _ => PrimitiveRender::default(),
         }
     }
@@ -208,6 +365,7 @@ impl PrimitiveRender {
         PrimitiveRender {
             texture_view: None,
             vertex_mode,
+            sdf: None,
         }
     }
 
@@ -219,12 +377,21 @@ impl PrimitiveRender {
         PrimitiveRender {
             vertex_mode: VertexMode::Texture,
             texture_view: Some(texture_view),
+            sdf: None,
         }
     }
 
     pub fn text() -> Self {
         Self::vertex_mode(VertexMode::Text)
     }
+
+    /// Text rendered via the SDF text pipeline with the given parameters.
+    pub fn sdf_text(sdf: SdfVertex) -> Self {
+        PrimitiveRender {
+            sdf: Some(sdf),
+            ..Self::text()
+        }
+    }
 }
 
 impl Builder {
@@ -238,6 +405,8 @@ impl Builder {
     /// The default position tolerance for the glyph cache.
     pub const DEFAULT_GLYPH_CACHE_POSITION_TOLERANCE: f32 =
         Renderer::DEFAULT_GLYPH_CACHE_POSITION_TOLERANCE;
+    /// The default number of image textures to keep cached once they are no longer drawn.
+    pub const DEFAULT_IMAGE_CACHE_CAPACITY: usize = Renderer::DEFAULT_IMAGE_CACHE_CAPACITY;
 
     /// Begin building a new **draw::Renderer**.
     pub fn new() -> Self {
@@ -246,6 +415,8 @@ impl Builder {
             glyph_cache_size: Self::DEFAULT_GLYPH_CACHE_SIZE,
             glyph_cache_scale_tolerance: Self::DEFAULT_GLYPH_CACHE_SCALE_TOLERANCE,
             glyph_cache_position_tolerance: Self::DEFAULT_GLYPH_CACHE_POSITION_TOLERANCE,
+            image_cache_capacity: Self::DEFAULT_IMAGE_CACHE_CAPACITY,
+            buffer_pool: None,
         }
     }
 
@@ -285,6 +456,25 @@ impl Builder {
         self
     }
 
+    /// The number of textures uploaded for `draw.image(..)` calls that may remain cached once
+    /// they are no longer drawn.
+    ///
+    /// When exceeded, the least recently drawn textures are freed first.
+    pub fn image_cache_capacity(mut self, capacity: usize) -> Self {
+        self.image_cache_capacity = capacity;
+        self
+    }
+
+    /// The pool from which the renderer should request the buffers that it uploads each frame.
+    ///
+    /// This is normally the pool of the **DeviceQueuePair** on which the renderer is built, as
+    /// its frames are advanced by nannou at the end of each application loop. By default, the
+    /// renderer owns a pool of its own and advances it each time it encodes a render pass.
+    pub fn buffer_pool(mut self, pool: wgpu::BufferPool) -> Self {
+        self.buffer_pool = Some(pool);
+        self
+    }
+
     /// Build the **draw::Renderer** ready to target an output attachment of the given descriptor.
     pub fn build_from_texture_descriptor(
         self,
@@ -311,7 +501,7 @@ impl Builder {
         sample_count: u32,
         output_color_format: wgpu::TextureFormat,
     ) -> Renderer {
-        Renderer::new(
+        let mut renderer = Renderer::new(
             device,
             output_attachment_size,
             output_scale_factor,
@@ -321,12 +511,18 @@ impl Builder {
             self.glyph_cache_size,
             self.glyph_cache_scale_tolerance,
             self.glyph_cache_position_tolerance,
-        )
+        );
+        renderer.image_cache.capacity = self.image_cache_capacity;
+        if let Some(pool) = self.buffer_pool {
+            renderer.buffer_pool = pool;
+            renderer.owns_buffer_pool = false;
+        }
+        renderer
     }
 }
 
 impl GlyphCache {
-    fn new(size: [u32; 2], scale_tolerance: f32, position_tolerance: f32) -> Self {
+    pub(crate) fn new(size: [u32; 2], scale_tolerance: f32, position_tolerance: f32) -> Self {
         let [w, h] = size;
         let cache = text::GlyphCache::builder()
             .dimensions(w, h)
@@ -342,6 +538,143 @@ impl GlyphCache {
             requires_upload,
         }
     }
+
+    /// Double the dimensions of the cache, up to `Renderer::MAX_GLYPH_CACHE_SIZE`.
+    ///
+    /// All glyphs are cleared from the cache and must be re-queued. The **Renderer** resizes the
+    /// glyph cache texture to match upon the next render pass. Text rendered earlier within the
+    /// same frame may display incorrect glyphs until the following frame.
+    ///
+    /// Returns `false` if the cache is already at its maximum size.
+    pub fn grow(&mut self) -> bool {
+        let (w, h) = self.cache.dimensions();
+        let max = Renderer::MAX_GLYPH_CACHE_SIZE;
+        if w >= max && h >= max {
+            return false;
+        }
+        let (w, h) = ((w * 2).min(max), (h * 2).min(max));
+        self.cache
+            .to_builder()
+            .dimensions(w, h)
+            .rebuild(&mut self.cache);
+        self.pixel_buffer.clear();
+        self.pixel_buffer.resize(w as usize * h as usize, 0);
+        self.requires_upload = true;
+        true
+    }
+}
+
+impl ImageCache {
+    fn new(capacity: usize) -> Self {
+        ImageCache {
+            capacity,
+            frame: 0,
+            raw_count: 0,
+            entries: Default::default(),
+            pending_uploads: vec![],
+        }
+    }
+
+    // Called at the beginning of each `fill`.
+    fn begin_frame(&mut self) {
+        self.frame = self.frame.wrapping_add(1);
+        self.raw_count = 0;
+    }
+
+    // Convert any `Image` primitives to `Texture`s and provide gradients with their lookup tables,
+    // uploading their data if necessary.
+    fn resolve(&mut self, device: &wgpu::Device, prim: draw::Primitive) -> draw::Primitive {
+        match prim {
+            draw::Primitive::Image(image) => {
+                let texture_view = self.texture_view(device, image.data());
+                draw::Primitive::Texture(image.into_texture(texture_view))
+            }
+            draw::Primitive::GradientFill(mut fill) => {
+                fill.primitive = Box::new(self.resolve(device, *fill.primitive));
+                fill.lut = Some(self.texture_view(device, &fill.gradient.lut()));
+                draw::Primitive::GradientFill(fill)
+            }
+            prim => prim,
+        }
+    }
+
+    // Retrieve the texture view for the given image data, creating the texture if necessary.
+    fn texture_view(
+        &mut self,
+        device: &wgpu::Device,
+        data: &draw::primitive::image::ImageData,
+    ) -> wgpu::TextureView {
+        let size = [data.width, data.height];
+        let key = match data.key {
+            draw::primitive::image::ImageKey::Content(hash) => ImageCacheKey::Content(hash),
+            draw::primitive::image::ImageKey::Raw => {
+                let index = self.raw_count;
+                self.raw_count += 1;
+                let format = data.format;
+                ImageCacheKey::Raw {
+                    index,
+                    size,
+                    format,
+                }
+            }
+        };
+        let frame = self.frame;
+        let mut requires_upload = match key {
+            ImageCacheKey::Content(_) => false,
+            ImageCacheKey::Raw { .. } => true,
+        };
+        let entry = self.entries.entry(key).or_insert_with(|| {
+            requires_upload = true;
+            let texture = wgpu::TextureBuilder::new()
+                .size(size)
+                .format(data.format)
+                .usage(wgpu::TextureUsage::SAMPLED | wgpu::TextureUsage::COPY_DST)
+                .build(device);
+            let texture_view = texture.view().build();
+            CachedImage {
+                texture,
+                texture_view,
+                last_used: frame,
+            }
+        });
+        entry.last_used = frame;
+        if requires_upload {
+            let upload = (entry.texture.clone(), data.bytes.clone());
+            self.pending_uploads.push(upload);
+        }
+        entry.texture_view.clone()
+    }
+
+    // Free the least recently used textures that were not drawn this frame until the number of
+    // cached textures is within capacity.
+    fn evict(&mut self) {
+        while self.entries.len() > self.capacity {
+            let frame = self.frame;
+            let oldest = self
+                .entries
+                .iter()
+                .filter(|(_, entry)| entry.last_used != frame)
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(&key, _)| key);
+            match oldest {
+                Some(key) => {
+                    self.entries.remove(&key);
+                }
+                None => break,
+            }
+        }
+    }
+
+    // Encode the uploads for all images that were created or changed during the last `fill`.
+    fn encode_pending_uploads(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+    ) {
+        for (texture, bytes) in self.pending_uploads.drain(..) {
+            texture.upload_data(device, encoder, &bytes);
+        }
+    }
 }
 
 impl Renderer {
@@ -355,6 +688,12 @@ impl Renderer {
     pub const DEFAULT_GLYPH_CACHE_POSITION_TOLERANCE: f32 = 0.1;
     /// The texture format of the inner glyph cache.
     pub const GLYPH_CACHE_TEXTURE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::R8Unorm;
+    /// The size to which the glyph cache may grow when it has no room for the glyphs of a frame.
+    pub const MAX_GLYPH_CACHE_SIZE: u32 = 8192;
+    /// The default number of image textures to keep cached once they are no longer drawn.
+    pub const DEFAULT_IMAGE_CACHE_CAPACITY: usize = 32;
+    /// The texture format of the inner SDF atlas.
+    pub const SDF_ATLAS_TEXTURE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
 
     /// Create a new **Renderer**, ready to target an output attachment with the given size, sample
     /// count and color format.
@@ -366,6 +705,9 @@ impl Renderer {
     ///
     /// The `glyph_cache_size` will be used to create a texture on which glyphs will be stored for
     /// efficient look-up.
+    ///
+    /// Up to `DEFAULT_IMAGE_CACHE_CAPACITY` textures uploaded for **Image** primitives may remain
+    /// cached once they are no longer drawn. See `RendererBuilder::image_cache_capacity`.
     pub fn new(
         device: &wgpu::Device,
         output_attachment_size: [u32; 2],
@@ -386,17 +728,26 @@ impl Renderer {
 
         // Load shader modules.
         let vs_mod = wgpu::shader_from_spirv_bytes(device, include_bytes!("shaders/vert.spv"));
+        let vs_instanced_mod =
+            wgpu::shader_from_spirv_bytes(device, include_bytes!("shaders/vert_instanced.spv"));
+        let vs_particles_mod =
+            wgpu::shader_from_spirv_bytes(device, include_bytes!("shaders/vert_particles.spv"));
         let fs_mod = wgpu::shader_from_spirv_bytes(device, include_bytes!("shaders/frag.spv"));
+        let vs_sdf_mod =
+            wgpu::shader_from_spirv_bytes(device, include_bytes!("shaders/vert_sdf.spv"));
+        let fs_sdf_mod =
+            wgpu::shader_from_spirv_bytes(device, include_bytes!("shaders/frag_sdf.spv"));
 
         // Create the glyph cache texture.
         let text_sampler = wgpu::SamplerBuilder::new().build(device);
-        let glyph_cache_texture = wgpu::TextureBuilder::new()
-            .size(glyph_cache_size)
-            .usage(wgpu::TextureUsage::SAMPLED | wgpu::TextureUsage::COPY_DST)
-            .format(Self::GLYPH_CACHE_TEXTURE_FORMAT)
-            .build(device);
+        let glyph_cache_texture = create_glyph_cache_texture(device, glyph_cache_size);
         let glyph_cache_texture_view = glyph_cache_texture.create_default_view();
 
+        // Create the SDF atlas texture. Distance fields rely on the sampler's linear filtering.
+        let sdf_atlas = text::sdf::SdfAtlas::default();
+        let sdf_atlas_texture = create_sdf_atlas_texture(device, sdf_atlas.dimensions());
+        let sdf_atlas_texture_view = sdf_atlas_texture.create_default_view();
+
         // Create the depth texture.
         let depth_texture =
             create_depth_texture(device, output_attachment_size, depth_format, sample_count);
@@ -410,10 +761,11 @@ impl Renderer {
         let default_texture_view = default_texture.view().build();
 
         // Initial uniform buffer values. These will be overridden on draw.
-        let uniforms = create_uniforms(output_attachment_size, output_scale_factor);
-        let uniforms_bytes = uniforms_as_bytes(&uniforms);
+        let uniforms = vec![create_uniforms(output_attachment_size, output_scale_factor)];
+        let uniforms_bytes = uniforms_as_aligned_bytes(&uniforms);
         let usage = wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST;
-        let uniform_buffer = device.create_buffer_with_data(uniforms_bytes, usage);
+        let uniform_buffer = device.create_buffer_with_data(&uniforms_bytes, usage);
+        let uniform_buffer_capacity = uniforms.len();
 
         // Bind group for uniforms.
         let uniform_bind_group_layout = create_uniform_bind_group_layout(device);
@@ -429,6 +781,14 @@ impl Renderer {
             &glyph_cache_texture_view,
         );
 
+        // Bind group for SDF text, sharing the layout of the text bind group.
+        let sdf_bind_group = create_text_bind_group(
+            device,
+            &text_bind_group_layout,
+            &text_sampler,
+            &sdf_atlas_texture_view,
+        );
+
         // Initialise the sampler set with the default sampler.
         let sampler_desc = wgpu::SamplerBuilder::new().into_descriptor();
         let sampler_id = sampler_descriptor_hash(&sampler_desc);
@@ -445,12 +805,25 @@ impl Renderer {
         let render_commands = vec![];
         let mesh = Default::default();
         let vertex_mode_buffer = vec![];
+        let sdf_vertex_buffer = vec![];
+        let instance_buffer = vec![];
+        let buffer_pool = Default::default();
+        let owns_buffer_pool = true;
+        let particle_systems = vec![];
+        let gpu_meshes = vec![];
+        let image_cache = ImageCache::new(Self::DEFAULT_IMAGE_CACHE_CAPACITY);
 
         Self {
             vs_mod,
+            vs_instanced_mod,
+            vs_particles_mod,
+            vs_sdf_mod,
             fs_mod,
+            fs_sdf_mod,
             glyph_cache,
             glyph_cache_texture,
+            sdf_atlas,
+            sdf_atlas_texture,
             depth_texture,
             depth_texture_view,
             default_texture,
@@ -459,17 +832,26 @@ impl Renderer {
             uniform_bind_group,
             text_bind_group_layout,
             text_bind_group,
+            sdf_bind_group,
             texture_samplers,
             texture_bind_group_layouts,
             texture_bind_groups,
             pipelines,
             output_color_format,
             sample_count,
-            scale_factor: output_scale_factor,
             render_commands,
             mesh,
             vertex_mode_buffer,
+            sdf_vertex_buffer,
+            instance_buffer,
+            buffer_pool,
+            owns_buffer_pool,
+            particle_systems,
+            gpu_meshes,
+            uniforms,
             uniform_buffer,
+            uniform_buffer_capacity,
+            image_cache,
         }
     }
 
@@ -478,6 +860,11 @@ impl Renderer {
         self.render_commands.clear();
         self.mesh.clear();
         self.vertex_mode_buffer.clear();
+        self.sdf_vertex_buffer.clear();
+        self.instance_buffer.clear();
+        self.particle_systems.clear();
+        self.gpu_meshes.clear();
+        self.uniforms.clear();
     }
 
     /// Generate a list of `RenderCommand`s from the given **Draw** instance and prepare any
@@ -498,6 +885,7 @@ impl Renderer {
         fn push_draw_cmd(
             curr_start_index: &mut u32,
             end_index: u32,
+            instance_range: std::ops::Range<u32>,
             render_commands: &mut Vec<RenderCommand>,
         ) -> bool {
             let index_range = *curr_start_index..end_index;
@@ -507,6 +895,7 @@ impl Renderer {
                 let cmd = RenderCommand::DrawIndexed {
                     start_vertex,
                     index_range,
+                    instance_range,
                 };
                 render_commands.push(cmd);
                 true
@@ -521,6 +910,7 @@ impl Renderer {
         let px_to_pt = |s: u32| s as f32 / scale_factor;
         let pt_to_px = |s: f32| (s * scale_factor).round() as u32;
         let full_rect = Rect::from_w_h(px_to_pt(w_px), px_to_pt(h_px));
+        let logical_size = Vector2::new(px_to_pt(w_px), px_to_pt(h_px));
 
         let window_to_scissor = |v: Vector2| -> [u32; 2] {
             let x = map_range(v.x, full_rect.left(), full_rect.right(), 0u32, w_px);
@@ -542,15 +932,58 @@ impl Renderer {
         let mut curr_pipeline_id = None;
         let mut curr_scissor = None;
         let mut curr_tex_sampler_id = None;
+        // The uniforms at index `0` describe the default projection and are used until a camera is
+        // set. A new set of uniforms is pushed each time the camera changes.
+        self.uniforms
+            .push(create_uniforms(output_attachment_size, scale_factor));
+        let mut curr_camera = None;
+        let mut camera_uniforms_index = 0;
+        let mut curr_uniforms_index = 0;
 
         // Collect all draw commands to avoid borrow errors.
         let draw_cmds: Vec<_> = draw.drain_commands().collect();
+        self.image_cache.begin_frame();
         let draw_state = draw.state.borrow_mut();
         let intermediary_state = draw_state.intermediary_state.borrow();
         for cmd in draw_cmds {
             match cmd {
                 draw::DrawCommand::Context(ctxt) => curr_ctxt = ctxt,
                 draw::DrawCommand::Primitive(prim) => {
+                    // Ensure any images have been uploaded to textures.
+                    let prim = self.image_cache.resolve(device, prim);
+
+                    // Instanced primitives and particle sprites are rendered once without the
+                    // context transform, which is instead applied on the GPU.
+                    let (prim, instances, particle_system) = match prim {
+                        draw::Primitive::Instanced(instanced) => {
+                            if instanced.instances.is_empty() {
+                                continue;
+                            }
+                            (*instanced.primitive, Some(instanced.instances), None)
+                        }
+                        draw::Primitive::Particles(particles) => {
+                            (*particles.primitive, None, Some(particles.system))
+                        }
+                        prim => (prim, None, None),
+                    };
+
+                    // GPU meshes are drawn from their own retained buffers, with the context
+                    // transform applied on the GPU.
+                    let gpu_mesh = match prim {
+                        draw::Primitive::GpuMesh(ref gpu_mesh) => {
+                            if gpu_mesh.mesh.data.indices.is_empty() {
+                                continue;
+                            }
+                            Some(gpu_mesh.clone())
+                        }
+                        _ => None,
+                    };
+                    let transform = if instances.is_some() || particle_system.is_some() {
+                        Matrix4::identity()
+                    } else {
+                        curr_ctxt.transform
+                    };
+
                     // Track the prev index and vertex counts.
                     let prev_index_count = self.mesh.indices().len() as u32;
                     let prev_vert_count = self.mesh.vertex_count();
@@ -564,19 +997,24 @@ impl Renderer {
                             .path_points_textured_buffer,
                         text_buffer: &intermediary_state.text_buffer,
                         theme: &draw_state.theme,
-                        transform: &curr_ctxt.transform,
+                        transform: &transform,
                         fill_tessellator: &mut fill_tessellator,
                         stroke_tessellator: &mut stroke_tessellator,
                         glyph_cache: &mut self.glyph_cache,
+                        sdf_atlas: &mut self.sdf_atlas,
                         output_attachment_size: Vector2::new(px_to_pt(w_px), px_to_pt(h_px)),
                         output_attachment_scale_factor: scale_factor,
+                        svg: None,
                     };
 
                     // Render the primitive.
-                    let render = prim.render_primitive(ctxt, &mut self.mesh);
+                    let render = match gpu_mesh {
+                        Some(ref gpu_mesh) => gpu_mesh.primitive_render(),
+                        None => prim.render_primitive(ctxt, &mut self.mesh),
+                    };
 
                     // If the mesh indices are unchanged, there's nothing to be drawn.
-                    if prev_index_count == self.mesh.indices().len() as u32 {
+                    if gpu_mesh.is_none() && prev_index_count == self.mesh.indices().len() as u32 {
                         assert_eq!(
                             prev_vert_count,
                             self.mesh.vertex_count(),
@@ -607,6 +1045,9 @@ impl Renderer {
                             alpha_id,
                             topology,
                             texture_component_type,
+                            instanced: instances.is_some(),
+                            particles: particle_system.is_some(),
+                            sdf: render.sdf.is_some(),
                         }
                     };
                     let new_bind_group_id = {
@@ -614,20 +1055,46 @@ impl Renderer {
                         (sampler_id, tex_view_id)
                     };
                     let new_scissor = curr_ctxt.scissor;
+                    if curr_ctxt.camera != curr_camera {
+                        curr_camera = curr_ctxt.camera;
+                        camera_uniforms_index = match curr_camera {
+                            None => 0,
+                            Some(ref camera) => {
+                                let proj = camera.view_projection(logical_size);
+                                self.uniforms.push(Uniforms { proj });
+                                self.uniforms.len() - 1
+                            }
+                        };
+                    }
+
+                    // Particle systems and GPU meshes are drawn with uniforms that include the
+                    // context transform.
+                    let new_uniforms_index = match particle_system.is_some() || gpu_mesh.is_some() {
+                        false => camera_uniforms_index,
+                        true => {
+                            let proj =
+                                self.uniforms[camera_uniforms_index].proj * curr_ctxt.transform;
+                            self.uniforms.push(Uniforms { proj });
+                            self.uniforms.len() - 1
+                        }
+                    };
 
                     // Determine which have changed and in turn which require submitting new
                     // commands.
                     let pipeline_changed = Some(new_pipeline_id) != curr_pipeline_id;
                     let bind_group_changed = Some(new_bind_group_id) != curr_tex_sampler_id;
                     let scissor_changed = Some(new_scissor) != curr_scissor;
+                    let uniforms_changed = new_uniforms_index != curr_uniforms_index;
 
-                    // If we require submitting a scissor, pipeline or bind group command, first
-                    // draw whatever pending vertices we have collected so far. If there have been
-                    // no graphics yet, this will do nothing.
-                    if scissor_changed || pipeline_changed || bind_group_changed {
+                    // If we require submitting a scissor, pipeline, bind group or uniforms
+                    // command, first draw whatever pending vertices we have collected so far. If
+                    // there have been no graphics yet, this will do nothing.
+                    if scissor_changed || pipeline_changed || bind_group_changed || uniforms_changed
+                    {
                         push_draw_cmd(
                             &mut curr_start_index,
                             prev_index_count,
+                            0..1,
                             &mut self.render_commands,
                         );
                     }
@@ -673,10 +1140,67 @@ impl Renderer {
                         self.render_commands.push(cmd);
                     }
 
+                    // If necessary, push a new uniforms command for the new camera.
+                    if uniforms_changed {
+                        curr_uniforms_index = new_uniforms_index;
+                        let cmd = RenderCommand::SetUniforms(curr_uniforms_index);
+                        self.render_commands.push(cmd);
+                    }
+
                     // Extend the vertex mode channel.
                     let mode = render.vertex_mode;
                     let new_vs = self.mesh.points().len() - self.vertex_mode_buffer.len();
                     self.vertex_mode_buffer.extend((0..new_vs).map(|_| mode));
+
+                    // Extend the SDF channel, padding the vertices of any earlier primitives.
+                    if let Some(sdf) = render.sdf {
+                        self.sdf_vertex_buffer
+                            .resize(prev_vert_count, SdfVertex::default());
+                        self.sdf_vertex_buffer.resize(self.mesh.points().len(), sdf);
+                    }
+
+                    // Instanced primitives are drawn immediately with their own range of instances.
+                    if let Some(instances) = instances {
+                        let start = self.instance_buffer.len() as u32;
+                        let instance_data = instances.iter().map(|instance| {
+                            let transform = curr_ctxt.transform * instance.matrix();
+                            let c = instance.color;
+                            InstanceData {
+                                transform: transform.into(),
+                                color: [c.red, c.green, c.blue, c.alpha],
+                            }
+                        });
+                        self.instance_buffer.extend(instance_data);
+                        let end = self.instance_buffer.len() as u32;
+                        push_draw_cmd(
+                            &mut curr_start_index,
+                            self.mesh.indices().len() as u32,
+                            start..end,
+                            &mut self.render_commands,
+                        );
+                    }
+
+                    // Particle sprites are drawn immediately for every particle of their system.
+                    if let Some(system) = particle_system {
+                        let instance_count =
+                            system.lock().map(|system| system.max_count()).unwrap_or(0);
+                        let index_range = curr_start_index..self.mesh.indices().len() as u32;
+                        curr_start_index = index_range.end;
+                        let cmd = RenderCommand::DrawParticles {
+                            system: self.particle_systems.len(),
+                            index_range,
+                            instance_count,
+                        };
+                        self.particle_systems.push(system);
+                        self.render_commands.push(cmd);
+                    }
+
+                    // GPU meshes are drawn immediately from their own buffers.
+                    if let Some(gpu_mesh) = gpu_mesh {
+                        let cmd = RenderCommand::DrawGpuMesh(self.gpu_meshes.len());
+                        self.gpu_meshes.push(gpu_mesh);
+                        self.render_commands.push(cmd);
+                    }
                 }
             }
         }
@@ -685,9 +1209,13 @@ impl Renderer {
         push_draw_cmd(
             &mut curr_start_index,
             self.mesh.indices().len() as u32,
+            0..1,
             &mut self.render_commands,
         );
 
+        // Free any image textures exceeding the cache capacity.
+        self.image_cache.evict();
+
         // Clear out unnecessary pipelines.
         self.pipelines
             .retain(|id, _| new_pipeline_ids.contains_key(id));
@@ -701,19 +1229,31 @@ impl Renderer {
                 .or_insert_with(|| {
                     create_texture_bind_group_layout(device, new_id.texture_component_type)
                 });
+            let (vs_mod, fs_mod) = if new_id.sdf {
+                (&self.vs_sdf_mod, &self.fs_sdf_mod)
+            } else if new_id.instanced {
+                (&self.vs_instanced_mod, &self.fs_mod)
+            } else if new_id.particles {
+                (&self.vs_particles_mod, &self.fs_mod)
+            } else {
+                (&self.vs_mod, &self.fs_mod)
+            };
             let new_pipeline = create_render_pipeline(
                 device,
                 &self.uniform_bind_group_layout,
                 &self.text_bind_group_layout,
                 &bind_group_layout,
-                &self.vs_mod,
-                &self.fs_mod,
+                vs_mod,
+                fs_mod,
                 self.output_color_format,
                 self.depth_texture.format(),
                 self.sample_count,
                 color_blend,
                 alpha_blend,
                 new_id.topology,
+                new_id.instanced,
+                new_id.particles,
+                new_id.sdf,
             );
             self.pipelines.insert(new_id, new_pipeline);
         }
@@ -760,34 +1300,82 @@ impl Renderer {
         draw: &draw::Draw,
         scale_factor: f32,
         output_attachment_size: [u32; 2],
-        output_attachment: &wgpu::TextureView,
+        output_attachment: &wgpu::TextureViewHandle,
         resolve_target: Option<&wgpu::TextureView>,
     ) {
+        // The commands of the previous render pass have been submitted by now, so the staging
+        // buffers that it used may be recycled.
+        if self.owns_buffer_pool {
+            self.buffer_pool.end_frame();
+        }
+
         self.clear();
         self.fill(device, draw, scale_factor, output_attachment_size);
+        self.image_cache.encode_pending_uploads(device, encoder);
 
         let Renderer {
             ref pipelines,
             ref glyph_cache,
-            ref glyph_cache_texture,
+            ref mut glyph_cache_texture,
+            ref mut sdf_atlas,
+            ref mut sdf_atlas_texture,
             ref mut depth_texture,
             ref mut depth_texture_view,
-            ref uniform_bind_group,
-            ref text_bind_group,
+            ref uniform_bind_group_layout,
+            ref mut uniform_bind_group,
+            ref text_bind_group_layout,
+            ref mut text_bind_group,
+            ref mut sdf_bind_group,
             ref texture_bind_groups,
             ref mesh,
             ref vertex_mode_buffer,
+            ref mut sdf_vertex_buffer,
+            ref instance_buffer,
+            ref buffer_pool,
+            ref particle_systems,
+            ref gpu_meshes,
             ref mut render_commands,
-            ref uniform_buffer,
-            scale_factor: ref mut old_scale_factor,
+            ref uniforms,
+            ref mut uniform_buffer,
+            ref mut uniform_buffer_capacity,
             ..
         } = *self;
 
+        // Resize the glyph cache texture if the glyph cache has grown.
+        let (glyph_cache_w, glyph_cache_h) = glyph_cache.dimensions();
+        if glyph_cache_texture.size() != [glyph_cache_w, glyph_cache_h] {
+            *glyph_cache_texture =
+                create_glyph_cache_texture(device, [glyph_cache_w, glyph_cache_h]);
+            let text_sampler = wgpu::SamplerBuilder::new().build(device);
+            *text_bind_group = create_text_bind_group(
+                device,
+                text_bind_group_layout,
+                &text_sampler,
+                &glyph_cache_texture.create_default_view(),
+            );
+        }
+
         // Update glyph cache texture if necessary.
         if glyph_cache.requires_upload {
             glyph_cache_texture.upload_data(device, encoder, &glyph_cache.pixel_buffer);
         }
 
+        // Resize and update the SDF atlas texture if new glyphs have been generated.
+        if sdf_atlas.requires_upload {
+            let (sdf_atlas_w, sdf_atlas_h) = sdf_atlas.dimensions();
+            if sdf_atlas_texture.size() != [sdf_atlas_w, sdf_atlas_h] {
+                *sdf_atlas_texture = create_sdf_atlas_texture(device, sdf_atlas.dimensions());
+                *sdf_bind_group = create_text_bind_group(
+                    device,
+                    text_bind_group_layout,
+                    &wgpu::SamplerBuilder::new().build(device),
+                    &sdf_atlas_texture.create_default_view(),
+                );
+            }
+            sdf_atlas_texture.upload_data(device, encoder, sdf_atlas.pixels());
+            sdf_atlas.requires_upload = false;
+        }
+
         // Resize the depth texture if the output attachment size has changed.
         let depth_size = depth_texture.size();
         if output_attachment_size != depth_size {
@@ -821,51 +1409,103 @@ impl Renderer {
             .depth_stencil_attachment(&*depth_texture_view, |depth| depth);
 
         // Guard for empty mesh.
-        if mesh.points().is_empty() {
+        if mesh.points().is_empty() && gpu_meshes.is_empty() {
             // Encode the render pass. Only clears the frame.
             render_pass_builder.begin(encoder);
             return;
         }
 
-        // Create the vertex and index buffers.
+        // Create the vertex and index buffers, unless only GPU meshes were drawn.
         let vertex_usage = wgpu::BufferUsage::VERTEX;
-        let points_bytes = points_as_bytes(mesh.points());
-        let colors_bytes = colors_as_bytes(mesh.colors());
-        let tex_coords_bytes = tex_coords_as_bytes(mesh.tex_coords());
-        let modes_bytes = vertex_modes_as_bytes(vertex_mode_buffer);
-        let indices_bytes = indices_as_bytes(mesh.indices());
-        let point_buffer = device.create_buffer_with_data(points_bytes, vertex_usage);
-        let color_buffer = device.create_buffer_with_data(colors_bytes, vertex_usage);
-        let tex_coords_buffer = device.create_buffer_with_data(tex_coords_bytes, vertex_usage);
-        let mode_buffer = device.create_buffer_with_data(modes_bytes, vertex_usage);
-        let index_buffer = device.create_buffer_with_data(indices_bytes, wgpu::BufferUsage::INDEX);
-
-        // If the scale factor or window size has changed, update the uniforms for vertex scaling.
-        if *old_scale_factor != scale_factor || output_attachment_size != depth_size {
-            *old_scale_factor = scale_factor;
-            // Upload uniform data for vertex scaling.
-            let uniforms = create_uniforms(output_attachment_size, scale_factor);
-            let uniforms_size = std::mem::size_of::<Uniforms>() as wgpu::BufferAddress;
-            let uniforms_bytes = uniforms_as_bytes(&uniforms);
-            let usage = wgpu::BufferUsage::COPY_SRC;
-            let new_uniform_buffer = device.create_buffer_with_data(uniforms_bytes, usage);
-            // Copy new uniform buffer state.
-            encoder.copy_buffer_to_buffer(&new_uniform_buffer, 0, uniform_buffer, 0, uniforms_size);
+        let mesh_buffers = if mesh.points().is_empty() {
+            None
+        } else {
+            let points_bytes = points_as_bytes(mesh.points());
+            let colors_bytes = colors_as_bytes(mesh.colors());
+            let tex_coords_bytes = tex_coords_as_bytes(mesh.tex_coords());
+            let modes_bytes = vertex_modes_as_bytes(vertex_mode_buffer);
+            let indices_bytes = indices_as_bytes(mesh.indices());
+            Some(MeshBuffers {
+                points: device.create_buffer_with_data(points_bytes, vertex_usage),
+                colors: device.create_buffer_with_data(colors_bytes, vertex_usage),
+                tex_coords: device.create_buffer_with_data(tex_coords_bytes, vertex_usage),
+                modes: device.create_buffer_with_data(modes_bytes, vertex_usage),
+                indices: device.create_buffer_with_data(indices_bytes, wgpu::BufferUsage::INDEX),
+            })
+        };
+
+        // Only create the SDF vertex buffer if SDF text was drawn, padding it to the vertex count.
+        let sdf_buffer = if sdf_vertex_buffer.is_empty() {
+            None
+        } else {
+            sdf_vertex_buffer.resize(mesh.points().len(), SdfVertex::default());
+            let sdf_bytes = sdf_vertices_as_bytes(sdf_vertex_buffer);
+            Some(device.create_buffer_with_data(sdf_bytes, vertex_usage))
+        };
+
+        // Upload the instance data to a pooled buffer, if there is any.
+        let instance_data_buffer = if instance_buffer.is_empty() {
+            None
+        } else {
+            let instances_bytes = instances_as_bytes(instance_buffer);
+            let usage = wgpu::BufferUsage::VERTEX;
+            Some(buffer_pool.upload(device, encoder, instances_bytes, usage))
+        };
+
+        // Simulate each particle system drawn during the frame, retrieving its particle buffer.
+        let particle_buffers: Vec<_> = particle_systems
+            .iter()
+            .map(|system| {
+                system
+                    .lock()
+                    .expect("failed to acquire particle system lock")
+                    .encode(device, encoder)
+            })
+            .collect();
+
+        // Upload any GPU meshes that are new or have changed, retrieving their buffers.
+        let gpu_mesh_buffers: Vec<_> = gpu_meshes
+            .iter()
+            .map(|gpu_mesh| {
+                let vertex_mode = gpu_mesh.primitive_render().vertex_mode;
+                gpu_mesh.mesh.buffers(device, encoder, vertex_mode)
+            })
+            .collect();
+
+        // Grow the uniform buffer if there are more cameras than there is room for.
+        if uniforms.len() > *uniform_buffer_capacity {
+            *uniform_buffer_capacity = uniforms.len().next_power_of_two();
+            *uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("nannou_draw_uniforms"),
+                size: *uniform_buffer_capacity as wgpu::BufferAddress * UNIFORMS_STRIDE,
+                usage: wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST,
+            });
+            *uniform_bind_group =
+                create_uniform_bind_group(device, uniform_bind_group_layout, uniform_buffer);
         }
 
+        // Upload the uniforms for the default projection and each camera.
+        let uniforms_bytes = uniforms_as_aligned_bytes(uniforms);
+        let uniforms_size = uniforms_bytes.len() as wgpu::BufferAddress;
+        let usage = wgpu::BufferUsage::COPY_SRC;
+        let new_uniform_buffer = device.create_buffer_with_data(&uniforms_bytes, usage);
+        encoder.copy_buffer_to_buffer(&new_uniform_buffer, 0, uniform_buffer, 0, uniforms_size);
+
         // Encode the render pass.
         let mut render_pass = render_pass_builder.begin(encoder);
 
         // Set the buffers.
-        render_pass.set_index_buffer(&index_buffer, 0, 0);
-        render_pass.set_vertex_buffer(0, &point_buffer, 0, 0);
-        render_pass.set_vertex_buffer(1, &color_buffer, 0, 0);
-        render_pass.set_vertex_buffer(2, &tex_coords_buffer, 0, 0);
-        render_pass.set_vertex_buffer(3, &mode_buffer, 0, 0);
+        if let Some(ref buffers) = mesh_buffers {
+            buffers.set(&mut render_pass);
+        }
+        if let Some(ref buffer) = instance_data_buffer {
+            render_pass.set_vertex_buffer(4, buffer, 0, 0);
+        }
 
         // Set the uniform and text bind groups here.
-        render_pass.set_bind_group(0, uniform_bind_group, &[]);
+        render_pass.set_bind_group(0, uniform_bind_group, &[0]);
         render_pass.set_bind_group(1, text_bind_group, &[]);
+        let mut sdf_bound = false;
 
         // Follow the render commands.
         for cmd in render_commands.drain(..) {
@@ -873,6 +1513,22 @@ impl Renderer {
                 RenderCommand::SetPipeline(id) => {
                     let pipeline = &pipelines[&id];
                     render_pass.set_pipeline(pipeline);
+
+                    // SDF pipelines sample the SDF atlas and read their parameters from slot 4.
+                    if id.sdf != sdf_bound {
+                        sdf_bound = id.sdf;
+                        if sdf_bound {
+                            render_pass.set_bind_group(1, &*sdf_bind_group, &[]);
+                            if let Some(ref buffer) = sdf_buffer {
+                                render_pass.set_vertex_buffer(4, buffer, 0, 0);
+                            }
+                        } else {
+                            render_pass.set_bind_group(1, &*text_bind_group, &[]);
+                            if let Some(ref buffer) = instance_data_buffer {
+                                render_pass.set_vertex_buffer(4, buffer, 0, 0);
+                            }
+                        }
+                    }
                 }
 
                 RenderCommand::SetBindGroup(tex_view_id) => {
@@ -880,6 +1536,12 @@ impl Renderer {
                     render_pass.set_bind_group(2, bind_group, &[]);
                 }
 
+                RenderCommand::SetUniforms(index) => {
+                    let offset =
+                        index as wgpu::DynamicOffset * UNIFORMS_STRIDE as wgpu::DynamicOffset;
+                    render_pass.set_bind_group(0, uniform_bind_group, &[offset]);
+                }
+
                 RenderCommand::SetScissor(Scissor {
                     left,
                     bottom,
@@ -892,10 +1554,41 @@ impl Renderer {
                 RenderCommand::DrawIndexed {
                     start_vertex,
                     index_range,
+                    instance_range,
                 } => {
-                    let instance_range = 0..1u32;
                     render_pass.draw_indexed(index_range, start_vertex, instance_range);
                 }
+
+                RenderCommand::DrawParticles {
+                    system,
+                    index_range,
+                    instance_count,
+                } => {
+                    render_pass.set_vertex_buffer(4, &particle_buffers[system], 0, 0);
+                    render_pass.draw_indexed(index_range, 0, 0..instance_count);
+                    // Restore the per-instance buffer expected by the other pipelines.
+                    let buffer = match sdf_bound {
+                        true => sdf_buffer.as_ref(),
+                        false => instance_data_buffer.as_deref(),
+                    };
+                    if let Some(buffer) = buffer {
+                        render_pass.set_vertex_buffer(4, buffer, 0, 0);
+                    }
+                }
+
+                RenderCommand::DrawGpuMesh(index) => {
+                    let buffers = &gpu_mesh_buffers[index];
+                    render_pass.set_index_buffer(&buffers.indices, 0, 0);
+                    render_pass.set_vertex_buffer(0, &buffers.positions, 0, 0);
+                    render_pass.set_vertex_buffer(1, &buffers.colors, 0, 0);
+                    render_pass.set_vertex_buffer(2, &buffers.tex_coords, 0, 0);
+                    render_pass.set_vertex_buffer(3, &buffers.modes, 0, 0);
+                    render_pass.draw_indexed(0..buffers.index_count, 0, 0..1);
+                    // Restore the frame's mesh buffers expected by the following commands.
+                    if let Some(ref buffers) = mesh_buffers {
+                        buffers.set(&mut render_pass);
+                    }
+                }
             }
         }
     }
@@ -983,6 +1676,22 @@ fn create_depth_texture(
         .build(device)
 }
 
+fn create_glyph_cache_texture(device: &wgpu::Device, size: [u32; 2]) -> wgpu::Texture {
+    wgpu::TextureBuilder::new()
+        .size(size)
+        .usage(wgpu::TextureUsage::SAMPLED | wgpu::TextureUsage::COPY_DST)
+        .format(Renderer::GLYPH_CACHE_TEXTURE_FORMAT)
+        .build(device)
+}
+
+fn create_sdf_atlas_texture(device: &wgpu::Device, (w, h): (u32, u32)) -> wgpu::Texture {
+    wgpu::TextureBuilder::new()
+        .size([w, h])
+        .usage(wgpu::TextureUsage::SAMPLED | wgpu::TextureUsage::COPY_DST)
+        .format(Renderer::SDF_ATLAS_TEXTURE_FORMAT)
+        .build(device)
+}
+
 fn create_uniforms([img_w, img_h]: [u32; 2], scale_factor: f32) -> Uniforms {
     let right = img_w as f32 * 0.5 / scale_factor;
     let left = -right;
@@ -1002,7 +1711,7 @@ fn create_uniforms([img_w, img_h]: [u32; 2], scale_factor: f32) -> Uniforms {
 
 fn create_uniform_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
     wgpu::BindGroupLayoutBuilder::new()
-        .uniform_buffer(wgpu::ShaderStage::VERTEX, false)
+        .uniform_buffer(wgpu::ShaderStage::VERTEX, true)
         .build(device)
 }
 
@@ -1080,15 +1789,47 @@ fn create_render_pipeline(
     color_blend: wgpu::BlendDescriptor,
     alpha_blend: wgpu::BlendDescriptor,
     topology: wgpu::PrimitiveTopology,
+    instanced: bool,
+    particles: bool,
+    sdf: bool,
 ) -> wgpu::RenderPipeline {
     let bind_group_layouts = &[uniform_layout, text_layout, texture_layout];
-    wgpu::RenderPipelineBuilder::from_layout_descriptor(&bind_group_layouts[..], vs_mod)
-        .fragment_shader(fs_mod)
-        .color_format(dst_format)
-        .add_vertex_buffer::<draw::mesh::vertex::Point>(&wgpu::vertex_attr_array![0 => Float3])
-        .add_vertex_buffer::<draw::mesh::vertex::Color>(&wgpu::vertex_attr_array![1 => Float4])
-        .add_vertex_buffer::<draw::mesh::vertex::TexCoords>(&wgpu::vertex_attr_array![2 => Float2])
-        .add_vertex_buffer::<VertexMode>(&wgpu::vertex_attr_array![3 => Uint])
+    let builder =
+        wgpu::RenderPipelineBuilder::from_layout_descriptor(&bind_group_layouts[..], vs_mod)
+            .fragment_shader(fs_mod)
+            .color_format(dst_format)
+            .add_vertex_buffer::<draw::mesh::vertex::Point>(&wgpu::vertex_attr_array![0 => Float3])
+            .add_vertex_buffer::<draw::mesh::vertex::Color>(&wgpu::vertex_attr_array![1 => Float4])
+            .add_vertex_buffer::<draw::mesh::vertex::TexCoords>(
+                &wgpu::vertex_attr_array![2 => Float2],
+            )
+            .add_vertex_buffer::<VertexMode>(&wgpu::vertex_attr_array![3 => Uint]);
+    let builder = if instanced {
+        builder.add_instance_buffer::<InstanceData>(&wgpu::vertex_attr_array![
+            4 => Float4,
+            5 => Float4,
+            6 => Float4,
+            7 => Float4,
+            8 => Float4
+        ])
+    } else if particles {
+        let stride = std::mem::size_of::<particle::GpuParticle>() as wgpu::BufferAddress;
+        builder.add_vertex_buffer_descriptor(wgpu::VertexBufferDescriptor {
+            stride,
+            step_mode: wgpu::InputStepMode::Instance,
+            attributes: &PARTICLE_ATTRIBUTES,
+        })
+    } else if sdf {
+        builder.add_vertex_buffer::<SdfVertex>(&wgpu::vertex_attr_array![
+            4 => Float4,
+            5 => Float4,
+            6 => Float4
+        ])
+    } else {
+        builder
+    };
+
+    builder
         .depth_format(depth_format)
         .sample_count(sample_count)
         .color_blend(color_blend)
@@ -1125,6 +1866,16 @@ fn uniforms_as_bytes(uniforms: &Uniforms) -> &[u8] {
     unsafe { wgpu::bytes::from(uniforms) }
 }
 
+impl MeshBuffers {
+    fn set<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
+        render_pass.set_index_buffer(&self.indices, 0, 0);
+        render_pass.set_vertex_buffer(0, &self.points, 0, 0);
+        render_pass.set_vertex_buffer(1, &self.colors, 0, 0);
+        render_pass.set_vertex_buffer(2, &self.tex_coords, 0, 0);
+        render_pass.set_vertex_buffer(3, &self.modes, 0, 0);
+    }
+}
+
 fn points_as_bytes(data: &[draw::mesh::vertex::Point]) -> &[u8] {
     unsafe { wgpu::bytes::from_slice(data) }
 }
@@ -1144,3 +1895,22 @@ fn vertex_modes_as_bytes(data: &[VertexMode]) -> &[u8] {
 fn indices_as_bytes(data: &[u32]) -> &[u8] {
     unsafe { wgpu::bytes::from_slice(data) }
 }
+
+fn sdf_vertices_as_bytes(data: &[SdfVertex]) -> &[u8] {
+    unsafe { wgpu::bytes::from_slice(data) }
+}
+
+fn instances_as_bytes(data: &[InstanceData]) -> &[u8] {
+    unsafe { wgpu::bytes::from_slice(data) }
+}
+
+// Lay out the given uniforms so that each begins at a multiple of `UNIFORMS_STRIDE`.
+fn uniforms_as_aligned_bytes(uniforms: &[Uniforms]) -> Vec<u8> {
+    let stride = UNIFORMS_STRIDE as usize;
+    let mut bytes = vec![0u8; uniforms.len() * stride];
+    for (u, chunk) in uniforms.iter().zip(bytes.chunks_mut(stride)) {
+        let u = uniforms_as_bytes(u);
+        chunk[..u.len()].copy_from_slice(u);
+    }
+    bytes
+}
@@ -31,6 +31,11 @@ void main() {
         float tex_a = texture(sampler2D(text, text_sampler), v_tex_coords).r;
         f_color = vec4(v_color.rgb, v_color.a * tex_a);
 
+    // Radial gradient
+    } else if (v_mode == uint(3)) {
+        vec2 gradient_coords = vec2(length(v_tex_coords), 0.5);
+        f_color = texture(sampler2D(tex, tex_sampler), gradient_coords);
+
     // Unhandled mode - Indicate error with red.
     } else {
         f_color = vec4(1.0, 0.0, 0.0, 1.0);
@@ -0,0 +1,37 @@
+// NOTE: This shader requires being manually compiled to SPIR-V in order to
+// avoid having downstream users require building shaderc and compiling the
+// shader themselves. If you update this shader, be sure to also re-compile it
+// and update `vert_particles.spv`. You can do so using `glslangValidator` with
+// the following command:
+// `glslangValidator -V -o vert_particles.spv shader_particles.vert`
+
+#version 450
+
+layout(set = 0, binding = 0) uniform Data {
+    mat4 proj;
+} uniforms;
+
+layout(location = 0) in vec3 position;
+layout(location = 1) in vec4 color;
+layout(location = 2) in vec2 tex_coords;
+layout(location = 3) in uint mode;
+
+// Per-instance attributes read directly from the particle system's storage buffer.
+layout(location = 4) in vec2 particle_position;
+layout(location = 5) in vec4 particle_color;
+// The age of the particle followed by its lifetime.
+layout(location = 6) in vec2 particle_life;
+
+layout(location = 0) out vec4 v_color;
+layout(location = 1) out vec2 v_tex_coords;
+layout(location = 2) flat out uint v_mode;
+
+void main() {
+    // Collapse the sprites of dead particles to a point so that they are not rasterized.
+    float alive = particle_life.x < particle_life.y ? 1.0 : 0.0;
+    vec3 sprite_position = position * alive + vec3(particle_position, 0.0);
+    gl_Position = uniforms.proj * vec4(sprite_position, 1.0);
+    v_color = color * particle_color;
+    v_tex_coords = tex_coords;
+    v_mode = mode;
+}
@@ -0,0 +1,42 @@
+// NOTE: This shader requires being manually compiled to SPIR-V in order to
+// avoid having downstream users require building shaderc and compiling the
+// shader themselves. If you update this shader, be sure to also re-compile it
+// and update `frag_sdf.spv`. You can do so using `glslangValidator` with the
+// following command: `glslangValidator -V -o frag_sdf.spv shader_sdf.frag`
+
+#version 450
+
+layout(set = 1, binding = 0) uniform sampler sdf_sampler;
+layout(set = 1, binding = 1) uniform texture2D sdf;
+
+layout(location = 0) in vec4 v_color;
+layout(location = 1) in vec2 v_tex_coords;
+layout(location = 2) in vec4 v_outline_color;
+layout(location = 3) in vec4 v_glow_color;
+// x: output pixels per unit of the distance field, y: outline width, z: glow radius.
+layout(location = 4) in vec4 v_params;
+
+layout(location = 0) out vec4 f_color;
+
+void main() {
+    // The signed distance to the outline in output pixels, positive within the glyph.
+    vec3 s = texture(sampler2D(sdf, sdf_sampler), v_tex_coords).rgb;
+    float median = max(min(s.r, s.g), min(max(s.r, s.g), s.b));
+    float dist = (median - 0.5) * v_params.x;
+    float outline_width = v_params.y;
+    float glow_radius = v_params.z;
+
+    float fill_a = clamp(dist + 0.5, 0.0, 1.0);
+    float outline_a = outline_width > 0.0 ? clamp(dist + outline_width + 0.5, 0.0, 1.0) : 0.0;
+    float glow_a = glow_radius > 0.0
+        ? 1.0 - smoothstep(0.0, glow_radius, -(dist + outline_width))
+        : 0.0;
+
+    // Composite the fill over the outline over the glow with premultiplied alpha.
+    vec4 glow = vec4(v_glow_color.rgb, 1.0) * (v_glow_color.a * glow_a);
+    vec4 outline = vec4(v_outline_color.rgb, 1.0) * v_outline_color.a;
+    vec4 fill = vec4(v_color.rgb, 1.0) * v_color.a;
+    vec4 c = mix(glow, outline, outline_a);
+    c = mix(c, fill, fill_a);
+    f_color = vec4(c.rgb / max(c.a, 0.0001), c.a);
+}
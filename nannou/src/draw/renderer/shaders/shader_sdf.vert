@@ -0,0 +1,33 @@
+// NOTE: This shader requires being manually compiled to SPIR-V in order to
+// avoid having downstream users require building shaderc and compiling the
+// shader themselves. If you update this shader, be sure to also re-compile it
+// and update `vert_sdf.spv`. You can do so using `glslangValidator` with the
+// following command: `glslangValidator -V -o vert_sdf.spv shader_sdf.vert`
+
+#version 450
+
+layout(set = 0, binding = 0) uniform Data {
+    mat4 proj;
+} uniforms;
+
+layout(location = 0) in vec3 position;
+layout(location = 1) in vec4 color;
+layout(location = 2) in vec2 tex_coords;
+layout(location = 4) in vec4 outline_color;
+layout(location = 5) in vec4 glow_color;
+layout(location = 6) in vec4 params;
+
+layout(location = 0) out vec4 v_color;
+layout(location = 1) out vec2 v_tex_coords;
+layout(location = 2) out vec4 v_outline_color;
+layout(location = 3) out vec4 v_glow_color;
+layout(location = 4) out vec4 v_params;
+
+void main() {
+    gl_Position = uniforms.proj * vec4(position, 1.0);
+    v_color = color;
+    v_tex_coords = tex_coords;
+    v_outline_color = outline_color;
+    v_glow_color = glow_color;
+    v_params = params;
+}
@@ -0,0 +1,457 @@
+//! Items related to exporting the commands of a **Draw** instance as an SVG document.
+//!
+//! See **Draw::to_svg** for details and limitations.
+
+use crate::color::{LinSrgba, Srgba};
+use crate::draw::primitive::{path, Gradient, Primitive};
+use crate::draw::renderer::{GlyphCache, RenderContext, RenderPrimitive};
+use crate::draw::{self, Draw, DrawCommand};
+use crate::geom::Vector2;
+use crate::math::{Matrix4, SquareMatrix};
+use crate::text;
+use lyon::path::PathEvent;
+use lyon::tessellation::{
+    FillOptions, FillRule, FillTessellator, LineCap, LineJoin, StrokeTessellator,
+};
+use std::io;
+
+/// Records primitives as SVG elements.
+///
+/// Used by **Draw::to_svg**, which provides a **Recorder** to primitives via the
+/// **RenderContext** in place of tessellating them.
+#[derive(Debug)]
+pub struct Recorder {
+    // The width and height of the document in points.
+    size: [f32; 2],
+    // Elements within the `<defs>` section, e.g. gradients.
+    defs: String,
+    // The elements describing the drawing.
+    body: String,
+    // The number of elements recorded so far.
+    elements: usize,
+    // Used to produce a unique ID for each gradient.
+    next_gradient: usize,
+    // If `Some`, overrides the paint of recorded elements, e.g. `url(#gradient0)`.
+    paint: Option<String>,
+    // If `Some`, multiplied with the colour of recorded elements, e.g. for instances.
+    tint: Option<LinSrgba>,
+}
+
+// The resources required to render primitives while recording them.
+struct Resources<'a> {
+    intermediary_state: &'a draw::IntermediaryState<f32>,
+    theme: &'a draw::Theme,
+    glyph_cache: GlyphCache,
+    sdf_atlas: text::sdf::SdfAtlas,
+    fill_tessellator: FillTessellator,
+    stroke_tessellator: StrokeTessellator,
+}
+
+impl Draw<f32> {
+    /// Produce an SVG document describing the drawing with the given width and height in points.
+    ///
+    /// The origin is at the centre of the document with the *y* axis pointing upwards, matching
+    /// the coordinates used when drawing to a window of the same size. Unlike rendering to a
+    /// frame, the commands are not drained and may still be rendered afterwards.
+    ///
+    /// Paths, polygons and shapes such as ellipses, rects, lines and arrows are exported as
+    /// `<path>` elements with their fill and stroke, text as `<path>` elements describing the
+    /// outlines of its glyphs and gradients as `<linearGradient>` and `<radialGradient>`
+    /// definitions. As text is exported as outlines of the font used for drawing, it appears the
+    /// same in every viewer, though it may no longer be selected or edited as text.
+    ///
+    /// SVG cannot describe everything that may be rendered, so the export is lossy:
+    ///
+    /// - Meshes and 3D primitives are exported as their triangles, each with the average colour
+    ///   of its vertices. Neighbouring triangles may display faint seams in some viewers.
+    /// - Per-vertex colours of paths are replaced with the colour of the first vertex.
+    /// - Textured primitives and images are omitted.
+    /// - Blend modes, scissors, cameras and the z-position of points are ignored.
+    /// - SDF outlines and glows of text are omitted.
+    pub fn to_svg(&self, width: u32, height: u32) -> String {
+        let mut svg = Recorder::new([width as f32, height as f32]);
+        self.finish_remaining_drawings();
+        let state = self.state.borrow();
+        if let Some(color) = state.background_color {
+            svg.background(color);
+        }
+        let cmds = state.draw_commands.iter().filter_map(|cmd| cmd.clone());
+        let cmds = draw::sort_by_z_layer(cmds);
+        let intermediary_state = state.intermediary_state.borrow();
+        let mut resources = Resources {
+            intermediary_state: &intermediary_state,
+            theme: &state.theme,
+            // Text is recorded as text elements, so the glyph caches remain unused.
+            glyph_cache: GlyphCache::new([1, 1], 0.1, 0.1),
+            sdf_atlas: text::sdf::SdfAtlas::new(1),
+            fill_tessellator: FillTessellator::new(),
+            stroke_tessellator: StrokeTessellator::new(),
+        };
+        let mut transform = Matrix4::identity();
+        for cmd in cmds {
+            match cmd {
+                DrawCommand::Context(ctxt) => transform = ctxt.transform,
+                DrawCommand::Primitive(prim) => {
+                    record_primitive(prim, &transform, &mut resources, &mut svg)
+                }
+            }
+        }
+        svg.finish()
+    }
+
+    /// The same as `to_svg`, but writes the document to the given writer.
+    pub fn write_svg<W>(&self, mut writer: W, width: u32, height: u32) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        writer.write_all(self.to_svg(width, height).as_bytes())
+    }
+}
+
+impl Recorder {
+    // An empty document of the given size.
+    fn new(size: [f32; 2]) -> Self {
+        Recorder {
+            size,
+            defs: String::new(),
+            body: String::new(),
+            elements: 0,
+            next_gradient: 0,
+            paint: None,
+            tint: None,
+        }
+    }
+
+    /// Record a path as a `<path>` element with the given fill or stroke options.
+    pub(crate) fn path<I>(
+        &mut self,
+        events: I,
+        transform: &Matrix4<f32>,
+        options: &path::Options,
+        color: LinSrgba,
+    ) where
+        I: IntoIterator<Item = PathEvent>,
+    {
+        let mut d = String::new();
+        for event in events {
+            match event {
+                PathEvent::Begin { at } => {
+                    d.push('M');
+                    self.push_point(&mut d, transform, at);
+                }
+                PathEvent::Line { to, .. } => {
+                    d.push('L');
+                    self.push_point(&mut d, transform, to);
+                }
+                PathEvent::Quadratic { ctrl, to, .. } => {
+                    d.push('Q');
+                    self.push_point(&mut d, transform, ctrl);
+                    self.push_point(&mut d, transform, to);
+                }
+                PathEvent::Cubic {
+                    ctrl1, ctrl2, to, ..
+                } => {
+                    d.push('C');
+                    self.push_point(&mut d, transform, ctrl1);
+                    self.push_point(&mut d, transform, ctrl2);
+                    self.push_point(&mut d, transform, to);
+                }
+                PathEvent::End { close: true, .. } => d.push_str("Z "),
+                PathEvent::End { .. } => (),
+            }
+        }
+        if d.is_empty() {
+            return;
+        }
+
+        let (paint, opacity) = self.paint(color);
+        let d = d.trim_end();
+        let element = match *options {
+            path::Options::Fill(ref opts) => {
+                let fill_rule = match opts.fill_rule {
+                    FillRule::EvenOdd => "evenodd",
+                    FillRule::NonZero => "nonzero",
+                };
+                format!(
+                    "<path d=\"{}\" fill=\"{}\" fill-opacity=\"{}\" fill-rule=\"{}\"/>\n",
+                    d, paint, opacity, fill_rule,
+                )
+            }
+            path::Options::Stroke(ref opts) => {
+                // SVG only supports a single cap style per path.
+                let cap = match opts.start_cap {
+                    LineCap::Butt => "butt",
+                    LineCap::Square => "square",
+                    LineCap::Round => "round",
+                };
+                let join = match opts.line_join {
+                    LineJoin::Miter | LineJoin::MiterClip => "miter",
+                    LineJoin::Round => "round",
+                    LineJoin::Bevel => "bevel",
+                };
+                let width = num(opts.line_width * transform_scale(transform));
+                format!(
+                    "<path d=\"{}\" fill=\"none\" stroke=\"{}\" stroke-opacity=\"{}\" \
+                     stroke-width=\"{}\" stroke-linecap=\"{}\" stroke-linejoin=\"{}\" \
+                     stroke-miterlimit=\"{}\"/>\n",
+                    d,
+                    paint,
+                    opacity,
+                    width,
+                    cap,
+                    join,
+                    num(opts.miter_limit),
+                )
+            }
+        };
+        self.body.push_str(&element);
+        self.elements += 1;
+    }
+
+    /// Record the outlines of the glyphs of the given text as a single filled `<path>` element.
+    pub(crate) fn text(&mut self, text: &text::Text, transform: &Matrix4<f32>, color: LinSrgba) {
+        // TrueType and OpenType outlines are wound such that the non-zero rule fills the glyphs.
+        let mut options = FillOptions::default();
+        options.fill_rule = FillRule::NonZero;
+        self.path(
+            text.path_events(),
+            transform,
+            &path::Options::Fill(options),
+            color,
+        );
+    }
+
+    // Record the triangles of the given mesh, each as a `<path>` element.
+    fn triangles(&mut self, mesh: &draw::Mesh) {
+        let points = mesh.points();
+        let colors = mesh.colors();
+        for tri in mesh.indices().chunks_exact(3) {
+            let mut d = String::new();
+            let mut sum = [0.0; 4];
+            for (i, &ix) in tri.iter().enumerate() {
+                let ix = ix as usize;
+                let p = points[ix];
+                d.push(if i == 0 { 'M' } else { 'L' });
+                d.push_str(&format!("{},{} ", num(self.x(p.x)), num(self.y(p.y))));
+                let c = colors[ix];
+                for (s, c) in sum.iter_mut().zip(&[c.red, c.green, c.blue, c.alpha]) {
+                    *s += c / 3.0;
+                }
+            }
+            let color = LinSrgba::new(sum[0], sum[1], sum[2], sum[3]);
+            let (paint, opacity) = self.paint(color);
+            self.body.push_str(&format!(
+                "<path d=\"{}Z\" fill=\"{}\" fill-opacity=\"{}\"/>\n",
+                d, paint, opacity,
+            ));
+            self.elements += 1;
+        }
+    }
+
+    // Define the given gradient, returning its ID.
+    fn gradient(&mut self, gradient: &Gradient<f32>, transform: &Matrix4<f32>) -> String {
+        let id = format!("gradient{}", self.next_gradient);
+        self.next_gradient += 1;
+        // Gradients are described in drawing space, so are transformed along with the drawing.
+        let matrix = self.matrix(transform);
+        let (tag, attrs) = match *gradient {
+            Gradient::Linear { start, end, .. } => (
+                "linearGradient",
+                format!(
+                    "x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\"",
+                    num(start.x),
+                    num(start.y),
+                    num(end.x),
+                    num(end.y),
+                ),
+            ),
+            Gradient::Radial { center, radius, .. } => (
+                "radialGradient",
+                format!(
+                    "cx=\"{}\" cy=\"{}\" r=\"{}\"",
+                    num(center.x),
+                    num(center.y),
+                    num(radius),
+                ),
+            ),
+        };
+        self.defs.push_str(&format!(
+            "<{} id=\"{}\" gradientUnits=\"userSpaceOnUse\" gradientTransform=\"matrix({})\" {}>\n",
+            tag, id, matrix, attrs,
+        ));
+        for &(offset, color) in gradient.stops() {
+            let (color, opacity) = hex(color);
+            self.defs.push_str(&format!(
+                "<stop offset=\"{}\" stop-color=\"{}\" stop-opacity=\"{}\"/>\n",
+                num(offset),
+                color,
+                opacity,
+            ));
+        }
+        self.defs.push_str(&format!("</{}>\n", tag));
+        id
+    }
+
+    // Fill the document with the given colour.
+    fn background(&mut self, color: LinSrgba) {
+        let (color, opacity) = hex(color);
+        self.body.push_str(&format!(
+            "<rect width=\"100%\" height=\"100%\" fill=\"{}\" fill-opacity=\"{}\"/>\n",
+            color, opacity,
+        ));
+    }
+
+    // Produce the complete document.
+    fn finish(self) -> String {
+        let [w, h] = self.size;
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" \
+             viewBox=\"0 0 {w} {h}\">\n",
+            w = w,
+            h = h,
+        );
+        if !self.defs.is_empty() {
+            svg.push_str("<defs>\n");
+            svg.push_str(&self.defs);
+            svg.push_str("</defs>\n");
+        }
+        svg.push_str(&self.body);
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    // The paint and opacity of an element with the given colour.
+    fn paint(&self, color: LinSrgba) -> (String, f32) {
+        if let Some(ref paint) = self.paint {
+            return (paint.clone(), 1.0);
+        }
+        let color = match self.tint {
+            None => color,
+            Some(t) => LinSrgba::new(
+                color.red * t.red,
+                color.green * t.green,
+                color.blue * t.blue,
+                color.alpha * t.alpha,
+            ),
+        };
+        hex(color)
+    }
+
+    // Append the transformed point to the path data.
+    fn push_point(&self, d: &mut String, transform: &Matrix4<f32>, p: lyon::math::Point) {
+        let p = cgmath::Point3::new(p.x, p.y, 0.0);
+        let p = cgmath::Transform::transform_point(transform, p);
+        d.push_str(&format!("{},{} ", num(self.x(p.x)), num(self.y(p.y))));
+    }
+
+    // Convert from drawing coordinates to document coordinates.
+    fn x(&self, x: f32) -> f32 {
+        x + self.size[0] / 2.0
+    }
+
+    fn y(&self, y: f32) -> f32 {
+        self.size[1] / 2.0 - y
+    }
+
+    // The SVG `matrix` mapping from the given drawing space to document coordinates.
+    fn matrix(&self, transform: &Matrix4<f32>) -> String {
+        let (x, y, w) = (transform.x, transform.y, transform.w);
+        format!(
+            "{} {} {} {} {} {}",
+            num(x.x),
+            num(-x.y),
+            num(y.x),
+            num(-y.y),
+            num(self.x(w.x)),
+            num(self.y(w.y)),
+        )
+    }
+}
+
+// Record the primitive with the given transform.
+fn record_primitive(
+    prim: Primitive,
+    transform: &Matrix4<f32>,
+    res: &mut Resources,
+    svg: &mut Recorder,
+) {
+    match prim {
+        Primitive::Instanced(instanced) => {
+            let draw::primitive::Instanced {
+                primitive,
+                instances,
+            } = instanced;
+            for instance in instances {
+                let transform = *transform * instance.matrix();
+                let tint = svg.tint.replace(instance.color);
+                record_primitive((*primitive).clone(), &transform, res, svg);
+                svg.tint = tint;
+            }
+        }
+        Primitive::GradientFill(fill) => {
+            let id = svg.gradient(&fill.gradient, transform);
+            let paint = svg.paint.replace(format!("url(#{})", id));
+            record_primitive(*fill.primitive, transform, res, svg);
+            svg.paint = paint;
+        }
+        prim => {
+            let elements = svg.elements;
+            let state = res.intermediary_state;
+            let [w, h] = svg.size;
+            let mut mesh = draw::Mesh::default();
+            let ctxt = RenderContext {
+                transform,
+                intermediary_mesh: &state.intermediary_mesh,
+                path_event_buffer: &state.path_event_buffer,
+                path_points_colored_buffer: &state.path_points_colored_buffer,
+                path_points_textured_buffer: &state.path_points_textured_buffer,
+                text_buffer: &state.text_buffer,
+                theme: res.theme,
+                glyph_cache: &mut res.glyph_cache,
+                sdf_atlas: &mut res.sdf_atlas,
+                fill_tessellator: &mut res.fill_tessellator,
+                stroke_tessellator: &mut res.stroke_tessellator,
+                output_attachment_size: Vector2::new(w, h),
+                output_attachment_scale_factor: 1.0,
+                svg: Some(&mut *svg),
+            };
+            let render = prim.render_primitive(ctxt, &mut mesh);
+
+            // Primitives that could not be recorded as paths or text, e.g. meshes and 3D shapes,
+            // are recorded as the triangles of their mesh. Textured primitives are omitted.
+            if svg.elements == elements && render.texture_view.is_none() {
+                svg.triangles(&mesh);
+            }
+        }
+    }
+}
+
+// The approximate scale applied by the transform, used to scale stroke widths.
+fn transform_scale(transform: &Matrix4<f32>) -> f32 {
+    let (x, y) = (transform.x, transform.y);
+    (x.x * y.y - x.y * y.x).abs().sqrt()
+}
+
+// The hex colour and opacity of the linear colour.
+fn hex(color: LinSrgba) -> (String, f32) {
+    let srgba = Srgba::from_linear(color);
+    let c = |c: f32| (c.max(0.0).min(1.0) * 255.0).round() as u8;
+    let hex = format!(
+        "#{:02x}{:02x}{:02x}",
+        c(srgba.red),
+        c(srgba.green),
+        c(srgba.blue),
+    );
+    (hex, num(srgba.alpha.max(0.0).min(1.0)))
+}
+
+// Round to a precision suitable for presentation, avoiding excessively long documents.
+fn num(f: f32) -> f32 {
+    let f = (f * 1000.0).round() / 1000.0;
+    // Avoid writing `-0`.
+    if f == 0.0 {
+        0.0
+    } else {
+        f
+    }
+}
@@ -24,7 +24,10 @@ pub struct Color {
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
 pub enum Primitive {
     Arrow,
+    Box3d,
+    Cone,
     Cuboid,
+    Cylinder,
     Ellipse,
     Line,
     Mesh,
@@ -32,8 +35,10 @@ pub enum Primitive {
     Polygon,
     Quad,
     Rect,
+    Sphere,
     Text,
     Texture,
+    Torus,
     Tri,
 }
 
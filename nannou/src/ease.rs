@@ -1,4 +1,345 @@
 //! A suite of common interpolation functions often referred to as "easing" and "tweening"
-//! functions. This API is provided by the [pennereq crate](https://docs.rs/pennereq).
+//! functions.
+//!
+//! The free functions in this module each take a normalised time `t` in the range `0.0..=1.0` and
+//! return the eased progress, where `0.0` maps to `0.0` and `1.0` maps to `1.0`. This allows for
+//! using them inline, e.g. `start.lerp(end, ease::cubic_in_out(t))`.
+//!
+//! The original Penner equations in their `(t, b, c, d)` form remain available via the
+//! [pennereq crate](https://docs.rs/pennereq) modules re-exported here.
+
+use std::f32::consts::PI;
 
 pub use pennereq::*;
+
+/// The overshoot used by the `back` easing functions in the original Penner equations.
+pub const DEFAULT_OVERSHOOT: f32 = 1.70158;
+
+/// The amplitude used by the `elastic` easing functions in the original Penner equations.
+pub const DEFAULT_AMPLITUDE: f32 = 1.0;
+
+/// The period used by the `elastic_in` and `elastic_out` functions in the original Penner
+/// equations. `elastic_in_out` typically uses a period 1.5 times as long.
+pub const DEFAULT_PERIOD: f32 = 0.3;
+
+/// Types that may be used to ease a normalised time `t` in the range `0.0..=1.0`.
+///
+/// This is implemented for all functions and closures of the form `Fn(f32) -> f32`, including all
+/// single argument functions within this module.
+pub trait Easing {
+    /// Ease the given normalised time.
+    fn ease(&self, t: f32) -> f32;
+}
+
+/// Wraps an easing function defined by the user.
+///
+/// Created via the `custom` function.
+#[derive(Copy, Clone, Debug)]
+pub struct Custom<F> {
+    f: F,
+}
+
+impl<F> Easing for F
+where
+    F: Fn(f32) -> f32,
+{
+    fn ease(&self, t: f32) -> f32 {
+        (*self)(t)
+    }
+}
+
+impl<F> Easing for Custom<F>
+where
+    F: Fn(f32) -> f32,
+{
+    fn ease(&self, t: f32) -> f32 {
+        (self.f)(t)
+    }
+}
+
+/// Wrap a custom easing function so that it may be used wherever an **Easing** is expected.
+///
+/// ```
+/// use nannou::ease::{self, Easing};
+///
+/// let steps = ease::custom(|t: f32| (t * 4.0).floor() / 4.0);
+/// assert_eq!(steps.ease(0.6), 0.5);
+/// ```
+pub fn custom<F>(f: F) -> Custom<F>
+where
+    F: Fn(f32) -> f32,
+{
+    Custom { f }
+}
+
+/// No easing, `t` is returned unchanged.
+pub fn linear(t: f32) -> f32 {
+    t
+}
+
+/// Accelerate from zero velocity with a quadratic curve.
+pub fn quad_in(t: f32) -> f32 {
+    t * t
+}
+
+/// Decelerate to zero velocity with a quadratic curve.
+pub fn quad_out(t: f32) -> f32 {
+    t * (2.0 - t)
+}
+
+/// Accelerate until halfway, then decelerate, with a quadratic curve.
+pub fn quad_in_out(t: f32) -> f32 {
+    if t < 0.5 {
+        2.0 * t * t
+    } else {
+        -1.0 + (4.0 - 2.0 * t) * t
+    }
+}
+
+/// Accelerate from zero velocity with a cubic curve.
+pub fn cubic_in(t: f32) -> f32 {
+    t * t * t
+}
+
+/// Decelerate to zero velocity with a cubic curve.
+pub fn cubic_out(t: f32) -> f32 {
+    let t = t - 1.0;
+    t * t * t + 1.0
+}
+
+/// Accelerate until halfway, then decelerate, with a cubic curve.
+pub fn cubic_in_out(t: f32) -> f32 {
+    if t < 0.5 {
+        4.0 * t * t * t
+    } else {
+        let t = 2.0 * t - 2.0;
+        0.5 * t * t * t + 1.0
+    }
+}
+
+/// Accelerate from zero velocity with a quartic curve.
+pub fn quart_in(t: f32) -> f32 {
+    t * t * t * t
+}
+
+/// Decelerate to zero velocity with a quartic curve.
+pub fn quart_out(t: f32) -> f32 {
+    let t = t - 1.0;
+    1.0 - t * t * t * t
+}
+
+/// Accelerate until halfway, then decelerate, with a quartic curve.
+pub fn quart_in_out(t: f32) -> f32 {
+    if t < 0.5 {
+        8.0 * t * t * t * t
+    } else {
+        let t = t - 1.0;
+        1.0 - 8.0 * t * t * t * t
+    }
+}
+
+/// Accelerate from zero velocity with a quintic curve.
+pub fn quint_in(t: f32) -> f32 {
+    t * t * t * t * t
+}
+
+/// Decelerate to zero velocity with a quintic curve.
+pub fn quint_out(t: f32) -> f32 {
+    let t = t - 1.0;
+    t * t * t * t * t + 1.0
+}
+
+/// Accelerate until halfway, then decelerate, with a quintic curve.
+pub fn quint_in_out(t: f32) -> f32 {
+    if t < 0.5 {
+        16.0 * t * t * t * t * t
+    } else {
+        let t = 2.0 * t - 2.0;
+        0.5 * t * t * t * t * t + 1.0
+    }
+}
+
+/// Accelerate from zero velocity along a quarter of a sine wave.
+pub fn sine_in(t: f32) -> f32 {
+    1.0 - (t * PI * 0.5).cos()
+}
+
+/// Decelerate to zero velocity along a quarter of a sine wave.
+pub fn sine_out(t: f32) -> f32 {
+    (t * PI * 0.5).sin()
+}
+
+/// Accelerate until halfway, then decelerate, along half of a sine wave.
+pub fn sine_in_out(t: f32) -> f32 {
+    0.5 * (1.0 - (t * PI).cos())
+}
+
+/// Accelerate from zero velocity exponentially.
+pub fn expo_in(t: f32) -> f32 {
+    if t <= 0.0 {
+        0.0
+    } else {
+        2.0f32.powf(10.0 * (t - 1.0))
+    }
+}
+
+/// Decelerate to zero velocity exponentially.
+pub fn expo_out(t: f32) -> f32 {
+    if t >= 1.0 {
+        1.0
+    } else {
+        1.0 - 2.0f32.powf(-10.0 * t)
+    }
+}
+
+/// Accelerate until halfway, then decelerate, exponentially.
+pub fn expo_in_out(t: f32) -> f32 {
+    if t <= 0.0 {
+        0.0
+    } else if t >= 1.0 {
+        1.0
+    } else if t < 0.5 {
+        0.5 * 2.0f32.powf(20.0 * t - 10.0)
+    } else {
+        1.0 - 0.5 * 2.0f32.powf(-20.0 * t + 10.0)
+    }
+}
+
+/// Accelerate from zero velocity along a quarter of a circle.
+pub fn circ_in(t: f32) -> f32 {
+    1.0 - (1.0 - t * t).max(0.0).sqrt()
+}
+
+/// Decelerate to zero velocity along a quarter of a circle.
+pub fn circ_out(t: f32) -> f32 {
+    let t = t - 1.0;
+    (1.0 - t * t).max(0.0).sqrt()
+}
+
+/// Accelerate until halfway, then decelerate, along two quarters of a circle.
+pub fn circ_in_out(t: f32) -> f32 {
+    if t < 0.5 {
+        0.5 * circ_in(2.0 * t)
+    } else {
+        0.5 * circ_out(2.0 * t - 1.0) + 0.5
+    }
+}
+
+/// Pull back slightly before accelerating towards the end.
+///
+/// `overshoot` describes how far back to pull, where `DEFAULT_OVERSHOOT` pulls back by roughly 10%.
+pub fn back_in(t: f32, overshoot: f32) -> f32 {
+    t * t * ((overshoot + 1.0) * t - overshoot)
+}
+
+/// Overshoot the end slightly before settling.
+///
+/// `overshoot` describes how far to overshoot, where `DEFAULT_OVERSHOOT` overshoots by roughly
+/// 10%.
+pub fn back_out(t: f32, overshoot: f32) -> f32 {
+    let t = t - 1.0;
+    t * t * ((overshoot + 1.0) * t + overshoot) + 1.0
+}
+
+/// Pull back slightly at the start and overshoot slightly at the end.
+pub fn back_in_out(t: f32, overshoot: f32) -> f32 {
+    let s = overshoot * 1.525;
+    if t < 0.5 {
+        let t = 2.0 * t;
+        0.5 * (t * t * ((s + 1.0) * t - s))
+    } else {
+        let t = 2.0 * t - 2.0;
+        0.5 * (t * t * ((s + 1.0) * t + s) + 2.0)
+    }
+}
+
+// The amplitude and phase shift of the elastic functions. Amplitudes below `1.0` cannot reach the
+// end points and are raised to `1.0`.
+fn elastic_shift(amplitude: f32, period: f32) -> (f32, f32) {
+    if amplitude < 1.0 {
+        (1.0, period / 4.0)
+    } else {
+        (amplitude, period / (2.0 * PI) * (1.0 / amplitude).asin())
+    }
+}
+
+/// Oscillate with growing amplitude before snapping to the end.
+///
+/// `amplitude` values below `1.0` are treated as `1.0`. `period` is the duration of a single
+/// oscillation as a fraction of the total duration.
+pub fn elastic_in(t: f32, amplitude: f32, period: f32) -> f32 {
+    if t <= 0.0 {
+        return 0.0;
+    } else if t >= 1.0 {
+        return 1.0;
+    }
+    let (a, s) = elastic_shift(amplitude, period);
+    let t = t - 1.0;
+    -(a * 2.0f32.powf(10.0 * t) * ((t - s) * (2.0 * PI) / period).sin())
+}
+
+/// Snap towards the end before oscillating with decaying amplitude.
+///
+/// `amplitude` values below `1.0` are treated as `1.0`. `period` is the duration of a single
+/// oscillation as a fraction of the total duration.
+pub fn elastic_out(t: f32, amplitude: f32, period: f32) -> f32 {
+    if t <= 0.0 {
+        return 0.0;
+    } else if t >= 1.0 {
+        return 1.0;
+    }
+    let (a, s) = elastic_shift(amplitude, period);
+    a * 2.0f32.powf(-10.0 * t) * ((t - s) * (2.0 * PI) / period).sin() + 1.0
+}
+
+/// Oscillate with growing and then decaying amplitude, snapping through the halfway point.
+///
+/// `amplitude` values below `1.0` are treated as `1.0`. `period` is the duration of a single
+/// oscillation as a fraction of the total duration.
+pub fn elastic_in_out(t: f32, amplitude: f32, period: f32) -> f32 {
+    if t <= 0.0 {
+        return 0.0;
+    } else if t >= 1.0 {
+        return 1.0;
+    }
+    let (a, s) = elastic_shift(amplitude, period);
+    let t = 2.0 * t - 1.0;
+    let wave = ((t - s) * (2.0 * PI) / period).sin();
+    if t < 0.0 {
+        -0.5 * a * 2.0f32.powf(10.0 * t) * wave
+    } else {
+        0.5 * a * 2.0f32.powf(-10.0 * t) * wave + 1.0
+    }
+}
+
+/// Bounce against the start with growing height before reaching the end.
+pub fn bounce_in(t: f32) -> f32 {
+    1.0 - bounce_out(1.0 - t)
+}
+
+/// Bounce against the end with decaying height, like a dropped ball.
+pub fn bounce_out(t: f32) -> f32 {
+    const N: f32 = 7.5625;
+    const D: f32 = 2.75;
+    if t < 1.0 / D {
+        N * t * t
+    } else if t < 2.0 / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984375
+    }
+}
+
+/// Bounce against the start and then the end.
+pub fn bounce_in_out(t: f32) -> f32 {
+    if t < 0.5 {
+        0.5 * bounce_in(2.0 * t)
+    } else {
+        0.5 * bounce_out(2.0 * t - 1.0) + 0.5
+    }
+}
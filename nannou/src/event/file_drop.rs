@@ -0,0 +1,117 @@
+//! Items related to files dragged and dropped onto a window.
+//!
+//! winit emits a separate event for each file hovered over or dropped onto a window. These events
+//! are collected throughout each pass of the event loop and delivered together as a single
+//! **FileDrop** before the next update, e.g. so that a folder of images dropped onto a window may
+//! be loaded at once.
+
+use crate::window;
+use std::path::PathBuf;
+
+/// A file drag and drop event associated with a single window.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FileDrop {
+    /// The given files are being dragged over the window.
+    Hovered(Vec<PathBuf>),
+    /// The given files were dropped onto the window.
+    Dropped(Vec<PathBuf>),
+    /// The hovered files were dragged away from the window or the drag was cancelled.
+    Cancelled,
+}
+
+// The file drop events received since they were last delivered.
+#[derive(Debug, Default)]
+pub(crate) struct FileDropQueue {
+    pending: Vec<(window::Id, FileDrop)>,
+}
+
+impl FileDrop {
+    /// The paths of the files associated with the event.
+    ///
+    /// Empty in the case of `Cancelled`.
+    pub fn paths(&self) -> &[PathBuf] {
+        match *self {
+            FileDrop::Hovered(ref paths) | FileDrop::Dropped(ref paths) => paths,
+            FileDrop::Cancelled => &[],
+        }
+    }
+}
+
+impl FileDropQueue {
+    // A file is being dragged over the window.
+    pub(crate) fn hovered(&mut self, id: window::Id, path: PathBuf) {
+        match self.pending.last_mut() {
+            Some((last_id, FileDrop::Hovered(paths))) if *last_id == id => paths.push(path),
+            _ => self.pending.push((id, FileDrop::Hovered(vec![path]))),
+        }
+    }
+
+    // A file was dropped onto the window.
+    pub(crate) fn dropped(&mut self, id: window::Id, path: PathBuf) {
+        match self.pending.last_mut() {
+            Some((last_id, FileDrop::Dropped(paths))) if *last_id == id => paths.push(path),
+            _ => self.pending.push((id, FileDrop::Dropped(vec![path]))),
+        }
+    }
+
+    // The hovered files were dragged away from the window.
+    pub(crate) fn cancelled(&mut self, id: window::Id) {
+        self.pending.push((id, FileDrop::Cancelled));
+    }
+
+    // Take all events received since the last call, in the order in which they were received.
+    pub(crate) fn drain(&mut self) -> Vec<(window::Id, FileDrop)> {
+        std::mem::replace(&mut self.pending, vec![])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id() -> window::Id {
+        unsafe { window::Id::dummy() }
+    }
+
+    #[test]
+    fn test_consecutive_files_are_batched() {
+        let mut queue = FileDropQueue::default();
+        queue.hovered(id(), "a.png".into());
+        queue.hovered(id(), "b.png".into());
+        queue.dropped(id(), "a.png".into());
+        queue.dropped(id(), "b.png".into());
+        let paths = vec![PathBuf::from("a.png"), PathBuf::from("b.png")];
+        let expected = vec![
+            (id(), FileDrop::Hovered(paths.clone())),
+            (id(), FileDrop::Dropped(paths)),
+        ];
+        assert_eq!(queue.drain(), expected);
+    }
+
+    #[test]
+    fn test_cancelled_ends_batch() {
+        let mut queue = FileDropQueue::default();
+        queue.hovered(id(), "a.png".into());
+        queue.cancelled(id());
+        queue.hovered(id(), "b.png".into());
+        let expected = vec![
+            (id(), FileDrop::Hovered(vec!["a.png".into()])),
+            (id(), FileDrop::Cancelled),
+            (id(), FileDrop::Hovered(vec!["b.png".into()])),
+        ];
+        assert_eq!(queue.drain(), expected);
+        assert_eq!(expected[1].1.paths(), &[] as &[PathBuf]);
+    }
+
+    #[test]
+    fn test_drain_empties_queue() {
+        let mut queue = FileDropQueue::default();
+        queue.dropped(id(), "a.png".into());
+        assert_eq!(queue.drain().len(), 1);
+        assert!(queue.drain().is_empty());
+        // Files dropped after draining begin a new batch.
+        queue.dropped(id(), "b.png".into());
+        let expected = vec![(id(), FileDrop::Dropped(vec!["b.png".into()]))];
+        assert_eq!(queue.drain(), expected);
+    }
+}
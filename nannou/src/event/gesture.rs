@@ -0,0 +1,319 @@
+//! Recognition of multi-touch gestures from raw touch events.
+//!
+//! A [**GestureRecogniser**](./struct.GestureRecogniser.html) consumes **TouchEvent**s and emits
+//! [**Gesture**](./enum.Gesture.html)s. Each window owns a recogniser, whose gestures are
+//! delivered to the function given to `window::Builder::gesture`.
+
+use crate::event::{TouchEvent, TouchPhase};
+use crate::geom::{Point2, Vector2};
+use std::f32::consts::PI;
+use std::time::{Duration, Instant};
+
+/// A gesture produced by a **GestureRecogniser**.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Gesture {
+    /// Two fingers moved towards or away from each other.
+    ///
+    /// `scale_delta` is the ratio of the distance between the fingers to the distance at the last
+    /// pinch, e.g. `1.1` when the fingers have moved 10% further apart.
+    Pinch { scale_delta: f32, center: Point2 },
+    /// Two fingers rotated around their center.
+    ///
+    /// `angle_delta` is the change in radians since the last rotation, counter-clockwise positive.
+    Rotate { angle_delta: f32, center: Point2 },
+    /// Two fingers moved together in the same direction and were lifted.
+    ///
+    /// `direction` is normalised and `velocity` is in points per second.
+    TwoFingerSwipe { direction: Vector2, velocity: f32 },
+    /// A single finger was briefly pressed and released without moving.
+    ///
+    /// `count` is the number of taps in quick succession, e.g. `2` for a double tap.
+    Tap { position: Point2, count: u32 },
+}
+
+/// Produces **Gesture**s from a stream of **TouchEvent**s.
+///
+/// Hysteresis thresholds ensure that a gesture only begins once the fingers have moved far enough
+/// to be deliberate. Fingers may be lifted or added during a gesture: the gesture always tracks
+/// the two earliest fingers still touching and restarts from their current positions whenever
+/// that pair changes, so deltas never jump.
+#[derive(Clone, Debug)]
+pub struct GestureRecogniser {
+    pinch_threshold: f32,
+    rotate_threshold: f32,
+    swipe_threshold: f32,
+    tap_max_distance: f32,
+    tap_max_duration: Duration,
+    multi_tap_interval: Duration,
+    touches: Vec<Touch>,
+    pair: Option<Pair>,
+    // Whether more than one finger has touched since the last time all fingers were lifted.
+    multi_touch: bool,
+    last_tap: Option<(Instant, Point2, u32)>,
+}
+
+// A finger currently touching.
+#[derive(Copy, Clone, Debug)]
+struct Touch {
+    id: u64,
+    start: Point2,
+    position: Point2,
+    started_at: Instant,
+}
+
+// The state of a gesture made by the two earliest fingers.
+#[derive(Copy, Clone, Debug)]
+struct Pair {
+    ids: (u64, u64),
+    started_at: Instant,
+    start_center: Point2,
+    start_distance: f32,
+    start_angle: f32,
+    last_distance: f32,
+    last_angle: f32,
+    pinching: bool,
+    rotating: bool,
+}
+
+impl GestureRecogniser {
+    /// The default fractional change in finger distance required to begin a pinch.
+    pub const DEFAULT_PINCH_THRESHOLD: f32 = 0.05;
+    /// The default rotation in radians required to begin a rotation.
+    pub const DEFAULT_ROTATE_THRESHOLD: f32 = 0.1;
+    /// The default distance in points the fingers must travel to produce a swipe.
+    pub const DEFAULT_SWIPE_THRESHOLD: f32 = 40.0;
+    /// The default maximum distance in points a finger may move and remain a tap.
+    pub const DEFAULT_TAP_MAX_DISTANCE: f32 = 10.0;
+    /// The default maximum duration of a tap.
+    pub const DEFAULT_TAP_MAX_DURATION: Duration = Duration::from_millis(300);
+    /// The default maximum interval between the taps of a multi-tap.
+    pub const DEFAULT_MULTI_TAP_INTERVAL: Duration = Duration::from_millis(300);
+
+    /// A recogniser with the default thresholds.
+    pub fn new() -> Self {
+        GestureRecogniser {
+            pinch_threshold: Self::DEFAULT_PINCH_THRESHOLD,
+            rotate_threshold: Self::DEFAULT_ROTATE_THRESHOLD,
+            swipe_threshold: Self::DEFAULT_SWIPE_THRESHOLD,
+            tap_max_distance: Self::DEFAULT_TAP_MAX_DISTANCE,
+            tap_max_duration: Self::DEFAULT_TAP_MAX_DURATION,
+            multi_tap_interval: Self::DEFAULT_MULTI_TAP_INTERVAL,
+            touches: vec![],
+            pair: None,
+            multi_touch: false,
+            last_tap: None,
+        }
+    }
+
+    /// The fractional change in distance between two fingers required to begin a pinch.
+    pub fn pinch_threshold(mut self, threshold: f32) -> Self {
+        self.pinch_threshold = threshold;
+        self
+    }
+
+    /// The rotation in radians of two fingers required to begin a rotation.
+    pub fn rotate_threshold(mut self, radians: f32) -> Self {
+        self.rotate_threshold = radians;
+        self
+    }
+
+    /// The distance in points two fingers must travel together to produce a swipe.
+    pub fn swipe_threshold(mut self, distance: f32) -> Self {
+        self.swipe_threshold = distance;
+        self
+    }
+
+    /// The maximum distance in points a finger may move and still produce a tap.
+    pub fn tap_max_distance(mut self, distance: f32) -> Self {
+        self.tap_max_distance = distance;
+        self
+    }
+
+    /// The maximum duration for which a finger may touch and still produce a tap.
+    pub fn tap_max_duration(mut self, duration: Duration) -> Self {
+        self.tap_max_duration = duration;
+        self
+    }
+
+    /// The maximum interval between taps for them to be counted as a multi-tap.
+    pub fn multi_tap_interval(mut self, interval: Duration) -> Self {
+        self.multi_tap_interval = interval;
+        self
+    }
+
+    /// The number of fingers currently touching.
+    pub fn touch_count(&self) -> usize {
+        self.touches.len()
+    }
+
+    /// Process the given touch event, returning any gestures it completes.
+    pub fn touch(&mut self, touch: &TouchEvent) -> Vec<Gesture> {
+        self.touch_at(touch, Instant::now())
+    }
+
+    /// The same as `touch`, but with the time at which the event occurred.
+    pub fn touch_at(&mut self, touch: &TouchEvent, now: Instant) -> Vec<Gesture> {
+        let mut gestures = vec![];
+        match touch.phase {
+            TouchPhase::Started => {
+                self.touches.retain(|t| t.id != touch.id);
+                self.touches.push(Touch {
+                    id: touch.id,
+                    start: touch.position,
+                    position: touch.position,
+                    started_at: now,
+                });
+                self.multi_touch |= self.touches.len() > 1;
+            }
+            TouchPhase::Moved => {
+                if let Some(t) = self.touches.iter_mut().find(|t| t.id == touch.id) {
+                    t.position = touch.position;
+                }
+                self.update_pair(&mut gestures);
+            }
+            TouchPhase::Ended | TouchPhase::Cancelled => {
+                let ix = match self.touches.iter().position(|t| t.id == touch.id) {
+                    Some(ix) => ix,
+                    None => return gestures,
+                };
+                self.touches[ix].position = touch.position;
+                // Lifting either finger of the pair may complete a swipe.
+                if touch.phase == TouchPhase::Ended {
+                    self.check_swipe(touch.id, now, &mut gestures);
+                }
+                let t = self.touches.remove(ix);
+                if touch.phase == TouchPhase::Ended && !self.multi_touch {
+                    self.check_tap(&t, now, &mut gestures);
+                }
+                if self.touches.is_empty() {
+                    self.multi_touch = false;
+                }
+            }
+        }
+        self.sync_pair(now);
+        gestures
+    }
+
+    // Ensure the pair tracks the two earliest fingers, restarting it if they have changed.
+    fn sync_pair(&mut self, now: Instant) {
+        let ids = match (self.touches.get(0), self.touches.get(1)) {
+            (Some(a), Some(b)) => (a.id, b.id),
+            _ => {
+                self.pair = None;
+                return;
+            }
+        };
+        if self.pair.map(|p| p.ids) == Some(ids) {
+            return;
+        }
+        let (a, b) = (self.touches[0].position, self.touches[1].position);
+        let (center, distance, angle) = measure(a, b);
+        self.pair = Some(Pair {
+            ids,
+            started_at: now,
+            start_center: center,
+            start_distance: distance,
+            start_angle: angle,
+            last_distance: distance,
+            last_angle: angle,
+            pinching: false,
+            rotating: false,
+        });
+    }
+
+    fn update_pair(&mut self, gestures: &mut Vec<Gesture>) {
+        let pair = match self.pair {
+            Some(ref mut pair) => pair,
+            None => return,
+        };
+        let (a, b) = (self.touches[0].position, self.touches[1].position);
+        let (center, distance, angle) = measure(a, b);
+
+        if !pair.pinching && pair.start_distance > 0.0 {
+            let change = (distance / pair.start_distance - 1.0).abs();
+            pair.pinching = change > self.pinch_threshold;
+        }
+        if pair.pinching && pair.last_distance > 0.0 {
+            let scale_delta = distance / pair.last_distance;
+            gestures.push(Gesture::Pinch {
+                scale_delta,
+                center,
+            });
+        }
+        pair.last_distance = distance;
+
+        if !pair.rotating {
+            let change = wrap_angle(angle - pair.start_angle).abs();
+            pair.rotating = change > self.rotate_threshold;
+        }
+        if pair.rotating {
+            let angle_delta = wrap_angle(angle - pair.last_angle);
+            gestures.push(Gesture::Rotate {
+                angle_delta,
+                center,
+            });
+        }
+        pair.last_angle = angle;
+    }
+
+    fn check_swipe(&self, id: u64, now: Instant, gestures: &mut Vec<Gesture>) {
+        let pair = match self.pair {
+            Some(pair) if pair.ids.0 == id || pair.ids.1 == id => pair,
+            _ => return,
+        };
+        // A deliberate pinch or rotation is not also a swipe.
+        if pair.pinching || pair.rotating {
+            return;
+        }
+        let (a, b) = (self.touches[0].position, self.touches[1].position);
+        let (center, _, _) = measure(a, b);
+        let displacement = center - pair.start_center;
+        let distance = displacement.magnitude();
+        if distance < self.swipe_threshold {
+            return;
+        }
+        let secs = now.duration_since(pair.started_at).as_secs_f32().max(1e-3);
+        gestures.push(Gesture::TwoFingerSwipe {
+            direction: displacement / distance,
+            velocity: distance / secs,
+        });
+    }
+
+    fn check_tap(&mut self, touch: &Touch, now: Instant, gestures: &mut Vec<Gesture>) {
+        let moved = (touch.position - touch.start).magnitude();
+        let duration = now.duration_since(touch.started_at);
+        if moved > self.tap_max_distance || duration > self.tap_max_duration {
+            return;
+        }
+        let position = touch.start;
+        let count = match self.last_tap {
+            Some((at, pos, count))
+                if now.duration_since(at) <= self.multi_tap_interval
+                    && (position - pos).magnitude() <= self.tap_max_distance * 2.0 =>
+            {
+                count + 1
+            }
+            _ => 1,
+        };
+        self.last_tap = Some((now, position, count));
+        gestures.push(Gesture::Tap { position, count });
+    }
+}
+
+impl Default for GestureRecogniser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// The center, distance and angle between two points.
+fn measure(a: Point2, b: Point2) -> (Point2, f32, f32) {
+    let center = (a + b) * 0.5;
+    let v = b - a;
+    (center, v.magnitude(), v.angle())
+}
+
+// Wrap the given angle to the range `-PI..=PI`.
+fn wrap_angle(angle: f32) -> f32 {
+    (angle + PI).rem_euclid(2.0 * PI) - PI
+}
@@ -5,13 +5,27 @@
 //!   events related to a single window.
 //! - [**WindowEvent**](./enum.WindowEvent.html) - a stripped-back, simplified, newcomer-friendly
 //!   version of the **raw**, low-level winit event.
+//! - [**FileDrop**](./file_drop/enum.FileDrop.html) - files hovered over or dropped onto a window.
+//! - [**Gesture**](./gesture/enum.Gesture.html) - pinch, rotate, swipe and tap gestures recognised
+//!   from touch events.
+//! - [**KeyboardShortcut**](./shortcut/struct.KeyboardShortcut.html) - hotkeys registered via
+//!   `app::Builder::shortcut`.
 
+use crate::asset::AssetEvent;
 use crate::geom::{self, Point2, Vector2};
 use crate::window;
 use crate::App;
 use std::path::PathBuf;
 use winit;
 
+pub mod file_drop;
+pub mod gesture;
+pub mod shortcut;
+
+pub use self::file_drop::FileDrop;
+pub(crate) use self::file_drop::FileDropQueue;
+pub use self::gesture::{Gesture, GestureRecogniser};
+pub use self::shortcut::{KeyboardShortcut, ShortcutFn};
 pub use winit::event::{
     ElementState, KeyboardInput, ModifiersState, MouseButton, MouseScrollDelta, TouchPhase,
     VirtualKeyCode as Key,
@@ -21,10 +35,32 @@ pub use winit::event::{
 pub trait LoopEvent: 'static + From<Update> {
     /// Produce a loop event from the given winit event.
     fn from_winit_event<'a, T>(_: &winit::event::Event<'a, T>, _: &App) -> Option<Self>;
+
+    /// Produce a loop event from the given asset event.
+    ///
+    /// By default, asset events are ignored.
+    fn from_asset_event(_: AssetEvent) -> Option<Self> {
+        None
+    }
+
+    /// Produce a loop event from the files hovered over or dropped onto the given window.
+    ///
+    /// By default, file drop events are ignored.
+    fn from_file_drop(_: window::Id, _: FileDrop) -> Option<Self> {
+        None
+    }
+
+    /// Produce a loop event from the given gamepad event.
+    ///
+    /// By default, gamepad events are ignored.
+    #[cfg(feature = "gamepad")]
+    fn from_gamepad_event(_: crate::gamepad::GamepadEvent) -> Option<Self> {
+        None
+    }
 }
 
 /// Update event, emitted on each pass of an application loop.
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Update {
     /// The duration since the last update was emitted.
     ///
@@ -34,6 +70,10 @@ pub struct Update {
     ///
     /// Specifically, this is the duration of time since the given `model` function returned.
     pub since_start: std::time::Duration,
+    /// The fraction of a step remaining after this frame's updates within the range `0.0..1.0`.
+    ///
+    /// Only meaningful in `LoopMode::FixedStep`, otherwise `0.0`.
+    pub accumulator_alpha: f32,
 }
 
 /// The default application **Event** type.
@@ -60,6 +100,19 @@ pub enum Event {
     /// The first update's delta will be the time since the `model` function returned.
     Update(Update),
 
+    /// An asset that was loaded via `App::load_asset` has completed loading.
+    Asset(AssetEvent),
+
+    /// Files were hovered over or dropped onto the window with the given Id.
+    ///
+    /// Unlike the per-file `HoveredFile` and `DroppedFile` window events, all files hovered or
+    /// dropped together are delivered within a single event before the next update.
+    FileDrop(window::Id, FileDrop),
+
+    /// An event was emitted by a gamepad.
+    #[cfg(feature = "gamepad")]
+    Gamepad(crate::gamepad::GamepadEvent),
+
     /// The application has been suspended or resumed.
     Suspended,
     /// The application has been awakened.
@@ -344,6 +397,19 @@ impl LoopEvent for Event {
         };
         Some(event)
     }
+
+    fn from_asset_event(event: AssetEvent) -> Option<Self> {
+        Some(Event::Asset(event))
+    }
+
+    fn from_file_drop(id: window::Id, drop: FileDrop) -> Option<Self> {
+        Some(Event::FileDrop(id, drop))
+    }
+
+    #[cfg(feature = "gamepad")]
+    fn from_gamepad_event(event: crate::gamepad::GamepadEvent) -> Option<Self> {
+        Some(Event::Gamepad(event))
+    }
 }
 
 impl From<Update> for Event {
@@ -0,0 +1,70 @@
+//! Keyboard shortcuts registered via `app::Builder::shortcut`.
+//!
+//! Shortcuts allow for handling hotkeys declaratively rather than matching on keys within an
+//! event function. Each shortcut calls a function with the model whenever its key is pressed
+//! while exactly its modifiers are held. Registered shortcuts may be listed via `App::shortcuts`,
+//! e.g. for displaying an on-screen help overlay, or printed via `App::print_shortcuts`.
+
+use crate::event::{Key, ModifiersState};
+use std::fmt;
+
+/// The function signature of a shortcut handler.
+pub type ShortcutFn<Model> = fn(&mut Model);
+
+/// A keyboard shortcut registered with the app.
+///
+/// The shortcut's handler is stored by the app's event loop alongside the model, ensuring its
+/// model type is checked at compile time.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyboardShortcut {
+    key: Key,
+    modifiers: ModifiersState,
+    description: String,
+}
+
+impl KeyboardShortcut {
+    // Create a new shortcut with the given description.
+    pub(crate) fn new(key: Key, modifiers: ModifiersState, description: &str) -> Self {
+        KeyboardShortcut {
+            key,
+            modifiers,
+            description: description.to_string(),
+        }
+    }
+
+    /// The key that triggers the shortcut.
+    pub fn key(&self) -> Key {
+        self.key
+    }
+
+    /// The modifiers that must be held for the shortcut to trigger.
+    pub fn modifiers(&self) -> ModifiersState {
+        self.modifiers
+    }
+
+    /// A description of what the shortcut does.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Whether or not the shortcut is triggered by the given key and held modifiers.
+    pub fn matches(&self, key: Key, modifiers: ModifiersState) -> bool {
+        self.key == key && self.modifiers == modifiers
+    }
+}
+
+/// Formats the shortcut's key combination followed by its description, e.g. `Ctrl+S: Save`.
+impl fmt::Display for KeyboardShortcut {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mods = [
+            (self.modifiers.ctrl(), "Ctrl"),
+            (self.modifiers.alt(), "Alt"),
+            (self.modifiers.shift(), "Shift"),
+            (self.modifiers.logo(), "Logo"),
+        ];
+        for &(_, name) in mods.iter().filter(|&&(held, _)| held) {
+            write!(f, "{}+", name)?;
+        }
+        write!(f, "{:?}: {}", self.key, self.description)
+    }
+}
@@ -0,0 +1,225 @@
+//! Items related to vector fields, e.g. for flow-field-driven animation.
+//!
+//! A **FlowField2D** derives a time-varying 2D vector field from any **NoiseSource**. Points may
+//! be carried along the field on the CPU via `advect`, while a **ParticleSystem** may be carried
+//! along the field on the GPU via `set_flow_field`:
+//!
+//! ```ignore
+//! let field = FlowField2D::new(PerlinNoise::new(), 0.005, 0.1).strength(60.0);
+//! model.particles.set_flow_field(&field);
+//!
+//! // Or advect a single point on the CPU.
+//! model.point = field.advect(model.point, dt, app.time);
+//! ```
+
+use crate::draw::{self, Draw, Drawing};
+use crate::geom::{vec2, Point2, Rect, Vector2};
+use crate::noise::{CurlNoise2d, NoiseSource};
+
+/// A 2D vector field derived from a noise source.
+///
+/// The noise is sampled at each position multiplied by `scale`, while time is multiplied by
+/// `time_scale` to produce the third dimension of the noise, animating the field.
+#[derive(Clone, Debug)]
+pub struct FlowField2D<N> {
+    field: Field<N>,
+    scale: f32,
+    time_scale: f32,
+    strength: f32,
+    grid: Grid,
+}
+
+// The way in which vectors are derived from the noise.
+#[derive(Clone, Debug)]
+enum Field<N> {
+    // Each noise value maps to the angle of a vector.
+    Angle(N),
+    // The curl of the noise.
+    Curl(CurlNoise2d<N>),
+}
+
+// The grid onto which the field is sampled when driving a particle system.
+#[derive(Copy, Clone, Debug)]
+struct Grid {
+    bounds: Rect,
+    cols: u32,
+    rows: u32,
+}
+
+/// A flow field sampled onto a grid, as uploaded to the GPU by a **ParticleSystem**.
+#[derive(Clone, Debug)]
+pub(crate) struct FlowGrid {
+    pub(crate) bounds: Rect,
+    pub(crate) cols: u32,
+    pub(crate) rows: u32,
+    // The vector at each grid point, row by row from the bottom left.
+    pub(crate) vectors: Vec<[f32; 2]>,
+}
+
+impl<N> FlowField2D<N>
+where
+    N: NoiseSource,
+{
+    /// The default multiplier applied to the length of every vector within the field.
+    pub const DEFAULT_STRENGTH: f32 = 1.0;
+
+    /// The default number of columns within the grid onto which the field is sampled for
+    /// particle systems.
+    pub const DEFAULT_GRID_COLS: u32 = 64;
+
+    /// The default number of rows within the grid onto which the field is sampled for particle
+    /// systems.
+    pub const DEFAULT_GRID_ROWS: u32 = 64;
+
+    /// A field in which each noise value maps to the angle of a vector, such that every vector has
+    /// a length equal to the field's `strength`.
+    pub fn new(noise: N, scale: f32, time_scale: f32) -> Self {
+        Self::with_field(Field::Angle(noise), scale, time_scale)
+    }
+
+    /// A field derived from the curl of the noise, producing divergence-free motion in which
+    /// points swirl rather than converge.
+    ///
+    /// The length of each vector is proportional to the steepness of the noise. The field is
+    /// static until a `time_scale` is specified.
+    pub fn curl_noise(noise: N, scale: f32) -> Self {
+        Self::with_field(Field::Curl(CurlNoise2d::new(noise)), scale, 0.0)
+    }
+
+    fn with_field(field: Field<N>, scale: f32, time_scale: f32) -> Self {
+        let grid = Grid {
+            bounds: Rect::from_w_h(1024.0, 1024.0),
+            cols: Self::DEFAULT_GRID_COLS,
+            rows: Self::DEFAULT_GRID_ROWS,
+        };
+        FlowField2D {
+            field,
+            scale,
+            time_scale,
+            strength: Self::DEFAULT_STRENGTH,
+            grid,
+        }
+    }
+
+    /// The multiplier applied to time when sampling the noise. `0.0` produces a static field.
+    pub fn time_scale(mut self, time_scale: f32) -> Self {
+        self.time_scale = time_scale;
+        self
+    }
+
+    /// A multiplier applied to the length of every vector within the field, e.g. the speed in
+    /// points per second at which particles are carried.
+    pub fn strength(mut self, strength: f32) -> Self {
+        self.strength = strength;
+        self
+    }
+
+    /// The region and resolution of the grid onto which the field is sampled when driving a
+    /// **ParticleSystem**. Particles outside of the region are carried by the nearest edge of the
+    /// grid.
+    ///
+    /// By default, the grid covers a `1024.0` by `1024.0` region centred on the origin with
+    /// `64` columns and rows.
+    ///
+    /// **Panics** if `cols` or `rows` is less than `2`.
+    pub fn grid(mut self, bounds: Rect, cols: u32, rows: u32) -> Self {
+        assert!(
+            cols >= 2 && rows >= 2,
+            "a flow field grid requires at least 2 columns and rows"
+        );
+        self.grid = Grid { bounds, cols, rows };
+        self
+    }
+
+    /// The noise source from which the field is derived.
+    pub fn source(&self) -> &N {
+        match self.field {
+            Field::Angle(ref noise) => noise,
+            Field::Curl(ref curl) => curl.source(),
+        }
+    }
+
+    /// The vector of the field at the given position and time.
+    pub fn vector_at(&self, pos: Point2, time: f32) -> Vector2 {
+        let (x, y) = (pos.x * self.scale, pos.y * self.scale);
+        let z = time * self.time_scale;
+        let v = match self.field {
+            Field::Angle(ref noise) => {
+                let angle = noise.get_3d(x, y, z) * std::f32::consts::PI * 2.0;
+                vec2(angle.cos(), angle.sin())
+            }
+            Field::Curl(ref curl) => curl.get_3d(x, y, z),
+        };
+        v * self.strength
+    }
+
+    /// The position of a point at `pos` after being carried along the field for `dt` seconds,
+    /// beginning at `time`.
+    ///
+    /// The path is integrated via the fourth-order Runge-Kutta method.
+    pub fn advect(&self, pos: Point2, dt: f32, time: f32) -> Point2 {
+        let half = dt * 0.5;
+        let k1 = self.vector_at(pos, time);
+        let k2 = self.vector_at(pos + k1 * half, time + half);
+        let k3 = self.vector_at(pos + k2 * half, time + half);
+        let k4 = self.vector_at(pos + k3 * dt, time + dt);
+        pos + (k1 + k2 * 2.0 + k3 * 2.0 + k4) * (dt / 6.0)
+    }
+
+    /// Visualise the field at the given time as a grid of `resolution` by `resolution` arrows
+    /// within `bounds`.
+    ///
+    /// Arrows are scaled relative to the longest vector so that none overlap. The returned
+    /// **Drawing** may be used to colour the arrows.
+    pub fn debug_draw<'a>(
+        &self,
+        draw: &'a Draw,
+        bounds: Rect,
+        resolution: u32,
+        time: f32,
+    ) -> Drawing<'a, draw::primitive::Arrow> {
+        let n = resolution.max(1);
+        let (w, h) = (bounds.w() / n as f32, bounds.h() / n as f32);
+        let positions: Vec<Point2> = (0..n * n)
+            .map(|i| {
+                let (col, row) = ((i % n) as f32, (i / n) as f32);
+                let x = bounds.left() + (col + 0.5) * w;
+                let y = bounds.bottom() + (row + 0.5) * h;
+                Point2::new(x, y)
+            })
+            .collect();
+        let vectors: Vec<Vector2> = positions.iter().map(|&p| self.vector_at(p, time)).collect();
+        let longest = vectors.iter().map(|v| v.magnitude()).fold(0.0, f32::max);
+        let scale = match longest > 0.0 {
+            true => 0.8 * w.min(h) / longest,
+            false => 0.0,
+        };
+        draw.vector_field(&positions, &vectors, scale)
+    }
+
+    // Sample the field across its grid at the given time.
+    pub(crate) fn sample_grid(&self, time: f32) -> FlowGrid {
+        let Grid { bounds, cols, rows } = self.grid;
+        let dx = bounds.w() / (cols - 1) as f32;
+        let dy = bounds.h() / (rows - 1) as f32;
+        let vectors = (0..cols * rows)
+            .map(|i| {
+                let (col, row) = ((i % cols) as f32, (i / cols) as f32);
+                let p = Point2::new(bounds.left() + col * dx, bounds.bottom() + row * dy);
+                let v = self.vector_at(p, time);
+                [v.x, v.y]
+            })
+            .collect();
+        FlowGrid {
+            bounds,
+            cols,
+            rows,
+            vectors,
+        }
+    }
+
+    // Whether or not the field varies over time.
+    pub(crate) fn is_static(&self) -> bool {
+        self.time_scale == 0.0
+    }
+}
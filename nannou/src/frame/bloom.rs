@@ -0,0 +1,217 @@
+//! A screen-space bloom effect that may be applied to a **Frame** via `Frame::apply_bloom`.
+//!
+//! Bloom is applied upon submission of the frame in three stages:
+//!
+//! 1. The bright portion of the frame, that above `BloomConfig::threshold`, is written to a
+//!    half resolution texture.
+//! 2. The bright texture is blurred with `BloomConfig::num_passes` horizontal and vertical
+//!    gaussian passes, ping-ponging between two textures.
+//! 3. The blurred texture is additively blended back onto the frame, scaled by
+//!    `BloomConfig::intensity`.
+
+use crate::frame::fullscreen::FullscreenQuad;
+use crate::frame::Frame;
+use crate::wgpu;
+
+/// Describes a bloom effect applied to a **Frame**.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct BloomConfig {
+    /// Only the brightness of the frame's colours exceeding this value will bloom.
+    pub threshold: f32,
+    /// A multiplier for the blurred light before it is added back onto the frame.
+    pub intensity: f32,
+    /// The approximate distance in pixels over which bright areas bleed into their surroundings.
+    pub radius: f32,
+    /// The number of horizontal and vertical blur pass pairs.
+    ///
+    /// More passes produce a smoother blur at the cost of performance. `BloomConfig::new`
+    /// determines a suitable value from the radius.
+    pub num_passes: u32,
+}
+
+// The cached pipelines and textures used to apply bloom to a window's frames.
+#[derive(Debug)]
+pub(crate) struct Bloom {
+    quad: FullscreenQuad,
+    _fs_mod: wgpu::ShaderModule,
+    blur_pipeline: wgpu::RenderPipeline,
+    composite_pipeline: wgpu::RenderPipeline,
+    targets: Option<Targets>,
+}
+
+// The half resolution textures between which the blur passes ping-pong.
+#[derive(Debug)]
+struct Targets {
+    size: [u32; 2],
+    _textures: [wgpu::Texture; 2],
+    ping: wgpu::TextureView,
+    pong: wgpu::TextureView,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct Uniforms {
+    direction: [f32; 2],
+    threshold: f32,
+    intensity: f32,
+}
+
+impl BloomConfig {
+    /// The maximum distance in texels between taps of a single blur pass before gaps between
+    /// samples become visible.
+    pub const MAX_TAP_SPACING: f32 = 2.0;
+
+    /// A bloom effect with the given parameters and a number of passes suitable for the radius.
+    pub fn new(threshold: f32, intensity: f32, radius: f32) -> Self {
+        let num_passes = Self::num_passes_for_radius(radius);
+        BloomConfig {
+            threshold,
+            intensity,
+            radius,
+            num_passes,
+        }
+    }
+
+    /// The number of blur pass pairs required to cover the given radius without the taps of
+    /// each pass becoming further than `MAX_TAP_SPACING` texels apart.
+    pub fn num_passes_for_radius(radius: f32) -> u32 {
+        // Each pass covers four taps either side within the half resolution texture.
+        let texels_per_pass = 4.0 * Self::MAX_TAP_SPACING * 2.0;
+        ((radius.max(0.0) / texels_per_pass).ceil() as u32).max(1)
+    }
+
+    /// Specify the number of blur pass pairs.
+    pub fn num_passes(mut self, num_passes: u32) -> Self {
+        self.num_passes = num_passes;
+        self
+    }
+}
+
+impl Bloom {
+    // Create the pipelines used to apply bloom to a frame's texture.
+    pub(crate) fn new(device: &wgpu::Device) -> Self {
+        let quad = FullscreenQuad::new(device);
+        let fs_mod =
+            wgpu::shader_from_spirv_bytes(device, include_bytes!("shaders/frag_bloom.spv"));
+        let replace = wgpu::BlendDescriptor::REPLACE;
+        let blur_pipeline = quad.render_pipeline(device, &fs_mod, replace, replace);
+        let composite_pipeline = quad.render_pipeline(device, &fs_mod, ADD_ONE, KEEP_DST);
+        Bloom {
+            quad,
+            _fs_mod: fs_mod,
+            blur_pipeline,
+            composite_pipeline,
+            targets: None,
+        }
+    }
+
+    // Encode the passes applying bloom to the given non-multisampled texture in place.
+    pub(crate) fn encode(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        texture: &wgpu::Texture,
+        texture_view: &wgpu::TextureView,
+        config: &BloomConfig,
+    ) {
+        let [w, h] = texture.size();
+        let size = [(w / 2).max(1), (h / 2).max(1)];
+        let recreate = self
+            .targets
+            .as_ref()
+            .map(|t| t.size != size)
+            .unwrap_or(true);
+        if recreate {
+            self.targets = Some(Targets::new(device, size));
+        }
+        let targets = self.targets.as_ref().expect("no bloom targets");
+
+        // Spread the radius across the passes, converting it to half resolution texels.
+        let num_passes = config.num_passes.max(1);
+        let spacing = config.radius.max(0.0) * 0.5 / (4.0 * num_passes as f32);
+        let horizontal = [spacing / size[0] as f32, 0.0];
+        let vertical = [0.0, spacing / size[1] as f32];
+
+        // Threshold and downsample.
+        let (quad, blur) = (&self.quad, &self.blur_pipeline);
+        let threshold = uniforms([0.0, 0.0], config.threshold, 1.0);
+        quad.encode_pass(
+            device,
+            encoder,
+            blur,
+            texture_view,
+            &targets.ping,
+            &threshold,
+        );
+
+        // Blur.
+        for _ in 0..num_passes {
+            let h = uniforms(horizontal, 0.0, 1.0);
+            quad.encode_pass(device, encoder, blur, &targets.ping, &targets.pong, &h);
+            let v = uniforms(vertical, 0.0, 1.0);
+            quad.encode_pass(device, encoder, blur, &targets.pong, &targets.ping, &v);
+        }
+
+        // Composite.
+        let composite = uniforms([0.0, 0.0], 0.0, config.intensity);
+        quad.encode_pass(
+            device,
+            encoder,
+            &self.composite_pipeline,
+            &targets.ping,
+            texture_view,
+            &composite,
+        );
+    }
+}
+
+impl Targets {
+    fn new(device: &wgpu::Device, size: [u32; 2]) -> Self {
+        let create = || {
+            wgpu::TextureBuilder::new()
+                .size(size)
+                .format(Frame::TEXTURE_FORMAT)
+                .usage(wgpu::TextureUsage::SAMPLED)
+                .as_render_target()
+                .label("nannou_bloom")
+                .build(device)
+        };
+        let textures = [create(), create()];
+        let ping = textures[0].view().build();
+        let pong = textures[1].view().build();
+        Targets {
+            size,
+            _textures: textures,
+            ping,
+            pong,
+        }
+    }
+}
+
+impl Default for BloomConfig {
+    fn default() -> Self {
+        BloomConfig::new(0.8, 1.0, 16.0)
+    }
+}
+
+// Adds the blurred light onto the frame.
+const ADD_ONE: wgpu::BlendDescriptor = wgpu::BlendDescriptor {
+    src_factor: wgpu::BlendFactor::One,
+    dst_factor: wgpu::BlendFactor::One,
+    operation: wgpu::BlendOperation::Add,
+};
+
+// Leaves the frame's alpha untouched.
+const KEEP_DST: wgpu::BlendDescriptor = wgpu::BlendDescriptor {
+    src_factor: wgpu::BlendFactor::Zero,
+    dst_factor: wgpu::BlendFactor::One,
+    operation: wgpu::BlendOperation::Add,
+};
+
+fn uniforms(direction: [f32; 2], threshold: f32, intensity: f32) -> Uniforms {
+    Uniforms {
+        direction,
+        threshold,
+        intensity,
+    }
+}
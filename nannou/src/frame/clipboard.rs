@@ -0,0 +1,54 @@
+//! A dedicated thread owning the system clipboard to which captured frames are copied.
+//!
+//! A single clipboard handle is kept alive for as long as the window exists, as on some platforms
+//! (e.g. X11) the copied image is only available while the handle that set it remains alive.
+
+use std::sync::{mpsc, Mutex};
+use std::thread;
+
+// Provides access to the window's clipboard thread, spawned upon the first copy.
+#[derive(Debug, Default)]
+pub(crate) struct Clipboard {
+    tx: Mutex<Option<mpsc::Sender<image::RgbaImage>>>,
+}
+
+impl Clipboard {
+    // A sender via which images may be copied to the clipboard, spawning the thread if necessary.
+    pub(crate) fn sender(&self) -> mpsc::Sender<image::RgbaImage> {
+        let mut tx = self.tx.lock().expect("failed to lock the clipboard sender");
+        tx.get_or_insert_with(spawn).clone()
+    }
+}
+
+fn spawn() -> mpsc::Sender<image::RgbaImage> {
+    let (tx, rx) = mpsc::channel();
+    thread::Builder::new()
+        .name("nannou_clipboard".into())
+        .spawn(move || run(rx))
+        .expect("failed to spawn clipboard thread");
+    tx
+}
+
+// Copy each received image to the clipboard until the window is dropped.
+fn run(rx: mpsc::Receiver<image::RgbaImage>) {
+    let mut clipboard = match arboard::Clipboard::new() {
+        Ok(clipboard) => clipboard,
+        Err(e) => {
+            // TODO: Log errors, don't print to stderr.
+            eprintln!("failed to access the clipboard: {}", e);
+            return;
+        }
+    };
+    for image in rx {
+        let (width, height) = image.dimensions();
+        let image_data = arboard::ImageData {
+            width: width as usize,
+            height: height as usize,
+            bytes: std::borrow::Cow::Borrowed(image.as_raw()),
+        };
+        if let Err(e) = clipboard.set_image(image_data) {
+            // TODO: Log errors, don't print to stderr.
+            eprintln!("failed to copy captured frame to the clipboard: {}", e);
+        }
+    }
+}
@@ -0,0 +1,84 @@
+//! Double-buffered textures allowing a window's previous frame to be read while rendering the
+//! next, e.g. for trails or accumulated blur.
+//!
+//! Feedback is enabled for a window upon the first call to `App::feedback_texture`. The textures
+//! are swapped before each call to `view`, at which point the texture written during the previous
+//! frame becomes readable via `RawFrame::feedback_texture_view`. Upon submission of a **Frame**,
+//! its image is written to the other texture in the same manner as it is written to the swap
+//! chain.
+
+use crate::wgpu;
+
+// The pair of textures between which a window's frames alternate.
+#[derive(Debug)]
+pub(crate) struct Feedback {
+    textures: [wgpu::Texture; 2],
+    views: [wgpu::TextureView; 2],
+    // The index of the texture written to by the current frame.
+    current: usize,
+    // Whether or not the textures must be cleared before their first use.
+    requires_clear: bool,
+}
+
+impl Feedback {
+    // Create a pair of textures with the given size and format.
+    pub(crate) fn new(device: &wgpu::Device, size: [u32; 2], format: wgpu::TextureFormat) -> Self {
+        let create = || {
+            wgpu::TextureBuilder::new()
+                .size(size)
+                .format(format)
+                .usage(wgpu::TextureUsage::SAMPLED | wgpu::TextureUsage::COPY_SRC)
+                .as_render_target()
+                .label("nannou_feedback")
+                .build(device)
+        };
+        let textures = [create(), create()];
+        let views = [textures[0].view().build(), textures[1].view().build()];
+        Feedback {
+            textures,
+            views,
+            current: 0,
+            requires_clear: true,
+        }
+    }
+
+    // Whether or not the textures match the given size and format.
+    pub(crate) fn matches(&self, size: [u32; 2], format: wgpu::TextureFormat) -> bool {
+        let texture = &self.textures[0];
+        texture.size() == size && texture.format() == format
+    }
+
+    // Swap the textures such that the last written texture becomes the previous frame's texture.
+    pub(crate) fn swap(&mut self) {
+        self.current = 1 - self.current;
+    }
+
+    // The texture written to by the current frame.
+    pub(crate) fn current_texture(&self) -> &wgpu::Texture {
+        &self.textures[self.current]
+    }
+
+    // The views to the previous and current frame's textures respectively.
+    //
+    // Encodes commands clearing both textures to transparent black if they have not yet been used.
+    pub(crate) fn views(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+    ) -> (wgpu::TextureView, wgpu::TextureView) {
+        if self.requires_clear {
+            for view in self.views.iter() {
+                wgpu::RenderPassBuilder::new()
+                    .color_attachment(view, |color| {
+                        color
+                            .load_op(wgpu::LoadOp::Clear)
+                            .clear_color(wgpu::Color::TRANSPARENT)
+                    })
+                    .begin(encoder);
+            }
+            self.requires_clear = false;
+        }
+        let previous = self.views[1 - self.current].clone();
+        let current = self.views[self.current].clone();
+        (previous, current)
+    }
+}
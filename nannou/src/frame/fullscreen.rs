@@ -0,0 +1,119 @@
+// The full-screen quad shared by the passes of the frame's screen-space effects.
+//
+// Each pass samples a single source texture and reads a single uniform buffer within the fragment
+// shader, which together make up the shared bind group layout.
+
+use crate::frame::Frame;
+use crate::wgpu;
+
+// The vertex shader, sampler, layout and vertex buffer shared by each pass of an effect.
+#[derive(Debug)]
+pub(crate) struct FullscreenQuad {
+    vs_mod: wgpu::ShaderModule,
+    bind_group_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    vertex_buffer: wgpu::Buffer,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+struct Vertex {
+    pub position: [f32; 2],
+}
+
+impl FullscreenQuad {
+    pub(crate) fn new(device: &wgpu::Device) -> Self {
+        let vs_mod = wgpu::shader_from_spirv_bytes(device, include_bytes!("shaders/vert.spv"));
+        let sampler = wgpu::SamplerBuilder::new().build(device);
+        let bind_group_layout = wgpu::BindGroupLayoutBuilder::new()
+            .sampled_texture(
+                wgpu::ShaderStage::FRAGMENT,
+                false,
+                wgpu::TextureViewDimension::D2,
+                wgpu::TextureComponentType::Float,
+            )
+            .sampler(wgpu::ShaderStage::FRAGMENT)
+            .uniform_buffer(wgpu::ShaderStage::FRAGMENT, false)
+            .build(device);
+        let vertices_bytes = unsafe { wgpu::bytes::from_slice(&VERTICES[..]) };
+        let vertex_buffer =
+            device.create_buffer_with_data(vertices_bytes, wgpu::BufferUsage::VERTEX);
+        FullscreenQuad {
+            vs_mod,
+            bind_group_layout,
+            sampler,
+            vertex_buffer,
+        }
+    }
+
+    // Create a pipeline rendering the quad to a texture of the frame's format with the given
+    // fragment shader and blending.
+    pub(crate) fn render_pipeline(
+        &self,
+        device: &wgpu::Device,
+        fs_mod: &wgpu::ShaderModule,
+        color_blend: wgpu::BlendDescriptor,
+        alpha_blend: wgpu::BlendDescriptor,
+    ) -> wgpu::RenderPipeline {
+        let desc = wgpu::PipelineLayoutDescriptor {
+            bind_group_layouts: &[&self.bind_group_layout],
+        };
+        let layout = device.create_pipeline_layout(&desc);
+        wgpu::RenderPipelineBuilder::from_layout(&layout, &self.vs_mod)
+            .fragment_shader(fs_mod)
+            .color_format(Frame::TEXTURE_FORMAT)
+            .color_blend(color_blend)
+            .alpha_blend(alpha_blend)
+            .add_vertex_buffer::<Vertex>(&wgpu::vertex_attr_array![0 => Float2])
+            .primitive_topology(wgpu::PrimitiveTopology::TriangleStrip)
+            .index_format(wgpu::IndexFormat::Uint16)
+            .build(device)
+    }
+
+    // Encode a single full-screen pass sampling `src` and writing to `dst` via the given pipeline.
+    //
+    // The `uniforms` must match the layout of the fragment shader's uniform block. The destination
+    // is loaded rather than cleared so that the pipeline may blend onto it.
+    pub(crate) fn encode_pass<U>(
+        &self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        pipeline: &wgpu::RenderPipeline,
+        src: &wgpu::TextureView,
+        dst: &wgpu::TextureView,
+        uniforms: &U,
+    ) where
+        U: Copy,
+    {
+        let uniforms_bytes = unsafe { wgpu::bytes::from(uniforms) };
+        let uniform_buffer =
+            device.create_buffer_with_data(uniforms_bytes, wgpu::BufferUsage::UNIFORM);
+        let bind_group = wgpu::BindGroupBuilder::new()
+            .texture_view(src)
+            .sampler(&self.sampler)
+            .buffer::<U>(&uniform_buffer, 0..1)
+            .build(device, &self.bind_group_layout);
+        let mut render_pass = wgpu::RenderPassBuilder::new()
+            .color_attachment(dst, |color| color.load_op(wgpu::LoadOp::Load))
+            .begin(encoder);
+        render_pass.set_pipeline(pipeline);
+        render_pass.set_vertex_buffer(0, &self.vertex_buffer, 0, 0);
+        render_pass.set_bind_group(0, &bind_group, &[]);
+        render_pass.draw(0..VERTICES.len() as u32, 0..1);
+    }
+}
+
+const VERTICES: [Vertex; 4] = [
+    Vertex {
+        position: [-1.0, 1.0],
+    },
+    Vertex {
+        position: [-1.0, -1.0],
+    },
+    Vertex {
+        position: [1.0, 1.0],
+    },
+    Vertex {
+        position: [1.0, -1.0],
+    },
+];
@@ -1,15 +1,34 @@
 //! Items related to the **Frame** type, describing a single frame of graphics for a single window.
 
 use crate::color::IntoLinSrgba;
+use crate::draw;
+use crate::geom;
 use crate::wgpu;
+use crate::window;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::future::Future;
 use std::ops;
 use std::path::PathBuf;
+#[cfg(feature = "clipboard")]
+use std::sync::atomic::{self, AtomicBool};
 use std::sync::Mutex;
 use std::time::Duration;
 
+pub mod bloom;
+#[cfg(feature = "clipboard")]
+mod clipboard;
+mod feedback;
+mod fullscreen;
+pub mod post_effects;
 pub mod raw;
+pub mod video;
 
+pub use self::bloom::BloomConfig;
+pub(crate) use self::feedback::Feedback;
+pub use self::post_effects::{PostEffect, PostEffectBuilder};
 pub use self::raw::RawFrame;
+pub use self::video::VideoRecorder;
 
 /// A **Frame** to which the user can draw graphics before it is presented to the display.
 ///
@@ -23,6 +42,16 @@ pub struct Frame<'swap_chain> {
     raw_frame: RawFrame<'swap_chain>,
     render_data: &'swap_chain RenderData,
     capture_data: &'swap_chain CaptureData,
+    // Draws targeting textures, rendered prior to the frame's commands upon submission.
+    texture_draws: RefCell<Vec<(wgpu::Texture, draw::Draw)>>,
+    // Bloom effects applied to the frame's texture upon submission, in order.
+    blooms: RefCell<Vec<BloomConfig>>,
+    // Post effects applied to the frame's texture upon submission, following any bloom.
+    post_effects: RefCell<Vec<PostEffect>>,
+    // The window's cursor image and the mouse position at which it is drawn upon submission.
+    cursor_image: RefCell<Option<(window::CursorImage, geom::Point2)>>,
+    // Frames fed to video recorders, captured upon submission.
+    video_frames: RefCell<Vec<video::QueuedFrame>>,
 }
 
 /// Data specific to the intermediary textures.
@@ -33,6 +62,12 @@ pub struct RenderData {
     size: [u32; 2],
     // For writing the intermediary linear sRGBA texture to the swap chain texture.
     texture_reshaper: wgpu::TextureReshaper,
+    // Renderers used for draws targeting textures, one per texture format and sample count.
+    texture_draw_renderers: Mutex<HashMap<(wgpu::TextureFormat, u32), draw::Renderer>>,
+    // The bloom pipeline, created upon the first frame to which bloom is applied.
+    bloom: Mutex<Option<bloom::Bloom>>,
+    // The post effects pipeline, created upon the first frame to which effects are applied.
+    post_effects: Mutex<Option<post_effects::PostEffects>>,
 }
 
 /// Data related to the capturing of a frame.
@@ -40,6 +75,12 @@ pub struct RenderData {
 pub(crate) struct CaptureData {
     // If `Some`, indicates a path to which the current frame should be written.
     pub(crate) next_frame_path: Mutex<Option<PathBuf>>,
+    // If `true`, indicates that the current frame should be copied to the clipboard.
+    #[cfg(feature = "clipboard")]
+    pub(crate) next_frame_to_clipboard: AtomicBool,
+    // The window's clipboard thread to which captured frames are sent.
+    #[cfg(feature = "clipboard")]
+    clipboard: clipboard::Clipboard,
     // The `TextureCapturer` used to capture the frame.
     pub(crate) texture_capturer: wgpu::TextureCapturer,
 }
@@ -64,6 +105,8 @@ impl<'swap_chain> Frame<'swap_chain> {
     /// The default number of multisample anti-aliasing samples used if the window with which the
     /// `Frame` is associated supports it.
     pub const DEFAULT_MSAA_SAMPLES: u32 = 4;
+    /// The set of MSAA sample counts that may be specified via `window::Builder::msaa_samples`.
+    pub const SUPPORTED_MSAA_SAMPLES: &'static [u32] = &[1, 2, 4, 8];
     /// The texture format used by the intermediary linear sRGBA image.
     ///
     /// We use a high bit depth format in order to retain as much information as possible when
@@ -74,14 +117,25 @@ impl<'swap_chain> Frame<'swap_chain> {
 
     // Initialise a new empty frame ready for "drawing".
     pub(crate) fn new_empty(
-        raw_frame: RawFrame<'swap_chain>,
+        mut raw_frame: RawFrame<'swap_chain>,
         render_data: &'swap_chain RenderData,
         capture_data: &'swap_chain CaptureData,
     ) -> Self {
+        raw_frame.set_render_data(render_data);
+        let texture_draws = Default::default();
+        let blooms = Default::default();
+        let post_effects = Default::default();
+        let cursor_image = Default::default();
+        let video_frames = Default::default();
         Frame {
             raw_frame,
             render_data,
             capture_data,
+            texture_draws,
+            blooms,
+            post_effects,
+            cursor_image,
+            video_frames,
         }
     }
 
@@ -92,32 +146,60 @@ impl<'swap_chain> Frame<'swap_chain> {
             ref capture_data,
             ref render_data,
             ref mut raw_frame,
+            ref texture_draws,
+            ref blooms,
+            ref post_effects,
+            ref cursor_image,
+            ref video_frames,
         } = *self;
 
+        // Render any draws targeting textures before the frame's own commands.
+        let texture_draws = std::mem::replace(&mut *texture_draws.borrow_mut(), vec![]);
+        render_data.submit_texture_draws(raw_frame, texture_draws);
+
         // Resolve the MSAA if necessary.
-        if let Some((_, ref msaa_texture_view)) = render_data.intermediary_lin_srgba.msaa_texture {
+        render_data.encode_msaa_resolve(raw_frame);
+
+        // Apply any bloom to the resolved texture.
+        let blooms = std::mem::replace(&mut *blooms.borrow_mut(), vec![]);
+        render_data.encode_blooms(raw_frame, &blooms);
+
+        // Apply any post effects.
+        let post_effects = std::mem::replace(&mut *post_effects.borrow_mut(), vec![]);
+        render_data.encode_post_effects(raw_frame, &post_effects);
+
+        // Check to see if the user specified capturing the frame.
+        let path = match capture_data.next_frame_path.lock() {
+            Ok(mut guard) => guard.take(),
+            Err(_) => None,
+        };
+        #[cfg(feature = "clipboard")]
+        let clipboard = match capture_data
+            .next_frame_to_clipboard
+            .swap(false, atomic::Ordering::SeqCst)
+        {
+            true => Some(capture_data.clipboard.sender()),
+            false => None,
+        };
+        #[cfg(not(feature = "clipboard"))]
+        let clipboard: Option<std::sync::mpsc::Sender<image::RgbaImage>> = None;
+        let mut snapshot_capture = None;
+        if path.is_some() || clipboard.is_some() {
+            let device = raw_frame.device_queue_pair().device();
             let mut encoder = raw_frame.command_encoder();
-            wgpu::resolve_texture(
-                msaa_texture_view,
-                &render_data.intermediary_lin_srgba.texture_view,
+            let snapshot = capture_data.texture_capturer.capture(
+                device,
                 &mut *encoder,
+                &render_data.intermediary_lin_srgba.texture,
             );
+            snapshot_capture = Some((path, clipboard, snapshot));
         }
 
-        // Check to see if the user specified capturing the frame.
-        let mut snapshot_capture = None;
-        if let Ok(mut guard) = capture_data.next_frame_path.lock() {
-            if let Some(path) = guard.take() {
-                let device = raw_frame.device_queue_pair().device();
-                let mut encoder = raw_frame.command_encoder();
-                let snapshot = capture_data.texture_capturer.capture(
-                    device,
-                    &mut *encoder,
-                    &render_data.intermediary_lin_srgba.texture,
-                );
-                snapshot_capture = Some((path, snapshot));
-            }
-        }
+        // Capture any frames fed to video recorders.
+        let video_frames: Vec<_> = std::mem::replace(&mut *video_frames.borrow_mut(), vec![])
+            .into_iter()
+            .map(|queued| queued.encode(raw_frame, &render_data.intermediary_lin_srgba.texture))
+            .collect();
 
         // Convert the linear sRGBA image to the swapchain image.
         //
@@ -128,25 +210,50 @@ impl<'swap_chain> Frame<'swap_chain> {
             render_data
                 .texture_reshaper
                 .encode_render_pass(raw_frame.swap_chain_texture(), &mut *encoder);
+            // Write the same image to the window's feedback texture for reading next frame.
+            if let Some(feedback) = raw_frame.feedback_target_view() {
+                render_data
+                    .texture_reshaper
+                    .encode_render_pass(feedback, &mut *encoder);
+            }
+        }
+
+        // Draw the cursor image onto the swapchain image alone so that it does not appear in
+        // captured frames or the feedback texture.
+        if let Some((ref cursor, position)) = *cursor_image.borrow() {
+            render_data.encode_cursor_image(raw_frame, cursor, position);
         }
 
         // Submit all commands on the device queue.
         raw_frame.submit_inner();
 
+        // Read the frames fed to video recorders now that their capture has been submitted.
+        for captured in video_frames {
+            captured.read();
+        }
+
         // If the user did specify capturing the frame, submit the asynchronous read.
-        if let Some((path, snapshot)) = snapshot_capture {
+        if let Some((path, clipboard, snapshot)) = snapshot_capture {
             let result = snapshot.read(move |result| match result {
                 // TODO: Log errors, don't print to stderr.
                 Err(e) => eprintln!("failed to async read captured frame: {:?}", e),
                 Ok(image) => {
                     let image = image.to_owned();
-                    if let Err(e) = image.save(&path) {
-                        // TODO: Log errors, don't print to stderr.
-                        eprintln!(
-                            "failed to save captured frame to \"{}\": {}",
-                            path.display(),
-                            e
-                        );
+                    if let Some(path) = path {
+                        if let Err(e) = image.save(&path) {
+                            // TODO: Log errors, don't print to stderr.
+                            eprintln!(
+                                "failed to save captured frame to \"{}\": {}",
+                                path.display(),
+                                e
+                            );
+                        }
+                    }
+                    if let Some(clipboard) = clipboard {
+                        if clipboard.send(image).is_err() {
+                            // TODO: Log errors, don't print to stderr.
+                            eprintln!("failed to copy captured frame to the clipboard");
+                        }
                     }
                 }
             });
@@ -195,6 +302,17 @@ impl<'swap_chain> Frame<'swap_chain> {
             .unwrap_or(&self.render_data.intermediary_lin_srgba.texture_view)
     }
 
+    /// The multisampled texture in the case that MSAA is enabled.
+    ///
+    /// This is the texture returned by `texture` when the number of MSAA samples is greater than
+    /// `1`. Advanced users may use this to render directly to the multisampled surface within
+    /// their own render passes, using `resolve_target` as the resolve attachment if necessary.
+    ///
+    /// Returns `None` if MSAA is disabled.
+    pub fn msaa_texture(&self) -> Option<&wgpu::Texture> {
+        self.raw_frame.msaa_texture()
+    }
+
     /// Returns the resolve target texture in the case that MSAA is enabled.
     pub fn resolve_target(&self) -> Option<&wgpu::TextureView> {
         if self.render_data.msaa_samples <= 1 {
@@ -257,6 +375,99 @@ impl<'swap_chain> Frame<'swap_chain> {
         wgpu::clear_texture(self.texture_view(), color, &mut *self.command_encoder())
     }
 
+    /// Read the current contents of the frame back to the CPU as a non-linear sRGBA8 image.
+    ///
+    /// This may be called from within `view` after drawing, e.g. `draw.to_frame(app, &frame)`.
+    /// All commands encoded so far will be submitted so that the image reflects everything drawn
+    /// up to this point. Drawing may continue afterwards.
+    ///
+    /// This blocks until the GPU has finished and the image has been read. If you only wish to
+    /// save frames to disk, `Window::capture_frame` avoids blocking by reading frames on a thread
+    /// pool.
+    pub fn capture_to_image(&self) -> Result<image::DynamicImage, wgpu::BufferAsyncErr> {
+        self.raw_frame.capture_to_image()
+    }
+
+    /// The same as `capture_to_image`, but returns a future that resolves to the image once the
+    /// GPU has finished.
+    ///
+    /// Note: The future will not resolve until the device is polled.
+    pub fn capture_to_image_async(
+        &self,
+    ) -> impl Future<Output = Result<image::DynamicImage, wgpu::BufferAsyncErr>> {
+        self.raw_frame.capture_to_image_async()
+    }
+
+    /// Produce a **Draw** whose commands are rendered to the given texture rather than the frame.
+    ///
+    /// Upon submission of the frame, all texture draws are rendered in the order in which they
+    /// were produced and before any of the frame's own commands. This allows for using the
+    /// texture within the frame, e.g. for feedback effects or blur passes.
+    ///
+    /// The texture must have the `OUTPUT_ATTACHMENT` usage. See `App::create_render_texture`.
+    pub fn draw_to_texture(&self, texture: &wgpu::Texture) -> draw::Draw {
+        let draw = draw::Draw::new();
+        self.push_texture_draw(texture.clone(), draw.clone());
+        draw
+    }
+
+    // Queue the given draw to be rendered to the texture upon submission.
+    pub(crate) fn push_texture_draw(&self, texture: wgpu::Texture, draw: draw::Draw) {
+        self.texture_draws.borrow_mut().push((texture, draw));
+    }
+
+    /// Apply a bloom effect to the frame, causing the parts of the frame brighter than `threshold`
+    /// to bleed light into their surroundings.
+    ///
+    /// `intensity` scales the light added back onto the frame, while `radius` describes roughly
+    /// how far in pixels the light spreads. See `apply_bloom_config` for more control.
+    pub fn apply_bloom(&self, threshold: f32, intensity: f32, radius: f32) {
+        self.apply_bloom_config(BloomConfig::new(threshold, intensity, radius));
+    }
+
+    /// Apply a bloom effect described by the given **BloomConfig** to the frame.
+    ///
+    /// Bloom is applied upon submission of the frame, after MSAA is resolved and before the frame
+    /// is captured or written to the swap chain, so it applies to everything drawn this frame
+    /// regardless of when this method is called. If called multiple times, each bloom is applied
+    /// in turn. The pipeline is created upon the first call and reused for following frames.
+    ///
+    /// Note that when MSAA is disabled the bloomed image is the same texture that is drawn to, so
+    /// bloom will accumulate across frames unless the frame is cleared.
+    pub fn apply_bloom_config(&self, config: BloomConfig) {
+        self.blooms.borrow_mut().push(config);
+    }
+
+    /// Begin a chain of post-processing effects to apply to the frame.
+    ///
+    /// Effects are applied once the chain is completed via `PostEffectBuilder::apply`, e.g.
+    ///
+    /// ```ignore
+    /// frame
+    ///     .post_effects()
+    ///     .chromatic_aberration(2.0)
+    ///     .vignette(0.8, 0.5)
+    ///     .apply(&frame);
+    /// ```
+    pub fn post_effects(&self) -> PostEffectBuilder {
+        PostEffectBuilder::new()
+    }
+
+    // Queue the given effects to be applied upon submission.
+    pub(crate) fn push_post_effects(&self, effects: Vec<PostEffect>) {
+        self.post_effects.borrow_mut().extend(effects);
+    }
+
+    // Capture the frame for a video recorder upon submission.
+    pub(crate) fn push_video_frame(&self, frame: video::QueuedFrame) {
+        self.video_frames.borrow_mut().push(frame);
+    }
+
+    // Draw the given cursor image at the mouse position upon submission.
+    pub(crate) fn set_cursor_image(&self, cursor: window::CursorImage, position: geom::Point2) {
+        *self.cursor_image.borrow_mut() = Some((cursor, position));
+    }
+
     /// Submit the frame to the GPU!
     ///
     /// Note that you do not need to call this manually as submission will occur automatically when
@@ -265,7 +476,10 @@ impl<'swap_chain> Frame<'swap_chain> {
     /// Before submission, the frame does the following:
     ///
     /// - If the frame's intermediary linear sRGBA texture is multisampled, resolve it.
-    /// - Write the intermediary linear sRGBA image to the swap chain texture.
+    /// - Apply any bloom effects, followed by any post effects.
+    /// - Capture the frame for any video recorders to which it was fed.
+    /// - Write the intermediary linear sRGBA image to the swap chain texture, along with the
+    ///   window's feedback texture if enabled.
     ///
     /// It can sometimes be useful to submit the **Frame** before `view` completes in order to read
     /// the frame's texture back to the CPU (e.g. for screen shots, recordings, etc).
@@ -278,6 +492,10 @@ impl CaptureData {
     pub(crate) fn new(max_jobs: u32, timeout: Option<Duration>) -> Self {
         CaptureData {
             next_frame_path: Default::default(),
+            #[cfg(feature = "clipboard")]
+            next_frame_to_clipboard: Default::default(),
+            #[cfg(feature = "clipboard")]
+            clipboard: Default::default(),
             texture_capturer: wgpu::TextureCapturer::new(Some(max_jobs), timeout),
         }
     }
@@ -308,11 +526,132 @@ impl RenderData {
             swap_chain_sample_count,
             swap_chain_format,
         );
+        let texture_draw_renderers = Default::default();
+        let bloom = Default::default();
+        let post_effects = Default::default();
         RenderData {
             intermediary_lin_srgba,
             texture_reshaper,
             size: swap_chain_dims,
             msaa_samples,
+            texture_draw_renderers,
+            bloom,
+            post_effects,
+        }
+    }
+
+    // Render the given texture draws and submit them ahead of the frame's command encoder.
+    fn submit_texture_draws(&self, raw_frame: &RawFrame, draws: Vec<(wgpu::Texture, draw::Draw)>) {
+        if draws.is_empty() {
+            return;
+        }
+        let device_queue_pair = raw_frame.device_queue_pair();
+        let device = device_queue_pair.device();
+        let ce_desc = wgpu::CommandEncoderDescriptor {
+            label: Some("nannou_texture_draws"),
+        };
+        let mut encoder = device.create_command_encoder(&ce_desc);
+        let mut renderers = self
+            .texture_draw_renderers
+            .lock()
+            .expect("failed to acquire texture draw renderers lock");
+        for (texture, draw) in draws {
+            encode_texture_draw(
+                device_queue_pair,
+                &mut encoder,
+                &mut renderers,
+                &texture,
+                &draw,
+            );
+        }
+        device_queue_pair.queue().submit(&[encoder.finish()]);
+    }
+
+    // Encode the passes for each of the given blooms, creating the bloom pipeline if necessary.
+    fn encode_blooms(&self, raw_frame: &RawFrame, blooms: &[BloomConfig]) {
+        if blooms.is_empty() {
+            return;
+        }
+        let device = raw_frame.device_queue_pair().device();
+        let mut guard = self.bloom.lock().expect("failed to acquire bloom lock");
+        let bloom = guard.get_or_insert_with(|| bloom::Bloom::new(device));
+        let mut encoder = raw_frame.command_encoder();
+        for config in blooms {
+            bloom.encode(
+                device,
+                &mut *encoder,
+                &self.intermediary_lin_srgba.texture,
+                &self.intermediary_lin_srgba.texture_view,
+                config,
+            );
+        }
+    }
+
+    // Encode the passes for the given post effects, creating the pipeline if necessary.
+    fn encode_post_effects(&self, raw_frame: &RawFrame, effects: &[PostEffect]) {
+        if effects.is_empty() {
+            return;
+        }
+        let device = raw_frame.device_queue_pair().device();
+        let mut guard = self
+            .post_effects
+            .lock()
+            .expect("failed to acquire post effects lock");
+        let post_effects = guard.get_or_insert_with(|| post_effects::PostEffects::new(device));
+        post_effects.encode(
+            device,
+            &mut *raw_frame.command_encoder(),
+            &self.intermediary_lin_srgba.texture,
+            &self.intermediary_lin_srgba.texture_view,
+            effects,
+            raw_frame.nth(),
+        );
+    }
+
+    // Encode the commands drawing the cursor image to the swapchain image with its hotspot at the
+    // given position in points.
+    fn encode_cursor_image(
+        &self,
+        raw_frame: &RawFrame,
+        cursor: &window::CursorImage,
+        position: geom::Point2,
+    ) {
+        // Texture draws are rendered in pixels.
+        let scale_factor = raw_frame.scale_factor();
+        let [w, h] = cursor.texture.size();
+        let (w, h) = (w as f32, h as f32);
+        let (hx, hy) = (cursor.hotspot.0 as f32, cursor.hotspot.1 as f32);
+        let x = position.x * scale_factor + w * 0.5 - hx;
+        let y = position.y * scale_factor - h * 0.5 + hy;
+        let draw = draw::Draw::new();
+        draw.texture(&cursor.texture).x_y(x, y).w_h(w, h);
+        let device_queue_pair = raw_frame.device_queue_pair();
+        let device = device_queue_pair.device();
+        let format = raw_frame.texture_format();
+        let size = self.size;
+        let mut renderers = self
+            .texture_draw_renderers
+            .lock()
+            .expect("failed to acquire texture draw renderers lock");
+        let renderer = renderers.entry((format, 1)).or_insert_with(|| {
+            draw::RendererBuilder::new()
+                .buffer_pool(device_queue_pair.buffer_pool().clone())
+                .build(device, size, 1.0, 1, format)
+        });
+        let mut encoder = raw_frame.command_encoder();
+        let view = raw_frame.swap_chain_texture();
+        renderer.encode_render_pass(device, &mut *encoder, &draw, 1.0, size, view, None);
+    }
+
+    // Encode a command to resolve the MSAA texture to the non-multisampled texture if necessary.
+    fn encode_msaa_resolve(&self, raw_frame: &RawFrame) {
+        if let Some((_, ref msaa_texture_view)) = self.intermediary_lin_srgba.msaa_texture {
+            let mut encoder = raw_frame.command_encoder();
+            wgpu::resolve_texture(
+                msaa_texture_view,
+                &self.intermediary_lin_srgba.texture_view,
+                &mut *encoder,
+            );
         }
     }
 }
@@ -325,6 +664,33 @@ impl<'swap_chain> Drop for Frame<'swap_chain> {
     }
 }
 
+// Encode the commands for rendering the draw to the texture, first rendering any of its children.
+fn encode_texture_draw(
+    device_queue_pair: &wgpu::DeviceQueuePair,
+    encoder: &mut wgpu::CommandEncoder,
+    renderers: &mut HashMap<(wgpu::TextureFormat, u32), draw::Renderer>,
+    texture: &wgpu::Texture,
+    draw: &draw::Draw,
+) {
+    for (child_texture, child) in draw.drain_texture_draws() {
+        encode_texture_draw(
+            device_queue_pair,
+            encoder,
+            renderers,
+            &child_texture,
+            &child,
+        );
+    }
+    let device = device_queue_pair.device();
+    let key = (texture.format(), texture.sample_count());
+    let renderer = renderers.entry(key).or_insert_with(|| {
+        draw::RendererBuilder::new()
+            .buffer_pool(device_queue_pair.buffer_pool().clone())
+            .build_from_texture_descriptor(device, texture.descriptor())
+    });
+    renderer.render_to_texture(device, encoder, draw, texture);
+}
+
 fn create_lin_srgba_msaa_texture(
     device: &wgpu::Device,
     swap_chain_dims: [u32; 2],
@@ -342,7 +708,11 @@ fn create_lin_srgba_texture(device: &wgpu::Device, swap_chain_dims: [u32; 2]) ->
     wgpu::TextureBuilder::new()
         .size(swap_chain_dims)
         .format(Frame::TEXTURE_FORMAT)
-        .usage(wgpu::TextureUsage::OUTPUT_ATTACHMENT | wgpu::TextureUsage::SAMPLED)
+        .usage(
+            wgpu::TextureUsage::OUTPUT_ATTACHMENT
+                | wgpu::TextureUsage::SAMPLED
+                | wgpu::TextureUsage::COPY_DST,
+        )
         .build(device)
 }
 
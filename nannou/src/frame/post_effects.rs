@@ -0,0 +1,249 @@
+//! Stylised post-processing effects that may be applied to a **Frame** via `Frame::post_effects`.
+//!
+//! Each effect is a stage of a single fragment shader. Effects are applied in the order in which
+//! they are chained, and consecutive effects whose stages appear in the shader's order
+//! (chromatic aberration, film grain, vignette, scanlines) are fused into a single render pass.
+//! A new pass only begins when an effect's stage would otherwise run out of order, e.g. a vignette
+//! followed by film grain, or the same effect applied twice.
+
+use crate::frame::fullscreen::FullscreenQuad;
+use crate::frame::Frame;
+use crate::wgpu;
+
+/// A single post-processing effect.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PostEffect {
+    /// Separate the red and blue channels by up to `offset_px` pixels towards the frame's edges.
+    ChromaticAberration { offset_px: f32 },
+    /// Add noise of the given strength to the luminance of each pixel. If `animated`, the noise
+    /// changes every frame.
+    FilmGrain { strength: f32, animated: bool },
+    /// Darken the frame beyond `radius` from the centre, fading over `softness`. Both are
+    /// normalised such that `1.0` is the distance from the centre to the middle of each edge.
+    Vignette { radius: f32, softness: f32 },
+    /// Darken `frequency` horizontal lines spanning the height of the frame by `alpha`.
+    Scanlines { frequency: f32, alpha: f32 },
+}
+
+/// Chains together a sequence of **PostEffect**s to be applied to a **Frame**.
+///
+/// Produced via `Frame::post_effects`. The effects are applied to the frame via `apply`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PostEffectBuilder {
+    effects: Vec<PostEffect>,
+}
+
+// The cached pipeline and intermediary texture used to apply post effects to a window's frames.
+#[derive(Debug)]
+pub(crate) struct PostEffects {
+    quad: FullscreenQuad,
+    _fs_mod: wgpu::ShaderModule,
+    render_pipeline: wgpu::RenderPipeline,
+    // The texture to and from which alternating passes are written, matching the frame's size.
+    scratch: Option<(wgpu::Texture, wgpu::TextureView)>,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Default)]
+struct Uniforms {
+    resolution: [f32; 2],
+    aberration: f32,
+    grain: f32,
+    seed: f32,
+    vignette_radius: f32,
+    vignette_softness: f32,
+    vignette_strength: f32,
+    scanline_frequency: f32,
+    scanline_alpha: f32,
+}
+
+impl PostEffect {
+    // The index of the effect's stage within the fragment shader.
+    fn stage(&self) -> usize {
+        match *self {
+            PostEffect::ChromaticAberration { .. } => 0,
+            PostEffect::FilmGrain { .. } => 1,
+            PostEffect::Vignette { .. } => 2,
+            PostEffect::Scanlines { .. } => 3,
+        }
+    }
+}
+
+impl PostEffectBuilder {
+    /// Begin with an empty chain of effects.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Separate the red and blue channels by up to `offset_px` pixels towards the frame's edges.
+    pub fn chromatic_aberration(self, offset_px: f32) -> Self {
+        self.effect(PostEffect::ChromaticAberration { offset_px })
+    }
+
+    /// Add noise of the given strength to the luminance of each pixel.
+    ///
+    /// If `animated`, the noise changes every frame. Otherwise, it is fixed to the screen.
+    pub fn film_grain(self, strength: f32, animated: bool) -> Self {
+        self.effect(PostEffect::FilmGrain { strength, animated })
+    }
+
+    /// Darken the frame beyond `radius` from the centre, fading over `softness`.
+    ///
+    /// Both are normalised such that `1.0` is the distance from the centre of the frame to the
+    /// middle of each edge.
+    pub fn vignette(self, radius: f32, softness: f32) -> Self {
+        self.effect(PostEffect::Vignette { radius, softness })
+    }
+
+    /// Darken `frequency` horizontal lines spanning the height of the frame by `alpha`.
+    pub fn scanlines(self, frequency: f32, alpha: f32) -> Self {
+        self.effect(PostEffect::Scanlines { frequency, alpha })
+    }
+
+    /// Append the given effect to the chain.
+    pub fn effect(mut self, effect: PostEffect) -> Self {
+        self.effects.push(effect);
+        self
+    }
+
+    /// The chain of effects in the order in which they are applied.
+    pub fn effects(&self) -> &[PostEffect] {
+        &self.effects
+    }
+
+    /// The number of render passes required to apply the chain of effects once fused.
+    pub fn num_passes(&self) -> usize {
+        passes(&self.effects).len()
+    }
+
+    /// Apply the chain of effects to the given frame.
+    ///
+    /// The effects are applied upon submission of the frame, after any bloom and before the frame
+    /// is captured or written to the swap chain.
+    pub fn apply(self, frame: &Frame) {
+        frame.push_post_effects(self.effects);
+    }
+}
+
+impl PostEffects {
+    // Create the pipeline used to apply post effects to a frame's texture.
+    pub(crate) fn new(device: &wgpu::Device) -> Self {
+        let quad = FullscreenQuad::new(device);
+        let fs_mod =
+            wgpu::shader_from_spirv_bytes(device, include_bytes!("shaders/frag_post_effects.spv"));
+        let replace = wgpu::BlendDescriptor::REPLACE;
+        let render_pipeline = quad.render_pipeline(device, &fs_mod, replace, replace);
+        PostEffects {
+            quad,
+            _fs_mod: fs_mod,
+            render_pipeline,
+            scratch: None,
+        }
+    }
+
+    // Encode the passes applying the effects to the given non-multisampled texture in place.
+    //
+    // Passes alternate between the texture and the scratch texture. If the final pass writes to
+    // the scratch texture, the result is copied back.
+    pub(crate) fn encode(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        texture: &wgpu::Texture,
+        texture_view: &wgpu::TextureView,
+        effects: &[PostEffect],
+        nth_frame: u64,
+    ) {
+        let passes = passes(effects);
+        if passes.is_empty() {
+            return;
+        }
+        let size = texture.size();
+        let recreate = self
+            .scratch
+            .as_ref()
+            .map(|(t, _)| t.size() != size)
+            .unwrap_or(true);
+        if recreate {
+            let scratch = wgpu::TextureBuilder::new()
+                .size(size)
+                .format(Frame::TEXTURE_FORMAT)
+                .usage(wgpu::TextureUsage::SAMPLED | wgpu::TextureUsage::COPY_SRC)
+                .as_render_target()
+                .label("nannou_post_effects")
+                .build(device);
+            let scratch_view = scratch.view().build();
+            self.scratch = Some((scratch, scratch_view));
+        }
+        let (scratch, scratch_view) = self.scratch.as_ref().expect("no scratch texture");
+
+        for (i, pass) in passes.iter().enumerate() {
+            let (src, dst) = match i % 2 {
+                0 => (texture_view, scratch_view),
+                _ => (scratch_view, texture_view),
+            };
+            let uniforms = uniforms(pass, size, nth_frame);
+            let pipeline = &self.render_pipeline;
+            self.quad
+                .encode_pass(device, encoder, pipeline, src, dst, &uniforms);
+        }
+
+        if passes.len() % 2 == 1 {
+            encoder.copy_texture_to_texture(
+                scratch.default_copy_view(),
+                texture.default_copy_view(),
+                texture.extent(),
+            );
+        }
+    }
+}
+
+// Split the effects into passes, each containing effects whose stages are in shader order.
+fn passes(effects: &[PostEffect]) -> Vec<Vec<PostEffect>> {
+    let mut passes: Vec<Vec<PostEffect>> = vec![];
+    for &effect in effects {
+        let fused = passes
+            .last()
+            .and_then(|pass| pass.last())
+            .map(|last| last.stage() < effect.stage())
+            .unwrap_or(false);
+        match fused {
+            true => passes.last_mut().expect("no pass").push(effect),
+            false => passes.push(vec![effect]),
+        }
+    }
+    passes
+}
+
+// The uniforms enabling each stage of the given pass.
+fn uniforms(pass: &[PostEffect], [w, h]: [u32; 2], nth_frame: u64) -> Uniforms {
+    let mut uniforms = Uniforms {
+        resolution: [w as f32, h as f32],
+        ..Default::default()
+    };
+    for effect in pass {
+        match *effect {
+            PostEffect::ChromaticAberration { offset_px } => {
+                uniforms.aberration = offset_px;
+            }
+            PostEffect::FilmGrain { strength, animated } => {
+                uniforms.grain = strength;
+                // Keep the seed small to retain precision within the shader's noise function.
+                uniforms.seed = match animated {
+                    true => (nth_frame % 997) as f32,
+                    false => 0.0,
+                };
+            }
+            PostEffect::Vignette { radius, softness } => {
+                uniforms.vignette_radius = radius;
+                uniforms.vignette_softness = softness;
+                uniforms.vignette_strength = 1.0;
+            }
+            PostEffect::Scanlines { frequency, alpha } => {
+                uniforms.scanline_frequency = frequency;
+                uniforms.scanline_alpha = alpha;
+            }
+        }
+    }
+    uniforms
+}
@@ -1,10 +1,14 @@
 //! The lower-level "raw" frame type allowing to draw directly to the window's swap chain image.
 
+use crate::frame::RenderData;
 use crate::geom;
 use crate::wgpu;
 use crate::window;
 use std::cell::{RefCell, RefMut};
+use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Instant;
 
 /// Allows the user to draw a single **RawFrame** to the surface of a window.
 ///
@@ -30,10 +34,20 @@ pub struct RawFrame<'swap_chain> {
     command_encoder: Option<RefCell<wgpu::CommandEncoder>>,
     window_id: window::Id,
     nth: u64,
+    // The instant at which this frame began and at which the window's first frame began.
+    timestamp: Instant,
+    first_timestamp: Instant,
     swap_chain_texture: &'swap_chain wgpu::TextureViewHandle,
     device_queue_pair: Arc<wgpu::DeviceQueuePair>,
     texture_format: wgpu::TextureFormat,
     window_rect: geom::Rect,
+    scale_factor: f32,
+    // Named intermediary textures owned by the frame. See `create_attachment`.
+    attachments: HashMap<String, (wgpu::Texture, wgpu::TextureView)>,
+    // Views to the previous and current frame's feedback textures, if enabled for the window.
+    feedback: Option<(wgpu::TextureView, wgpu::TextureView)>,
+    // The intermediary textures of the `Frame` wrapping this raw frame, if any.
+    render_data: Option<&'swap_chain RenderData>,
 }
 
 impl<'swap_chain> RawFrame<'swap_chain> {
@@ -42,27 +56,47 @@ impl<'swap_chain> RawFrame<'swap_chain> {
         device_queue_pair: Arc<wgpu::DeviceQueuePair>,
         window_id: window::Id,
         nth: u64,
+        timestamp: Instant,
+        first_timestamp: Instant,
         swap_chain_texture: &'swap_chain wgpu::TextureViewHandle,
         texture_format: wgpu::TextureFormat,
         window_rect: geom::Rect,
+        scale_factor: f32,
     ) -> Self {
-        let ce_desc = wgpu::CommandEncoderDescriptor {
-            label: Some("nannou_raw_frame"),
-        };
-        let command_encoder = device_queue_pair.device().create_command_encoder(&ce_desc);
+        let command_encoder = create_command_encoder(device_queue_pair.device());
         let command_encoder = Some(RefCell::new(command_encoder));
+        let attachments = Default::default();
         let frame = RawFrame {
             command_encoder,
             window_id,
             nth,
+            timestamp,
+            first_timestamp,
             swap_chain_texture,
             device_queue_pair,
             texture_format,
             window_rect,
+            scale_factor,
+            attachments,
+            feedback: None,
+            render_data: None,
         };
         frame
     }
 
+    // Provide the views to the previous and current frame's feedback textures.
+    pub(crate) fn set_feedback(
+        &mut self,
+        feedback: Option<(wgpu::TextureView, wgpu::TextureView)>,
+    ) {
+        self.feedback = feedback;
+    }
+
+    // Provide the intermediary textures of the `Frame` wrapping this raw frame.
+    pub(crate) fn set_render_data(&mut self, render_data: &'swap_chain RenderData) {
+        self.render_data = Some(render_data);
+    }
+
     // Submit the encoded commands to the queue of the device that was used to create the swap
     // chain texture.
     pub(crate) fn submit_inner(&mut self) {
@@ -76,6 +110,16 @@ impl<'swap_chain> RawFrame<'swap_chain> {
         queue.submit(&[command_buffer]);
     }
 
+    // Submit all commands encoded so far and replace the encoder with a fresh one, allowing the
+    // user to continue encoding commands for the remainder of the frame.
+    pub(crate) fn submit_pending(&self) {
+        let new_encoder = create_command_encoder(self.device_queue_pair.device());
+        let command_encoder = std::mem::replace(&mut *self.command_encoder(), new_encoder);
+        let command_buffer = command_encoder.finish();
+        let queue = self.device_queue_pair.queue();
+        queue.submit(&[command_buffer]);
+    }
+
     // Allow the `Frame` to check if the raw frame has already been submitted on drop.
     pub(crate) fn is_submitted(&self) -> bool {
         self.command_encoder.is_none()
@@ -103,6 +147,20 @@ impl<'swap_chain> RawFrame<'swap_chain> {
         self.window_rect
     }
 
+    /// The same as `rect`, but with dimensions in physical pixels.
+    ///
+    /// The returned **Rect** is equivalent to the result of calling **Window::rect_physical** on
+    /// the window associated with this **Frame**.
+    pub fn rect_physical(&self) -> geom::Rect {
+        let wh = self.window_rect.wh() * self.scale_factor;
+        geom::Rect::from_wh(wh)
+    }
+
+    /// The ratio of physical pixels to logical points for the associated window.
+    pub fn scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
+
     /// The `nth` frame for the associated window since the application started.
     ///
     /// E.g. the first frame yielded will return `0`, the second will return `1`, and so on.
@@ -110,11 +168,44 @@ impl<'swap_chain> RawFrame<'swap_chain> {
         self.nth
     }
 
+    /// The instant at which the frame began, i.e. when the window was ready for a new image.
+    ///
+    /// All calls within the same **view** function return the same instant, allowing the view to
+    /// be a pure function of the model and the frame.
+    pub fn timestamp(&self) -> Instant {
+        self.timestamp
+    }
+
+    /// The number of seconds between the start of the first frame for the associated window and
+    /// the start of this frame.
+    ///
+    /// The first frame yielded will return `0.0`.
+    pub fn elapsed_secs(&self) -> f64 {
+        self.timestamp
+            .saturating_duration_since(self.first_timestamp)
+            .as_secs_f64()
+    }
+
     /// The swap chain texture that will be the target for drawing this frame.
     pub fn swap_chain_texture(&self) -> &wgpu::TextureViewHandle {
         &self.swap_chain_texture
     }
 
+    /// The multisampled texture to which the **Frame** wrapping this raw frame draws in the case
+    /// that MSAA is enabled.
+    ///
+    /// The texture is resolved to the frame's intermediary texture before being written to the
+    /// swap chain texture, so advanced users may render directly to the multisampled surface
+    /// within their own render passes.
+    ///
+    /// Returns `None` if MSAA is disabled or if the window's `view` function draws to a
+    /// **RawFrame**.
+    pub fn msaa_texture(&self) -> Option<&'swap_chain wgpu::Texture> {
+        self.render_data
+            .and_then(|data| data.intermediary_lin_srgba.msaa_texture.as_ref())
+            .map(|(tex, _)| tex)
+    }
+
     /// The texture format of the frame's swap chain texture.
     pub fn texture_format(&self) -> wgpu::TextureFormat {
         self.texture_format
@@ -129,6 +220,253 @@ impl<'swap_chain> RawFrame<'swap_chain> {
         &self.device_queue_pair
     }
 
+    /// The pool of reusable buffers associated with the frame's device.
+    ///
+    /// This is useful for avoiding the creation of new buffers each frame, e.g. for uniform
+    /// uploads. See the **BufferPool** docs for details.
+    pub fn buffer_pool(&self) -> &wgpu::BufferPool {
+        self.device_queue_pair.buffer_pool()
+    }
+
+    /// A view to the window's feedback texture containing the image of the previous frame.
+    ///
+    /// Returns `None` unless feedback has been enabled for the window via `App::feedback_texture`.
+    /// The texture is transparent black until a frame has been written to it. Its format matches
+    /// that of the swap chain.
+    pub fn feedback_texture_view(&self) -> Option<&wgpu::TextureView> {
+        self.feedback.as_ref().map(|(previous, _)| previous)
+    }
+
+    /// A view to the window's feedback texture that will be read during the next frame.
+    ///
+    /// A **Frame** writes its image to this texture automatically upon submission. When drawing
+    /// to a **RawFrame** directly, the image to be read next frame should be rendered to this
+    /// texture manually.
+    pub fn feedback_target_view(&self) -> Option<&wgpu::TextureView> {
+        self.feedback.as_ref().map(|(_, current)| current)
+    }
+
+    /// Create a named intermediary texture owned by the frame and return a view to it.
+    ///
+    /// This is useful for multi-pass rendering (e.g. shadow maps, geometry buffers or
+    /// post-processing) without having to store textures within the model between frames.
+    ///
+    /// If an attachment with the same name and descriptor already exists within this frame, the
+    /// existing attachment is returned. If the descriptor differs, the attachment is replaced.
+    ///
+    /// All attachments are freed when the frame is dropped after submission.
+    pub fn create_attachment(
+        &mut self,
+        name: &str,
+        descriptor: wgpu::TextureDescriptor<'static>,
+    ) -> &wgpu::TextureView {
+        let device = self.device_queue_pair.device();
+        let requires_new = match self.attachments.get(name) {
+            Some((texture, _)) => !wgpu::texture_descriptor_eq(texture.descriptor(), &descriptor),
+            None => true,
+        };
+        if requires_new {
+            let handle = Arc::new(device.create_texture(&descriptor));
+            let texture = wgpu::Texture::from_handle_and_descriptor(handle, descriptor);
+            let view = texture.view().build();
+            self.attachments.insert(name.to_string(), (texture, view));
+        }
+        &self.attachments[name].1
+    }
+
+    /// The view to the attachment with the given name, if one was created via
+    /// `create_attachment`.
+    pub fn attachment(&self, name: &str) -> Option<&wgpu::TextureView> {
+        self.attachments.get(name).map(|(_, view)| view)
+    }
+
+    /// The texture of the attachment with the given name, if one was created via
+    /// `create_attachment`.
+    pub fn attachment_texture(&self, name: &str) -> Option<&wgpu::Texture> {
+        self.attachments.get(name).map(|(texture, _)| texture)
+    }
+
+    /// Begin a render pass on the frame's command encoder whose color attachment is the named
+    /// attachment.
+    ///
+    /// The `color_builder` may be used to specify the load and store ops, clear color and resolve
+    /// target of the color attachment, e.g. `|color| color.clear_color(wgpu::Color::BLACK)`.
+    ///
+    /// **Panics** if no attachment with the given name was created via `create_attachment`.
+    pub fn begin_render_pass_to<F>(
+        &mut self,
+        attachment: &str,
+        color_builder: F,
+    ) -> wgpu::RenderPass
+    where
+        F: for<'a> FnOnce(
+            wgpu::RenderPassColorAttachmentDescriptorBuilder<'a>,
+        ) -> wgpu::RenderPassColorAttachmentDescriptorBuilder<'a>,
+    {
+        let RawFrame {
+            ref mut command_encoder,
+            ref attachments,
+            ..
+        } = *self;
+        let (_, view) = attachments
+            .get(attachment)
+            .unwrap_or_else(|| panic!("no attachment named \"{}\"", attachment));
+        let encoder = command_encoder
+            .as_mut()
+            .expect("`RawFrame`'s command_encoder was `None`")
+            .get_mut();
+        wgpu::RenderPassBuilder::new()
+            .color_attachment(view, color_builder)
+            .begin(encoder)
+    }
+
+    /// Begin building a compute pass that will be encoded via the frame's command encoder.
+    ///
+    /// The returned **ComputePassBuilder** holds the lock to the command encoder until it is
+    /// dropped. See the **ComputePassBuilder** docs for details.
+    pub fn begin_compute_pass(&self) -> wgpu::ComputePassBuilder {
+        wgpu::ComputePassBuilder::new(&self.device_queue_pair, self.command_encoder())
+    }
+
+    /// Read the given texture back to the CPU as an image.
+    ///
+    /// The texture is converted to non-linear sRGBA8 before being copied to a staging buffer,
+    /// meaning textures of any color format (and any sample count) may be captured. All commands
+    /// encoded so far this frame will be submitted so that the texture's current contents are
+    /// read. The frame's command encoder remains usable afterwards.
+    ///
+    /// Note that wgpu does not allow reading back from the swap chain texture directly. In order to
+    /// capture what is drawn, render to a texture and pass it here, or use `capture_to_image`
+    /// which captures the intermediary linear sRGBA texture of a **Frame**.
+    ///
+    /// This blocks the current thread until the GPU has finished and the buffer has been mapped.
+    /// See `capture_texture_to_image_async` for a non-blocking alternative.
+    pub fn capture_texture_to_image(
+        &self,
+        texture: &wgpu::Texture,
+    ) -> Result<image::DynamicImage, wgpu::BufferAsyncErr> {
+        let future = self.capture_texture_to_image_async(texture);
+        self.device_queue_pair.device().poll(wgpu::Maintain::Wait);
+        futures::executor::block_on(future)
+    }
+
+    /// The same as `capture_texture_to_image`, but returns a future that resolves once the staging
+    /// buffer has been mapped.
+    ///
+    /// Note: The future will not resolve until the buffer is mapped and the device is polled.
+    pub fn capture_texture_to_image_async(
+        &self,
+        texture: &wgpu::Texture,
+    ) -> impl Future<Output = Result<image::DynamicImage, wgpu::BufferAsyncErr>> {
+        let device = self.device_queue_pair.device();
+        let [width, height] = texture.size();
+
+        // Convert the texture to non-linear sRGBA8 if necessary.
+        let requires_conversion = texture.format() != CAPTURE_FORMAT
+            || texture.sample_count() > 1
+            || !texture.usage().contains(wgpu::TextureUsage::COPY_SRC);
+        let converted = if requires_conversion {
+            let dst_texture = wgpu::TextureBuilder::new()
+                .size([width, height])
+                .format(CAPTURE_FORMAT)
+                .usage(wgpu::TextureUsage::OUTPUT_ATTACHMENT | wgpu::TextureUsage::COPY_SRC)
+                .build(device);
+            let src_view = texture.create_default_view();
+            let reshaper = wgpu::TextureReshaper::new(
+                device,
+                &src_view,
+                texture.sample_count(),
+                texture.component_type(),
+                1,
+                CAPTURE_FORMAT,
+            );
+            let dst_view = dst_texture.create_default_view();
+            reshaper.encode_render_pass(&dst_view, &mut *self.command_encoder());
+            Some(dst_texture)
+        } else {
+            None
+        };
+        let src_texture = converted.as_ref().unwrap_or(texture);
+
+        // Copy the texture to a staging buffer, padding each row as required by wgpu.
+        let bytes_per_row = width * CAPTURE_FORMAT_SIZE_BYTES;
+        let padded_bytes_per_row = padded_bytes_per_row(bytes_per_row);
+        let len_bytes = padded_bytes_per_row as wgpu::BufferAddress * height as wgpu::BufferAddress;
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("nannou_raw_frame_capture"),
+            size: len_bytes,
+            usage: wgpu::BufferUsage::COPY_DST | wgpu::BufferUsage::MAP_READ,
+        });
+        let buffer_copy_view = wgpu::BufferCopyView {
+            buffer: &buffer,
+            offset: 0,
+            bytes_per_row: padded_bytes_per_row,
+            rows_per_image: height,
+        };
+        self.command_encoder().copy_texture_to_buffer(
+            src_texture.default_copy_view(),
+            buffer_copy_view,
+            src_texture.extent(),
+        );
+
+        // Submit the commands so that the buffer may be mapped.
+        self.submit_pending();
+
+        let mapping = buffer.map_read(0, len_bytes);
+        async move {
+            let mapping = mapping.await?;
+            let bytes = mapping.as_slice();
+            let mut data = Vec::with_capacity((bytes_per_row * height) as usize);
+            for row in bytes.chunks(padded_bytes_per_row as usize) {
+                data.extend_from_slice(&row[..bytes_per_row as usize]);
+            }
+            // The buffer must outlive the mapping.
+            drop(mapping);
+            drop(buffer);
+            let image = image::ImageBuffer::from_raw(width, height, data)
+                .expect("image buffer dimensions did not match mapping");
+            Ok(image::DynamicImage::ImageRgba8(image))
+        }
+    }
+
+    /// Read everything drawn to the frame so far back to the CPU as an image.
+    ///
+    /// The frame's intermediary linear sRGBA texture is resolved if necessary and captured via
+    /// `capture_texture_to_image`, so the image is non-linear sRGBA8 regardless of the swap chain
+    /// format. Drawing may continue afterwards.
+    ///
+    /// This blocks until the GPU has finished and the image has been read. See
+    /// `capture_to_image_async` for a non-blocking alternative.
+    ///
+    /// **Panics** if the **RawFrame** is not wrapped by a **Frame**, i.e. if the window's `view`
+    /// function draws to a **RawFrame**, as wgpu does not allow reading back from the swap chain
+    /// texture.
+    pub fn capture_to_image(&self) -> Result<image::DynamicImage, wgpu::BufferAsyncErr> {
+        let texture = self.resolved_texture();
+        self.capture_texture_to_image(texture)
+    }
+
+    /// The same as `capture_to_image`, but returns a future that resolves to the image once the
+    /// GPU has finished.
+    ///
+    /// Note: The future will not resolve until the device is polled.
+    pub fn capture_to_image_async(
+        &self,
+    ) -> impl Future<Output = Result<image::DynamicImage, wgpu::BufferAsyncErr>> {
+        let texture = self.resolved_texture();
+        self.capture_texture_to_image_async(texture)
+    }
+
+    // Encode the MSAA resolve of the intermediary texture if necessary and return the resolved
+    // texture.
+    fn resolved_texture(&self) -> &'swap_chain wgpu::Texture {
+        let render_data = self
+            .render_data
+            .expect("frame capture requires that `view` draws to a `Frame` (not a `RawFrame`)");
+        render_data.encode_msaa_resolve(self);
+        &render_data.intermediary_lin_srgba.texture
+    }
+
     /// Submit the frame to the GPU!
     ///
     /// Specifically, this submits the encoded commands to the queue of the device that was used to
@@ -153,3 +491,22 @@ impl<'swap_chain> Drop for RawFrame<'swap_chain> {
         }
     }
 }
+
+// The format to which textures are converted before being read back to the CPU.
+const CAPTURE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+const CAPTURE_FORMAT_SIZE_BYTES: u32 = 4;
+
+// wgpu requires that the `bytes_per_row` of texture-to-buffer copies is a multiple of 256.
+const COPY_BYTES_PER_ROW_ALIGNMENT: u32 = 256;
+
+fn padded_bytes_per_row(bytes_per_row: u32) -> u32 {
+    let align = COPY_BYTES_PER_ROW_ALIGNMENT;
+    (bytes_per_row + align - 1) / align * align
+}
+
+fn create_command_encoder(device: &wgpu::Device) -> wgpu::CommandEncoder {
+    let ce_desc = wgpu::CommandEncoderDescriptor {
+        label: Some("nannou_raw_frame"),
+    };
+    device.create_command_encoder(&ce_desc)
+}
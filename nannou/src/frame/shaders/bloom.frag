@@ -0,0 +1,34 @@
+// NOTE: This shader requires being manually compiled to SPIR-V in order to
+// avoid having downstream users require building shaderc and compiling the
+// shader themselves. If you update this shader, be sure to also re-compile it
+// and update `frag_bloom.spv`. You can do so using `glslangValidator` with the
+// following command: `glslangValidator -V -o frag_bloom.spv bloom.frag`
+
+#version 450
+
+layout(location = 0) in vec2 tex_coords;
+layout(location = 0) out vec4 f_color;
+
+layout(set = 0, binding = 0) uniform texture2D tex;
+layout(set = 0, binding = 1) uniform sampler tex_sampler;
+layout(set = 0, binding = 2) uniform Uniforms {
+    // The offset between blur taps in texture coordinates. Zero samples a single texel.
+    vec2 direction;
+    // Only the portion of brightness exceeding this value is kept.
+    float threshold;
+    float intensity;
+};
+
+const float WEIGHTS[5] = float[](0.227027, 0.1945946, 0.1216216, 0.054054, 0.016216);
+
+void main() {
+    vec4 color = texture(sampler2D(tex, tex_sampler), tex_coords) * WEIGHTS[0];
+    for (int i = 1; i < 5; i++) {
+        vec2 offset = direction * float(i);
+        color += texture(sampler2D(tex, tex_sampler), tex_coords + offset) * WEIGHTS[i];
+        color += texture(sampler2D(tex, tex_sampler), tex_coords - offset) * WEIGHTS[i];
+    }
+    float brightness = max(color.r, max(color.g, color.b));
+    float contribution = max(brightness - threshold, 0.0) / max(brightness, 0.0001);
+    f_color = vec4(color.rgb * contribution * intensity, 1.0);
+}
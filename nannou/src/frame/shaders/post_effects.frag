@@ -0,0 +1,66 @@
+// NOTE: This shader requires being manually compiled to SPIR-V in order to
+// avoid having downstream users require building shaderc and compiling the
+// shader themselves. If you update this shader, be sure to also re-compile it
+// and update `frag_post_effects.spv`. You can do so using `glslangValidator`
+// with the following command:
+// `glslangValidator -V -o frag_post_effects.spv post_effects.frag`
+
+#version 450
+
+layout(location = 0) in vec2 tex_coords;
+layout(location = 0) out vec4 f_color;
+
+layout(set = 0, binding = 0) uniform texture2D tex;
+layout(set = 0, binding = 1) uniform sampler tex_sampler;
+layout(set = 0, binding = 2) uniform Uniforms {
+    // The size of the frame in pixels.
+    vec2 resolution;
+    // Each stage is disabled while its strength (the offset or alpha) is zero.
+    float aberration;
+    float grain;
+    float seed;
+    float vignette_radius;
+    float vignette_softness;
+    float vignette_strength;
+    float scanline_frequency;
+    float scanline_alpha;
+};
+
+// Offset the red and blue channels away from and towards the centre of the frame.
+vec4 chromatic_aberration(vec2 uv) {
+    vec2 offset = (uv - 0.5) * 2.0 * aberration / resolution;
+    vec4 color = texture(sampler2D(tex, tex_sampler), uv);
+    color.r = texture(sampler2D(tex, tex_sampler), uv + offset).r;
+    color.b = texture(sampler2D(tex, tex_sampler), uv - offset).b;
+    return color;
+}
+
+// Add noise to the luminance of each pixel.
+vec3 film_grain(vec3 rgb, vec2 uv) {
+    float n = fract(sin(dot(uv * resolution + seed, vec2(12.9898, 78.233))) * 43758.5453);
+    return rgb + (n - 0.5) * grain;
+}
+
+// Darken the frame with the distance from the centre, where `1.0` is the middle of each edge.
+//
+// The softness is clamped above zero, as `smoothstep` is undefined when both edges are equal.
+vec3 vignette(vec3 rgb, vec2 uv) {
+    float d = length(uv - 0.5) * 2.0;
+    float softness = max(vignette_softness, 1e-5);
+    float v = 1.0 - smoothstep(vignette_radius, vignette_radius + softness, d);
+    return rgb * mix(1.0, v, vignette_strength);
+}
+
+// Darken horizontal lines, `scanline_frequency` of which span the height of the frame.
+vec3 scanlines(vec3 rgb, vec2 uv) {
+    float s = 0.5 + 0.5 * sin(uv.y * scanline_frequency * 6.2831853);
+    return rgb * (1.0 - scanline_alpha * s);
+}
+
+void main() {
+    vec4 color = chromatic_aberration(tex_coords);
+    vec3 rgb = film_grain(color.rgb, tex_coords);
+    rgb = vignette(rgb, tex_coords);
+    rgb = scanlines(rgb, tex_coords);
+    f_color = vec4(rgb, color.a);
+}
@@ -0,0 +1,446 @@
+//! Items related to recording frames to a video file via an `ffmpeg` child process.
+//!
+//! See `App::record_video` and the **VideoRecorder** docs for details.
+
+use crate::frame::{Frame, RawFrame};
+use crate::wgpu;
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use std::{ffi, fmt};
+
+/// Records frames to a video file by piping them to an `ffmpeg` child process.
+///
+/// Created via `App::record_video`. Call `feed_frame` during each call to `view` and `finish`
+/// once recording is complete, e.g. within the app's `exit` function. Requires that `ffmpeg` is
+/// installed and available on the `PATH`.
+///
+/// Frames are captured without loss and read back from the GPU on a thread pool, so feeding a
+/// frame does not block `view`. They are written to `ffmpeg` in the order in which they were fed.
+/// The `ffmpeg` process is spawned upon the first frame, as the video size is that of the first
+/// frame.
+pub struct VideoRecorder {
+    path: PathBuf,
+    fps: f64,
+    codec: Codec,
+    // Shared with the frames fed via `feed_frame` until they are captured upon submission.
+    texture_capturer: Arc<wgpu::TextureCapturer>,
+    state: Mutex<Option<Encoder>>,
+}
+
+/// The codec with which a **VideoRecorder** encodes frames.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Codec {
+    /// Lossless H.264 via `libx264` with 4:2:0 chroma subsampling for compatibility with most
+    /// players.
+    ///
+    /// While the luma is encoded without loss, 4:2:0 halves the chroma resolution. As 4:2:0
+    /// requires even dimensions, odd frame sizes are padded by a single pixel.
+    H264,
+    /// ProRes 4444 via `prores_ks`, retaining full chroma resolution and alpha.
+    ProRes,
+}
+
+/// Errors that might occur while recording a video.
+#[derive(Debug)]
+pub enum Error {
+    /// Failed to spawn or write to the `ffmpeg` process.
+    Io(io::Error),
+    /// The `ffmpeg` process exited unsuccessfully.
+    Ffmpeg(process::ExitStatus),
+    /// The captured frame could not be read from the GPU.
+    ReadFrame,
+    /// The size of a fed frame differs from the size of the first frame.
+    FrameSize { expected: [u32; 2], found: [u32; 2] },
+    /// The format of the captured texture has no known `ffmpeg` pixel format.
+    UnsupportedFormat(wgpu::TextureFormat),
+    /// Timed out while waiting for captured frames to be read.
+    CaptureTimeout,
+}
+
+// The running `ffmpeg` process and the thread writing frames to it.
+struct Encoder {
+    size: [u32; 2],
+    next_frame: u64,
+    device_queue_pair: Arc<wgpu::DeviceQueuePair>,
+    frame_tx: FrameSender,
+    // Receives the error upon which the writer stopped, if any.
+    error_rx: mpsc::Receiver<Error>,
+    writer: thread::JoinHandle<()>,
+    child: process::Child,
+}
+
+// Sends the bytes of each frame, or the error that occurred while reading it, to the writer.
+type FrameSender = mpsc::Sender<(u64, Result<Vec<u8>, Error>)>;
+
+// A frame fed via `VideoRecorder::feed_frame`, captured upon submission of the **Frame** once its
+// texture has been resolved and any effects applied.
+pub(crate) struct QueuedFrame {
+    texture_capturer: Arc<wgpu::TextureCapturer>,
+    index: u64,
+    frame_tx: FrameSender,
+}
+
+// A **QueuedFrame** whose capture has been encoded, ready to be read once submitted.
+pub(crate) struct CapturedFrame {
+    snapshot: wgpu::TextureSnapshot,
+    index: u64,
+    frame_tx: FrameSender,
+}
+
+impl VideoRecorder {
+    // Initialise a recorder. Used by `App::record_video`.
+    pub(crate) fn new(
+        path: PathBuf,
+        fps: f64,
+        max_capture_frame_jobs: u32,
+        capture_frame_timeout: Option<Duration>,
+    ) -> Self {
+        assert!(fps > 0.0, "`fps` must be greater than `0.0`");
+        let codec = Codec::from_path(&path);
+        let texture_capturer =
+            wgpu::TextureCapturer::new(Some(max_capture_frame_jobs), capture_frame_timeout);
+        let texture_capturer = Arc::new(texture_capturer);
+        VideoRecorder {
+            path,
+            fps,
+            codec,
+            texture_capturer,
+            state: Mutex::new(None),
+        }
+    }
+
+    /// Specify the codec used to encode the video.
+    ///
+    /// By default, the codec is chosen via `Codec::from_path`.
+    pub fn codec(mut self, codec: Codec) -> Self {
+        self.codec = codec;
+        self
+    }
+
+    /// The path to which the video is written.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The rate at which frames are played back in frames per second.
+    pub fn fps(&self) -> f64 {
+        self.fps
+    }
+
+    /// The number of frames fed to the recorder so far.
+    pub fn frame_count(&self) -> u64 {
+        self.state
+            .lock()
+            .ok()
+            .and_then(|guard| guard.as_ref().map(|encoder| encoder.next_frame))
+            .unwrap_or(0)
+    }
+
+    /// Queue the frame's contents to be written to the video.
+    ///
+    /// The frame's intermediary linear sRGBA texture is captured upon submission of the frame,
+    /// once it has been resolved and any bloom or post effects have been applied, as wgpu does not
+    /// allow for reading the swap chain texture. As a result, everything drawn during `view` is
+    /// recorded, regardless of when this is called.
+    ///
+    /// Frames whose size differs from the first frame are skipped, returning an error. If writing
+    /// a previous frame to `ffmpeg` failed, the error is returned and no further frames are
+    /// written.
+    ///
+    /// Note that `finish` waits for the frame to be captured, so must not be called within the
+    /// same call to `view`.
+    pub fn feed_frame(&self, frame: &Frame) -> Result<(), Error> {
+        let (index, frame_tx) = self.queue(frame, frame.texture_size())?;
+        let texture_capturer = self.texture_capturer.clone();
+        frame.push_video_frame(QueuedFrame {
+            texture_capturer,
+            index,
+            frame_tx,
+        });
+        Ok(())
+    }
+
+    /// The same as `feed_frame`, but captures the given texture, e.g. one rendered to within a
+    /// `raw_view` function.
+    ///
+    /// The texture is captured at the state of the frame's command encoder, so should be drawn to
+    /// via the frame's encoder before calling this.
+    pub fn feed_texture(&self, frame: &RawFrame, texture: &wgpu::Texture) -> Result<(), Error> {
+        let (index, frame_tx) = self.queue(frame, texture.size())?;
+        let queued = QueuedFrame {
+            texture_capturer: self.texture_capturer.clone(),
+            index,
+            frame_tx,
+        };
+        let captured = queued.encode(frame, texture);
+        frame.submit_pending();
+        captured.read();
+        Ok(())
+    }
+
+    /// Wait for all queued frames to be written and for `ffmpeg` to finish encoding the video.
+    pub fn finish(mut self) -> Result<(), Error> {
+        self.finish_inner()
+    }
+
+    // Reserve the index of the next frame of the given size, spawning `ffmpeg` upon the first.
+    fn queue(&self, frame: &RawFrame, size: [u32; 2]) -> Result<(u64, FrameSender), Error> {
+        let mut guard = self
+            .state
+            .lock()
+            .expect("failed to acquire video recorder lock");
+        if guard.is_none() {
+            let encoder = self.spawn_encoder(frame.device_queue_pair().clone(), size)?;
+            *guard = Some(encoder);
+        }
+        let encoder = guard.as_mut().expect("no encoder");
+        if let Ok(err) = encoder.error_rx.try_recv() {
+            return Err(err);
+        }
+        if size != encoder.size {
+            let (expected, found) = (encoder.size, size);
+            return Err(Error::FrameSize { expected, found });
+        }
+        let index = encoder.next_frame;
+        encoder.next_frame += 1;
+        Ok((index, encoder.frame_tx.clone()))
+    }
+
+    // Spawn `ffmpeg` along with the thread that writes frames to it in order.
+    fn spawn_encoder(
+        &self,
+        device_queue_pair: Arc<wgpu::DeviceQueuePair>,
+        size: [u32; 2],
+    ) -> Result<Encoder, Error> {
+        let src_format = wgpu::TextureCapturer::DST_FORMAT;
+        let pixel_format =
+            ffmpeg_pixel_format(src_format).ok_or(Error::UnsupportedFormat(src_format))?;
+        let mut child = Command::new("ffmpeg")
+            .args(&["-y", "-hide_banner", "-loglevel", "error"])
+            .args(&["-f", "rawvideo", "-pix_fmt", pixel_format])
+            .arg("-s")
+            .arg(format!("{}x{}", size[0], size[1]))
+            .arg("-r")
+            .arg(self.fps.to_string())
+            .args(&["-i", "-"])
+            .args(self.codec.ffmpeg_args())
+            .arg(&self.path)
+            .stdin(Stdio::piped())
+            .spawn()?;
+        let stdin = child.stdin.take().expect("ffmpeg stdin was not piped");
+        let (frame_tx, frame_rx) = mpsc::channel();
+        let (error_tx, error_rx) = mpsc::channel();
+        let writer = thread::Builder::new()
+            .name("nannou_video_writer".into())
+            .spawn(move || {
+                if let Err(err) = write_frames(stdin, frame_rx) {
+                    error_tx.send(err).ok();
+                }
+            })?;
+        Ok(Encoder {
+            size,
+            next_frame: 0,
+            device_queue_pair,
+            frame_tx,
+            error_rx,
+            writer,
+            child,
+        })
+    }
+
+    // The implementation of `finish`, shared with `drop`.
+    fn finish_inner(&mut self) -> Result<(), Error> {
+        let encoder = match self.state.lock().ok().and_then(|mut guard| guard.take()) {
+            None => return Ok(()),
+            Some(encoder) => encoder,
+        };
+        let Encoder {
+            device_queue_pair,
+            frame_tx,
+            error_rx,
+            writer,
+            mut child,
+            ..
+        } = encoder;
+
+        // Wait for all pending reads, after which the writer receives the final frame.
+        let timeout = self
+            .texture_capturer
+            .await_active_snapshots(device_queue_pair.device())
+            .map_err(|_| Error::CaptureTimeout);
+        drop(frame_tx);
+        writer.join().expect("video writer thread panicked");
+
+        // Closing stdin upon the writer's return signals ffmpeg to finish encoding.
+        let status = child.wait()?;
+        timeout?;
+        if let Ok(err) = error_rx.try_recv() {
+            return Err(err);
+        }
+        if !status.success() {
+            return Err(Error::Ffmpeg(status));
+        }
+        Ok(())
+    }
+}
+
+impl QueuedFrame {
+    // Encode the capture of the given texture via the frame's command encoder.
+    pub(crate) fn encode(self, frame: &RawFrame, texture: &wgpu::Texture) -> CapturedFrame {
+        let QueuedFrame {
+            texture_capturer,
+            index,
+            frame_tx,
+        } = self;
+        let device = frame.device_queue_pair().device();
+        let mut command_encoder = frame.command_encoder();
+        let snapshot = texture_capturer.capture(device, &mut *command_encoder, texture);
+        CapturedFrame {
+            snapshot,
+            index,
+            frame_tx,
+        }
+    }
+}
+
+impl CapturedFrame {
+    // Read the captured frame on the capturer's thread pool, forwarding the result or any error
+    // to the writer. Must be called once the frame's commands have been submitted.
+    pub(crate) fn read(self) {
+        let CapturedFrame {
+            snapshot,
+            index,
+            frame_tx,
+        } = self;
+        let timeout_tx = frame_tx.clone();
+        let result = snapshot.read(move |result| {
+            let bytes = result
+                .map(|image| image.to_owned().into_raw())
+                .map_err(|_| Error::ReadFrame);
+            frame_tx.send((index, bytes)).ok();
+        });
+        if result.is_err() {
+            timeout_tx.send((index, Err(Error::CaptureTimeout))).ok();
+        }
+    }
+}
+
+impl Codec {
+    /// The codec suited to the extension of the given path.
+    ///
+    /// Returns `ProRes` for `.mov` files and `H264` otherwise.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(ffi::OsStr::to_str) {
+            Some(ext) if ext.eq_ignore_ascii_case("mov") => Codec::ProRes,
+            _ => Codec::H264,
+        }
+    }
+
+    // The `ffmpeg` output arguments for the codec.
+    fn ffmpeg_args(&self) -> &'static [&'static str] {
+        match *self {
+            Codec::H264 => &[
+                "-c:v",
+                "libx264",
+                "-preset",
+                "slow",
+                "-crf",
+                "0",
+                "-pix_fmt",
+                "yuv420p",
+                "-vf",
+                "pad=ceil(iw/2)*2:ceil(ih/2)*2",
+            ],
+            Codec::ProRes => &[
+                "-c:v",
+                "prores_ks",
+                "-profile:v",
+                "4444",
+                "-pix_fmt",
+                "yuva444p10le",
+            ],
+        }
+    }
+}
+
+impl Drop for VideoRecorder {
+    fn drop(&mut self) {
+        // Errors are returned by `feed_frame` and `finish` where possible. Those remaining upon
+        // drop have no caller to return to.
+        if let Err(e) = self.finish_inner() {
+            eprintln!(
+                "failed to finish recording \"{}\": {}",
+                self.path.display(),
+                e
+            );
+        }
+    }
+}
+
+/// The `ffmpeg` raw video pixel format matching the given texture format, if there is one.
+pub fn ffmpeg_pixel_format(format: wgpu::TextureFormat) -> Option<&'static str> {
+    let pix_fmt = match format {
+        wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb => "rgba",
+        wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb => "bgra",
+        wgpu::TextureFormat::R8Unorm => "gray",
+        _ => return None,
+    };
+    Some(pix_fmt)
+}
+
+// Write the frames to ffmpeg in order, buffering those that arrive early.
+fn write_frames(
+    mut stdin: process::ChildStdin,
+    frame_rx: mpsc::Receiver<(u64, Result<Vec<u8>, Error>)>,
+) -> Result<(), Error> {
+    let mut next = 0;
+    let mut pending = BTreeMap::new();
+    for (index, bytes) in frame_rx {
+        pending.insert(index, bytes);
+        while let Some(bytes) = pending.remove(&next) {
+            stdin.write_all(&bytes?)?;
+            next += 1;
+        }
+    }
+    stdin.flush()?;
+    Ok(())
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl std::error::Error for Error {
+    fn cause(&self) -> Option<&dyn std::error::Error> {
+        match *self {
+            Error::Io(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Io(ref e) => e.fmt(f),
+            Error::Ffmpeg(status) => write!(f, "ffmpeg exited unsuccessfully: {}", status),
+            Error::ReadFrame => write!(f, "failed to read captured frame"),
+            Error::FrameSize { expected, found } => write!(
+                f,
+                "frame size {:?} differs from video size {:?}",
+                found, expected
+            ),
+            Error::UnsupportedFormat(format) => {
+                write!(f, "no ffmpeg pixel format for texture format {:?}", format)
+            }
+            Error::CaptureTimeout => write!(f, "timed out while waiting to capture frames"),
+        }
+    }
+}
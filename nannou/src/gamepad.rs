@@ -0,0 +1,225 @@
+//! Gamepad and controller input, provided by the [gilrs](https://docs.rs/gilrs) crate.
+//!
+//! Requires the `gamepad` feature. Connected gamepads may be queried via `app.gamepads()`, while
+//! **GamepadEvent**s are delivered to the app's event function as `Event::Gamepad`.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+pub use gilrs;
+pub use gilrs::{Axis, Button, GamepadId};
+
+/// An event emitted by a gamepad.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GamepadEvent {
+    /// A gamepad was connected.
+    Connected(GamepadId),
+    /// A gamepad was disconnected.
+    Disconnected(GamepadId),
+    /// A button was pressed.
+    ButtonPressed(Button, GamepadId),
+    /// A button was released.
+    ButtonReleased(Button, GamepadId),
+    /// The value of a button changed, e.g. an analog trigger. The value is in the range
+    /// `0.0..=1.0`.
+    ButtonChanged(Button, f32, GamepadId),
+    /// The value of an axis changed, e.g. a thumb stick. The value is in the range `-1.0..=1.0`.
+    AxisChanged(Axis, f32, GamepadId),
+}
+
+/// The state of a single gamepad button.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct ButtonState {
+    /// Whether or not the button is currently held.
+    pub pressed: bool,
+    /// The value of the button in the range `0.0..=1.0`.
+    ///
+    /// Digital buttons are either `0.0` or `1.0`, while analog buttons such as triggers may be
+    /// anywhere in between.
+    pub value: f32,
+}
+
+/// A connected gamepad.
+///
+/// Produced by `app.gamepads()`. State is queried on demand, so a **Gamepad** always reflects the
+/// events received so far.
+#[derive(Clone)]
+pub struct Gamepad {
+    id: GamepadId,
+    context: Rc<RefCell<Inner>>,
+}
+
+/// Errors that might occur while sending force feedback to a gamepad.
+pub type VibrateError = gilrs::ff::Error;
+
+// The gamepad state shared between the `App` and each `Gamepad`.
+pub(crate) struct Context {
+    inner: Option<Rc<RefCell<Inner>>>,
+}
+
+struct Inner {
+    gilrs: gilrs::Gilrs,
+    // Effects are stopped when dropped, so keep them alive until they complete.
+    effects: Vec<(Instant, gilrs::ff::Effect)>,
+}
+
+impl Gamepad {
+    /// The unique ID of the gamepad.
+    pub fn id(&self) -> GamepadId {
+        self.id
+    }
+
+    /// The name of the gamepad as reported by the platform.
+    pub fn name(&self) -> String {
+        self.context
+            .borrow()
+            .gilrs
+            .gamepad(self.id)
+            .name()
+            .to_string()
+    }
+
+    /// Whether or not the gamepad is still connected.
+    pub fn is_connected(&self) -> bool {
+        self.context.borrow().gilrs.gamepad(self.id).is_connected()
+    }
+
+    /// The value of the given axis in the range `-1.0..=1.0`.
+    ///
+    /// Returns `0.0` if the gamepad has no such axis.
+    pub fn axis(&self, axis: Axis) -> f32 {
+        self.context.borrow().gilrs.gamepad(self.id).value(axis)
+    }
+
+    /// The state of the given button.
+    pub fn button(&self, button: Button) -> ButtonState {
+        let inner = self.context.borrow();
+        let gamepad = inner.gilrs.gamepad(self.id);
+        match gamepad.button_data(button) {
+            Some(data) => ButtonState {
+                pressed: data.is_pressed(),
+                value: data.value(),
+            },
+            None => ButtonState::default(),
+        }
+    }
+
+    /// Whether or not the gamepad supports force feedback via `vibrate`.
+    pub fn is_ff_supported(&self) -> bool {
+        self.context
+            .borrow()
+            .gilrs
+            .gamepad(self.id)
+            .is_ff_supported()
+    }
+
+    /// Rumble the gamepad's strong (low frequency) and weak (high frequency) motors for the given
+    /// duration.
+    ///
+    /// Magnitudes are in the range `0.0..=1.0`. Returns an error on hardware that does not
+    /// support force feedback.
+    pub fn vibrate(&self, strong: f32, weak: f32, duration: Duration) -> Result<(), VibrateError> {
+        use gilrs::ff::{BaseEffect, BaseEffectType, EffectBuilder, Repeat, Replay, Ticks};
+        let magnitude = |m: f32| (m.max(0.0).min(1.0) * std::u16::MAX as f32) as u16;
+        let ticks = Ticks::from_ms(duration.as_millis() as u32);
+        let scheduling = Replay {
+            play_for: ticks,
+            ..Default::default()
+        };
+        let mut inner = self.context.borrow_mut();
+        let effect = EffectBuilder::new()
+            .add_effect(BaseEffect {
+                kind: BaseEffectType::Strong {
+                    magnitude: magnitude(strong),
+                },
+                scheduling,
+                envelope: Default::default(),
+            })
+            .add_effect(BaseEffect {
+                kind: BaseEffectType::Weak {
+                    magnitude: magnitude(weak),
+                },
+                scheduling,
+                envelope: Default::default(),
+            })
+            .repeat(Repeat::For(ticks))
+            .gamepads(&[self.id])
+            .finish(&mut inner.gilrs)?;
+        effect.play()?;
+        inner.effects.push((Instant::now() + duration, effect));
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for Gamepad {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Gamepad").field("id", &self.id).finish()
+    }
+}
+
+impl Context {
+    // Initialise gilrs, logging and disabling gamepad support if it is unavailable.
+    pub(crate) fn new() -> Self {
+        match gilrs::Gilrs::new() {
+            Ok(gilrs) => {
+                let effects = vec![];
+                let inner = Some(Rc::new(RefCell::new(Inner { gilrs, effects })));
+                Context { inner }
+            }
+            Err(err) => {
+                eprintln!("failed to initialise gamepad support: {}", err);
+                Context { inner: None }
+            }
+        }
+    }
+
+    // All currently connected gamepads.
+    pub(crate) fn gamepads(&self) -> Vec<Gamepad> {
+        let context = match self.inner {
+            Some(ref inner) => inner,
+            None => return vec![],
+        };
+        let ids: Vec<_> = context
+            .borrow()
+            .gilrs
+            .gamepads()
+            .map(|(id, _)| id)
+            .collect();
+        ids.into_iter()
+            .map(|id| Gamepad {
+                id,
+                context: context.clone(),
+            })
+            .collect()
+    }
+
+    // Process all pending gilrs events, returning those of interest to the user.
+    pub(crate) fn poll(&self) -> Vec<GamepadEvent> {
+        let mut inner = match self.inner {
+            Some(ref inner) => inner.borrow_mut(),
+            None => return vec![],
+        };
+        let now = Instant::now();
+        inner.effects.retain(|&(end, _)| end > now);
+        let mut events = vec![];
+        while let Some(gilrs::Event { id, event, .. }) = inner.gilrs.next_event() {
+            use gilrs::EventType;
+            let event = match event {
+                EventType::Connected => GamepadEvent::Connected(id),
+                EventType::Disconnected => GamepadEvent::Disconnected(id),
+                EventType::ButtonPressed(button, _) => GamepadEvent::ButtonPressed(button, id),
+                EventType::ButtonReleased(button, _) => GamepadEvent::ButtonReleased(button, id),
+                EventType::ButtonChanged(button, value, _) => {
+                    GamepadEvent::ButtonChanged(button, value, id)
+                }
+                EventType::AxisChanged(axis, value, _) => {
+                    GamepadEvent::AxisChanged(axis, value, id)
+                }
+                EventType::ButtonRepeated(_, _) | EventType::Dropped => continue,
+            };
+            events.push(event);
+        }
+        events
+    }
+}
@@ -0,0 +1,421 @@
+//! Items related to cubic Bézier curves.
+//!
+//! The **Bezier** type is generic over its vertex type, allowing for curves in both 2D (e.g.
+//! `Point2`) and 3D (e.g. `Point3`) space.
+
+use crate::geom::{scalar, Point2};
+use crate::math::{BaseFloat, InnerSpace, NumCast, One, Zero};
+
+/// A cubic Bézier curve described by a start point, two control points and an end point.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Bezier<V = Point2> {
+    /// The start of the curve.
+    pub from: V,
+    /// The control point associated with the start of the curve.
+    pub ctrl1: V,
+    /// The control point associated with the end of the curve.
+    pub ctrl2: V,
+    /// The end of the curve.
+    pub to: V,
+}
+
+/// A lookup table mapping distances along a curve to the curve's `t` parameter.
+///
+/// Produced via **Bezier::uniform_parameterisation**. Useful for moving along a curve at a
+/// constant speed or for distributing points evenly along a curve.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArcLengthLut<S = scalar::Default> {
+    // The cumulative arc length at uniformly spaced values of `t` from `0` to `1`.
+    lengths: Vec<S>,
+}
+
+/// Describes how two consecutive segments within a **BezierPath** are joined.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Join {
+    /// The control points either side of the join are collinear, producing a continuous tangent.
+    Smooth,
+    /// The control points are independent, allowing for a sharp corner.
+    Cusp,
+}
+
+/// A continuous sequence of cubic Bézier segments.
+///
+/// The end of each segment is the start of the next.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BezierPath<V = Point2> {
+    segments: Vec<Bezier<V>>,
+    // The join between `segments[i]` and `segments[i + 1]`.
+    joins: Vec<Join>,
+}
+
+impl<V> Bezier<V> {
+    /// Construct a new cubic Bézier curve.
+    pub fn new(from: V, ctrl1: V, ctrl2: V, to: V) -> Self {
+        Bezier {
+            from,
+            ctrl1,
+            ctrl2,
+            to,
+        }
+    }
+
+    /// Maps the underlying vertices to a new type and returns the resulting **Bezier**.
+    pub fn map_vertices<F, V2>(self, mut map: F) -> Bezier<V2>
+    where
+        F: FnMut(V) -> V2,
+    {
+        let Bezier {
+            from,
+            ctrl1,
+            ctrl2,
+            to,
+        } = self;
+        Bezier::new(map(from), map(ctrl1), map(ctrl2), map(to))
+    }
+
+    /// The same curve traversed in the opposite direction.
+    pub fn reversed(self) -> Self {
+        let Bezier {
+            from,
+            ctrl1,
+            ctrl2,
+            to,
+        } = self;
+        Bezier::new(to, ctrl2, ctrl1, from)
+    }
+}
+
+impl<V> Bezier<V>
+where
+    V: InnerSpace,
+    V::Scalar: BaseFloat,
+{
+    /// The point along the curve at `t`, where `t` ranges from `0.0` (the start) to `1.0` (the
+    /// end).
+    pub fn point_at(&self, t: V::Scalar) -> V {
+        let three = cast::<V::Scalar>(3.0);
+        let mt = V::Scalar::one() - t;
+        self.from * (mt * mt * mt)
+            + self.ctrl1 * (three * mt * mt * t)
+            + self.ctrl2 * (three * mt * t * t)
+            + self.to * (t * t * t)
+    }
+
+    /// The derivative of the curve at `t`.
+    ///
+    /// The returned vector points in the direction of travel and is not normalised.
+    pub fn tangent_at(&self, t: V::Scalar) -> V {
+        let three = cast::<V::Scalar>(3.0);
+        let six = cast::<V::Scalar>(6.0);
+        let mt = V::Scalar::one() - t;
+        (self.ctrl1 - self.from) * (three * mt * mt)
+            + (self.ctrl2 - self.ctrl1) * (six * mt * t)
+            + (self.to - self.ctrl2) * (three * t * t)
+    }
+
+    /// Split the curve into two at `t`.
+    ///
+    /// The first curve covers `0.0..t` and the second covers `t..1.0`.
+    pub fn split_at(&self, t: V::Scalar) -> (Self, Self) {
+        let lerp = |a: V, b: V| a + (b - a) * t;
+        let ab = lerp(self.from, self.ctrl1);
+        let bc = lerp(self.ctrl1, self.ctrl2);
+        let cd = lerp(self.ctrl2, self.to);
+        let abc = lerp(ab, bc);
+        let bcd = lerp(bc, cd);
+        let mid = lerp(abc, bcd);
+        let a = Bezier::new(self.from, ab, abc, mid);
+        let b = Bezier::new(mid, bcd, cd, self.to);
+        (a, b)
+    }
+
+    /// Approximate the length of the curve by summing the distance between `steps + 1` evenly
+    /// spaced samples.
+    ///
+    /// A `steps` of `0` is treated as `1`.
+    pub fn arc_length(&self, steps: usize) -> V::Scalar {
+        let steps = steps.max(1);
+        let step = V::Scalar::one() / cast(steps as f64);
+        let mut prev = self.from;
+        let mut len = V::Scalar::zero();
+        for i in 1..=steps {
+            let p = self.point_at(step * cast(i as f64));
+            len = len + (p - prev).magnitude();
+            prev = p;
+        }
+        len
+    }
+
+    /// Produce a lookup table that can be used to map distances along the curve to `t`.
+    ///
+    /// `lut_size` is the number of samples taken along the curve. Higher values are more accurate.
+    /// A `lut_size` of less than `2` is treated as `2`.
+    pub fn uniform_parameterisation(&self, lut_size: usize) -> ArcLengthLut<V::Scalar> {
+        let lut_size = lut_size.max(2);
+        let step = V::Scalar::one() / cast((lut_size - 1) as f64);
+        let mut lengths = Vec::with_capacity(lut_size);
+        let mut prev = self.from;
+        let mut len = V::Scalar::zero();
+        lengths.push(len);
+        for i in 1..lut_size {
+            let p = self.point_at(step * cast(i as f64));
+            len = len + (p - prev).magnitude();
+            lengths.push(len);
+            prev = p;
+        }
+        ArcLengthLut { lengths }
+    }
+}
+
+impl<V> Bezier<V>
+where
+    V: Into<Point2>,
+{
+    /// Produce the sequence of lyon path events describing this curve.
+    pub fn path_events(self) -> impl Iterator<Item = lyon::path::PathEvent> {
+        let Bezier {
+            from,
+            ctrl1,
+            ctrl2,
+            to,
+        } = self.map_vertices(|v| {
+            let p: Point2 = v.into();
+            lyon::math::point(p.x, p.y)
+        });
+        let begin = lyon::path::PathEvent::Begin { at: from };
+        let cubic = lyon::path::PathEvent::Cubic {
+            from,
+            ctrl1,
+            ctrl2,
+            to,
+        };
+        let end = lyon::path::PathEvent::End {
+            last: to,
+            first: from,
+            close: false,
+        };
+        vec![begin, cubic, end].into_iter()
+    }
+}
+
+impl<S> ArcLengthLut<S>
+where
+    S: BaseFloat,
+{
+    /// The total approximate length of the curve.
+    pub fn arc_length(&self) -> S {
+        *self
+            .lengths
+            .last()
+            .expect("lut always contains at least two lengths")
+    }
+
+    /// The cumulative lengths at each of the uniformly spaced samples along the curve.
+    pub fn lengths(&self) -> &[S] {
+        &self.lengths
+    }
+
+    /// The value of `t` at which the given distance along the curve is reached.
+    ///
+    /// The distance is clamped to the range `0.0..=len`.
+    pub fn t_at_distance(&self, distance: S) -> S {
+        let len = self.arc_length();
+        if len <= S::zero() || distance <= S::zero() {
+            return S::zero();
+        }
+        if distance >= len {
+            return S::one();
+        }
+        // Find the first sample whose length is at least the target distance.
+        let ix = match self
+            .lengths
+            .binary_search_by(|l| l.partial_cmp(&distance).expect("NaN length"))
+        {
+            Ok(ix) => ix,
+            Err(ix) => ix,
+        };
+        let ix = ix.max(1);
+        let (l0, l1) = (self.lengths[ix - 1], self.lengths[ix]);
+        let frac = if l1 > l0 {
+            (distance - l0) / (l1 - l0)
+        } else {
+            S::zero()
+        };
+        let step = S::one() / cast((self.lengths.len() - 1) as f64);
+        (cast::<S>((ix - 1) as f64) + frac) * step
+    }
+
+    /// The value of `t` at the given fraction of the curve's total length.
+    ///
+    /// E.g. `0.5` returns the `t` at which exactly half of the curve's length has been traversed.
+    pub fn t_at_fraction(&self, fraction: S) -> S {
+        self.t_at_distance(fraction * self.arc_length())
+    }
+}
+
+impl<V> BezierPath<V> {
+    /// An empty path.
+    pub fn new() -> Self {
+        BezierPath {
+            segments: vec![],
+            joins: vec![],
+        }
+    }
+
+    /// The segments that make up the path.
+    pub fn segments(&self) -> &[Bezier<V>] {
+        &self.segments
+    }
+
+    /// The joins between each consecutive pair of segments.
+    ///
+    /// There is always one less join than there are segments.
+    pub fn joins(&self) -> &[Join] {
+        &self.joins
+    }
+
+    /// The number of segments within the path.
+    pub fn len(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Whether or not the path contains any segments.
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Append the given segment.
+    ///
+    /// The segment is joined to the end of the path with a **Join::Cusp**. Note that the segment's
+    /// `from` point is used as is and should match the end of the path to remain continuous.
+    pub fn push(&mut self, segment: Bezier<V>) {
+        if !self.segments.is_empty() {
+            self.joins.push(Join::Cusp);
+        }
+        self.segments.push(segment);
+    }
+
+    /// Consumes the path and returns the inner segments.
+    pub fn into_segments(self) -> Vec<Bezier<V>> {
+        self.segments
+    }
+}
+
+impl<V> BezierPath<V>
+where
+    V: InnerSpace,
+    V::Scalar: BaseFloat,
+{
+    /// Append a new segment from the end of the path with independent control points.
+    ///
+    /// **Panics** if the path is empty.
+    pub fn cusp_to(&mut self, ctrl1: V, ctrl2: V, to: V) {
+        let from = self.last_point();
+        self.push(Bezier::new(from, ctrl1, ctrl2, to));
+    }
+
+    /// Append a new segment from the end of the path whose first control point is the reflection
+    /// of the previous segment's last control point, ensuring a continuous tangent.
+    ///
+    /// **Panics** if the path is empty.
+    pub fn smooth_to(&mut self, ctrl2: V, to: V) {
+        let last = *self
+            .segments
+            .last()
+            .expect("`smooth_to` requires a non-empty path");
+        let ctrl1 = last.to + (last.to - last.ctrl2);
+        self.segments.push(Bezier::new(last.to, ctrl1, ctrl2, to));
+        self.joins.push(Join::Smooth);
+    }
+
+    /// The point along the entire path at `t`, where `t` ranges from `0.0` to `1.0`.
+    ///
+    /// Each segment occupies an equal portion of the range regardless of its length.
+    ///
+    /// Returns `None` if the path is empty.
+    pub fn point_at(&self, t: V::Scalar) -> Option<V> {
+        if self.segments.is_empty() {
+            return None;
+        }
+        let n = self.segments.len();
+        let scaled = t.max(V::Scalar::zero()).min(V::Scalar::one()) * cast(n as f64);
+        let ix = NumCast::from(scaled.floor()).unwrap_or(0usize).min(n - 1);
+        let local_t = scaled - cast(ix as f64);
+        Some(self.segments[ix].point_at(local_t))
+    }
+
+    /// The approximate length of the entire path, using the given number of `steps` per segment.
+    pub fn arc_length(&self, steps: usize) -> V::Scalar {
+        self.segments
+            .iter()
+            .fold(V::Scalar::zero(), |acc, s| acc + s.arc_length(steps))
+    }
+
+    fn last_point(&self) -> V {
+        self.segments
+            .last()
+            .map(|s| s.to)
+            .expect("the path must contain at least one segment")
+    }
+}
+
+impl<V> BezierPath<V>
+where
+    V: Copy + Into<Point2>,
+{
+    /// Produce the sequence of lyon path events describing the path.
+    pub fn path_events(&self) -> impl Iterator<Item = lyon::path::PathEvent> + '_ {
+        let to_lyon = |v: V| {
+            let p: Point2 = v.into();
+            lyon::math::point(p.x, p.y)
+        };
+        let first = self.segments.first().map(|s| to_lyon(s.from));
+        let last = self.segments.last().map(|s| to_lyon(s.to));
+        let begin = first.map(|at| lyon::path::PathEvent::Begin { at });
+        let cubics = self
+            .segments
+            .iter()
+            .map(move |s| lyon::path::PathEvent::Cubic {
+                from: to_lyon(s.from),
+                ctrl1: to_lyon(s.ctrl1),
+                ctrl2: to_lyon(s.ctrl2),
+                to: to_lyon(s.to),
+            });
+        let end = first.and_then(|first| {
+            last.map(|last| lyon::path::PathEvent::End {
+                last,
+                first,
+                close: false,
+            })
+        });
+        begin.into_iter().chain(cubics).chain(end)
+    }
+}
+
+impl<V> Default for BezierPath<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V> From<Bezier<V>> for BezierPath<V> {
+    fn from(segment: Bezier<V>) -> Self {
+        let mut path = BezierPath::new();
+        path.push(segment);
+        path
+    }
+}
+
+impl<V> From<Vec<Bezier<V>>> for BezierPath<V> {
+    fn from(segments: Vec<Bezier<V>>) -> Self {
+        let mut path = BezierPath::new();
+        for segment in segments {
+            path.push(segment);
+        }
+        path
+    }
+}
+
+fn cast<S: NumCast>(f: f64) -> S {
+    NumCast::from(f).expect("failed to cast `f64` to scalar")
+}
@@ -0,0 +1,151 @@
+//! Items related to Catmull-Rom splines - smooth curves that pass through all of their control
+//! points.
+
+use crate::geom::{Bezier, Point2};
+use crate::math::{BaseFloat, InnerSpace, NumCast, One, Zero};
+
+/// A Catmull-Rom (or more generally, cardinal) spline passing through each of its points.
+///
+/// The spline is made up of one cubic segment between each consecutive pair of points. In the
+/// case that the spline is `looped`, an extra segment joins the last point back to the first.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CatmullRom<V = Point2> {
+    /// The points through which the spline passes.
+    pub points: Vec<V>,
+    /// Describes how tightly the spline bends toward each point.
+    ///
+    /// `0.0` produces a loose curve, `0.5` produces a standard Catmull-Rom spline and `1.0`
+    /// produces straight lines between points.
+    pub tension: f32,
+    /// Whether or not the last point should be joined back to the first.
+    pub looped: bool,
+}
+
+impl<V> CatmullRom<V> {
+    /// The default tension, producing a standard Catmull-Rom spline.
+    pub const DEFAULT_TENSION: f32 = 0.5;
+
+    /// Create an open spline that passes through the given points.
+    pub fn new<I>(points: I) -> Self
+    where
+        I: IntoIterator<Item = V>,
+    {
+        CatmullRom {
+            points: points.into_iter().collect(),
+            tension: Self::DEFAULT_TENSION,
+            looped: false,
+        }
+    }
+
+    /// Specify the tension of the spline.
+    ///
+    /// `0.0` is loose, `0.5` is a standard Catmull-Rom spline and `1.0` is linear.
+    pub fn tension(mut self, tension: f32) -> Self {
+        self.tension = tension;
+        self
+    }
+
+    /// Join the last point back to the first, producing a closed loop.
+    pub fn looped(mut self) -> Self {
+        self.looped = true;
+        self
+    }
+
+    /// The number of cubic segments that make up the spline.
+    pub fn segment_count(&self) -> usize {
+        match self.points.len() {
+            0 | 1 => 0,
+            n if self.looped => n,
+            n => n - 1,
+        }
+    }
+}
+
+impl<V> CatmullRom<V>
+where
+    V: InnerSpace,
+    V::Scalar: BaseFloat,
+{
+    /// The cubic Bézier equivalent of the segment at the given index.
+    ///
+    /// **Panics** if `segment` is out of range of `segment_count`.
+    pub fn segment(&self, segment: usize) -> Bezier<V> {
+        assert!(segment < self.segment_count(), "segment index out of range");
+        let n = self.points.len();
+        let p1 = self.points[segment];
+        let p2 = self.points[(segment + 1) % n];
+        // The points either side of the segment. At the ends of an open spline, reflect the
+        // neighbouring point so that the tangent follows the direction of the first/last segment.
+        let p0 = if segment > 0 || self.looped {
+            self.points[(segment + n - 1) % n]
+        } else {
+            p1 + (p1 - p2)
+        };
+        let p3 = if segment + 2 < n || self.looped {
+            self.points[(segment + 2) % n]
+        } else {
+            p2 + (p2 - p1)
+        };
+        // Scale the tangents by the tension. At `0.5` this is the standard Catmull-Rom tangent.
+        let one = V::Scalar::one();
+        let tension: V::Scalar = NumCast::from(self.tension).expect("failed to cast tension");
+        let three: V::Scalar = NumCast::from(3.0).unwrap();
+        let scale = (one - tension) / three;
+        let m1 = (p2 - p0) * scale;
+        let m2 = (p3 - p1) * scale;
+        Bezier::new(p1, p1 + m1, p2 - m2, p2)
+    }
+
+    /// The point at `t` (`0.0` to `1.0`) along the segment at the given index.
+    ///
+    /// **Panics** if `segment` is out of range of `segment_count`.
+    pub fn point_at(&self, segment: usize, t: V::Scalar) -> V {
+        self.segment(segment).point_at(t)
+    }
+
+    /// Convert the spline into its equivalent sequence of cubic Bézier segments.
+    pub fn to_bezier_segments(&self) -> Vec<Bezier<V>> {
+        (0..self.segment_count()).map(|i| self.segment(i)).collect()
+    }
+
+    /// Produce `n` points distributed evenly by arc length along the entire spline.
+    ///
+    /// For an open spline, the first and last points are the ends of the spline. For a looped
+    /// spline, the points are distributed around the loop without duplicating the first point.
+    pub fn uniformly_sampled(&self, n: usize) -> Vec<V> {
+        const LUT_SIZE: usize = 64;
+        if n == 0 || self.points.is_empty() {
+            return vec![];
+        }
+        let segments = self.to_bezier_segments();
+        if segments.is_empty() {
+            return vec![self.points[0]; n];
+        }
+        let luts: Vec<_> = segments
+            .iter()
+            .map(|s| s.uniform_parameterisation(LUT_SIZE))
+            .collect();
+        let total = luts
+            .iter()
+            .fold(V::Scalar::zero(), |acc, lut| acc + lut.arc_length());
+        let divisions = if self.looped || n == 1 { n } else { n - 1 };
+        let step = total / NumCast::from(divisions).expect("failed to cast divisions");
+
+        let mut points = Vec::with_capacity(n);
+        let mut segment = 0;
+        let mut segment_start = V::Scalar::zero();
+        for i in 0..n {
+            let distance = step * NumCast::from(i).expect("failed to cast index");
+            // Advance to the segment containing the distance.
+            while segment + 1 < segments.len()
+                && distance > segment_start + luts[segment].arc_length()
+            {
+                segment_start = segment_start + luts[segment].arc_length();
+                segment += 1;
+            }
+            let t = luts[segment].t_at_distance(distance - segment_start);
+            points.push(segments[segment].point_at(t));
+        }
+        points
+    }
+}
@@ -0,0 +1,243 @@
+//! Items related to iso-contouring scalar fields via the marching squares algorithm.
+//!
+//! A **ScalarGrid** samples a field over a regular grid of points, from which the lines along
+//! which the field is equal to some level may be produced via `ScalarGrid::contour`.
+
+use crate::geom::{scalar, Point2, Rect};
+use crate::math::num_traits::cast;
+use crate::math::BaseFloat;
+use std::collections::HashMap;
+
+/// The values of a scalar field sampled over a regular grid of points within some bounds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScalarGrid<S = scalar::Default> {
+    bounds: Rect<S>,
+    resolution: u32,
+    values: Vec<S>,
+    interpolate: bool,
+}
+
+/// A single line along which a scalar field is equal to some level.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContourLine<S = scalar::Default> {
+    /// The points along the line.
+    pub points: Vec<Point2<S>>,
+    /// Whether or not the last point joins back to the first, e.g. when the line encloses a peak.
+    pub closed: bool,
+}
+
+// The edge of a cell on which the contour crosses.
+#[derive(Copy, Clone, Debug)]
+enum Edge {
+    Bottom,
+    Right,
+    Top,
+    Left,
+}
+
+impl<S> ScalarGrid<S>
+where
+    S: BaseFloat,
+{
+    /// Sample the given field on a `resolution` x `resolution` grid of points spanning `bounds`.
+    ///
+    /// Crossing positions are interpolated between samples by default. See `interpolate`.
+    ///
+    /// **Panics** if `resolution` is less than `2`.
+    pub fn sample<F>(field: F, bounds: Rect<S>, resolution: u32) -> Self
+    where
+        F: Fn(Point2<S>) -> S,
+    {
+        assert!(resolution >= 2, "grid resolution must be at least `2`");
+        let n = resolution as usize;
+        let mut grid = ScalarGrid {
+            bounds,
+            resolution,
+            values: Vec::with_capacity(n * n),
+            interpolate: true,
+        };
+        for j in 0..n {
+            for i in 0..n {
+                let value = field(grid.point(i, j));
+                grid.values.push(value);
+            }
+        }
+        grid
+    }
+
+    /// Whether or not crossing positions are bilinearly interpolated between samples.
+    ///
+    /// When disabled, each crossing is placed at the midpoint of the edge between the two samples,
+    /// producing jagged lines that follow the grid. Enabled by default.
+    pub fn interpolate(mut self, interpolate: bool) -> Self {
+        self.interpolate = interpolate;
+        self
+    }
+
+    /// The bounds over which the field was sampled.
+    pub fn bounds(&self) -> Rect<S> {
+        self.bounds
+    }
+
+    /// The number of samples along each axis.
+    pub fn resolution(&self) -> u32 {
+        self.resolution
+    }
+
+    /// The position of the sample at the given column and row, where `(0, 0)` is the bottom left
+    /// corner of the bounds.
+    pub fn point(&self, i: usize, j: usize) -> Point2<S> {
+        let steps: S = cast(self.resolution - 1).unwrap();
+        let tx: S = cast::<_, S>(i).unwrap() / steps;
+        let ty: S = cast::<_, S>(j).unwrap() / steps;
+        let x = self.bounds.left() + self.bounds.w() * tx;
+        let y = self.bounds.bottom() + self.bounds.h() * ty;
+        Point2 { x, y }
+    }
+
+    /// The value of the sample at the given column and row.
+    pub fn value(&self, i: usize, j: usize) -> S {
+        self.values[j * self.resolution as usize + i]
+    }
+
+    /// Produce the lines along which the field is equal to the given level.
+    ///
+    /// Samples greater than or equal to `level` are considered to be inside the contour. Cells
+    /// whose opposite corners are inside are disambiguated via the mean of their four samples.
+    /// Segments produced for each cell are joined into continuous lines.
+    pub fn contour(&self, level: S) -> Vec<ContourLine<S>> {
+        let n = self.resolution as usize;
+        let mut segments: Vec<[usize; 2]> = vec![];
+        let mut crossings: HashMap<usize, Point2<S>> = HashMap::new();
+        let mut edge_segments: HashMap<usize, Vec<usize>> = HashMap::new();
+
+        for j in 0..n - 1 {
+            for i in 0..n - 1 {
+                let bl = self.value(i, j);
+                let br = self.value(i + 1, j);
+                let tr = self.value(i + 1, j + 1);
+                let tl = self.value(i, j + 1);
+                let case = (bl >= level) as u8
+                    | ((br >= level) as u8) << 1
+                    | ((tr >= level) as u8) << 2
+                    | ((tl >= level) as u8) << 3;
+                let four: S = cast(4.0).unwrap();
+                let center_inside = || (bl + br + tr + tl) / four >= level;
+                let cell_segments: &[[Edge; 2]] = match case {
+                    0 | 15 => &[],
+                    1 | 14 => &[[Edge::Left, Edge::Bottom]],
+                    2 | 13 => &[[Edge::Bottom, Edge::Right]],
+                    3 | 12 => &[[Edge::Left, Edge::Right]],
+                    4 | 11 => &[[Edge::Right, Edge::Top]],
+                    6 | 9 => &[[Edge::Bottom, Edge::Top]],
+                    7 | 8 => &[[Edge::Top, Edge::Left]],
+                    5 if center_inside() => &[[Edge::Top, Edge::Left], [Edge::Bottom, Edge::Right]],
+                    5 => &[[Edge::Left, Edge::Bottom], [Edge::Right, Edge::Top]],
+                    10 if center_inside() => {
+                        &[[Edge::Left, Edge::Bottom], [Edge::Right, Edge::Top]]
+                    }
+                    _ => &[[Edge::Top, Edge::Left], [Edge::Bottom, Edge::Right]],
+                };
+                for &[a, b] in cell_segments {
+                    let index = segments.len();
+                    let mut ids = [0; 2];
+                    for (id, &edge) in ids.iter_mut().zip(&[a, b]) {
+                        *id = self.edge_id(i, j, edge);
+                        crossings
+                            .entry(*id)
+                            .or_insert_with(|| self.crossing(i, j, edge, level));
+                        edge_segments
+                            .entry(*id)
+                            .or_insert_with(Vec::new)
+                            .push(index);
+                    }
+                    segments.push(ids);
+                }
+            }
+        }
+
+        // Join segments into lines, beginning with those that end at the bounds so that open lines
+        // are walked from one end to the other. Only closed loops remain for the second pass.
+        let mut visited = vec![false; segments.len()];
+        let mut lines = vec![];
+        for open_pass in &[true, false] {
+            for s in 0..segments.len() {
+                if visited[s] {
+                    continue;
+                }
+                let start = match segments[s].iter().find(|&&e| edge_segments[&e].len() == 1) {
+                    Some(&edge) => edge,
+                    None if *open_pass => continue,
+                    None => segments[s][0],
+                };
+                let mut edges = vec![start];
+                let (mut edge, mut segment) = (start, s);
+                loop {
+                    visited[segment] = true;
+                    let [a, b] = segments[segment];
+                    edge = if a == edge { b } else { a };
+                    edges.push(edge);
+                    let next = edge_segments[&edge].iter().find(|&&s| !visited[s]);
+                    match next {
+                        Some(&next) => segment = next,
+                        None => break,
+                    }
+                }
+                let closed = edges.len() > 2 && edges.first() == edges.last();
+                if closed {
+                    edges.pop();
+                }
+                let points = edges.iter().map(|e| crossings[e]).collect();
+                lines.push(ContourLine { points, closed });
+            }
+        }
+        lines
+    }
+
+    // A unique identifier for the given edge of the cell at the given column and row, shared by
+    // the neighbouring cell on the other side of the edge.
+    fn edge_id(&self, i: usize, j: usize, edge: Edge) -> usize {
+        let n = self.resolution as usize;
+        let horizontal = |i, j| j * (n - 1) + i;
+        let vertical = |i, j| n * (n - 1) + j * n + i;
+        match edge {
+            Edge::Bottom => horizontal(i, j),
+            Edge::Top => horizontal(i, j + 1),
+            Edge::Left => vertical(i, j),
+            Edge::Right => vertical(i + 1, j),
+        }
+    }
+
+    // The position at which the contour crosses the given edge of the cell.
+    fn crossing(&self, i: usize, j: usize, edge: Edge, level: S) -> Point2<S> {
+        let ([ai, aj], [bi, bj]) = match edge {
+            Edge::Bottom => ([i, j], [i + 1, j]),
+            Edge::Right => ([i + 1, j], [i + 1, j + 1]),
+            Edge::Top => ([i, j + 1], [i + 1, j + 1]),
+            Edge::Left => ([i, j], [i, j + 1]),
+        };
+        let (a, b) = (self.point(ai, aj), self.point(bi, bj));
+        let (va, vb) = (self.value(ai, aj), self.value(bi, bj));
+        let half: S = cast(0.5).unwrap();
+        let t = if self.interpolate && va != vb {
+            (level - va) / (vb - va)
+        } else {
+            half
+        };
+        Point2 {
+            x: a.x + (b.x - a.x) * t,
+            y: a.y + (b.y - a.y) * t,
+        }
+    }
+}
+
+/// Sample the given field and produce the lines along which it is equal to the given level.
+///
+/// This is short-hand for `ScalarGrid::sample(field, bounds, resolution).contour(level)`.
+pub fn contour<F, S>(field: F, bounds: Rect<S>, resolution: u32, level: S) -> Vec<ContourLine<S>>
+where
+    F: Fn(Point2<S>) -> S,
+    S: BaseFloat,
+{
+    ScalarGrid::sample(field, bounds, resolution).contour(level)
+}
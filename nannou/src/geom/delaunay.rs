@@ -0,0 +1,126 @@
+//! Items related to Delaunay triangulation - the triangulation of a set of points such that no
+//! point lies within the circumcircle of any triangle.
+
+use crate::geom::{self, Point2, Tri};
+use crate::math::num_traits::cast;
+use crate::math::BaseFloat;
+
+/// Produce the Delaunay triangulation of the given set of points.
+///
+/// The triangulation is produced via the Bowyer-Watson algorithm. Each resulting triangle is wound
+/// counter-clockwise.
+///
+/// Duplicate points are ignored. Returns an empty `Vec` if fewer than three points are given or if
+/// all points are collinear.
+pub fn triangulate<S>(points: &[Point2<S>]) -> Vec<Tri<Point2<S>>>
+where
+    S: BaseFloat,
+{
+    if points.len() < 3 {
+        return vec![];
+    }
+
+    // Begin with a "super" triangle that comfortably bounds all points.
+    let rect = match geom::bounding_rect(points.iter().cloned()) {
+        None => return vec![],
+        Some(rect) => rect,
+    };
+    let (w, h) = rect.w_h();
+    let size = if w > h { w } else { h };
+    let size = if size > S::zero() { size } else { S::one() };
+    let extent = size * cast(20.0).unwrap();
+    let (cx, cy) = rect.x_y();
+    let mut vertices: Vec<Point2<S>> = points.to_vec();
+    let super_start = vertices.len();
+    vertices.push(Point2::new(cx - extent, cy - extent));
+    vertices.push(Point2::new(cx + extent, cy - extent));
+    vertices.push(Point2::new(cx, cy + extent));
+
+    let mut tris = vec![Triangle::new(
+        &vertices,
+        [super_start, super_start + 1, super_start + 2],
+    )];
+    let mut edges = vec![];
+
+    for (i, &p) in points.iter().enumerate() {
+        // Find the triangles whose circumcircle contains the point.
+        let mut duplicate = false;
+        edges.clear();
+        for tri in tris.iter_mut() {
+            if !tri.circumcircle_contains(p) {
+                continue;
+            }
+            if tri.indices.iter().any(|&ix| vertices[ix] == p) {
+                duplicate = true;
+                break;
+            }
+            tri.bad = true;
+            let [a, b, c] = tri.indices;
+            edges.push([a, b]);
+            edges.push([b, c]);
+            edges.push([c, a]);
+        }
+        if duplicate {
+            tris.iter_mut().for_each(|tri| tri.bad = false);
+            continue;
+        }
+        tris.retain(|tri| !tri.bad);
+
+        // The boundary of the polygonal hole is made up of the edges that are not shared.
+        for (j, edge) in edges.iter().enumerate() {
+            let shared = edges
+                .iter()
+                .enumerate()
+                .any(|(k, other)| j != k && edge[0] == other[1] && edge[1] == other[0]);
+            if !shared {
+                tris.push(Triangle::new(&vertices, [edge[0], edge[1], i]));
+            }
+        }
+    }
+
+    tris.into_iter()
+        .filter(|tri| tri.indices.iter().all(|&ix| ix < super_start))
+        .map(|tri| {
+            let [a, b, c] = tri.indices;
+            Tri([vertices[a], vertices[b], vertices[c]])
+        })
+        .collect()
+}
+
+// A triangle of vertex indices along with its cached circumcircle.
+struct Triangle<S> {
+    indices: [usize; 3],
+    // `None` for degenerate triangles, whose circumcircle is considered to contain all points.
+    circumcircle: Option<(Point2<S>, S)>,
+    bad: bool,
+}
+
+impl<S> Triangle<S>
+where
+    S: BaseFloat,
+{
+    // Create the triangle, ensuring its vertices are wound counter-clockwise.
+    fn new(vertices: &[Point2<S>], [a, b, c]: [usize; 3]) -> Self {
+        let (pa, pb, pc) = (vertices[a], vertices[b], vertices[c]);
+        let indices = match (pb - pa).perp_dot(pc - pa) < S::zero() {
+            true => [a, c, b],
+            false => [a, b, c],
+        };
+        let circumcircle = Tri([pa, pb, pc])
+            .circumcenter()
+            .map(|center| (center, (pa - center).magnitude2()));
+        let bad = false;
+        Triangle {
+            indices,
+            circumcircle,
+            bad,
+        }
+    }
+
+    fn circumcircle_contains(&self, p: Point2<S>) -> bool {
+        match self.circumcircle {
+            None => true,
+            Some((center, radius2)) => (p - center).magnitude2() <= radius2,
+        }
+    }
+}
@@ -24,6 +24,15 @@ pub struct Section<S = scalar::Default> {
     pub section_radians: S,
 }
 
+/// A lookup table mapping the distance along the circumference of an `Ellipse` to the angle at
+/// which it is reached, allowing for uniform arc-length parameterisation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArcLengthLut<S = scalar::Default> {
+    ellipse: Ellipse<S>,
+    // The cumulative arc length at each of `n + 1` equally spaced angles from `0` to `2 * PI`.
+    lengths: Vec<S>,
+}
+
 /// An iterator yielding the edges of an ellipse (or some section of an `ellipse`) as a series of
 /// points.
 #[derive(Clone, Debug)]
@@ -110,6 +119,153 @@ where
     pub fn triangle_indices(&self) -> (TriangleVertices<S>, TriangleIndices) {
         self.circumference().triangle_indices()
     }
+
+    /// The point on the circumference at the given angle in radians, via the standard parametric
+    /// form `(x + w/2 * cos(theta), y + h/2 * sin(theta))`.
+    ///
+    /// Note that for non-circular ellipses `theta` is not the angle between the point and the
+    /// *x* axis, and equal steps in `theta` are not equally spaced along the circumference. See
+    /// `point_at_fraction` for the latter.
+    pub fn point_at_angle(&self, theta: S) -> Point2<S> {
+        let (x, y, w, h) = self.rect.x_y_w_h();
+        let two = math::two::<S>();
+        [x + w / two * theta.cos(), y + h / two * theta.sin()].into()
+    }
+
+    /// The unit-length tangent of the circumference at the given angle in radians, pointing in
+    /// the direction of increasing `theta`.
+    pub fn tangent_at_angle(&self, theta: S) -> Point2<S> {
+        let (w, h) = self.rect.w_h();
+        let two = math::two::<S>();
+        let t: Point2<S> = [-w / two * theta.sin(), h / two * theta.cos()].into();
+        t.normalize()
+    }
+
+    /// The approximate length of the circumference via Ramanujan's second approximation.
+    pub fn perimeter(&self) -> S {
+        let (w, h) = self.rect.w_h();
+        let two = math::two::<S>();
+        let (a, b) = ((w / two).abs(), (h / two).abs());
+        if a + b == S::zero() {
+            return S::zero();
+        }
+        let f = |x: f64| -> S { NumCast::from(x).unwrap() };
+        let hh = ((a - b) / (a + b)).powi(2);
+        let three_h = f(3.0) * hh;
+        let pi = f(std::f64::consts::PI);
+        pi * (a + b) * (S::one() + three_h / (f(10.0) + (f(4.0) - three_h).sqrt()))
+    }
+
+    /// Produce a lookup table of the arc length at `n` equally spaced angles around the
+    /// circumference, used to place points at equal distances along the circumference.
+    ///
+    /// Larger values of `n` produce more accurate results. `n` is clamped to a minimum of `1`.
+    pub fn arc_length_lut(&self, n: usize) -> ArcLengthLut<S> {
+        let n = std::cmp::max(n, 1);
+        let step = S::from(2.0 * std::f64::consts::PI).unwrap() / S::from(n).unwrap();
+        let mut lengths = Vec::with_capacity(n + 1);
+        let mut total = S::zero();
+        let mut last = self.point_at_angle(S::zero());
+        lengths.push(total);
+        for i in 1..=n {
+            let p = self.point_at_angle(step * S::from(i).unwrap());
+            total = total + (p - last).magnitude();
+            lengths.push(total);
+            last = p;
+        }
+        ArcLengthLut {
+            ellipse: *self,
+            lengths,
+        }
+    }
+
+    /// The point at the given fraction of the distance around the circumference, beginning at
+    /// angle `0`.
+    ///
+    /// This builds an `ArcLengthLut` on each call. When placing many points, prefer building the
+    /// table once via `arc_length_lut` and calling `ArcLengthLut::point_at_fraction`.
+    pub fn point_at_fraction(&self, t: S) -> Point2<S> {
+        self.arc_length_lut(ArcLengthLut::<S>::DEFAULT_LEN)
+            .point_at_fraction(t)
+    }
+
+    /// Whether or not the given point lies within the ellipse, including its edge.
+    pub fn contains(&self, p: Point2<S>) -> bool {
+        let (x, y, w, h) = self.rect.x_y_w_h();
+        let two = math::two::<S>();
+        let (a, b) = (w / two, h / two);
+        if a == S::zero() || b == S::zero() {
+            return false;
+        }
+        let (dx, dy) = ((p.x - x) / a, (p.y - y) / b);
+        dx * dx + dy * dy <= S::one()
+    }
+}
+
+impl<S> ArcLengthLut<S>
+where
+    S: BaseFloat,
+{
+    /// The number of samples used by `Ellipse::point_at_fraction`.
+    pub const DEFAULT_LEN: usize = 256;
+
+    /// The ellipse described by the table.
+    pub fn ellipse(&self) -> &Ellipse<S> {
+        &self.ellipse
+    }
+
+    /// The total length of the circumference as measured by the table.
+    pub fn total_length(&self) -> S {
+        *self.lengths.last().expect("no arc lengths")
+    }
+
+    /// The angle in radians at which the given distance along the circumference is reached.
+    ///
+    /// Distances beyond either end of the circumference wrap around.
+    pub fn angle_at_length(&self, length: S) -> S {
+        let total = self.total_length();
+        if total == S::zero() {
+            return S::zero();
+        }
+        let length = length % total;
+        let length = if length < S::zero() {
+            length + total
+        } else {
+            length
+        };
+        // Find the segment containing the length and interpolate the angle within it.
+        let n = self.lengths.len() - 1;
+        let i = match self
+            .lengths
+            .binary_search_by(|l| l.partial_cmp(&length).expect("NaN arc length"))
+        {
+            Ok(i) => return self.angle_at_index(i),
+            Err(i) => std::cmp::min(std::cmp::max(i, 1), n),
+        };
+        let (start, end) = (self.lengths[i - 1], self.lengths[i]);
+        let fraction = match end - start {
+            d if d > S::zero() => (length - start) / d,
+            _ => S::zero(),
+        };
+        let (a, b) = (self.angle_at_index(i - 1), self.angle_at_index(i));
+        a + (b - a) * fraction
+    }
+
+    /// The angle in radians at which the given fraction of the distance around the circumference
+    /// is reached.
+    pub fn angle_at_fraction(&self, t: S) -> S {
+        self.angle_at_length(t * self.total_length())
+    }
+
+    /// The point at the given fraction of the distance around the circumference.
+    pub fn point_at_fraction(&self, t: S) -> Point2<S> {
+        self.ellipse.point_at_angle(self.angle_at_fraction(t))
+    }
+
+    fn angle_at_index(&self, i: usize) -> S {
+        let n = S::from(self.lengths.len() - 1).unwrap();
+        S::from(2.0 * std::f64::consts::PI).unwrap() * S::from(i).unwrap() / n
+    }
 }
 
 impl<S> Section<S>
@@ -0,0 +1,302 @@
+//! Items related to k-d trees - spatial indices for finding the points nearest to a query.
+//!
+//! **KdTree2** is immutable once built, allowing for it to be shared between threads.
+//! **DynamicKdTree2** supports the insertion of points, e.g. when points are added each frame.
+
+use crate::geom::Point2;
+use std::cmp::Ordering;
+
+/// A two-dimensional k-d tree associating each point with a value of type `T`.
+///
+/// The tree is balanced upon construction and cannot be modified afterwards. Points are stored
+/// within a single flat array with each median point located at the centre of its subtree's range.
+#[derive(Clone, Debug)]
+pub struct KdTree2<T> {
+    points: Vec<(Point2, T)>,
+}
+
+/// A two-dimensional k-d tree supporting the insertion of new points.
+///
+/// Newly inserted points are stored within a flat array that is searched linearly by queries. Once
+/// the array exceeds a portion of the tree's size, the tree is rebuilt with all points.
+#[derive(Clone, Debug)]
+pub struct DynamicKdTree2<T> {
+    tree: KdTree2<T>,
+    pending: Vec<(Point2, T)>,
+}
+
+impl<T> KdTree2<T> {
+    /// Build a balanced tree from the given points and their associated values.
+    pub fn build(mut points: Vec<(Point2, T)>) -> Self {
+        build(&mut points, 0);
+        KdTree2 { points }
+    }
+
+    /// The number of points within the tree.
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Whether or not the tree contains no points.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// An iterator yielding all points and their values in no particular order.
+    pub fn iter(&self) -> std::slice::Iter<(Point2, T)> {
+        self.points.iter()
+    }
+
+    /// The value of the point nearest to the query along with its distance.
+    ///
+    /// Returns `None` if the tree is empty.
+    pub fn nearest(&self, query: Point2) -> Option<(f32, &T)> {
+        let mut best = None;
+        nearest(&self.points, 0, query, 0, &mut best);
+        best.map(|(dist2, i)| (dist2.sqrt(), &self.points[i].1))
+    }
+
+    /// The values of the `k` points nearest to the query along with their distances, ordered from
+    /// nearest to furthest.
+    ///
+    /// Fewer than `k` values are returned if the tree contains fewer than `k` points.
+    pub fn k_nearest(&self, query: Point2, k: usize) -> Vec<(f32, &T)> {
+        let mut best = Vec::with_capacity(k.min(self.points.len()));
+        k_nearest(&self.points, 0, query, k, 0, &mut best);
+        best.into_iter()
+            .map(|(dist2, i)| (dist2.sqrt(), &self.points[i].1))
+            .collect()
+    }
+
+    /// The values of all points within `radius` of `center` in no particular order.
+    pub fn within_radius(&self, center: Point2, radius: f32) -> Vec<&T> {
+        let mut found = vec![];
+        within_radius(&self.points, 0, center, radius * radius, 0, &mut found);
+        found.into_iter().map(|i| &self.points[i].1).collect()
+    }
+
+    // Consume the tree, producing its points.
+    fn into_points(self) -> Vec<(Point2, T)> {
+        self.points
+    }
+}
+
+impl<T> DynamicKdTree2<T> {
+    /// The minimum number of inserted points before the tree is rebuilt.
+    pub const MIN_PENDING: usize = 32;
+
+    /// Create an empty tree.
+    pub fn new() -> Self {
+        Self::build(vec![])
+    }
+
+    /// Build a balanced tree from the given points and their associated values.
+    pub fn build(points: Vec<(Point2, T)>) -> Self {
+        let tree = KdTree2::build(points);
+        let pending = vec![];
+        DynamicKdTree2 { tree, pending }
+    }
+
+    /// The number of points within the tree.
+    pub fn len(&self) -> usize {
+        self.tree.len() + self.pending.len()
+    }
+
+    /// Whether or not the tree contains no points.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Insert a point along with its value.
+    ///
+    /// The tree is rebuilt once the number of points inserted since the last rebuild exceeds both
+    /// `MIN_PENDING` and the square root of the number of points within the tree.
+    pub fn insert(&mut self, point: Point2, value: T) {
+        self.pending.push((point, value));
+        let limit = (self.tree.len() as f32).sqrt() as usize;
+        if self.pending.len() > Self::MIN_PENDING.max(limit) {
+            self.rebuild();
+        }
+    }
+
+    /// Rebuild the balanced tree with all inserted points.
+    pub fn rebuild(&mut self) {
+        if self.pending.is_empty() {
+            return;
+        }
+        let tree = std::mem::replace(&mut self.tree, KdTree2::build(vec![]));
+        let mut points = tree.into_points();
+        points.append(&mut self.pending);
+        self.tree = KdTree2::build(points);
+    }
+
+    /// Remove all points from the tree.
+    pub fn clear(&mut self) {
+        self.tree = KdTree2::build(vec![]);
+        self.pending.clear();
+    }
+
+    /// The value of the point nearest to the query along with its distance.
+    ///
+    /// Returns `None` if the tree is empty.
+    pub fn nearest(&self, query: Point2) -> Option<(f32, &T)> {
+        let pending = self
+            .pending
+            .iter()
+            .map(|(p, t)| ((*p - query).magnitude2(), t))
+            .min_by(|a, b| cmp_f32(a.0, b.0))
+            .map(|(dist2, t)| (dist2.sqrt(), t));
+        match (self.tree.nearest(query), pending) {
+            (Some(a), Some(b)) if b.0 < a.0 => Some(b),
+            (a, b) => a.or(b),
+        }
+    }
+
+    /// The values of the `k` points nearest to the query along with their distances, ordered from
+    /// nearest to furthest.
+    pub fn k_nearest(&self, query: Point2, k: usize) -> Vec<(f32, &T)> {
+        let mut nearest = self.tree.k_nearest(query, k);
+        let pending = self
+            .pending
+            .iter()
+            .map(|(p, t)| ((*p - query).magnitude(), t));
+        nearest.extend(pending);
+        nearest.sort_by(|a, b| cmp_f32(a.0, b.0));
+        nearest.truncate(k);
+        nearest
+    }
+
+    /// The values of all points within `radius` of `center` in no particular order.
+    pub fn within_radius(&self, center: Point2, radius: f32) -> Vec<&T> {
+        let mut found = self.tree.within_radius(center, radius);
+        let radius2 = radius * radius;
+        let pending = self
+            .pending
+            .iter()
+            .filter(|(p, _)| (*p - center).magnitude2() <= radius2)
+            .map(|(_, t)| t);
+        found.extend(pending);
+        found
+    }
+}
+
+impl<T> Default for DynamicKdTree2<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn cmp_f32(a: f32, b: f32) -> Ordering {
+    a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+}
+
+fn axis_value(p: Point2, depth: usize) -> f32 {
+    match depth % 2 {
+        0 => p.x,
+        _ => p.y,
+    }
+}
+
+// Recursively sort the points so that the median of each range along the axis of its depth is
+// located at the centre of the range.
+fn build<T>(points: &mut [(Point2, T)], depth: usize) {
+    if points.len() <= 1 {
+        return;
+    }
+    points.sort_by(|a, b| cmp_f32(axis_value(a.0, depth), axis_value(b.0, depth)));
+    let mid = points.len() / 2;
+    let (left, right) = points.split_at_mut(mid);
+    build(left, depth + 1);
+    build(&mut right[1..], depth + 1);
+}
+
+// The squared distance and index of the nearest point.
+fn nearest<T>(
+    points: &[(Point2, T)],
+    depth: usize,
+    query: Point2,
+    start: usize,
+    best: &mut Option<(f32, usize)>,
+) {
+    if points.is_empty() {
+        return;
+    }
+    let mid = points.len() / 2;
+    let point = points[mid].0;
+    let dist2 = (point - query).magnitude2();
+    if best.map(|(d, _)| dist2 < d).unwrap_or(true) {
+        *best = Some((dist2, start + mid));
+    }
+    let delta = axis_value(query, depth) - axis_value(point, depth);
+    let (left, right) = (&points[..mid], &points[mid + 1..]);
+    let (near, near_start, far, far_start) = match delta < 0.0 {
+        true => (left, start, right, start + mid + 1),
+        false => (right, start + mid + 1, left, start),
+    };
+    nearest(near, depth + 1, query, near_start, best);
+    if best.map(|(d, _)| delta * delta < d).unwrap_or(true) {
+        nearest(far, depth + 1, query, far_start, best);
+    }
+}
+
+// Collects the squared distances and indices of the `k` nearest points sorted by distance.
+fn k_nearest<T>(
+    points: &[(Point2, T)],
+    depth: usize,
+    query: Point2,
+    k: usize,
+    start: usize,
+    best: &mut Vec<(f32, usize)>,
+) {
+    if points.is_empty() || k == 0 {
+        return;
+    }
+    let mid = points.len() / 2;
+    let point = points[mid].0;
+    let dist2 = (point - query).magnitude2();
+    if best.len() < k || dist2 < best[best.len() - 1].0 {
+        let ix = best
+            .iter()
+            .position(|&(d, _)| dist2 < d)
+            .unwrap_or(best.len());
+        best.insert(ix, (dist2, start + mid));
+        best.truncate(k);
+    }
+    let delta = axis_value(query, depth) - axis_value(point, depth);
+    let (left, right) = (&points[..mid], &points[mid + 1..]);
+    let (near, near_start, far, far_start) = match delta < 0.0 {
+        true => (left, start, right, start + mid + 1),
+        false => (right, start + mid + 1, left, start),
+    };
+    k_nearest(near, depth + 1, query, k, near_start, best);
+    if best.len() < k || delta * delta < best[best.len() - 1].0 {
+        k_nearest(far, depth + 1, query, k, far_start, best);
+    }
+}
+
+// Collects the indices of all points within the squared radius.
+fn within_radius<T>(
+    points: &[(Point2, T)],
+    depth: usize,
+    center: Point2,
+    radius2: f32,
+    start: usize,
+    found: &mut Vec<usize>,
+) {
+    if points.is_empty() {
+        return;
+    }
+    let mid = points.len() / 2;
+    let point = points[mid].0;
+    if (point - center).magnitude2() <= radius2 {
+        found.push(start + mid);
+    }
+    let delta = axis_value(center, depth) - axis_value(point, depth);
+    let (left, right) = (&points[..mid], &points[mid + 1..]);
+    if delta <= 0.0 || delta * delta <= radius2 {
+        within_radius(left, depth + 1, center, radius2, start, found);
+    }
+    if delta >= 0.0 || delta * delta <= radius2 {
+        within_radius(right, depth + 1, center, radius2, start + mid + 1, found);
+    }
+}
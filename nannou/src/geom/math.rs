@@ -0,0 +1,161 @@
+//! Shader-style shaping functions, named after their GLSL counterparts.
+//!
+//! Each function may be applied to scalars along with `Vector2`, `Vector3` and `Vector4`, in which
+//! case the function is applied to each component.
+//!
+//! ```
+//! # use nannou::prelude::*;
+//! assert_eq!(smoothstep(0.0, 1.0, 0.5), 0.5);
+//! assert_eq!(fract(vec2(1.25, -0.25)), vec2(0.25, 0.75));
+//! ```
+
+use crate::geom::{Vector2, Vector3, Vector4};
+use crate::math::num_traits::cast;
+use crate::math::BaseFloat;
+
+/// Types to which the shaping functions are applied component-wise.
+pub trait Components: Copy {
+    /// The type of each component.
+    type Scalar: BaseFloat;
+
+    /// Produce a new value by applying the given function to each trio of matching components.
+    fn map3<F>(self, b: Self, c: Self, f: F) -> Self
+    where
+        F: FnMut(Self::Scalar, Self::Scalar, Self::Scalar) -> Self::Scalar;
+}
+
+/// Smooth Hermite interpolation between `0.0` and `1.0` as `x` moves from `edge0` to `edge1`.
+///
+/// The result is clamped to `0.0` below `edge0` and `1.0` above `edge1`.
+pub fn smoothstep<T>(edge0: T, edge1: T, x: T) -> T
+where
+    T: Components,
+{
+    edge0.map3(edge1, x, |e0, e1, x| {
+        let t = saturate((x - e0) / (e1 - e0));
+        let (two, three) = (scalar(2.0), scalar(3.0));
+        t * t * (three - two * t)
+    })
+}
+
+/// Ken Perlin's smoother variant of `smoothstep`, i.e. `6t^5 - 15t^4 + 10t^3`.
+///
+/// Unlike `smoothstep`, both the first and second derivatives are zero at the edges.
+pub fn smootherstep<T>(edge0: T, edge1: T, x: T) -> T
+where
+    T: Components,
+{
+    edge0.map3(edge1, x, |e0, e1, x| {
+        let t = saturate((x - e0) / (e1 - e0));
+        let (six, fifteen, ten) = (scalar(6.0), scalar(15.0), scalar(10.0));
+        t * t * t * (t * (t * six - fifteen) + ten)
+    })
+}
+
+/// Produces `0.0` where `x` is less than `edge` and `1.0` otherwise.
+pub fn step<T>(edge: T, x: T) -> T
+where
+    T: Components,
+{
+    edge.map3(x, x, |e, x, _| match x < e {
+        true => T::Scalar::zero(),
+        false => T::Scalar::one(),
+    })
+}
+
+/// The fractional part of `x`, i.e. `x - x.floor()`.
+///
+/// Note that unlike `Float::fract`, the result is always positive, matching GLSL.
+pub fn fract<T>(x: T) -> T
+where
+    T: Components,
+{
+    x.map3(x, x, |x, _, _| x - x.floor())
+}
+
+/// Linearly interpolate from `a` to `b` by `t`.
+pub fn mix<T>(a: T, b: T, t: T::Scalar) -> T
+where
+    T: Components,
+{
+    a.map3(b, b, |a, b, _| a + (b - a) * t)
+}
+
+/// Map the given value from the input range to the output range.
+///
+/// Like `map_range`, the output is not clamped. See `remap_clamp`.
+pub fn remap<T>(value: T, in_lo: T, in_hi: T, out_lo: T, out_hi: T) -> T
+where
+    T: Components,
+{
+    let t = value.map3(in_lo, in_hi, |v, lo, hi| (v - lo) / (hi - lo));
+    t.map3(out_lo, out_hi, |t, lo, hi| lo + (hi - lo) * t)
+}
+
+/// Map the given value from the input range to the output range, clamping the result to the
+/// output range.
+pub fn remap_clamp<T>(value: T, in_lo: T, in_hi: T, out_lo: T, out_hi: T) -> T
+where
+    T: Components,
+{
+    let t = value.map3(in_lo, in_hi, |v, lo, hi| saturate((v - lo) / (hi - lo)));
+    t.map3(out_lo, out_hi, |t, lo, hi| lo + (hi - lo) * t)
+}
+
+// Clamp the given value to the range `0.0..=1.0`.
+fn saturate<S>(s: S) -> S
+where
+    S: BaseFloat,
+{
+    s.max(S::zero()).min(S::one())
+}
+
+fn scalar<S>(f: f64) -> S
+where
+    S: BaseFloat,
+{
+    cast(f).expect("failed to cast constant")
+}
+
+impl Components for f32 {
+    type Scalar = f32;
+    fn map3<F>(self, b: Self, c: Self, mut f: F) -> Self
+    where
+        F: FnMut(f32, f32, f32) -> f32,
+    {
+        f(self, b, c)
+    }
+}
+
+impl Components for f64 {
+    type Scalar = f64;
+    fn map3<F>(self, b: Self, c: Self, mut f: F) -> Self
+    where
+        F: FnMut(f64, f64, f64) -> f64,
+    {
+        f(self, b, c)
+    }
+}
+
+macro_rules! impl_components_for_vector {
+    ($VectorN:ident { $($field:ident),+ }) => {
+        impl<S> Components for $VectorN<S>
+        where
+            S: BaseFloat,
+        {
+            type Scalar = S;
+            fn map3<F>(self, b: Self, c: Self, mut f: F) -> Self
+            where
+                F: FnMut(S, S, S) -> S,
+            {
+                $VectorN {
+                    $($field: f(self.$field, b.$field, c.$field)),+
+                }
+            }
+        }
+    };
+}
+
+impl_components_for_vector!(Vector2 { x, y });
+impl_components_for_vector!(Vector3 { x, y, z });
+impl_components_for_vector!(Vector4 { x, y, z, w });
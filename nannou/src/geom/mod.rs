@@ -10,12 +10,18 @@
 //! - A function for finding the centroid.
 
 use crate::math::num_traits::cast;
-use crate::math::{BaseFloat, EuclideanSpace};
+use crate::math::{BaseFloat, EuclideanSpace, Zero};
 use std::ops;
 
+pub mod bezier;
+pub mod catmull_rom;
+pub mod contour;
 pub mod cuboid;
+pub mod delaunay;
 pub mod ellipse;
 pub mod graph;
+pub mod kdtree;
+pub mod math;
 pub mod path;
 pub mod point;
 pub mod polygon;
@@ -23,22 +29,31 @@ pub mod quad;
 pub mod range;
 pub mod rect;
 pub mod scalar;
+pub mod solid;
 pub mod tri;
 pub mod vector;
 pub mod vertex;
+pub mod voronoi;
 
+pub use self::bezier::{Bezier, BezierPath};
+pub use self::catmull_rom::CatmullRom;
+pub use self::contour::{ContourLine, ScalarGrid};
 pub use self::cuboid::Cuboid;
 pub use self::ellipse::Ellipse;
 pub use self::graph::Graph;
+pub use self::kdtree::{DynamicKdTree2, KdTree2};
+pub use self::math::{fract, mix, remap, remap_clamp, smootherstep, smoothstep, step};
 pub use self::path::{path, Path};
 pub use self::point::{pt2, pt3, pt4, Point2, Point3, Point4};
 pub use self::polygon::Polygon;
 pub use self::quad::Quad;
 pub use self::range::{Align, Edge, Range};
-pub use self::rect::{Corner, Padding, Rect};
+pub use self::rect::{Cells, Corner, Padding, Rect};
+pub use self::solid::Solid;
 pub use self::tri::Tri;
 pub use self::vector::{vec2, vec3, vec4, Vector2, Vector3, Vector4};
 pub use self::vertex::{Vertex, Vertex2d, Vertex3d};
+pub use self::voronoi::{Voronoi, VoronoiCell, VoronoiDiagram};
 
 // General geometry utility functions
 
@@ -106,3 +121,50 @@ where
         EuclideanSpace::from_vec(total / cast(len).unwrap())
     })
 }
+
+/// The convex hull of the given vertices.
+///
+/// The hull is produced via Andrew's monotone chain variation of the Graham scan. Vertices of the
+/// resulting polygon are wound counter-clockwise and collinear points along the hull are omitted.
+pub fn convex_hull<I>(vertices: I) -> polygon::OwnedPolygon<<I::Item as Vertex>::Scalar>
+where
+    I: IntoIterator,
+    I::Item: Vertex2d,
+    <I::Item as Vertex>::Scalar: BaseFloat,
+{
+    let mut points: Vec<_> = vertices.into_iter().map(Vertex2d::point2).collect();
+    points.sort_by(|a, b| {
+        a.x.partial_cmp(&b.x)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.y.partial_cmp(&b.y).unwrap_or(std::cmp::Ordering::Equal))
+    });
+    points.dedup();
+    if points.len() < 3 {
+        return Polygon::new(points);
+    }
+
+    // Whether or not the turn from `a` through `b` to `c` is clockwise or straight.
+    let is_not_ccw = |a: Point2<_>, b: Point2<_>, c: Point2<_>| {
+        let (ab, ac) = (b - a, c - a);
+        ab.x * ac.y - ab.y * ac.x <= Zero::zero()
+    };
+    let mut hull: Vec<Point2<_>> = Vec::with_capacity(points.len() * 2);
+    // Lower hull.
+    for &p in &points {
+        while hull.len() >= 2 && is_not_ccw(hull[hull.len() - 2], hull[hull.len() - 1], p) {
+            hull.pop();
+        }
+        hull.push(p);
+    }
+    // Upper hull.
+    let lower_len = hull.len() + 1;
+    for &p in points.iter().rev().skip(1) {
+        while hull.len() >= lower_len && is_not_ccw(hull[hull.len() - 2], hull[hull.len() - 1], p) {
+            hull.pop();
+        }
+        hull.push(p);
+    }
+    // The last point is the same as the first.
+    hull.pop();
+    Polygon::new(hull)
+}
@@ -3,17 +3,57 @@
 //! This module attempts to provide abstractions around the various `Path` and `Builder` types
 //! offerred by `lyon` in a way that interoperates a little more fluidly and consistently with the
 //! rest of nannou's API.
+//!
+//! Paths may be described in the style of Cairo or the HTML canvas via the **Builder**, e.g.
+//!
+//! ```ignore
+//! let path = geom::path()
+//!     .move_to(pt2(-100.0, 0.0))
+//!     .line_to(pt2(0.0, 100.0))
+//!     .quadratic_bezier_to(pt2(100.0, 100.0), pt2(100.0, 0.0))
+//!     .arc_to(pt2(0.0, 0.0), 100.0, 0.0, -PI)
+//!     .close()
+//!     .build();
+//! draw.path().stroke().weight(4.0).events(&path).color(PLUM);
+//! ```
 
 use crate::geom::{Point2, Vector2};
+use std::f32::consts::PI;
 
 /// A wrapper around a 2D lyon path exposing a nannou-friendly API.
 pub struct Path {
     path: lyon::path::Path,
 }
 
+/// The shape used to join the offset edges either side of a vertex on the outside of a turn.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum JoinStyle {
+    /// Extend the edges until they meet.
+    ///
+    /// Falls back to `Bevel` for corners where the miter would be longer than
+    /// `JoinStyle::MITER_LIMIT` times the offset.
+    Miter,
+    /// Join the edges with a circular arc around the vertex.
+    Round,
+    /// Join the ends of the edges with a straight line.
+    Bevel,
+}
+
+/// The shape added to each end of an open sub-path when offsetting.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CapStyle {
+    /// End flat at the end of the sub-path.
+    Butt,
+    /// End with a semicircle around the end of the sub-path.
+    Round,
+    /// End flat, extended beyond the end of the sub-path by the offset.
+    Square,
+}
+
 /// A type used for building a 2D lyon path.
 pub struct Builder {
     builder: lyon::path::Builder,
+    in_sub_path: bool,
 }
 
 impl Path {
@@ -70,6 +110,49 @@ impl Path {
     pub fn merge(&self, other: &Self) -> Self {
         self.path.merge(&other.path).into()
     }
+
+    /// Offset the outline of the path outward by `amount`, or inward if `amount` is negative.
+    ///
+    /// Curves are first flattened, after which the edges of each closed sub-path are moved by
+    /// `amount` along their normals and joined around each vertex according to the `join_style`.
+    /// Each closed sub-path is offset relative to its own winding. Open sub-paths are expanded
+    /// to the closed outline of a stroke `amount` either side of the sub-path, with its ends
+    /// shaped according to the `cap_style`.
+    ///
+    /// Offsetting may produce a self-intersecting outline, e.g. when insetting a shape with a
+    /// region narrower than the inset. The outline is split at each intersection, discarding the
+    /// loops that turned inside out. As a result, each sub-path may produce any number of paths,
+    /// each of which is a single closed polygon.
+    pub fn offset(&self, amount: f32, join_style: JoinStyle, cap_style: CapStyle) -> Vec<Path> {
+        let mut paths = vec![];
+        for (points, closed) in flatten(&self.path, OFFSET_TOLERANCE) {
+            let polygons = if closed {
+                offset_polygon(&points, amount, join_style)
+            } else {
+                offset_polyline(&points, amount.abs(), join_style, cap_style)
+            };
+            let polygons = polygons.into_iter().filter(|p| p.len() >= 3);
+            paths.extend(polygons.map(|p| Builder::new().polygon(&p).build()));
+        }
+        paths
+    }
+
+    /// Offset the outline of the path inward by `amount`.
+    ///
+    /// Short-hand for `offset(-amount, JoinStyle::Miter, CapStyle::Butt)`.
+    pub fn inset(&self, amount: f32) -> Vec<Path> {
+        self.offset(-amount, JoinStyle::Miter, CapStyle::Butt)
+    }
+
+    // Flatten each sub-path, producing its points and whether or not it is closed.
+    pub(crate) fn flatten(&self, tolerance: f32) -> Vec<(Vec<Point2>, bool)> {
+        flatten(&self.path, tolerance)
+    }
+}
+
+impl JoinStyle {
+    /// The maximum length of a miter join as a multiple of the offset, matching the SVG default.
+    pub const MITER_LIMIT: f32 = 4.0;
 }
 
 impl Builder {
@@ -97,36 +180,68 @@ impl Builder {
     ///
     /// If the current sub-path contains edges, this ends the sub-path without closing it.
     pub fn move_to(mut self, to: Point2) -> Self {
-        self.builder.move_to(to.into());
+        lyon::path::builder::FlatPathBuilder::move_to(&mut self, to.into());
         self
     }
 
     /// Adds a line segment to the current sub-path and sets the current position.
     pub fn line_to(mut self, to: Point2) -> Self {
-        self.builder.line_to(to.into());
+        lyon::path::builder::FlatPathBuilder::line_to(&mut self, to.into());
         self
     }
 
     /// Closes the current sub path and sets the current position to the first position of the
     /// current sub-path.
     pub fn close(mut self) -> Self {
-        self.builder.close();
+        lyon::path::builder::FlatPathBuilder::close(&mut self);
         self
     }
 
     /// Add a quadratic bezier curve to the path.
     pub fn quadratic_bezier_to(mut self, ctrl: Point2, to: Point2) -> Self {
-        self.builder.quadratic_bezier_to(ctrl.into(), to.into());
+        lyon::path::builder::PathBuilder::quadratic_bezier_to(&mut self, ctrl.into(), to.into());
         self
     }
 
+    /// Short-hand for the `quadratic_bezier_to` method.
+    pub fn quad_bezier_to(self, ctrl: Point2, to: Point2) -> Self {
+        self.quadratic_bezier_to(ctrl, to)
+    }
+
     /// Add a cubic bezier curve to the path.
     pub fn cubic_bezier_to(mut self, ctrl1: Point2, ctrl2: Point2, to: Point2) -> Self {
-        self.builder
-            .cubic_bezier_to(ctrl1.into(), ctrl2.into(), to.into());
+        lyon::path::builder::PathBuilder::cubic_bezier_to(
+            &mut self,
+            ctrl1.into(),
+            ctrl2.into(),
+            to.into(),
+        );
         self
     }
 
+    /// Add a circular arc around `center` from `start_angle` to `end_angle` in radians.
+    ///
+    /// Angles are measured counter-clockwise from the positive *x* axis, and the arc is drawn
+    /// clockwise if `end_angle` is less than `start_angle`. In the style of the HTML canvas, a
+    /// line is added from the current position to the start of the arc if a sub-path is in
+    /// progress, otherwise a new sub-path begins at the start of the arc.
+    pub fn arc_to(self, center: Point2, radius: f32, start_angle: f32, end_angle: f32) -> Self {
+        let start = Point2 {
+            x: center.x + radius * start_angle.cos(),
+            y: center.y + radius * start_angle.sin(),
+        };
+        let builder = if self.in_sub_path {
+            self.line_to(start)
+        } else {
+            self.move_to(start)
+        };
+        let radii = Vector2 {
+            x: radius,
+            y: radius,
+        };
+        builder.arc(center, radii, end_angle - start_angle, 0.0)
+    }
+
     /// Add an arc to the path.
     pub fn arc(
         mut self,
@@ -135,7 +250,8 @@ impl Builder {
         sweep_angle_radians: f32,
         x_rotation_radians: f32,
     ) -> Self {
-        self.builder.arc(
+        lyon::path::builder::PathBuilder::arc(
+            &mut self,
             center.into(),
             radii.into(),
             lyon::math::Angle::radians(sweep_angle_radians),
@@ -146,7 +262,7 @@ impl Builder {
 
     /// Add a closed polygon.
     pub fn polygon(mut self, points: &[Point2]) -> Self {
-        self.builder.polygon(point_slice_nannou_to_lyon(points));
+        lyon::path::builder::PolygonBuilder::polygon(&mut self, point_slice_nannou_to_lyon(points));
         self
     }
 
@@ -166,6 +282,9 @@ impl Builder {
     }
 
     /// Mutable access to the inner `lyon::path::Builder`.
+    ///
+    /// Note that the **Builder** cannot track whether or not a sub-path is in progress for
+    /// commands submitted directly to the inner builder, e.g. for the sake of `arc_to`.
     pub fn inner_mut(&mut self) -> &mut lyon::path::Builder {
         &mut self.builder
     }
@@ -181,20 +300,24 @@ impl lyon::path::builder::Build for Builder {
     }
 
     fn build_and_reset(&mut self) -> Self::PathType {
+        self.in_sub_path = false;
         self.builder.build_and_reset().into()
     }
 }
 
 impl lyon::path::builder::FlatPathBuilder for Builder {
     fn move_to(&mut self, to: lyon::math::Point) {
+        self.in_sub_path = true;
         self.builder.move_to(to);
     }
 
     fn line_to(&mut self, to: lyon::math::Point) {
+        self.in_sub_path = true;
         self.builder.line_to(to);
     }
 
     fn close(&mut self) {
+        self.in_sub_path = false;
         self.builder.close();
     }
 
@@ -205,6 +328,7 @@ impl lyon::path::builder::FlatPathBuilder for Builder {
 
 impl lyon::path::builder::PathBuilder for Builder {
     fn quadratic_bezier_to(&mut self, ctrl: lyon::math::Point, to: lyon::math::Point) {
+        self.in_sub_path = true;
         self.builder.quadratic_bezier_to(ctrl, to);
     }
 
@@ -214,6 +338,7 @@ impl lyon::path::builder::PathBuilder for Builder {
         ctrl2: lyon::math::Point,
         to: lyon::math::Point,
     ) {
+        self.in_sub_path = true;
         self.builder.cubic_bezier_to(ctrl1, ctrl2, to);
     }
 
@@ -224,16 +349,22 @@ impl lyon::path::builder::PathBuilder for Builder {
         sweep_angle: lyon::math::Angle,
         x_rotation: lyon::math::Angle,
     ) {
+        self.in_sub_path = true;
         self.builder.arc(center, radii, sweep_angle, x_rotation);
     }
 
     fn path_event(&mut self, event: lyon::path::PathEvent) {
+        self.in_sub_path = match event {
+            lyon::path::PathEvent::End { .. } => false,
+            _ => true,
+        };
         self.builder.path_event(event);
     }
 }
 
 impl lyon::path::builder::PolygonBuilder for Builder {
     fn polygon(&mut self, points: &[lyon::math::Point]) {
+        self.in_sub_path = false;
         self.builder.polygon(points);
     }
 }
@@ -275,7 +406,11 @@ impl From<lyon::path::Path> for Path {
 
 impl From<lyon::path::Builder> for Builder {
     fn from(builder: lyon::path::Builder) -> Self {
-        Builder { builder }
+        let in_sub_path = false;
+        Builder {
+            builder,
+            in_sub_path,
+        }
     }
 }
 
@@ -309,6 +444,299 @@ pub fn path_with_capacity(points: usize, edges: usize) -> Builder {
     Builder::with_capacity(points, edges)
 }
 
+// Offsetting
+
+// The tolerance with which curves and round joins are flattened while offsetting.
+const OFFSET_TOLERANCE: f32 = 0.1;
+
+// Flatten each sub-path of the given path, producing its points and whether or not it is closed.
+fn flatten(path: &lyon::path::Path, tolerance: f32) -> Vec<(Vec<Point2>, bool)> {
+    flatten_events(path.iter(), tolerance)
+}
+
+// Flatten each sub-path described by the given events.
+pub(crate) fn flatten_events<I>(events: I, tolerance: f32) -> Vec<(Vec<Point2>, bool)>
+where
+    I: IntoIterator<Item = lyon::path::PathEvent>,
+{
+    let mut sub_paths = vec![];
+    let mut points: Vec<Point2> = vec![];
+    for event in events {
+        match event {
+            lyon::path::PathEvent::Begin { at } => points.push(at.into()),
+            lyon::path::PathEvent::Line { to, .. } => points.push(to.into()),
+            lyon::path::PathEvent::Quadratic { from, ctrl, to } => {
+                let curve = lyon::geom::QuadraticBezierSegment { from, ctrl, to };
+                points.extend(curve.flattened(tolerance).map(Point2::from));
+            }
+            lyon::path::PathEvent::Cubic {
+                from,
+                ctrl1,
+                ctrl2,
+                to,
+            } => {
+                let curve = lyon::geom::CubicBezierSegment {
+                    from,
+                    ctrl1,
+                    ctrl2,
+                    to,
+                };
+                points.extend(curve.flattened(tolerance).map(Point2::from));
+            }
+            lyon::path::PathEvent::End { close, .. } => {
+                let mut points = std::mem::replace(&mut points, vec![]);
+                points.dedup_by(|a, b| (*a - *b).magnitude2() <= std::f32::EPSILON);
+                while close && points.len() > 1 {
+                    match (points.first(), points.last()) {
+                        (Some(&a), Some(&b)) if (a - b).magnitude2() <= std::f32::EPSILON => {
+                            points.pop();
+                        }
+                        _ => break,
+                    }
+                }
+                sub_paths.push((points, close));
+            }
+        }
+    }
+    sub_paths
+}
+
+// Offset the closed polygon outward by `amount` relative to its winding.
+fn offset_polygon(points: &[Point2], amount: f32, join_style: JoinStyle) -> Vec<Vec<Point2>> {
+    let area = signed_area(points);
+    if points.len() < 3 || area == 0.0 {
+        return vec![];
+    }
+    // The left of each edge is inside a counter-clockwise polygon.
+    let left = if area > 0.0 { -amount } else { amount };
+    let outline = offset_side(points, true, left, join_style);
+    split_self_intersections(outline)
+        .into_iter()
+        .filter(|p| signed_area(p).signum() == area.signum())
+        .filter(|p| is_offset_by(p, points, true, amount))
+        .collect()
+}
+
+// Produce the outline of a stroke `amount` either side of the open polyline.
+fn offset_polyline(
+    points: &[Point2],
+    amount: f32,
+    join_style: JoinStyle,
+    cap_style: CapStyle,
+) -> Vec<Vec<Point2>> {
+    if points.len() < 2 || amount == 0.0 {
+        return vec![];
+    }
+    let n = points.len();
+    let start_dir = (points[1] - points[0]).normalize();
+    let end_dir = (points[n - 1] - points[n - 2]).normalize();
+    let reversed: Vec<_> = points.iter().rev().cloned().collect();
+    let mut outline = offset_side(points, false, amount, join_style);
+    add_cap(&mut outline, points[n - 1], end_dir, amount, cap_style);
+    outline.extend(offset_side(&reversed, false, amount, join_style));
+    add_cap(&mut outline, points[0], -start_dir, amount, cap_style);
+
+    // The outline runs clockwise around the stroke, so counter-clockwise loops are inside out.
+    split_self_intersections(outline)
+        .into_iter()
+        .filter(|p| signed_area(p) < 0.0)
+        .filter(|p| is_offset_by(p, points, false, amount))
+        .map(|mut p| {
+            p.reverse();
+            p
+        })
+        .collect()
+}
+
+// Offset each edge to the left by `amount`, joining the edges around each vertex.
+fn offset_side(points: &[Point2], closed: bool, amount: f32, join_style: JoinStyle) -> Vec<Point2> {
+    let n = points.len();
+    let edges = if closed { n } else { n - 1 };
+    let dir = |i: usize| (points[(i + 1) % n] - points[i]).normalize();
+    let mut outline = vec![];
+    for i in 0..edges {
+        let d1 = dir(i);
+        if closed || i > 0 {
+            let d0 = dir((i + n - 1) % n);
+            add_join(&mut outline, points[i], d0, d1, amount, join_style);
+        } else {
+            outline.push(points[i] + left_normal(d1) * amount);
+        }
+        if !closed && i == edges - 1 {
+            outline.push(points[i + 1] + left_normal(d1) * amount);
+        }
+    }
+    outline
+}
+
+// Join the end of the edge along `d0` to the start of the edge along `d1` around `p`.
+fn add_join(
+    outline: &mut Vec<Point2>,
+    p: Point2,
+    d0: Vector2,
+    d1: Vector2,
+    amount: f32,
+    join_style: JoinStyle,
+) {
+    let (n0, n1) = (left_normal(d0), left_normal(d1));
+    let (a, b) = (p + n0 * amount, p + n1 * amount);
+    let turn = cross(d0, d1);
+    if turn == 0.0 && d0.dot(d1) > 0.0 {
+        outline.push(a);
+        return;
+    }
+
+    // On the inside of the turn the offset edges overlap, producing a loop that is later removed.
+    let outside = turn * amount < 0.0;
+    outline.push(a);
+    if outside {
+        match join_style {
+            JoinStyle::Bevel => (),
+            JoinStyle::Miter => {
+                let bisector = n0 + n1;
+                let cos_half = bisector.magnitude() * 0.5;
+                if cos_half * JoinStyle::MITER_LIMIT >= 1.0 {
+                    outline.push(p + bisector.normalize() * (amount / cos_half));
+                }
+            }
+            JoinStyle::Round => {
+                let (v0, v1) = (n0 * amount, n1 * amount);
+                let sweep = cross(v0, v1).atan2(v0.dot(v1));
+                add_arc(outline, p, v0, sweep);
+            }
+        }
+    }
+    outline.push(b);
+}
+
+// Add the cap between the left and right sides of a stroke ending at `p` along `dir`.
+fn add_cap(outline: &mut Vec<Point2>, p: Point2, dir: Vector2, amount: f32, cap_style: CapStyle) {
+    let n = left_normal(dir);
+    match cap_style {
+        CapStyle::Butt => (),
+        CapStyle::Square => {
+            outline.push(p + (n + dir) * amount);
+            outline.push(p + (dir - n) * amount);
+        }
+        CapStyle::Round => add_arc(outline, p, n * amount, -PI),
+    }
+}
+
+// Add the points between the ends of the arc around `center`, beginning at `center + start` and
+// sweeping `sweep` radians.
+fn add_arc(outline: &mut Vec<Point2>, center: Point2, start: Vector2, sweep: f32) {
+    let radius = start.magnitude();
+    let step = if radius > OFFSET_TOLERANCE {
+        2.0 * (1.0 - OFFSET_TOLERANCE / radius).acos()
+    } else {
+        PI
+    };
+    let steps = (sweep.abs() / step).ceil().max(1.0) as usize;
+    for k in 1..steps {
+        let (sin, cos) = (sweep * k as f32 / steps as f32).sin_cos();
+        let v = Vector2 {
+            x: start.x * cos - start.y * sin,
+            y: start.x * sin + start.y * cos,
+        };
+        outline.push(center + v);
+    }
+}
+
+// Whether or not every point of the loop is at least `amount` from the edges of the original.
+//
+// Loops that turned inside out but retained their winding, e.g. the inset of a square by more than
+// half of its width, are closer to the original than the offset.
+fn is_offset_by(points: &[Point2], original: &[Point2], closed: bool, amount: f32) -> bool {
+    let n = original.len();
+    let edges = if closed { n } else { n - 1 };
+    let min_distance = amount.abs() * 0.999;
+    points.iter().all(|&p| {
+        (0..edges).all(|i| {
+            let (a, b) = (original[i], original[(i + 1) % n]);
+            let ab = b - a;
+            let t = ((p - a).dot(ab) / ab.magnitude2()).max(0.0).min(1.0);
+            (p - (a + ab * t)).magnitude() >= min_distance
+        })
+    })
+}
+
+// Split the polygon into simple loops at each point where its edges intersect.
+fn split_self_intersections(points: Vec<Point2>) -> Vec<Vec<Point2>> {
+    let mut pending = vec![points];
+    let mut loops = vec![];
+    while let Some(points) = pending.pop() {
+        match first_self_intersection(&points) {
+            None => loops.push(points),
+            Some((i, j, x)) => {
+                let mut a = vec![x];
+                a.extend_from_slice(&points[i + 1..=j]);
+                let mut b = points[j + 1..].to_vec();
+                b.extend_from_slice(&points[..=i]);
+                b.push(x);
+                pending.push(a);
+                pending.push(b);
+            }
+        }
+    }
+    loops
+}
+
+// The first pair of non-adjacent edges that intersect along with the point of intersection.
+fn first_self_intersection(points: &[Point2]) -> Option<(usize, usize, Point2)> {
+    let n = points.len();
+    for i in 0..n {
+        for j in i + 2..n {
+            if i == 0 && j == n - 1 {
+                continue;
+            }
+            let (a0, a1) = (points[i], points[(i + 1) % n]);
+            let (b0, b1) = (points[j], points[(j + 1) % n]);
+            if let Some(x) = segment_intersection(a0, a1, b0, b1) {
+                return Some((i, j, x));
+            }
+        }
+    }
+    None
+}
+
+// The point at which the two segments cross, excluding their ends.
+fn segment_intersection(a0: Point2, a1: Point2, b0: Point2, b1: Point2) -> Option<Point2> {
+    const EPSILON: f32 = 1e-6;
+    let (r, s) = (a1 - a0, b1 - b0);
+    let denom = cross(r, s);
+    if denom.abs() <= std::f32::EPSILON {
+        return None;
+    }
+    let qp = b0 - a0;
+    let t = cross(qp, s) / denom;
+    let u = cross(qp, r) / denom;
+    let within = |t: f32| t > EPSILON && t < 1.0 - EPSILON;
+    if within(t) && within(u) {
+        Some(a0 + r * t)
+    } else {
+        None
+    }
+}
+
+fn signed_area(points: &[Point2]) -> f32 {
+    let n = points.len();
+    let sum: f32 = (0..n)
+        .map(|i| {
+            let (a, b) = (points[i], points[(i + 1) % n]);
+            a.x * b.y - b.x * a.y
+        })
+        .sum();
+    sum * 0.5
+}
+
+fn left_normal(v: Vector2) -> Vector2 {
+    Vector2 { x: -v.y, y: v.x }
+}
+
+fn cross(a: Vector2, b: Vector2) -> f32 {
+    a.x * b.y - a.y * b.x
+}
+
 // Conversions between slice types.
 //
 // The following conversions are safe as both `Point2` and `lyon::path::Point` have the same size,
@@ -1,5 +1,7 @@
 use crate::geom::tri::{self, Tri};
-use crate::geom::{Cuboid, Rect, Vertex, Vertex2d, Vertex3d};
+use crate::geom::{Cuboid, Point2, Rect, Vertex, Vertex2d, Vertex3d};
+use crate::math::num_traits::cast;
+use crate::math::BaseFloat;
 
 /// A simple type wrapper around a list of points that describe a polygon.
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -150,3 +152,785 @@ impl Iterator for TriangleIndices {
         }
     }
 }
+
+// Boolean operations.
+
+/// The polygon type produced by the boolean operations.
+pub type OwnedPolygon<S> = Polygon<std::vec::IntoIter<Point2<S>>>;
+
+/// The boolean operations that may be applied between a pair of polygons.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum BooleanOp {
+    /// The area covered by either polygon.
+    Union,
+    /// The area covered by both polygons.
+    Intersection,
+    /// The area covered by the first polygon but not the second.
+    Difference,
+    /// The area covered by exactly one of the two polygons.
+    Xor,
+}
+
+impl<I> Polygon<I>
+where
+    I: Iterator,
+    I::Item: Vertex2d,
+    <I::Item as Vertex>::Scalar: BaseFloat,
+{
+    /// The area covered by either `self` or `other`.
+    ///
+    /// See the `boolean` function for details on how the result is produced.
+    pub fn union<J>(self, other: Polygon<J>) -> Vec<OwnedPolygon<<I::Item as Vertex>::Scalar>>
+    where
+        J: Iterator<Item = I::Item>,
+    {
+        self.boolean(BooleanOp::Union, other)
+    }
+
+    /// The area covered by both `self` and `other`.
+    ///
+    /// See the `boolean` function for details on how the result is produced.
+    pub fn intersection<J>(
+        self,
+        other: Polygon<J>,
+    ) -> Vec<OwnedPolygon<<I::Item as Vertex>::Scalar>>
+    where
+        J: Iterator<Item = I::Item>,
+    {
+        self.boolean(BooleanOp::Intersection, other)
+    }
+
+    /// The area covered by `self` but not by `other`.
+    ///
+    /// See the `boolean` function for details on how the result is produced.
+    pub fn difference<J>(self, other: Polygon<J>) -> Vec<OwnedPolygon<<I::Item as Vertex>::Scalar>>
+    where
+        J: Iterator<Item = I::Item>,
+    {
+        self.boolean(BooleanOp::Difference, other)
+    }
+
+    /// The area covered by exactly one of `self` and `other`.
+    ///
+    /// See the `boolean` function for details on how the result is produced.
+    pub fn xor<J>(self, other: Polygon<J>) -> Vec<OwnedPolygon<<I::Item as Vertex>::Scalar>>
+    where
+        J: Iterator<Item = I::Item>,
+    {
+        self.boolean(BooleanOp::Xor, other)
+    }
+
+    /// Apply the given boolean operation between `self` and `other`.
+    ///
+    /// See the `boolean` function for details on how the result is produced.
+    pub fn boolean<J>(
+        self,
+        op: BooleanOp,
+        other: Polygon<J>,
+    ) -> Vec<OwnedPolygon<<I::Item as Vertex>::Scalar>>
+    where
+        J: Iterator<Item = I::Item>,
+    {
+        let a: Vec<_> = self.points.map(Vertex2d::point2).collect();
+        let b: Vec<_> = other.points.map(Vertex2d::point2).collect();
+        boolean(op, &a, &b).into_iter().map(Polygon::new).collect()
+    }
+}
+
+/// The area covered by either `a` or `b`.
+pub fn union<S>(a: &[Point2<S>], b: &[Point2<S>]) -> Vec<Vec<Point2<S>>>
+where
+    S: BaseFloat,
+{
+    boolean(BooleanOp::Union, a, b)
+}
+
+/// The area covered by both `a` and `b`.
+pub fn intersection<S>(a: &[Point2<S>], b: &[Point2<S>]) -> Vec<Vec<Point2<S>>>
+where
+    S: BaseFloat,
+{
+    boolean(BooleanOp::Intersection, a, b)
+}
+
+/// The area covered by `a` but not by `b`.
+pub fn difference<S>(a: &[Point2<S>], b: &[Point2<S>]) -> Vec<Vec<Point2<S>>>
+where
+    S: BaseFloat,
+{
+    boolean(BooleanOp::Difference, a, b)
+}
+
+/// The area covered by exactly one of `a` and `b`.
+pub fn xor<S>(a: &[Point2<S>], b: &[Point2<S>]) -> Vec<Vec<Point2<S>>>
+where
+    S: BaseFloat,
+{
+    boolean(BooleanOp::Xor, a, b)
+}
+
+/// Apply the given boolean operation between the simple polygons described by `a` and `b`.
+///
+/// The intersecting case is handled via the Greiner-Hormann clipping algorithm. Degenerate cases
+/// where a vertex of one polygon lies on an edge of the other (including polygons that only touch)
+/// are resolved by nudging `b` by a tiny amount relative to the size of the polygons before
+/// clipping. Disjoint polygons and polygons that wholly contain one another are handled
+/// separately.
+///
+/// The result is a list of polygons wound counter-clockwise. In the case that the result contains
+/// a hole (e.g. the difference between a polygon and another that it wholly contains), the hole is
+/// returned as an additional polygon wound clockwise. Filling the result with the even-odd fill
+/// rule will render holes correctly.
+///
+/// `Rect`s and `Ellipse`s may be used by first converting them to polygons, e.g. via
+/// `Polygon::new(rect.corners_iter())` or `Polygon::new(ellipse.circumference())`.
+pub fn boolean<S>(op: BooleanOp, a: &[Point2<S>], b: &[Point2<S>]) -> Vec<Vec<Point2<S>>>
+where
+    S: BaseFloat,
+{
+    if a.len() < 3 || b.len() < 3 {
+        return match op {
+            BooleanOp::Intersection => vec![],
+            BooleanOp::Difference => non_empty(a),
+            BooleanOp::Union | BooleanOp::Xor => {
+                non_empty(a).into_iter().chain(non_empty(b)).collect()
+            }
+        };
+    }
+
+    // Find the intersections, nudging `b` whenever a degenerate intersection is encountered.
+    const MAX_ATTEMPTS: usize = 8;
+    let scale = polygon_scale(a).max(polygon_scale(b));
+    let mut b_nudged = b.to_vec();
+    let mut attempt = 0;
+    let intersections = loop {
+        match find_intersections(a, &b_nudged) {
+            Some(intersections) => break intersections,
+            None if attempt < MAX_ATTEMPTS => {
+                attempt += 1;
+                let nudge = nudge_offset(scale, attempt);
+                b_nudged = b.iter().map(|&p| p + nudge).collect();
+            }
+            // Give up and treat the remaining degeneracies as non-intersecting.
+            None => break vec![],
+        }
+    };
+    let b = &b_nudged[..];
+
+    let polygons = if intersections.is_empty() {
+        boolean_without_intersections(op, a, b)
+    } else {
+        boolean_with_intersections(op, a, b, &intersections)
+    };
+    orient(polygons)
+}
+
+// Produce the result for a pair of polygons whose edges intersect.
+fn boolean_with_intersections<S>(
+    op: BooleanOp,
+    a: &[Point2<S>],
+    b: &[Point2<S>],
+    intersections: &[Intersection<S>],
+) -> Vec<Vec<Point2<S>>>
+where
+    S: BaseFloat,
+{
+    match op {
+        BooleanOp::Xor => {
+            let mut result = clip(a, b, intersections, false, true);
+            let swapped: Vec<_> = intersections
+                .iter()
+                .map(|i| Intersection {
+                    a_edge: i.b_edge,
+                    a_alpha: i.b_alpha,
+                    b_edge: i.a_edge,
+                    b_alpha: i.a_alpha,
+                    point: i.point,
+                })
+                .collect();
+            result.extend(clip(b, a, &swapped, false, true));
+            result
+        }
+        BooleanOp::Union => clip(a, b, intersections, false, false),
+        BooleanOp::Intersection => clip(a, b, intersections, true, true),
+        BooleanOp::Difference => clip(a, b, intersections, false, true),
+    }
+}
+
+// An intersection between an edge of polygon `a` and an edge of polygon `b`.
+#[derive(Copy, Clone, Debug)]
+struct Intersection<S> {
+    a_edge: usize,
+    a_alpha: S,
+    b_edge: usize,
+    b_alpha: S,
+    point: Point2<S>,
+}
+
+// A vertex within the linked lists used by the Greiner-Hormann algorithm.
+#[derive(Copy, Clone, Debug)]
+struct Node<S> {
+    point: Point2<S>,
+    next: usize,
+    prev: usize,
+    neighbour: Option<usize>,
+    entry: bool,
+    visited: bool,
+}
+
+fn non_empty<S: Copy>(points: &[Point2<S>]) -> Vec<Vec<Point2<S>>> {
+    if points.is_empty() {
+        vec![]
+    } else {
+        vec![points.to_vec()]
+    }
+}
+
+// Twice the signed area of the polygon. Positive for counter-clockwise winding.
+fn signed_area_x2<S: BaseFloat>(points: &[Point2<S>]) -> S {
+    let mut area = S::zero();
+    for (i, &p) in points.iter().enumerate() {
+        let q = points[(i + 1) % points.len()];
+        area = area + cross(p, q);
+    }
+    area
+}
+
+// Wind each polygon counter-clockwise, or clockwise if it describes a hole within another.
+fn orient<S: BaseFloat>(mut polygons: Vec<Vec<Point2<S>>>) -> Vec<Vec<Point2<S>>> {
+    let holes: Vec<bool> = polygons
+        .iter()
+        .enumerate()
+        .map(|(i, polygon)| {
+            let containing = polygons
+                .iter()
+                .enumerate()
+                .filter(|&(j, other)| {
+                    // Vertices shared between results lie on both boundaries so are skipped.
+                    i != j
+                        && polygon
+                            .iter()
+                            .find(|p| !other.contains(p))
+                            .map(|&p| point_in_polygon(p, other))
+                            .unwrap_or(false)
+                })
+                .count();
+            containing % 2 == 1
+        })
+        .collect();
+    for (polygon, is_hole) in polygons.iter_mut().zip(holes) {
+        let ccw = signed_area_x2(polygon) > S::zero();
+        if ccw == is_hole {
+            polygon.reverse();
+        }
+    }
+    polygons
+}
+
+fn cross<S: BaseFloat>(a: Point2<S>, b: Point2<S>) -> S {
+    a.x * b.y - a.y * b.x
+}
+
+// The size of the largest dimension of the polygon's bounding rectangle.
+fn polygon_scale<S: BaseFloat>(points: &[Point2<S>]) -> S {
+    super::bounding_rect(points.iter().cloned())
+        .map(|r| r.w().max(r.h()))
+        .unwrap_or_else(S::zero)
+}
+
+// An offset that grows with each attempt and avoids being aligned with either axis.
+fn nudge_offset<S: BaseFloat>(scale: S, attempt: usize) -> Point2<S> {
+    let base: S = cast(1e-5).unwrap();
+    let attempt: S = cast(attempt).unwrap();
+    let magnitude = scale.max(S::one()) * base * attempt;
+    let y_ratio: S = cast(0.618_034).unwrap();
+    Point2 {
+        x: magnitude,
+        y: magnitude * y_ratio,
+    }
+}
+
+// Even-odd point in polygon test.
+fn point_in_polygon<S: BaseFloat>(p: Point2<S>, points: &[Point2<S>]) -> bool {
+    let mut inside = false;
+    let mut j = points.len() - 1;
+    for (i, &pi) in points.iter().enumerate() {
+        let pj = points[j];
+        if (pi.y > p.y) != (pj.y > p.y) && p.x < (pj.x - pi.x) * (p.y - pi.y) / (pj.y - pi.y) + pi.x
+        {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+// Find all proper intersections between the edges of `a` and `b`.
+//
+// Returns `None` if a degenerate intersection is found, e.g. a vertex lying on an edge or a pair
+// of overlapping collinear edges.
+fn find_intersections<S>(a: &[Point2<S>], b: &[Point2<S>]) -> Option<Vec<Intersection<S>>>
+where
+    S: BaseFloat,
+{
+    let eps: S = cast(1e-6).unwrap();
+    let zero = S::zero();
+    let one = S::one();
+    let mut intersections = vec![];
+    for (i, &p) in a.iter().enumerate() {
+        let r = a[(i + 1) % a.len()] - p;
+        for (j, &q) in b.iter().enumerate() {
+            let s = b[(j + 1) % b.len()] - q;
+            let qp = q - p;
+            let denom = cross(r, s);
+            let r_len = r.x.abs() + r.y.abs();
+            let s_len = s.x.abs() + s.y.abs();
+            if denom.abs() <= eps * r_len * s_len {
+                // Parallel edges are only degenerate if they are collinear and overlapping.
+                let collinear = cross(qp, r).abs() <= eps * r_len * (qp.x.abs() + qp.y.abs());
+                if collinear && r_len > zero {
+                    let rr = r.x * r.x + r.y * r.y;
+                    let t0 = (qp.x * r.x + qp.y * r.y) / rr;
+                    let t1 = t0 + (s.x * r.x + s.y * r.y) / rr;
+                    if t0.min(t1) <= one + eps && t0.max(t1) >= -eps {
+                        return None;
+                    }
+                }
+                continue;
+            }
+            let t = cross(qp, s) / denom;
+            let u = cross(qp, r) / denom;
+            let in_range = |x: S| x >= -eps && x <= one + eps;
+            if !in_range(t) || !in_range(u) {
+                continue;
+            }
+            let near_end = |x: S| x <= eps || x >= one - eps;
+            if near_end(t) || near_end(u) {
+                return None;
+            }
+            intersections.push(Intersection {
+                a_edge: i,
+                a_alpha: t,
+                b_edge: j,
+                b_alpha: u,
+                point: p + r * t,
+            });
+        }
+    }
+    Some(intersections)
+}
+
+// Produce the result for a pair of polygons whose edges do not intersect.
+fn boolean_without_intersections<S>(
+    op: BooleanOp,
+    a: &[Point2<S>],
+    b: &[Point2<S>],
+) -> Vec<Vec<Point2<S>>>
+where
+    S: BaseFloat,
+{
+    let a_in_b = point_in_polygon(a[0], b);
+    let b_in_a = point_in_polygon(b[0], a);
+    match op {
+        BooleanOp::Union if a_in_b => vec![b.to_vec()],
+        BooleanOp::Union if b_in_a => vec![a.to_vec()],
+        BooleanOp::Union => vec![a.to_vec(), b.to_vec()],
+        BooleanOp::Intersection if a_in_b => vec![a.to_vec()],
+        BooleanOp::Intersection if b_in_a => vec![b.to_vec()],
+        BooleanOp::Intersection => vec![],
+        BooleanOp::Difference if a_in_b => vec![],
+        BooleanOp::Difference if b_in_a => vec![a.to_vec(), b.to_vec()],
+        BooleanOp::Difference => vec![a.to_vec()],
+        BooleanOp::Xor if a_in_b => vec![b.to_vec(), a.to_vec()],
+        BooleanOp::Xor if b_in_a => vec![a.to_vec(), b.to_vec()],
+        BooleanOp::Xor => vec![a.to_vec(), b.to_vec()],
+    }
+}
+
+// Insert the polygon's vertices and intersections into `nodes` as a circular linked list.
+//
+// Returns the index of each intersection's node in the order of the given intersections.
+fn insert_polygon<S, F>(
+    nodes: &mut Vec<Node<S>>,
+    points: &[Point2<S>],
+    intersections: &[Intersection<S>],
+    edge_and_alpha: F,
+) -> Vec<usize>
+where
+    S: BaseFloat,
+    F: Fn(&Intersection<S>) -> (usize, S),
+{
+    let mut order: Vec<usize> = (0..intersections.len()).collect();
+    order.sort_by(|&x, &y| {
+        let (xe, xa) = edge_and_alpha(&intersections[x]);
+        let (ye, ya) = edge_and_alpha(&intersections[y]);
+        xe.cmp(&ye)
+            .then(xa.partial_cmp(&ya).unwrap_or(std::cmp::Ordering::Equal))
+    });
+
+    let start = nodes.len();
+    let mut intersection_nodes = vec![0; intersections.len()];
+    let mut order = order.into_iter().peekable();
+    let push = |nodes: &mut Vec<Node<S>>, point| {
+        let index = nodes.len();
+        nodes.push(Node {
+            point,
+            next: index + 1,
+            prev: index.wrapping_sub(1),
+            neighbour: None,
+            entry: false,
+            visited: false,
+        });
+        index
+    };
+    for (edge, &point) in points.iter().enumerate() {
+        push(nodes, point);
+        while let Some(&ix) = order.peek() {
+            if edge_and_alpha(&intersections[ix]).0 != edge {
+                break;
+            }
+            order.next();
+            intersection_nodes[ix] = push(nodes, intersections[ix].point);
+        }
+    }
+    let end = nodes.len() - 1;
+    nodes[start].prev = end;
+    nodes[end].next = start;
+    intersection_nodes
+}
+
+// Clip `a` against `b` using the Greiner-Hormann algorithm.
+//
+// `a_forwards` and `b_forwards` describe the direction in which each polygon is traversed upon
+// entering the other and determine the operation performed.
+fn clip<S>(
+    a: &[Point2<S>],
+    b: &[Point2<S>],
+    intersections: &[Intersection<S>],
+    a_forwards: bool,
+    b_forwards: bool,
+) -> Vec<Vec<Point2<S>>>
+where
+    S: BaseFloat,
+{
+    let mut nodes = Vec::with_capacity(a.len() + b.len() + intersections.len() * 2);
+    let a_nodes = insert_polygon(&mut nodes, a, intersections, |i| (i.a_edge, i.a_alpha));
+    let b_start = nodes.len();
+    let b_nodes = insert_polygon(&mut nodes, b, intersections, |i| (i.b_edge, i.b_alpha));
+    for (&an, &bn) in a_nodes.iter().zip(&b_nodes) {
+        nodes[an].neighbour = Some(bn);
+        nodes[bn].neighbour = Some(an);
+    }
+
+    // Mark each intersection as either an entry or an exit.
+    let mut mark = |start: usize, mut forwards: bool| {
+        let mut ix = start;
+        loop {
+            if nodes[ix].neighbour.is_some() {
+                nodes[ix].entry = forwards;
+                forwards = !forwards;
+            }
+            ix = nodes[ix].next;
+            if ix == start {
+                break;
+            }
+        }
+    };
+    mark(0, a_forwards ^ point_in_polygon(a[0], b));
+    mark(b_start, b_forwards ^ point_in_polygon(b[0], a));
+
+    // Walk the lists, switching between polygons at each intersection.
+    let mut polygons = vec![];
+    for &start in &a_nodes {
+        if nodes[start].visited {
+            continue;
+        }
+        let mut polygon = vec![nodes[start].point];
+        let mut ix = start;
+        while !nodes[ix].visited {
+            nodes[ix].visited = true;
+            let neighbour = nodes[ix].neighbour.expect("intersection has no neighbour");
+            nodes[neighbour].visited = true;
+            let forwards = nodes[ix].entry;
+            loop {
+                ix = if forwards {
+                    nodes[ix].next
+                } else {
+                    nodes[ix].prev
+                };
+                polygon.push(nodes[ix].point);
+                if nodes[ix].neighbour.is_some() {
+                    break;
+                }
+            }
+            ix = nodes[ix].neighbour.expect("intersection has no neighbour");
+        }
+        // The walk finishes back on the starting intersection.
+        polygon.pop();
+        if polygon.len() >= 3 {
+            polygons.push(polygon);
+        }
+    }
+    polygons
+}
+
+// Simplification.
+
+impl<I> Polygon<I>
+where
+    I: Iterator,
+    I::Item: Vertex2d,
+    <I::Item as Vertex>::Scalar: BaseFloat,
+{
+    /// Reduce the number of points describing the polygon via the Ramer-Douglas-Peucker algorithm.
+    ///
+    /// See the `simplify` function for details.
+    pub fn simplify(
+        self,
+        tolerance: <I::Item as Vertex>::Scalar,
+    ) -> OwnedPolygon<<I::Item as Vertex>::Scalar> {
+        let points: Vec<_> = self.points.map(Vertex2d::point2).collect();
+        Polygon::new(simplify(&points, tolerance))
+    }
+
+    /// Reduce the number of points describing the polygon via the Visvalingam-Whyatt algorithm.
+    ///
+    /// See the `simplify_visvalingam` function for details.
+    pub fn simplify_visvalingam(
+        self,
+        target_count: usize,
+    ) -> OwnedPolygon<<I::Item as Vertex>::Scalar> {
+        let points: Vec<_> = self.points.map(Vertex2d::point2).collect();
+        Polygon::new(simplify_visvalingam(&points, target_count))
+    }
+
+    /// Smooth the polygon via Chaikin's corner-cutting algorithm.
+    ///
+    /// See the `smooth` function for details.
+    pub fn smooth(
+        self,
+        iterations: u32,
+        factor: <I::Item as Vertex>::Scalar,
+    ) -> OwnedPolygon<<I::Item as Vertex>::Scalar> {
+        let points: Vec<_> = self.points.map(Vertex2d::point2).collect();
+        Polygon::new(smooth(&points, iterations, factor))
+    }
+}
+
+/// Simplify the closed polygon described by the given points via the Ramer-Douglas-Peucker
+/// algorithm.
+///
+/// Points are removed while the simplified outline remains within `tolerance` of the original.
+/// The outline is first split at the first point and the point furthest from it so that the
+/// result does not depend on where the closed outline begins.
+///
+/// Polygons with fewer than four points are returned unchanged.
+pub fn simplify<S>(points: &[Point2<S>], tolerance: S) -> Vec<Point2<S>>
+where
+    S: BaseFloat,
+{
+    if points.len() < 4 {
+        return points.to_vec();
+    }
+    let first = points[0];
+    let (far, _) = points
+        .iter()
+        .enumerate()
+        .map(|(i, &p)| (i, (p - first).magnitude2()))
+        .fold(
+            (0, S::zero()),
+            |max, (i, d)| if d > max.1 { (i, d) } else { max },
+        );
+    if far == 0 {
+        return vec![first];
+    }
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[far] = true;
+    // The closing half of the outline, wrapping around to the first point.
+    let mut closing: Vec<_> = points[far..].to_vec();
+    closing.push(first);
+    let mut closing_keep = vec![false; closing.len()];
+    rdp(&points[..=far], tolerance, &mut keep[..=far]);
+    rdp(&closing, tolerance, &mut closing_keep);
+    for (i, &k) in closing_keep[..closing.len() - 1].iter().enumerate() {
+        keep[far + i] |= k;
+    }
+    points
+        .iter()
+        .zip(keep)
+        .filter(|&(_, k)| k)
+        .map(|(&p, _)| p)
+        .collect()
+}
+
+// Mark the points of the open polyline that must be kept to remain within `tolerance`.
+//
+// The first and last points are always kept.
+fn rdp<S>(points: &[Point2<S>], tolerance: S, keep: &mut [bool])
+where
+    S: BaseFloat,
+{
+    let last = points.len() - 1;
+    keep[0] = true;
+    keep[last] = true;
+    let mut stack = vec![(0, last)];
+    while let Some((start, end)) = stack.pop() {
+        if end <= start + 1 {
+            continue;
+        }
+        let (a, b) = (points[start], points[end]);
+        let (ix, dist) = (start + 1..end)
+            .map(|i| (i, segment_distance(points[i], a, b)))
+            .fold(
+                (start, S::zero()),
+                |max, (i, d)| if d > max.1 { (i, d) } else { max },
+            );
+        if dist > tolerance {
+            keep[ix] = true;
+            stack.push((start, ix));
+            stack.push((ix, end));
+        }
+    }
+}
+
+// The distance from `p` to the line segment from `a` to `b`.
+fn segment_distance<S>(p: Point2<S>, a: Point2<S>, b: Point2<S>) -> S
+where
+    S: BaseFloat,
+{
+    let ab = b - a;
+    let len2 = ab.magnitude2();
+    if len2 == S::zero() {
+        return (p - a).magnitude();
+    }
+    let t = ((p - a).dot(ab) / len2).max(S::zero()).min(S::one());
+    (p - (a + ab * t)).magnitude()
+}
+
+/// Simplify the closed polygon described by the given points to `target_count` points via the
+/// Visvalingam-Whyatt algorithm.
+///
+/// The point forming the smallest triangle with its neighbours is repeatedly removed, preserving
+/// the overall area and shape of the polygon as closely as possible.
+///
+/// The `target_count` is limited to a minimum of `3`. Polygons with no more than `target_count`
+/// points are returned unchanged.
+pub fn simplify_visvalingam<S>(points: &[Point2<S>], target_count: usize) -> Vec<Point2<S>>
+where
+    S: BaseFloat,
+{
+    use std::cmp::Ordering;
+    use std::collections::BinaryHeap;
+
+    // A candidate for removal, ordered so that the smallest area is popped first.
+    struct Candidate<S> {
+        area: S,
+        index: usize,
+        version: u32,
+    }
+    impl<S: PartialOrd> PartialEq for Candidate<S> {
+        fn eq(&self, other: &Self) -> bool {
+            self.cmp(other) == Ordering::Equal
+        }
+    }
+    impl<S: PartialOrd> Eq for Candidate<S> {}
+    impl<S: PartialOrd> PartialOrd for Candidate<S> {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+    impl<S: PartialOrd> Ord for Candidate<S> {
+        fn cmp(&self, other: &Self) -> Ordering {
+            other
+                .area
+                .partial_cmp(&self.area)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| other.index.cmp(&self.index))
+        }
+    }
+
+    let target_count = target_count.max(3);
+    let len = points.len();
+    if len <= target_count {
+        return points.to_vec();
+    }
+    let mut prev: Vec<usize> = (0..len).map(|i| (i + len - 1) % len).collect();
+    let mut next: Vec<usize> = (0..len).map(|i| (i + 1) % len).collect();
+    let mut versions = vec![0u32; len];
+    let mut removed = vec![false; len];
+    let area = |prev: usize, i: usize, next: usize| -> S {
+        let (a, b, c) = (points[prev], points[i], points[next]);
+        ((b - a).perp_dot(c - a) / crate::math::two()).abs()
+    };
+    let mut heap: BinaryHeap<_> = (0..len)
+        .map(|index| Candidate {
+            area: area(prev[index], index, next[index]),
+            index,
+            version: 0,
+        })
+        .collect();
+
+    let mut count = len;
+    while count > target_count {
+        let Candidate { index, version, .. } = match heap.pop() {
+            None => break,
+            Some(candidate) => candidate,
+        };
+        if removed[index] || version != versions[index] {
+            continue;
+        }
+        removed[index] = true;
+        count -= 1;
+        let (p, n) = (prev[index], next[index]);
+        next[p] = n;
+        prev[n] = p;
+        for &i in &[p, n] {
+            versions[i] += 1;
+            heap.push(Candidate {
+                area: area(prev[i], i, next[i]),
+                index: i,
+                version: versions[i],
+            });
+        }
+    }
+
+    points
+        .iter()
+        .zip(removed)
+        .filter(|&(_, r)| !r)
+        .map(|(&p, _)| p)
+        .collect()
+}
+
+/// Smooth the closed polygon described by the given points via Chaikin's corner-cutting
+/// algorithm.
+///
+/// Each iteration replaces every edge with two points placed at `factor` and `1.0 - factor` along
+/// the edge, doubling the number of points. The `factor` is clamped to the range `0.0..=0.5`,
+/// where the classic value is `0.25`.
+///
+/// Polygons with fewer than three points are returned unchanged.
+pub fn smooth<S>(points: &[Point2<S>], iterations: u32, factor: S) -> Vec<Point2<S>>
+where
+    S: BaseFloat,
+{
+    let half: S = cast(0.5).unwrap();
+    let factor = factor.max(S::zero()).min(half);
+    let mut points = points.to_vec();
+    if points.len() < 3 {
+        return points;
+    }
+    for _ in 0..iterations {
+        let len = points.len();
+        let mut smoothed = Vec::with_capacity(len * 2);
+        for i in 0..len {
+            let (a, b) = (points[i], points[(i + 1) % len]);
+            let ab = b - a;
+            smoothed.push(a + ab * factor);
+            smoothed.push(a + ab * (S::one() - factor));
+        }
+        points = smoothed;
+    }
+    points
+}
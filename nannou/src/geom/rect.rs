@@ -1,10 +1,11 @@
 use crate::geom::{quad, scalar, Align, Edge, Point2, Quad, Range, Tri, Vector2};
 use crate::math::num_traits::Float;
 use crate::math::{self, BaseNum};
+use crate::serde_derive::{Deserialize, Serialize};
 use std::ops::Neg;
 
 /// Defines a Rectangle's bounds across the x and y axes.
-#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
 pub struct Rect<S = scalar::Default> {
     /// The start and end positions of the Rectangle on the x axis.
     pub x: Range<S>,
@@ -57,6 +58,19 @@ pub struct SubdivisionRanges<S = scalar::Default> {
     pub y_b: Range<S>,
 }
 
+/// Yields the cells of a grid dividing a `Rect` into equal columns and rows.
+///
+/// Produced via the `Rect::subdivide` and `Rect::subdivide_with_gap` methods. Cells are yielded in
+/// row-major order, beginning with the top left cell and ending with the bottom right.
+#[derive(Clone, Debug)]
+pub struct Cells<S = scalar::Default> {
+    rect: Rect<S>,
+    cols: usize,
+    rows: usize,
+    gap: S,
+    index: usize,
+}
+
 /// An iterator yielding the four corners of a `Rect`.
 #[derive(Clone, Debug)]
 pub struct Corners<S = scalar::Default> {
@@ -326,6 +340,43 @@ where
         }
     }
 
+    /// The point at the given normalised coordinates, where `(0.0, 0.0)` is the bottom left corner
+    /// and `(1.0, 1.0)` is the top right corner.
+    ///
+    /// Coordinates outside of the range `0.0..=1.0` produce points outside of the Rect.
+    pub fn lerp_point(&self, x: S, y: S) -> Point2<S> {
+        [self.x.lerp(x), self.y.lerp(y)].into()
+    }
+
+    /// The normalised coordinates of the given point, the inverse of `lerp_point`.
+    pub fn normalise_point(&self, p: Point2<S>) -> Point2<S> {
+        let x = (p.x - self.x.start) / (self.x.end - self.x.start);
+        let y = (p.y - self.y.start) / (self.y.end - self.y.start);
+        [x, y].into()
+    }
+
+    /// Map the given point from the space of the `src` Rect to the same relative position within
+    /// this Rect.
+    pub fn map_point_from(&self, p: Point2<S>, src: &Self) -> Point2<S> {
+        let n = src.normalise_point(p);
+        self.lerp_point(n.x, n.y)
+    }
+
+    /// The closest point to `p` that lies within the Rect.
+    pub fn clamp_point(&self, p: Point2<S>) -> Point2<S> {
+        [self.x.clamp_value(p.x), self.y.clamp_value(p.y)].into()
+    }
+
+    /// The sub-Rect spanning the given normalised coordinates, as described by `lerp_point`.
+    ///
+    /// E.g. `rect.lerp_rect(0.0, 0.5, 0.5, 1.0)` is the top left quarter of `rect`.
+    pub fn lerp_rect(&self, x0: S, y0: S, x1: S, y1: S) -> Self {
+        Rect {
+            x: Range::new(self.x.lerp(x0), self.x.lerp(x1)),
+            y: Range::new(self.y.lerp(y0), self.y.lerp(y1)),
+        }
+    }
+
     /// Align `self`'s right edge with the left edge of the `other` **Rect**.
     pub fn left_of(self, other: Self) -> Self {
         Rect {
@@ -723,6 +774,118 @@ where
             ..self
         }
     }
+
+    /// The Rect shrunk by the given margin on each edge.
+    ///
+    /// Equivalent to `pad`. A negative margin grows the Rect.
+    #[inline]
+    pub fn inset(self, margin: S) -> Self {
+        self.pad(margin)
+    }
+
+    /// The Rect with `x` padding applied to the left and right edges and `y` padding applied to the
+    /// bottom and top edges.
+    #[inline]
+    pub fn pad_xy(self, x: S, y: S) -> Self {
+        Rect {
+            x: self.x.pad(x),
+            y: self.y.pad(y),
+        }
+    }
+
+    /// The ratio of the width to the height.
+    #[inline]
+    pub fn aspect_ratio(&self) -> S {
+        self.w() / self.h()
+    }
+
+    /// Divide the Rect into a grid of `cols * rows` equally sized cells.
+    ///
+    /// Cells are yielded in row-major order, beginning with the top left cell. Yields nothing if
+    /// either `cols` or `rows` is `0`.
+    #[inline]
+    pub fn subdivide(self, cols: usize, rows: usize) -> Cells<S> {
+        self.subdivide_with_gap(cols, rows, S::zero())
+    }
+
+    /// Divide the Rect into a grid of `cols * rows` equally sized cells separated by `gap`.
+    ///
+    /// The gap is only applied between neighbouring cells, so the outer edges of the outer cells
+    /// align with the edges of the Rect. Cells are yielded in row-major order, beginning with the
+    /// top left cell.
+    #[inline]
+    pub fn subdivide_with_gap(self, cols: usize, rows: usize, gap: S) -> Cells<S> {
+        Cells {
+            rect: self,
+            cols,
+            rows,
+            gap,
+            index: 0,
+        }
+    }
+
+    /// The `i`th of `rows` equally sized horizontal bands, where `0` is the top band.
+    ///
+    /// Panics if `i` is not less than `rows`.
+    #[inline]
+    pub fn row(&self, i: usize, rows: usize) -> Self {
+        assert!(i < rows, "row index out of range");
+        let (bottom, top) = band(self.bottom(), self.h(), S::zero(), rows - 1 - i, rows);
+        Rect {
+            x: self.x,
+            y: Range::new(bottom, top),
+        }
+    }
+
+    /// The `j`th of `cols` equally sized vertical bands, where `0` is the leftmost band.
+    ///
+    /// Panics if `j` is not less than `cols`.
+    #[inline]
+    pub fn col(&self, j: usize, cols: usize) -> Self {
+        assert!(j < cols, "column index out of range");
+        let (left, right) = band(self.left(), self.w(), S::zero(), j, cols);
+        Rect {
+            x: Range::new(left, right),
+            y: self.y,
+        }
+    }
+}
+
+impl<S> Cells<S>
+where
+    S: BaseNum + Neg<Output = S>,
+{
+    // The cell at the given column and row.
+    fn cell(&self, col: usize, row: usize) -> Rect<S> {
+        let rect = &self.rect;
+        let (bottom, top) = band(
+            rect.bottom(),
+            rect.h(),
+            self.gap,
+            self.rows - 1 - row,
+            self.rows,
+        );
+        let (left, right) = band(rect.left(), rect.w(), self.gap, col, self.cols);
+        Rect {
+            x: Range::new(left, right),
+            y: Range::new(bottom, top),
+        }
+    }
+}
+
+// The `(start, end)` of the `i`th of `n` equal bands beginning at `start` and spanning `len`, with
+// `gap` between neighbouring bands.
+#[inline]
+fn band<S>(start: S, len: S, gap: S, i: usize, n: usize) -> (S, S)
+where
+    S: BaseNum,
+{
+    let n_s: S = math::NumCast::from(n).unwrap();
+    let i_s: S = math::NumCast::from(i).unwrap();
+    let gaps: S = math::NumCast::from(n.saturating_sub(1)).unwrap();
+    let band_len = (len - gap * gaps) / n_s;
+    let band_start = start + i_s * (band_len + gap);
+    (band_start, band_start + band_len)
 }
 
 impl<S> Iterator for Subdivisions<S>
@@ -771,6 +934,36 @@ where
     }
 }
 
+impl<S> Iterator for Cells<S>
+where
+    S: BaseNum + Neg<Output = S>,
+{
+    type Item = Rect<S>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.cols * self.rows {
+            return None;
+        }
+        let (row, col) = (self.index / self.cols, self.index % self.cols);
+        self.index += 1;
+        Some(self.cell(col, row))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<S> ExactSizeIterator for Cells<S>
+where
+    S: BaseNum + Neg<Output = S>,
+{
+    fn len(&self) -> usize {
+        (self.cols * self.rows).saturating_sub(self.index)
+    }
+}
+
 impl<S> Iterator for Corners<S>
 where
     S: BaseNum,
@@ -0,0 +1,282 @@
+//! Triangulated 3D solids with per-vertex normals.
+//!
+//! The main type is the `Solid` type, produced via the `sphere`, `box_3d`, `cylinder`, `cone` and
+//! `torus` functions.
+//!
+//! All solids are centred on the origin. Solids with an axis (cylinders, cones and tori) are
+//! aligned with the *y* axis. Triangles are wound counter-clockwise when viewed from outside the
+//! solid, i.e. the cross product of the first two edges of each triangle points outward.
+
+use crate::geom::{scalar, Point3, Vector3};
+use crate::math::BaseFloat;
+
+/// A triangulated solid with a normal for every vertex.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Solid<S = scalar::Default> {
+    /// The position of each vertex.
+    pub points: Vec<Point3<S>>,
+    /// The unit length normal of each vertex.
+    pub normals: Vec<Vector3<S>>,
+    /// Each set of three consecutive indices describes a triangle.
+    pub indices: Vec<u32>,
+}
+
+impl<S> Solid<S> {
+    /// The number of vertices within the solid.
+    pub fn vertex_count(&self) -> usize {
+        self.points.len()
+    }
+
+    /// The number of triangles within the solid.
+    pub fn triangle_count(&self) -> usize {
+        self.indices.len() / 3
+    }
+
+    /// An iterator yielding the indices of each triangle.
+    pub fn triangles(&self) -> impl Iterator<Item = [u32; 3]> + '_ {
+        self.indices.chunks(3).map(|t| [t[0], t[1], t[2]])
+    }
+
+    // Push a vertex, returning its index.
+    fn push(&mut self, point: Point3<S>, normal: Vector3<S>) -> u32 {
+        let index = self.points.len() as u32;
+        self.points.push(point);
+        self.normals.push(normal);
+        index
+    }
+
+    fn push_tri(&mut self, a: u32, b: u32, c: u32) {
+        self.indices.extend_from_slice(&[a, b, c]);
+    }
+
+    fn empty() -> Self {
+        Solid {
+            points: vec![],
+            normals: vec![],
+            indices: vec![],
+        }
+    }
+}
+
+/// A UV sphere with the given radius.
+///
+/// The `resolution` is the number of segments around the equator, clamped to a minimum of `3`.
+/// The number of rings from pole to pole is half of the resolution, with a minimum of `2`.
+///
+/// Produces `(rings + 1) * (segments + 1)` vertices and `2 * segments * (rings - 1)` triangles.
+pub fn sphere<S>(radius: S, resolution: u32) -> Solid<S>
+where
+    S: BaseFloat,
+{
+    let segments = resolution.max(3);
+    let rings = (segments / 2).max(2);
+    let pi = S::from(std::f64::consts::PI).unwrap();
+    let mut solid = Solid::empty();
+    for i in 0..=rings {
+        let theta = pi * fraction(i, rings);
+        let (sin_theta, cos_theta) = theta.sin_cos();
+        for j in 0..=segments {
+            let d = direction(fraction(j, segments));
+            let normal = Vector3::new(d.x * sin_theta, cos_theta, d.z * sin_theta);
+            solid.push(normal * radius, normal);
+        }
+    }
+    let stride = segments + 1;
+    for i in 0..rings {
+        for j in 0..segments {
+            let t0 = i * stride + j;
+            let t1 = t0 + 1;
+            let b0 = t0 + stride;
+            let b1 = b0 + 1;
+            // Skip the degenerate triangles at the poles.
+            if i + 1 < rings {
+                solid.push_tri(b0, b1, t1);
+            }
+            if i > 0 {
+                solid.push_tri(b0, t1, t0);
+            }
+        }
+    }
+    solid
+}
+
+/// A box with the given width, height and depth.
+///
+/// Each face has its own four vertices so that normals are flat, producing `24` vertices and `12`
+/// triangles.
+pub fn box_3d<S>(w: S, h: S, d: S) -> Solid<S>
+where
+    S: BaseFloat,
+{
+    let (zero, one) = (S::zero(), S::one());
+    let two = one + one;
+    let half = Vector3::new(w / two, h / two, d / two);
+    let x = Vector3::new(one, zero, zero);
+    let y = Vector3::new(zero, one, zero);
+    let z = Vector3::new(zero, zero, one);
+    // Each face normal along with its "up" axis.
+    let faces = [(x, y), (-x, y), (y, -z), (-y, z), (z, y), (-z, y)];
+    let mut solid = Solid::empty();
+    for &(n, v) in faces.iter() {
+        let u = v.cross(n);
+        let corners = [n - u - v, n + u - v, n + u + v, n - u + v];
+        let start = solid.points.len() as u32;
+        for c in corners.iter() {
+            let p = Vector3::new(c.x * half.x, c.y * half.y, c.z * half.z);
+            solid.push(p, n);
+        }
+        solid.push_tri(start, start + 1, start + 2);
+        solid.push_tri(start, start + 2, start + 3);
+    }
+    solid
+}
+
+/// A capped cylinder with the given radius and height.
+///
+/// The `resolution` is the number of segments around the circumference, clamped to a minimum of
+/// `3`. Produces `4 * segments + 6` vertices and `4 * segments` triangles.
+pub fn cylinder<S>(radius: S, height: S, resolution: u32) -> Solid<S>
+where
+    S: BaseFloat,
+{
+    let segments = resolution.max(3);
+    let half_h = height / (S::one() + S::one());
+    let up = Vector3::new(S::zero(), S::one(), S::zero());
+    let mut solid = Solid::empty();
+
+    // The sides.
+    let start = solid.points.len() as u32;
+    for j in 0..=segments {
+        let d = direction(fraction(j, segments));
+        let bottom = d * radius - up * half_h;
+        solid.push(bottom, d);
+        solid.push(bottom + up * height, d);
+    }
+    for j in 0..segments {
+        let b0 = start + j * 2;
+        let t0 = b0 + 1;
+        let b1 = b0 + 2;
+        let t1 = b0 + 3;
+        solid.push_tri(b0, b1, t1);
+        solid.push_tri(b0, t1, t0);
+    }
+
+    // The caps.
+    push_cap(&mut solid, radius, up * half_h, up, segments);
+    push_cap(&mut solid, radius, -up * half_h, -up, segments);
+    solid
+}
+
+/// A cone with the given base radius and height, with its apex pointing along the *y* axis.
+///
+/// The `resolution` is the number of segments around the base, clamped to a minimum of `3`.
+/// Produces `3 * segments + 3` vertices and `2 * segments` triangles.
+pub fn cone<S>(radius: S, height: S, resolution: u32) -> Solid<S>
+where
+    S: BaseFloat,
+{
+    let segments = resolution.max(3);
+    let two = S::one() + S::one();
+    let half_h = height / two;
+    let up = Vector3::new(S::zero(), S::one(), S::zero());
+    let apex = up * half_h;
+    let slope_normal = |d: Vector3<S>| (d * height + up * radius).normalize();
+    let mut solid = Solid::empty();
+
+    // The sides. The apex is duplicated for each segment so that its normal may match the
+    // segment's slope.
+    let start = solid.points.len() as u32;
+    for j in 0..=segments {
+        let d = direction(fraction(j, segments));
+        solid.push(d * radius - apex, slope_normal(d));
+    }
+    for j in 0..segments {
+        let mid = (fraction(j, segments) + fraction(j + 1, segments)) / two;
+        let a = solid.push(apex, slope_normal(direction(mid)));
+        solid.push_tri(start + j, start + j + 1, a);
+    }
+
+    // The base.
+    push_cap(&mut solid, radius, -apex, -up, segments);
+    solid
+}
+
+/// A torus around the *y* axis.
+///
+/// The `radius` is the distance from the centre of the torus to the centre of the tube. The
+/// `resolution` is the number of segments around the torus, clamped to a minimum of `3`, while
+/// the `tube_resolution` is the number of segments around the tube, clamped to a minimum of `3`.
+///
+/// Produces `(segments + 1) * (tube_segments + 1)` vertices and `2 * segments * tube_segments`
+/// triangles.
+pub fn torus<S>(radius: S, tube_radius: S, resolution: u32, tube_resolution: u32) -> Solid<S>
+where
+    S: BaseFloat,
+{
+    let segments = resolution.max(3);
+    let tube_segments = tube_resolution.max(3);
+    let tau = S::from(std::f64::consts::PI * 2.0).unwrap();
+    let up = Vector3::new(S::zero(), S::one(), S::zero());
+    let mut solid = Solid::empty();
+    for i in 0..=tube_segments {
+        let (sin_psi, cos_psi) = (tau * fraction(i, tube_segments)).sin_cos();
+        for j in 0..=segments {
+            let d = direction(fraction(j, segments));
+            let normal = d * cos_psi + up * sin_psi;
+            solid.push(d * radius + normal * tube_radius, normal);
+        }
+    }
+    let stride = segments + 1;
+    for i in 0..tube_segments {
+        for j in 0..segments {
+            let b0 = i * stride + j;
+            let b1 = b0 + 1;
+            let t0 = b0 + stride;
+            let t1 = t0 + 1;
+            solid.push_tri(b0, b1, t1);
+            solid.push_tri(b0, t1, t0);
+        }
+    }
+    solid
+}
+
+// Push a circular cap centred on `center` facing along `normal`, which must be `up` or `-up`.
+fn push_cap<S>(solid: &mut Solid<S>, radius: S, center: Point3<S>, normal: Vector3<S>, n: u32)
+where
+    S: BaseFloat,
+{
+    let c = solid.push(center, normal);
+    for j in 0..=n {
+        let d = direction(fraction(j, n));
+        solid.push(center + d * radius, normal);
+    }
+    let facing_up = normal.y > S::zero();
+    for j in 0..n {
+        let (a, b) = (c + 1 + j, c + 2 + j);
+        if facing_up {
+            solid.push_tri(c, a, b);
+        } else {
+            solid.push_tri(c, b, a);
+        }
+    }
+}
+
+// `i / n` as a scalar.
+fn fraction<S>(i: u32, n: u32) -> S
+where
+    S: BaseFloat,
+{
+    S::from(i).unwrap() / S::from(n).unwrap()
+}
+
+// The unit direction within the *xz* plane at the given fraction of a turn around the *y* axis.
+//
+// A fraction of `0.0` points along *z*, progressing towards *x*.
+fn direction<S>(turns: S) -> Vector3<S>
+where
+    S: BaseFloat,
+{
+    let tau = S::from(std::f64::consts::PI * 2.0).unwrap();
+    let (sin, cos) = (turns * tau).sin_cos();
+    Vector3::new(sin, S::zero(), cos)
+}
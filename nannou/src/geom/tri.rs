@@ -1,5 +1,7 @@
-use crate::geom::{vertex, Cuboid, Point2, Range, Rect, Vertex, Vertex2d, Vertex3d};
-use crate::math::{BaseNum, EuclideanSpace, Zero};
+use crate::geom::{vertex, Cuboid, Point2, Range, Rect, Vector3, Vertex, Vertex2d, Vertex3d};
+use crate::math::num_traits::cast;
+use crate::math::{BaseFloat, BaseNum, EuclideanSpace, Zero};
+use crate::tween::Lerp;
 use std::ops::Deref;
 
 /// The number of vertices in a triangle.
@@ -121,6 +123,141 @@ impl<V> Tri<V> {
         (b1 == b2) && (b2 == b3)
     }
 
+    /// The barycentric coordinates of the given 2D point with respect to the triangle.
+    ///
+    /// Each coordinate describes the weight of the corresponding vertex, such that the point is equal
+    /// to `a * u + b * v + c * w` where `(u, v, w)` are the returned `x`, `y` and `z` components.
+    /// The coordinates always sum to `1`. All coordinates lie within `0.0..=1.0` if the point lies
+    /// within the triangle.
+    ///
+    /// Returns `NaN` coordinates if the triangle is degenerate (i.e. has no area).
+    pub fn barycentric(self, p: Point2<V::Scalar>) -> Vector3<V::Scalar>
+    where
+        V: Vertex2d,
+        V::Scalar: BaseFloat,
+    {
+        let (a, b, c) = self.into();
+        let (a, b, c) = (a.point2(), b.point2(), c.point2());
+        let (ab, ac, ap) = (b - a, c - a, p - a);
+        let denom = ab.perp_dot(ac);
+        let v = ap.perp_dot(ac) / denom;
+        let w = ab.perp_dot(ap) / denom;
+        let u = V::Scalar::one() - v - w;
+        Vector3::new(u, v, w)
+    }
+
+    /// Returns `true` if the given 2D point lies within or on the edge of the triangle.
+    ///
+    /// Unlike `contains`, this method uses the signs of the barycentric coordinates of the point and
+    /// does not require that the point is of the same type as the vertices.
+    pub fn contains_point(self, p: Point2<V::Scalar>) -> bool
+    where
+        V: Vertex2d,
+        V::Scalar: BaseFloat,
+    {
+        let zero = V::Scalar::zero();
+        let bc = self.barycentric(p);
+        bc.x >= zero && bc.y >= zero && bc.z >= zero
+    }
+
+    /// Interpolate between the values `a`, `b` and `c` associated with each of the triangle's
+    /// vertices, weighted by the barycentric coordinates of the given point.
+    ///
+    /// This is useful for blending colours, texture coordinates or other attributes across the
+    /// surface of the triangle. Points outside the triangle extrapolate.
+    pub fn interpolate<A>(self, p: Point2<V::Scalar>, a: &A, b: &A, c: &A) -> A
+    where
+        V: Vertex2d,
+        V::Scalar: BaseFloat,
+        A: Lerp,
+    {
+        let bc = self.barycentric(p);
+        let (u, v, w): (f32, f32, f32) = (
+            cast(bc.x).expect("failed to cast weight"),
+            cast(bc.y).expect("failed to cast weight"),
+            cast(bc.z).expect("failed to cast weight"),
+        );
+        // Blend `a` and `b` first, then blend the result with `c` by its own weight.
+        let ab_weight = u + v;
+        let ab = match ab_weight == 0.0 {
+            true => a.lerp(b, 0.5),
+            false => a.lerp(b, v / ab_weight),
+        };
+        ab.lerp(c, w)
+    }
+
+    /// The centre of the circle that passes through all three vertices of the triangle.
+    ///
+    /// Returns `None` if the triangle is degenerate (i.e. its vertices are collinear).
+    pub fn circumcenter(self) -> Option<Point2<V::Scalar>>
+    where
+        V: Vertex2d,
+        V::Scalar: BaseFloat,
+    {
+        let (a, b, c) = self.into();
+        let (a, b, c) = (a.point2(), b.point2(), c.point2());
+        let (ab, ac) = (b - a, c - a);
+        let d = ab.perp_dot(ac) * crate::math::two();
+        if d == V::Scalar::zero() {
+            return None;
+        }
+        let (ab2, ac2) = (ab.magnitude2(), ac.magnitude2());
+        let x = (ac.y * ab2 - ab.y * ac2) / d;
+        let y = (ab.x * ac2 - ac.x * ab2) / d;
+        Some(a + Point2::new(x, y))
+    }
+
+    /// The centre of the largest circle that fits within the triangle.
+    ///
+    /// This is the average of the vertices weighted by the length of the opposite edge.
+    ///
+    /// Returns `None` if the triangle is degenerate (i.e. its vertices are coincident).
+    pub fn incenter(self) -> Option<Point2<V::Scalar>>
+    where
+        V: Vertex2d,
+        V::Scalar: BaseFloat,
+    {
+        let (a, b, c) = self.into();
+        let (a, b, c) = (a.point2(), b.point2(), c.point2());
+        let la = (c - b).magnitude();
+        let lb = (a - c).magnitude();
+        let lc = (b - a).magnitude();
+        let perimeter = la + lb + lc;
+        if perimeter == V::Scalar::zero() {
+            return None;
+        }
+        Some((a * la + b * lb + c * lc) / perimeter)
+    }
+
+    /// The area of the triangle in the 2D plane.
+    ///
+    /// The result is always positive, regardless of the winding order of the vertices.
+    pub fn area(self) -> V::Scalar
+    where
+        V: Vertex2d,
+        V::Scalar: BaseFloat,
+    {
+        let (a, b, c) = self.into();
+        let (a, b, c) = (a.point2(), b.point2(), c.point2());
+        (b - a).perp_dot(c - a).abs() / crate::math::two()
+    }
+
+    /// The unit vector perpendicular to the surface of the 3D triangle.
+    ///
+    /// The direction follows the right-hand rule, i.e. the normal faces towards the viewer when the
+    /// vertices are wound counter-clockwise.
+    ///
+    /// Returns a `NaN` vector if the triangle is degenerate.
+    pub fn normal(self) -> Vector3<V::Scalar>
+    where
+        V: Vertex3d,
+        V::Scalar: BaseFloat,
+    {
+        let (a, b, c) = self.into();
+        let (a, b, c) = (a.point3(), b.point3(), c.point3());
+        (b - a).cross(c - a).normalize()
+    }
+
     /// The bounding `Rect` of the triangle.
     pub fn bounding_rect(self) -> Rect<V::Scalar>
     where
@@ -0,0 +1,170 @@
+//! Items related to Voronoi diagrams - the partitioning of a plane into regions closest to each of
+//! a set of sites.
+
+use crate::geom::polygon::OwnedPolygon;
+use crate::geom::{scalar, Point2, Polygon, Rect};
+use crate::math::num_traits::cast;
+use crate::math::BaseFloat;
+
+/// A builder for a Voronoi diagram over a set of sites.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Voronoi<S = scalar::Default> {
+    sites: Vec<Point2<S>>,
+}
+
+/// A Voronoi diagram clipped to a bounding rectangle.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VoronoiDiagram<S = scalar::Default> {
+    cells: Vec<VoronoiCell<S>>,
+}
+
+/// A single cell of a Voronoi diagram.
+///
+/// The cell describes the convex region of the bounds that is closer to its site than to any other.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VoronoiCell<S = scalar::Default> {
+    site: Point2<S>,
+    points: Vec<Point2<S>>,
+    neighbors: Vec<usize>,
+}
+
+impl<S> Voronoi<S>
+where
+    S: BaseFloat,
+{
+    /// Begin building a Voronoi diagram for the given sites.
+    pub fn new(sites: &[Point2<S>]) -> Self {
+        let sites = sites.to_vec();
+        Voronoi { sites }
+    }
+
+    /// Produce the diagram, clipped to the given bounding rectangle.
+    ///
+    /// Each cell is produced by clipping the bounds against the half-plane closer to its site for
+    /// every other site. Cells are yielded in the same order as the sites that produced them.
+    pub fn bounds(self, rect: Rect<S>) -> VoronoiDiagram<S> {
+        let Voronoi { sites } = self;
+        let cells = (0..sites.len()).map(|i| cell(&sites, i, rect)).collect();
+        VoronoiDiagram { cells }
+    }
+}
+
+impl<S> VoronoiDiagram<S> {
+    /// An iterator yielding each cell of the diagram in the order of their sites.
+    pub fn cells(&self) -> std::slice::Iter<VoronoiCell<S>> {
+        self.cells.iter()
+    }
+
+    /// Consume the diagram and produce the list of cells.
+    pub fn into_cells(self) -> Vec<VoronoiCell<S>> {
+        self.cells
+    }
+}
+
+impl<S> VoronoiCell<S>
+where
+    S: Copy,
+{
+    /// The site that produced the cell.
+    pub fn site(&self) -> Point2<S> {
+        self.site
+    }
+
+    /// The vertices of the cell's polygon, wound counter-clockwise.
+    ///
+    /// This is empty in the case that the cell does not overlap the bounds.
+    pub fn points(&self) -> &[Point2<S>] {
+        &self.points
+    }
+
+    /// The cell as a polygon, e.g. for use with `draw.polygon().points(cell.polygon().points)`.
+    pub fn polygon(&self) -> OwnedPolygon<S> {
+        Polygon::new(self.points.clone())
+    }
+
+    /// The indices of the sites whose cells share an edge with this cell.
+    pub fn neighbors(&self) -> &[usize] {
+        &self.neighbors
+    }
+}
+
+// Produce the cell for the site at the given index.
+fn cell<S>(sites: &[Point2<S>], index: usize, rect: Rect<S>) -> VoronoiCell<S>
+where
+    S: BaseFloat,
+{
+    let site = sites[index];
+    // Each vertex along with the index of the site whose bisector produced the following edge.
+    let corner = |x, y| (Point2 { x, y }, None);
+    let mut polygon: Vec<(Point2<S>, Option<usize>)> = vec![
+        corner(rect.left(), rect.bottom()),
+        corner(rect.right(), rect.bottom()),
+        corner(rect.right(), rect.top()),
+        corner(rect.left(), rect.top()),
+    ];
+    let half: S = cast(0.5).unwrap();
+    for (j, &other) in sites.iter().enumerate() {
+        if j == index || other == site || polygon.is_empty() {
+            continue;
+        }
+        // Keep the side of the bisector closest to `site`.
+        let normal = other - site;
+        let midpoint = (site + other) * half;
+        let distance = |p: Point2<S>| {
+            let d = p - midpoint;
+            d.x * normal.x + d.y * normal.y
+        };
+        polygon = clip(&polygon, j, distance);
+    }
+
+    let points = polygon.iter().map(|&(p, _)| p).collect();
+    let mut neighbors = vec![];
+    for &(_, edge) in &polygon {
+        if let Some(j) = edge {
+            if !neighbors.contains(&j) {
+                neighbors.push(j);
+            }
+        }
+    }
+    VoronoiCell {
+        site,
+        points,
+        neighbors,
+    }
+}
+
+// Clip the convex polygon to the region where `distance` is not positive.
+//
+// Edges produced along the clipping line are labelled with the given `label`.
+fn clip<S, F>(
+    polygon: &[(Point2<S>, Option<usize>)],
+    label: usize,
+    distance: F,
+) -> Vec<(Point2<S>, Option<usize>)>
+where
+    S: BaseFloat,
+    F: Fn(Point2<S>) -> S,
+{
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+    for (i, &(a, edge)) in polygon.iter().enumerate() {
+        let (b, _) = polygon[(i + 1) % polygon.len()];
+        let (da, db) = (distance(a), distance(b));
+        let a_inside = da <= S::zero();
+        let b_inside = db <= S::zero();
+        if a_inside {
+            clipped.push((a, edge));
+        }
+        if a_inside != b_inside {
+            let t = da / (da - db);
+            let p = a + (b - a) * t;
+            // Leaving the region begins an edge along the clipping line.
+            let edge = if a_inside { Some(label) } else { edge };
+            clipped.push((p, edge));
+        }
+    }
+    // Discard degenerate results.
+    if clipped.len() < 3 {
+        clipped.clear();
+    }
+    clipped
+}
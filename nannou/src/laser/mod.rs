@@ -0,0 +1,321 @@
+//! Laser projector output via the [Ether Dream](https://ether-dream.com/) DAC, provided by the
+//! [nannou_laser](https://docs.rs/nannou_laser) crate.
+//!
+//! Requires the `laser` feature. An **EtherDreamStream** is connected to the DAC at a known
+//! address and handed to a **Renderer**, which streams points to the DAC on a background thread.
+//! Each time the app renders, the user's `laser_view` function describes a new **Frame**, which
+//! the renderer repeats until the next frame arrives:
+//!
+//! ```ignore
+//! struct Model {
+//!     laser: laser::Renderer,
+//! }
+//!
+//! fn model(_app: &App) -> Model {
+//!     let stream = laser::EtherDreamStream::connect("192.168.1.77").unwrap();
+//!     let laser = laser::Renderer::new(stream);
+//!     Model { laser }
+//! }
+//!
+//! fn update(app: &App, model: &mut Model, _update: Update) {
+//!     model.laser.render(app, model, laser_view);
+//! }
+//!
+//! fn laser_view(app: &App, _model: &Model, frame: &mut laser::Frame) {
+//!     let r = 0.5 + (app.time.sin() * 0.25);
+//!     let path = geom::path().arc_to(pt2(0.0, 0.0), r, 0.0, 2.0 * PI).close().build();
+//!     frame.add_path(&path, lin_srgba(1.0, 0.2, 0.6, 1.0), 400);
+//! }
+//! ```
+//!
+//! ## Coordinates and blanking
+//!
+//! Points use the Ether Dream's native resolution: positions span the full range of `i16` and
+//! colors the full range of `u16`. Paths added to a frame are expected to lie within the range
+//! `-1.0..=1.0` along both axes, where `(0.0, 0.0)` is the centre of the projection.
+//!
+//! The scanner cannot jump instantly between points. Whenever a frame moves from the end of one
+//! segment to the start of the next, along with when the renderer returns from the end of a frame
+//! to its start, blanked points are inserted automatically so that the laser is off during the
+//! retrace.
+//!
+//! The higher-level frame stream API offered by the `nannou_laser` crate, including DAC detection
+//! and path optimisation, remains available via the `api` re-export.
+
+use crate::color::LinSrgba;
+use crate::geom::{self, Point2};
+
+pub use nannou_laser as api;
+pub use nannou_laser::ether_dream;
+
+pub use self::renderer::{Renderer, ViewFn};
+pub use self::stream::{Error, EtherDreamStream};
+
+pub mod renderer;
+pub mod stream;
+
+/// The number of blanked points on which the scanner dwells at either end of a retrace.
+///
+/// This allows the scanner to settle before the laser is switched on or after it is switched off.
+pub const BLANK_DWELL_POINTS: usize = 8;
+
+/// The maximum distance between the interpolated blanked points of a retrace, where `1.0` is the
+/// distance from the centre of the projection to its edge.
+pub const BLANK_STEP: f32 = 0.05;
+
+// The tolerance with which the curves of a path are flattened before sampling.
+const FLATTEN_TOLERANCE: f32 = 0.001;
+
+/// A single point streamed to the DAC.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Point {
+    /// The horizontal position, where `i16::MIN` and `i16::MAX` are the edges of the projection.
+    pub x: i16,
+    /// The vertical position, where `i16::MIN` and `i16::MAX` are the edges of the projection.
+    pub y: i16,
+    /// The intensity of the red laser.
+    pub r: u16,
+    /// The intensity of the green laser.
+    pub g: u16,
+    /// The intensity of the blue laser.
+    pub b: u16,
+    /// The overall intensity, used by some projectors for an additional laser or a dimmer.
+    pub i: u16,
+    /// The control bits of the point, e.g. `ether_dream::dac::PointControl::CHANGE_RATE`.
+    pub flags: u16,
+}
+
+/// A sequence of points describing a single frame of laser output.
+///
+/// Blanked points are inserted automatically between each segment added to the frame.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Frame {
+    points: Vec<Point>,
+}
+
+impl Point {
+    /// A point at the given position with the given color.
+    ///
+    /// The overall intensity is the maximum of the three color channels.
+    pub fn new(x: i16, y: i16, r: u16, g: u16, b: u16) -> Self {
+        let i = std::cmp::max(r, std::cmp::max(g, b));
+        let flags = 0;
+        Point {
+            x,
+            y,
+            r,
+            g,
+            b,
+            i,
+            flags,
+        }
+    }
+
+    /// A blanked point at the given position.
+    pub fn blank(x: i16, y: i16) -> Self {
+        Point::new(x, y, 0, 0, 0)
+    }
+
+    /// A point at the given position within the range `-1.0..=1.0` along each axis with the
+    /// given color.
+    ///
+    /// Positions beyond the range are clamped. The color channels are multiplied by the alpha.
+    pub fn from_position_color(position: Point2, color: LinSrgba) -> Self {
+        let x = position_component(position.x);
+        let y = position_component(position.y);
+        let (r, g, b) = (color.red, color.green, color.blue);
+        let a = color.alpha;
+        let r = color_component(r * a);
+        let g = color_component(g * a);
+        let b = color_component(b * a);
+        Point::new(x, y, r, g, b)
+    }
+
+    /// The same point with the laser switched off.
+    pub fn blanked(&self) -> Self {
+        Point {
+            r: 0,
+            g: 0,
+            b: 0,
+            i: 0,
+            ..*self
+        }
+    }
+
+    /// Whether or not the laser is switched off at this point.
+    pub fn is_blank(&self) -> bool {
+        self.r == 0 && self.g == 0 && self.b == 0 && self.i == 0
+    }
+
+    // The position within the range `-1.0..=1.0` along each axis.
+    fn position(&self) -> Point2 {
+        let max = std::i16::MAX as f32;
+        geom::pt2(self.x as f32 / max, self.y as f32 / max)
+    }
+}
+
+impl Frame {
+    /// An empty frame.
+    pub fn new() -> Self {
+        Frame::default()
+    }
+
+    /// A frame containing `point_count` points sampled uniformly along the given path.
+    ///
+    /// See `add_path` for details.
+    pub fn from_draw_path(path: &geom::path::Path, color: LinSrgba, point_count: usize) -> Self {
+        let mut frame = Frame::new();
+        frame.add_path(path, color, point_count);
+        frame
+    }
+
+    /// The points of the frame, including any blanked points.
+    pub fn points(&self) -> &[Point] {
+        &self.points
+    }
+
+    /// The number of points within the frame, including any blanked points.
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Whether or not the frame contains any points.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Remove all points from the frame.
+    pub fn clear(&mut self) {
+        self.points.clear();
+    }
+
+    /// Add a segment of points to the frame.
+    ///
+    /// If the frame already contains points, a blanked retrace from the last point to the first
+    /// point of the segment is inserted first.
+    pub fn add_points<I>(&mut self, points: I)
+    where
+        I: IntoIterator<Item = Point>,
+    {
+        let mut points = points.into_iter();
+        let first = match points.next() {
+            None => return,
+            Some(p) => p,
+        };
+        if let Some(&last) = self.points.last() {
+            blank_transition(last, first, &mut self.points);
+        }
+        self.points.push(first);
+        self.points.extend(points);
+    }
+
+    /// Add `point_count` points sampled uniformly along the given path.
+    ///
+    /// The path is expected to lie within the range `-1.0..=1.0` along both axes. The points are
+    /// distributed between the path's sub-paths according to their length and closed sub-paths
+    /// return to their starting point. Each sub-path is added as its own segment, so the laser is
+    /// blanked while moving between them.
+    pub fn add_path(&mut self, path: &geom::path::Path, color: LinSrgba, point_count: usize) {
+        for segment in sample_path(path, point_count) {
+            let points = segment
+                .into_iter()
+                .map(|p| Point::from_position_color(p, color));
+            self.add_points(points);
+        }
+    }
+}
+
+// Blanked points moving the scanner from `from` to `to`, dwelling at either end.
+//
+// No points are produced if the positions are equal, e.g. at the end of a closed loop.
+pub(crate) fn blank_transition(from: Point, to: Point, out: &mut Vec<Point>) {
+    if (from.x, from.y) == (to.x, to.y) {
+        return;
+    }
+    let (start, end) = (from.position(), to.position());
+    let steps = ((end - start).magnitude() / BLANK_STEP).ceil() as usize;
+    let dwell = |p: Point| std::iter::repeat(p.blanked()).take(BLANK_DWELL_POINTS);
+    out.extend(dwell(from));
+    for i in 1..steps {
+        let p = start + (end - start) * (i as f32 / steps as f32);
+        let (x, y) = (position_component(p.x), position_component(p.y));
+        out.push(Point::blank(x, y));
+    }
+    out.extend(dwell(to));
+}
+
+// Sample `count` points uniformly along the flattened sub-paths of the path.
+fn sample_path(path: &geom::path::Path, count: usize) -> Vec<Vec<Point2>> {
+    let polylines: Vec<(Vec<Point2>, f32)> = path
+        .flatten(FLATTEN_TOLERANCE)
+        .into_iter()
+        .filter_map(|(mut points, closed)| {
+            if closed && !points.is_empty() {
+                let first = points[0];
+                points.push(first);
+            }
+            let length = polyline_length(&points);
+            match points.len() >= 2 && length > 0.0 {
+                true => Some((points, length)),
+                false => None,
+            }
+        })
+        .collect();
+    let total: f32 = polylines.iter().map(|&(_, len)| len).sum();
+    let mut segments = vec![];
+    let mut travelled = 0.0;
+    let mut allocated = 0;
+    for (points, length) in polylines {
+        travelled += length;
+        let end = ((travelled / total) * count as f32).round() as usize;
+        let n = end.saturating_sub(allocated).min(count - allocated);
+        allocated += n;
+        if n > 0 {
+            segments.push(sample_polyline(&points, length, n));
+        }
+    }
+    segments
+}
+
+// Sample `n` points evenly spaced along the polyline, including both of its ends.
+fn sample_polyline(points: &[Point2], length: f32, n: usize) -> Vec<Point2> {
+    let mut samples = Vec::with_capacity(n);
+    let mut seg = 0;
+    let mut seg_start = 0.0;
+    for i in 0..n {
+        let d = match n {
+            1 => 0.0,
+            _ => length * i as f32 / (n - 1) as f32,
+        };
+        while seg + 2 < points.len() {
+            let seg_len = (points[seg + 1] - points[seg]).magnitude();
+            if seg_start + seg_len >= d {
+                break;
+            }
+            seg_start += seg_len;
+            seg += 1;
+        }
+        let (a, b) = (points[seg], points[seg + 1]);
+        let seg_len = (b - a).magnitude();
+        let t = match seg_len > 0.0 {
+            true => ((d - seg_start) / seg_len).max(0.0).min(1.0),
+            false => 0.0,
+        };
+        samples.push(a + (b - a) * t);
+    }
+    samples
+}
+
+fn polyline_length(points: &[Point2]) -> f32 {
+    points.windows(2).map(|w| (w[1] - w[0]).magnitude()).sum()
+}
+
+// Convert a position component within `-1.0..=1.0` to the DAC's resolution.
+fn position_component(s: f32) -> i16 {
+    (s.max(-1.0).min(1.0) * std::i16::MAX as f32) as i16
+}
+
+// Convert a color component within `0.0..=1.0` to the DAC's resolution.
+fn color_component(s: f32) -> u16 {
+    (s.max(0.0).min(1.0) * std::u16::MAX as f32) as u16
+}
@@ -0,0 +1,166 @@
+//! Streaming frames to the DAC in real-time on a background thread.
+
+use super::stream::{Error, EtherDreamStream};
+use super::{blank_transition, Frame, Point};
+use crate::App;
+use std::collections::VecDeque;
+use std::sync::atomic::{self, AtomicBool, AtomicU32};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+
+pub use nannou_laser::stream::DEFAULT_POINT_HZ;
+
+/// The function signature of a user's laser view, describing the frame to be streamed.
+pub type ViewFn<Model> = fn(&App, &Model, &mut Frame);
+
+// The duration for which the stream thread sleeps when the DAC's buffer is full.
+const FULL_BUFFER_SLEEP: Duration = Duration::from_millis(1);
+
+/// Streams frames to an **EtherDreamStream** in real-time on a background thread.
+///
+/// The DAC must be fed continuously, regardless of the app's frame rate. The renderer repeats the
+/// most recently submitted frame until a new one arrives, switching frames only once the previous
+/// frame has completed. A blanked retrace is inserted each time the scanner returns from the end
+/// of a frame to its start.
+pub struct Renderer {
+    frames: mpsc::Sender<Frame>,
+    point_hz: Arc<AtomicU32>,
+    is_closed: Arc<AtomicBool>,
+    thread: Option<thread::JoinHandle<Result<(), Error>>>,
+}
+
+impl Renderer {
+    /// Begin streaming to the given stream at the `DEFAULT_POINT_HZ`.
+    pub fn new(stream: EtherDreamStream) -> Self {
+        let (frames, rx) = mpsc::channel();
+        let point_hz = Arc::new(AtomicU32::new(DEFAULT_POINT_HZ));
+        let is_closed = Arc::new(AtomicBool::new(false));
+        let thread_point_hz = point_hz.clone();
+        let thread_is_closed = is_closed.clone();
+        let thread = thread::Builder::new()
+            .name(format!("nannou_laser_renderer_{}", stream.ip()))
+            .spawn(move || {
+                let result = run(stream, rx, &thread_point_hz, &thread_is_closed);
+                thread_is_closed.store(true, atomic::Ordering::Relaxed);
+                result
+            })
+            .expect("failed to spawn laser renderer thread");
+        Renderer {
+            frames,
+            point_hz,
+            is_closed,
+            thread: Some(thread),
+        }
+    }
+
+    /// Describe a new frame via the given view function and submit it to the stream.
+    pub fn render<M>(&self, app: &App, model: &M, view: ViewFn<M>) {
+        let mut frame = Frame::new();
+        view(app, model, &mut frame);
+        self.submit(frame);
+    }
+
+    /// Submit the given frame to the stream, replacing the previous frame once it completes.
+    ///
+    /// Does nothing if the stream has closed.
+    pub fn submit(&self, frame: Frame) {
+        self.frames.send(frame).ok();
+    }
+
+    /// The rate at which points are played back by the DAC.
+    pub fn point_hz(&self) -> u32 {
+        self.point_hz.load(atomic::Ordering::Relaxed)
+    }
+
+    /// Specify the rate at which points are played back by the DAC.
+    ///
+    /// The rate is clamped to the DAC's maximum point rate.
+    pub fn set_point_hz(&self, point_hz: u32) {
+        self.point_hz.store(point_hz, atomic::Ordering::Relaxed);
+    }
+
+    /// Whether or not the stream has closed, e.g. due to an error communicating with the DAC.
+    ///
+    /// The error may be retrieved via `close`.
+    pub fn is_closed(&self) -> bool {
+        self.is_closed.load(atomic::Ordering::Relaxed)
+    }
+
+    /// Stop the DAC and close the stream, returning the error that closed it if any.
+    pub fn close(mut self) -> Result<(), Error> {
+        self.is_closed.store(true, atomic::Ordering::Relaxed);
+        match self.thread.take() {
+            Some(thread) => thread.join().expect("laser renderer thread panicked"),
+            None => Ok(()),
+        }
+    }
+}
+
+// Feed the DAC until the renderer is closed or an error occurs.
+fn run(
+    mut stream: EtherDreamStream,
+    frames: mpsc::Receiver<Frame>,
+    point_hz: &AtomicU32,
+    is_closed: &AtomicBool,
+) -> Result<(), Error> {
+    let mut frame = Frame::new();
+    let mut next_frame = None;
+    let mut index = 0;
+    // The last point queued, from which the retrace to the start of each frame begins.
+    let mut last = Point::default();
+    let mut queue: VecDeque<Point> = VecDeque::new();
+    let mut points = vec![];
+    while !is_closed.load(atomic::Ordering::Relaxed) {
+        if let Some(f) = frames.try_iter().last() {
+            next_frame = Some(f);
+        }
+
+        let hz = point_hz.load(atomic::Ordering::Relaxed);
+        let hz = std::cmp::min(hz, stream.max_point_hz());
+        let latency = nannou_laser::stream::raw::default_latency_points(hz);
+        let fullness = stream.buffer_fullness() as u32;
+        let n = std::cmp::min(
+            latency.saturating_sub(fullness),
+            stream.remaining_capacity() as u32,
+        ) as usize;
+
+        // Fill the queue with enough points, switching frames only at the end of each frame.
+        while queue.len() < n {
+            if index >= frame.len() {
+                if let Some(f) = next_frame.take() {
+                    frame = f;
+                }
+                index = 0;
+                if frame.is_empty() {
+                    last = Point::default();
+                    queue.push_back(last);
+                    continue;
+                }
+                let mut retrace = vec![];
+                blank_transition(last, frame.points()[0], &mut retrace);
+                queue.extend(retrace);
+            }
+            last = frame.points()[index];
+            queue.push_back(last);
+            index += 1;
+        }
+
+        points.clear();
+        points.extend(queue.drain(..n));
+        stream.write(&points, hz)?;
+        if n == 0 {
+            thread::sleep(FULL_BUFFER_SLEEP);
+        }
+    }
+    stream.stop()
+}
+
+impl Drop for Renderer {
+    fn drop(&mut self) {
+        self.is_closed.store(true, atomic::Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            thread.join().ok();
+        }
+    }
+}
@@ -0,0 +1,227 @@
+//! A direct connection to an Ether Dream DAC at a known address.
+
+use super::Point;
+use nannou_laser::ether_dream;
+use nannou_laser::{Api, DetectedDac};
+use std::fmt;
+use std::io;
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+
+/// The duration to wait for the DAC's broadcast before `EtherDreamStream::connect` fails.
+///
+/// Ether Dream DACs broadcast their presence once per second.
+pub const DETECT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// A stream of points to an Ether Dream DAC.
+///
+/// Usually handed to a **Renderer**, which writes to the stream from a background thread.
+pub struct EtherDreamStream {
+    stream: ether_dream::dac::stream::Stream,
+    broadcast: ether_dream::protocol::DacBroadcast,
+    ip: IpAddr,
+    point_hz: u32,
+    is_playing: bool,
+}
+
+/// Errors that might occur while connecting or writing to an **EtherDreamStream**.
+#[derive(Debug)]
+pub enum Error {
+    /// An error occurred while listening for DAC broadcasts.
+    Io(io::Error),
+    /// The given address was not a valid IP address.
+    InvalidAddress(String),
+    /// No DAC broadcast was received from the given address within the `DETECT_TIMEOUT`.
+    NotFound(IpAddr),
+    /// An error occurred while communicating with the DAC.
+    Communication(ether_dream::dac::stream::CommunicationError),
+}
+
+impl EtherDreamStream {
+    /// Connect to the Ether Dream DAC at the given IP address, e.g. `"192.168.1.77"`.
+    ///
+    /// Blocks until the DAC's broadcast is received, returning `Error::NotFound` if it is not
+    /// received within the `DETECT_TIMEOUT`.
+    pub fn connect(ip: &str) -> Result<Self, Error> {
+        let ip: IpAddr = ip
+            .parse()
+            .map_err(|_| Error::InvalidAddress(ip.to_string()))?;
+        let broadcast = detect_broadcast(ip)?;
+        let mut stream = ether_dream::dac::stream::connect(&broadcast, ip)?;
+        stream.queue_commands().prepare_stream().submit()?;
+        Ok(EtherDreamStream {
+            stream,
+            broadcast,
+            ip,
+            point_hz: 0,
+            is_playing: false,
+        })
+    }
+
+    /// The IP address of the DAC.
+    pub fn ip(&self) -> IpAddr {
+        self.ip
+    }
+
+    /// The maximum number of points per second supported by the DAC.
+    pub fn max_point_hz(&self) -> u32 {
+        self.broadcast.max_point_rate as _
+    }
+
+    /// The number of points that may be stored within the DAC's buffer.
+    pub fn buffer_capacity(&self) -> u16 {
+        self.stream.dac().buffer_capacity
+    }
+
+    /// The number of points within the DAC's buffer as of the last response from the DAC.
+    pub fn buffer_fullness(&self) -> u16 {
+        self.stream.dac().status.buffer_fullness
+    }
+
+    /// The number of points that may currently be written without overflowing the DAC's buffer.
+    pub fn remaining_capacity(&self) -> u16 {
+        self.buffer_capacity()
+            .saturating_sub(1)
+            .saturating_sub(self.buffer_fullness())
+    }
+
+    /// Write the given points to the DAC's buffer, to be played back at the given rate.
+    ///
+    /// Playback begins upon the first write. The rate is clamped to `max_point_hz`. Writing an
+    /// empty slice of points refreshes the `buffer_fullness`.
+    pub fn write(&mut self, points: &[Point], point_hz: u32) -> Result<(), Error> {
+        let point_hz = std::cmp::min(point_hz, self.max_point_hz());
+        let mut data: Vec<_> = points.iter().cloned().map(dac_point).collect();
+        if !self.is_playing {
+            if data.is_empty() {
+                return Ok(());
+            }
+            let low_water_mark = 0;
+            self.stream
+                .queue_commands()
+                .data(data)
+                .begin(low_water_mark, point_hz)
+                .submit()?;
+            self.is_playing = true;
+            self.point_hz = point_hz;
+        } else if point_hz != self.point_hz && !data.is_empty() {
+            // The new rate takes effect upon reaching the point with the `CHANGE_RATE` bit.
+            data[0].control |= ether_dream::dac::PointControl::CHANGE_RATE.bits();
+            self.stream
+                .queue_commands()
+                .point_rate(point_hz)
+                .data(data)
+                .submit()?;
+            self.point_hz = point_hz;
+        } else {
+            self.stream.queue_commands().data(data).submit()?;
+        }
+        Ok(())
+    }
+
+    /// Stop playback, clearing the DAC's buffer.
+    ///
+    /// Playback begins again upon the next `write`.
+    pub fn stop(&mut self) -> Result<(), Error> {
+        if self.is_playing {
+            self.stream.queue_commands().stop().submit()?;
+            self.is_playing = false;
+            self.stream.queue_commands().prepare_stream().submit()?;
+        }
+        Ok(())
+    }
+}
+
+// Block until the broadcast of the DAC at the given address is received.
+fn detect_broadcast(ip: IpAddr) -> Result<ether_dream::protocol::DacBroadcast, Error> {
+    let dacs = Api::new().detect_dacs()?;
+    dacs.set_timeout(Some(DETECT_TIMEOUT))?;
+    let start = Instant::now();
+    for res in dacs {
+        let dac = match res {
+            Ok(dac) => dac,
+            Err(ref err)
+                if err.kind() == io::ErrorKind::TimedOut
+                    || err.kind() == io::ErrorKind::WouldBlock =>
+            {
+                break
+            }
+            Err(err) => return Err(err.into()),
+        };
+        match dac {
+            DetectedDac::EtherDream {
+                broadcast,
+                source_addr,
+            } => {
+                if source_addr.ip() == ip {
+                    return Ok(broadcast);
+                }
+            }
+        }
+        // Broadcasts from other DACs may continue to arrive indefinitely.
+        if start.elapsed() >= DETECT_TIMEOUT {
+            break;
+        }
+    }
+    Err(Error::NotFound(ip))
+}
+
+// Convert the point to the representation used by the ether dream protocol.
+fn dac_point(p: Point) -> ether_dream::protocol::DacPoint {
+    ether_dream::protocol::DacPoint {
+        control: p.flags,
+        x: p.x,
+        y: p.y,
+        r: p.r,
+        g: p.g,
+        b: p.b,
+        i: p.i,
+        u1: 0,
+        u2: 0,
+    }
+}
+
+impl fmt::Debug for EtherDreamStream {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("EtherDreamStream")
+            .field("ip", &self.ip)
+            .field("point_hz", &self.point_hz)
+            .field("is_playing", &self.is_playing)
+            .finish()
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<ether_dream::dac::stream::CommunicationError> for Error {
+    fn from(err: ether_dream::dac::stream::CommunicationError) -> Self {
+        Error::Communication(err)
+    }
+}
+
+impl std::error::Error for Error {
+    fn cause(&self) -> Option<&dyn std::error::Error> {
+        match *self {
+            Error::Io(ref err) => Some(err),
+            Error::Communication(ref err) => Some(err),
+            Error::InvalidAddress(_) | Error::NotFound(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Io(ref err) => err.fmt(f),
+            Error::InvalidAddress(ref addr) => write!(f, "invalid DAC address: `{}`", addr),
+            Error::NotFound(ip) => write!(f, "no Ether Dream DAC found at {}", ip),
+            Error::Communication(ref err) => {
+                write!(f, "failed to communicate with the DAC: {}", err)
+            }
+        }
+    }
+}
@@ -19,7 +19,16 @@ pub use conrod_winit;
 pub use daggy;
 pub use find_folder;
 pub use lyon;
+#[cfg(feature = "audio")]
+pub use nannou_audio as audio;
+#[cfg(feature = "hotreload")]
+pub use nannou_hotreload_macros::reloadable;
+#[cfg(feature = "midi")]
+pub use nannou_midi as midi;
 use serde_derive;
+#[cfg(feature = "hotreload")]
+#[doc(hidden)]
+pub use serde_json;
 pub use winit;
 
 pub use self::event::Event;
@@ -29,22 +38,42 @@ pub use crate::app::{App, LoopMode};
 pub use crate::draw::Draw;
 
 pub mod app;
+pub mod asset;
+pub mod cloth;
 pub mod color;
+pub mod compat;
+#[cfg(feature = "dialog")]
+pub mod dialog;
+pub mod dmx;
 pub mod draw;
 pub mod ease;
 pub mod event;
+pub mod field;
 pub mod frame;
+#[cfg(feature = "gamepad")]
+pub mod gamepad;
 pub mod geom;
 pub mod image;
 pub mod io;
+#[cfg(feature = "laser")]
+pub mod laser;
+pub mod lsystem;
 pub mod math;
 pub mod mesh;
+pub mod net;
 pub mod noise;
+pub mod particle;
 pub mod prelude;
 pub mod rand;
+pub mod scene;
+#[cfg(feature = "serial")]
+pub mod serial;
 pub mod state;
+pub mod testing;
 pub mod text;
 pub mod time;
+pub mod turtle;
+pub mod tween;
 pub mod ui;
 pub mod wgpu;
 pub mod window;
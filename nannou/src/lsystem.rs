@@ -0,0 +1,128 @@
+//! Items related to L-systems, the string-rewriting grammars often used to generate plants,
+//! fractals and other self-similar forms.
+//!
+//! An **LSystem** repeatedly rewrites its *axiom* via a set of rules, while a
+//! [**Turtle**](../turtle/struct.Turtle.html) interprets the resulting string as a series of
+//! drawing commands:
+//!
+//! ```ignore
+//! let mut system = LSystem::new("X");
+//! system.add_rule('X', "F+[[X]-X]-F[-FX]+X");
+//! system.add_rule('F', "FF");
+//! let plant = system.nth(5);
+//!
+//! let mut turtle = Turtle::new();
+//! turtle.hide();
+//! turtle.interpret(&plant, &lsystem::default_commands(4.0, 25.0));
+//! turtle.draw(&draw);
+//! ```
+
+use std::collections::HashMap;
+
+pub use crate::turtle::Turtle;
+
+/// A string-rewriting system, beginning from an axiom and applying all rules in parallel at each
+/// step.
+#[derive(Clone, Debug)]
+pub struct LSystem {
+    /// The initial string from which the system is rewritten.
+    pub axiom: String,
+    rules: HashMap<char, String>,
+    current: String,
+    generation: usize,
+}
+
+/// The action performed by a **Turtle** for a character within an L-system string.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TurtleCommand {
+    /// Move forward by the given distance while drawing.
+    Forward(f32),
+    /// Move forward by the given distance without drawing.
+    Move(f32),
+    /// Turn counter-clockwise by the given angle in degrees. Negative angles turn clockwise.
+    Turn(f32),
+    /// Push the position and heading onto the state stack.
+    Push,
+    /// Pop the position and heading from the state stack.
+    Pop,
+}
+
+impl LSystem {
+    /// A system with the given axiom and no rules.
+    pub fn new(axiom: &str) -> Self {
+        LSystem {
+            axiom: axiom.to_string(),
+            rules: HashMap::new(),
+            current: axiom.to_string(),
+            generation: 0,
+        }
+    }
+
+    /// Replace every occurrence of the character `from` with `to` at each step.
+    ///
+    /// Characters without a rule are preserved. Adding a rule for a character that already has one
+    /// replaces the existing rule.
+    pub fn add_rule(&mut self, from: char, to: &str) {
+        self.rules.insert(from, to.to_string());
+    }
+
+    /// The string produced by the most recent step, or the axiom if no steps have been
+    /// performed.
+    pub fn current(&self) -> &str {
+        &self.current
+    }
+
+    /// The number of steps performed since the system was created or reset.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Perform a single rewriting step, returning the new string.
+    pub fn step(&mut self) -> &str {
+        self.current = self.rewrite(&self.current);
+        self.generation += 1;
+        &self.current
+    }
+
+    /// The string produced by rewriting the axiom `n` times.
+    ///
+    /// This does not affect the `current` string.
+    pub fn nth(&self, n: usize) -> String {
+        (0..n).fold(self.axiom.clone(), |s, _| self.rewrite(&s))
+    }
+
+    /// Return to the axiom.
+    pub fn reset(&mut self) {
+        self.current = self.axiom.clone();
+        self.generation = 0;
+    }
+
+    fn rewrite(&self, s: &str) -> String {
+        let mut rewritten = String::with_capacity(s.len());
+        for c in s.chars() {
+            match self.rules.get(&c) {
+                Some(to) => rewritten.push_str(to),
+                None => rewritten.push(c),
+            }
+        }
+        rewritten
+    }
+}
+
+/// The conventional commands for L-system strings.
+///
+/// - `F` and `G` move forward by `distance` while drawing.
+/// - `f` moves forward by `distance` without drawing.
+/// - `+` turns counter-clockwise by `angle_degrees`, while `-` turns clockwise.
+/// - `[` and `]` push and pop the state stack.
+pub fn default_commands(distance: f32, angle_degrees: f32) -> HashMap<char, TurtleCommand> {
+    let mut commands = HashMap::new();
+    commands.insert('F', TurtleCommand::Forward(distance));
+    commands.insert('G', TurtleCommand::Forward(distance));
+    commands.insert('f', TurtleCommand::Move(distance));
+    commands.insert('+', TurtleCommand::Turn(angle_degrees));
+    commands.insert('-', TurtleCommand::Turn(-angle_degrees));
+    commands.insert('[', TurtleCommand::Push);
+    commands.insert(']', TurtleCommand::Pop);
+    commands
+}
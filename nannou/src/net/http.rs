@@ -0,0 +1,222 @@
+//! A minimal HTTP server for controlling an app remotely, e.g. from a phone's browser.
+//!
+//! The server accepts connections on a background thread and reads each request on a thread of its
+//! own, responding with `200 OK` and forwarding the request's path and query parameters to the
+//! app. Requests are usually handled via
+//! `App::http_control_server`, which calls the given function with the model before the next
+//! update:
+//!
+//! ```ignore
+//! app.http_control_server(8000, |model: &mut Model, path, params| {
+//!     if path == "/play" {
+//!         model.bpm = params.get("bpm").and_then(|s| s.parse().ok()).unwrap_or(120.0);
+//!     }
+//! })
+//! .unwrap();
+//! ```
+//!
+//! Visiting `http://<ip>:8000/play?bpm=140` then sets the model's `bpm`. The server does not
+//! support TLS or authentication and should only be exposed on trusted networks.
+
+use crate::app::Proxy;
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{self, AtomicBool};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+
+/// A running HTTP control server.
+///
+/// Once dropped, the server's thread exits upon accepting its next connection.
+#[derive(Debug)]
+pub struct ControlServer {
+    addr: SocketAddr,
+    rx: mpsc::Receiver<ControlRequest>,
+    // Set upon drop, signalling the server's thread to stop accepting connections.
+    closed: Arc<AtomicBool>,
+}
+
+/// The path and query parameters of a request received by a **ControlServer**.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ControlRequest {
+    path: String,
+    params: Vec<(String, String)>,
+}
+
+// The time allowed for a client to send the head of its request.
+const READ_TIMEOUT: Duration = Duration::from_secs(2);
+
+// The maximum size of the head of a request in bytes. Larger requests are truncated.
+const MAX_HEAD_LEN: u64 = 8 * 1024;
+
+impl ControlServer {
+    /// Bind a server to the given port on all network interfaces.
+    ///
+    /// A port of `0` requests that the OS assign an available port, which may be retrieved via
+    /// `addr`.
+    pub fn bind(port: u16) -> io::Result<Self> {
+        Self::spawn(port, None)
+    }
+
+    // Bind a server that wakes the app's event loop upon receiving requests.
+    pub(crate) fn bind_with_proxy(port: u16, proxy: Proxy) -> io::Result<Self> {
+        Self::spawn(port, Some(proxy))
+    }
+
+    fn spawn(port: u16, proxy: Option<Proxy>) -> io::Result<Self> {
+        let listener = TcpListener::bind(("0.0.0.0", port))?;
+        let addr = listener.local_addr()?;
+        let (tx, rx) = mpsc::channel();
+        let closed = Arc::new(AtomicBool::new(false));
+        let thread_closed = closed.clone();
+        thread::Builder::new()
+            .name(format!("nannou_http_control_{}", addr.port()))
+            .spawn(move || {
+                for stream in listener.incoming() {
+                    let stream = match stream {
+                        Ok(stream) => stream,
+                        Err(err) => {
+                            eprintln!("failed to accept http control connection: {}", err);
+                            continue;
+                        }
+                    };
+                    if thread_closed.load(atomic::Ordering::Acquire) {
+                        break;
+                    }
+                    let tx = tx.clone();
+                    let proxy = proxy.clone();
+                    let spawned = thread::Builder::new()
+                        .name("nannou_http_control_connection".into())
+                        .spawn(move || {
+                            if let Err(err) = handle_connection(stream, &tx, proxy.as_ref()) {
+                                eprintln!("failed to read http control request: {}", err);
+                            }
+                        });
+                    if let Err(err) = spawned {
+                        eprintln!("failed to spawn http control connection thread: {}", err);
+                    }
+                }
+            })?;
+        Ok(ControlServer { addr, rx, closed })
+    }
+
+    /// The address to which the server is bound.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Yields all requests received since the last call without blocking.
+    pub fn try_iter(&self) -> mpsc::TryIter<ControlRequest> {
+        self.rx.try_iter()
+    }
+}
+
+impl Drop for ControlServer {
+    fn drop(&mut self) {
+        self.closed.store(true, atomic::Ordering::Release);
+    }
+}
+
+impl ControlRequest {
+    /// Parse a request from its target, e.g. `/play?bpm=120&name=a+b`.
+    ///
+    /// The path and query parameters are percent-decoded. `+` is decoded as a space within query
+    /// parameters alone, as it has no special meaning within the path.
+    pub fn from_target(target: &str) -> Self {
+        let mut parts = target.splitn(2, '?');
+        let path = decode(parts.next().unwrap_or(""), false);
+        let params = parts
+            .next()
+            .unwrap_or("")
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let mut kv = pair.splitn(2, '=');
+                let key = decode(kv.next().unwrap_or(""), true);
+                let value = decode(kv.next().unwrap_or(""), true);
+                (key, value)
+            })
+            .collect();
+        ControlRequest { path, params }
+    }
+
+    /// The path of the request, e.g. `/play`.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The query parameters of the request.
+    ///
+    /// If a parameter appears more than once, the last value is used.
+    pub fn params(&self) -> HashMap<&str, &str> {
+        self.params.iter().map(|(k, v)| (&k[..], &v[..])).collect()
+    }
+}
+
+// Read the head of a request, forward it to the app and respond.
+fn handle_connection(
+    mut stream: TcpStream,
+    tx: &mpsc::Sender<ControlRequest>,
+    proxy: Option<&Proxy>,
+) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?.take(MAX_HEAD_LEN));
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Skip the headers.
+    let mut line = String::new();
+    while reader.read_line(&mut line)? > 0 && !line.trim_end().is_empty() {
+        line.clear();
+    }
+    let status = match request_line.split_whitespace().nth(1) {
+        Some(target) => {
+            // Forward the request before responding so that it is queued by the time the client
+            // receives the response. The server may have been dropped, in which case the request
+            // is discarded.
+            if tx.send(ControlRequest::from_target(target)).is_ok() {
+                // Wake the app in case it is waiting for events.
+                if let Some(proxy) = proxy {
+                    proxy.wakeup().ok();
+                }
+            }
+            "200 OK"
+        }
+        None => "400 Bad Request",
+    };
+    let body = status;
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body,
+    )?;
+    stream.flush()
+}
+
+// Percent-decode the given path or query component, decoding `+` as a space if `plus_as_space`.
+fn decode(s: &str, plus_as_space: bool) -> String {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' if plus_as_space => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        i += 2;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
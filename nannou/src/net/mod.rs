@@ -0,0 +1,194 @@
+//! Items related to controlling and synchronising apps over the network or via the OS, e.g. for
+//! installations spanning multiple machines.
+//!
+//! - [**ControlServer**](./http/struct.ControlServer.html) - a minimal HTTP server delivering
+//!   requests to the model, registered via `App::http_control_server`.
+//! - [**UrlScheme**](./url/struct.UrlScheme.html) - a custom URL scheme such as `nannou://`,
+//!   registered via `App::register_url_scheme`.
+//! - [**udp**](./udp/index.html) - UDP multicast senders and receivers.
+//! - [**BeatClock**](./sync/struct.BeatClock.html) - a tempo clock synchronised via multicast.
+//! - [**ws**](./ws/index.html) - WebSocket servers and clients. Requires the `websocket` feature.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub mod http;
+pub mod sync;
+pub mod udp;
+pub mod url;
+#[cfg(feature = "websocket")]
+pub mod ws;
+
+pub use self::http::{ControlRequest, ControlServer};
+pub use self::sync::BeatClock;
+pub use self::udp::{MulticastReceiver, MulticastSender};
+pub use self::url::UrlScheme;
+
+/// The function signature of a handler for URLs of a registered scheme.
+pub type UrlFn<Model> = fn(&mut Model, &str);
+
+/// The function signature of a handler for requests to an HTTP control server.
+///
+/// The handler receives the path of the request along with its decoded query parameters.
+pub type HttpControlFn<Model> = fn(&mut Model, &str, HashMap<&str, &str>);
+
+// The URL schemes, control servers and sockets registered with the app.
+#[derive(Default)]
+pub(crate) struct Handlers {
+    // Each scheme alongside a `UrlFn<M>` for the app's model type.
+    url_schemes: Vec<(UrlScheme, Arc<dyn Any>)>,
+    // Each server alongside a `HttpControlFn<M>` for the app's model type.
+    control_servers: Vec<(ControlServer, Arc<dyn Any>)>,
+    // Each receiver alongside a `MulticastFn<M>` for the app's model type.
+    multicast_receivers: Vec<(MulticastReceiver, Arc<dyn Any>)>,
+    // Each server alongside a `ServerMessageFn<M>` for the app's model type.
+    #[cfg(feature = "websocket")]
+    ws_servers: Vec<(ws::Server, Arc<dyn Any>)>,
+    // Each client alongside a `ClientMessageFn<M>` for the app's model type.
+    #[cfg(feature = "websocket")]
+    ws_clients: Vec<(ws::Client, Arc<dyn Any>)>,
+}
+
+// A message received since the last update, ready to be handed to the model.
+pub(crate) enum Received {
+    Url(Arc<dyn Any>, String),
+    Control(Arc<dyn Any>, ControlRequest),
+    Multicast(Arc<dyn Any>, Vec<u8>),
+    #[cfg(feature = "websocket")]
+    WsServer(Arc<dyn Any>, usize, Vec<u8>),
+    #[cfg(feature = "websocket")]
+    WsClient(Arc<dyn Any>, Vec<u8>),
+}
+
+impl Handlers {
+    pub(crate) fn add_url_scheme<M>(&mut self, scheme: UrlScheme, handler: UrlFn<M>)
+    where
+        M: 'static,
+    {
+        self.url_schemes.push((scheme, Arc::new(handler)));
+    }
+
+    pub(crate) fn add_control_server<M>(&mut self, server: ControlServer, handler: HttpControlFn<M>)
+    where
+        M: 'static,
+    {
+        self.control_servers.push((server, Arc::new(handler)));
+    }
+
+    pub(crate) fn add_multicast_receiver<M>(
+        &mut self,
+        receiver: MulticastReceiver,
+        handler: udp::MulticastFn<M>,
+    ) where
+        M: 'static,
+    {
+        self.multicast_receivers.push((receiver, Arc::new(handler)));
+    }
+
+    #[cfg(feature = "websocket")]
+    pub(crate) fn add_ws_server<M>(&mut self, server: ws::Server, handler: ws::ServerMessageFn<M>)
+    where
+        M: 'static,
+    {
+        self.ws_servers.push((server, Arc::new(handler)));
+    }
+
+    #[cfg(feature = "websocket")]
+    pub(crate) fn add_ws_client<M>(&mut self, client: ws::Client, handler: ws::ClientMessageFn<M>)
+    where
+        M: 'static,
+    {
+        self.ws_clients.push((client, Arc::new(handler)));
+    }
+
+    // Collect the URLs, requests and messages received since the last call.
+    pub(crate) fn poll(&mut self) -> Vec<Received> {
+        let mut received = vec![];
+        for (scheme, handler) in &mut self.url_schemes {
+            for url in scheme.drain() {
+                received.push(Received::Url(handler.clone(), url));
+            }
+        }
+        for (server, handler) in &self.control_servers {
+            for request in server.try_iter() {
+                received.push(Received::Control(handler.clone(), request));
+            }
+        }
+        for (receiver, handler) in &self.multicast_receivers {
+            while let Some(data) = receiver.try_recv() {
+                received.push(Received::Multicast(handler.clone(), data));
+            }
+        }
+        #[cfg(feature = "websocket")]
+        {
+            for (server, handler) in &self.ws_servers {
+                while let Some((id, msg)) = server.try_recv() {
+                    received.push(Received::WsServer(handler.clone(), id, msg));
+                }
+            }
+            for (client, handler) in &self.ws_clients {
+                while let Some(msg) = client.try_recv() {
+                    received.push(Received::WsClient(handler.clone(), msg));
+                }
+            }
+        }
+        received
+    }
+}
+
+impl Received {
+    // Call the received message's handler with the model.
+    pub(crate) fn handle<M>(self, model: &mut M)
+    where
+        M: 'static,
+    {
+        match self {
+            Received::Url(handler, url) => {
+                let handler = handler
+                    .downcast_ref::<UrlFn<M>>()
+                    .expect("unexpected model argument given to url scheme handler");
+                handler(model, &url);
+            }
+            Received::Control(handler, request) => {
+                let handler = handler
+                    .downcast_ref::<HttpControlFn<M>>()
+                    .expect("unexpected model argument given to http control handler");
+                handler(model, request.path(), request.params());
+            }
+            Received::Multicast(handler, data) => {
+                let handler = handler
+                    .downcast_ref::<udp::MulticastFn<M>>()
+                    .expect("unexpected model argument given to multicast handler");
+                handler(model, &data);
+            }
+            #[cfg(feature = "websocket")]
+            Received::WsServer(handler, id, msg) => {
+                let handler = handler
+                    .downcast_ref::<ws::ServerMessageFn<M>>()
+                    .expect("unexpected model argument given to websocket server handler");
+                handler(model, id, &msg);
+            }
+            #[cfg(feature = "websocket")]
+            Received::WsClient(handler, msg) => {
+                let handler = handler
+                    .downcast_ref::<ws::ClientMessageFn<M>>()
+                    .expect("unexpected model argument given to websocket client handler");
+                handler(model, &msg);
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for Handlers {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let schemes: Vec<_> = self.url_schemes.iter().map(|(s, _)| s).collect();
+        let servers: Vec<_> = self.control_servers.iter().map(|(s, _)| s).collect();
+        let receivers: Vec<_> = self.multicast_receivers.iter().map(|(r, _)| r).collect();
+        f.debug_struct("Handlers")
+            .field("url_schemes", &schemes)
+            .field("control_servers", &servers)
+            .field("multicast_receivers", &receivers)
+            .finish()
+    }
+}
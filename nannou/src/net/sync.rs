@@ -0,0 +1,237 @@
+//! Items related to synchronising time between apps on the same network.
+//!
+//! **BeatClock** tracks musical time at a given tempo. A *leader* clock broadcasts its tempo and
+//! beat via UDP multicast while *follower* clocks on other machines adopt them, allowing for
+//! beat-synced visuals across many screens:
+//!
+//! ```ignore
+//! // On the machine driving the tempo.
+//! let mut clock = BeatClock::leader("239.255.0.1", 9100, 128.0).unwrap();
+//! // On every other machine.
+//! let mut clock = BeatClock::follower("239.255.0.1", 9100).unwrap();
+//!
+//! fn update(_app: &App, model: &mut Model, _update: Update) {
+//!     model.clock.update();
+//!     model.pulse = 1.0 - model.clock.phase();
+//! }
+//! ```
+//!
+//! Network latency is not compensated for, though on a LAN it is typically well below a
+//! millisecond.
+
+use crate::net::udp::{MulticastReceiver, MulticastSender};
+use std::io;
+use std::time::{Duration, Instant};
+
+/// A clock measuring time in beats at a tempo, optionally synchronised via multicast.
+#[derive(Debug)]
+pub struct BeatClock {
+    bpm: f64,
+    // The instant at which beat zero occurred.
+    origin: Instant,
+    role: Role,
+}
+
+#[derive(Debug)]
+enum Role {
+    Local,
+    Leader {
+        sender: MulticastSender,
+        last_broadcast: Option<Instant>,
+    },
+    Follower {
+        receiver: MulticastReceiver,
+        synced: bool,
+    },
+}
+
+// Identifies beat clock datagrams, followed by the bpm and beat as little-endian `f64`s.
+const MAGIC: &[u8; 4] = b"NBCK";
+const MESSAGE_LEN: usize = 20;
+
+impl BeatClock {
+    /// The interval at which a leader broadcasts its tempo and beat while being updated.
+    pub const BROADCAST_INTERVAL: Duration = Duration::from_millis(20);
+
+    /// The tempo of a follower before it has received its first broadcast.
+    pub const DEFAULT_BPM: f64 = 120.0;
+
+    /// A clock beginning at beat zero with the given tempo that is not synchronised with others.
+    pub fn new(bpm: f64) -> Self {
+        BeatClock {
+            bpm,
+            origin: Instant::now(),
+            role: Role::Local,
+        }
+    }
+
+    /// A clock that broadcasts its tempo and beat to the given multicast group and port.
+    pub fn leader(group: &str, port: u16, bpm: f64) -> io::Result<Self> {
+        let sender = MulticastSender::new(group, port)?;
+        let role = Role::Leader {
+            sender,
+            last_broadcast: None,
+        };
+        Ok(BeatClock {
+            role,
+            ..Self::new(bpm)
+        })
+    }
+
+    /// A clock that adopts the tempo and beat broadcast by a leader to the given multicast group
+    /// and port.
+    pub fn follower(group: &str, port: u16) -> io::Result<Self> {
+        let receiver = MulticastReceiver::join(group, port)?;
+        let role = Role::Follower {
+            receiver,
+            synced: false,
+        };
+        Ok(BeatClock {
+            role,
+            ..Self::new(Self::DEFAULT_BPM)
+        })
+    }
+
+    /// The tempo in beats per minute.
+    pub fn bpm(&self) -> f64 {
+        self.bpm
+    }
+
+    /// Change the tempo without affecting the current beat.
+    ///
+    /// The tempo of a follower is overwritten upon receiving the leader's next broadcast.
+    pub fn set_bpm(&mut self, bpm: f64) {
+        let now = Instant::now();
+        let beat = self.beat_at(now);
+        self.bpm = bpm;
+        self.set_beat_at(now, beat);
+    }
+
+    /// The number of beats elapsed since the clock began, including the fraction of the current
+    /// beat.
+    pub fn beat(&self) -> f64 {
+        self.beat_at(Instant::now())
+    }
+
+    /// The beat at the given instant.
+    pub fn beat_at(&self, instant: Instant) -> f64 {
+        let secs = match instant.checked_duration_since(self.origin) {
+            Some(elapsed) => elapsed.as_secs_f64(),
+            None => -self.origin.duration_since(instant).as_secs_f64(),
+        };
+        secs * self.bpm / 60.0
+    }
+
+    /// The progress through the current beat within the range `0.0..1.0`.
+    pub fn phase(&self) -> f64 {
+        fract(self.beat())
+    }
+
+    /// The progress through the current bar of the given number of beats within the range
+    /// `0.0..1.0`.
+    pub fn bar_phase(&self, beats_per_bar: u32) -> f64 {
+        fract(self.beat() / beats_per_bar.max(1) as f64)
+    }
+
+    /// Restart the clock at beat zero.
+    pub fn reset(&mut self) {
+        self.origin = Instant::now();
+    }
+
+    /// Whether or not the clock is driven by a leader's broadcasts.
+    ///
+    /// This is `true` for leaders and local clocks, while followers are synced once they have
+    /// received their first broadcast.
+    pub fn is_synced(&self) -> bool {
+        match self.role {
+            Role::Follower { synced, .. } => synced,
+            _ => true,
+        }
+    }
+
+    /// Broadcast the tempo and beat if this clock is a leader, or adopt those of the latest
+    /// received broadcast if this clock is a follower.
+    ///
+    /// This should be called once per update. Leaders broadcast at most once per
+    /// `BROADCAST_INTERVAL`.
+    pub fn update(&mut self) {
+        let now = Instant::now();
+        let beat = self.beat_at(now);
+        let bpm = self.bpm;
+        let mut received = None;
+        match self.role {
+            Role::Local => (),
+            Role::Leader {
+                ref sender,
+                ref mut last_broadcast,
+            } => {
+                let due = last_broadcast
+                    .map(|last| now.duration_since(last) >= Self::BROADCAST_INTERVAL)
+                    .unwrap_or(true);
+                if due {
+                    // Datagrams that fail to send are superseded by the next broadcast.
+                    sender.send(&encode(bpm, beat)).ok();
+                    *last_broadcast = Some(now);
+                }
+            }
+            Role::Follower {
+                ref receiver,
+                ref mut synced,
+            } => {
+                while let Some(data) = receiver.try_recv() {
+                    if let Some(msg) = decode(&data) {
+                        received = Some(msg);
+                        *synced = true;
+                    }
+                }
+            }
+        }
+        if let Some((bpm, beat)) = received {
+            self.bpm = bpm;
+            self.set_beat_at(now, beat);
+        }
+    }
+
+    // Move the origin such that the given instant occurs at the given beat.
+    fn set_beat_at(&mut self, instant: Instant, beat: f64) {
+        if self.bpm <= 0.0 {
+            return;
+        }
+        let secs = beat * 60.0 / self.bpm;
+        let offset = Duration::from_secs_f64(secs.abs());
+        let origin = match secs >= 0.0 {
+            true => instant.checked_sub(offset),
+            false => instant.checked_add(offset),
+        };
+        if let Some(origin) = origin {
+            self.origin = origin;
+        }
+    }
+}
+
+fn fract(x: f64) -> f64 {
+    x - x.floor()
+}
+
+fn encode(bpm: f64, beat: f64) -> [u8; MESSAGE_LEN] {
+    let mut msg = [0u8; MESSAGE_LEN];
+    msg[..4].copy_from_slice(MAGIC);
+    msg[4..12].copy_from_slice(&bpm.to_le_bytes());
+    msg[12..].copy_from_slice(&beat.to_le_bytes());
+    msg
+}
+
+fn decode(data: &[u8]) -> Option<(f64, f64)> {
+    if data.len() != MESSAGE_LEN || &data[..4] != MAGIC {
+        return None;
+    }
+    let mut bpm = [0u8; 8];
+    let mut beat = [0u8; 8];
+    bpm.copy_from_slice(&data[4..12]);
+    beat.copy_from_slice(&data[12..]);
+    let (bpm, beat) = (f64::from_le_bytes(bpm), f64::from_le_bytes(beat));
+    match bpm.is_finite() && beat.is_finite() && bpm > 0.0 {
+        true => Some((bpm, beat)),
+        false => None,
+    }
+}
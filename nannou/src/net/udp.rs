@@ -0,0 +1,191 @@
+//! UDP multicast for low-latency messaging between apps on the same network, e.g. for
+//! synchronising the visuals of an installation spanning many screens.
+//!
+//! A **MulticastSender** sends datagrams to a *group* address, which are received by every
+//! **MulticastReceiver** that has joined the group on the same port. Unlike WebSockets, no
+//! connections are established and datagrams may be lost or arrive out of order, making multicast
+//! best suited to frequently repeated state such as a clock.
+//!
+//! ## Group addresses and TTL
+//!
+//! For LAN-only use, choose a group within the administratively scoped range `239.0.0.0/8`,
+//! e.g. `239.255.0.1`, which routers do not forward beyond the local organisation. The TTL of the
+//! sender limits the number of routers each datagram may cross. The default TTL of `1` restricts
+//! datagrams to the local subnet, which is almost always what an installation wants. Only IPv4
+//! groups are supported.
+//!
+//! Note that only a single **MulticastReceiver** may be bound to a given port on each machine.
+
+use crate::app::Proxy;
+use crate::App;
+use std::io;
+use std::net::{Ipv4Addr, SocketAddr, UdpSocket};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+
+/// The function signature of a handler for datagrams received by a **MulticastReceiver**.
+pub type MulticastFn<Model> = fn(&mut Model, &[u8]);
+
+/// The default time-to-live of sent datagrams, restricting them to the local subnet.
+pub const DEFAULT_TTL: u32 = 1;
+
+/// The maximum size of a received datagram in bytes. Larger datagrams are truncated.
+pub const MAX_DATAGRAM_SIZE: usize = 65_507;
+
+/// Sends datagrams to a multicast group.
+#[derive(Debug)]
+pub struct MulticastSender {
+    socket: UdpSocket,
+    target: SocketAddr,
+}
+
+/// Receives the datagrams sent to a multicast group on a background thread.
+///
+/// Cloning a **MulticastReceiver** produces a handle to the same receiver.
+#[derive(Clone)]
+pub struct MulticastReceiver {
+    group: Ipv4Addr,
+    port: u16,
+    incoming: Arc<Mutex<mpsc::Receiver<Vec<u8>>>>,
+    // Set once the receiver is registered with the app via `poll_into_model`.
+    proxy: Arc<Mutex<Option<Proxy>>>,
+}
+
+impl MulticastSender {
+    /// Create a sender targeting the given group and port, e.g. `("239.255.0.1", 9100)`.
+    ///
+    /// Datagrams are sent with the `DEFAULT_TTL`.
+    pub fn new(group: &str, port: u16) -> io::Result<Self> {
+        let group = parse_group(group)?;
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?;
+        socket.set_multicast_ttl_v4(DEFAULT_TTL)?;
+        socket.set_nonblocking(true)?;
+        let target = SocketAddr::from((group, port));
+        Ok(MulticastSender { socket, target })
+    }
+
+    /// Specify the time-to-live of sent datagrams, i.e. the number of routers that they may cross.
+    pub fn set_ttl(&self, ttl: u32) -> io::Result<()> {
+        self.socket.set_multicast_ttl_v4(ttl)
+    }
+
+    /// Specify whether or not sent datagrams are also received by receivers on this machine.
+    ///
+    /// Enabled by default.
+    pub fn set_loopback(&self, loopback: bool) -> io::Result<()> {
+        self.socket.set_multicast_loop_v4(loopback)
+    }
+
+    /// The group address and port to which datagrams are sent.
+    pub fn target(&self) -> SocketAddr {
+        self.target
+    }
+
+    /// Send the given data to the group without blocking.
+    ///
+    /// Returns an error of kind `WouldBlock` if the socket's send buffer is full.
+    pub fn send(&self, data: &[u8]) -> io::Result<usize> {
+        self.socket.send_to(data, self.target)
+    }
+}
+
+impl MulticastReceiver {
+    /// Join the given group, receiving all datagrams sent to it on the given port.
+    pub fn join(group: &str, port: u16) -> io::Result<Self> {
+        let group = parse_group(group)?;
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, port))?;
+        socket.join_multicast_v4(&group, &Ipv4Addr::UNSPECIFIED)?;
+        let (tx, rx) = mpsc::channel();
+        let proxy = Arc::new(Mutex::new(None));
+        let thread_proxy = proxy.clone();
+        thread::Builder::new()
+            .name(format!("nannou_multicast_{}_{}", group, port))
+            .spawn(move || receive(socket, tx, thread_proxy))?;
+        Ok(MulticastReceiver {
+            group,
+            port,
+            incoming: Arc::new(Mutex::new(rx)),
+            proxy,
+        })
+    }
+
+    /// The group that the receiver has joined.
+    pub fn group(&self) -> Ipv4Addr {
+        self.group
+    }
+
+    /// The port on which the receiver is bound.
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// The next datagram received from the group, or `None` if no datagram is available.
+    ///
+    /// This never blocks.
+    pub fn try_recv(&self) -> Option<Vec<u8>> {
+        self.incoming.lock().ok()?.try_recv().ok()
+    }
+
+    /// Call `handler` with the model for each datagram received from the group.
+    ///
+    /// Datagrams are delivered before each update, after which they are no longer available via
+    /// `try_recv`. The `Model` type must match the app's model type, otherwise the app will
+    /// **panic!** upon delivering a datagram.
+    pub fn poll_into_model<M>(&self, app: &App, handler: MulticastFn<M>)
+    where
+        M: 'static,
+    {
+        if let Ok(mut proxy) = self.proxy.lock() {
+            *proxy = Some(app.create_proxy());
+        }
+        app.net_handlers
+            .borrow_mut()
+            .add_multicast_receiver(self.clone(), handler);
+    }
+}
+
+// Parse the given IPv4 multicast group address.
+fn parse_group(group: &str) -> io::Result<Ipv4Addr> {
+    let invalid = || {
+        let msg = format!("`{}` is not an IPv4 multicast group address", group);
+        io::Error::new(io::ErrorKind::InvalidInput, msg)
+    };
+    let addr: Ipv4Addr = group.parse().map_err(|_| invalid())?;
+    match addr.is_multicast() {
+        true => Ok(addr),
+        false => Err(invalid()),
+    }
+}
+
+// Receive datagrams until every `MulticastReceiver` handle is dropped.
+fn receive(socket: UdpSocket, tx: mpsc::Sender<Vec<u8>>, proxy: Arc<Mutex<Option<Proxy>>>) {
+    let mut buffer = vec![0u8; MAX_DATAGRAM_SIZE];
+    loop {
+        let len = match socket.recv(&mut buffer) {
+            Ok(len) => len,
+            Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => {
+                eprintln!("failed to receive multicast datagram: {}", err);
+                return;
+            }
+        };
+        if tx.send(buffer[..len].to_vec()).is_err() {
+            return;
+        }
+        // Wake the app in case it is waiting for events.
+        if let Ok(proxy) = proxy.lock() {
+            if let Some(ref proxy) = *proxy {
+                proxy.wakeup().ok();
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for MulticastReceiver {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("MulticastReceiver")
+            .field("group", &self.group)
+            .field("port", &self.port)
+            .finish()
+    }
+}
@@ -0,0 +1,364 @@
+//! Custom URL schemes, e.g. `nannou://play?bpm=120`, for launching and controlling apps via links.
+//!
+//! Registering a scheme via `App::register_url_scheme` associates it with the running executable
+//! and delivers any URLs of the scheme to the given function before the next update, both those
+//! that the app was launched with and those opened while the app is running.
+//!
+//! ## Platform-specific
+//!
+//! - **Linux**: a `.desktop` entry is written to `$XDG_DATA_HOME/applications` and set as the
+//!   default handler via `xdg-mime`.
+//! - **Windows**: the scheme is registered under `HKEY_CURRENT_USER\Software\Classes`.
+//! - **macOS**: schemes must be declared via `CFBundleURLTypes` within the app bundle's
+//!   `Info.plist` and cannot be registered at runtime. URLs are delivered to the running app via
+//!   the `NSAppleEventManager`.
+//!
+//! On Linux and Windows the OS launches a new instance of the app for each URL, passing the URL as
+//! an argument. The first instance to register a scheme listens on a loopback socket, whose port
+//! is written to the temporary directory. Later instances launched with URLs of the scheme forward
+//! them to the first instance and exit.
+
+use crate::app::Proxy;
+use std::env;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+
+/// A custom URL scheme along with the URLs of that scheme that are yet to be handled.
+#[derive(Debug)]
+pub struct UrlScheme {
+    scheme: String,
+    pending: Vec<String>,
+    // URLs delivered to the running app, once listening.
+    received: Option<mpsc::Receiver<String>>,
+}
+
+impl UrlScheme {
+    /// A URL scheme with the given name, e.g. `"nannou"` for URLs like `nannou://play`.
+    ///
+    /// Any URLs of the scheme within the process's arguments become pending.
+    pub fn new(scheme: &str) -> Self {
+        let mut url_scheme = UrlScheme {
+            scheme: scheme.trim_end_matches("://").to_lowercase(),
+            pending: vec![],
+            received: None,
+        };
+        url_scheme.pending = env::args()
+            .skip(1)
+            .filter(|a| url_scheme.matches(a))
+            .collect();
+        url_scheme
+    }
+
+    /// The name of the scheme.
+    pub fn scheme(&self) -> &str {
+        &self.scheme
+    }
+
+    /// Whether or not the given URL is of this scheme.
+    pub fn matches(&self, url: &str) -> bool {
+        scheme_matches(&self.scheme, url)
+    }
+
+    /// Queue the given URL to be handled, ignoring URLs of other schemes.
+    pub fn push(&mut self, url: &str) {
+        if self.matches(url) {
+            self.pending.push(url.to_string());
+        }
+    }
+
+    /// Register the current executable as the handler for the scheme with the OS.
+    ///
+    /// Registration is skipped if the executable is already registered, so this may be called
+    /// upon every launch. See the module docs for platform-specific details.
+    pub fn register_with_os(&self) -> io::Result<()> {
+        register(&self.scheme, &env::current_exe()?)
+    }
+
+    // Begin receiving URLs opened while the app is running, waking the app upon each URL.
+    //
+    // Returns `true` if the pending URLs were instead forwarded to an instance of the app that is
+    // already running, in which case this instance should exit.
+    pub(crate) fn listen(&mut self, proxy: Proxy) -> io::Result<bool> {
+        let (tx, rx) = mpsc::channel();
+        let forwarded = listen(&self.scheme, &mut self.pending, tx, proxy)?;
+        if !forwarded {
+            self.received = Some(rx);
+        }
+        Ok(forwarded)
+    }
+
+    // Take all pending URLs along with those received since the last call.
+    pub(crate) fn drain(&mut self) -> Vec<String> {
+        let mut urls = std::mem::replace(&mut self.pending, vec![]);
+        if let Some(ref rx) = self.received {
+            urls.extend(rx.try_iter());
+        }
+        urls
+    }
+}
+
+// Whether or not the given URL is of the given lowercase scheme.
+fn scheme_matches(scheme: &str, url: &str) -> bool {
+    let mut parts = url.splitn(2, ':');
+    match (parts.next(), parts.next()) {
+        (Some(s), Some(_)) => s.eq_ignore_ascii_case(scheme),
+        _ => false,
+    }
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn register(scheme: &str, exe: &Path) -> io::Result<()> {
+    use std::process::Command;
+    let data_home = env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no home directory"))?;
+    let applications = data_home.join("applications");
+    let desktop_name = format!("nannou-{}.desktop", scheme);
+    let desktop_path = applications.join(&desktop_name);
+    let desktop = format!(
+        "[Desktop Entry]\nType=Application\nName={scheme}\nExec=\"{exe}\" %u\n\
+         NoDisplay=true\nMimeType=x-scheme-handler/{scheme};\n",
+        scheme = scheme,
+        exe = exe.display(),
+    );
+    let mime = format!("x-scheme-handler/{}", scheme);
+
+    // Skip registration if the entry is unchanged and already the default handler.
+    let entry_unchanged = std::fs::read_to_string(&desktop_path)
+        .map(|existing| existing == desktop)
+        .unwrap_or(false);
+    let is_default = Command::new("xdg-mime")
+        .args(&["query", "default", &mime])
+        .output()
+        .map(|out| String::from_utf8_lossy(&out.stdout).trim() == desktop_name)
+        .unwrap_or(false);
+    if entry_unchanged && is_default {
+        return Ok(());
+    }
+
+    std::fs::create_dir_all(&applications)?;
+    std::fs::write(&desktop_path, desktop)?;
+    let status = Command::new("xdg-mime")
+        .args(&["default", &desktop_name, &mime])
+        .status()?;
+    match status.success() {
+        true => Ok(()),
+        false => Err(io::Error::new(io::ErrorKind::Other, "`xdg-mime` failed")),
+    }
+}
+
+#[cfg(windows)]
+fn register(scheme: &str, exe: &Path) -> io::Result<()> {
+    use std::process::Command;
+    let key = format!("HKCU\\Software\\Classes\\{}", scheme);
+    let command_key = format!("{}\\shell\\open\\command", key);
+    let description = format!("URL:{}", scheme);
+    let command = format!("\"{}\" \"%1\"", exe.display());
+
+    // Skip registration if the scheme already opens this executable.
+    let registered = Command::new("reg")
+        .args(&["query", &command_key, "/ve"])
+        .output()
+        .map(|out| String::from_utf8_lossy(&out.stdout).contains(&command))
+        .unwrap_or(false);
+    if registered {
+        return Ok(());
+    }
+
+    let entries: [&[&str]; 3] = [
+        &["add", &key, "/ve", "/d", &description, "/f"],
+        &["add", &key, "/v", "URL Protocol", "/d", "", "/f"],
+        &["add", &command_key, "/ve", "/d", &command, "/f"],
+    ];
+    for args in entries.iter() {
+        if !Command::new("reg").args(*args).status()?.success() {
+            return Err(io::Error::new(io::ErrorKind::Other, "`reg add` failed"));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(any(windows, all(unix, not(target_os = "macos")))))]
+fn register(_scheme: &str, _exe: &Path) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Other,
+        "URL schemes must be declared via `CFBundleURLTypes` within the app bundle's `Info.plist`",
+    ))
+}
+
+// Forward the pending URLs to a running instance if there is one, otherwise listen on a loopback
+// socket for URLs forwarded by later instances.
+#[cfg(not(target_os = "macos"))]
+fn listen(
+    scheme: &str,
+    pending: &mut Vec<String>,
+    tx: mpsc::Sender<String>,
+    proxy: Proxy,
+) -> io::Result<bool> {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+    use std::time::Duration;
+
+    // The maximum length of a forwarded URL in bytes.
+    const MAX_URL_LEN: u64 = 8 * 1024;
+    const TIMEOUT: Duration = Duration::from_secs(1);
+
+    let port_path = env::temp_dir().join(format!("nannou-{}.port", scheme));
+
+    // The port file may be stale, in which case this instance becomes the listener.
+    if !pending.is_empty() {
+        let port = std::fs::read_to_string(&port_path)
+            .ok()
+            .and_then(|s| s.trim().parse::<u16>().ok());
+        if let Some(port) = port {
+            let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+            if let Ok(mut stream) = TcpStream::connect_timeout(&addr, TIMEOUT) {
+                for url in pending.drain(..) {
+                    writeln!(stream, "{}", url)?;
+                }
+                stream.flush()?;
+                return Ok(true);
+            }
+        }
+    }
+
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+    let port = listener.local_addr()?.port();
+    std::fs::write(&port_path, port.to_string())?;
+    let scheme = scheme.to_string();
+    std::thread::Builder::new()
+        .name(format!("nannou_url_scheme_{}", scheme))
+        .spawn(move || {
+            for stream in listener.incoming() {
+                let stream = match stream {
+                    Ok(stream) => stream,
+                    Err(_) => continue,
+                };
+                if stream.set_read_timeout(Some(TIMEOUT)).is_err() {
+                    continue;
+                }
+                let mut reader = BufReader::new(stream);
+                loop {
+                    let mut url = String::new();
+                    match (&mut reader).take(MAX_URL_LEN).read_line(&mut url) {
+                        Ok(0) | Err(_) => break,
+                        Ok(_) => (),
+                    }
+                    let url = url.trim();
+                    if !scheme_matches(&scheme, url) {
+                        continue;
+                    }
+                    if tx.send(url.to_string()).is_err() {
+                        return;
+                    }
+                    proxy.wakeup().ok();
+                }
+            }
+        })?;
+    Ok(false)
+}
+
+// URLs are delivered to the running app by the OS via Apple events.
+#[cfg(target_os = "macos")]
+fn listen(
+    scheme: &str,
+    _pending: &mut Vec<String>,
+    tx: mpsc::Sender<String>,
+    proxy: Proxy,
+) -> io::Result<bool> {
+    let listener = apple_event::Listener {
+        scheme: scheme.to_string(),
+        tx,
+        proxy,
+    };
+    apple_event::listeners()
+        .lock()
+        .expect("failed to acquire url scheme listeners lock")
+        .push(listener);
+    Ok(false)
+}
+
+// Handling of `kAEGetURL` events delivered via the `NSAppleEventManager`.
+#[cfg(target_os = "macos")]
+mod apple_event {
+    use crate::app::Proxy;
+    use objc::declare::ClassDecl;
+    use objc::runtime::{Object, Sel};
+    use objc::{class, msg_send, sel, sel_impl};
+    use std::ffi::CStr;
+    use std::os::raw::c_char;
+    use std::sync::{mpsc, Mutex, Once};
+
+    // The four character codes `'GURL'` and `'----'`.
+    const K_INTERNET_EVENT_CLASS: u32 = 0x4755_524c;
+    const K_AE_GET_URL: u32 = 0x4755_524c;
+    const KEY_DIRECT_OBJECT: u32 = 0x2d2d_2d2d;
+
+    // A scheme registered by the app along with where to deliver its URLs.
+    pub(super) struct Listener {
+        pub(super) scheme: String,
+        pub(super) tx: mpsc::Sender<String>,
+        pub(super) proxy: Proxy,
+    }
+
+    // The listeners of all registered schemes, installing the event handler upon first access.
+    //
+    // The OS only allows for a single handler per event, so one handler delivers the URLs of all
+    // schemes.
+    pub(super) fn listeners() -> &'static Mutex<Vec<Listener>> {
+        static INIT: Once = Once::new();
+        static mut LISTENERS: *const Mutex<Vec<Listener>> = std::ptr::null();
+        unsafe {
+            INIT.call_once(|| {
+                LISTENERS = Box::into_raw(Box::new(Mutex::new(vec![])));
+                install_handler();
+            });
+            &*LISTENERS
+        }
+    }
+
+    unsafe fn install_handler() {
+        let mut decl = ClassDecl::new("NannouUrlSchemeHandler", class!(NSObject))
+            .expect("failed to declare url scheme handler class");
+        decl.add_method(
+            sel!(handleGetURLEvent:withReplyEvent:),
+            handle_get_url as extern "C" fn(&Object, Sel, *mut Object, *mut Object),
+        );
+        let class = decl.register();
+        let handler: *mut Object = msg_send![class, new];
+        let manager: *mut Object = msg_send![class!(NSAppleEventManager), sharedAppleEventManager];
+        let () = msg_send![manager,
+            setEventHandler: handler
+            andSelector: sel!(handleGetURLEvent:withReplyEvent:)
+            forEventClass: K_INTERNET_EVENT_CLASS
+            andEventID: K_AE_GET_URL];
+    }
+
+    extern "C" fn handle_get_url(_: &Object, _: Sel, event: *mut Object, _reply: *mut Object) {
+        let url = unsafe {
+            let descriptor: *mut Object =
+                msg_send![event, paramDescriptorForKeyword: KEY_DIRECT_OBJECT];
+            if descriptor.is_null() {
+                return;
+            }
+            let string: *mut Object = msg_send![descriptor, stringValue];
+            if string.is_null() {
+                return;
+            }
+            let utf8: *const c_char = msg_send![string, UTF8String];
+            CStr::from_ptr(utf8).to_string_lossy().into_owned()
+        };
+        let listeners = match listeners().lock() {
+            Ok(listeners) => listeners,
+            Err(_) => return,
+        };
+        for listener in listeners.iter() {
+            if super::scheme_matches(&listener.scheme, &url) {
+                listener.tx.send(url.clone()).ok();
+                listener.proxy.wakeup().ok();
+            }
+        }
+    }
+}
@@ -0,0 +1,400 @@
+//! WebSocket servers and clients, provided by the [tungstenite](https://docs.rs/tungstenite)
+//! crate, e.g. for synchronising state between the machines of a multi-screen installation.
+//!
+//! Requires the `websocket` feature. Each connection is read on a background thread of its own that
+//! blocks until a message arrives, with received messages delivered to the model before the next update via `on_message`:
+//!
+//! ```ignore
+//! fn model(app: &App) -> Model {
+//!     let server = ws::Server::bind("0.0.0.0:9001").unwrap();
+//!     server.on_message(app, |model: &mut Model, client_id, msg| {
+//!         model.last_message = Some((client_id, msg.to_vec()));
+//!     });
+//!     Model { server, last_message: None }
+//! }
+//!
+//! fn update(_app: &App, model: &mut Model, _update: Update) {
+//!     model.server.broadcast(&ws::json(&model.state).unwrap());
+//! }
+//! ```
+//!
+//! Only unencrypted `ws://` connections are supported.
+
+use crate::app::Proxy;
+use crate::App;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::{fmt, io, thread};
+use tungstenite::protocol::Role;
+use tungstenite::{Message, WebSocket};
+
+pub use tungstenite;
+
+/// The function signature of a handler for messages received by a **Server**.
+pub type ServerMessageFn<Model> = fn(&mut Model, usize, &[u8]);
+
+/// The function signature of a handler for messages received by a **Client**.
+pub type ClientMessageFn<Model> = fn(&mut Model, &[u8]);
+
+/// An alias for **Server**.
+pub type WsServer = Server;
+
+/// An alias for **Client**.
+pub type WsClient = Client;
+
+/// A WebSocket server accepting connections on a background thread.
+///
+/// Each connected client is identified by a unique `usize` assigned upon connection. Cloning a
+/// **Server** produces a handle to the same server.
+#[derive(Clone)]
+pub struct Server {
+    addr: SocketAddr,
+    clients: Arc<Mutex<HashMap<usize, WebSocket<TcpStream>>>>,
+    incoming: Arc<Mutex<mpsc::Receiver<(usize, Vec<u8>)>>>,
+    waker: Waker,
+}
+
+/// A connection to a WebSocket server.
+///
+/// Cloning a **Client** produces a handle to the same connection.
+#[derive(Clone)]
+pub struct Client {
+    url: String,
+    outgoing: Arc<Mutex<WebSocket<TcpStream>>>,
+    incoming: Arc<Mutex<mpsc::Receiver<Vec<u8>>>>,
+    waker: Waker,
+}
+
+/// Errors that might occur while binding a server or connecting a client.
+#[derive(Debug)]
+pub enum Error {
+    /// An error occurred on the underlying TCP stream.
+    Io(io::Error),
+    /// The WebSocket handshake failed.
+    Handshake(String),
+    /// The given URL was invalid or did not use the `ws` scheme.
+    InvalidUrl(String),
+}
+
+// Wakes the app's event loop upon receiving messages once registered via `on_message`.
+#[derive(Clone, Default)]
+struct Waker {
+    proxy: Arc<Mutex<Option<Proxy>>>,
+}
+
+impl Server {
+    /// Bind a server to the given address, e.g. `"0.0.0.0:9001"`.
+    pub fn bind(addr: &str) -> Result<Self, Error> {
+        let listener = TcpListener::bind(addr)?;
+        let addr = listener.local_addr()?;
+        let clients = Arc::new(Mutex::new(HashMap::new()));
+        let (tx, rx) = mpsc::channel();
+        let waker = Waker::default();
+        let thread_clients = clients.clone();
+        let thread_waker = waker.clone();
+        thread::Builder::new()
+            .name(format!("nannou_ws_server_{}", addr.port()))
+            .spawn(move || accept(listener, thread_clients, tx, thread_waker))?;
+        Ok(Server {
+            addr,
+            clients,
+            incoming: Arc::new(Mutex::new(rx)),
+            waker,
+        })
+    }
+
+    /// The address to which the server is bound.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// The IDs of all currently connected clients.
+    pub fn clients(&self) -> Vec<usize> {
+        match self.clients.lock() {
+            Ok(clients) => clients.keys().cloned().collect(),
+            Err(_) => vec![],
+        }
+    }
+
+    /// Send the given binary message to all connected clients.
+    ///
+    /// Messages are written on the calling thread.
+    pub fn broadcast(&self, msg: &[u8]) {
+        if let Ok(mut clients) = self.clients.lock() {
+            for socket in clients.values_mut() {
+                write(socket, msg);
+            }
+        }
+    }
+
+    /// Send the given binary message to the client with the given ID.
+    ///
+    /// Returns `false` if no client with the ID is connected or if writing failed.
+    pub fn send(&self, client_id: usize, msg: &[u8]) -> bool {
+        match self.clients.lock() {
+            Ok(mut clients) => clients
+                .get_mut(&client_id)
+                .map(|socket| write(socket, msg))
+                .unwrap_or(false),
+            Err(_) => false,
+        }
+    }
+
+    /// The next message received from any client along with the client's ID, or `None` if no
+    /// message is available.
+    ///
+    /// This never blocks. Text messages are yielded as their UTF-8 bytes.
+    pub fn try_recv(&self) -> Option<(usize, Vec<u8>)> {
+        self.incoming.lock().ok()?.try_recv().ok()
+    }
+
+    /// Call `handler` with the model for each message received from any client.
+    ///
+    /// Messages are delivered before each update, after which they are no longer available via
+    /// `try_recv`. The `Model` type must match the app's model type, otherwise the app will
+    /// **panic!** upon delivering a message.
+    pub fn on_message<M>(&self, app: &App, handler: ServerMessageFn<M>)
+    where
+        M: 'static,
+    {
+        self.waker.register(app);
+        app.net_handlers
+            .borrow_mut()
+            .add_ws_server(self.clone(), handler);
+    }
+}
+
+impl Client {
+    /// Connect to the server at the given URL, e.g. `"ws://192.168.1.10:9001"`.
+    ///
+    /// Blocks until the connection is established and the handshake is complete.
+    pub fn connect(url: &str) -> Result<Self, Error> {
+        let uri: tungstenite::http::Uri = url
+            .parse()
+            .map_err(|_| Error::InvalidUrl(url.to_string()))?;
+        if uri.scheme_str() != Some("ws") {
+            return Err(Error::InvalidUrl(url.to_string()));
+        }
+        let host = uri
+            .host()
+            .ok_or_else(|| Error::InvalidUrl(url.to_string()))?;
+        let port = uri.port_u16().unwrap_or(80);
+        let addr = (host, port)
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| Error::InvalidUrl(url.to_string()))?;
+        let stream = TcpStream::connect(addr)?;
+        let (socket, _response) =
+            tungstenite::client(url, stream).map_err(|err| Error::Handshake(err.to_string()))?;
+        let (reader, writer) = split(socket, Role::Client)?;
+
+        let (incoming_tx, incoming_rx) = mpsc::channel();
+        let waker = Waker::default();
+        let thread_waker = waker.clone();
+        thread::Builder::new()
+            .name(format!("nannou_ws_client_{}", addr))
+            .spawn(move || {
+                service(reader, |msg| {
+                    let sent = incoming_tx.send(msg).is_ok();
+                    thread_waker.wake();
+                    sent
+                })
+            })?;
+        Ok(Client {
+            url: url.to_string(),
+            outgoing: Arc::new(Mutex::new(writer)),
+            incoming: Arc::new(Mutex::new(incoming_rx)),
+            waker,
+        })
+    }
+
+    /// The URL to which the client connected.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Send the given binary message to the server.
+    ///
+    /// The message is written on the calling thread. Returns `false` if the connection has closed.
+    pub fn send(&self, msg: &[u8]) -> bool {
+        match self.outgoing.lock() {
+            Ok(mut socket) => write(&mut socket, msg),
+            Err(_) => false,
+        }
+    }
+
+    /// The next message received from the server, or `None` if no message is available.
+    ///
+    /// This never blocks. Text messages are yielded as their UTF-8 bytes.
+    pub fn try_recv(&self) -> Option<Vec<u8>> {
+        self.incoming.lock().ok()?.try_recv().ok()
+    }
+
+    /// Call `handler` with the model for each message received from the server.
+    ///
+    /// Messages are delivered before each update, after which they are no longer available via
+    /// `try_recv`. The `Model` type must match the app's model type, otherwise the app will
+    /// **panic!** upon delivering a message.
+    pub fn on_message<M>(&self, app: &App, handler: ClientMessageFn<M>)
+    where
+        M: 'static,
+    {
+        self.waker.register(app);
+        app.net_handlers
+            .borrow_mut()
+            .add_ws_client(self.clone(), handler);
+    }
+}
+
+impl Waker {
+    fn register(&self, app: &App) {
+        if let Ok(mut proxy) = self.proxy.lock() {
+            *proxy = Some(app.create_proxy());
+        }
+    }
+
+    fn wake(&self) {
+        if let Ok(proxy) = self.proxy.lock() {
+            if let Some(ref proxy) = *proxy {
+                proxy.wakeup().ok();
+            }
+        }
+    }
+}
+
+/// Serialise the given value to JSON, e.g. for sending via `Server::broadcast` or `Client::send`.
+pub fn json<T>(value: &T) -> serde_json::Result<Vec<u8>>
+where
+    T: Serialize,
+{
+    serde_json::to_vec(value)
+}
+
+// Accept connections, reading from each on its own thread.
+fn accept(
+    listener: TcpListener,
+    clients: Arc<Mutex<HashMap<usize, WebSocket<TcpStream>>>>,
+    incoming: mpsc::Sender<(usize, Vec<u8>)>,
+    waker: Waker,
+) {
+    let next_id = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(err) => {
+                eprintln!("failed to accept websocket connection: {}", err);
+                continue;
+            }
+        };
+        let clients = clients.clone();
+        let incoming = incoming.clone();
+        let waker = waker.clone();
+        let id = next_id.fetch_add(1, Ordering::Relaxed);
+        thread::spawn(move || {
+            let socket = match tungstenite::accept(stream) {
+                Ok(socket) => socket,
+                Err(err) => {
+                    eprintln!("websocket handshake failed: {}", err);
+                    return;
+                }
+            };
+            let (reader, writer) = match split(socket, Role::Server) {
+                Ok(sockets) => sockets,
+                Err(err) => {
+                    eprintln!("failed to clone websocket stream: {}", err);
+                    return;
+                }
+            };
+            if let Ok(mut clients) = clients.lock() {
+                clients.insert(id, writer);
+            }
+            service(reader, |msg| {
+                let sent = incoming.send((id, msg)).is_ok();
+                waker.wake();
+                sent
+            });
+            if let Ok(mut clients) = clients.lock() {
+                clients.remove(&id);
+            }
+        });
+    }
+}
+
+// Split the socket into one for reading and another sharing the same stream for writing, allowing
+// for writing while the reading socket blocks.
+//
+// The reading socket retains any data buffered during the handshake.
+fn split(
+    socket: WebSocket<TcpStream>,
+    role: Role,
+) -> io::Result<(WebSocket<TcpStream>, WebSocket<TcpStream>)> {
+    let stream = socket.get_ref().try_clone()?;
+    let writer = WebSocket::from_raw_socket(stream, role, None);
+    Ok((socket, writer))
+}
+
+// Block upon reading from the socket until the connection closes or the receiving handle is
+// dropped, in which case `received` returns `false`.
+fn service<F>(mut socket: WebSocket<TcpStream>, mut received: F)
+where
+    F: FnMut(Vec<u8>) -> bool,
+{
+    loop {
+        let msg = match socket.read_message() {
+            Ok(Message::Binary(bytes)) => bytes,
+            Ok(Message::Text(text)) => text.into_bytes(),
+            Ok(Message::Close(_)) | Err(_) => break,
+            Ok(_) => continue,
+        };
+        if !received(msg) {
+            break;
+        }
+    }
+    // Ensure that writes to the connection fail from here on.
+    socket.get_ref().shutdown(Shutdown::Both).ok();
+}
+
+// Write the given binary message, returning `false` if the connection has closed.
+fn write(socket: &mut WebSocket<TcpStream>, msg: &[u8]) -> bool {
+    socket.write_message(Message::Binary(msg.to_vec())).is_ok()
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl std::error::Error for Error {
+    fn cause(&self) -> Option<&dyn std::error::Error> {
+        match *self {
+            Error::Io(ref err) => Some(err),
+            Error::Handshake(_) | Error::InvalidUrl(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Io(ref err) => err.fmt(f),
+            Error::Handshake(ref err) => write!(f, "websocket handshake failed: {}", err),
+            Error::InvalidUrl(ref url) => write!(f, "invalid websocket url: `{}`", url),
+        }
+    }
+}
+
+impl fmt::Debug for Server {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Server").field("addr", &self.addr).finish()
+    }
+}
+
+impl fmt::Debug for Client {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Client").field("url", &self.url).finish()
+    }
+}
@@ -1,4 +1,305 @@
 //! Items related to [noise generation](https://docs.rs/noise/0.5.1/noise/), API offered via the
 //! [noise-rs crate](https://docs.rs/noise).
+//!
+//! **SimplexNoise**, **PerlinNoise** and **WorleyNoise** wrap their noise-rs counterparts,
+//! providing a unified `f32` API via the **NoiseSource** trait. They may be layered via **Fbm** and
+//! **Ridged**, while **CurlNoise2d** produces a divergence-free vector field from any
+//! **NoiseSource**.
+//!
+//! The wrappers also implement the noise-rs `NoiseFn` and `Seedable` traits.
 
+use crate::geom::{vec2, Vector2};
+
+pub use noise;
 pub use noise::*;
+
+/// A source of coherent noise that may be sampled in two, three or four dimensions.
+///
+/// All values are expected to be within the range `-1.0..=1.0`.
+pub trait NoiseSource {
+    /// Sample the noise at the given 2D point.
+    fn get_2d(&self, x: f32, y: f32) -> f32;
+
+    /// Sample the noise at the given 3D point.
+    fn get_3d(&self, x: f32, y: f32, z: f32) -> f32;
+
+    /// Sample the noise at the given 4D point.
+    fn get_4d(&self, x: f32, y: f32, z: f32, w: f32) -> f32;
+}
+
+/// Simplex noise, provided by the noise-rs `OpenSimplex` generator.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SimplexNoise(noise::OpenSimplex);
+
+/// Perlin gradient noise.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct PerlinNoise(noise::Perlin);
+
+/// Worley cellular noise, also known as Voronoi noise.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct WorleyNoise(noise::Worley);
+
+/// Fractal Brownian motion, the sum of multiple octaves of a noise source at increasing
+/// frequencies and decreasing amplitudes.
+#[derive(Clone, Debug)]
+pub struct Fbm<N> {
+    source: N,
+    octaves: u32,
+    lacunarity: f32,
+    gain: f32,
+}
+
+/// A ridged multifractal, producing sharp ridges from the absolute value of each octave of a
+/// noise source.
+#[derive(Clone, Debug)]
+pub struct Ridged<N> {
+    source: N,
+    octaves: u32,
+    lacunarity: f32,
+    gain: f32,
+}
+
+/// A 2D vector field derived from the curl of a scalar noise source.
+///
+/// The resulting field is divergence-free, producing the swirling, non-converging motion often
+/// desired for flow fields and particle systems.
+#[derive(Clone, Debug)]
+pub struct CurlNoise2d<N> {
+    source: N,
+    epsilon: f32,
+}
+
+macro_rules! impl_noise_wrapper {
+    ($Wrapper:ident, $Source:ty) => {
+        impl $Wrapper {
+            /// A new generator with the default seed.
+            pub fn new() -> Self {
+                $Wrapper(<$Source>::new())
+            }
+
+            /// A new generator with the given seed.
+            pub fn with_seed(seed: u32) -> Self {
+                $Wrapper(<$Source>::new().set_seed(seed))
+            }
+        }
+
+        impl NoiseSource for $Wrapper {
+            fn get_2d(&self, x: f32, y: f32) -> f32 {
+                self.0.get([x as f64, y as f64]) as f32
+            }
+
+            fn get_3d(&self, x: f32, y: f32, z: f32) -> f32 {
+                self.0.get([x as f64, y as f64, z as f64]) as f32
+            }
+
+            fn get_4d(&self, x: f32, y: f32, z: f32, w: f32) -> f32 {
+                self.0.get([x as f64, y as f64, z as f64, w as f64]) as f32
+            }
+        }
+
+        impl Seedable for $Wrapper {
+            fn set_seed(self, seed: u32) -> Self {
+                $Wrapper(self.0.set_seed(seed))
+            }
+
+            fn seed(&self) -> u32 {
+                self.0.seed()
+            }
+        }
+
+        impl NoiseFn<[f64; 2]> for $Wrapper {
+            fn get(&self, point: [f64; 2]) -> f64 {
+                self.0.get(point)
+            }
+        }
+
+        impl NoiseFn<[f64; 3]> for $Wrapper {
+            fn get(&self, point: [f64; 3]) -> f64 {
+                self.0.get(point)
+            }
+        }
+
+        impl NoiseFn<[f64; 4]> for $Wrapper {
+            fn get(&self, point: [f64; 4]) -> f64 {
+                self.0.get(point)
+            }
+        }
+    };
+}
+
+impl_noise_wrapper!(SimplexNoise, noise::OpenSimplex);
+impl_noise_wrapper!(PerlinNoise, noise::Perlin);
+impl_noise_wrapper!(WorleyNoise, noise::Worley);
+
+impl WorleyNoise {
+    /// Access the inner noise-rs generator, e.g. to change its range or distance function.
+    pub fn inner(&self) -> &noise::Worley {
+        &self.0
+    }
+
+    /// Map the inner noise-rs generator, e.g. to change its range or distance function.
+    pub fn map_inner<F>(self, f: F) -> Self
+    where
+        F: FnOnce(noise::Worley) -> noise::Worley,
+    {
+        WorleyNoise(f(self.0))
+    }
+}
+
+macro_rules! impl_fractal_builder {
+    ($Fractal:ident) => {
+        impl<N> $Fractal<N> {
+            /// The default number of octaves.
+            pub const DEFAULT_OCTAVES: u32 = 6;
+            /// The default factor by which the frequency increases each octave.
+            pub const DEFAULT_LACUNARITY: f32 = 2.0;
+            /// The default factor by which the amplitude decreases each octave.
+            pub const DEFAULT_GAIN: f32 = 0.5;
+
+            /// Layer octaves of the given noise source.
+            pub fn new(source: N) -> Self {
+                $Fractal {
+                    source,
+                    octaves: Self::DEFAULT_OCTAVES,
+                    lacunarity: Self::DEFAULT_LACUNARITY,
+                    gain: Self::DEFAULT_GAIN,
+                }
+            }
+
+            /// The number of octaves to sum. Values below `1` are treated as `1`.
+            pub fn octaves(mut self, octaves: u32) -> Self {
+                self.octaves = octaves;
+                self
+            }
+
+            /// The factor by which the frequency increases each octave.
+            pub fn lacunarity(mut self, lacunarity: f32) -> Self {
+                self.lacunarity = lacunarity;
+                self
+            }
+
+            /// The factor by which the amplitude decreases each octave.
+            pub fn gain(mut self, gain: f32) -> Self {
+                self.gain = gain;
+                self
+            }
+
+            /// The layered noise source.
+            pub fn source(&self) -> &N {
+                &self.source
+            }
+        }
+
+        impl<N> NoiseSource for $Fractal<N>
+        where
+            N: NoiseSource,
+        {
+            fn get_2d(&self, x: f32, y: f32) -> f32 {
+                self.sum(|f| self.source.get_2d(x * f, y * f))
+            }
+
+            fn get_3d(&self, x: f32, y: f32, z: f32) -> f32 {
+                self.sum(|f| self.source.get_3d(x * f, y * f, z * f))
+            }
+
+            fn get_4d(&self, x: f32, y: f32, z: f32, w: f32) -> f32 {
+                self.sum(|f| self.source.get_4d(x * f, y * f, z * f, w * f))
+            }
+        }
+    };
+}
+
+impl_fractal_builder!(Fbm);
+impl_fractal_builder!(Ridged);
+
+impl<N> Fbm<N> {
+    // Sum the octaves produced by `sample` for each frequency, normalised to `-1.0..=1.0`.
+    fn sum<F>(&self, sample: F) -> f32
+    where
+        F: Fn(f32) -> f32,
+    {
+        let mut total = 0.0;
+        let mut max = 0.0;
+        let mut frequency = 1.0;
+        let mut amplitude = 1.0;
+        for _ in 0..self.octaves.max(1) {
+            total += sample(frequency) * amplitude;
+            max += amplitude;
+            frequency *= self.lacunarity;
+            amplitude *= self.gain;
+        }
+        total / max
+    }
+}
+
+impl<N> Ridged<N> {
+    // Sum the ridges of the octaves produced by `sample`, normalised to `-1.0..=1.0`.
+    //
+    // Each octave is weighted by the previous octave's ridge so that detail accumulates along the
+    // ridges rather than in the valleys.
+    fn sum<F>(&self, sample: F) -> f32
+    where
+        F: Fn(f32) -> f32,
+    {
+        let mut total = 0.0;
+        let mut max = 0.0;
+        let mut frequency = 1.0;
+        let mut amplitude = 1.0;
+        let mut weight = 1.0;
+        for _ in 0..self.octaves.max(1) {
+            let ridge = 1.0 - sample(frequency).abs().min(1.0);
+            let signal = ridge * ridge * weight;
+            weight = signal;
+            total += signal * amplitude;
+            max += amplitude;
+            frequency *= self.lacunarity;
+            amplitude *= self.gain;
+        }
+        total / max * 2.0 - 1.0
+    }
+}
+
+impl<N> CurlNoise2d<N>
+where
+    N: NoiseSource,
+{
+    /// The default distance over which the gradient of the noise is approximated.
+    pub const DEFAULT_EPSILON: f32 = 1e-3;
+
+    /// Derive a vector field from the given noise source.
+    pub fn new(source: N) -> Self {
+        let epsilon = Self::DEFAULT_EPSILON;
+        CurlNoise2d { source, epsilon }
+    }
+
+    /// The distance over which the gradient of the noise is approximated via central differences.
+    pub fn epsilon(mut self, epsilon: f32) -> Self {
+        self.epsilon = epsilon;
+        self
+    }
+
+    /// The noise source from which the field is derived.
+    pub fn source(&self) -> &N {
+        &self.source
+    }
+
+    /// The vector at the given point in the field.
+    ///
+    /// The magnitude of the vector is proportional to the steepness of the noise at the point.
+    pub fn get(&self, x: f32, y: f32) -> Vector2<f32> {
+        let e = self.epsilon;
+        let dx = self.source.get_2d(x + e, y) - self.source.get_2d(x - e, y);
+        let dy = self.source.get_2d(x, y + e) - self.source.get_2d(x, y - e);
+        vec2(dy, -dx) / (2.0 * e)
+    }
+
+    /// The vector at the given point in the field, sampling the 3D noise source at the slice `z`.
+    ///
+    /// Varying `z` over time allows for animating the field.
+    pub fn get_3d(&self, x: f32, y: f32, z: f32) -> Vector2<f32> {
+        let e = self.epsilon;
+        let dx = self.source.get_3d(x + e, y, z) - self.source.get_3d(x - e, y, z);
+        let dy = self.source.get_3d(x, y + e, z) - self.source.get_3d(x, y - e, z);
+        vec2(dy, -dx) / (2.0 * e)
+    }
+}
@@ -0,0 +1,471 @@
+//! A particle system simulated on the GPU via a compute shader.
+//!
+//! A **ParticleSystem** is created with a fixed capacity and stored within the model. Particles
+//! are spawned via `emit` and simulated via `update`, after which the system is drawn via
+//! `draw.particle_system(&system)`:
+//!
+//! ```ignore
+//! fn update(app: &App, model: &mut Model, update: Update) {
+//!     let dt = update.since_last.secs() as f32;
+//!     model.particles.emit(app.mouse.position(), vec2(0.0, 100.0), 3.0, PLUM);
+//!     let forces = [Force::Gravity(vec2(0.0, -98.0)), Force::Drag(0.2)];
+//!     model.particles.update(dt, &forces);
+//! }
+//!
+//! fn view(app: &App, model: &Model, frame: Frame) {
+//!     let draw = app.draw();
+//!     draw.background().color(BLACK);
+//!     draw.particle_system(&model.particles);
+//!     draw.to_frame(app, &frame).unwrap();
+//! }
+//! ```
+//!
+//! Particle data lives entirely within a GPU buffer, so there is no per-frame cost on the CPU for
+//! simulating or drawing a particle. Emissions and updates are queued and encoded to the GPU the
+//! next time the system is drawn, before the simulated particles are drawn via a single instanced
+//! draw call.
+//!
+//! Particles may also be carried along a **FlowField2D** via `set_flow_field`. The field is sampled
+//! onto a grid on the CPU during each update, which is interpolated by the compute shader.
+//!
+//! The GPU resources of a system are created on the device of the first window to which it is
+//! drawn. A system should not be drawn to windows on different devices.
+
+use crate::color::{IntoLinSrgba, LinSrgba};
+use crate::draw::properties::ColorScalar;
+use crate::field::{FlowField2D, FlowGrid};
+use crate::geom::{Point2, Vector2};
+use crate::noise::NoiseSource;
+use crate::wgpu;
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// The maximum number of **Force::Attractor**s applied during a single update.
+///
+/// Any additional attractors are ignored.
+pub const MAX_ATTRACTORS: usize = 16;
+
+/// The maximum number of updates that may be queued before the system is drawn.
+///
+/// The oldest updates are discarded if the system is updated more often than this between draws.
+pub const MAX_PENDING_UPDATES: usize = 16;
+
+// The number of invocations per work group within the compute shader.
+const WORK_GROUP_SIZE: u32 = 64;
+
+/// A force applied to every live particle during an update.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Force {
+    /// A constant acceleration applied to all particles.
+    Gravity(Vector2),
+    /// A deceleration proportional to the velocity of each particle.
+    Drag(f32),
+    /// An acceleration toward `pos`, proportional to `strength` and inversely proportional to the
+    /// squared distance from `pos`. A negative `strength` repels particles.
+    Attractor { pos: Point2, strength: f32 },
+}
+
+/// A fixed-capacity set of particles simulated and drawn on the GPU.
+///
+/// Emitting a particle while the system is full replaces its oldest particle.
+#[derive(Debug)]
+pub struct ParticleSystem {
+    max_count: u32,
+    // The slot to which the next particle will be emitted.
+    next_slot: u32,
+    // The total simulated time in seconds.
+    time: f64,
+    // The simulated time at which the particle in each slot dies, mirroring the GPU state.
+    deaths: Vec<f64>,
+    flow_field: Option<FlowField>,
+    shared: Handle,
+}
+
+// The flow field carrying the particles of a system.
+enum FlowField {
+    // A field that does not vary over time, sampled once.
+    Static(Arc<FlowGrid>),
+    // Samples a time-varying field at the given time.
+    Dynamic(Box<dyn Fn(f32) -> FlowGrid + Send>),
+}
+
+// A handle to the state of a system held by the draw primitives that refer to it.
+pub(crate) type Handle = Arc<Mutex<Shared>>;
+
+// The state shared between the system and the draw primitives that refer to it.
+#[derive(Debug)]
+pub(crate) struct Shared {
+    max_count: u32,
+    // Particles emitted since the system was last drawn, along with their slots.
+    pending_emissions: VecDeque<(u32, GpuParticle)>,
+    // Updates since the system was last drawn.
+    pending_updates: VecDeque<Update>,
+    gpu: Option<Gpu>,
+}
+
+// A single queued simulation step.
+#[derive(Debug)]
+struct Update {
+    uniforms: Uniforms,
+    flow_grid: Option<Arc<FlowGrid>>,
+}
+
+#[derive(Debug)]
+struct Gpu {
+    particle_buffer: Arc<wgpu::Buffer>,
+    // The zeroed grid bound when the system has no flow field.
+    empty_flow_buffer: wgpu::Buffer,
+    // The most recently uploaded flow field grid, reused until the grid changes.
+    flow_buffer: Option<(Arc<FlowGrid>, wgpu::Buffer)>,
+    bind_group_layout: wgpu::BindGroupLayout,
+    pipeline: wgpu::ComputePipeline,
+}
+
+/// The layout of a particle within the GPU buffer, matching `particles.comp`.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+pub(crate) struct GpuParticle {
+    position: [f32; 2],
+    velocity: [f32; 2],
+    color: [f32; 4],
+    age: f32,
+    lifetime: f32,
+    _padding: [f32; 2],
+}
+
+// The uniforms of a single update, matching `particles.comp`.
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+struct Uniforms {
+    gravity: [f32; 2],
+    drag: f32,
+    dt: f32,
+    particle_count: u32,
+    attractor_count: u32,
+    field_cols: u32,
+    field_rows: u32,
+    field_min: [f32; 2],
+    field_size: [f32; 2],
+    attractors: [[f32; 4]; MAX_ATTRACTORS],
+}
+
+impl ParticleSystem {
+    /// Create a system with room for at most `max_count` live particles.
+    ///
+    /// **Panics** if `max_count` is `0`.
+    pub fn new(max_count: u32) -> Self {
+        assert!(
+            max_count > 0,
+            "a particle system requires a `max_count` of at least 1"
+        );
+        let shared = Shared {
+            max_count,
+            pending_emissions: VecDeque::new(),
+            pending_updates: VecDeque::new(),
+            gpu: None,
+        };
+        ParticleSystem {
+            max_count,
+            next_slot: 0,
+            time: 0.0,
+            deaths: vec![0.0; max_count as usize],
+            flow_field: None,
+            shared: Arc::new(Mutex::new(shared)),
+        }
+    }
+
+    /// The maximum number of live particles.
+    pub fn max_count(&self) -> u32 {
+        self.max_count
+    }
+
+    /// The number of live particles.
+    pub fn len(&self) -> usize {
+        let time = self.time;
+        self.deaths.iter().filter(|&&death| death > time).count()
+    }
+
+    /// Whether or not there are no live particles.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Spawn a particle at `position` that lives for `lifetime` seconds.
+    ///
+    /// The entire particle is drawn with the given `color`, multiplied by the colour of the
+    /// sprite.
+    pub fn emit<C>(&mut self, position: Point2, velocity: Vector2, lifetime: f32, color: C)
+    where
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        let slot = self.next_slot;
+        self.next_slot = (slot + 1) % self.max_count;
+        self.deaths[slot as usize] = self.time + lifetime as f64;
+        let LinSrgba { color, alpha } = color.into_lin_srgba();
+        let particle = GpuParticle {
+            position: [position.x, position.y],
+            velocity: [velocity.x, velocity.y],
+            color: [color.red, color.green, color.blue, alpha],
+            age: 0.0,
+            lifetime,
+            _padding: [0.0; 2],
+        };
+        let mut shared = self.shared();
+        // Only the latest emission to each slot needs uploading.
+        if shared.pending_emissions.len() == self.max_count as usize {
+            shared.pending_emissions.pop_front();
+        }
+        shared.pending_emissions.push_back((slot, particle));
+    }
+
+    /// Advance the simulation of all live particles by `dt` seconds under the given forces.
+    ///
+    /// The simulation step is performed on the GPU the next time the system is drawn.
+    ///
+    /// If a flow field is set, it is sampled at the simulated time before the step.
+    pub fn update(&mut self, dt: f32, forces: &[Force]) {
+        let flow_grid = match self.flow_field {
+            None => None,
+            Some(FlowField::Static(ref grid)) => Some(grid.clone()),
+            Some(FlowField::Dynamic(ref sample)) => Some(Arc::new(sample(self.time as f32))),
+        };
+        self.time += dt as f64;
+        let mut uniforms = Uniforms::new(dt, self.max_count, forces);
+        if let Some(ref grid) = flow_grid {
+            uniforms.set_flow_grid(grid);
+        }
+        let update = Update {
+            uniforms,
+            flow_grid,
+        };
+        let mut shared = self.shared();
+        if shared.pending_updates.len() == MAX_PENDING_UPDATES {
+            shared.pending_updates.pop_front();
+        }
+        shared.pending_updates.push_back(update);
+    }
+
+    /// Carry all live particles along the given flow field in addition to their own velocity.
+    ///
+    /// The field is copied into the system, so later changes to `field` require calling this
+    /// again. Time-varying fields are sampled at the total simulated time of the system.
+    pub fn set_flow_field<N>(&mut self, field: &FlowField2D<N>)
+    where
+        N: 'static + NoiseSource + Clone + Send,
+    {
+        let flow_field = match field.is_static() {
+            true => FlowField::Static(Arc::new(field.sample_grid(0.0))),
+            false => {
+                let field = field.clone();
+                FlowField::Dynamic(Box::new(move |time| field.sample_grid(time)))
+            }
+        };
+        self.flow_field = Some(flow_field);
+    }
+
+    /// Stop carrying particles along the flow field, if any.
+    pub fn clear_flow_field(&mut self) {
+        self.flow_field = None;
+    }
+
+    // A handle to the shared state for use by draw primitives.
+    pub(crate) fn handle(&self) -> Handle {
+        self.shared.clone()
+    }
+
+    fn shared(&self) -> std::sync::MutexGuard<Shared> {
+        self.shared
+            .lock()
+            .expect("failed to acquire particle system lock")
+    }
+}
+
+impl Shared {
+    /// The maximum number of live particles and in turn the number of instances to draw.
+    pub(crate) fn max_count(&self) -> u32 {
+        self.max_count
+    }
+
+    /// Encode any pending emissions and updates, returning the buffer of simulated particles.
+    ///
+    /// The GPU resources are created upon the first call.
+    pub(crate) fn encode(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+    ) -> Arc<wgpu::Buffer> {
+        let max_count = self.max_count;
+        let gpu = self.gpu.get_or_insert_with(|| Gpu::new(device, max_count));
+
+        // Upload emitted particles, copying each run of consecutive slots at once.
+        let stride = std::mem::size_of::<GpuParticle>() as wgpu::BufferAddress;
+        let mut emissions = self.pending_emissions.drain(..).peekable();
+        while let Some((start, particle)) = emissions.next() {
+            let mut run = vec![particle];
+            while let Some(&(slot, particle)) = emissions.peek() {
+                if slot != start + run.len() as u32 {
+                    break;
+                }
+                run.push(particle);
+                emissions.next();
+            }
+            let bytes = unsafe { wgpu::bytes::from_slice(&run) };
+            let usage = wgpu::BufferUsage::COPY_SRC;
+            let staging = device.create_buffer_with_data(bytes, usage);
+            let offset = start as wgpu::BufferAddress * stride;
+            let size = bytes.len() as wgpu::BufferAddress;
+            encoder.copy_buffer_to_buffer(&staging, 0, &gpu.particle_buffer, offset, size);
+        }
+
+        // Dispatch a simulation step for each update.
+        let particles_size = max_count as wgpu::BufferAddress * stride;
+        let work_groups = (max_count + WORK_GROUP_SIZE - 1) / WORK_GROUP_SIZE;
+        for Update {
+            uniforms,
+            flow_grid,
+        } in self.pending_updates.drain(..)
+        {
+            if let Some(ref grid) = flow_grid {
+                gpu.upload_flow_grid(device, grid);
+            }
+            let (flow_buffer, flow_size) = gpu.flow_buffer(flow_grid.is_some());
+            let bytes = unsafe { wgpu::bytes::from(&uniforms) };
+            let usage = wgpu::BufferUsage::UNIFORM;
+            let uniform_buffer = device.create_buffer_with_data(bytes, usage);
+            let uniforms_size = bytes.len() as wgpu::BufferAddress;
+            let bind_group = wgpu::BindGroupBuilder::new()
+                .buffer_bytes(&gpu.particle_buffer, 0..particles_size)
+                .buffer_bytes(&uniform_buffer, 0..uniforms_size)
+                .buffer_bytes(flow_buffer, 0..flow_size)
+                .build(device, &gpu.bind_group_layout);
+            let mut pass = encoder.begin_compute_pass();
+            pass.set_pipeline(&gpu.pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch(work_groups, 1, 1);
+        }
+
+        gpu.particle_buffer.clone()
+    }
+}
+
+impl Gpu {
+    fn new(device: &wgpu::Device, max_count: u32) -> Self {
+        // Zeroed particles have a lifetime of `0.0` and in turn are dead.
+        let particles = vec![GpuParticle::default(); max_count as usize];
+        let bytes = unsafe { wgpu::bytes::from_slice(&particles) };
+        let usage =
+            wgpu::BufferUsage::STORAGE | wgpu::BufferUsage::VERTEX | wgpu::BufferUsage::COPY_DST;
+        let particle_buffer = Arc::new(device.create_buffer_with_data(bytes, usage));
+
+        let bind_group_layout = wgpu::BindGroupLayoutBuilder::new()
+            .storage_buffer(wgpu::ShaderStage::COMPUTE, false, false)
+            .uniform_buffer(wgpu::ShaderStage::COMPUTE, false)
+            .storage_buffer(wgpu::ShaderStage::COMPUTE, false, true)
+            .build(device);
+        let pipeline_layout = wgpu::create_pipeline_layout(device, &[&bind_group_layout]);
+        let module = wgpu::shader_from_spirv_bytes(device, include_bytes!("shaders/comp.spv"));
+        let compute_stage = wgpu::ProgrammableStageDescriptor {
+            module: &module,
+            entry_point: "main",
+        };
+        let descriptor = wgpu::ComputePipelineDescriptor {
+            layout: &pipeline_layout,
+            compute_stage,
+        };
+        let pipeline = device.create_compute_pipeline(&descriptor);
+
+        let empty_flow = [[0.0f32; 2]; 4];
+        let bytes = unsafe { wgpu::bytes::from_slice(&empty_flow) };
+        let empty_flow_buffer = device.create_buffer_with_data(bytes, wgpu::BufferUsage::STORAGE);
+
+        Gpu {
+            particle_buffer,
+            empty_flow_buffer,
+            flow_buffer: None,
+            bind_group_layout,
+            pipeline,
+        }
+    }
+}
+
+impl Gpu {
+    // Upload the given flow field grid if it differs from the last.
+    fn upload_flow_grid(&mut self, device: &wgpu::Device, grid: &Arc<FlowGrid>) {
+        if let Some((ref last, _)) = self.flow_buffer {
+            if Arc::ptr_eq(last, grid) {
+                return;
+            }
+        }
+        let bytes = unsafe { wgpu::bytes::from_slice(&grid.vectors) };
+        let buffer = device.create_buffer_with_data(bytes, wgpu::BufferUsage::STORAGE);
+        self.flow_buffer = Some((grid.clone(), buffer));
+    }
+
+    // The buffer to bind for the flow field along with its size.
+    fn flow_buffer(&self, has_flow_field: bool) -> (&wgpu::Buffer, wgpu::BufferAddress) {
+        match self.flow_buffer {
+            Some((ref grid, ref buffer)) if has_flow_field => {
+                let size = std::mem::size_of_val(&grid.vectors[..]);
+                (buffer, size as wgpu::BufferAddress)
+            }
+            _ => {
+                let size = std::mem::size_of::<[[f32; 2]; 4]>();
+                (&self.empty_flow_buffer, size as wgpu::BufferAddress)
+            }
+        }
+    }
+}
+
+impl Uniforms {
+    // Gravity and drag are summed, while attractors are applied individually by the shader.
+    fn new(dt: f32, particle_count: u32, forces: &[Force]) -> Self {
+        let mut uniforms = Uniforms {
+            gravity: [0.0; 2],
+            drag: 0.0,
+            dt,
+            particle_count,
+            attractor_count: 0,
+            // A 2x2 grid of zeroed vectors is bound when the system has no flow field.
+            field_cols: 2,
+            field_rows: 2,
+            field_min: [0.0; 2],
+            field_size: [1.0; 2],
+            attractors: [[0.0; 4]; MAX_ATTRACTORS],
+        };
+        for force in forces {
+            match *force {
+                Force::Gravity(g) => {
+                    uniforms.gravity[0] += g.x;
+                    uniforms.gravity[1] += g.y;
+                }
+                Force::Drag(drag) => uniforms.drag += drag,
+                Force::Attractor { pos, strength } => {
+                    let index = uniforms.attractor_count as usize;
+                    if index < MAX_ATTRACTORS {
+                        uniforms.attractors[index] = [pos.x, pos.y, strength, 0.0];
+                        uniforms.attractor_count += 1;
+                    }
+                }
+            }
+        }
+        uniforms
+    }
+
+    fn set_flow_grid(&mut self, grid: &FlowGrid) {
+        let (w, h) = grid.bounds.w_h();
+        self.field_cols = grid.cols;
+        self.field_rows = grid.rows;
+        self.field_min = [grid.bounds.left(), grid.bounds.bottom()];
+        self.field_size = [w, h];
+    }
+}
+
+impl fmt::Debug for FlowField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FlowField::Static(ref grid) => f.debug_tuple("Static").field(grid).finish(),
+            FlowField::Dynamic(_) => f.debug_tuple("Dynamic").finish(),
+        }
+    }
+}
@@ -3,19 +3,21 @@
 pub use crate::app::{self, App, LoopMode};
 pub use crate::color::named::*;
 pub use crate::color::{
-    gray, hsl, hsla, hsv, hsva, lin_srgb, lin_srgba, rgb, rgb8, rgba, rgba8, srgb, srgb8, srgba,
-    srgba8,
+    gray, hsl, hsla, hsv, hsva, lin_srgb, lin_srgba, oklab, oklch, rgb, rgb8, rgba, rgba8, srgb,
+    srgb8, srgba, srgba8,
 };
 pub use crate::color::{
-    Gray, Hsl, Hsla, Hsv, Hsva, LinSrgb, LinSrgba, Rgb, Rgb8, Rgba, Rgba8, Srgb, Srgba,
+    Gray, Hsl, Hsla, Hsv, Hsva, LinSrgb, LinSrgba, Oklab, Oklch, Rgb, Rgb8, Rgba, Rgba8, Srgb,
+    Srgba,
 };
 pub use crate::draw::Draw;
 pub use crate::event::WindowEvent::*;
 pub use crate::event::{
-    AxisMotion, Event, Key, MouseButton, MouseScrollDelta, TouchEvent, TouchPhase,
-    TouchpadPressure, Update, WindowEvent,
+    AxisMotion, Event, FileDrop, Gesture, Key, ModifiersState, MouseButton, MouseScrollDelta,
+    TouchEvent, TouchPhase, TouchpadPressure, Update, WindowEvent,
 };
 pub use crate::frame::{Frame, RawFrame};
+pub use crate::geom::math::{fract, mix, remap, remap_clamp, smootherstep, smoothstep, step};
 pub use crate::geom::{
     self, pt2, pt3, vec2, vec3, vec4, Cuboid, Point2, Point3, Rect, Vector2, Vector3, Vector4,
 };
@@ -26,9 +28,13 @@ pub use crate::math::{
     clamp, deg_to_rad, fmod, map_range, partial_max, partial_min, rad_to_deg, rad_to_turns,
     turns_to_rad,
 };
-pub use crate::rand::{random, random_ascii, random_f32, random_f64, random_range};
+pub use crate::particle::{self, ParticleSystem};
+pub use crate::rand::{
+    random, random_ascii, random_color, random_f32, random_f64, random_in_disk, random_on_circle,
+    random_point_in, random_range,
+};
 pub use crate::text::{self, text};
-pub use crate::time::DurationF64;
+pub use crate::time::{DurationF64, Sequence, Timer};
 pub use crate::ui;
 pub use crate::wgpu;
 pub use crate::wgpu::blend::{
@@ -48,3 +54,46 @@ pub use std::f64::consts::PI as PI_F64;
 pub const TAU: f32 = PI * 2.0;
 /// Two times PI.
 pub const TAU_F64: f64 = PI_F64 * 2.0;
+
+/// A slim variant of the prelude, exporting only items unique to nannou.
+///
+/// Unlike the full prelude, this exports no vector, point or matrix types, no color types or
+/// named colors and no constants, avoiding collisions with the standard library and other crates.
+/// This is recommended for libraries that embed nannou. See the `math` module for vector types
+/// with unambiguous names.
+pub mod slim {
+    pub use crate::app::{self, App, LoopMode};
+    pub use crate::draw::Draw;
+    pub use crate::event::{Event, Key, MouseButton, Update, WindowEvent};
+    pub use crate::frame::{Frame, RawFrame};
+    pub use crate::window::{self, Id as WindowId, Window};
+    pub use crate::{color, geom, wgpu};
+}
+
+/// The math types of the prelude, named with a `Nan` prefix to avoid collisions.
+pub mod math {
+    use crate::geom::scalar;
+
+    pub use crate::geom::{pt2, pt3, pt4, vec2, vec3, vec4};
+
+    /// A 2D vector, i.e. `geom::Vector2`.
+    pub type NanVec2<S = scalar::Default> = crate::geom::Vector2<S>;
+    /// A 3D vector, i.e. `geom::Vector3`.
+    pub type NanVec3<S = scalar::Default> = crate::geom::Vector3<S>;
+    /// A 4D vector, i.e. `geom::Vector4`.
+    pub type NanVec4<S = scalar::Default> = crate::geom::Vector4<S>;
+    /// A 2D point, i.e. `geom::Point2`.
+    pub type NanPoint2<S = scalar::Default> = crate::geom::Point2<S>;
+    /// A 3D point, i.e. `geom::Point3`.
+    pub type NanPoint3<S = scalar::Default> = crate::geom::Point3<S>;
+    /// A 4D point, i.e. `geom::Point4`.
+    pub type NanPoint4<S = scalar::Default> = crate::geom::Point4<S>;
+    /// A 2x2 matrix, i.e. `math::Matrix2`.
+    pub type NanMat2<S = scalar::Default> = crate::math::Matrix2<S>;
+    /// A 3x3 matrix, i.e. `math::Matrix3`.
+    pub type NanMat3<S = scalar::Default> = crate::math::Matrix3<S>;
+    /// A 4x4 matrix, i.e. `math::Matrix4`.
+    pub type NanMat4<S = scalar::Default> = crate::math::Matrix4<S>;
+    /// A quaternion, i.e. `math::Quaternion`.
+    pub type NanQuat<S = scalar::Default> = crate::math::Quaternion<S>;
+}
@@ -4,11 +4,97 @@
 //! Helper functions include [**random_f32()**](./fn.random_f32.html),
 //! [**random_f64()**](./fn.random_f64.html) and [**random_range(min,
 //! max)**](./fn.random_range.html).
+//!
+//! All helper functions draw from a seedable, thread-local random number generator that is also
+//! accessible via [**rng()**](./fn.rng.html) and `App::rng`. A random seed is chosen for each
+//! thread upon first use. Calling [**seed_rng(seed)**](./fn.seed_rng.html) resets the generator so
+//! that a sketch may be reproduced, while [**rng_seed()**](./fn.rng_seed.html) returns the seed in
+//! use so that a "good" run may be noted and revisited.
 
 pub use rand;
 
 pub use self::rand::*;
 
+use self::rand::distributions::Standard;
+use self::rand::rngs::SmallRng;
+use self::rand::seq::SliceRandom;
+use crate::color::LinSrgba;
+use crate::geom::{pt2, vec2, Point2, Rect, Vector2};
+use std::cell::RefCell;
+
+/// A handle to the seedable, thread-local random number generator used by nannou's random helper
+/// functions.
+///
+/// The handle implements `RngCore`, and in turn the `Rng` trait, so that it may be used anywhere
+/// that a random number generator is expected.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct AppRng {
+    _private: (),
+}
+
+// The thread-local state shared by all `AppRng` handles.
+struct State {
+    seed: u64,
+    rng: SmallRng,
+}
+
+thread_local! {
+    static STATE: RefCell<State> = RefCell::new(State::new(rand::random()));
+}
+
+impl State {
+    fn new(seed: u64) -> Self {
+        let rng = SmallRng::seed_from_u64(seed);
+        State { seed, rng }
+    }
+}
+
+impl RngCore for AppRng {
+    fn next_u32(&mut self) -> u32 {
+        STATE.with(|s| s.borrow_mut().rng.next_u32())
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        STATE.with(|s| s.borrow_mut().rng.next_u64())
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        STATE.with(|s| s.borrow_mut().rng.fill_bytes(dest))
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        STATE.with(|s| s.borrow_mut().rng.try_fill_bytes(dest))
+    }
+}
+
+/// A handle to the seedable, thread-local random number generator.
+pub fn rng() -> AppRng {
+    AppRng::default()
+}
+
+/// Reset the thread-local random number generator with the given seed.
+///
+/// All following calls to the random helper functions on this thread will produce the same
+/// sequence of values for the same seed.
+pub fn seed_rng(seed: u64) {
+    STATE.with(|s| *s.borrow_mut() = State::new(seed));
+}
+
+/// The seed with which the thread-local random number generator was last reset.
+pub fn rng_seed() -> u64 {
+    STATE.with(|s| s.borrow().seed)
+}
+
+/// Generate a random value using the seedable, thread-local random number generator.
+///
+/// This shadows `rand::random` so that seeding via `seed_rng` applies.
+pub fn random<T>() -> T
+where
+    Standard: distributions::Distribution<T>,
+{
+    rng().gen()
+}
+
 /// A wrapper function around the `random` function that avoids the need for specifying a type in
 /// the case that it cannot be inferred. The primary purpose for this is to simplify the random API
 /// for new rust users.
@@ -31,14 +117,13 @@ pub fn random_f64() -> f64 {
 /// If the given `min` is greater than the given `max`, they will be swapped before calling
 /// `gen_range` internally to avoid triggering a `panic!`.
 ///
-/// This calls `rand::thread_rng().gen_range(min, max)` internally, in turn using the thread-local
-/// default random number generator.
+/// This uses the seedable, thread-local random number generator.
 pub fn random_range<T>(min: T, max: T) -> T
 where
     T: PartialOrd + distributions::uniform::SampleUniform,
 {
     let (min, max) = if min <= max { (min, max) } else { (max, min) };
-    rand::thread_rng().gen_range(min, max)
+    rng().gen_range(min, max)
 }
 
 /// Generates and returns a random ascii character.
@@ -53,6 +138,40 @@ pub fn random_ascii() -> char {
                             abcdefghijklmnopqrstuvwxyz\
                             0123456789)(*&^%$#@!~. ";
 
-    let idx = rand::thread_rng().gen_range(0, ASCIISET.len());
+    let idx = rng().gen_range(0, ASCIISET.len());
     ASCIISET[idx] as char
 }
+
+/// A uniformly distributed random point within the given rectangle.
+pub fn random_point_in(rect: Rect) -> Point2 {
+    let x = rect.x.start + random_f32() * (rect.x.end - rect.x.start);
+    let y = rect.y.start + random_f32() * (rect.y.end - rect.y.start);
+    pt2(x, y)
+}
+
+/// A random point on the circumference of a circle with the given radius, centered on the origin.
+pub fn random_on_circle(radius: f32) -> Vector2 {
+    let angle = random_f32() * std::f32::consts::PI * 2.0;
+    vec2(angle.cos(), angle.sin()) * radius
+}
+
+/// A uniformly distributed random point within a disk of the given radius, centered on the origin.
+pub fn random_in_disk(radius: f32) -> Vector2 {
+    // The square root compensates for the larger area of the outer rings.
+    random_on_circle(radius * random_f32().sqrt())
+}
+
+/// A fully opaque color with random red, green and blue components.
+pub fn random_color() -> LinSrgba {
+    LinSrgba::new(random_f32(), random_f32(), random_f32(), 1.0)
+}
+
+/// Shuffle the given slice in place using the given random number generator.
+///
+/// E.g. `rand::shuffle(&mut app.rng(), &mut points)`.
+pub fn shuffle<R, T>(rng: &mut R, slice: &mut [T])
+where
+    R: Rng + ?Sized,
+{
+    slice.shuffle(rng);
+}
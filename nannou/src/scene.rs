@@ -0,0 +1,155 @@
+//! A hierarchy of transforms for grouping and parenting drawings.
+//!
+//! Each node of a **Scene** has a transform relative to its parent. The world-space transform of a
+//! node is the product of the transforms along the chain from the root to the node, which may be
+//! applied to a **Draw** via `Scene::draw_node`. This is useful for skeletal animation, robot arm
+//! visualisations and any other drawing composed of nested parts.
+//!
+//! World-space transforms are cached, so only the chains of nodes whose transforms have changed
+//! since they were last evaluated are multiplied again.
+
+use crate::draw::Draw;
+use crate::geom;
+use crate::math::{BaseFloat, Matrix4, SquareMatrix};
+use std::cell::{Cell, RefCell};
+
+/// A hierarchy of nodes, each with a transform relative to its parent.
+#[derive(Clone, Debug)]
+pub struct Scene<S = geom::scalar::Default> {
+    nodes: Vec<Node<S>>,
+}
+
+/// A unique identifier for a node within a **Scene**.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(usize);
+
+/// A single node within a **Scene**.
+#[derive(Clone, Debug)]
+pub struct Node<S = geom::scalar::Default> {
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+    transform: Matrix4<S>,
+    // The cached world-space transform, valid while `dirty` is `false`.
+    world_transform: RefCell<Matrix4<S>>,
+    dirty: Cell<bool>,
+}
+
+impl<S> Scene<S>
+where
+    S: BaseFloat,
+{
+    /// Create an empty scene.
+    pub fn new() -> Self {
+        Scene { nodes: vec![] }
+    }
+
+    /// Add a node with an identity transform as a child of the given parent, or as a root node if
+    /// `parent` is `None`.
+    ///
+    /// **Panics** if the parent does not belong to this scene.
+    pub fn add_node(&mut self, parent: Option<NodeId>) -> NodeId {
+        let id = NodeId(self.nodes.len());
+        if let Some(parent) = parent {
+            self.nodes[parent.0].children.push(id);
+        }
+        let node = Node {
+            parent,
+            children: vec![],
+            transform: Matrix4::identity(),
+            world_transform: RefCell::new(Matrix4::identity()),
+            dirty: Cell::new(true),
+        };
+        self.nodes.push(node);
+        id
+    }
+
+    /// The node with the given ID.
+    pub fn node(&self, id: NodeId) -> &Node<S> {
+        &self.nodes[id.0]
+    }
+
+    /// The number of nodes within the scene.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Whether or not the scene contains any nodes.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Set the transform of the node relative to its parent.
+    ///
+    /// The world-space transforms of the node and all of its descendants are re-evaluated the next
+    /// time they are requested.
+    pub fn set_transform(&mut self, id: NodeId, transform: Matrix4<S>) {
+        self.nodes[id.0].transform = transform;
+        self.mark_dirty(id);
+    }
+
+    /// The transform of the node in world space.
+    ///
+    /// This is the product of the transforms of the node and all of its ancestors.
+    pub fn world_transform(&self, id: NodeId) -> Matrix4<S> {
+        let node = &self.nodes[id.0];
+        if node.dirty.get() {
+            let world = match node.parent {
+                None => node.transform,
+                Some(parent) => self.world_transform(parent) * node.transform,
+            };
+            *node.world_transform.borrow_mut() = world;
+            node.dirty.set(false);
+        }
+        *node.world_transform.borrow()
+    }
+
+    /// Produce a **Draw** transformed by the world-space transform of the node.
+    ///
+    /// The transform is applied to the existing transform of the given **Draw**, so drawings made
+    /// with the result are positioned relative to the node.
+    pub fn draw_node(&self, id: NodeId, draw: &Draw<S>) -> Draw<S> {
+        draw.transform(self.world_transform(id))
+    }
+
+    // Invalidate the cached world-space transforms of the node and its descendants.
+    fn mark_dirty(&self, id: NodeId) {
+        let mut stack = vec![id];
+        while let Some(id) = stack.pop() {
+            let node = &self.nodes[id.0];
+            // The descendants of a node that is already dirty are also dirty.
+            if node.dirty.replace(true) {
+                continue;
+            }
+            stack.extend(node.children.iter().cloned());
+        }
+    }
+}
+
+impl<S> Node<S>
+where
+    S: Copy,
+{
+    /// The parent of the node, or `None` if the node is a root.
+    pub fn parent(&self) -> Option<NodeId> {
+        self.parent
+    }
+
+    /// The children of the node in the order in which they were added.
+    pub fn children(&self) -> &[NodeId] {
+        &self.children
+    }
+
+    /// The transform of the node relative to its parent.
+    pub fn transform(&self) -> Matrix4<S> {
+        self.transform
+    }
+}
+
+impl<S> Default for Scene<S>
+where
+    S: BaseFloat,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,263 @@
+//! Serial port I/O for communicating with microcontrollers such as Arduino, provided by the
+//! [serialport](https://docs.rs/serialport) crate.
+//!
+//! Requires the `serial` feature. Each **Port** reads from the device on a background thread,
+//! splitting the incoming data into lines as produced by Arduino's `Serial.println`:
+//!
+//! ```ignore
+//! fn model(app: &App) -> Model {
+//!     let port = serial::Port::open("/dev/ttyACM0", 9600).unwrap();
+//!     port.on_data(app, |model: &mut Model, line| {
+//!         if let Some(values) = serial::parse_csv_floats(line) {
+//!             model.sensors = values;
+//!         }
+//!     });
+//!     Model { port, sensors: vec![] }
+//! }
+//! ```
+
+use crate::app::Proxy;
+use crate::App;
+use std::io::{self, Read, Write};
+use std::sync::atomic::{self, AtomicBool};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex, Weak};
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub use serialport;
+pub use serialport::{Error, SerialPortInfo as PortInfo};
+
+/// The function signature of a handler for lines received by a **Port**.
+pub type DataFn<Model> = fn(&mut Model, &str);
+
+/// An open serial port.
+///
+/// Cloning a **Port** produces a handle to the same port. The port's reader thread exits once
+/// every handle is dropped or the device is disconnected.
+#[derive(Clone)]
+pub struct Port {
+    name: String,
+    writer: Arc<Mutex<Box<dyn serialport::SerialPort>>>,
+    lines: Arc<Mutex<mpsc::Receiver<String>>>,
+    // Set once the port is registered with the app via `on_data`.
+    proxy: Arc<Mutex<Option<Proxy>>>,
+    // Signals the reader thread to exit once the last handle is dropped.
+    shutdown: Arc<Shutdown>,
+}
+
+// A handle to a port registered with the app via `on_data` that does not keep the port open.
+#[derive(Clone)]
+pub(crate) struct WeakPort {
+    lines: Arc<Mutex<mpsc::Receiver<String>>>,
+    shutdown: Weak<Shutdown>,
+}
+
+// Sets the inner flag upon drop.
+struct Shutdown(Arc<AtomicBool>);
+
+// The duration that the reader thread blocks upon each read.
+const READ_TIMEOUT: Duration = Duration::from_millis(10);
+
+// The duration of silence after which the reader thread checks whether the device is connected.
+const DISCONNECT_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+impl Port {
+    /// All serial ports available on the system.
+    ///
+    /// Returns an empty list if the ports could not be enumerated.
+    pub fn available_ports() -> Vec<PortInfo> {
+        serialport::available_ports().unwrap_or_default()
+    }
+
+    /// Open the port with the given name at the given baud rate, e.g. `"/dev/ttyACM0"` on Linux,
+    /// `"/dev/cu.usbmodem1101"` on macOS or `"COM3"` on Windows.
+    pub fn open(name: &str, baud_rate: u32) -> Result<Self, Error> {
+        let port = serialport::new(name, baud_rate)
+            .timeout(READ_TIMEOUT)
+            .open()?;
+        let reader = port.try_clone()?;
+        let (tx, rx) = mpsc::channel();
+        let proxy = Arc::new(Mutex::new(None));
+        let thread_proxy = proxy.clone();
+        let closed = Arc::new(AtomicBool::new(false));
+        let thread_closed = closed.clone();
+        thread::Builder::new()
+            .name(format!("nannou_serial_{}", name))
+            .spawn(move || read_lines(reader, tx, thread_proxy, thread_closed))?;
+        Ok(Port {
+            name: name.to_string(),
+            writer: Arc::new(Mutex::new(port)),
+            lines: Arc::new(Mutex::new(rx)),
+            proxy,
+            shutdown: Arc::new(Shutdown(closed)),
+        })
+    }
+
+    /// The name with which the port was opened.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The next line received from the device with its line ending removed, or `None` if no
+    /// complete line is available.
+    ///
+    /// This never blocks.
+    pub fn read_line(&self) -> Option<String> {
+        self.lines.lock().ok()?.try_recv().ok()
+    }
+
+    /// Parse the next received line as comma-separated floats via `parse_csv_floats`.
+    ///
+    /// Returns `None` if no line is available or if the line could not be parsed.
+    pub fn parse_csv_floats(&self) -> Option<Vec<f32>> {
+        self.read_line().and_then(|line| parse_csv_floats(&line))
+    }
+
+    /// Write the given bytes to the device.
+    pub fn write(&self, bytes: &[u8]) -> io::Result<()> {
+        let mut writer = self
+            .writer
+            .lock()
+            .map_err(|_| io::Error::new(io::ErrorKind::Other, "serial port writer poisoned"))?;
+        writer.write_all(bytes)?;
+        writer.flush()
+    }
+
+    /// Call `handler` with the model for each line received from the device.
+    ///
+    /// Lines are delivered before each update, after which they are no longer available via
+    /// `read_line`. The `Model` type must match the app's model type, otherwise the app will
+    /// **panic!** upon delivering a line.
+    ///
+    /// The app does not keep the port open. Delivery stops once every handle to the port is
+    /// dropped, e.g. when the model holding it is dropped.
+    pub fn on_data<M>(&self, app: &App, handler: DataFn<M>)
+    where
+        M: 'static,
+    {
+        if let Ok(mut proxy) = self.proxy.lock() {
+            *proxy = Some(app.create_proxy());
+        }
+        let port = WeakPort {
+            lines: self.lines.clone(),
+            shutdown: Arc::downgrade(&self.shutdown),
+        };
+        let mut ports = app.serial_ports.borrow_mut();
+        ports.retain(|(port, _)| port.is_open());
+        ports.push((port, Arc::new(handler)));
+    }
+}
+
+impl WeakPort {
+    // Whether or not a `Port` handle to the port remains.
+    fn is_open(&self) -> bool {
+        self.shutdown.upgrade().is_some()
+    }
+
+    // The same as `Port::read_line`.
+    fn read_line(&self) -> Option<String> {
+        self.lines.lock().ok()?.try_recv().ok()
+    }
+}
+
+/// Parse a line of comma-separated values, e.g. `"0.5, 12, -3.25"`, as produced by a series of
+/// Arduino `Serial.print` calls.
+///
+/// Whitespace around values is ignored. Returns `None` if any value is not a number.
+pub fn parse_csv_floats(line: &str) -> Option<Vec<f32>> {
+    let line = line.trim();
+    if line.is_empty() {
+        return Some(vec![]);
+    }
+    line.split(',').map(|s| s.trim().parse().ok()).collect()
+}
+
+// Deliver the lines received by each port registered via `on_data` to the model.
+pub(crate) fn deliver<M>(ports: &[(WeakPort, Arc<dyn std::any::Any>)], model: &mut M)
+where
+    M: 'static,
+{
+    for (port, handler) in ports {
+        if !port.is_open() {
+            continue;
+        }
+        let handler = handler
+            .downcast_ref::<DataFn<M>>()
+            .expect("unexpected model argument given to serial port handler");
+        while let Some(line) = port.read_line() {
+            handler(model, &line);
+        }
+    }
+}
+
+// Read from the device, sending each complete line until the port is closed, the device is
+// disconnected or every `Port` handle is dropped.
+fn read_lines(
+    mut reader: Box<dyn serialport::SerialPort>,
+    tx: mpsc::Sender<String>,
+    proxy: Arc<Mutex<Option<Proxy>>>,
+    closed: Arc<AtomicBool>,
+) {
+    let mut buffer = [0u8; 1024];
+    let mut line = vec![];
+    let mut last_checked = Instant::now();
+    while !closed.load(atomic::Ordering::Acquire) {
+        let len = match reader.read(&mut buffer) {
+            Ok(0) => return,
+            Ok(len) => len,
+            Err(ref err) if err.kind() == io::ErrorKind::TimedOut => {
+                // A silent device may have been disconnected without producing an error.
+                if last_checked.elapsed() >= DISCONNECT_CHECK_INTERVAL {
+                    if let Err(err) = reader.bytes_to_read() {
+                        eprintln!("serial port disconnected: {}", err);
+                        return;
+                    }
+                    last_checked = Instant::now();
+                }
+                continue;
+            }
+            Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => {
+                eprintln!("failed to read from serial port: {}", err);
+                return;
+            }
+        };
+        let mut received = false;
+        for &byte in &buffer[..len] {
+            if byte != b'\n' {
+                line.push(byte);
+                continue;
+            }
+            if line.last() == Some(&b'\r') {
+                line.pop();
+            }
+            let s = String::from_utf8_lossy(&line).into_owned();
+            line.clear();
+            if tx.send(s).is_err() {
+                return;
+            }
+            received = true;
+        }
+        // Wake the app in case it is waiting for events.
+        if received {
+            if let Ok(proxy) = proxy.lock() {
+                if let Some(ref proxy) = *proxy {
+                    proxy.wakeup().ok();
+                }
+            }
+        }
+    }
+}
+
+impl Drop for Shutdown {
+    fn drop(&mut self) {
+        self.0.store(true, atomic::Ordering::Release);
+    }
+}
+
+impl std::fmt::Debug for Port {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Port").field("name", &self.name).finish()
+    }
+}
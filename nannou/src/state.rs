@@ -306,4 +306,85 @@ pub mod time {
             1000.0 / millis
         }
     }
+
+    /// The timing of recent frames, recorded at the start of each call to the focused window's
+    /// **view** function.
+    #[derive(Clone, Debug)]
+    pub struct FrameTimes {
+        last_frame: Option<std::time::Instant>,
+        // The most recent frame durations, oldest first.
+        history: Vec<std::time::Duration>,
+        fps_smoothed: f64,
+    }
+
+    impl FrameTimes {
+        /// The maximum number of frame durations retained for `history`.
+        pub const HISTORY_LEN: usize = 240;
+
+        /// The number of frames over which `fps_smoothed` is averaged.
+        pub const SMOOTHING_FRAMES: f64 = 60.0;
+
+        /// Record the start of a new frame at the given instant.
+        pub fn record(&mut self, now: std::time::Instant) {
+            if let Some(last) = self.last_frame {
+                let delta = now.duration_since(last);
+                if self.history.len() == Self::HISTORY_LEN {
+                    self.history.remove(0);
+                }
+                self.history.push(delta);
+                let fps = self.fps();
+                self.fps_smoothed = match self.history.len() {
+                    1 => fps,
+                    _ => {
+                        let alpha = 2.0 / (Self::SMOOTHING_FRAMES + 1.0);
+                        self.fps_smoothed + (fps - self.fps_smoothed) * alpha
+                    }
+                };
+            }
+            self.last_frame = Some(now);
+        }
+
+        /// The duration between the two most recent frames.
+        ///
+        /// Returns a zero duration until at least two frames have been recorded.
+        pub fn delta(&self) -> std::time::Duration {
+            self.history.last().cloned().unwrap_or_default()
+        }
+
+        /// The instantaneous frame rate, derived from the duration between the two most recent
+        /// frames.
+        ///
+        /// Returns `0.0` until at least two frames have been recorded.
+        pub fn fps(&self) -> f64 {
+            let secs = self.delta().as_secs_f64();
+            match secs > 0.0 {
+                true => 1.0 / secs,
+                false => 0.0,
+            }
+        }
+
+        /// The exponential moving average of the frame rate over roughly the last
+        /// `SMOOTHING_FRAMES` frames.
+        pub fn fps_smoothed(&self) -> f64 {
+            self.fps_smoothed
+        }
+
+        /// The most recent `n` frame durations, oldest first.
+        ///
+        /// At most `HISTORY_LEN` durations are retained.
+        pub fn history(&self, n: usize) -> &[std::time::Duration] {
+            let start = self.history.len().saturating_sub(n);
+            &self.history[start..]
+        }
+    }
+
+    impl Default for FrameTimes {
+        fn default() -> Self {
+            FrameTimes {
+                last_frame: None,
+                history: Vec::with_capacity(Self::HISTORY_LEN),
+                fps_smoothed: 0.0,
+            }
+        }
+    }
 }
@@ -0,0 +1,217 @@
+//! Helpers for testing visual output against stored "golden" reference images.
+//!
+//! Render a frame, e.g. via `App::run_headless` or `Frame::capture_to_image`, and pass the image
+//! to `assert_frame_matches_golden`. Golden images are stored within the `tests/golden` directory
+//! of the crate under test, or within the directory specified by the `NANNOU_GOLDEN_DIR`
+//! environment variable.
+//!
+//! To create or update golden images, run the tests with `NANNOU_UPDATE_GOLDEN=1`, or use
+//! `cargo nannou golden update <test-name>` provided by the `nannou_cli` crate.
+
+use image::{DynamicImage, GenericImageView, Rgba, RgbaImage};
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// When set to `1`, `assert_frame_matches_golden` overwrites golden images rather than comparing
+/// against them.
+pub const UPDATE_GOLDEN_ENV_VAR: &str = "NANNOU_UPDATE_GOLDEN";
+
+/// Overrides the directory in which golden images are stored.
+pub const GOLDEN_DIR_ENV_VAR: &str = "NANNOU_GOLDEN_DIR";
+
+/// The name of the directory within the golden directory to which the actual image and a diff
+/// image are written when a comparison fails.
+pub const DIFF_DIR_NAME: &str = "diff";
+
+// The size of the square window over which SSIM is measured and the step between windows.
+const SSIM_WINDOW: u32 = 8;
+const SSIM_STEP: u32 = 4;
+
+// Constants stabilising the SSIM division for the dynamic range of 8-bit channels.
+const SSIM_C1: f64 = (0.01 * 255.0) * (0.01 * 255.0);
+const SSIM_C2: f64 = (0.03 * 255.0) * (0.03 * 255.0);
+
+/// Assert that the given frame matches the stored golden image with the given name.
+///
+/// Images are compared via the structural similarity index (SSIM), where `1.0` indicates that the
+/// images are identical. The `threshold` is the maximum tolerated dissimilarity, i.e. the
+/// assertion passes if `1.0 - ssim <= threshold`. A `threshold` of `0.0` requires the RGB colors to
+/// match exactly (alpha is ignored, see [`ssim`](./fn.ssim.html)), while `0.01` tolerates small differences such as those produced by different GPU
+/// drivers.
+///
+/// If `NANNOU_UPDATE_GOLDEN=1` is set, the golden image is overwritten with the given frame
+/// instead.
+///
+/// **Panics** if the golden image is missing, its size differs from the frame, or the images do
+/// not match. On mismatch, the frame and a diff image are written to the `diff` directory within
+/// the golden directory so that they may be inspected, e.g. as CI artifacts.
+pub fn assert_frame_matches_golden(frame: &DynamicImage, name: &str, threshold: f32) {
+    let path = golden_path(name);
+    if update_golden() {
+        save(frame, &path);
+        return;
+    }
+
+    let expected = image::open(&path).unwrap_or_else(|e| {
+        panic!(
+            "failed to open golden image \"{}\": {}. Run with `{}=1` to create it",
+            path.display(),
+            e,
+            UPDATE_GOLDEN_ENV_VAR,
+        )
+    });
+    if expected.dimensions() != frame.dimensions() {
+        panic!(
+            "frame size {:?} does not match golden image \"{}\" size {:?}",
+            frame.dimensions(),
+            name,
+            expected.dimensions(),
+        );
+    }
+
+    let similarity = ssim(frame, &expected);
+    if 1.0 - similarity <= threshold {
+        return;
+    }
+
+    let diff_dir = golden_dir().join(DIFF_DIR_NAME);
+    let actual_path = diff_dir.join(format!("{}.actual.png", name));
+    let diff_path = diff_dir.join(format!("{}.diff.png", name));
+    save(frame, &actual_path);
+    save(
+        &DynamicImage::ImageRgba8(diff(frame, &expected)),
+        &diff_path,
+    );
+    panic!(
+        "frame does not match golden image \"{}\": SSIM {} is below {}. See \"{}\"",
+        name,
+        similarity,
+        1.0 - threshold,
+        diff_path.display(),
+    );
+}
+
+/// The directory in which golden images are stored.
+///
+/// This is the `NANNOU_GOLDEN_DIR` environment variable if set, otherwise the `tests/golden`
+/// directory of the crate under test.
+pub fn golden_dir() -> PathBuf {
+    if let Some(dir) = env::var_os(GOLDEN_DIR_ENV_VAR) {
+        return dir.into();
+    }
+    let manifest_dir = env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default();
+    Path::new(&manifest_dir).join("tests").join("golden")
+}
+
+/// The path to the golden image with the given name.
+pub fn golden_path(name: &str) -> PathBuf {
+    golden_dir().join(format!("{}.png", name))
+}
+
+/// Whether or not golden images should be updated, i.e. `NANNOU_UPDATE_GOLDEN=1` is set.
+pub fn update_golden() -> bool {
+    env::var(UPDATE_GOLDEN_ENV_VAR)
+        .map(|v| v == "1")
+        .unwrap_or(false)
+}
+
+/// The mean structural similarity index of the two images in the range `-1.0..=1.0`, where `1.0`
+/// indicates that the images are identical.
+///
+/// SSIM is measured for each RGB channel over overlapping 8x8 windows covering the whole image,
+/// including its right and bottom edges, and averaged. Alpha is
+/// ignored, as otherwise the uniform alpha of opaque frames would inflate the similarity.
+///
+/// **Panics** if the images differ in size.
+pub fn ssim(a: &DynamicImage, b: &DynamicImage) -> f32 {
+    assert_eq!(
+        a.dimensions(),
+        b.dimensions(),
+        "cannot compare images of different sizes"
+    );
+    let (a, b) = (a.to_rgba(), b.to_rgba());
+    let (w, h) = a.dimensions();
+    if w == 0 || h == 0 {
+        return 1.0;
+    }
+    let (win_w, win_h) = (SSIM_WINDOW.min(w), SSIM_WINDOW.min(h));
+    let mut total = 0.0;
+    let mut count = 0;
+    for &y in &window_offsets(h, win_h) {
+        for &x in &window_offsets(w, win_w) {
+            for channel in 0..3 {
+                total += window_ssim(&a, &b, [x, y], [win_w, win_h], channel);
+                count += 1;
+            }
+        }
+    }
+    (total / count as f64) as f32
+}
+
+// The offsets of the windows along an axis, ending with a window flush with the far edge.
+fn window_offsets(len: u32, win: u32) -> Vec<u32> {
+    let last = len - win;
+    let mut offsets: Vec<u32> = (0..=last).step_by(SSIM_STEP as usize).collect();
+    if offsets.last() != Some(&last) {
+        offsets.push(last);
+    }
+    offsets
+}
+
+/// An image describing the absolute difference between each pixel of the two images.
+///
+/// Identical pixels are black, while differences appear as the difference of each color channel.
+/// The alpha of each pixel is opaque so that differences in alpha alone remain visible as white.
+///
+/// **Panics** if the images differ in size.
+pub fn diff(a: &DynamicImage, b: &DynamicImage) -> RgbaImage {
+    assert_eq!(
+        a.dimensions(),
+        b.dimensions(),
+        "cannot diff images of different sizes"
+    );
+    let (a, b) = (a.to_rgba(), b.to_rgba());
+    let (w, h) = a.dimensions();
+    RgbaImage::from_fn(w, h, |x, y| {
+        let (pa, pb) = (a.get_pixel(x, y), b.get_pixel(x, y));
+        let d = |i: usize| (pa[i] as i16 - pb[i] as i16).abs() as u8;
+        let alpha = d(3);
+        Rgba([d(0).max(alpha), d(1).max(alpha), d(2).max(alpha), 255])
+    })
+}
+
+// The SSIM of a single channel within the given window.
+fn window_ssim(a: &RgbaImage, b: &RgbaImage, [x, y]: [u32; 2], [w, h]: [u32; 2], ch: usize) -> f64 {
+    let n = (w * h) as f64;
+    let (mut sum_a, mut sum_b) = (0.0, 0.0);
+    let (mut sum_aa, mut sum_bb, mut sum_ab) = (0.0, 0.0, 0.0);
+    for py in y..y + h {
+        for px in x..x + w {
+            let va = a.get_pixel(px, py)[ch] as f64;
+            let vb = b.get_pixel(px, py)[ch] as f64;
+            sum_a += va;
+            sum_b += vb;
+            sum_aa += va * va;
+            sum_bb += vb * vb;
+            sum_ab += va * vb;
+        }
+    }
+    let (mean_a, mean_b) = (sum_a / n, sum_b / n);
+    let var_a = sum_aa / n - mean_a * mean_a;
+    let var_b = sum_bb / n - mean_b * mean_b;
+    let cov = sum_ab / n - mean_a * mean_b;
+    let numerator = (2.0 * mean_a * mean_b + SSIM_C1) * (2.0 * cov + SSIM_C2);
+    let denominator = (mean_a * mean_a + mean_b * mean_b + SSIM_C1) * (var_a + var_b + SSIM_C2);
+    numerator / denominator
+}
+
+// Save the image to the given path, creating its directory if necessary.
+fn save(image: &DynamicImage, path: &Path) {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .unwrap_or_else(|e| panic!("failed to create \"{}\": {}", dir.display(), e));
+    }
+    image
+        .save(path)
+        .unwrap_or_else(|e| panic!("failed to save \"{}\": {}", path.display(), e));
+}
@@ -140,6 +140,18 @@ where
     collection.into_font().or(Err(Error::NoFont))
 }
 
+/// Load a single `Font` from the given bytes, e.g. those of a TTF or OTF file.
+///
+/// Accepts either owned bytes or a slice, which will be copied. Static data such as that produced
+/// by `include_bytes!` may be loaded without copying via `Font::from_bytes` directly.
+pub fn from_bytes<B>(bytes: B) -> Result<Font, Error>
+where
+    B: Into<Vec<u8>>,
+{
+    let collection = FontCollection::from_bytes(bytes.into()).map_err(|e| Error::Io(e.into()))?;
+    collection.into_font().or(Err(Error::NoFont))
+}
+
 /// Load the default notosans font.
 ///
 /// This function is only available if the `notosans` feature is enabled, which it is by default.
@@ -105,6 +105,27 @@ impl Builder {
         self
     }
 
+    /// Specify how the text should be aligned along the x axis of its bounding rectangle.
+    ///
+    /// This is an alternative to `justify` where `Align::Start`, `Align::Middle` and `Align::End`
+    /// are equivalent to `Justify::Left`, `Justify::Center` and `Justify::Right` respectively.
+    pub fn align_x(self, align: Align) -> Self {
+        let justify = match align {
+            Align::Start => Justify::Left,
+            Align::Middle => Justify::Center,
+            Align::End => Justify::Right,
+        };
+        self.justify(justify)
+    }
+
+    /// Specify how the text should be aligned along the y axis of its bounding rectangle.
+    ///
+    /// This is the same as `y_align`, where `Align::Start` is the bottom and `Align::End` is the
+    /// top.
+    pub fn align_y(self, align: Align) -> Self {
+        self.y_align(align)
+    }
+
     /// Align the top edge of the text with the top edge of its bounding rectangle.
     pub fn align_top(self) -> Self {
         self.y_align(Align::End)
@@ -192,6 +192,16 @@ impl<'a> Infos<'a, NextBreakFnPtr> {
         self.max_width = max_width;
         self
     }
+
+    /// Converts `Self` into an `Infos` whose lines are wrapped at the last line break opportunity
+    /// prior to the character that causes the line width to exceed the given `max_width`.
+    ///
+    /// Line break opportunities are determined via the Unicode line breaking algorithm (UAX #14).
+    pub fn wrap_by_unicode(mut self, max_width: Scalar) -> Self {
+        self.next_break_fn = next_break_by_unicode;
+        self.max_width = max_width;
+        self
+    }
 }
 
 /// A function for finding the advance width between the given character that also considers
@@ -473,6 +483,146 @@ fn next_break_by_whitespace(
     }
 }
 
+/// Returns the next index at which the text will break by either:
+/// - A newline character.
+/// - A line wrap at the last Unicode line break opportunity prior to the first character
+/// exceeding the `max_width`. A single whitespace character preceding the opportunity is skipped.
+/// - A line wrap at the beginning of the first character exceeding the `max_width`,
+/// if no break opportunity appears for `max_width` characters.
+///
+/// Whitespace never causes a line wrap by itself, allowing trailing whitespace to overflow.
+///
+/// Also returns the width the line alongside the Break.
+fn next_break_by_unicode(
+    text: &str,
+    font: &text::Font,
+    font_size: FontSize,
+    max_width: Scalar,
+) -> NextBreak {
+    struct Last {
+        byte: usize,
+        char: usize,
+        len_bytes: usize,
+        width_before: Scalar,
+    }
+    let scale = text::pt_to_scale(font_size);
+    let mut opportunities = unicode_linebreak::linebreaks(text)
+        .map(|(byte, _)| byte)
+        .peekable();
+    let mut last_opportunity = None;
+    // The byte length of the previous character and the width before it, if it was whitespace.
+    let mut prev_whitespace = None;
+    let mut width = 0.0;
+    let mut height = 0.0;
+    let mut char_i = 0;
+    let mut char_indices = text.char_indices().peekable();
+    let mut last_glyph = None;
+    while let Some((byte_i, ch)) = char_indices.next() {
+        // Check for a newline.
+        if ch == '\r' {
+            if let Some(&(_, '\n')) = char_indices.peek() {
+                let break_ = Break::Newline {
+                    byte: byte_i,
+                    char: char_i,
+                    len_bytes: 2,
+                };
+                return NextBreak {
+                    break_,
+                    width,
+                    height,
+                };
+            }
+        } else if ch == '\n' {
+            let break_ = Break::Newline {
+                byte: byte_i,
+                char: char_i,
+                len_bytes: 1,
+            };
+            return NextBreak {
+                break_,
+                width,
+                height,
+            };
+        }
+
+        // Check for a break opportunity before this character.
+        while opportunities.peek().map(|&b| b < byte_i).unwrap_or(false) {
+            opportunities.next();
+        }
+        if byte_i > 0 && opportunities.peek() == Some(&byte_i) {
+            last_opportunity = Some(match prev_whitespace {
+                Some((len_bytes, width_before)) => Last {
+                    byte: byte_i - len_bytes,
+                    char: char_i - 1,
+                    len_bytes,
+                    width_before,
+                },
+                None => Last {
+                    byte: byte_i,
+                    char: char_i,
+                    len_bytes: 0,
+                    width_before: width,
+                },
+            });
+        }
+
+        // Add the character's width to the width so far.
+        let (adv_w, h) = advance_width_and_height(ch, font, scale, &mut last_glyph);
+        let new_width = width + adv_w;
+
+        // Check for a line wrap.
+        if new_width > max_width && !ch.is_whitespace() && char_i > 0 {
+            let (break_, width) = match last_opportunity {
+                Some(Last {
+                    byte,
+                    char,
+                    len_bytes,
+                    width_before,
+                }) => {
+                    let break_ = Break::Wrap {
+                        byte,
+                        char,
+                        len_bytes,
+                    };
+                    (break_, width_before)
+                }
+                None => {
+                    let break_ = Break::Wrap {
+                        byte: byte_i,
+                        char: char_i,
+                        len_bytes: 0,
+                    };
+                    (break_, width)
+                }
+            };
+            return NextBreak {
+                break_,
+                width,
+                height,
+            };
+        }
+
+        prev_whitespace = if ch.is_whitespace() {
+            Some((ch.len_utf8(), width))
+        } else {
+            None
+        };
+        width = new_width;
+        height = height.max(h);
+        char_i += 1;
+    }
+
+    let break_ = Break::End {
+        byte: text.len(),
+        char: char_i,
+    };
+    NextBreak {
+        break_,
+        width,
+        height,
+    }
+}
+
 /// Produce the width of the given line of text including spaces (i.e. ' ').
 pub fn width(text: &str, font: &text::Font, font_size: FontSize) -> Scalar {
     let scale = text::Scale::uniform(text::pt_to_px(font_size));
@@ -545,6 +695,7 @@ pub fn infos_maybe_wrapped<'a>(
         None => infos(text, font, font_size),
         Some(Wrap::Character) => infos(text, font, font_size).wrap_by_character(max_width),
         Some(Wrap::Whitespace) => infos(text, font, font_size).wrap_by_whitespace(max_width),
+        Some(Wrap::Unicode) => infos(text, font, font_size).wrap_by_unicode(max_width),
     }
 }
 
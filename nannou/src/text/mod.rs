@@ -8,6 +8,7 @@ pub mod font;
 pub mod glyph;
 pub mod layout;
 pub mod line;
+pub mod sdf;
 pub mod rt {
     //! Re-exported RustType geometric types.
     pub use rusttype::{gpu_cache, point, vector, Point, Rect, Vector};
@@ -123,6 +124,9 @@ pub enum Wrap {
     Character,
     /// Wrap at the first word that exceeds the width.
     Whitespace,
+    /// Wrap at the last Unicode line break opportunity before the character that exceeds the
+    /// width, following the Unicode line breaking algorithm (UAX #14).
+    Unicode,
 }
 
 impl<'a> From<Cow<'a, str>> for Builder<'a> {
@@ -227,6 +231,22 @@ impl<'a> Builder<'a> {
         self.map_layout(|l| l.y_align(align))
     }
 
+    /// Specify how the text should be aligned along the x axis of its bounding rectangle.
+    ///
+    /// `Align::Start`, `Align::Middle` and `Align::End` align the text to the left, centre and
+    /// right respectively.
+    pub fn align_x(self, align: Align) -> Self {
+        self.map_layout(|l| l.align_x(align))
+    }
+
+    /// Specify how the text should be aligned along the y axis of its bounding rectangle.
+    ///
+    /// `Align::Start`, `Align::Middle` and `Align::End` align the text to the bottom, middle and
+    /// top respectively.
+    pub fn align_y(self, align: Align) -> Self {
+        self.map_layout(|l| l.align_y(align))
+    }
+
     /// Align the top edge of the text with the top edge of its bounding rectangle.
     pub fn align_top(self) -> Self {
         self.map_layout(|l| l.align_top())
@@ -0,0 +1,514 @@
+//! Multi-channel signed distance fields for rendering glyphs that remain sharp at any scale.
+//!
+//! Each glyph is converted to a multi-channel signed distance field (MSDF) once, upon being
+//! inserted into an **SdfAtlas**. Edges of the glyph outline are assigned to different colour
+//! channels such that the median of the three channels reconstructs sharp corners when the field
+//! is sampled with linear filtering.
+//!
+//! See the `TextRenderMode::Sdf` mode of the `Draw::text` primitive.
+
+use crate::text::{Font, GlyphId, Scale};
+use std::collections::HashMap;
+
+/// The size in pixels of the em square at which each glyph's distance field is generated.
+pub const EM_SIZE: f32 = 48.0;
+
+/// The distance in pixels from the glyph outline at which the distance field saturates.
+///
+/// This also determines the padding around each glyph within the atlas.
+pub const SPREAD: f32 = 4.0;
+
+/// The distance field of a single glyph.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bitmap {
+    /// The width of the bitmap in pixels.
+    pub width: u32,
+    /// The height of the bitmap in pixels.
+    pub height: u32,
+    /// RGBA pixel data in rows from top to bottom. The alpha channel is unused.
+    pub pixels: Vec<u8>,
+    /// The offset from the glyph origin to the left edge of the bitmap at `EM_SIZE`.
+    pub left: f32,
+    /// The offset from the glyph origin up to the top edge of the bitmap at `EM_SIZE`.
+    pub top: f32,
+}
+
+/// The location of a glyph's distance field within an **SdfAtlas**.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Entry {
+    /// The left edge of the glyph within the atlas in pixels.
+    pub x: u32,
+    /// The top edge of the glyph within the atlas in pixels.
+    pub y: u32,
+    /// The width of the glyph's bitmap in pixels.
+    pub width: u32,
+    /// The height of the glyph's bitmap in pixels.
+    pub height: u32,
+    /// The offset from the glyph origin to the left edge of the bitmap at `EM_SIZE`.
+    pub left: f32,
+    /// The offset from the glyph origin up to the top edge of the bitmap at `EM_SIZE`.
+    pub top: f32,
+}
+
+/// An atlas of glyph distance fields, generated as glyphs are first requested.
+///
+/// The atlas grows as necessary up to `SdfAtlas::MAX_SIZE` and is shared between all fonts.
+/// Glyphs are never rasterised more than once, regardless of the size at which they are drawn.
+#[derive(Debug)]
+pub struct SdfAtlas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+    // `None` for glyphs without an outline, e.g. spaces.
+    glyphs: HashMap<(usize, GlyphId), Option<Entry>>,
+    // The position of the next glyph within the current row and the height of the row.
+    cursor: [u32; 2],
+    row_height: u32,
+    /// Will be set to `true` after the atlas has been updated if the texture requires re-uploading.
+    pub requires_upload: bool,
+}
+
+// A flattened segment of the glyph outline.
+#[derive(Copy, Clone, Debug)]
+struct Edge {
+    a: [f32; 2],
+    b: [f32; 2],
+    // The bitmask of the channels to which the edge contributes.
+    channels: u8,
+    // Whether or not the ends are the ends of an edge of the original outline, beyond which the
+    // edge may be extended.
+    extend_a: bool,
+    extend_b: bool,
+}
+
+// A segment of the original outline, flattened to a polyline.
+struct Segment {
+    points: Vec<[f32; 2]>,
+    start_dir: [f32; 2],
+    end_dir: [f32; 2],
+}
+
+const RED: u8 = 0b001;
+const GREEN: u8 = 0b010;
+const BLUE: u8 = 0b100;
+const WHITE: u8 = RED | GREEN | BLUE;
+const COLORS: [u8; 3] = [GREEN | BLUE, RED | BLUE, RED | GREEN];
+
+// The number of lines used to approximate each curve.
+const CURVE_SUBDIVISIONS: usize = 8;
+
+// Corners sharper than this angle in radians are preserved by switching channels.
+const CORNER_ANGLE: f32 = 3.0;
+
+// The padding between glyphs within the atlas, preventing bleeding between neighbours.
+const PADDING: u32 = 1;
+
+impl SdfAtlas {
+    /// The default width and height of the atlas.
+    pub const DEFAULT_SIZE: u32 = 256;
+    /// The maximum size to which the atlas may grow.
+    pub const MAX_SIZE: u32 = 4096;
+
+    /// Create an empty atlas with the given width and height.
+    pub fn new(size: u32) -> Self {
+        SdfAtlas {
+            width: size,
+            height: size,
+            pixels: vec![0; size as usize * size as usize * 4],
+            glyphs: HashMap::new(),
+            cursor: [PADDING, PADDING],
+            row_height: 0,
+            requires_upload: false,
+        }
+    }
+
+    /// The width and height of the atlas in pixels.
+    pub fn dimensions(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// The RGBA pixel data of the atlas.
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Retrieve the location of the given glyph, generating its distance field if necessary.
+    ///
+    /// The `font_id` should uniquely identify the font, e.g. via `text::font::id`. Returns `None`
+    /// if the glyph has no outline or there is no more room within the atlas.
+    pub fn glyph(&mut self, font_id: usize, font: &Font, id: GlyphId) -> Option<Entry> {
+        let key = (font_id, id);
+        if let Some(entry) = self.glyphs.get(&key) {
+            return *entry;
+        }
+        let entry = match generate(font, id) {
+            None => None,
+            Some(bitmap) => {
+                let entry = self.insert(&bitmap)?;
+                Some(entry)
+            }
+        };
+        self.glyphs.insert(key, entry);
+        entry
+    }
+
+    /// The normalised texture coordinates of the given entry as `[left, top, right, bottom]`.
+    pub fn uv_rect(&self, entry: &Entry) -> [f32; 4] {
+        let (w, h) = (self.width as f32, self.height as f32);
+        [
+            entry.x as f32 / w,
+            entry.y as f32 / h,
+            (entry.x + entry.width) as f32 / w,
+            (entry.y + entry.height) as f32 / h,
+        ]
+    }
+
+    // Insert the bitmap, growing the atlas if necessary.
+    fn insert(&mut self, bitmap: &Bitmap) -> Option<Entry> {
+        let [x, y] = loop {
+            if let Some(position) = self.allocate(bitmap.width, bitmap.height) {
+                break position;
+            }
+            if !self.grow() {
+                eprintln!("failed to insert glyph: the SDF atlas is full");
+                return None;
+            }
+        };
+        let row_bytes = bitmap.width as usize * 4;
+        for row in 0..bitmap.height as usize {
+            let src = row * row_bytes;
+            let dst = ((y as usize + row) * self.width as usize + x as usize) * 4;
+            self.pixels[dst..dst + row_bytes].copy_from_slice(&bitmap.pixels[src..src + row_bytes]);
+        }
+        self.requires_upload = true;
+        Some(Entry {
+            x,
+            y,
+            width: bitmap.width,
+            height: bitmap.height,
+            left: bitmap.left,
+            top: bitmap.top,
+        })
+    }
+
+    // Find room for a rect of the given size, beginning a new row if necessary.
+    fn allocate(&mut self, w: u32, h: u32) -> Option<[u32; 2]> {
+        if self.cursor[0] + w + PADDING > self.width {
+            self.cursor = [PADDING, self.cursor[1] + self.row_height + PADDING];
+            self.row_height = 0;
+        }
+        if self.cursor[0] + w + PADDING > self.width || self.cursor[1] + h + PADDING > self.height {
+            return None;
+        }
+        let position = self.cursor;
+        self.cursor[0] += w + PADDING;
+        self.row_height = self.row_height.max(h);
+        Some(position)
+    }
+
+    // Double the size of the atlas, retaining the position of all existing glyphs.
+    fn grow(&mut self) -> bool {
+        if self.width >= Self::MAX_SIZE {
+            return false;
+        }
+        let (old_w, old_h) = (self.width as usize, self.height as usize);
+        let size = (self.width * 2).min(Self::MAX_SIZE);
+        let mut pixels = vec![0; size as usize * size as usize * 4];
+        for row in 0..old_h {
+            let src = row * old_w * 4;
+            let dst = row * size as usize * 4;
+            pixels[dst..dst + old_w * 4].copy_from_slice(&self.pixels[src..src + old_w * 4]);
+        }
+        // Continue the current row into the newly available space.
+        self.width = size;
+        self.height = size;
+        self.pixels = pixels;
+        self.requires_upload = true;
+        true
+    }
+}
+
+impl Default for SdfAtlas {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_SIZE)
+    }
+}
+
+/// Generate the multi-channel signed distance field for the given glyph at `EM_SIZE`.
+///
+/// Distances are encoded such that `0.5` lies on the outline, values greater than `0.5` lie
+/// within the glyph and the field saturates at `SPREAD` pixels from the outline. The signed
+/// distance is reconstructed via the median of the red, green and blue channels.
+///
+/// Returns `None` if the glyph has no outline.
+pub fn generate(font: &Font, id: GlyphId) -> Option<Bitmap> {
+    let glyph = font.glyph(id).scaled(Scale::uniform(EM_SIZE));
+    let contours = glyph.shape()?;
+    let mut edges = vec![];
+    for contour in contours {
+        let segments: Vec<Segment> = contour.segments.iter().map(flatten).collect();
+        let colors = edge_colors(&segments);
+        for (segment, channels) in segments.iter().zip(colors) {
+            let n = segment.points.len() - 1;
+            for (i, w) in segment.points.windows(2).enumerate() {
+                edges.push(Edge {
+                    a: w[0],
+                    b: w[1],
+                    channels,
+                    extend_a: i == 0,
+                    extend_b: i + 1 == n,
+                });
+            }
+        }
+    }
+    if edges.is_empty() {
+        return None;
+    }
+
+    // Determine whether outer contours wind clockwise (e.g. TrueType) or counter-clockwise so
+    // that the side of each edge may be mapped to inside or outside.
+    let area: f32 = edges
+        .iter()
+        .map(|e| e.a[0] * e.b[1] - e.b[0] * e.a[1])
+        .sum();
+    let orientation = if area >= 0.0 { 1.0 } else { -1.0 };
+
+    // The bounds of the outline in y-up glyph space, padded by the spread.
+    let (mut min, mut max) = ([std::f32::MAX; 2], [std::f32::MIN; 2]);
+    for e in &edges {
+        for p in [e.a, e.b].iter() {
+            min = [min[0].min(p[0]), min[1].min(p[1])];
+            max = [max[0].max(p[0]), max[1].max(p[1])];
+        }
+    }
+    let left = (min[0] - SPREAD).floor();
+    let top = (max[1] + SPREAD).ceil();
+    let width = ((max[0] + SPREAD).ceil() - left) as u32;
+    let height = (top - (min[1] - SPREAD).floor()) as u32;
+
+    let mut pixels = Vec::with_capacity(width as usize * height as usize * 4);
+    for row in 0..height {
+        for col in 0..width {
+            let p = [left + col as f32 + 0.5, top - row as f32 - 0.5];
+            let mut channels = [0.0; 3];
+            for (c, channel) in channels.iter_mut().enumerate() {
+                *channel = channel_distance(&edges, 1 << c, orientation, p);
+            }
+            // Fall back to the true distance where the median would produce the wrong side,
+            // avoiding artifacts where edges of the same channel meet.
+            let distance = true_distance(&edges, p);
+            let median = median(channels[0], channels[1], channels[2]);
+            if (median > 0.0) != (distance > 0.0) {
+                channels = [distance; 3];
+            }
+            for &d in channels.iter() {
+                let v = (0.5 + d / (2.0 * SPREAD)).max(0.0).min(1.0);
+                pixels.push((v * 255.0).round() as u8);
+            }
+            pixels.push(255);
+        }
+    }
+
+    Some(Bitmap {
+        width,
+        height,
+        pixels,
+        left,
+        top,
+    })
+}
+
+// Flatten the given segment to a polyline along with its start and end tangents.
+fn flatten(segment: &rusttype::Segment) -> Segment {
+    let v = |p: rusttype::Point<f32>| [p.x, p.y];
+    match *segment {
+        rusttype::Segment::Line(ref line) => {
+            let (a, b) = (v(line.p[0]), v(line.p[1]));
+            let dir = sub(b, a);
+            Segment {
+                points: vec![a, b],
+                start_dir: dir,
+                end_dir: dir,
+            }
+        }
+        rusttype::Segment::Curve(ref curve) => {
+            let (a, c, b) = (v(curve.p[0]), v(curve.p[1]), v(curve.p[2]));
+            let points = (0..=CURVE_SUBDIVISIONS)
+                .map(|i| {
+                    let t = i as f32 / CURVE_SUBDIVISIONS as f32;
+                    let u = 1.0 - t;
+                    [
+                        u * u * a[0] + 2.0 * u * t * c[0] + t * t * b[0],
+                        u * u * a[1] + 2.0 * u * t * c[1] + t * t * b[1],
+                    ]
+                })
+                .collect();
+            // Fall back to the chord for degenerate control points.
+            let chord = sub(b, a);
+            let start_dir = if c == a { chord } else { sub(c, a) };
+            let end_dir = if c == b { chord } else { sub(b, c) };
+            Segment {
+                points,
+                start_dir,
+                end_dir,
+            }
+        }
+    }
+}
+
+// Assign channels to each segment of a contour so that the segments meeting at each corner share
+// exactly one channel.
+fn edge_colors(segments: &[Segment]) -> Vec<u8> {
+    let n = segments.len();
+    let corner_threshold = CORNER_ANGLE.sin();
+    let corners: Vec<usize> = (0..n)
+        .filter(|&i| {
+            let prev = &segments[(i + n - 1) % n];
+            is_corner(prev.end_dir, segments[i].start_dir, corner_threshold)
+        })
+        .collect();
+    match corners.len() {
+        // A smooth contour requires no distinction between channels.
+        0 => vec![WHITE; n],
+        // A "teardrop" is split into thirds so that the single corner is preserved.
+        1 if n >= 3 => {
+            let start = corners[0];
+            let mut colors = vec![WHITE; n];
+            for i in 0..n {
+                let third = i * 3 / n;
+                colors[(start + i) % n] = match third {
+                    0 => COLORS[1],
+                    1 => WHITE,
+                    _ => COLORS[2],
+                };
+            }
+            colors
+        }
+        1 => vec![WHITE; n],
+        // Switch between colours at each corner, avoiding matching the first colour at the end.
+        count => {
+            let mut colors = vec![WHITE; n];
+            for (k, &corner) in corners.iter().enumerate() {
+                let mut color = COLORS[k % 3];
+                if k + 1 == count && k % 3 == 0 {
+                    color = COLORS[1];
+                }
+                let end = corners[(k + 1) % count];
+                let mut i = corner;
+                loop {
+                    colors[i] = color;
+                    i = (i + 1) % n;
+                    if i == end {
+                        break;
+                    }
+                }
+            }
+            colors
+        }
+    }
+}
+
+fn is_corner(a: [f32; 2], b: [f32; 2], threshold: f32) -> bool {
+    let (a, b) = (normalize(a), normalize(b));
+    dot(a, b) <= 0.0 || cross(a, b).abs() > threshold
+}
+
+// The signed pseudo-distance to the nearest edge contributing to the given channel.
+fn channel_distance(edges: &[Edge], channel: u8, orientation: f32, p: [f32; 2]) -> f32 {
+    let mut best_distance = std::f32::MAX;
+    let mut best_orthogonality = 0.0;
+    let mut best_signed = -std::f32::MAX;
+    for e in edges.iter().filter(|e| e.channels & channel != 0) {
+        let (t, q) = closest_point(e, p);
+        let to_p = sub(p, q);
+        let distance = length(to_p);
+        let dir = normalize(sub(e.b, e.a));
+        let orthogonality = if distance > 0.0 {
+            cross(dir, to_p).abs() / distance
+        } else {
+            1.0
+        };
+        let closer = distance < best_distance - 1e-4;
+        let tied = (distance - best_distance).abs() <= 1e-4;
+        if !closer && !(tied && orthogonality > best_orthogonality) {
+            continue;
+        }
+        best_distance = distance;
+        best_orthogonality = orthogonality;
+        // Beyond the ends of an original edge, use the distance to the edge's extension.
+        let side = cross(dir, sub(p, e.a)) * orientation;
+        let extended = (t <= 0.0 && e.extend_a) || (t >= 1.0 && e.extend_b);
+        best_signed = if extended {
+            side
+        } else if side >= 0.0 {
+            distance
+        } else {
+            -distance
+        };
+    }
+    best_signed
+}
+
+// The true signed distance to the outline, positive within the glyph.
+fn true_distance(edges: &[Edge], p: [f32; 2]) -> f32 {
+    let mut distance = std::f32::MAX;
+    let mut winding = 0;
+    for e in edges {
+        let (_, q) = closest_point(e, p);
+        distance = distance.min(length(sub(p, q)));
+        if (e.a[1] <= p[1]) != (e.b[1] <= p[1]) {
+            let t = (p[1] - e.a[1]) / (e.b[1] - e.a[1]);
+            let x = e.a[0] + t * (e.b[0] - e.a[0]);
+            if p[0] < x {
+                winding += if e.b[1] > e.a[1] { 1 } else { -1 };
+            }
+        }
+    }
+    if winding != 0 {
+        distance
+    } else {
+        -distance
+    }
+}
+
+// The unclamped projection of `p` onto the edge along with the closest point on the edge.
+fn closest_point(e: &Edge, p: [f32; 2]) -> (f32, [f32; 2]) {
+    let ab = sub(e.b, e.a);
+    let len_sq = dot(ab, ab);
+    let t = if len_sq > 0.0 {
+        dot(sub(p, e.a), ab) / len_sq
+    } else {
+        0.0
+    };
+    let c = t.max(0.0).min(1.0);
+    (t, [e.a[0] + ab[0] * c, e.a[1] + ab[1] * c])
+}
+
+fn median(a: f32, b: f32, c: f32) -> f32 {
+    a.min(b).max(a.max(b).min(c))
+}
+
+fn sub(a: [f32; 2], b: [f32; 2]) -> [f32; 2] {
+    [a[0] - b[0], a[1] - b[1]]
+}
+
+fn dot(a: [f32; 2], b: [f32; 2]) -> f32 {
+    a[0] * b[0] + a[1] * b[1]
+}
+
+fn cross(a: [f32; 2], b: [f32; 2]) -> f32 {
+    a[0] * b[1] - a[1] * b[0]
+}
+
+fn length(a: [f32; 2]) -> f32 {
+    dot(a, a).sqrt()
+}
+
+fn normalize(a: [f32; 2]) -> [f32; 2] {
+    let len = length(a);
+    if len > 0.0 {
+        [a[0] / len, a[1] / len]
+    } else {
+        a
+    }
+}
@@ -1,5 +1,9 @@
 //! Extensions and utilities for working with time.
 
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
 /// An extension for the `std::time::Duration` type providing some simple methods for easy access
 /// to an `f64` representation of seconds, ms, mins, hrs, and other units of time.
 ///
@@ -42,3 +46,247 @@ impl DurationF64 for std::time::Duration {
         self.as_secs() as f64 + self.subsec_nanos() as f64 * 1e-9
     }
 }
+
+/// A shared handle to a source of time, measured from some starting point.
+///
+/// The clock of an **App** advances with `app.duration.since_start` upon each update. Clones of a
+/// **Clock** share the same time.
+#[derive(Clone, Debug, Default)]
+pub struct Clock {
+    now: Rc<Cell<Duration>>,
+}
+
+/// A timer that measures the progress of a fixed duration.
+///
+/// A **Timer** created via `Timer::new` is driven by the system clock, while a **Timer** created
+/// via `App::create_timer` advances with the app's time. Either way, the timer begins upon
+/// creation and may be polled from within `update` or `view`:
+///
+/// ```ignore
+/// fn update(app: &App, model: &mut Model, _update: Update) {
+///     model.radius = model.timer.elapsed_fraction() * 100.0;
+///     if model.timer.finished() {
+///         model.timer.reset();
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Timer {
+    duration: f32,
+    source: Source,
+    start: Duration,
+}
+
+/// A sequence of callbacks, each called once a specified amount of time has passed.
+///
+/// Callbacks are scheduled via `wait` and `then`, and receive a mutable reference to some state
+/// of type `T` when called. The sequence is driven by calling `update` within the app's `update`
+/// function. Storing the state alongside the sequence allows both to live within the app's model:
+///
+/// ```ignore
+/// struct Model {
+///     sequence: Sequence<Scene>,
+///     scene: Scene,
+/// }
+///
+/// let sequence = Sequence::new()
+///     .wait(1.0)
+///     .then(|scene: &mut Scene| scene.show_title = true)
+///     .wait(2.0)
+///     .then(|scene: &mut Scene| scene.show_title = false);
+///
+/// fn update(_app: &App, model: &mut Model, update: Update) {
+///     model.sequence.update(&update, &mut model.scene);
+/// }
+/// ```
+pub struct Sequence<T = ()> {
+    steps: Vec<Step<T>>,
+    // The total of all `wait`s so far, i.e. the time at which the next callback is scheduled.
+    end: f32,
+    elapsed: f32,
+    // The number of steps that have already been called.
+    next: usize,
+}
+
+#[derive(Clone, Debug)]
+enum Source {
+    System(Instant),
+    Clock(Clock),
+}
+
+struct Step<T> {
+    at: f32,
+    callback: Box<dyn FnMut(&mut T)>,
+}
+
+impl Clock {
+    /// The current time of the clock.
+    pub fn now(&self) -> Duration {
+        self.now.get()
+    }
+
+    /// Set the current time of the clock.
+    pub fn set(&self, now: Duration) {
+        self.now.set(now);
+    }
+}
+
+impl Timer {
+    /// A timer of the given duration in seconds, measured via the system clock.
+    pub fn new(duration_secs: f32) -> Self {
+        let source = Source::System(Instant::now());
+        Self::with_source(duration_secs, source)
+    }
+
+    /// A timer of the given duration in seconds, measured via the given clock.
+    pub fn from_clock(duration_secs: f32, clock: Clock) -> Self {
+        Self::with_source(duration_secs, Source::Clock(clock))
+    }
+
+    fn with_source(duration_secs: f32, source: Source) -> Self {
+        let mut timer = Timer {
+            duration: duration_secs,
+            source,
+            start: Duration::default(),
+        };
+        timer.start = timer.now();
+        timer
+    }
+
+    // The current time of the timer's source.
+    fn now(&self) -> Duration {
+        match self.source {
+            Source::System(origin) => origin.elapsed(),
+            Source::Clock(ref clock) => clock.now(),
+        }
+    }
+
+    /// The duration of the timer in seconds.
+    pub fn duration_secs(&self) -> f32 {
+        self.duration
+    }
+
+    /// The number of seconds since the timer started.
+    ///
+    /// Unlike `elapsed_fraction`, this continues to grow after the timer has finished.
+    pub fn elapsed_secs(&self) -> f32 {
+        let now = self.now();
+        if now > self.start {
+            (now - self.start).secs() as f32
+        } else {
+            0.0
+        }
+    }
+
+    /// The number of seconds until the timer finishes, or `0.0` if it has finished.
+    pub fn remaining_secs(&self) -> f32 {
+        (self.duration - self.elapsed_secs()).max(0.0)
+    }
+
+    /// The progress of the timer in the range `0.0` to `1.0`.
+    ///
+    /// A timer with a duration of `0.0` is always complete.
+    pub fn elapsed_fraction(&self) -> f32 {
+        if self.duration <= 0.0 {
+            return 1.0;
+        }
+        (self.elapsed_secs() / self.duration).min(1.0)
+    }
+
+    /// Whether or not the duration of the timer has elapsed.
+    pub fn finished(&self) -> bool {
+        self.elapsed_secs() >= self.duration
+    }
+
+    /// Restart the timer from the current time.
+    pub fn reset(&mut self) {
+        self.start = self.now();
+    }
+}
+
+impl<T> Sequence<T> {
+    /// An empty sequence.
+    pub fn new() -> Self {
+        Sequence {
+            steps: vec![],
+            end: 0.0,
+            elapsed: 0.0,
+            next: 0,
+        }
+    }
+
+    /// Delay the following callbacks by the given number of seconds.
+    pub fn wait(mut self, secs: f32) -> Self {
+        self.end += secs.max(0.0);
+        self
+    }
+
+    /// Call the given function once all preceding `wait`s have elapsed.
+    ///
+    /// Callbacks scheduled for the same time are called in the order in which they were added.
+    pub fn then<F>(mut self, callback: F) -> Self
+    where
+        F: 'static + FnMut(&mut T),
+    {
+        let at = self.end;
+        let callback = Box::new(callback);
+        self.steps.push(Step { at, callback });
+        self
+    }
+
+    /// Advance the sequence by the time since the last update, calling any callbacks that are due.
+    pub fn update(&mut self, update: &crate::event::Update, state: &mut T) {
+        self.advance(update.since_last.secs() as f32, state);
+    }
+
+    /// Advance the sequence by the given number of seconds, calling any callbacks that are due.
+    pub fn advance(&mut self, secs: f32, state: &mut T) {
+        self.elapsed += secs;
+        while let Some(step) = self.steps.get_mut(self.next) {
+            if step.at > self.elapsed {
+                break;
+            }
+            (step.callback)(state);
+            self.next += 1;
+        }
+    }
+
+    /// The number of seconds that the sequence has been advanced by since it began.
+    pub fn elapsed_secs(&self) -> f32 {
+        self.elapsed
+    }
+
+    /// The total duration of the sequence in seconds, i.e. the sum of all `wait`s.
+    pub fn duration_secs(&self) -> f32 {
+        self.end
+    }
+
+    /// Whether or not all callbacks have been called and all `wait`s have elapsed.
+    pub fn finished(&self) -> bool {
+        self.next == self.steps.len() && self.elapsed >= self.end
+    }
+
+    /// Restart the sequence so that all callbacks will be called again.
+    pub fn reset(&mut self) {
+        self.elapsed = 0.0;
+        self.next = 0;
+    }
+}
+
+impl<T> Default for Sequence<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> std::fmt::Debug for Sequence<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let steps: Vec<_> = self.steps.iter().map(|step| step.at).collect();
+        f.debug_struct("Sequence")
+            .field("steps", &steps)
+            .field("end", &self.end)
+            .field("elapsed", &self.elapsed)
+            .field("next", &self.next)
+            .finish()
+    }
+}
@@ -0,0 +1,322 @@
+//! Logo-style turtle graphics.
+//!
+//! A **Turtle** moves about the plane at the command of the user, drawing a line behind it while
+//! its pen is down. Each turtle records its own lines, so any number of turtles may be operated
+//! independently. The **App** also provides a registry of named turtles via `app.turtle(name)`:
+//!
+//! ```ignore
+//! fn model(app: &App) -> Model {
+//!     let mut turtle = app.turtle("leonardo");
+//!     turtle.pen_color(ORANGE);
+//!     for _ in 0..4 {
+//!         turtle.forward(100.0);
+//!         turtle.turn_right(90.0);
+//!     }
+//!     Model
+//! }
+//!
+//! fn view(app: &App, _model: &Model, frame: Frame) {
+//!     let draw = app.draw();
+//!     draw.background().color(BLACK);
+//!     app.turtle("leonardo").draw(&draw);
+//!     draw.to_frame(app, &frame).unwrap();
+//! }
+//! ```
+//!
+//! Recorded lines persist until `clear` is called, allowing for drawing the same picture every
+//! frame or building it up over time.
+//!
+//! Turtles may also interpret the strings produced by an L-system via `interpret`. See the
+//! [**lsystem**](../lsystem/index.html) module for details.
+
+use crate::color::{lin_srgba, IntoLinSrgba, LinSrgba};
+use crate::draw::properties::ColorScalar;
+use crate::geom::{self, pt2, Point2};
+use crate::lsystem::TurtleCommand;
+use crate::Draw;
+use std::collections::HashMap;
+
+/// A Logo-style turtle that records lines as it moves.
+///
+/// The turtle begins at the origin facing up the *y* axis with its pen down, drawing white lines
+/// with a width of `1.0`.
+#[derive(Clone, Debug)]
+pub struct Turtle {
+    position: Point2,
+    // The heading in degrees, clockwise from the positive *y* axis as in Logo.
+    heading: f32,
+    pen_down: bool,
+    pen_color: LinSrgba,
+    pen_width: f32,
+    visible: bool,
+    // The positions and headings pushed via `push_state`.
+    stack: Vec<(Point2, f32)>,
+    marks: Vec<Mark>,
+    // Whether the last mark is a line ending at the turtle's position that may be extended.
+    extending: bool,
+}
+
+// A line or stamp recorded by a turtle.
+#[derive(Clone, Debug)]
+enum Mark {
+    Line {
+        points: Vec<Point2>,
+        color: LinSrgba,
+        width: f32,
+    },
+    Stamp {
+        position: Point2,
+        heading: f32,
+        color: LinSrgba,
+    },
+}
+
+impl Turtle {
+    /// The length of the triangle drawn to represent the turtle and its stamps.
+    pub const SIZE: f32 = 12.0;
+
+    /// A turtle at the origin facing up the *y* axis with its pen down.
+    pub fn new() -> Self {
+        Turtle {
+            position: pt2(0.0, 0.0),
+            heading: 0.0,
+            pen_down: true,
+            pen_color: lin_srgba(1.0, 1.0, 1.0, 1.0),
+            pen_width: 1.0,
+            visible: true,
+            stack: vec![],
+            marks: vec![],
+            extending: false,
+        }
+    }
+
+    /// The current position.
+    pub fn position(&self) -> Point2 {
+        self.position
+    }
+
+    /// The current heading in degrees, clockwise from the positive *y* axis.
+    pub fn heading(&self) -> f32 {
+        self.heading
+    }
+
+    /// Face the given heading in degrees, clockwise from the positive *y* axis.
+    pub fn set_heading(&mut self, degrees: f32) {
+        self.heading = degrees.rem_euclid(360.0);
+    }
+
+    /// Whether or not the turtle draws as it moves.
+    pub fn is_pen_down(&self) -> bool {
+        self.pen_down
+    }
+
+    /// Move forward by the given distance.
+    pub fn forward(&mut self, distance: f32) {
+        let radians = self.heading.to_radians();
+        let direction = pt2(radians.sin(), radians.cos());
+        self.goto(self.position + direction * distance);
+    }
+
+    /// Move backward by the given distance without changing the heading.
+    pub fn backward(&mut self, distance: f32) {
+        self.forward(-distance);
+    }
+
+    /// Turn clockwise by the given number of degrees.
+    pub fn turn_right(&mut self, degrees: f32) {
+        self.set_heading(self.heading + degrees);
+    }
+
+    /// Turn counter-clockwise by the given number of degrees.
+    pub fn turn_left(&mut self, degrees: f32) {
+        self.set_heading(self.heading - degrees);
+    }
+
+    /// Draw while moving.
+    pub fn pen_down(&mut self) {
+        self.pen_down = true;
+    }
+
+    /// Stop drawing while moving.
+    pub fn pen_up(&mut self) {
+        self.pen_down = false;
+        self.extending = false;
+    }
+
+    /// The colour of lines drawn from now on.
+    pub fn pen_color<C>(&mut self, color: C)
+    where
+        C: IntoLinSrgba<ColorScalar>,
+    {
+        self.pen_color = color.into_lin_srgba();
+        self.extending = false;
+    }
+
+    /// The width of lines drawn from now on.
+    pub fn pen_width(&mut self, width: f32) {
+        self.pen_width = width;
+        self.extending = false;
+    }
+
+    /// Move directly to the given position without changing the heading, drawing a line if the
+    /// pen is down.
+    pub fn goto(&mut self, position: Point2) {
+        let start = self.position;
+        self.position = position;
+        if !self.pen_down {
+            return;
+        }
+        if self.extending {
+            if let Some(Mark::Line { ref mut points, .. }) = self.marks.last_mut() {
+                points.push(position);
+                return;
+            }
+        }
+        self.marks.push(Mark::Line {
+            points: vec![start, position],
+            color: self.pen_color,
+            width: self.pen_width,
+        });
+        self.extending = true;
+    }
+
+    /// Return to the origin facing up the *y* axis, drawing a line if the pen is down.
+    pub fn home(&mut self) {
+        self.goto(pt2(0.0, 0.0));
+        self.heading = 0.0;
+    }
+
+    /// Push the current position and heading onto the state stack.
+    pub fn push_state(&mut self) {
+        self.stack.push((self.position, self.heading));
+    }
+
+    /// Return to the most recently pushed position and heading without drawing.
+    ///
+    /// Does nothing if the stack is empty.
+    pub fn pop_state(&mut self) {
+        if let Some((position, heading)) = self.stack.pop() {
+            self.position = position;
+            self.heading = heading;
+            self.extending = false;
+        }
+    }
+
+    /// Perform the command associated with each character of the given L-system string.
+    ///
+    /// Characters without a command are ignored, with the exception of `[` and `]` which push and
+    /// pop the state stack by default, allowing for branching. The resulting lines are recorded
+    /// in the current pen colour and width like any others.
+    pub fn interpret(&mut self, lsystem_string: &str, commands: &HashMap<char, TurtleCommand>) {
+        for c in lsystem_string.chars() {
+            let command = match (commands.get(&c), c) {
+                (Some(&command), _) => command,
+                (None, '[') => TurtleCommand::Push,
+                (None, ']') => TurtleCommand::Pop,
+                (None, _) => continue,
+            };
+            match command {
+                TurtleCommand::Forward(distance) => self.forward(distance),
+                TurtleCommand::Move(distance) => {
+                    let pen_down = self.pen_down;
+                    self.pen_up();
+                    self.forward(distance);
+                    self.pen_down = pen_down;
+                }
+                TurtleCommand::Turn(degrees) => self.turn_left(degrees),
+                TurtleCommand::Push => self.push_state(),
+                TurtleCommand::Pop => self.pop_state(),
+            }
+        }
+    }
+
+    /// Leave an imprint of the turtle in the pen colour at its current position.
+    pub fn stamp(&mut self) {
+        self.marks.push(Mark::Stamp {
+            position: self.position,
+            heading: self.heading,
+            color: self.pen_color,
+        });
+        self.extending = false;
+    }
+
+    /// Show the turtle when drawn.
+    pub fn show(&mut self) {
+        self.visible = true;
+    }
+
+    /// Hide the turtle when drawn, leaving only its lines and stamps.
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    /// Remove all recorded lines and stamps without moving the turtle.
+    pub fn clear(&mut self) {
+        self.marks.clear();
+        self.extending = false;
+    }
+
+    /// All recorded lines as a single path, ignoring their colours and widths.
+    pub fn path(&self) -> geom::path::Path {
+        let mut builder = geom::path::Builder::new();
+        for mark in &self.marks {
+            if let Mark::Line { ref points, .. } = *mark {
+                let mut points = points.iter().cloned();
+                if let Some(start) = points.next() {
+                    builder = builder.move_to(start);
+                }
+                for point in points {
+                    builder = builder.line_to(point);
+                }
+            }
+        }
+        builder.build()
+    }
+
+    /// Draw all recorded lines and stamps in the order in which they were made, followed by the
+    /// turtle itself if visible.
+    pub fn draw(&self, draw: &Draw) {
+        for mark in &self.marks {
+            match *mark {
+                Mark::Line {
+                    ref points,
+                    color,
+                    width,
+                } => {
+                    draw.polyline()
+                        .weight(width)
+                        .points(points.iter().cloned())
+                        .color(color);
+                }
+                Mark::Stamp {
+                    position,
+                    heading,
+                    color,
+                } => draw_turtle(draw, position, heading, color),
+            }
+        }
+        if self.visible {
+            draw_turtle(draw, self.position, self.heading, self.pen_color);
+        }
+    }
+}
+
+impl Default for Turtle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Draw the turtle as a triangle pointing along its heading.
+fn draw_turtle(draw: &Draw, position: Point2, heading: f32, color: LinSrgba) {
+    let s = Turtle::SIZE;
+    draw.tri()
+        .points(
+            pt2(0.0, s * 0.5),
+            pt2(-s * 0.35, -s * 0.5),
+            pt2(s * 0.35, -s * 0.5),
+        )
+        .xy(position)
+        .rotate(-heading.to_radians())
+        .color(color);
+}
@@ -0,0 +1,252 @@
+//! Items related to tweening, the interpolation of values over time.
+//!
+//! A **Tween** interpolates between a `start` and `end` value over a duration, shaping its
+//! progress with an **Easing**. Tweens may be stored within the app's model and advanced within
+//! the `update` function:
+//!
+//! ```ignore
+//! struct Model {
+//!     position: Tween<Point2>,
+//! }
+//!
+//! fn model(_app: &App) -> Model {
+//!     let position = Tween::new(pt2(-200.0, 0.0), pt2(200.0, 0.0), 2.0, ease::cubic_in_out);
+//!     Model { position }
+//! }
+//!
+//! fn update(app: &App, model: &mut Model, _update: Update) {
+//!     let dt = app.duration.since_prev_update.secs() as f32;
+//!     model.position.update(dt);
+//! }
+//! ```
+
+use crate::color::LinSrgba;
+use crate::ease::Easing;
+use crate::geom::{Vector2, Vector3, Vector4};
+use crate::math::{BaseFloat, Matrix4, NumCast};
+use std::fmt;
+
+/// Types that may be linearly interpolated.
+pub trait Lerp {
+    /// Linearly interpolate between `self` and `end` by the given amount, where `0.0` produces
+    /// `self` and `1.0` produces `end`.
+    ///
+    /// An `amount` outside of the `0.0..=1.0` range extrapolates beyond `self` or `end`, as may be
+    /// produced by easings that overshoot.
+    fn lerp(&self, end: &Self, amount: f32) -> Self;
+}
+
+/// Interpolates from a `start` to an `end` value over a duration in seconds.
+pub struct Tween<T> {
+    start: T,
+    end: T,
+    duration: f32,
+    elapsed: f32,
+    easing: Box<dyn Easing>,
+}
+
+/// A sequence of **Tween**s, each beginning once the previous tween completes.
+///
+/// Created via `Tween::chained`.
+#[derive(Debug)]
+pub struct TweenChain<T> {
+    tweens: Vec<Tween<T>>,
+    // The index of the tween that is currently progressing.
+    current: usize,
+}
+
+impl<T> Tween<T>
+where
+    T: Clone + Lerp,
+{
+    /// Interpolate from `start` to `end` over the given number of seconds, shaping progress via
+    /// the given easing.
+    ///
+    /// Any function of the form `Fn(f32) -> f32` may be used as an easing, e.g. `ease::quad_in`.
+    pub fn new<E>(start: T, end: T, duration_secs: f32, easing: E) -> Self
+    where
+        E: 'static + Easing,
+    {
+        Tween {
+            start,
+            end,
+            duration: duration_secs,
+            elapsed: 0.0,
+            easing: Box::new(easing),
+        }
+    }
+
+    /// Advance the tween by `dt` seconds and return the current value.
+    ///
+    /// Once the tween is complete, the `end` value is returned.
+    pub fn update(&mut self, dt: f32) -> T {
+        self.elapsed = (self.elapsed + dt).min(self.duration.max(0.0));
+        self.value()
+    }
+
+    /// The current value of the tween.
+    pub fn value(&self) -> T {
+        if self.finished() {
+            return self.end.clone();
+        }
+        let t = self.easing.ease(self.elapsed_fraction());
+        self.start.lerp(&self.end, t)
+    }
+
+    /// The progress of the tween in the range `0.0` to `1.0` prior to easing.
+    pub fn elapsed_fraction(&self) -> f32 {
+        if self.duration <= 0.0 {
+            return 1.0;
+        }
+        (self.elapsed / self.duration).min(1.0)
+    }
+
+    /// Whether or not the tween has reached its `end` value.
+    pub fn finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    /// Restart the tween from its `start` value.
+    pub fn reset(&mut self) {
+        self.elapsed = 0.0;
+    }
+
+    /// The value at which the tween starts.
+    pub fn start(&self) -> &T {
+        &self.start
+    }
+
+    /// The value at which the tween ends.
+    pub fn end(&self) -> &T {
+        &self.end
+    }
+
+    /// The duration of the tween in seconds.
+    pub fn duration_secs(&self) -> f32 {
+        self.duration
+    }
+
+    /// Follow this tween with the given tween, producing a **TweenChain**.
+    pub fn chained(self, next: Tween<T>) -> TweenChain<T> {
+        TweenChain {
+            tweens: vec![self, next],
+            current: 0,
+        }
+    }
+}
+
+impl<T> TweenChain<T>
+where
+    T: Clone + Lerp,
+{
+    /// Append the given tween to the end of the chain.
+    pub fn chained(mut self, next: Tween<T>) -> Self {
+        self.tweens.push(next);
+        self
+    }
+
+    /// Advance the chain by `dt` seconds and return the current value.
+    ///
+    /// Any time remaining after a tween completes is carried over to the next. Once the last tween
+    /// is complete, its `end` value is returned.
+    pub fn update(&mut self, mut dt: f32) -> T {
+        while let Some(tween) = self.tweens.get_mut(self.current) {
+            let remaining = (tween.duration - tween.elapsed).max(0.0);
+            tween.update(dt);
+            if dt < remaining || self.current + 1 == self.tweens.len() {
+                break;
+            }
+            dt -= remaining;
+            self.current += 1;
+        }
+        self.value()
+    }
+
+    /// The current value of the chain.
+    pub fn value(&self) -> T {
+        self.tweens[self.current].value()
+    }
+
+    /// Whether or not the last tween in the chain has reached its `end` value.
+    pub fn finished(&self) -> bool {
+        self.current + 1 == self.tweens.len() && self.tweens[self.current].finished()
+    }
+
+    /// Restart the chain from the `start` value of its first tween.
+    pub fn reset(&mut self) {
+        for tween in &mut self.tweens {
+            tween.reset();
+        }
+        self.current = 0;
+    }
+
+    /// The total duration of all tweens in the chain in seconds.
+    pub fn duration_secs(&self) -> f32 {
+        self.tweens.iter().map(|t| t.duration.max(0.0)).sum()
+    }
+}
+
+impl<T> fmt::Debug for Tween<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Tween")
+            .field("start", &self.start)
+            .field("end", &self.end)
+            .field("duration", &self.duration)
+            .field("elapsed", &self.elapsed)
+            .finish()
+    }
+}
+
+impl Lerp for f32 {
+    fn lerp(&self, end: &Self, amount: f32) -> Self {
+        self + (end - self) * amount
+    }
+}
+
+impl Lerp for f64 {
+    fn lerp(&self, end: &Self, amount: f32) -> Self {
+        self + (end - self) * amount as f64
+    }
+}
+
+macro_rules! impl_lerp_for_vector {
+    ($($VectorN:ident),*) => {
+        $(
+            impl<S> Lerp for $VectorN<S>
+            where
+                S: BaseFloat,
+            {
+                fn lerp(&self, end: &Self, amount: f32) -> Self {
+                    let amount: S = NumCast::from(amount).unwrap();
+                    *self + (*end - *self) * amount
+                }
+            }
+        )*
+    };
+}
+
+impl_lerp_for_vector!(Vector2, Vector3, Vector4);
+
+impl<S> Lerp for Matrix4<S>
+where
+    S: BaseFloat,
+{
+    fn lerp(&self, end: &Self, amount: f32) -> Self {
+        let amount: S = NumCast::from(amount).unwrap();
+        *self + (*end - *self) * amount
+    }
+}
+
+impl Lerp for LinSrgba {
+    fn lerp(&self, end: &Self, amount: f32) -> Self {
+        LinSrgba::new(
+            self.red.lerp(&end.red, amount),
+            self.green.lerp(&end.green, amount),
+            self.blue.lerp(&end.blue, amount),
+            self.alpha.lerp(&end.alpha, amount),
+        )
+    }
+}
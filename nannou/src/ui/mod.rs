@@ -1,6 +1,8 @@
 //! The User Interface API.
 //!
 //! Instantiate a [**Ui**](struct.Ui.html) via `app.new_ui()`.
+//!
+//! For simple parameter tweaking without conrod, see the [**Panel**](panel/struct.Panel.html).
 
 pub use self::conrod_core::event::Input;
 pub use self::conrod_core::{
@@ -11,10 +13,13 @@ pub use self::conrod_core::{
     Borderable, Bordering, Color, Colorable, Dimensions, FontSize, Labelable, Point, Positionable,
     Range, Rect, Scalar, Sizeable, Theme, UiCell, Widget,
 };
+pub use self::panel::Panel;
 pub use crate::conrod_core;
 pub use crate::conrod_wgpu;
 pub use crate::conrod_winit;
 
+pub mod panel;
+
 /// Simplify inclusion of common traits with a `nannou::ui::prelude` module.
 pub mod prelude {
     // Traits.
@@ -0,0 +1,302 @@
+//! A minimal overlay panel for live-tweaking parameters, i.e. `ui::Panel`.
+//!
+//! Unlike the conrod-based **Ui**, the **Panel** is drawn via a **Draw** and requires no setup
+//! beyond storing it within the model. Widgets are declared each update in immediate mode and
+//! write directly to the given values:
+//!
+//! ```ignore
+//! fn update(app: &App, model: &mut Model, _update: Update) {
+//!     let panel = model.panel.begin(app);
+//!     panel.slider("frequency", 0.0..10.0, &mut model.frequency);
+//!     panel.checkbox("show_grid", &mut model.show_grid);
+//!     panel.color_picker("bg_color", &mut model.bg);
+//!     panel.button("randomize", || model.randomize());
+//! }
+//!
+//! fn view(app: &App, model: &Model, frame: Frame) {
+//!     // ...
+//!     model.panel.draw_to_frame(app, &frame).unwrap();
+//! }
+//! ```
+//!
+//! `begin` returns a cheap clone of the panel, allowing for the model to be borrowed by the
+//! widgets while the panel is in use.
+
+use crate::color::{self, Rgb};
+use crate::draw::{self, Draw};
+use crate::frame::Frame;
+use crate::geom::{pt2, Point2, Rect};
+use crate::App;
+use std::cell::RefCell;
+use std::ops::Range;
+use std::rc::Rc;
+
+/// A translucent sidebar of widgets drawn on top of the frame.
+///
+/// Cloning a **Panel** produces a handle to the same panel.
+#[derive(Clone, Debug)]
+pub struct Panel {
+    state: Rc<RefCell<State>>,
+}
+
+#[derive(Debug)]
+struct State {
+    width: f32,
+    row_height: f32,
+    draw: Draw,
+    // The area of the window covered by the panel this update.
+    rect: Rect,
+    // The top of the next widget.
+    cursor_y: f32,
+    mouse: Point2,
+    mouse_down: bool,
+    pressed: bool,
+    released: bool,
+    // The label of the widget that the mouse was pressed over, if any.
+    active: Option<String>,
+}
+
+impl Panel {
+    /// The default width of the panel.
+    pub const DEFAULT_WIDTH: f32 = 240.0;
+    /// The default height of each widget row.
+    pub const DEFAULT_ROW_HEIGHT: f32 = 24.0;
+    /// The padding between the edges of the panel and its widgets.
+    pub const PADDING: f32 = 8.0;
+
+    /// Create a new panel.
+    pub fn new() -> Self {
+        let state = State {
+            width: Self::DEFAULT_WIDTH,
+            row_height: Self::DEFAULT_ROW_HEIGHT,
+            draw: Draw::new(),
+            rect: Rect::from_w_h(0.0, 0.0),
+            cursor_y: 0.0,
+            mouse: pt2(0.0, 0.0),
+            mouse_down: false,
+            pressed: false,
+            released: false,
+            active: None,
+        };
+        let state = Rc::new(RefCell::new(state));
+        Panel { state }
+    }
+
+    /// Specify the width of the panel.
+    pub fn width(self, width: f32) -> Self {
+        self.state.borrow_mut().width = width;
+        self
+    }
+
+    /// Specify the height of each widget row.
+    pub fn row_height(self, height: f32) -> Self {
+        self.state.borrow_mut().row_height = height;
+        self
+    }
+
+    /// Begin declaring the panel's widgets for this update.
+    ///
+    /// Clears the widgets of the previous update and reads the mouse state of the app. The panel
+    /// spans the height of the left side of the app's main window.
+    pub fn begin(&self, app: &App) -> Panel {
+        {
+            let mut state = self.state.borrow_mut();
+            let window = app.window_rect();
+            let rect = Rect::from_w_h(state.width, window.h()).top_left_of(window);
+            let mouse_down = app.mouse.buttons.left().is_down();
+            state.pressed = mouse_down && !state.mouse_down;
+            state.released = !mouse_down && state.mouse_down;
+            state.mouse_down = mouse_down;
+            state.mouse = app.mouse.position();
+            state.rect = rect;
+            state.cursor_y = rect.top() - Self::PADDING;
+            if !mouse_down && !state.released {
+                state.active = None;
+            }
+            state.draw.reset();
+            state
+                .draw
+                .rect()
+                .xy(rect.xy())
+                .wh(rect.wh())
+                .color(color::rgba(0.0, 0.0, 0.0, 0.6));
+        }
+        self.clone()
+    }
+
+    /// Whether or not the mouse is over the panel, e.g. for ignoring clicks intended for the
+    /// panel's widgets.
+    pub fn is_hovered(&self) -> bool {
+        let state = self.state.borrow();
+        state.rect.contains(state.mouse)
+    }
+
+    /// A horizontal slider setting `value` within the given range.
+    ///
+    /// Returns `true` if the value changed.
+    pub fn slider(&self, label: &str, range: Range<f32>, value: &mut f32) -> bool {
+        let mut state = self.state.borrow_mut();
+        let rect = state.next_row();
+        let held = state.interact(label, rect);
+        let before = *value;
+        if held && range.end != range.start {
+            let t = ((state.mouse.x - rect.left()) / rect.w()).max(0.0).min(1.0);
+            *value = range.start + (range.end - range.start) * t;
+        }
+        let t = ((*value - range.start) / (range.end - range.start))
+            .max(0.0)
+            .min(1.0);
+        state.draw_track(rect, t, held);
+        state.draw_label(rect, &format!("{}: {:.3}", label, *value));
+        *value != before
+    }
+
+    /// A checkbox toggling `value` when clicked.
+    ///
+    /// Returns `true` if the value changed.
+    pub fn checkbox(&self, label: &str, value: &mut bool) -> bool {
+        let mut state = self.state.borrow_mut();
+        let rect = state.next_row();
+        let clicked = state.clicked(label, rect);
+        if clicked {
+            *value = !*value;
+        }
+        let size = rect.h() * 0.6;
+        let box_rect = Rect::from_w_h(size, size).mid_left_of(rect);
+        let fill = match *value {
+            true => color::rgba(0.9, 0.9, 0.9, 1.0),
+            false => color::rgba(0.3, 0.3, 0.3, 1.0),
+        };
+        state
+            .draw
+            .rect()
+            .xy(box_rect.xy())
+            .wh(box_rect.wh())
+            .color(fill);
+        let label_rect = rect.pad_left(size + Self::PADDING);
+        state.draw_label(label_rect, label);
+        clicked
+    }
+
+    /// A swatch of the colour followed by a slider for each of its red, green and blue channels.
+    ///
+    /// Returns `true` if the colour changed.
+    pub fn color_picker(&self, label: &str, value: &mut Rgb) -> bool {
+        {
+            let mut state = self.state.borrow_mut();
+            let rect = state.next_row();
+            let swatch = Rect::from_w_h(rect.h() * 2.0, rect.h() * 0.8).mid_left_of(rect);
+            state
+                .draw
+                .rect()
+                .xy(swatch.xy())
+                .wh(swatch.wh())
+                .color(*value);
+            let label_rect = rect.pad_left(swatch.w() + Self::PADDING);
+            state.draw_label(label_rect, label);
+        }
+        let mut changed = false;
+        changed |= self.slider(&format!("{}.r", label), 0.0..1.0, &mut value.red);
+        changed |= self.slider(&format!("{}.g", label), 0.0..1.0, &mut value.green);
+        changed |= self.slider(&format!("{}.b", label), 0.0..1.0, &mut value.blue);
+        changed
+    }
+
+    /// A button calling `on_click` when clicked.
+    ///
+    /// Returns `true` if the button was clicked.
+    pub fn button<F>(&self, label: &str, on_click: F) -> bool
+    where
+        F: FnOnce(),
+    {
+        let clicked = {
+            let mut state = self.state.borrow_mut();
+            let rect = state.next_row();
+            let held = state.active.as_ref().map(|a| a == label).unwrap_or(false)
+                && rect.contains(state.mouse);
+            let clicked = state.clicked(label, rect);
+            let fill = match held {
+                true => color::rgba(0.5, 0.5, 0.5, 1.0),
+                false => color::rgba(0.3, 0.3, 0.3, 1.0),
+            };
+            state.draw.rect().xy(rect.xy()).wh(rect.wh()).color(fill);
+            state.draw_label(rect.pad_left(Self::PADDING), label);
+            clicked
+        };
+        if clicked {
+            on_click();
+        }
+        clicked
+    }
+
+    /// Render the panel's widgets to the frame.
+    ///
+    /// This should be called at the end of `view` so that the panel is drawn on top of the
+    /// frame's other graphics.
+    pub fn draw_to_frame(&self, app: &App, frame: &Frame) -> Result<(), draw::renderer::DrawError> {
+        self.state.borrow().draw.to_frame(app, frame)
+    }
+}
+
+impl State {
+    // The rect of the next widget row, advancing the cursor.
+    fn next_row(&mut self) -> Rect {
+        let w = self.rect.w() - Panel::PADDING * 2.0;
+        let h = self.row_height;
+        let x = self.rect.x();
+        let y = self.cursor_y - h * 0.5;
+        self.cursor_y -= h + Panel::PADDING * 0.5;
+        Rect::from_x_y_w_h(x, y, w, h)
+    }
+
+    // Activate the widget if it was pressed, returning whether or not it is currently held.
+    fn interact(&mut self, label: &str, rect: Rect) -> bool {
+        if self.pressed && rect.contains(self.mouse) {
+            self.active = Some(label.to_string());
+        }
+        self.mouse_down && self.active.as_ref().map(|a| a == label).unwrap_or(false)
+    }
+
+    // Whether or not the widget was clicked, i.e. pressed and released over the widget.
+    fn clicked(&mut self, label: &str, rect: Rect) -> bool {
+        self.interact(label, rect);
+        self.released
+            && rect.contains(self.mouse)
+            && self.active.as_ref().map(|a| a == label).unwrap_or(false)
+    }
+
+    // Draw a slider track filled to `t`.
+    fn draw_track(&self, rect: Rect, t: f32, held: bool) {
+        self.draw
+            .rect()
+            .xy(rect.xy())
+            .wh(rect.wh())
+            .color(color::rgba(0.3, 0.3, 0.3, 1.0));
+        let fill = Rect::from_w_h(rect.w() * t, rect.h()).mid_left_of(rect);
+        let alpha = if held { 1.0 } else { 0.8 };
+        self.draw
+            .rect()
+            .xy(fill.xy())
+            .wh(fill.wh())
+            .color(color::rgba(0.2, 0.5, 0.9, alpha));
+    }
+
+    // Draw a left-justified label within the given rect.
+    fn draw_label(&self, rect: Rect, text: &str) {
+        let rect = rect.pad_left(Panel::PADDING * 0.5);
+        self.draw
+            .text(text)
+            .xy(rect.xy())
+            .wh(rect.wh())
+            .font_size(12)
+            .left_justify()
+            .align_text_middle_y()
+            .color(color::WHITE);
+    }
+}
+
+impl Default for Panel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,4 +1,7 @@
 use crate::wgpu;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::{Arc, Mutex};
 
 /// A type aimed at simplifying the creation of a bind group layout.
 #[derive(Debug, Default)]
@@ -7,11 +10,34 @@ pub struct LayoutBuilder {
 }
 
 /// Simplified creation of a bind group.
+///
+/// Resources may be added either via the `binding` family of methods, in which case the binding
+/// positions are inferred by order and the layout must be provided to `build`, or via the `bind_*`
+/// family of methods, which take an explicit binding position and visibility. The latter allows
+/// for producing the layout alongside the bind group via `build_with_layout`:
+///
+/// ```ignore
+/// let (layout, bind_group) = wgpu::BindGroupBuilder::new()
+///     .bind_texture(0, &texture_view, wgpu::ShaderStage::FRAGMENT)
+///     .bind_sampler(1, &sampler, wgpu::ShaderStage::FRAGMENT)
+///     .build_with_layout(window.swap_chain_device_queue_pair());
+/// ```
 #[derive(Debug, Default)]
 pub struct Builder<'a> {
-    resources: Vec<wgpu::BindingResource<'a>>,
+    resources: Vec<(u32, wgpu::BindingResource<'a>)>,
+    // The layout of each resource added via the `bind_*` methods.
+    layout_entries: Vec<wgpu::BindGroupLayoutEntry>,
 }
 
+/// A cache of bind group layouts created via `BindGroupBuilder::build_with_layout`.
+#[derive(Debug, Default)]
+pub(crate) struct LayoutCache {
+    layouts: Mutex<HashMap<LayoutKey, Arc<wgpu::BindGroupLayout>>>,
+}
+
+// The binding, visibility and type of each layout entry, sorted by binding position.
+type LayoutKey = Vec<(u32, wgpu::ShaderStage, wgpu::BindingType)>;
+
 impl LayoutBuilder {
     /// Begin building the bind group layout.
     pub fn new() -> Self {
@@ -163,10 +189,18 @@ impl<'a> Builder<'a> {
     ///
     /// The `binding` position of each binding will be inferred as the index within the order that
     /// they are added to this builder type. If you require manually specifying the binding
-    /// location, you may be better off not using the `BindGroupBuilder` and instead constructing
-    /// the `BindGroupLayout` and `BindGroup` manually.
+    /// location, see the `bind_*` methods instead.
+    ///
+    /// When mixed with the `bind_*` methods, the resource is bound to the position following the
+    /// greatest position bound so far.
     pub fn binding(mut self, resource: wgpu::BindingResource<'a>) -> Self {
-        self.resources.push(resource);
+        let binding = self
+            .resources
+            .iter()
+            .map(|&(b, _)| b + 1)
+            .max()
+            .unwrap_or(0);
+        self.resources.push((binding, resource));
         self
     }
 
@@ -211,14 +245,135 @@ impl<'a> Builder<'a> {
         self.binding(resource)
     }
 
+    /// Bind a full view of a texture to the given binding position.
+    pub fn bind_texture(
+        self,
+        binding: u32,
+        view: &'a wgpu::TextureView,
+        visibility: wgpu::ShaderStage,
+    ) -> Self {
+        let ty = wgpu::BindingType::SampledTexture {
+            multisampled: false,
+            dimension: view.dimension(),
+            component_type: view.component_type(),
+        };
+        let resource = wgpu::BindingResource::TextureView(view);
+        self.bind(binding, visibility, ty, resource)
+    }
+
+    /// Bind a texture view as a storage texture to the given binding position.
+    pub fn bind_storage_texture(
+        self,
+        binding: u32,
+        view: &'a wgpu::TextureView,
+        readonly: bool,
+        visibility: wgpu::ShaderStage,
+    ) -> Self {
+        let ty = wgpu::BindingType::StorageTexture {
+            dimension: view.dimension(),
+            component_type: view.component_type(),
+            format: view.format(),
+            readonly,
+        };
+        let resource = wgpu::BindingResource::TextureView(view);
+        self.bind(binding, visibility, ty, resource)
+    }
+
+    /// Bind a sampler to the given binding position.
+    pub fn bind_sampler(
+        self,
+        binding: u32,
+        sampler: &'a wgpu::Sampler,
+        visibility: wgpu::ShaderStage,
+    ) -> Self {
+        let ty = wgpu::BindingType::Sampler { comparison: false };
+        let resource = wgpu::BindingResource::Sampler(sampler);
+        self.bind(binding, visibility, ty, resource)
+    }
+
+    /// Bind the given byte range of a uniform buffer to the given binding position.
+    pub fn bind_uniform_buffer(
+        self,
+        binding: u32,
+        buffer: &'a wgpu::Buffer,
+        range: Range<wgpu::BufferAddress>,
+        visibility: wgpu::ShaderStage,
+    ) -> Self {
+        let ty = wgpu::BindingType::UniformBuffer { dynamic: false };
+        let resource = wgpu::BindingResource::Buffer { buffer, range };
+        self.bind(binding, visibility, ty, resource)
+    }
+
+    /// Bind the given byte range of a storage buffer to the given binding position.
+    pub fn bind_storage_buffer(
+        self,
+        binding: u32,
+        buffer: &'a wgpu::Buffer,
+        range: Range<wgpu::BufferAddress>,
+        readonly: bool,
+        visibility: wgpu::ShaderStage,
+    ) -> Self {
+        let ty = wgpu::BindingType::StorageBuffer {
+            dynamic: false,
+            readonly,
+        };
+        let resource = wgpu::BindingResource::Buffer { buffer, range };
+        self.bind(binding, visibility, ty, resource)
+    }
+
+    /// The layout entries for all resources added via the `bind_*` methods, sorted by binding
+    /// position.
+    pub fn layout_entries(&self) -> Vec<wgpu::BindGroupLayoutEntry> {
+        let mut entries = self.layout_entries.clone();
+        entries.sort_by_key(|entry| entry.binding);
+        entries
+    }
+
+    /// Build the bind group along with its layout.
+    ///
+    /// Layouts are cached on the **DeviceQueuePair** keyed by the set of layout entries, so
+    /// repeated calls for bind groups of the same shape share a single layout.
+    ///
+    /// **Panics** if any resource was added via the `binding` family of methods rather than the
+    /// `bind_*` methods, as the layout of such resources is unknown.
+    pub fn build_with_layout(
+        self,
+        device_queue_pair: &wgpu::DeviceQueuePair,
+    ) -> (Arc<wgpu::BindGroupLayout>, wgpu::BindGroup) {
+        assert_eq!(
+            self.resources.len(),
+            self.layout_entries.len(),
+            "`build_with_layout` requires that all resources are added via the `bind_*` methods"
+        );
+        let entries = self.layout_entries();
+        let key: LayoutKey = entries
+            .iter()
+            .map(|entry| (entry.binding, entry.visibility, entry.ty.clone()))
+            .collect();
+        let device = device_queue_pair.device();
+        let layout = device_queue_pair
+            .bind_group_layout_cache()
+            .layouts
+            .lock()
+            .expect("failed to acquire bind group layout cache lock")
+            .entry(key)
+            .or_insert_with(|| {
+                let descriptor = wgpu::BindGroupLayoutDescriptor {
+                    label: Some("nannou"),
+                    bindings: &entries,
+                };
+                Arc::new(device.create_bind_group_layout(&descriptor))
+            })
+            .clone();
+        let bind_group = self.build(device, &layout);
+        (layout, bind_group)
+    }
+
     /// Build the bind group with the specified resources.
     pub fn build(self, device: &wgpu::Device, layout: &wgpu::BindGroupLayout) -> wgpu::BindGroup {
         let mut bindings = Vec::with_capacity(self.resources.len());
-        for (i, resource) in self.resources.into_iter().enumerate() {
-            let binding = wgpu::Binding {
-                binding: i as u32,
-                resource,
-            };
+        for (binding, resource) in self.resources {
+            let binding = wgpu::Binding { binding, resource };
             bindings.push(binding);
         }
         let descriptor = wgpu::BindGroupDescriptor {
@@ -228,4 +383,23 @@ impl<'a> Builder<'a> {
         };
         device.create_bind_group(&descriptor)
     }
+
+    // Add the resource along with its layout, replacing any existing resource at the binding.
+    fn bind(
+        mut self,
+        binding: u32,
+        visibility: wgpu::ShaderStage,
+        ty: wgpu::BindingType,
+        resource: wgpu::BindingResource<'a>,
+    ) -> Self {
+        self.resources.retain(|&(b, _)| b != binding);
+        self.layout_entries.retain(|entry| entry.binding != binding);
+        self.resources.push((binding, resource));
+        self.layout_entries.push(wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility,
+            ty,
+        });
+        self
+    }
 }
@@ -35,3 +35,21 @@ pub const LIGHTEST: wgpu::BlendDescriptor = wgpu::BlendDescriptor {
     dst_factor: wgpu::BlendFactor::DstColor,
     operation: wgpu::BlendOperation::Max,
 };
+
+pub const MULTIPLY: wgpu::BlendDescriptor = wgpu::BlendDescriptor {
+    src_factor: wgpu::BlendFactor::DstColor,
+    dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+    operation: wgpu::BlendOperation::Add,
+};
+
+pub const SCREEN: wgpu::BlendDescriptor = wgpu::BlendDescriptor {
+    src_factor: wgpu::BlendFactor::One,
+    dst_factor: wgpu::BlendFactor::OneMinusSrcColor,
+    operation: wgpu::BlendOperation::Add,
+};
+
+pub const ADDITIVE: wgpu::BlendDescriptor = wgpu::BlendDescriptor {
+    src_factor: wgpu::BlendFactor::SrcAlpha,
+    dst_factor: wgpu::BlendFactor::One,
+    operation: wgpu::BlendOperation::Add,
+};
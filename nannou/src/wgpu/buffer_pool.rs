@@ -0,0 +1,349 @@
+use crate::wgpu;
+use futures::FutureExt;
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::ops::Deref;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+/// A pool of reusable buffers, e.g. for uniform uploads or CPU to GPU streaming.
+///
+/// Buffers are requested by size and usage via `get` and returned to the pool when the resulting
+/// **PooledBuffer** is dropped. Requested sizes are rounded up to the next power of two so that
+/// buffers of similar sizes may be shared.
+///
+/// Data may be written to pooled buffers via `upload`, which copies the data through a staging
+/// buffer that is also provided by the pool. Once the frame in which a staging buffer was used
+/// has been submitted, the staging buffer is mapped for writing again so that it may be re-used
+/// without being recreated.
+///
+/// Buffers that have not been requested for more than `max_idle_frames` frames are freed. Nannou
+/// advances the pool's frame via `end_frame` at the end of each application loop, after all
+/// frames have been submitted.
+///
+/// Every **DeviceQueuePair** owns a pool. All buffers requested from a pool must be created on
+/// the device that owns it.
+#[derive(Clone, Debug, Default)]
+pub struct BufferPool {
+    state: Arc<Mutex<State>>,
+}
+
+/// A buffer borrowed from a **BufferPool**.
+///
+/// The buffer is returned to the pool on `Drop`.
+#[derive(Debug)]
+pub struct PooledBuffer {
+    buffer: Option<wgpu::Buffer>,
+    key: Key,
+    size: wgpu::BufferAddress,
+    state: Arc<Mutex<State>>,
+}
+
+// Buffers are binned by usage and capacity.
+type Key = (wgpu::BufferUsage, wgpu::BufferAddress);
+
+#[derive(Debug)]
+struct State {
+    frame: u64,
+    max_idle_frames: u64,
+    bins: HashMap<Key, Vec<IdleBuffer>>,
+    staging: Staging,
+}
+
+#[derive(Debug)]
+struct IdleBuffer {
+    buffer: wgpu::Buffer,
+    last_used: u64,
+}
+
+// Staging buffers binned by capacity, moving from `ready` to `in_flight` to `mapping` and back.
+#[derive(Default)]
+struct Staging {
+    // Staging buffers that are mapped and ready to be written to.
+    ready: HashMap<wgpu::BufferAddress, Vec<MappedStaging>>,
+    // Staging buffers that were copied from during the current frame.
+    in_flight: Vec<(wgpu::BufferAddress, wgpu::Buffer)>,
+    // Staging buffers for which a mapping was requested once their frame was submitted.
+    mapping: Vec<PendingStaging>,
+}
+
+struct MappedStaging {
+    buffer: wgpu::Buffer,
+    mapping: wgpu::BufferWriteMapping,
+    last_used: u64,
+}
+
+struct PendingStaging {
+    capacity: wgpu::BufferAddress,
+    buffer: wgpu::Buffer,
+    mapping: Pin<Box<dyn Future<Output = MapResult> + Send>>,
+}
+
+type MapResult = Result<wgpu::BufferWriteMapping, wgpu::BufferAsyncErr>;
+
+impl BufferPool {
+    /// The default number of frames that a buffer may go unused before it is freed.
+    pub const DEFAULT_MAX_IDLE_FRAMES: u64 = 3;
+
+    /// The smallest capacity of a buffer produced by the pool.
+    pub const MIN_CAPACITY: wgpu::BufferAddress = 256;
+
+    /// Request a buffer of at least the given size with the given usage.
+    ///
+    /// A buffer of the same usage and capacity is reused if one is available. Otherwise, a new
+    /// buffer is created on the given `device`.
+    pub fn get(
+        &self,
+        device: &wgpu::Device,
+        size: wgpu::BufferAddress,
+        usage: wgpu::BufferUsage,
+    ) -> PooledBuffer {
+        let capacity = Self::capacity(size);
+        let key = (usage, capacity);
+        let idle = self
+            .lock()
+            .bins
+            .get_mut(&key)
+            .and_then(|bin| bin.pop())
+            .map(|idle| idle.buffer);
+        let buffer = idle.unwrap_or_else(|| {
+            device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("nannou_buffer_pool"),
+                size: capacity,
+                usage,
+            })
+        });
+        PooledBuffer {
+            buffer: Some(buffer),
+            key,
+            size,
+            state: self.state.clone(),
+        }
+    }
+
+    /// Request a buffer of at least the size of `data` with the given usage and encode a copy of
+    /// `data` into it.
+    ///
+    /// `COPY_DST` is added to the given usage. The data is first written to a staging buffer
+    /// provided by the pool, which becomes available again once the current frame has ended.
+    pub fn upload(
+        &self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        data: &[u8],
+        usage: wgpu::BufferUsage,
+    ) -> PooledBuffer {
+        let size = data.len() as wgpu::BufferAddress;
+        let buffer = self.get(device, size, usage | wgpu::BufferUsage::COPY_DST);
+        if size > 0 {
+            let (capacity, staging) = self.staging_buffer(device, data);
+            encoder.copy_buffer_to_buffer(&staging, 0, &buffer, 0, size);
+            self.lock().staging.in_flight.push((capacity, staging));
+        }
+        buffer
+    }
+
+    /// The capacity of the buffer produced by the pool for a request of the given size.
+    ///
+    /// This is the size rounded up to the next power of two, and at least `MIN_CAPACITY`.
+    pub fn capacity(size: wgpu::BufferAddress) -> wgpu::BufferAddress {
+        size.max(Self::MIN_CAPACITY).next_power_of_two()
+    }
+
+    /// The number of frames that a buffer may go unused before it is freed.
+    pub fn max_idle_frames(&self) -> u64 {
+        self.lock().max_idle_frames
+    }
+
+    /// Specify the number of frames that a buffer may go unused before it is freed.
+    ///
+    /// By default, this is `DEFAULT_MAX_IDLE_FRAMES`.
+    pub fn set_max_idle_frames(&self, frames: u64) {
+        self.lock().max_idle_frames = frames;
+    }
+
+    /// The number of buffers that are currently idle within the pool.
+    ///
+    /// This includes staging buffers that are mapped and ready to be written to.
+    pub fn idle_count(&self) -> usize {
+        let state = self.lock();
+        let buffers: usize = state.bins.values().map(|bin| bin.len()).sum();
+        let staging: usize = state.staging.ready.values().map(|bin| bin.len()).sum();
+        buffers + staging
+    }
+
+    /// Free all idle buffers.
+    pub fn clear(&self) {
+        let mut state = self.lock();
+        state.bins.clear();
+        state.staging.ready.clear();
+    }
+
+    /// Advance the pool's frame, freeing all buffers that have been idle for too long.
+    ///
+    /// Staging buffers used during the frame are mapped for writing again, so this must only be
+    /// called once the commands encoded during the frame have been submitted. Nannou calls this
+    /// at the end of each application loop for the pool of every **DeviceQueuePair**.
+    pub fn end_frame(&self) {
+        let mut state = self.lock();
+        state.frame += 1;
+        let State {
+            frame,
+            max_idle_frames,
+            ref mut bins,
+            ref mut staging,
+        } = *state;
+        bins.retain(|_, bin| {
+            bin.retain(|idle| frame - idle.last_used <= max_idle_frames);
+            !bin.is_empty()
+        });
+
+        // Collect the staging buffers whose mappings have completed.
+        let pending = std::mem::replace(&mut staging.mapping, vec![]);
+        for mut pending in pending {
+            match (&mut pending.mapping).now_or_never() {
+                None => staging.mapping.push(pending),
+                Some(Ok(mapping)) => {
+                    let buffer = pending.buffer;
+                    let last_used = frame;
+                    let mapped = MappedStaging {
+                        buffer,
+                        mapping,
+                        last_used,
+                    };
+                    let bin = staging
+                        .ready
+                        .entry(pending.capacity)
+                        .or_insert_with(Vec::new);
+                    bin.push(mapped);
+                }
+                // The buffer could not be mapped, so free it.
+                Some(Err(_)) => (),
+            }
+        }
+
+        // The frame has been submitted, so the staging buffers it used may be mapped again.
+        for (capacity, buffer) in staging.in_flight.drain(..) {
+            let mapping = Box::pin(buffer.map_write(0, capacity));
+            let pending = PendingStaging {
+                capacity,
+                buffer,
+                mapping,
+            };
+            staging.mapping.push(pending);
+        }
+
+        staging.ready.retain(|_, bin| {
+            bin.retain(|mapped| frame - mapped.last_used <= max_idle_frames);
+            !bin.is_empty()
+        });
+    }
+
+    // Write the data to a mapped staging buffer, creating one if none are ready.
+    fn staging_buffer(
+        &self,
+        device: &wgpu::Device,
+        data: &[u8],
+    ) -> (wgpu::BufferAddress, wgpu::Buffer) {
+        let capacity = Self::capacity(data.len() as wgpu::BufferAddress);
+        let ready = self
+            .lock()
+            .staging
+            .ready
+            .get_mut(&capacity)
+            .and_then(|bin| bin.pop());
+        let buffer = match ready {
+            Some(MappedStaging {
+                buffer,
+                mut mapping,
+                ..
+            }) => {
+                mapping.as_slice()[..data.len()].copy_from_slice(data);
+                // Dropping the mapping unmaps the buffer, allowing it to be copied from.
+                drop(mapping);
+                buffer
+            }
+            None => {
+                let mapped = device.create_buffer_mapped(&wgpu::BufferDescriptor {
+                    label: Some("nannou_buffer_pool_staging"),
+                    size: capacity,
+                    usage: wgpu::BufferUsage::MAP_WRITE | wgpu::BufferUsage::COPY_SRC,
+                });
+                mapped.data[..data.len()].copy_from_slice(data);
+                mapped.finish()
+            }
+        };
+        (capacity, buffer)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<State> {
+        self.state
+            .lock()
+            .expect("failed to acquire `BufferPool` lock")
+    }
+}
+
+impl PooledBuffer {
+    /// The size that was requested for the buffer.
+    pub fn size(&self) -> wgpu::BufferAddress {
+        self.size
+    }
+
+    /// The actual size of the buffer, i.e. the requested size rounded up to a power of two.
+    pub fn capacity(&self) -> wgpu::BufferAddress {
+        self.key.1
+    }
+
+    /// The usage with which the buffer was created.
+    pub fn usage(&self) -> wgpu::BufferUsage {
+        self.key.0
+    }
+}
+
+impl Default for State {
+    fn default() -> Self {
+        State {
+            frame: 0,
+            max_idle_frames: BufferPool::DEFAULT_MAX_IDLE_FRAMES,
+            bins: Default::default(),
+            staging: Default::default(),
+        }
+    }
+}
+
+impl fmt::Debug for Staging {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ready: usize = self.ready.values().map(|bin| bin.len()).sum();
+        f.debug_struct("Staging")
+            .field("ready", &ready)
+            .field("in_flight", &self.in_flight.len())
+            .field("mapping", &self.mapping.len())
+            .finish()
+    }
+}
+
+impl Deref for PooledBuffer {
+    type Target = wgpu::Buffer;
+    fn deref(&self) -> &Self::Target {
+        self.buffer
+            .as_ref()
+            .expect("`PooledBuffer` buffer was `None`")
+    }
+}
+
+impl Drop for PooledBuffer {
+    fn drop(&mut self) {
+        if let Some(buffer) = self.buffer.take() {
+            if let Ok(mut state) = self.state.lock() {
+                let last_used = state.frame;
+                let idle = IdleBuffer { buffer, last_used };
+                state
+                    .bins
+                    .entry(self.key)
+                    .or_insert_with(Vec::new)
+                    .push(idle);
+            }
+        }
+    }
+}
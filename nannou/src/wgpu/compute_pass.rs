@@ -0,0 +1,284 @@
+use crate::wgpu;
+use std::cell::RefMut;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+use std::sync::{Arc, Mutex};
+
+/// A builder type to simplify the process of dispatching a compute shader.
+///
+/// The builder creates the bind group layout, pipeline layout and compute pipeline from the given
+/// shader and the types of the resources that are bound to it. Pipelines are cached on the
+/// **DeviceQueuePair** keyed by a hash of the shader and its bindings, so building the same
+/// pass every frame only creates the pipeline once.
+///
+/// All resources are bound to bind group `0` at the given `slot`, visible to the compute stage
+/// only. The shader's entry point must be named `main`.
+///
+/// Note that wgpu 0.5 only accepts SPIR-V shader modules. Shaders must be compiled ahead of time,
+/// e.g. from GLSL via `glslangValidator -V shader.comp -o comp.spv`.
+///
+/// ```ignore
+/// frame
+///     .begin_compute_pass()
+///     .pipeline_from_spirv(include_bytes!("shaders/comp.spv"))
+///     .bind_buffer(0, &particle_buffer, 0..particle_buffer_size)
+///     .bind_uniform_buffer(1, &uniform_buffer, 0..uniforms_size)
+///     .dispatch(workgroup_count, 1, 1);
+/// ```
+pub struct ComputePassBuilder<'a> {
+    device_queue_pair: &'a wgpu::DeviceQueuePair,
+    encoder: RefMut<'a, wgpu::CommandEncoder>,
+    shader: Option<(u64, &'a [u8])>,
+    bindings: Vec<(u32, Resource<'a>)>,
+}
+
+/// A cache of compute pipelines created via the **ComputePassBuilder**.
+#[derive(Debug, Default)]
+pub(crate) struct PipelineCache {
+    pipelines: Mutex<HashMap<u64, Arc<CachedPipeline>>>,
+}
+
+#[derive(Debug)]
+struct CachedPipeline {
+    bind_group_layout: wgpu::BindGroupLayout,
+    pipeline: wgpu::ComputePipeline,
+}
+
+// A resource bound to the pass along with the binding type it is exposed to the shader as.
+enum Resource<'a> {
+    Buffer {
+        buffer: &'a wgpu::Buffer,
+        range: Range<wgpu::BufferAddress>,
+        uniform: bool,
+    },
+    Texture {
+        view: &'a wgpu::TextureView,
+        storage: Option<bool>,
+    },
+}
+
+impl<'a> ComputePassBuilder<'a> {
+    pub(crate) fn new(
+        device_queue_pair: &'a wgpu::DeviceQueuePair,
+        encoder: RefMut<'a, wgpu::CommandEncoder>,
+    ) -> Self {
+        ComputePassBuilder {
+            device_queue_pair,
+            encoder,
+            shader: None,
+            bindings: vec![],
+        }
+    }
+
+    /// Specify the compute shader to dispatch as SPIR-V bytes.
+    ///
+    /// The shader is only loaded in the case that no pipeline for it exists within the cache.
+    pub fn pipeline_from_spirv(&mut self, bytes: &'a [u8]) -> &mut Self {
+        let mut hasher = DefaultHasher::new();
+        bytes.hash(&mut hasher);
+        self.shader = Some((hasher.finish(), bytes));
+        self
+    }
+
+    /// Bind the given range of a buffer as a read-write storage buffer at the given slot.
+    ///
+    /// The buffer must have been created with `BufferUsage::STORAGE`.
+    pub fn bind_buffer(
+        &mut self,
+        slot: u32,
+        buffer: &'a wgpu::Buffer,
+        range: Range<wgpu::BufferAddress>,
+    ) -> &mut Self {
+        let uniform = false;
+        self.bind(
+            slot,
+            Resource::Buffer {
+                buffer,
+                range,
+                uniform,
+            },
+        )
+    }
+
+    /// Bind the given range of a buffer as a uniform buffer at the given slot.
+    ///
+    /// The buffer must have been created with `BufferUsage::UNIFORM`.
+    pub fn bind_uniform_buffer(
+        &mut self,
+        slot: u32,
+        buffer: &'a wgpu::Buffer,
+        range: Range<wgpu::BufferAddress>,
+    ) -> &mut Self {
+        let uniform = true;
+        self.bind(
+            slot,
+            Resource::Buffer {
+                buffer,
+                range,
+                uniform,
+            },
+        )
+    }
+
+    /// Bind the given texture view as a sampled texture at the given slot.
+    ///
+    /// The texture must have been created with `TextureUsage::SAMPLED`.
+    pub fn bind_texture(&mut self, slot: u32, view: &'a wgpu::TextureView) -> &mut Self {
+        let storage = None;
+        self.bind(slot, Resource::Texture { view, storage })
+    }
+
+    /// Bind the given texture view as a storage texture at the given slot, allowing the shader to
+    /// write to it unless `readonly` is `true`.
+    ///
+    /// The texture must have been created with `TextureUsage::STORAGE`.
+    pub fn bind_storage_texture(
+        &mut self,
+        slot: u32,
+        view: &'a wgpu::TextureView,
+        readonly: bool,
+    ) -> &mut Self {
+        let storage = Some(readonly);
+        self.bind(slot, Resource::Texture { view, storage })
+    }
+
+    /// Encode a compute pass that dispatches the shader with the given number of work groups in
+    /// each dimension.
+    ///
+    /// The builder's shader and bindings are retained, so `dispatch` may be called again, e.g.
+    /// for multiple simulation steps per frame.
+    ///
+    /// **Panics** if no shader was specified via `pipeline_from_spirv`.
+    pub fn dispatch(&mut self, x: u32, y: u32, z: u32) {
+        let (shader_hash, shader_bytes) = self
+            .shader
+            .expect("no shader was specified for the compute pass");
+        let device = self.device_queue_pair.device();
+        let layout_entries: Vec<_> = self
+            .bindings
+            .iter()
+            .map(|(slot, resource)| wgpu::BindGroupLayoutEntry {
+                binding: *slot,
+                visibility: wgpu::ShaderStage::COMPUTE,
+                ty: resource.binding_type(),
+            })
+            .collect();
+
+        // Retrieve the pipeline for this shader and set of bindings or create it.
+        let key = {
+            let mut hasher = DefaultHasher::new();
+            shader_hash.hash(&mut hasher);
+            for entry in &layout_entries {
+                entry.binding.hash(&mut hasher);
+                entry.ty.hash(&mut hasher);
+            }
+            hasher.finish()
+        };
+        let cache = self.device_queue_pair.compute_pipeline_cache();
+        let cached = cache
+            .pipelines
+            .lock()
+            .expect("failed to acquire compute pipeline cache lock")
+            .entry(key)
+            .or_insert_with(|| Arc::new(CachedPipeline::new(device, shader_bytes, &layout_entries)))
+            .clone();
+
+        let bindings: Vec<_> = self
+            .bindings
+            .iter()
+            .map(|(slot, resource)| wgpu::Binding {
+                binding: *slot,
+                resource: resource.binding_resource(),
+            })
+            .collect();
+        let descriptor = wgpu::BindGroupDescriptor {
+            label: Some("nannou_compute_pass"),
+            layout: &cached.bind_group_layout,
+            bindings: &bindings,
+        };
+        let bind_group = device.create_bind_group(&descriptor);
+
+        let mut pass = self.encoder.begin_compute_pass();
+        pass.set_pipeline(&cached.pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.dispatch(x, y, z);
+    }
+
+    // Add the resource, replacing any existing resource at the same slot.
+    fn bind(&mut self, slot: u32, resource: Resource<'a>) -> &mut Self {
+        self.bindings.retain(|&(s, _)| s != slot);
+        self.bindings.push((slot, resource));
+        self.bindings.sort_by_key(|&(s, _)| s);
+        self
+    }
+}
+
+impl CachedPipeline {
+    fn new(device: &wgpu::Device, shader: &[u8], entries: &[wgpu::BindGroupLayoutEntry]) -> Self {
+        let module = wgpu::shader_from_spirv_bytes(device, shader);
+        let descriptor = wgpu::BindGroupLayoutDescriptor {
+            label: Some("nannou_compute_pass"),
+            bindings: entries,
+        };
+        let bind_group_layout = device.create_bind_group_layout(&descriptor);
+        let pipeline_layout = wgpu::create_pipeline_layout(device, &[&bind_group_layout]);
+        let compute_stage = wgpu::ProgrammableStageDescriptor {
+            module: &module,
+            entry_point: "main",
+        };
+        let descriptor = wgpu::ComputePipelineDescriptor {
+            layout: &pipeline_layout,
+            compute_stage,
+        };
+        let pipeline = device.create_compute_pipeline(&descriptor);
+        CachedPipeline {
+            bind_group_layout,
+            pipeline,
+        }
+    }
+}
+
+impl<'a> Resource<'a> {
+    fn binding_type(&self) -> wgpu::BindingType {
+        match *self {
+            Resource::Buffer { uniform: true, .. } => {
+                wgpu::BindingType::UniformBuffer { dynamic: false }
+            }
+            Resource::Buffer { uniform: false, .. } => wgpu::BindingType::StorageBuffer {
+                dynamic: false,
+                readonly: false,
+            },
+            Resource::Texture {
+                view,
+                storage: None,
+            } => wgpu::BindingType::SampledTexture {
+                multisampled: false,
+                dimension: view.dimension(),
+                component_type: view.component_type(),
+            },
+            Resource::Texture {
+                view,
+                storage: Some(readonly),
+            } => wgpu::BindingType::StorageTexture {
+                dimension: view.dimension(),
+                component_type: view.component_type(),
+                format: view.format(),
+                readonly,
+            },
+        }
+    }
+
+    fn binding_resource(&self) -> wgpu::BindingResource {
+        match *self {
+            Resource::Buffer {
+                buffer, ref range, ..
+            } => wgpu::BindingResource::Buffer {
+                buffer,
+                range: range.clone(),
+            },
+            Resource::Texture { view, .. } => wgpu::BindingResource::TextureView(view),
+        }
+    }
+}
@@ -58,6 +58,9 @@ pub struct DeviceMapKey {
 pub struct DeviceQueuePair {
     device: wgpu::Device,
     queue: wgpu::Queue,
+    buffer_pool: super::BufferPool,
+    compute_pipeline_cache: super::compute_pass::PipelineCache,
+    bind_group_layout_cache: super::bind_group_builder::LayoutCache,
 }
 
 impl AdapterMap {
@@ -144,6 +147,15 @@ impl AdapterMap {
         Some(adapter)
     }
 
+    /// A handle to each of the currently active adapters.
+    pub fn adapters(&self) -> Vec<Arc<ActiveAdapter>> {
+        let map = self
+            .map
+            .lock()
+            .expect("failed to acquire `AdapterMap` lock");
+        map.values().cloned().collect()
+    }
+
     /// Clear all adapters that currently have no connected devices.
     ///
     /// First clears all devices that no longer have any external references.
@@ -158,6 +170,17 @@ impl AdapterMap {
         });
     }
 
+    /// Advance the frame of each active device's buffer pool, freeing idle buffers.
+    pub(crate) fn end_frame_buffer_pools(&self) {
+        let map = self
+            .map
+            .lock()
+            .expect("failed to acquire `AdapterMap` lock");
+        for adapter in map.values() {
+            adapter.end_frame_buffer_pools();
+        }
+    }
+
     /// Poll all devices within all active adapters.
     pub(crate) fn _poll_all_devices(&self, maintain: wgpu::Maintain) {
         let map = self
@@ -211,7 +234,7 @@ impl ActiveAdapter {
             }
         }
         let (device, queue) = self.adapter.request_device(&key.descriptor).await;
-        let device = Arc::new(DeviceQueuePair { device, queue });
+        let device = Arc::new(DeviceQueuePair::new(device, queue));
         map.insert(key, Arc::downgrade(&device));
         device
     }
@@ -226,7 +249,7 @@ impl ActiveAdapter {
         descriptor: wgpu::DeviceDescriptor,
     ) -> Arc<DeviceQueuePair> {
         let (device, queue) = self.adapter.request_device(&descriptor).await;
-        let device = Arc::new(DeviceQueuePair { device, queue });
+        let device = Arc::new(DeviceQueuePair::new(device, queue));
         let key = DeviceMapKey { descriptor };
         let mut map = self
             .device_map
@@ -237,6 +260,24 @@ impl ActiveAdapter {
         device
     }
 
+    /// Information about the physical device represented by the adapter.
+    pub fn info(&self) -> wgpu::AdapterInfo {
+        self.adapter.get_info()
+    }
+
+    /// The descriptors of all logical devices that are currently connected to the adapter.
+    pub fn device_descriptors(&self) -> Vec<wgpu::DeviceDescriptor> {
+        let map = self
+            .device_map
+            .map
+            .lock()
+            .expect("failed to acquire `DeviceMap` lock");
+        map.iter()
+            .filter(|(_, pair)| pair.upgrade().is_some())
+            .map(|(key, _)| key.descriptor.clone())
+            .collect()
+    }
+
     /// A count of devices that are currently active.
     fn device_count(&self) -> usize {
         let map = self
@@ -257,6 +298,20 @@ impl ActiveAdapter {
         map.retain(|_, pair| pair.upgrade().is_some());
     }
 
+    /// Advance the frame of the buffer pool of each active device within the map.
+    fn end_frame_buffer_pools(&self) {
+        let map = self
+            .device_map
+            .map
+            .lock()
+            .expect("failed to acquire `DeviceMap` lock");
+        for weak in map.values() {
+            if let Some(pair) = weak.upgrade() {
+                pair.buffer_pool().end_frame();
+            }
+        }
+    }
+
     /// Poll all of the active devices within the map.
     fn _poll_all_devices(&self, maintain: wgpu::Maintain) {
         let map = self
@@ -273,6 +328,19 @@ impl ActiveAdapter {
 }
 
 impl DeviceQueuePair {
+    fn new(device: wgpu::Device, queue: wgpu::Queue) -> Self {
+        let buffer_pool = Default::default();
+        let compute_pipeline_cache = Default::default();
+        let bind_group_layout_cache = Default::default();
+        DeviceQueuePair {
+            device,
+            queue,
+            buffer_pool,
+            compute_pipeline_cache,
+            bind_group_layout_cache,
+        }
+    }
+
     /// A reference to the inner `wgpu::Device`.
     pub fn device(&self) -> &wgpu::Device {
         &self.device
@@ -285,6 +353,21 @@ impl DeviceQueuePair {
     pub fn queue(&self) -> &wgpu::Queue {
         &self.queue
     }
+
+    /// The pool of reusable buffers associated with the device.
+    pub fn buffer_pool(&self) -> &super::BufferPool {
+        &self.buffer_pool
+    }
+
+    // The cache of pipelines created via the `ComputePassBuilder`.
+    pub(crate) fn compute_pipeline_cache(&self) -> &super::compute_pass::PipelineCache {
+        &self.compute_pipeline_cache
+    }
+
+    // The cache of layouts created via `BindGroupBuilder::build_with_layout`.
+    pub(crate) fn bind_group_layout_cache(&self) -> &super::bind_group_builder::LayoutCache {
+        &self.bind_group_layout_cache
+    }
 }
 
 impl Hash for DeviceMapKey {
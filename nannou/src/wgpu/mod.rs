@@ -17,11 +17,16 @@
 
 mod bind_group_builder;
 pub mod blend;
+mod buffer_pool;
+mod compute_pass;
 mod device_map;
 mod render_pass;
 mod render_pipeline_builder;
 mod sampler_builder;
+#[cfg(feature = "hotglsl")]
+mod shader_watch;
 mod texture;
+mod uniform_buffer;
 
 // Re-export all of `wgpu` along with its documentation.
 //
@@ -30,6 +35,8 @@ mod texture;
 pub use self::bind_group_builder::{
     Builder as BindGroupBuilder, LayoutBuilder as BindGroupLayoutBuilder,
 };
+pub use self::buffer_pool::{BufferPool, PooledBuffer};
+pub use self::compute_pass::ComputePassBuilder;
 pub use self::device_map::{
     ActiveAdapter, AdapterMap, AdapterMapKey, DeviceMap, DeviceMapKey, DeviceQueuePair,
 };
@@ -39,6 +46,8 @@ pub use self::render_pass::{
 };
 pub use self::render_pipeline_builder::RenderPipelineBuilder;
 pub use self::sampler_builder::SamplerBuilder;
+#[cfg(feature = "hotglsl")]
+pub use self::shader_watch::ShaderHandle;
 pub use self::texture::capturer::{
     AwaitWorkerTimeout as TextureCapturerAwaitWorkerTimeout, Capturer as TextureCapturer,
     Rgba8ReadMapping, Snapshot as TextureSnapshot,
@@ -51,9 +60,13 @@ pub use self::texture::reshaper::Reshaper as TextureReshaper;
 pub use self::texture::{
     descriptor_eq as texture_descriptor_eq, extent_3d_eq,
     format_size_bytes as texture_format_size_bytes,
-    format_to_component_type as texture_format_to_component_type, BufferBytes,
-    Builder as TextureBuilder, Texture, TextureId, TextureView, TextureViewId, ToTextureView,
+    format_to_component_type as texture_format_to_component_type,
+    max_mip_level_count as texture_max_mip_level_count,
+    padded_bytes_per_row as texture_padded_bytes_per_row, BufferBytes, Builder as TextureBuilder,
+    Texel, Texture, TextureId, TextureView, TextureViewId, ToTextureView,
+    COPY_BYTES_PER_ROW_ALIGNMENT,
 };
+pub use self::uniform_buffer::{Uniform, UniformBuffer};
 #[doc(inline)]
 pub use wgpu::{
     read_spirv, vertex_attr_array, vertex_format_size, Adapter, AdapterInfo, AddressMode, Backend,
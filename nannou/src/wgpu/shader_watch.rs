@@ -0,0 +1,156 @@
+use crate::wgpu;
+use std::cell::{Ref, RefCell};
+use std::path::{Path, PathBuf};
+
+/// A handle to a GLSL shader file that is recompiled each time the file changes.
+///
+/// The shader is compiled to SPIR-V via `hotglsl`. The shader stage is inferred from the file
+/// extension, e.g. `.vert`, `.frag` or `.comp`.
+///
+/// Compilation errors are printed to stderr rather than causing a panic. In the case that
+/// compilation fails, the last successfully compiled module remains available via `module`.
+///
+/// Use `version` to determine when pipelines created from an earlier module require rebuilding.
+pub struct ShaderHandle {
+    path: PathBuf,
+    watch: Option<hotglsl::Watch>,
+    state: RefCell<State>,
+}
+
+struct State {
+    // The latest successfully compiled SPIR-V along with its version.
+    spirv: Option<(u64, Vec<u8>)>,
+    // The module created from the SPIR-V along with the version from which it was created.
+    module: Option<(u64, wgpu::ShaderModule)>,
+    error: Option<String>,
+}
+
+impl ShaderHandle {
+    /// Compile the GLSL shader at the given path and begin watching it for changes.
+    pub fn new<P>(path: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let watch_dir = path.parent().unwrap_or(path.as_path());
+        let watch = match hotglsl::watch(watch_dir) {
+            Ok(watch) => Some(watch),
+            Err(err) => {
+                eprintln!("failed to watch shader \"{}\": {}", path.display(), err);
+                None
+            }
+        };
+        let state = RefCell::new(State {
+            spirv: None,
+            module: None,
+            error: None,
+        });
+        let handle = ShaderHandle { path, watch, state };
+        handle.compile();
+        handle
+    }
+
+    /// The path to the shader.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The version of the most recently successfully compiled shader.
+    ///
+    /// This begins at `1` and is incremented each time the shader successfully recompiles. A
+    /// version of `0` indicates that the shader has not yet successfully compiled.
+    pub fn version(&self) -> u64 {
+        self.update();
+        self.state
+            .borrow()
+            .spirv
+            .as_ref()
+            .map(|&(version, _)| version)
+            .unwrap_or(0)
+    }
+
+    /// The error produced by the most recent compilation, if it failed.
+    pub fn error(&self) -> Option<String> {
+        self.update();
+        self.state.borrow().error.clone()
+    }
+
+    /// Recompile the shader if its file has changed since the last update.
+    ///
+    /// This is called automatically by `module`, `version` and `error`.
+    pub fn update(&self) {
+        let watch = match self.watch {
+            None => return,
+            Some(ref watch) => watch,
+        };
+        let touched = match watch.paths_touched() {
+            Ok(paths) => paths,
+            Err(err) => {
+                eprintln!(
+                    "failed to check shader \"{}\": {}",
+                    self.path.display(),
+                    err
+                );
+                return;
+            }
+        };
+        let touched = touched.into_iter().any(|p| {
+            let p = p.canonicalize().unwrap_or(p);
+            p == self.path
+        });
+        if touched {
+            self.compile();
+        }
+    }
+
+    /// The latest successfully compiled shader module.
+    ///
+    /// The module is recreated on the given device in the case that the shader has recompiled
+    /// since the last call. Returns `None` if the shader has never successfully compiled.
+    pub fn module(&self, device: &wgpu::Device) -> Option<Ref<wgpu::ShaderModule>> {
+        self.update();
+        {
+            let mut state = self.state.borrow_mut();
+            let State {
+                ref spirv,
+                ref mut module,
+                ..
+            } = *state;
+            let (version, bytes) = spirv.as_ref()?;
+            let outdated = match *module {
+                Some((module_version, _)) => module_version != *version,
+                None => true,
+            };
+            if outdated {
+                let new_module = wgpu::shader_from_spirv_bytes(device, bytes);
+                *module = Some((*version, new_module));
+            }
+        }
+        let state = self.state.borrow();
+        Some(Ref::map(state, |state| {
+            &state.module.as_ref().expect("no shader module").1
+        }))
+    }
+
+    // Compile the shader, printing any errors to stderr.
+    fn compile(&self) {
+        let mut state = self.state.borrow_mut();
+        match hotglsl::compile(&self.path) {
+            Ok(bytes) => {
+                let version = state.spirv.as_ref().map(|&(v, _)| v).unwrap_or(0) + 1;
+                state.spirv = Some((version, bytes));
+                state.error = None;
+            }
+            Err(err) => {
+                let error = format!("{}", err);
+                eprintln!(
+                    "failed to compile shader \"{}\": {}",
+                    self.path.display(),
+                    error
+                );
+                state.error = Some(error);
+            }
+        }
+    }
+}
@@ -17,6 +17,20 @@ pub trait ToTextureView {
     fn to_texture_view(&self) -> TextureView;
 }
 
+/// Types that may be uploaded directly to a texture as a single texel, via `Texture::from_data`.
+///
+/// The texture format is inferred from the implementing type, e.g. `[u8; 4]` maps to
+/// `Rgba8Unorm`, `[f32; 4]` to `Rgba32Float` and `f32` to `R32Float`.
+///
+/// # Safety
+///
+/// Implementors must contain no padding bytes and must have a size equal to the size of
+/// `TEXTURE_FORMAT` in bytes, as the data is reinterpreted as a slice of bytes for uploading.
+pub unsafe trait Texel: 'static + Copy {
+    /// The format of a texture whose texels are of this type.
+    const TEXTURE_FORMAT: wgpu::TextureFormat;
+}
+
 /// A convenient wrapper around a handle to a texture on the GPU along with its descriptor.
 ///
 /// A texture can be thought of as an image that resides in GPU memory (as opposed to CPU memory).
@@ -27,6 +41,8 @@ pub trait ToTextureView {
 pub struct Texture {
     handle: Arc<TextureHandle>,
     descriptor: wgpu::TextureDescriptor<'static>,
+    // Whether or not mipmaps are generated after each call to `upload_data`.
+    auto_generate_mipmaps: bool,
 }
 
 /// A convenient wrapper around a handle to a texture view along with its descriptor.
@@ -60,12 +76,13 @@ pub struct TextureViewId(u64);
 
 /// A type aimed at simplifying the construction of a **Texture**.
 ///
-/// The builder assumes a set of defaults describing a 128x128, non-multisampled, single-layer,
-/// non-linear sRGBA-8 texture. A suite of builder methods may be used to specify the exact
-/// properties desired.
+/// The builder assumes a set of defaults describing a 128x128, 2D, non-multisampled,
+/// single-layer, single-mip, `Rgba8Unorm` texture that may be sampled and copied to. A suite of
+/// builder methods may be used to specify the exact properties desired.
 #[derive(Debug)]
 pub struct Builder {
     descriptor: wgpu::TextureDescriptor<'static>,
+    auto_generate_mipmaps: bool,
 }
 
 /// A type aimed at simplifying the construction of a **TextureView**.
@@ -133,6 +150,7 @@ impl Texture {
         self.descriptor.array_layer_count
     }
 
+    /// The number of mip levels, including the full resolution base level.
     pub fn mip_level_count(&self) -> u32 {
         self.descriptor.mip_level_count
     }
@@ -180,7 +198,12 @@ impl Texture {
         handle: Arc<TextureHandle>,
         descriptor: wgpu::TextureDescriptor<'static>,
     ) -> Self {
-        Texture { handle, descriptor }
+        let auto_generate_mipmaps = false;
+        Texture {
+            handle,
+            descriptor,
+            auto_generate_mipmaps,
+        }
     }
 
     // Custom common use methods.
@@ -280,6 +303,166 @@ impl Texture {
         let texture_copy_view = self.default_copy_view();
         let extent = self.extent();
         encoder.copy_buffer_to_texture(buffer_copy_view, texture_copy_view, extent);
+
+        if self.auto_generate_mipmaps {
+            self.generate_mipmaps(device, encoder);
+        }
+    }
+
+    /// Create a 2D texture with the given size and upload the given texel data to it.
+    ///
+    /// The texture format is inferred from the texel type `T`. See the `Texel` trait for
+    /// supported types. The texture is created with `SAMPLED` and `COPY_DST` usage, the latter
+    /// allowing for re-uploading changed data via `update_data`.
+    ///
+    /// **Panics** if the length of `data` is not equal to `width * height`.
+    pub fn from_data<T>(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        width: u32,
+        height: u32,
+        data: &[T],
+    ) -> Self
+    where
+        T: Texel,
+    {
+        let texture = wgpu::TextureBuilder::new()
+            .size([width, height])
+            .format(T::TEXTURE_FORMAT)
+            .usage(wgpu::TextureUsage::SAMPLED | wgpu::TextureUsage::COPY_DST)
+            .build(device);
+        texture.update_data(device, queue, data);
+        texture
+    }
+
+    /// Upload the given texel data to the texture, replacing its contents.
+    ///
+    /// Rows are padded internally to satisfy wgpu's required buffer row alignment, so `data`
+    /// should be tightly packed. The texture must have `COPY_DST` usage.
+    ///
+    /// **Panics** if `T::TEXTURE_FORMAT` does not match the texture's format or if the length of
+    /// `data` does not match the texture's width multiplied by its height.
+    pub fn update_data<T>(&self, device: &wgpu::Device, queue: &wgpu::Queue, data: &[T])
+    where
+        T: Texel,
+    {
+        let cmd_encoder_desc = wgpu::CommandEncoderDescriptor {
+            label: Some("nannou_texture_update_data"),
+        };
+        let mut encoder = device.create_command_encoder(&cmd_encoder_desc);
+        self.encode_update_data(device, &mut encoder, data);
+        queue.submit(&[encoder.finish()]);
+    }
+
+    /// Encode a command for uploading the given texel data to the texture.
+    ///
+    /// This is the same as `update_data` but allows for submitting the upload alongside other
+    /// commands.
+    pub fn encode_update_data<T>(
+        &self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        data: &[T],
+    ) where
+        T: Texel,
+    {
+        assert_eq!(
+            T::TEXTURE_FORMAT,
+            self.format(),
+            "texel type does not match the texture format"
+        );
+        let [width, height] = self.size();
+        assert_eq!(data.len(), width as usize * height as usize);
+
+        // Copy each row into a buffer with rows padded to the required alignment.
+        let bytes = unsafe { wgpu::bytes::from_slice(data) };
+        let bytes_per_row = width as usize * std::mem::size_of::<T>();
+        let padded_bytes_per_row = padded_bytes_per_row(bytes_per_row as u32);
+        let buffer = match padded_bytes_per_row as usize == bytes_per_row {
+            true => device.create_buffer_with_data(bytes, wgpu::BufferUsage::COPY_SRC),
+            false => {
+                let mut padded = vec![0u8; padded_bytes_per_row as usize * height as usize];
+                for (dst, src) in padded
+                    .chunks_mut(padded_bytes_per_row as usize)
+                    .zip(bytes.chunks(bytes_per_row))
+                {
+                    dst[..bytes_per_row].copy_from_slice(src);
+                }
+                device.create_buffer_with_data(&padded, wgpu::BufferUsage::COPY_SRC)
+            }
+        };
+        let buffer_copy_view = wgpu::BufferCopyView {
+            buffer: &buffer,
+            offset: 0,
+            bytes_per_row: padded_bytes_per_row,
+            rows_per_image: height,
+        };
+        let texture_copy_view = self.default_copy_view();
+        let extent = self.extent();
+        encoder.copy_buffer_to_texture(buffer_copy_view, texture_copy_view, extent);
+
+        if self.auto_generate_mipmaps {
+            self.generate_mipmaps(device, encoder);
+        }
+    }
+
+    /// Encode the commands for generating each mip level from the one before it, beginning with
+    /// the base level.
+    ///
+    /// Each level is produced by rendering the previous level with linear filtering, so this works
+    /// for any format that may be both sampled and rendered to, unlike a copy between levels. The
+    /// texture's usage must include both `SAMPLED` and `OUTPUT_ATTACHMENT`.
+    ///
+    /// This is a no-op for textures with a single mip level. Textures created via
+    /// `TextureBuilder::auto_generate_mipmaps` call this automatically within `upload_data`.
+    ///
+    /// **Panics** if the texture is not a 2D, non-multisampled texture with the necessary usage.
+    pub fn generate_mipmaps(&self, device: &wgpu::Device, encoder: &mut wgpu::CommandEncoder) {
+        assert_eq!(
+            self.dimension(),
+            wgpu::TextureDimension::D2,
+            "mipmap generation requires a 2D texture"
+        );
+        assert_eq!(
+            self.sample_count(),
+            1,
+            "mipmap generation requires a non-multisampled texture"
+        );
+        let required_usage = wgpu::TextureUsage::SAMPLED | wgpu::TextureUsage::OUTPUT_ATTACHMENT;
+        assert!(
+            self.usage().contains(required_usage),
+            "mipmap generation requires `SAMPLED` and `OUTPUT_ATTACHMENT` texture usage"
+        );
+        if self.mip_level_count() < 2 {
+            return;
+        }
+        // A single pipeline is shared by every level, only the source view differs.
+        let mut reshaper = wgpu::TextureReshaper::new(
+            device,
+            &self.mip_level_view(0, 0),
+            1,
+            self.component_type(),
+            1,
+            self.format(),
+        );
+        for layer in 0..self.array_layer_count() {
+            for level in 1..self.mip_level_count() {
+                let src_view = self.mip_level_view(level - 1, layer);
+                let dst_view = self.mip_level_view(level, layer);
+                reshaper.set_src_texture(device, &src_view);
+                reshaper.encode_render_pass(&dst_view, encoder);
+            }
+        }
+    }
+
+    // A 2D view of a single mip level of a single layer.
+    fn mip_level_view(&self, level: u32, layer: u32) -> TextureView {
+        self.view()
+            .dimension(wgpu::TextureViewDimension::D2)
+            .base_mip_level(level)
+            .level_count(1)
+            .layer(layer)
+            .build()
     }
 
     /// Write the contents of the texture into a new buffer.
@@ -461,7 +644,9 @@ impl Builder {
     pub const DEFAULT_SAMPLE_COUNT: u32 = 1;
     pub const DEFAULT_DIMENSION: wgpu::TextureDimension = wgpu::TextureDimension::D2;
     pub const DEFAULT_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
-    pub const DEFAULT_USAGE: wgpu::TextureUsage = wgpu::TextureUsage::ORDERED;
+    pub const DEFAULT_USAGE: wgpu::TextureUsage = wgpu::TextureUsage::from_bits_truncate(
+        wgpu::TextureUsage::SAMPLED.bits() | wgpu::TextureUsage::COPY_DST.bits(),
+    );
     pub const DEFAULT_DESCRIPTOR: wgpu::TextureDescriptor<'static> = wgpu::TextureDescriptor {
         label: Some("nannou_texture_descriptor"),
         size: Self::DEFAULT_SIZE,
@@ -512,11 +697,13 @@ impl Builder {
         self
     }
 
+    /// Specify the number of layers in the case that the texture is an array texture.
     pub fn array_layer_count(mut self, count: u32) -> Self {
         self.descriptor.array_layer_count = count;
         self
     }
 
+    /// Specify the number of mip levels, including the full resolution base level.
     pub fn mip_level_count(mut self, count: u32) -> Self {
         self.descriptor.mip_level_count = count;
         self
@@ -542,6 +729,36 @@ impl Builder {
         self
     }
 
+    /// Add `OUTPUT_ATTACHMENT` to the texture's usage, allowing for the texture to be used as the
+    /// target of a render pass.
+    pub fn as_render_target(mut self) -> Self {
+        self.descriptor.usage |= wgpu::TextureUsage::OUTPUT_ATTACHMENT;
+        self
+    }
+
+    /// Add `STORAGE` to the texture's usage, allowing for the texture to be read from and written
+    /// to as a storage texture within shaders, e.g. in a compute pass.
+    pub fn as_storage(mut self) -> Self {
+        self.descriptor.usage |= wgpu::TextureUsage::STORAGE;
+        self
+    }
+
+    /// Whether or not mipmaps should be generated after each upload via `Texture::upload_data`.
+    ///
+    /// When enabled, the texture is created with the `SAMPLED` and `OUTPUT_ATTACHMENT` usages
+    /// required by `Texture::generate_mipmaps`. If the mip level count has not been specified, it
+    /// is set to the full chain for the texture's size, i.e. `texture_max_mip_level_count(size)`.
+    pub fn auto_generate_mipmaps(mut self, auto_generate: bool) -> Self {
+        self.auto_generate_mipmaps = auto_generate;
+        self
+    }
+
+    /// A label for the texture, useful for identifying the texture while debugging.
+    pub fn label(mut self, label: &'static str) -> Self {
+        self.descriptor.label = Some(label);
+        self
+    }
+
     // If `depth` is greater than `1` then `D3` is assumed, otherwise if `height` is greater than
     // `1` then `D2` is assumed, otherwise `D1` is assumed.
     fn infer_dimension_from_size(&mut self) {
@@ -556,9 +773,14 @@ impl Builder {
 
     /// Build the texture resulting from the specified parameters with the given device.
     pub fn build(self, device: &wgpu::Device) -> Texture {
-        let handle = Arc::new(device.create_texture(&self.descriptor));
-        let descriptor = self.into();
-        Texture { handle, descriptor }
+        let auto_generate_mipmaps = self.auto_generate_mipmaps;
+        let descriptor: wgpu::TextureDescriptor<'static> = self.into();
+        let handle = Arc::new(device.create_texture(&descriptor));
+        Texture {
+            handle,
+            descriptor,
+            auto_generate_mipmaps,
+        }
     }
 
     /// Consumes the builder and returns the resulting `wgpu::TextureDescriptor`.
@@ -583,6 +805,11 @@ impl<'a> ViewBuilder<'a> {
         self
     }
 
+    pub fn base_mip_level(mut self, base_mip_level: u32) -> Self {
+        self.descriptor.base_mip_level = base_mip_level;
+        self
+    }
+
     pub fn level_count(mut self, level_count: u32) -> Self {
         self.descriptor.level_count = level_count;
         self
@@ -697,7 +924,12 @@ impl Clone for Texture {
     fn clone(&self) -> Self {
         let handle = self.handle.clone();
         let descriptor = self.descriptor_cloned();
-        Self { handle, descriptor }
+        let auto_generate_mipmaps = self.auto_generate_mipmaps;
+        Self {
+            handle,
+            descriptor,
+            auto_generate_mipmaps,
+        }
     }
 }
 
@@ -725,19 +957,32 @@ impl Default for Builder {
     fn default() -> Self {
         Self {
             descriptor: Self::DEFAULT_DESCRIPTOR,
+            auto_generate_mipmaps: false,
         }
     }
 }
 
 impl From<wgpu::TextureDescriptor<'static>> for Builder {
     fn from(descriptor: wgpu::TextureDescriptor<'static>) -> Self {
-        Self { descriptor }
+        let auto_generate_mipmaps = false;
+        Self {
+            descriptor,
+            auto_generate_mipmaps,
+        }
     }
 }
 
 impl Into<wgpu::TextureDescriptor<'static>> for Builder {
     fn into(self) -> wgpu::TextureDescriptor<'static> {
-        self.descriptor
+        let mut descriptor = self.descriptor;
+        if self.auto_generate_mipmaps {
+            descriptor.usage |= wgpu::TextureUsage::SAMPLED | wgpu::TextureUsage::OUTPUT_ATTACHMENT;
+            if descriptor.mip_level_count == 1 {
+                let size = [descriptor.size.width, descriptor.size.height];
+                descriptor.mip_level_count = max_mip_level_count(size);
+            }
+        }
+        descriptor
     }
 }
 
@@ -767,6 +1012,24 @@ fn texture_view_id(texture_id: &TextureId, desc: &wgpu::TextureViewDescriptor) -
     TextureViewId(s.finish())
 }
 
+/// The number of mip levels in a full mip chain for a texture of the given size, including the
+/// base level.
+///
+/// Each level is half the size of the previous, down to a single pixel along the largest axis.
+pub fn max_mip_level_count([width, height]: [u32; 2]) -> u32 {
+    32 - width.max(height).max(1).leading_zeros()
+}
+
+/// The alignment in bytes required of each row of a buffer copied to or from a texture.
+pub const COPY_BYTES_PER_ROW_ALIGNMENT: u32 = 256;
+
+/// Round the given number of bytes per row up to the nearest multiple of
+/// `COPY_BYTES_PER_ROW_ALIGNMENT`.
+pub fn padded_bytes_per_row(bytes_per_row: u32) -> u32 {
+    let align = COPY_BYTES_PER_ROW_ALIGNMENT;
+    (bytes_per_row + align - 1) / align * align
+}
+
 /// The size of the texture data in bytes as described by the given descriptor.
 pub fn data_size_bytes(desc: &wgpu::TextureDescriptor) -> usize {
     desc.size.width as usize
@@ -788,8 +1051,9 @@ pub fn format_size_bytes(format: wgpu::TextureFormat) -> u32 {
         | Rgba8UnormSrgb | Rgba8Snorm | Rgba8Uint | Rgba8Sint | Bgra8Unorm | Bgra8UnormSrgb
         | Rgb10a2Unorm | Rg11b10Float => 4,
 
-        Rg32Uint | Rg32Sint | Rg32Float | Rgba16Uint | Rgba16Sint | Rgba16Float | Rgba32Uint
-        | Rgba32Sint | Rgba32Float => 8,
+        Rg32Uint | Rg32Sint | Rg32Float | Rgba16Uint | Rgba16Sint | Rgba16Float => 8,
+
+        Rgba32Uint | Rgba32Sint | Rgba32Float => 16,
 
         Depth32Float | Depth24Plus | Depth24PlusStencil8 => 4,
     }
@@ -857,3 +1121,31 @@ pub fn format_to_component_type(format: wgpu::TextureFormat) -> wgpu::TextureCom
         | wgpu::TextureFormat::Depth24PlusStencil8 => wgpu::TextureComponentType::Float,
     }
 }
+
+unsafe impl Texel for u8 {
+    const TEXTURE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::R8Unorm;
+}
+
+unsafe impl Texel for [u8; 2] {
+    const TEXTURE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rg8Unorm;
+}
+
+unsafe impl Texel for [u8; 4] {
+    const TEXTURE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+}
+
+unsafe impl Texel for u32 {
+    const TEXTURE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::R32Uint;
+}
+
+unsafe impl Texel for f32 {
+    const TEXTURE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::R32Float;
+}
+
+unsafe impl Texel for [f32; 2] {
+    const TEXTURE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rg32Float;
+}
+
+unsafe impl Texel for [f32; 4] {
+    const TEXTURE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba32Float;
+}
@@ -107,6 +107,25 @@ impl Reshaper {
         }
     }
 
+    /// Replace the source texture, retaining the render pipeline.
+    ///
+    /// The new source texture must have the same sample count and component type as the original.
+    /// This allows for reusing a single reshaper across many textures, e.g. each level of a mip
+    /// chain.
+    pub fn set_src_texture(
+        &mut self,
+        device: &wgpu::Device,
+        src_texture: &wgpu::TextureViewHandle,
+    ) {
+        self.bind_group = bind_group(
+            device,
+            &self.bind_group_layout,
+            src_texture,
+            &self.sampler,
+            self.uniform_buffer.as_ref(),
+        );
+    }
+
     /// Given an encoder, submits a render pass command for writing the source texture to the
     /// destination texture.
     pub fn encode_render_pass(
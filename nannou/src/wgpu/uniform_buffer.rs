@@ -0,0 +1,195 @@
+use crate::wgpu;
+use std::marker::PhantomData;
+
+/// A uniform buffer containing one or more values of type `T`.
+///
+/// Simplifies the creation, binding and updating of uniform buffers by handling the conversion of
+/// values to bytes along with any padding required between elements.
+///
+/// A buffer created via `new` contains a single value. A buffer created via `new_dynamic` contains
+/// a value for each of the given elements, each padded to `wgpu::BIND_BUFFER_ALIGNMENT` so that
+/// they may be selected via the dynamic offsets passed to `RenderPass::set_bind_group`. See
+/// `dynamic_offset`.
+///
+/// Type `T` must implement the **Uniform** trait and its layout must match the shader's uniform
+/// block, e.g. following the GLSL `std140` layout rules.
+#[derive(Debug)]
+pub struct UniformBuffer<T> {
+    buffer: wgpu::Buffer,
+    len: usize,
+    dynamic: bool,
+    _value: PhantomData<T>,
+}
+
+/// Types that may be written to a **UniformBuffer**.
+///
+/// Implemented for `f32`, `i32` and `u32` along with arrays of up to four of each and `4x4`
+/// matrices of `f32`.
+///
+/// # Safety
+///
+/// Implementors must be either `#[repr(C)]` or `#[repr(transparent)]` and must contain no padding
+/// bytes, as values are reinterpreted as a slice of bytes for uploading. Introduce explicit
+/// padding fields where necessary.
+pub unsafe trait Uniform: 'static + Copy {}
+
+impl<T> UniformBuffer<T> {
+    /// The usage with which uniform buffers are created, allowing them to be updated via copies.
+    pub const USAGE: wgpu::BufferUsage = wgpu::BufferUsage::from_bits_truncate(
+        wgpu::BufferUsage::UNIFORM.bits() | wgpu::BufferUsage::COPY_DST.bits(),
+    );
+
+    /// The distance in bytes between the start of each element within a buffer.
+    ///
+    /// This is the size of `T` rounded up to `wgpu::BIND_BUFFER_ALIGNMENT` for dynamic buffers.
+    pub fn stride(dynamic: bool) -> wgpu::BufferAddress {
+        let size = std::mem::size_of::<T>() as wgpu::BufferAddress;
+        match dynamic {
+            false => size,
+            true => {
+                let align = wgpu::BIND_BUFFER_ALIGNMENT;
+                ((size + align - 1) / align).max(1) * align
+            }
+        }
+    }
+
+    /// The underlying buffer.
+    pub fn buffer(&self) -> &wgpu::Buffer {
+        &self.buffer
+    }
+
+    /// The number of values within the buffer.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether or not the values are intended to be selected via dynamic offsets.
+    pub fn is_dynamic(&self) -> bool {
+        self.dynamic
+    }
+
+    /// The size of the buffer in bytes, including any padding.
+    pub fn size_bytes(&self) -> wgpu::BufferAddress {
+        Self::stride(self.dynamic) * self.len as wgpu::BufferAddress
+    }
+
+    /// The dynamic offset of the value at the given index, for use with
+    /// `RenderPass::set_bind_group`.
+    ///
+    /// **Panics** if `index` is out of range.
+    pub fn dynamic_offset(&self, index: usize) -> wgpu::DynamicOffset {
+        assert!(index < self.len, "uniform buffer index out of range");
+        (Self::stride(self.dynamic) * index as wgpu::BufferAddress) as wgpu::DynamicOffset
+    }
+
+    /// A binding of the buffer for use within a `wgpu::BindGroupDescriptor`.
+    ///
+    /// For dynamic buffers, the bound range is a single value with the value selected via its
+    /// dynamic offset.
+    pub fn bind_group_entry(&self, binding: u32) -> wgpu::Binding {
+        let size = std::mem::size_of::<T>() as wgpu::BufferAddress;
+        let range = match self.dynamic {
+            true => 0..size,
+            false => 0..self.size_bytes(),
+        };
+        let buffer = &self.buffer;
+        let resource = wgpu::BindingResource::Buffer { buffer, range };
+        wgpu::Binding { binding, resource }
+    }
+
+    /// A layout entry describing the buffer for use within a `wgpu::BindGroupLayoutDescriptor`.
+    pub fn layout_entry(
+        &self,
+        binding: u32,
+        visibility: wgpu::ShaderStage,
+    ) -> wgpu::BindGroupLayoutEntry {
+        let ty = wgpu::BindingType::UniformBuffer {
+            dynamic: self.dynamic,
+        };
+        wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility,
+            ty,
+        }
+    }
+}
+
+impl<T> UniformBuffer<T>
+where
+    T: Uniform,
+{
+    /// Create a uniform buffer containing the given value.
+    pub fn new(device: &wgpu::Device, value: T) -> Self {
+        Self::from_values(device, &[value], false)
+    }
+
+    /// Create a uniform buffer containing each of the given values for selection via dynamic
+    /// offsets.
+    ///
+    /// Each value is padded to `wgpu::BIND_BUFFER_ALIGNMENT`.
+    ///
+    /// **Panics** if `values` is empty.
+    pub fn new_dynamic(device: &wgpu::Device, values: &[T]) -> Self {
+        assert!(
+            !values.is_empty(),
+            "a uniform buffer requires at least one value"
+        );
+        Self::from_values(device, values, true)
+    }
+
+    /// Encode a copy that replaces the first value within the buffer.
+    pub fn update(&self, device: &wgpu::Device, encoder: &mut wgpu::CommandEncoder, value: &T) {
+        self.update_at(device, encoder, 0, value);
+    }
+
+    /// Encode a copy that replaces the value at the given index.
+    ///
+    /// **Panics** if `index` is out of range.
+    pub fn update_at(
+        &self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        index: usize,
+        value: &T,
+    ) {
+        assert!(index < self.len, "uniform buffer index out of range");
+        let bytes = unsafe { wgpu::bytes::from(value) };
+        let usage = wgpu::BufferUsage::COPY_SRC;
+        let staging = device.create_buffer_with_data(bytes, usage);
+        let offset = Self::stride(self.dynamic) * index as wgpu::BufferAddress;
+        let size = bytes.len() as wgpu::BufferAddress;
+        encoder.copy_buffer_to_buffer(&staging, 0, &self.buffer, offset, size);
+    }
+
+    // Create the buffer, padding each value to the stride.
+    fn from_values(device: &wgpu::Device, values: &[T], dynamic: bool) -> Self {
+        let stride = Self::stride(dynamic) as usize;
+        let mut bytes = vec![0u8; stride * values.len()];
+        for (chunk, value) in bytes.chunks_mut(stride).zip(values) {
+            let value_bytes = unsafe { wgpu::bytes::from(value) };
+            chunk[..value_bytes.len()].copy_from_slice(value_bytes);
+        }
+        let buffer = device.create_buffer_with_data(&bytes, Self::USAGE);
+        UniformBuffer {
+            buffer,
+            len: values.len(),
+            dynamic,
+            _value: PhantomData,
+        }
+    }
+}
+
+macro_rules! impl_uniform {
+    ($($T:ty),*) => {
+        $(
+            unsafe impl Uniform for $T {}
+            unsafe impl Uniform for [$T; 2] {}
+            unsafe impl Uniform for [$T; 3] {}
+            unsafe impl Uniform for [$T; 4] {}
+        )*
+    };
+}
+
+impl_uniform!(f32, i32, u32);
+
+unsafe impl Uniform for [[f32; 4]; 4] {}
@@ -4,7 +4,8 @@
 //! which can be used to build a [**Window**](./struct.Window.html).
 
 use crate::event::{
-    Key, MouseButton, MouseScrollDelta, TouchEvent, TouchPhase, TouchpadPressure, WindowEvent,
+    Gesture, GestureRecogniser, Key, MouseButton, MouseScrollDelta, TouchEvent, TouchPhase,
+    TouchpadPressure, WindowEvent,
 };
 use crate::frame::{self, Frame, RawFrame};
 use crate::geom;
@@ -13,11 +14,13 @@ use crate::wgpu;
 use crate::App;
 use std::any::Any;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use std::{env, fmt};
 use winit::dpi::LogicalSize;
 
+pub use winit::monitor::{MonitorHandle, VideoMode};
+pub use winit::window::CursorIcon;
 pub use winit::window::Fullscreen;
 pub use winit::window::WindowId as Id;
 
@@ -37,6 +40,8 @@ pub struct Builder<'app> {
     backends: wgpu::BackendBit,
     device_desc: Option<wgpu::DeviceDescriptor>,
     user_functions: UserFunctions,
+    gesture_recogniser: GestureRecogniser,
+    placement: Option<Placement>,
     msaa_samples: Option<u32>,
     max_capture_frame_jobs: u32,
     capture_frame_timeout: Option<Duration>,
@@ -60,9 +65,11 @@ pub(crate) struct UserFunctions {
     pub(crate) resized: Option<ResizedFnAny>,
     pub(crate) touch: Option<TouchFnAny>,
     pub(crate) touchpad_pressure: Option<TouchpadPressureFnAny>,
+    pub(crate) gesture: Option<GestureFnAny>,
     pub(crate) hovered_file: Option<HoveredFileFnAny>,
     pub(crate) hovered_file_cancelled: Option<HoveredFileCancelledFnAny>,
     pub(crate) dropped_file: Option<DroppedFileFnAny>,
+    pub(crate) file_drop: Option<FileDropFnAny>,
     pub(crate) focused: Option<FocusedFnAny>,
     pub(crate) unfocused: Option<UnfocusedFnAny>,
     pub(crate) closed: Option<ClosedFnAny>,
@@ -132,6 +139,9 @@ pub type TouchFn<Model> = fn(&App, &mut Model, TouchEvent);
 /// A function for processing touchpad pressure events.
 pub type TouchpadPressureFn<Model> = fn(&App, &mut Model, TouchpadPressure);
 
+/// A function for processing gestures recognised from touch events.
+pub type GestureFn<Model> = fn(&App, &mut Model, Gesture);
+
 /// A function for processing hovered file events.
 pub type HoveredFileFn<Model> = fn(&App, &mut Model, PathBuf);
 
@@ -141,6 +151,9 @@ pub type HoveredFileCancelledFn<Model> = fn(&App, &mut Model);
 /// A function for processing dropped file events.
 pub type DroppedFileFn<Model> = fn(&App, &mut Model, PathBuf);
 
+/// A function for processing all files dropped onto a window together.
+pub type FileDropFn<Model> = fn(&mut Model, Vec<PathBuf>);
+
 /// A function for processing window focused events.
 pub type FocusedFn<Model> = fn(&App, &mut Model);
 
@@ -155,6 +168,39 @@ pub type ClosedFn<Model> = fn(&App, &mut Model);
 pub enum BuildError {
     NoAvailableAdapter,
     WinitOsError(winit::error::OsError),
+    /// No monitor exists at the index given to `Builder::monitor`.
+    NoMonitor(usize),
+}
+
+/// Information about a monitor connected to the system.
+#[derive(Clone, Debug)]
+pub struct Monitor {
+    handle: winit::monitor::MonitorHandle,
+    name: Option<String>,
+    is_primary: bool,
+}
+
+/// The fullscreen mode of a window.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FullscreenMode {
+    /// A borderless window covering the monitor, sometimes called "fullscreen windowed".
+    ///
+    /// This is the common choice for installations as it does not change the monitor's video mode
+    /// and allows for switching between applications. If no monitor is given, the window's current
+    /// monitor is used, or the primary monitor for windows that are yet to be built.
+    Borderless(Option<MonitorHandle>),
+    /// True exclusive fullscreen at the resolution, bit depth and refresh rate of the given video
+    /// mode.
+    ///
+    /// Supported video modes may be enumerated via `Monitor::video_modes`.
+    Exclusive(VideoMode),
+}
+
+// Where to place a window upon creation.
+#[derive(Copy, Clone, Debug)]
+enum Placement {
+    Monitor(usize),
+    SpanAllMonitors,
 }
 
 // A macro for generating a handle to a function that can be stored within the Window without
@@ -210,9 +256,11 @@ fn_any!(MovedFn<M>, MovedFnAny);
 fn_any!(ResizedFn<M>, ResizedFnAny);
 fn_any!(TouchFn<M>, TouchFnAny);
 fn_any!(TouchpadPressureFn<M>, TouchpadPressureFnAny);
+fn_any!(GestureFn<M>, GestureFnAny);
 fn_any!(HoveredFileFn<M>, HoveredFileFnAny);
 fn_any!(HoveredFileCancelledFn<M>, HoveredFileCancelledFnAny);
 fn_any!(DroppedFileFn<M>, DroppedFileFnAny);
+fn_any!(FileDropFn<M>, FileDropFnAny);
 fn_any!(FocusedFn<M>, FocusedFnAny);
 fn_any!(UnfocusedFn<M>, UnfocusedFnAny);
 fn_any!(ClosedFn<M>, ClosedFnAny);
@@ -230,8 +278,23 @@ pub struct Window {
     pub(crate) swap_chain: WindowSwapChain,
     pub(crate) frame_data: Option<FrameData>,
     pub(crate) frame_count: u64,
+    // The instant at which the window's first frame began, used by `RawFrame::elapsed_secs`.
+    pub(crate) first_frame_at: Option<Instant>,
     pub(crate) user_functions: UserFunctions,
+    pub(crate) gesture_recogniser: GestureRecogniser,
     pub(crate) tracked_state: TrackedState,
+    // The double-buffered feedback textures, if enabled via `App::feedback_texture`.
+    pub(crate) feedback: Mutex<Option<frame::Feedback>>,
+    // The image drawn in place of the platform cursor, if any.
+    cursor_image: Mutex<Option<CursorImage>>,
+}
+
+// An image drawn in place of the platform cursor. See `Window::set_cursor_image`.
+#[derive(Clone, Debug)]
+pub(crate) struct CursorImage {
+    pub(crate) texture: wgpu::Texture,
+    // The pixel within the image located at the mouse position, relative to the top left.
+    pub(crate) hotspot: (u32, u32),
 }
 
 // Data related to `Frame`s produced for this window's swapchain textures.
@@ -354,6 +417,8 @@ impl<'app> Builder<'app> {
             backends: Self::DEFAULT_BACKENDS,
             device_desc: None,
             user_functions: Default::default(),
+            gesture_recogniser: Default::default(),
+            placement: None,
             msaa_samples: None,
             max_capture_frame_jobs: Default::default(),
             capture_frame_timeout: Default::default(),
@@ -415,6 +480,9 @@ impl<'app> Builder<'app> {
     /// One for forcing a certain number of samples and returning an error otherwise, and another
     /// for attempting to use the given number of samples but falling back to a supported value in
     /// the case that the specified number is not supported.*
+    ///
+    /// The given value must be one of `Frame::SUPPORTED_MSAA_SAMPLES` (1, 2, 4 or 8). A value of
+    /// `1` disables MSAA. `Window::build` will `panic!` if any other value is specified.
     pub fn msaa_samples(mut self, msaa_samples: u32) -> Self {
         self.msaa_samples = Some(msaa_samples);
         self
@@ -585,6 +653,24 @@ impl<'app> Builder<'app> {
         self
     }
 
+    /// A function for processing gestures recognised from the touch events associated with this
+    /// window.
+    pub fn gesture<M>(mut self, f: GestureFn<M>) -> Self
+    where
+        M: 'static,
+    {
+        self.user_functions.gesture = Some(GestureFnAny::from_fn_ptr(f));
+        self
+    }
+
+    /// Specify the recogniser used to produce gestures, e.g. to adjust its thresholds.
+    ///
+    /// By default, `GestureRecogniser::default()` is used.
+    pub fn gesture_recogniser(mut self, recogniser: GestureRecogniser) -> Self {
+        self.gesture_recogniser = recogniser;
+        self
+    }
+
     /// A function for processing window moved events associated with this window.
     pub fn moved<M>(mut self, f: MovedFn<M>) -> Self
     where
@@ -631,6 +717,18 @@ impl<'app> Builder<'app> {
         self
     }
 
+    /// A function for processing all files dropped onto this window at once.
+    ///
+    /// Unlike `dropped_file`, which is called once per file, the given function is called once
+    /// before the next update with the paths of all files that were dropped together.
+    pub fn on_file_drop<M>(mut self, f: FileDropFn<M>) -> Self
+    where
+        M: 'static,
+    {
+        self.user_functions.file_drop = Some(FileDropFnAny::from_fn_ptr(f));
+        self
+    }
+
     /// A function for processing the focused event associated with this window.
     pub fn focused<M>(mut self, f: FocusedFn<M>) -> Self
     where
@@ -704,6 +802,8 @@ impl<'app> Builder<'app> {
             backends,
             device_desc,
             user_functions,
+            gesture_recogniser,
+            placement,
             msaa_samples,
             max_capture_frame_jobs,
             capture_frame_timeout,
@@ -781,6 +881,27 @@ impl<'app> Builder<'app> {
             window.window.inner_size = Some(initial_window_size);
         }
 
+        // Determine the desktop position of the window for the requested placement, if any.
+        let position = match placement {
+            None => None,
+            Some(Placement::Monitor(index)) => {
+                let monitor = app
+                    .available_monitors()
+                    .into_iter()
+                    .nth(index)
+                    .ok_or(BuildError::NoMonitor(index))?;
+                if let Some(Fullscreen::Borderless(_)) = window.window.fullscreen {
+                    window.window.fullscreen = Some(Fullscreen::Borderless(monitor.clone()));
+                }
+                Some(monitor_bounds(&[monitor]))
+            }
+            Some(Placement::SpanAllMonitors) => {
+                let (pos, size) = monitor_bounds(&app.available_monitors());
+                window.window.inner_size = Some(size.into());
+                Some((pos, size))
+            }
+        };
+
         // Build the window.
         let window = {
             let window_target = app
@@ -791,6 +912,14 @@ impl<'app> Builder<'app> {
             window.build(window_target)?
         };
 
+        // Move the window into position, centering it within the bounds.
+        if let Some((pos, size)) = position {
+            let outer = window.outer_size();
+            let x = pos.x + (size.width as i32 - outer.width as i32).max(0) / 2;
+            let y = pos.y + (size.height as i32 - outer.height as i32).max(0) / 2;
+            window.set_outer_position(winit::dpi::PhysicalPosition { x, y });
+        }
+
         // Build the wgpu surface.
         let surface = wgpu::Surface::create(&window);
 
@@ -820,7 +949,15 @@ impl<'app> Builder<'app> {
         let (frame_data, msaa_samples) = match user_functions.view {
             Some(View::WithModel(_)) | Some(View::Sketch(_)) | None => {
                 let msaa_samples = msaa_samples.unwrap_or(Frame::DEFAULT_MSAA_SAMPLES);
-                // TODO: Verity that requested sample count is valid for surface?
+                // NOTE: wgpu does not yet expose a way to query the sample counts supported by
+                // the adapter for a given format, so we can only check against the set of sample
+                // counts that are valid in general.
+                assert!(
+                    Frame::SUPPORTED_MSAA_SAMPLES.contains(&msaa_samples),
+                    "unsupported `msaa_samples` value {}: expected one of {:?}",
+                    msaa_samples,
+                    Frame::SUPPORTED_MSAA_SAMPLES,
+                );
                 let swap_chain_dims = [swap_chain_desc.width, swap_chain_desc.height];
                 let render = frame::RenderData::new(
                     &device,
@@ -856,8 +993,12 @@ impl<'app> Builder<'app> {
             swap_chain,
             frame_data,
             frame_count,
+            first_frame_at: None,
             user_functions,
+            gesture_recogniser,
             tracked_state,
+            feedback: Mutex::new(None),
+            cursor_image: Mutex::new(None),
         };
         app.windows.borrow_mut().insert(window_id, window);
 
@@ -882,6 +1023,8 @@ impl<'app> Builder<'app> {
             backends,
             swap_chain_builder,
             user_functions,
+            gesture_recogniser,
+            placement,
             msaa_samples,
             max_capture_frame_jobs,
             capture_frame_timeout,
@@ -896,6 +1039,8 @@ impl<'app> Builder<'app> {
             backends,
             swap_chain_builder,
             user_functions,
+            gesture_recogniser,
+            placement,
             msaa_samples,
             max_capture_frame_jobs,
             capture_frame_timeout,
@@ -947,9 +1092,12 @@ impl<'app> Builder<'app> {
         self.map_window(|w| w.with_title(title))
     }
 
-    /// Create the window fullscreened on the current monitor.
-    pub fn fullscreen(self) -> Self {
-        let fullscreen = Fullscreen::Borderless(self.app.primary_monitor());
+    /// Create the window fullscreen with the given mode.
+    ///
+    /// `FullscreenMode::Borderless(None)` creates a borderless fullscreen window on the primary
+    /// monitor.
+    pub fn fullscreen(self, mode: FullscreenMode) -> Self {
+        let fullscreen = mode.into_winit(|| self.app.primary_monitor());
         self.fullscreen_with(Some(fullscreen))
     }
 
@@ -961,6 +1109,26 @@ impl<'app> Builder<'app> {
         self.map_window(|w| w.with_fullscreen(fullscreen))
     }
 
+    /// Place the window on the monitor at the given index within `app.monitors()`.
+    ///
+    /// The window is centered on the monitor. If the window was requested to be fullscreen via
+    /// `fullscreen`, it is made fullscreen on this monitor instead.
+    ///
+    /// Building the window fails with `BuildError::NoMonitor` if no monitor exists at the index.
+    pub fn monitor(mut self, index: usize) -> Self {
+        self.placement = Some(Placement::Monitor(index));
+        self
+    }
+
+    /// Create an undecorated window covering the combined area of all monitors.
+    ///
+    /// Useful for multi-screen installations where a single window should be shown across all
+    /// displays.
+    pub fn span_all_monitors(mut self) -> Self {
+        self.placement = Some(Placement::SpanAllMonitors);
+        self.map_window(|w| w.with_fullscreen(None).with_decorations(false))
+    }
+
     /// Requests maximized mode.
     pub fn maximized(self, maximized: bool) -> Self {
         self.map_window(|w| w.with_maximized(maximized))
@@ -1165,20 +1333,20 @@ impl Window {
         self.window.set_maximized(maximized)
     }
 
-    /// Set the window to fullscreen on the primary monitor.
+    /// Set the window to fullscreen with the given mode without recreating the window.
     ///
-    /// `true` enables fullscreen, `false` disables fullscreen.
+    /// `FullscreenMode::Borderless(None)` uses the monitor that the window is currently on.
     ///
-    /// See the `set_fullscreen_with` method for more options and details about behaviour related
-    /// to fullscreen.
-    pub fn set_fullscreen(&self, fullscreen: bool) {
-        if fullscreen {
-            let monitor = self.current_monitor();
-            let fullscreen = Fullscreen::Borderless(monitor);
-            self.set_fullscreen_with(Some(fullscreen));
-        } else {
-            self.set_fullscreen_with(None);
-        }
+    /// See the `set_fullscreen_with` method for more details about behaviour related to
+    /// fullscreen.
+    pub fn set_fullscreen(&self, mode: FullscreenMode) {
+        let fullscreen = mode.into_winit(|| self.current_monitor());
+        self.set_fullscreen_with(Some(fullscreen));
+    }
+
+    /// Return the window from fullscreen to a normal window.
+    pub fn exit_fullscreen(&self) {
+        self.set_fullscreen_with(None);
     }
 
     /// Set the window to fullscreen.
@@ -1246,6 +1414,27 @@ impl Window {
         self.window.set_window_icon(window_icon)
     }
 
+    /// Sets the window icon from the given image, e.g. one loaded via `App::new_icon_from_file`.
+    ///
+    /// The image is converted to RGBA8 before being passed to `set_window_icon`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// Window icons are unsupported on macOS, where the icon is instead specified by the app
+    /// bundle's `Info.plist`. A warning is printed and the image is ignored.
+    pub fn set_icon(&self, image: &image::DynamicImage) {
+        if cfg!(target_os = "macos") {
+            eprintln!("window icons are not supported on macOS, ignoring `set_icon`");
+            return;
+        }
+        let rgba = image.to_rgba();
+        let (width, height) = rgba.dimensions();
+        match winit::window::Icon::from_rgba(rgba.into_raw(), width, height) {
+            Ok(icon) => self.set_window_icon(Some(icon)),
+            Err(err) => eprintln!("failed to create window icon: {}", err),
+        }
+    }
+
     /// Sets the location of IME candidate box in client area coordinates relative to the top left.
     ///
     /// ## Platform-specific
@@ -1267,6 +1456,61 @@ impl Window {
         self.window.set_cursor_icon(state);
     }
 
+    /// Modifies the mouse cursor of the window, removing any image set via `set_cursor_image`.
+    ///
+    /// The same as `set_cursor_icon`, but also restores the visibility of the platform cursor if
+    /// it was hidden in favour of a cursor image.
+    pub fn set_cursor_shape(&self, icon: CursorIcon) {
+        if self.take_cursor_image().is_some() {
+            self.window.set_cursor_visible(true);
+        }
+        self.window.set_cursor_icon(icon);
+    }
+
+    /// Display the given image in place of the platform cursor.
+    ///
+    /// `hotspot` is the pixel of the image, relative to its top left corner, that is located at
+    /// the mouse position.
+    ///
+    /// The platform cursor is hidden and the image is instead drawn on top of each **Frame** of
+    /// the window at the mouse position, as custom platform cursors are not yet supported by
+    /// winit. The image is not drawn to frames captured via `Window::capture_frame`, nor when the
+    /// window's view function receives a **RawFrame**. As the image is drawn with the frame, it
+    /// lags the platform cursor by up to a frame.
+    ///
+    /// The cursor image may be removed via `set_cursor_shape` or `clear_cursor_image`.
+    pub fn set_cursor_image(&self, image: &image::DynamicImage, hotspot: (u32, u32)) {
+        let texture = wgpu::Texture::from_image(self, image);
+        let cursor = CursorImage { texture, hotspot };
+        *self
+            .cursor_image
+            .lock()
+            .expect("failed to acquire cursor image lock") = Some(cursor);
+        self.window.set_cursor_visible(false);
+    }
+
+    /// Remove any image set via `set_cursor_image`, restoring the platform cursor.
+    pub fn clear_cursor_image(&self) {
+        if self.take_cursor_image().is_some() {
+            self.window.set_cursor_visible(true);
+        }
+    }
+
+    // The image drawn in place of the platform cursor, if any.
+    pub(crate) fn cursor_image(&self) -> Option<CursorImage> {
+        self.cursor_image
+            .lock()
+            .expect("failed to acquire cursor image lock")
+            .clone()
+    }
+
+    fn take_cursor_image(&self) -> Option<CursorImage> {
+        self.cursor_image
+            .lock()
+            .expect("failed to acquire cursor image lock")
+            .take()
+    }
+
     /// Changes the position of the cursor in logical window coordinates.
     ///
     /// ## Platform-specific
@@ -1295,6 +1539,14 @@ impl Window {
         self.window.set_cursor_grab(grab)
     }
 
+    /// Lock the cursor to the window, e.g. for first-person navigation.
+    ///
+    /// This is an alias for `set_cursor_grab`. Combine with `set_cursor_visible(false)` and the
+    /// deltas of `DeviceEvent::MouseMotion` events for unbounded mouse movement.
+    pub fn set_cursor_locked(&self, locked: bool) -> Result<(), winit::error::ExternalError> {
+        self.set_cursor_grab(locked)
+    }
+
     /// Set the cursor's visibility.
     ///
     /// If `false`, hides the cursor. If `true`, shows the cursor.
@@ -1383,6 +1635,49 @@ impl Window {
         }
     }
 
+    // The feedback texture written to by the current frame, enabling feedback if necessary.
+    pub(crate) fn feedback_texture(&self) -> wgpu::Texture {
+        let desc = &self.swap_chain.descriptor;
+        let size = [desc.width, desc.height];
+        let mut feedback = self
+            .feedback
+            .lock()
+            .expect("failed to acquire feedback lock");
+        let feedback = feedback.get_or_insert_with(|| {
+            frame::Feedback::new(self.swap_chain_device(), size, desc.format)
+        });
+        feedback.current_texture().clone()
+    }
+
+    // Swap the feedback textures ahead of `view`, recreating them if the window was resized.
+    pub(crate) fn swap_feedback(&mut self) {
+        let desc = &self.swap_chain.descriptor;
+        let size = [desc.width, desc.height];
+        let device = self.device_queue_pair.device();
+        let feedback = self
+            .feedback
+            .get_mut()
+            .expect("failed to acquire feedback lock");
+        if let Some(feedback) = feedback.as_mut() {
+            match feedback.matches(size, desc.format) {
+                true => feedback.swap(),
+                false => *feedback = frame::Feedback::new(device, size, desc.format),
+            }
+        }
+    }
+
+    // The views to the previous and current frame's feedback textures, if enabled.
+    pub(crate) fn feedback_views(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+    ) -> Option<(wgpu::TextureView, wgpu::TextureView)> {
+        let mut feedback = self
+            .feedback
+            .lock()
+            .expect("failed to acquire feedback lock");
+        feedback.as_mut().map(|feedback| feedback.views(encoder))
+    }
+
     /// Attempts to determine whether or not the window is currently fullscreen.
     pub fn is_fullscreen(&self) -> bool {
         self.fullscreen().is_some()
@@ -1406,6 +1701,13 @@ impl Window {
         geom::Rect::from_w_h(w, h)
     }
 
+    /// The same as `rect`, but with dimensions in physical pixels as given by
+    /// `inner_size_pixels`.
+    pub fn rect_physical(&self) -> geom::Rect {
+        let (w, h) = self.inner_size_pixels();
+        geom::Rect::from_w_h(w as _, h as _)
+    }
+
     /// Capture the next frame right before it is drawn to this window and write it to an image
     /// file at the given path. If a frame already exists, it will be captured before its `submit`
     /// method is called or before it is `drop`ped.
@@ -1418,6 +1720,17 @@ impl Window {
         self.capture_frame_inner(path.as_ref());
     }
 
+    /// Capture the next frame drawn to this window and write it to a PNG file at the given path.
+    ///
+    /// A short-hand for `capture_frame` that replaces the path's extension with `png`. Like
+    /// `capture_frame`, the frame is read and written on a background thread.
+    pub fn frame_to_png<P>(&self, path: P)
+    where
+        P: AsRef<Path>,
+    {
+        self.capture_frame_inner(&path.as_ref().with_extension("png"));
+    }
+
     fn capture_frame_inner(&self, path: &Path) {
         // If the parent directory does not exist, create it.
         let dir = path.parent().expect("capture_frame path has no directory");
@@ -1436,6 +1749,23 @@ impl Window {
         *capture_next_frame_path = Some(path.to_path_buf());
     }
 
+    /// Capture the next frame right before it is drawn to this window and copy it to the system
+    /// clipboard, e.g. for pasting into a chat or social media post.
+    ///
+    /// Like `capture_frame`, the frame is read on a background thread. On Linux, the image only
+    /// remains available while the app is running.
+    ///
+    /// Requires the `clipboard` feature.
+    #[cfg(feature = "clipboard")]
+    pub fn screenshot_to_clipboard(&self) {
+        self.frame_data
+            .as_ref()
+            .expect("window capture requires that `view` draws to a `Frame` (not a `RawFrame`)")
+            .capture
+            .next_frame_to_clipboard
+            .store(true, std::sync::atomic::Ordering::SeqCst);
+    }
+
     /// Block and wait for all active capture frame jobs to complete.
     ///
     /// This is called implicitly when the window is dropped to ensure any pending captures
@@ -1490,11 +1820,100 @@ impl fmt::Debug for WindowSwapChain {
 
 // Error implementations.
 
+impl FullscreenMode {
+    // Convert to the winit representation, using `default_monitor` if none was specified.
+    fn into_winit<F>(self, default_monitor: F) -> Fullscreen
+    where
+        F: FnOnce() -> MonitorHandle,
+    {
+        match self {
+            FullscreenMode::Borderless(monitor) => {
+                Fullscreen::Borderless(monitor.unwrap_or_else(default_monitor))
+            }
+            FullscreenMode::Exclusive(video_mode) => Fullscreen::Exclusive(video_mode),
+        }
+    }
+}
+
+impl Default for FullscreenMode {
+    fn default() -> Self {
+        FullscreenMode::Borderless(None)
+    }
+}
+
+impl From<Fullscreen> for FullscreenMode {
+    fn from(fullscreen: Fullscreen) -> Self {
+        match fullscreen {
+            Fullscreen::Borderless(monitor) => FullscreenMode::Borderless(Some(monitor)),
+            Fullscreen::Exclusive(video_mode) => FullscreenMode::Exclusive(video_mode),
+        }
+    }
+}
+
+impl Monitor {
+    pub(crate) fn new(
+        handle: winit::monitor::MonitorHandle,
+        primary: &winit::monitor::MonitorHandle,
+    ) -> Self {
+        let name = handle.name();
+        let is_primary = handle == *primary;
+        Monitor {
+            handle,
+            name,
+            is_primary,
+        }
+    }
+
+    /// The area covered by the monitor within the desktop in physical pixels.
+    ///
+    /// Unlike a window's **Rect**, the desktop's origin is the top-left corner of the primary
+    /// monitor and *y* increases downwards.
+    pub fn rect(&self) -> geom::Rect {
+        let pos = self.handle.position();
+        let size = self.handle.size();
+        let (x, y) = (
+            pos.x as geom::scalar::Default,
+            pos.y as geom::scalar::Default,
+        );
+        let (w, h) = (
+            size.width as geom::scalar::Default,
+            size.height as geom::scalar::Default,
+        );
+        geom::Rect::from_corners(geom::pt2(x, y), geom::pt2(x + w, y + h))
+    }
+
+    /// The ratio of physical pixels to logical points for this monitor.
+    pub fn scale_factor(&self) -> f64 {
+        self.handle.scale_factor()
+    }
+
+    /// A human-readable name for the monitor, if available.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_ref().map(|s| &s[..])
+    }
+
+    /// Whether or not this is the system's primary monitor.
+    pub fn is_primary(&self) -> bool {
+        self.is_primary
+    }
+
+    /// The video modes supported by the monitor, for use with `FullscreenMode::Exclusive`.
+    pub fn video_modes(&self) -> Vec<VideoMode> {
+        self.handle.video_modes().collect()
+    }
+
+    /// The underlying winit monitor handle.
+    pub fn handle(&self) -> &winit::monitor::MonitorHandle {
+        &self.handle
+    }
+}
+
 impl fmt::Display for BuildError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             BuildError::NoAvailableAdapter => write!(f, "no available wgpu adapter detected"),
             BuildError::WinitOsError(ref e) => e.fmt(f),
+            BuildError::NoMonitor(index) => write!(f, "no monitor at index {}", index),
         }
     }
 }
@@ -1504,3 +1923,29 @@ impl From<winit::error::OsError> for BuildError {
         BuildError::WinitOsError(e)
     }
 }
+
+// The top-left position and size of the area covered by all of the given monitors.
+fn monitor_bounds(
+    monitors: &[winit::monitor::MonitorHandle],
+) -> (
+    winit::dpi::PhysicalPosition<i32>,
+    winit::dpi::PhysicalSize<u32>,
+) {
+    let mut min = (std::i32::MAX, std::i32::MAX);
+    let mut max = (std::i32::MIN, std::i32::MIN);
+    for monitor in monitors {
+        let pos = monitor.position();
+        let size = monitor.size();
+        min = (min.0.min(pos.x), min.1.min(pos.y));
+        max = (
+            max.0.max(pos.x + size.width as i32),
+            max.1.max(pos.y + size.height as i32),
+        );
+    }
+    if monitors.is_empty() {
+        return (Default::default(), Default::default());
+    }
+    let pos = winit::dpi::PhysicalPosition::new(min.0, min.1);
+    let size = winit::dpi::PhysicalSize::new((max.0 - min.0) as u32, (max.1 - min.1) as u32);
+    (pos, size)
+}
@@ -0,0 +1,46 @@
+use nannou::asset::{Asset, Error};
+use nannou::image::{DynamicImage, GenericImageView};
+use nannou::text::Font;
+use std::path::{Path, PathBuf};
+
+fn asset_path(path: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("assets")
+        .join(path)
+}
+
+#[test]
+fn image_asset_test() {
+    let image = <DynamicImage as Asset>::load(&asset_path("images/nannou.png")).unwrap();
+    assert!(image.width() > 0 && image.height() > 0);
+}
+
+#[test]
+fn font_asset_test() {
+    let path = asset_path("fonts/NotoSans/NotoSans-Regular.ttf");
+    let font = <Font as Asset>::load(&path).unwrap();
+    assert!(font.glyph('a').id().0 > 0);
+}
+
+#[test]
+fn missing_asset_test() {
+    match <DynamicImage as Asset>::load(&asset_path("does/not/exist.png")) {
+        Err(Error::Image(_)) | Err(Error::Io(_)) => (),
+        other => panic!("unexpected result: {:?}", other.map(|_| ())),
+    }
+    match <Font as Asset>::load(&asset_path("does/not/exist.ttf")) {
+        Err(Error::Font(_)) => (),
+        other => panic!("unexpected result: {:?}", other.map(|_| ())),
+    }
+}
+
+#[cfg(feature = "audio")]
+#[test]
+fn audio_asset_test() {
+    use nannou::audio;
+    let file = <audio::File as Asset>::load(&asset_path("sounds/thumbpiano.wav")).unwrap();
+    assert!(file.channels() > 0);
+    assert!(file.len_frames() > 0);
+    assert!(file.samples().iter().all(|s| s.abs() <= 1.0));
+}
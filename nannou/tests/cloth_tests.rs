@@ -0,0 +1,75 @@
+use nannou::cloth::ClothSimulation;
+use nannou::geom::{pt3, vec2};
+
+#[test]
+fn cloth_pin_test() {
+    let mut cloth = ClothSimulation::new(4, 3, 10.0);
+    cloth.pin(0, 0);
+    cloth.pin(3, 0);
+    let pinned = cloth.position(0, 0);
+    let free = cloth.position(1, 2);
+    cloth.wind(vec2(1.0, 0.0), 100.0);
+    for _ in 0..60 {
+        cloth.update(1.0 / 60.0);
+    }
+    assert_eq!(cloth.position(0, 0), pinned);
+    // Free nodes fall under gravity while remaining within the plane of a 2D cloth.
+    let moved = cloth.position(1, 2);
+    assert!(moved.y < free.y);
+    assert_eq!(moved.z, 0.0);
+}
+
+#[test]
+fn cloth_cut_spring_test() {
+    let mut cloth = ClothSimulation::new(3, 3, 10.0);
+    // Two triangles per cell.
+    assert_eq!(cloth.indices().len(), 4 * 2 * 3);
+    assert!(cloth.cut_spring((1, 1), (0, 1)));
+    assert!(!cloth.cut_spring((0, 1), (1, 1)));
+    assert!(!cloth.has_spring((0, 1), (1, 1)));
+    // Only adjacent nodes share a spring.
+    assert!(!cloth.cut_spring((0, 0), (1, 1)));
+    assert!(!cloth.cut_spring((2, 0), (0, 1)));
+    // The cut edge is shared by one triangle in each of the cells above and below it.
+    assert_eq!(cloth.indices().len(), 2 * 2 * 3 + 2 * 3);
+    assert_eq!(cloth.tex_coords().len(), 9);
+}
+
+#[test]
+fn cloth_3d_wind_test() {
+    let mut cloth = ClothSimulation::new_3d(3, 3, 10.0);
+    assert!(cloth.is_3d());
+    cloth.set_position(1, 1, pt3(0.0, 0.0, 0.0));
+    cloth.wind(pt3(0.0, 0.0, 1.0), 100.0);
+    cloth.update(1.0 / 60.0);
+    assert!(cloth.position(1, 1).z > 0.0);
+}
+
+#[test]
+fn cloth_degenerate_wind_test() {
+    // A zero wind direction stops the wind rather than producing NaN.
+    let mut cloth = ClothSimulation::new_3d(3, 3, 10.0);
+    cloth.wind(vec2(0.0, 0.0), 100.0);
+    cloth.update(1.0 / 60.0);
+    assert!(!cloth.position(1, 1).x.is_nan());
+
+    // Nodes folded onto their neighbours have no normal, yet remain finite under wind.
+    let mut cloth = ClothSimulation::new_3d(3, 3, 10.0);
+    for row in 0..3 {
+        for col in 0..3 {
+            cloth.set_position(col, row, pt3(0.0, 0.0, 0.0));
+        }
+    }
+    cloth.wind(pt3(1.0, 0.0, 1.0), 100.0);
+    cloth.update(1.0 / 60.0);
+    let p = cloth.position(1, 1);
+    assert!(p.x.is_finite() && p.y.is_finite() && p.z.is_finite());
+
+    // Wind along the surface of a flat 3D cloth still drags it.
+    let mut cloth = ClothSimulation::new_3d(3, 3, 10.0);
+    cloth.set_gravity(pt3(0.0, 0.0, 0.0));
+    let start = cloth.position(1, 1);
+    cloth.wind(vec2(1.0, 0.0), 100.0);
+    cloth.update(1.0 / 60.0);
+    assert!(cloth.position(1, 1).x > start.x);
+}
@@ -0,0 +1,50 @@
+use nannou::color::{self, IntoLinSrgba, LinSrgba, Oklab, Oklch};
+
+#[test]
+fn oklab_round_trip_test() {
+    let rgb = color::lin_srgb(0.2, 0.5, 0.8);
+    let lab = Oklab::from_lin_srgb(rgb);
+    let back = lab.to_lin_srgb();
+    assert!((back.red - rgb.red).abs() < 1e-4);
+    assert!((back.green - rgb.green).abs() < 1e-4);
+    assert!((back.blue - rgb.blue).abs() < 1e-4);
+
+    // White has full lightness and no chroma.
+    let white = Oklch::from_lin_srgb(color::lin_srgb(1.0, 1.0, 1.0));
+    assert!((white.l - 1.0).abs() < 1e-3);
+    assert!(white.chroma < 1e-3);
+    let white: LinSrgba = white.into_lin_srgba();
+    assert!((white.red - 1.0).abs() < 1e-3);
+}
+
+#[test]
+fn oklch_lerp_test() {
+    let a = Oklch::new(0.5, 0.1, 350.0);
+    let b = Oklch::new(0.7, 0.2, 30.0);
+    // The hue travels across 0 degrees rather than back through 180.
+    let mid = color::oklch_lerp(a, b, 0.5);
+    assert!((mid.l - 0.6).abs() < 1e-6);
+    assert!((mid.chroma - 0.15).abs() < 1e-6);
+    assert!((mid.hue - 10.0).abs() < 1e-3);
+    assert!((color::oklch_lerp(a, b, 1.0).hue - 30.0).abs() < 1e-3);
+}
+
+#[test]
+fn palette_test() {
+    let palette = color::Palette::from_hex_array(&["#ff0000", "0f0", "#0000ff80"])
+        .unwrap()
+        .with_name("primary", 1);
+    assert_eq!(palette.len(), 3);
+    assert_eq!(palette[0], color::lin_srgba(1.0, 0.0, 0.0, 1.0));
+    assert_eq!(palette["primary"], palette[1]);
+    assert!((palette[2].alpha - 128.0 / 255.0).abs() < 1e-6);
+    assert!(color::Palette::from_hex_array(&["#ff00"]).is_ok());
+    assert!(color::Palette::from_hex_array(&["#gg0000"]).is_err());
+    let css = palette.to_css();
+    assert!(css.contains("--color-0: #ff0000;"));
+    assert!(css.contains("--primary: #00ff00;"));
+    assert!(css.contains("--color-2: #0000ff80;"));
+    let ase = palette.to_ase();
+    assert_eq!(&ase[..4], b"ASEF");
+    assert_eq!(&ase[8..12], &3u32.to_be_bytes());
+}
@@ -0,0 +1,39 @@
+use nannou::compat::p5;
+use nannou::draw::{Draw, DrawCommand};
+
+#[test]
+fn p5_math_test() {
+    assert_eq!(p5::map(5.0, 0.0, 10.0, 0.0, 100.0), 50.0);
+    assert_eq!(p5::constrain(12.0, 0.0, 10.0), 10.0);
+    assert_eq!(p5::dist(0.0, 0.0, 3.0, 4.0), 5.0);
+    for i in 0..100 {
+        let n = p5::noise(i as f32 * 0.37, i as f32 * 0.11, 0.5);
+        assert!(n >= 0.0 && n <= 1.0);
+    }
+}
+
+#[test]
+fn p5_implicit_draw_test() {
+    let draw = Draw::new();
+    p5::begin(&draw);
+    p5::fill(255.0, 0.0, 0.0, 255.0);
+    p5::ellipse(0.0, 0.0, 10.0, 10.0);
+    p5::noFill();
+    p5::noStroke();
+    // Neither a fill nor a stroke, so nothing is drawn.
+    p5::rect(0.0, 0.0, 10.0, 10.0);
+    p5::text("hello", 0.0, 0.0);
+    p5::stroke(0.0, 0.0, 255.0, 255.0);
+    p5::strokeWeight(2.0);
+    p5::line(0.0, 0.0, 10.0, 10.0);
+    assert!(p5::end().is_some());
+    assert!(p5::end().is_none());
+    let count = draw
+        .drain_commands()
+        .filter(|cmd| match cmd {
+            DrawCommand::Primitive(_) => true,
+            _ => false,
+        })
+        .count();
+    assert_eq!(count, 2);
+}
@@ -0,0 +1,44 @@
+use nannou::dmx::{self, DmxAnimator, PatchError};
+
+#[test]
+fn dmx_packet_test() {
+    let mut data = [0u8; dmx::UNIVERSE_SIZE];
+    data[0] = 255;
+    data[511] = 7;
+    let packet = dmx::dmx_packet(3, 0x0123, &data);
+    assert_eq!(packet.len(), dmx::HEADER_SIZE + dmx::UNIVERSE_SIZE);
+    assert_eq!(&packet[..8], b"Art-Net\0");
+    assert_eq!(&packet[8..10], &[0x00, 0x50]);
+    assert_eq!(&packet[10..12], &[0, 14]);
+    assert_eq!(packet[12], 3);
+    assert_eq!(&packet[14..16], &[0x23, 0x01]);
+    assert_eq!(&packet[16..18], &[0x02, 0x00]);
+    assert_eq!(packet[18], 255);
+    assert_eq!(packet[dmx::HEADER_SIZE + 511], 7);
+}
+
+#[test]
+fn dmx_animator_test() {
+    let patch = "channel,fixture-type,fixture-name\n\
+                 # The wash lights either side of the stage.\n\
+                 1, rgb-par, wash-left\n\
+                 \n\
+                 4, rgb-par, wash-right\n";
+    let mut animator = DmxAnimator::from_patch_str(patch).unwrap();
+    assert_eq!(animator.fixtures().len(), 2);
+    let right = animator.fixture("wash-right").unwrap().clone();
+    assert_eq!(right.channel, 4);
+    assert_eq!(right.kind, "rgb-par");
+
+    animator.set_rgb(right.channel, 10, 20, 30);
+    assert_eq!(&animator.data()[3..6], &[10, 20, 30]);
+    assert_eq!(animator.channel(5), 20);
+    assert_eq!(animator.channel(0), 0);
+    animator.set_channel(513, 1);
+    assert!(animator.is_changed());
+
+    match DmxAnimator::from_patch_str("1,par,a\n600,par,b") {
+        Err(PatchError::InvalidLine { line, .. }) => assert_eq!(line, 2),
+        _ => panic!("expected an invalid patch"),
+    }
+}
@@ -0,0 +1,199 @@
+use nannou::color::{LinSrgba, BLUE, RED};
+use nannou::draw::primitive::gradient::LUT_WIDTH;
+use nannou::draw::{Draw, DrawCommand, Gradient};
+use nannou::geom::pt2;
+
+fn primitive_count(draw: &Draw) -> usize {
+    draw.drain_commands()
+        .filter(|cmd| match cmd {
+            DrawCommand::Primitive(_) => true,
+            _ => false,
+        })
+        .count()
+}
+
+#[test]
+fn draw_recording_merges_baked_primitives_test() {
+    let draw = Draw::new();
+    let recording = draw.record();
+    recording.rect().w_h(10.0, 10.0).color(RED);
+    recording.x(20.0).ellipse().radius(5.0).color(BLUE);
+    assert!(!recording.is_baked());
+    recording.replay(&draw);
+    assert!(recording.is_baked());
+    // Both primitives only differ by transform, so they are baked into a single mesh.
+    assert_eq!(primitive_count(&draw), 1);
+    // Replaying again submits the same baked mesh.
+    recording.replay(&draw);
+    assert_eq!(primitive_count(&draw), 1);
+    recording.invalidate();
+    assert!(!recording.is_baked());
+}
+
+#[test]
+fn gpu_mesh_normals_test() {
+    use nannou::prelude::*;
+    let mut mesh = nannou::draw::GpuMesh::new();
+    let n = vec3(0.0, 0.0, 0.0);
+    let uv = pt2(0.0, 0.0);
+    let c = lin_srgba(1.0, 1.0, 1.0, 1.0);
+    // Two triangles folded along the shared edge from `a` to `b`.
+    let a = mesh.push_vertex(pt3(0.0, 0.0, 0.0), n, uv, c);
+    let b = mesh.push_vertex(pt3(1.0, 0.0, 0.0), n, uv, c);
+    let c0 = mesh.push_vertex(pt3(0.0, 1.0, 0.0), n, uv, c);
+    let c1 = mesh.push_vertex(pt3(0.0, 0.0, 1.0), n, uv, c);
+    mesh.push_triangle(a, b, c0);
+    mesh.push_triangle(b, a, c1);
+    assert_eq!(mesh.triangle_count(), 2);
+
+    mesh.compute_smooth_normals();
+    assert!((mesh.normals()[c0 as usize] - vec3(0.0, 0.0, 1.0)).magnitude() < 1e-6);
+    assert!((mesh.normals()[c1 as usize] - vec3(0.0, 1.0, 0.0)).magnitude() < 1e-6);
+    let shared = mesh.normals()[a as usize];
+    assert!((shared - vec3(0.0, 1.0, 1.0).normalize()).magnitude() < 1e-6);
+
+    mesh.compute_flat_normals();
+    assert_eq!(mesh.vertex_count(), 6);
+    assert_eq!(mesh.indices(), &[0, 1, 2, 3, 4, 5]);
+    for i in 0..3 {
+        assert_eq!(mesh.normals()[i], vec3(0.0, 0.0, 1.0));
+        assert_eq!(mesh.normals()[3 + i], vec3(0.0, 1.0, 0.0));
+    }
+
+    let positions: Vec<_> = mesh.positions().iter().map(|p| *p * 2.0).collect();
+    mesh.update_positions(&positions);
+    assert_eq!(mesh.positions()[1], pt3(2.0, 0.0, 0.0));
+}
+
+#[test]
+fn canvas_2d_test() {
+    let draw = Draw::new();
+    let mut ctx = draw.canvas_2d();
+    // Stroking or filling an empty path draws nothing.
+    ctx.begin_path();
+    ctx.stroke();
+    ctx.fill();
+    assert_eq!(primitive_count(&draw), 0);
+    ctx.fill_style(RED);
+    ctx.fill_rect(-10.0, -10.0, 20.0, 20.0);
+    ctx.save();
+    ctx.translate(50.0, 0.0);
+    ctx.stroke_style(BLUE);
+    ctx.begin_path();
+    ctx.move_to(0.0, 0.0);
+    ctx.line_to(10.0, 0.0);
+    ctx.arc(0.0, 0.0, 10.0, 0.0, std::f32::consts::PI);
+    ctx.close_path();
+    ctx.stroke();
+    ctx.fill();
+    ctx.restore();
+    assert_eq!(ctx.draw().current_transform(), draw.current_transform());
+    assert_eq!(primitive_count(&draw), 3);
+}
+
+fn context_layers(draw: &Draw) -> Vec<(f32, bool)> {
+    draw.drain_commands()
+        .filter_map(|cmd| match cmd {
+            DrawCommand::Context(ctxt) => Some((ctxt.z_layer, ctxt.opaque)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn z_layer_sort_test() {
+    let draw = Draw::new();
+    draw.z_layer(2.0).rect();
+    draw.z_layer(1.0).ellipse();
+    draw.rect();
+    assert_eq!(
+        context_layers(&draw),
+        vec![(0.0, false), (1.0, false), (2.0, false)]
+    );
+}
+
+#[test]
+fn z_layer_opaque_sort_test() {
+    let draw = Draw::new();
+    draw.z_layer(3.0).rect();
+    draw.opaque(true).z_layer(1.0).rect();
+    draw.opaque(true).z_layer(2.0).rect();
+    // Opaque primitives are drawn first, front to back.
+    assert_eq!(
+        context_layers(&draw),
+        vec![(2.0, true), (1.0, true), (3.0, false)]
+    );
+    // The layer is applied as a z offset so that the depth test keeps nearer layers in front.
+    draw.opaque(true).z_layer(2.0).rect();
+    let offset = draw
+        .drain_commands()
+        .find_map(|cmd| match cmd {
+            DrawCommand::Context(ctxt) => Some(ctxt.transform.w.z),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(offset, 2.0);
+}
+
+fn black_to_white() -> Vec<(f32, LinSrgba)> {
+    vec![
+        (0.0, LinSrgba::new(0.0, 0.0, 0.0, 1.0)),
+        (1.0, LinSrgba::new(1.0, 1.0, 1.0, 1.0)),
+    ]
+}
+
+#[test]
+fn gradient_position_test() {
+    let stops = black_to_white();
+    let linear = Gradient::Linear {
+        start: pt2(0.0, 0.0),
+        end: pt2(10.0, 0.0),
+        stops: stops.clone(),
+    };
+    assert_eq!(linear.position_at(pt2(5.0, 3.0)), 0.5);
+    assert_eq!(linear.position_at(pt2(-5.0, 0.0)), 0.0);
+    assert_eq!(linear.position_at(pt2(20.0, 0.0)), 1.0);
+    let radial = Gradient::Radial {
+        center: pt2(1.0, 1.0),
+        radius: 2.0,
+        stops,
+    };
+    assert_eq!(radial.position_at(pt2(1.0, 1.0)), 0.0);
+    assert_eq!(radial.position_at(pt2(1.0, 2.0)), 0.5);
+    assert_eq!(radial.color_at(pt2(1.0, 2.0)).red, 0.5);
+    assert_eq!(radial.position_at(pt2(10.0, 1.0)), 1.0);
+}
+
+#[test]
+fn gradient_lut_test() {
+    let gradient = Gradient::Radial {
+        center: pt2(0.0, 0.0),
+        radius: 1.0,
+        stops: black_to_white(),
+    };
+    let lut = gradient.lut();
+    assert_eq!((lut.width, lut.height), (LUT_WIDTH, 1));
+    assert_eq!(lut.bytes.len(), LUT_WIDTH as usize * 4);
+    let pixels: Vec<_> = lut.bytes.chunks(4).collect();
+    assert!(pixels[0][0] < 8);
+    assert_eq!(pixels[pixels.len() - 1][0], 255);
+    assert!(pixels.windows(2).all(|w| w[0][0] <= w[1][0]));
+    assert!(pixels.iter().all(|p| p[3] == 255));
+    // Tables with the same content share a key, and so a texture.
+    assert_eq!(lut.key, gradient.lut().key);
+}
+
+#[test]
+fn draw_recording_serde_test() {
+    let draw = Draw::new();
+    let recording = draw.record();
+    recording.rect().w_h(10.0, 10.0).color(RED);
+    let json = serde_json::to_string(&recording).unwrap();
+    // Serializing bakes the recording.
+    assert!(recording.is_baked());
+    let loaded: nannou::draw::DrawRecording = serde_json::from_str(&json).unwrap();
+    assert!(loaded.is_baked());
+    loaded.replay(&draw);
+    assert_eq!(primitive_count(&draw), 1);
+    assert_eq!(serde_json::to_string(&loaded).unwrap(), json);
+}
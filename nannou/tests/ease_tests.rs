@@ -0,0 +1,70 @@
+use nannou::ease::{self, Easing};
+
+fn easings() -> Vec<(&'static str, Box<dyn Fn(f32) -> f32>)> {
+    let a = ease::DEFAULT_AMPLITUDE;
+    let p = ease::DEFAULT_PERIOD;
+    let s = ease::DEFAULT_OVERSHOOT;
+    vec![
+        ("linear", Box::new(ease::linear)),
+        ("quad_in", Box::new(ease::quad_in)),
+        ("quad_out", Box::new(ease::quad_out)),
+        ("quad_in_out", Box::new(ease::quad_in_out)),
+        ("cubic_in", Box::new(ease::cubic_in)),
+        ("cubic_out", Box::new(ease::cubic_out)),
+        ("cubic_in_out", Box::new(ease::cubic_in_out)),
+        ("quart_in", Box::new(ease::quart_in)),
+        ("quart_out", Box::new(ease::quart_out)),
+        ("quart_in_out", Box::new(ease::quart_in_out)),
+        ("quint_in", Box::new(ease::quint_in)),
+        ("quint_out", Box::new(ease::quint_out)),
+        ("quint_in_out", Box::new(ease::quint_in_out)),
+        ("sine_in", Box::new(ease::sine_in)),
+        ("sine_out", Box::new(ease::sine_out)),
+        ("sine_in_out", Box::new(ease::sine_in_out)),
+        ("expo_in", Box::new(ease::expo_in)),
+        ("expo_out", Box::new(ease::expo_out)),
+        ("expo_in_out", Box::new(ease::expo_in_out)),
+        ("circ_in", Box::new(ease::circ_in)),
+        ("circ_out", Box::new(ease::circ_out)),
+        ("circ_in_out", Box::new(ease::circ_in_out)),
+        ("back_in", Box::new(move |t| ease::back_in(t, s))),
+        ("back_out", Box::new(move |t| ease::back_out(t, s))),
+        ("back_in_out", Box::new(move |t| ease::back_in_out(t, s))),
+        ("elastic_in", Box::new(move |t| ease::elastic_in(t, a, p))),
+        ("elastic_out", Box::new(move |t| ease::elastic_out(t, a, p))),
+        (
+            "elastic_in_out",
+            Box::new(move |t| ease::elastic_in_out(t, a, p * 1.5)),
+        ),
+        ("bounce_in", Box::new(ease::bounce_in)),
+        ("bounce_out", Box::new(ease::bounce_out)),
+        ("bounce_in_out", Box::new(ease::bounce_in_out)),
+    ]
+}
+
+#[test]
+fn ease_end_points_test() {
+    for (name, f) in easings() {
+        assert!(f.ease(0.0).abs() < 1e-3, "{}(0.0) = {}", name, f(0.0));
+        assert!(
+            (f.ease(1.0) - 1.0).abs() < 1e-3,
+            "{}(1.0) = {}",
+            name,
+            f(1.0)
+        );
+    }
+}
+
+#[test]
+fn ease_in_out_symmetry_test() {
+    for (name, f) in easings() {
+        if !name.ends_with("in_out") {
+            continue;
+        }
+        for i in 1..10 {
+            let t = i as f32 / 10.0;
+            let sum = f(t) + f(1.0 - t);
+            assert!((sum - 1.0).abs() < 1e-3, "{}: {} + {}", name, t, 1.0 - t);
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use nannou::field::FlowField2D;
+use nannou::geom::pt2;
+use nannou::noise::PerlinNoise;
+
+#[test]
+fn flow_field_vector_at_test() {
+    let field = FlowField2D::new(PerlinNoise::new(), 0.01, 0.5).strength(2.0);
+    for i in 0..16 {
+        let p = pt2(i as f32 * 13.7, i as f32 * -7.3);
+        let v = field.vector_at(p, i as f32 * 0.1);
+        assert!((v.magnitude() - 2.0).abs() < 1e-4);
+    }
+}
+
+#[test]
+fn flow_field_advect_test() {
+    let field = FlowField2D::new(PerlinNoise::new(), 0.01, 0.0).strength(10.0);
+    let p = pt2(12.5, -40.0);
+    // Over a short step, RK4 closely matches a single Euler step.
+    let dt = 1e-3;
+    let euler = p + field.vector_at(p, 0.0) * dt;
+    let rk4 = field.advect(p, dt, 0.0);
+    assert!((rk4 - euler).magnitude() < 1e-5);
+    assert_eq!(field.advect(p, 0.0, 0.0), p);
+}
+
+#[test]
+fn curl_flow_field_divergence_test() {
+    let field = FlowField2D::curl_noise(PerlinNoise::new(), 0.02);
+    let e = 0.5;
+    for i in 0..8 {
+        let p = pt2(i as f32 * 21.3 + 0.5, i as f32 * 9.1 + 0.25);
+        let dvx = field.vector_at(p + pt2(e, 0.0), 0.0).x - field.vector_at(p - pt2(e, 0.0), 0.0).x;
+        let dvy = field.vector_at(p + pt2(0.0, e), 0.0).y - field.vector_at(p - pt2(0.0, e), 0.0).y;
+        let divergence = (dvx + dvy) / (2.0 * e);
+        assert!(divergence.abs() < 2e-3);
+    }
+}
@@ -0,0 +1,80 @@
+use nannou::app::headless;
+use nannou::frame::{BloomConfig, PostEffectBuilder};
+use nannou::image::Rgba;
+use nannou::prelude::*;
+
+#[test]
+fn bloom_num_passes_test() {
+    assert_eq!(BloomConfig::new(0.8, 1.0, 0.0).num_passes, 1);
+    assert_eq!(BloomConfig::new(0.8, 1.0, 16.0).num_passes, 1);
+    assert_eq!(BloomConfig::new(0.8, 1.0, 17.0).num_passes, 2);
+    assert_eq!(BloomConfig::new(0.8, 1.0, 64.0).num_passes(8).num_passes, 8);
+}
+
+#[test]
+fn post_effects_fusion_test() {
+    assert_eq!(PostEffectBuilder::new().num_passes(), 0);
+
+    // Stages in shader order are fused into a single pass.
+    let effects = PostEffectBuilder::new()
+        .chromatic_aberration(2.0)
+        .film_grain(0.1, true)
+        .vignette(0.8, 0.5)
+        .scanlines(240.0, 0.2);
+    assert_eq!(effects.num_passes(), 1);
+
+    // Out of order or repeated stages begin a new pass.
+    let effects = PostEffectBuilder::new()
+        .vignette(0.8, 0.5)
+        .film_grain(0.1, false)
+        .scanlines(240.0, 0.2)
+        .scanlines(120.0, 0.2)
+        .chromatic_aberration(1.0);
+    assert_eq!(effects.num_passes(), 4);
+}
+
+fn capture_model(_app: &App) {}
+
+fn capture_update(_app: &App, _model: &mut (), _update: Update) {}
+
+fn capture_view(_app: &App, _model: &(), frame: Frame) {
+    // The frame forwards to the raw frame's multisampled texture.
+    let msaa_samples = frame.msaa_texture().map(|tex| tex.sample_count());
+    assert_eq!(msaa_samples.is_some(), frame.texture_msaa_samples() > 1);
+    assert_eq!(
+        RawFrame::msaa_texture(&frame).map(|tex| tex.sample_count()),
+        msaa_samples
+    );
+
+    frame.clear(RED);
+    let image = frame.capture_to_image().expect("failed to capture frame");
+    let image = image.to_rgba();
+    assert_eq!(image.dimensions(), (16, 8));
+    assert!(image.pixels().all(|p| *p == Rgba([255, 0, 0, 255])));
+
+    // Drawing may continue after a capture, and the raw frame captures the same texture.
+    frame.clear(BLUE);
+    let image = RawFrame::capture_to_image(&frame).expect("failed to capture raw frame");
+    assert_eq!(image.to_rgba().get_pixel(15, 7), &Rgba([0, 0, 255, 255]));
+}
+
+fn run_capture_test(msaa_samples: u32) {
+    let config = headless::Config::new()
+        .size(16, 8)
+        .msaa_samples(msaa_samples);
+    match App::run_headless(capture_model, capture_update, capture_view, config) {
+        // Skip the test on machines without a GPU.
+        Err(headless::Error::NoAvailableAdapter) => (),
+        result => result.expect("failed to run headless"),
+    }
+}
+
+#[test]
+fn capture_to_image_test() {
+    run_capture_test(1);
+}
+
+#[test]
+fn capture_to_image_msaa_test() {
+    run_capture_test(4);
+}
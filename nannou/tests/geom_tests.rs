@@ -13,3 +13,443 @@ fn angle_test() {
     let vector = Vector2::new(70.7, -60.8);
     assert_eq!(vector.angle(), -0.7102547457375739);
 }
+
+#[test]
+fn bezier_split_test() {
+    let b = geom::Bezier::new(pt2(0.0, 0.0), pt2(0.0, 1.0), pt2(1.0, 1.0), pt2(1.0, 0.0));
+    assert_eq!(b.point_at(0.0), pt2(0.0, 0.0));
+    assert_eq!(b.point_at(1.0), pt2(1.0, 0.0));
+    assert_eq!(b.point_at(0.5), pt2(0.5, 0.75));
+    let (l, r) = b.split_at(0.5);
+    assert_eq!(l.to, b.point_at(0.5));
+    assert_eq!(r.from, b.point_at(0.5));
+    let lut = b.uniform_parameterisation(256);
+    assert!((lut.t_at_fraction(0.5) - 0.5).abs() < 0.01);
+}
+
+#[test]
+fn catmull_rom_test() {
+    let points = vec![pt2(0.0, 0.0), pt2(1.0, 1.0), pt2(2.0, 0.0), pt2(3.0, 1.0)];
+    let spline = geom::CatmullRom::new(points.clone());
+    assert_eq!(spline.segment_count(), 3);
+    for (i, segment) in spline.to_bezier_segments().iter().enumerate() {
+        assert_eq!(segment.from, points[i]);
+        assert_eq!(segment.to, points[i + 1]);
+    }
+    assert_eq!(spline.clone().looped().segment_count(), 4);
+    let samples = spline.uniformly_sampled(10);
+    assert_eq!(samples.len(), 10);
+    assert_eq!(samples[0], points[0]);
+}
+
+#[test]
+fn polygon_boolean_test() {
+    let square = |x: f32, y: f32| {
+        vec![
+            pt2(x, y),
+            pt2(x + 2.0, y),
+            pt2(x + 2.0, y + 2.0),
+            pt2(x, y + 2.0),
+        ]
+    };
+    let a = square(0.0, 0.0);
+    let b = square(1.0, 1.0);
+    let intersection = geom::polygon::intersection(&a, &b);
+    assert_eq!(intersection.len(), 1);
+    assert_eq!(intersection[0].len(), 4);
+    assert_eq!(geom::polygon::union(&a, &b)[0].len(), 8);
+    assert_eq!(geom::polygon::difference(&a, &b)[0].len(), 6);
+    assert_eq!(geom::polygon::xor(&a, &b).len(), 2);
+    let far = square(10.0, 10.0);
+    assert!(geom::polygon::intersection(&a, &far).is_empty());
+    assert_eq!(geom::polygon::union(&a, &far).len(), 2);
+}
+
+#[test]
+fn convex_hull_test() {
+    let points = vec![
+        pt2(0.0, 0.0),
+        pt2(1.0, 0.0),
+        pt2(0.5, 0.5),
+        pt2(1.0, 1.0),
+        pt2(0.0, 1.0),
+    ];
+    let hull: Vec<_> = geom::convex_hull(points).points.collect();
+    assert_eq!(
+        hull,
+        vec![pt2(0.0, 0.0), pt2(1.0, 0.0), pt2(1.0, 1.0), pt2(0.0, 1.0)]
+    );
+}
+
+#[test]
+fn voronoi_test() {
+    let sites = [pt2(-1.0, 0.0), pt2(1.0, 0.0)];
+    let bounds = geom::Rect::from_w_h(4.0, 4.0);
+    let diagram = geom::Voronoi::new(&sites).bounds(bounds);
+    let cells: Vec<_> = diagram.cells().collect();
+    assert_eq!(cells.len(), 2);
+    assert_eq!(cells[0].site(), sites[0]);
+    assert_eq!(cells[0].neighbors(), &[1]);
+    assert_eq!(cells[1].neighbors(), &[0]);
+    assert!(cells[0].points().iter().all(|p| p.x <= 0.0));
+    assert!(cells[1].points().iter().all(|p| p.x >= 0.0));
+}
+
+#[test]
+fn contour_test() {
+    let bounds = geom::Rect::from_w_h(4.0, 4.0);
+    let field = |p: Point2| p.x.hypot(p.y);
+    let lines = geom::contour::contour(field, bounds, 33, 1.0);
+    assert_eq!(lines.len(), 1);
+    assert!(lines[0].closed);
+    assert!(lines[0]
+        .points
+        .iter()
+        .all(|p| (p.x.hypot(p.y) - 1.0).abs() < 0.02));
+
+    // A level crossing the bounds produces open lines ending at the edges.
+    let lines = geom::ScalarGrid::sample(|p: Point2| p.x, bounds, 5).contour(0.5);
+    assert_eq!(lines.len(), 1);
+    assert!(!lines[0].closed);
+    assert_eq!(lines[0].points.len(), 5);
+    assert!(lines[0].points.iter().all(|p| p.x == 0.5));
+}
+
+#[test]
+fn path_arc_to_test() {
+    use nannou::lyon::path::PathEvent;
+
+    // Without a sub-path in progress, the arc begins a new sub-path at its start.
+    let path = geom::path().arc_to(pt2(0.0, 0.0), 1.0, 0.0, PI).build();
+    let events: Vec<_> = path.iter().collect();
+    match events[0] {
+        PathEvent::Begin { at } => assert_eq!((at.x, at.y), (1.0, 0.0)),
+        ref e => panic!("unexpected event {:?}", e),
+    }
+    match events[events.len() - 1] {
+        PathEvent::End { last, .. } => {
+            assert!((last.x + 1.0).abs() < 1e-4 && last.y.abs() < 1e-4)
+        }
+        ref e => panic!("unexpected event {:?}", e),
+    }
+
+    // Otherwise a line joins the current position to the start of the arc.
+    let path = geom::path()
+        .move_to(pt2(2.0, 0.0))
+        .arc_to(pt2(0.0, 0.0), 1.0, 0.0, PI)
+        .build();
+    match path.iter().nth(1) {
+        Some(PathEvent::Line { from, to }) => {
+            assert_eq!((from.x, to.x), (2.0, 1.0));
+        }
+        e => panic!("unexpected event {:?}", e),
+    }
+}
+
+#[test]
+fn path_offset_test() {
+    use nannou::geom::path::{CapStyle, JoinStyle};
+
+    let square = [
+        pt2(0.0, 0.0),
+        pt2(10.0, 0.0),
+        pt2(10.0, 10.0),
+        pt2(0.0, 10.0),
+    ];
+    let path = geom::path().polygon(&square).build();
+    let bounds = |path: &geom::Path| {
+        let points = path.iter().filter_map(|e| match e {
+            nannou::lyon::path::PathEvent::Begin { at } => Some(pt2(at.x, at.y)),
+            nannou::lyon::path::PathEvent::Line { to, .. } => Some(pt2(to.x, to.y)),
+            nannou::lyon::path::PathEvent::End { .. } => None,
+            e => panic!("unexpected event {:?}", e),
+        });
+        geom::bounding_rect(points.collect::<Vec<_>>()).unwrap()
+    };
+    let assert_bounds = |path: &geom::Path, [l, b, r, t]: [f32; 4]| {
+        let rect = bounds(path);
+        let edges = [rect.left(), rect.bottom(), rect.right(), rect.top()];
+        for (edge, expected) in edges.iter().zip(&[l, b, r, t]) {
+            assert!(
+                (edge - expected).abs() < 1e-4,
+                "{:?} != {:?}",
+                edges,
+                [l, b, r, t]
+            );
+        }
+    };
+
+    let outset = path.offset(1.0, JoinStyle::Miter, CapStyle::Butt);
+    assert_eq!(outset.len(), 1);
+    assert_bounds(&outset[0], [-1.0, -1.0, 11.0, 11.0]);
+
+    let inset = path.inset(1.0);
+    assert_eq!(inset.len(), 1);
+    assert_bounds(&inset[0], [1.0, 1.0, 9.0, 9.0]);
+
+    // Insetting by more than half the width leaves nothing behind.
+    assert!(path.inset(6.0).is_empty());
+
+    // An open line becomes the outline of a stroke.
+    let line = geom::path()
+        .move_to(pt2(0.0, 0.0))
+        .line_to(pt2(10.0, 0.0))
+        .build();
+    let stroke = line.offset(1.0, JoinStyle::Round, CapStyle::Square);
+    assert_eq!(stroke.len(), 1);
+    assert_bounds(&stroke[0], [-1.0, -1.0, 11.0, 1.0]);
+}
+
+// Asserts that every triangle of the solid is wound counter-clockwise when viewed from outside.
+fn assert_ccw_winding(solid: &geom::Solid) {
+    for [a, b, c] in solid.triangles() {
+        let (a, b, c) = (a as usize, b as usize, c as usize);
+        let p = &solid.points;
+        let face_normal = (p[b] - p[a]).cross(p[c] - p[a]);
+        let n = &solid.normals;
+        let vertex_normal = n[a] + n[b] + n[c];
+        assert!(face_normal.dot(vertex_normal) > 0.0);
+    }
+}
+
+#[test]
+fn solid_test() {
+    let sphere = geom::solid::sphere(1.0, 16);
+    assert_eq!(sphere.vertex_count(), 9 * 17);
+    assert_eq!(sphere.triangle_count(), 2 * 16 * 7);
+    assert_ccw_winding(&sphere);
+
+    let box_3d = geom::solid::box_3d(1.0, 2.0, 3.0);
+    assert_eq!(box_3d.vertex_count(), 24);
+    assert_eq!(box_3d.triangle_count(), 12);
+    assert_ccw_winding(&box_3d);
+
+    let cylinder = geom::solid::cylinder(1.0, 2.0, 12);
+    assert_eq!(cylinder.vertex_count(), 4 * 12 + 6);
+    assert_eq!(cylinder.triangle_count(), 4 * 12);
+    assert_ccw_winding(&cylinder);
+
+    let cone = geom::solid::cone(1.0, 2.0, 12);
+    assert_eq!(cone.vertex_count(), 3 * 12 + 3);
+    assert_eq!(cone.triangle_count(), 2 * 12);
+    assert_ccw_winding(&cone);
+
+    let torus = geom::solid::torus(2.0, 0.5, 16, 8);
+    assert_eq!(torus.vertex_count(), 17 * 9);
+    assert_eq!(torus.triangle_count(), 2 * 16 * 8);
+    assert_ccw_winding(&torus);
+}
+
+#[test]
+fn rect_subdivide_test() {
+    let rect = geom::Rect::from_w_h(100.0, 50.0);
+    let cells: Vec<_> = rect.subdivide(4, 2).collect();
+    assert_eq!(cells.len(), 8);
+    assert_eq!(
+        cells[0],
+        geom::Rect::from_corners(pt2(-50.0, 0.0), pt2(-25.0, 25.0))
+    );
+    assert_eq!(
+        cells[7],
+        geom::Rect::from_corners(pt2(25.0, -25.0), pt2(50.0, 0.0))
+    );
+    assert_eq!(rect.subdivide(0, 3).count(), 0);
+
+    let cells: Vec<_> = rect.subdivide_with_gap(2, 1, 10.0).collect();
+    assert_eq!(cells[0].w(), 45.0);
+    assert_eq!(cells[0].left(), rect.left());
+    assert_eq!(cells[1].right(), rect.right());
+    assert_eq!(cells[1].left() - cells[0].right(), 10.0);
+
+    assert_eq!(rect.row(0, 2), cells_row(&rect, 0.0, 25.0));
+    assert_eq!(rect.row(1, 2), cells_row(&rect, -25.0, 0.0));
+    assert_eq!(rect.col(3, 4).x, geom::Range::new(25.0, 50.0));
+    assert_eq!(rect.inset(5.0).w_h(), (90.0, 40.0));
+    assert_eq!(rect.pad_xy(10.0, 5.0).w_h(), (80.0, 40.0));
+    assert_eq!(rect.aspect_ratio(), 2.0);
+}
+
+#[test]
+fn rect_lerp_test() {
+    let rect = geom::Rect::from_w_h(100.0, 50.0);
+    assert_eq!(rect.lerp_point(0.0, 0.0), rect.bottom_left());
+    assert_eq!(rect.lerp_point(1.0, 1.0), rect.top_right());
+    assert_eq!(rect.lerp_point(0.75, 0.5), pt2(25.0, 0.0));
+    assert_eq!(rect.normalise_point(pt2(25.0, 0.0)), pt2(0.75, 0.5));
+
+    let src = geom::Rect::from_w_h(2.0, 2.0);
+    assert_eq!(
+        rect.map_point_from(pt2(1.0, -1.0), &src),
+        rect.bottom_right()
+    );
+    assert_eq!(rect.clamp_point(pt2(80.0, -10.0)), pt2(50.0, -10.0));
+    assert_eq!(
+        rect.lerp_rect(0.0, 0.5, 0.5, 1.0),
+        geom::Rect::from_corners(pt2(-50.0, 0.0), pt2(0.0, 25.0))
+    );
+}
+
+#[test]
+fn ellipse_arc_length_test() {
+    let circle = geom::Ellipse::new(geom::Rect::from_w_h(2.0, 2.0), 32);
+    assert!((circle.perimeter() - 2.0 * PI).abs() < 1e-5);
+    assert!((circle.point_at_angle(PI / 2.0) - pt2(0.0, 1.0)).magnitude() < 1e-6);
+    assert!((circle.tangent_at_angle(0.0) - pt2(0.0, 1.0)).magnitude() < 1e-6);
+    assert!(circle.contains(pt2(0.5, 0.5)));
+    assert!(!circle.contains(pt2(0.8, 0.8)));
+
+    // Points at equal fractions are equally spaced along a non-circular ellipse.
+    let ellipse = geom::Ellipse::new(geom::Rect::from_w_h(400.0, 100.0), 32);
+    let lut = ellipse.arc_length_lut(1024);
+    assert!((lut.total_length() - ellipse.perimeter()).abs() < 0.1);
+    let points: Vec<_> = (0..8)
+        .map(|i| lut.point_at_fraction(i as f32 / 8.0))
+        .collect();
+    let spacing = lut.total_length() / 8.0;
+    for pair in points.windows(2) {
+        // Chords are slightly shorter than the arcs they span.
+        let chord = (pair[1] - pair[0]).magnitude();
+        assert!(chord <= spacing && chord > spacing * 0.9);
+    }
+}
+
+fn cells_row(rect: &geom::Rect, bottom: f32, top: f32) -> geom::Rect {
+    geom::Rect {
+        x: rect.x,
+        y: geom::Range::new(bottom, top),
+    }
+}
+
+#[test]
+fn kdtree_test() {
+    let points: Vec<_> = (0..100)
+        .map(|i| (pt2((i % 10) as f32, (i / 10) as f32), i))
+        .collect();
+    let tree = geom::KdTree2::build(points.clone());
+    let (dist, &nearest) = tree.nearest(pt2(3.2, 4.1)).unwrap();
+    assert_eq!(nearest, 43);
+    assert!((dist - pt2(0.2, 0.1).magnitude()).abs() < 1e-5);
+    let k = tree.k_nearest(pt2(0.0, 0.0), 3);
+    assert_eq!(k.len(), 3);
+    assert_eq!(*k[0].1, 0);
+    assert_eq!(k[2].0, 1.0);
+    let mut within: Vec<_> = tree
+        .within_radius(pt2(5.0, 5.0), 1.0)
+        .into_iter()
+        .cloned()
+        .collect();
+    within.sort();
+    assert_eq!(within, vec![45, 54, 55, 56, 65]);
+
+    let mut dynamic = geom::DynamicKdTree2::new();
+    for (p, i) in points {
+        dynamic.insert(p, i);
+    }
+    assert_eq!(dynamic.len(), 100);
+    assert_eq!(dynamic.nearest(pt2(9.0, 9.0)), Some((0.0, &99)));
+    assert_eq!(dynamic.within_radius(pt2(5.0, 5.0), 1.0).len(), 5);
+}
+
+#[test]
+fn tri_barycentric_test() {
+    let tri = geom::Tri([pt2(0.0, 0.0), pt2(4.0, 0.0), pt2(0.0, 4.0)]);
+    let bc = tri.barycentric(pt2(1.0, 1.0));
+    assert!((bc.x - 0.5).abs() < 1e-6);
+    assert!((bc.y - 0.25).abs() < 1e-6);
+    assert!((bc.z - 0.25).abs() < 1e-6);
+    assert!(tri.contains_point(pt2(1.0, 1.0)));
+    assert!(!tri.contains_point(pt2(3.0, 3.0)));
+    assert_eq!(tri.area(), 8.0);
+    assert_eq!(tri.circumcenter(), Some(pt2(2.0, 2.0)));
+    let value = tri.interpolate(pt2(1.0, 1.0), &0.0f32, &4.0, &8.0);
+    assert!((value - 3.0).abs() < 1e-6);
+    let tri3 = geom::Tri([pt3(0.0, 0.0, 0.0), pt3(1.0, 0.0, 0.0), pt3(0.0, 1.0, 0.0)]);
+    assert_eq!(tri3.normal(), vec3(0.0, 0.0, 1.0));
+}
+
+#[test]
+fn delaunay_test() {
+    let points = [
+        pt2(0.0, 0.0),
+        pt2(1.0, 0.0),
+        pt2(1.0, 1.0),
+        pt2(0.0, 1.0),
+        pt2(0.5, 0.4),
+    ];
+    let tris = geom::delaunay::triangulate(&points);
+    assert_eq!(tris.len(), 4);
+    let area: f32 = tris.iter().map(|t| t.area()).sum();
+    assert!((area - 1.0).abs() < 1e-5);
+    // No point may lie strictly within the circumcircle of any triangle.
+    for tri in &tris {
+        let center = tri.circumcenter().unwrap();
+        let radius2 = (tri[0] - center).magnitude2();
+        for &p in &points {
+            assert!((p - center).magnitude2() >= radius2 - 1e-5);
+        }
+    }
+}
+
+#[test]
+fn shaping_functions_test() {
+    assert_eq!(smoothstep(0.0f32, 1.0, -1.0), 0.0);
+    assert_eq!(smoothstep(0.0f32, 1.0, 2.0), 1.0);
+    assert_eq!(smootherstep(0.0f32, 1.0, 0.5), 0.5);
+    assert!(smootherstep(0.0f32, 1.0, 0.25) < smoothstep(0.0, 1.0, 0.25));
+    assert_eq!(step(0.5f32, 0.4), 0.0);
+    assert_eq!(step(0.5f32, 0.5), 1.0);
+    assert_eq!(fract(-1.25f32), 0.75);
+    assert_eq!(mix(2.0f32, 4.0, 0.5), 3.0);
+    assert_eq!(remap(5.0f32, 0.0, 10.0, 100.0, 200.0), 150.0);
+    assert_eq!(remap_clamp(20.0f32, 0.0, 10.0, 100.0, 200.0), 200.0);
+    let v = smoothstep(
+        vec3(0.0, 0.0, 0.0),
+        vec3(1.0, 2.0, 4.0),
+        vec3(0.5, 1.0, 8.0),
+    );
+    assert_eq!(v, vec3(0.5, 0.5, 1.0));
+    assert_eq!(mix(vec2(0.0, 10.0), vec2(10.0, 20.0), 0.5), vec2(5.0, 15.0));
+    assert_eq!(
+        step(vec4(0.5, 0.5, 0.5, 0.5), vec4(0.0, 1.0, 0.5, 0.2)),
+        vec4(0.0, 1.0, 1.0, 0.0)
+    );
+}
+
+#[test]
+fn polygon_simplify_test() {
+    use nannou::geom::polygon;
+    // A square with redundant, nearly collinear points along each edge.
+    let mut points = vec![];
+    for &(a, b) in &[
+        (pt2(0.0, 0.0), pt2(10.0, 0.0)),
+        (pt2(10.0, 0.0), pt2(10.0, 10.0)),
+        (pt2(10.0, 10.0), pt2(0.0, 10.0)),
+        (pt2(0.0, 10.0), pt2(0.0, 0.0)),
+    ] {
+        for i in 0..5 {
+            let jitter = if i % 2 == 1 { 0.01 } else { 0.0 };
+            points.push(a + (b - a) * (i as f32 / 5.0) + vec2(jitter, jitter));
+        }
+    }
+    let simplified = polygon::simplify(&points, 0.1);
+    assert_eq!(simplified.len(), 4);
+    let visvalingam = polygon::simplify_visvalingam(&points, 4);
+    assert_eq!(visvalingam.len(), 4);
+    for corner in &[
+        pt2(0.0, 0.0),
+        pt2(10.0, 0.0),
+        pt2(10.0, 10.0),
+        pt2(0.0, 10.0),
+    ] {
+        assert!(simplified.contains(corner));
+        assert!(visvalingam.contains(corner));
+    }
+    let smoothed = polygon::smooth(&simplified, 2, 0.25);
+    assert_eq!(smoothed.len(), 16);
+    // Corner cutting keeps all points within the original square.
+    for p in &smoothed {
+        assert!(p.x >= 0.0 && p.x <= 10.0 && p.y >= 0.0 && p.y <= 10.0);
+    }
+    let owned = geom::Polygon::new(points.iter().cloned()).simplify(0.1);
+    assert_eq!(owned.points.count(), 4);
+}
@@ -0,0 +1,46 @@
+use nannou::event::{Gesture, GestureRecogniser, TouchEvent, TouchPhase};
+use nannou::geom::pt2;
+use std::time::{Duration, Instant};
+
+fn touch(id: u64, phase: TouchPhase, x: f32, y: f32) -> TouchEvent {
+    let position = pt2(x, y);
+    TouchEvent {
+        id,
+        phase,
+        position,
+    }
+}
+
+#[test]
+fn pinch_after_threshold_test() {
+    let mut recogniser = GestureRecogniser::new().pinch_threshold(0.1);
+    let now = Instant::now();
+    recogniser.touch_at(&touch(0, TouchPhase::Started, -50.0, 0.0), now);
+    recogniser.touch_at(&touch(1, TouchPhase::Started, 50.0, 0.0), now);
+
+    // A 5% change is within the threshold.
+    let gestures = recogniser.touch_at(&touch(1, TouchPhase::Moved, 55.0, 0.0), now);
+    assert!(gestures.is_empty());
+
+    // Beyond the threshold the pinch begins.
+    let gestures = recogniser.touch_at(&touch(1, TouchPhase::Moved, 70.0, 0.0), now);
+    match gestures[..] {
+        [Gesture::Pinch { scale_delta, .. }] => assert!((scale_delta - 120.0 / 105.0).abs() < 1e-5),
+        _ => panic!("expected a pinch, found {:?}", gestures),
+    }
+}
+
+#[test]
+fn double_tap_test() {
+    let mut recogniser = GestureRecogniser::new();
+    let now = Instant::now();
+    let ms = Duration::from_millis;
+    recogniser.touch_at(&touch(0, TouchPhase::Started, 10.0, 10.0), now);
+    let first = recogniser.touch_at(&touch(0, TouchPhase::Ended, 10.0, 10.0), now + ms(50));
+    recogniser.touch_at(&touch(1, TouchPhase::Started, 12.0, 10.0), now + ms(150));
+    let second = recogniser.touch_at(&touch(1, TouchPhase::Ended, 12.0, 10.0), now + ms(200));
+    match (&first[..], &second[..]) {
+        ([Gesture::Tap { count: 1, .. }], [Gesture::Tap { count: 2, .. }]) => (),
+        _ => panic!("expected a double tap, found {:?} {:?}", first, second),
+    }
+}
@@ -0,0 +1,47 @@
+#![cfg(feature = "laser")]
+
+use nannou::color::lin_srgba;
+use nannou::geom::{self, pt2};
+use nannou::laser::{self, Frame, Point};
+
+#[test]
+fn laser_frame_from_draw_path_test() {
+    let path = geom::path()
+        .move_to(pt2(-1.0, 0.0))
+        .line_to(pt2(0.0, 0.0))
+        .move_to(pt2(0.5, 0.5))
+        .line_to(pt2(0.5, 1.0))
+        .build();
+    let color = lin_srgba(1.0, 0.0, 0.5, 1.0);
+    let frame = Frame::from_draw_path(&path, color, 30);
+
+    // The points are split between the sub-paths according to their length.
+    let lit: Vec<_> = frame.points().iter().filter(|p| !p.is_blank()).collect();
+    assert_eq!(lit.len(), 30);
+    assert_eq!((lit[0].x, lit[0].y), (-std::i16::MAX, 0));
+    assert_eq!((lit[19].x, lit[19].y), (0, 0));
+    assert_eq!(
+        (lit[20].x, lit[20].y),
+        (std::i16::MAX / 2, std::i16::MAX / 2)
+    );
+    assert_eq!(lit[0].r, std::u16::MAX);
+    assert_eq!(lit[0].g, 0);
+
+    // The laser is blanked while moving between the sub-paths.
+    let blanked = frame.len() - lit.len();
+    assert!(blanked >= 2 * laser::BLANK_DWELL_POINTS);
+    let first_blank = frame.points().iter().position(|p| p.is_blank()).unwrap();
+    assert_eq!(first_blank, 20);
+    let p = frame.points()[first_blank];
+    assert_eq!((p.x, p.y), (0, 0));
+}
+
+#[test]
+fn laser_point_test() {
+    let p = Point::new(10, -10, 100, 300, 200);
+    assert_eq!(p.i, 300);
+    assert!(!p.is_blank());
+    let b = p.blanked();
+    assert!(b.is_blank());
+    assert_eq!((b.x, b.y), (10, -10));
+}
@@ -0,0 +1,41 @@
+use nannou::geom::pt2;
+use nannou::lsystem::{self, LSystem, Turtle};
+use nannou::lyon::path::PathEvent;
+
+#[test]
+fn lsystem_rewrite_test() {
+    // Lindenmayer's original model of algae growth.
+    let mut system = LSystem::new("A");
+    system.add_rule('A', "AB");
+    system.add_rule('B', "A");
+    assert_eq!(system.step(), "AB");
+    assert_eq!(system.step(), "ABA");
+    assert_eq!(system.step(), "ABAAB");
+    assert_eq!(system.generation(), 3);
+    assert_eq!(system.nth(5), "ABAABABAABAAB");
+    assert_eq!(system.current(), "ABAAB");
+    system.reset();
+    assert_eq!(system.current(), "A");
+}
+
+#[test]
+fn turtle_branching_test() {
+    let mut turtle = Turtle::new();
+    let commands = lsystem::default_commands(10.0, 90.0);
+    turtle.interpret("F[+F]F", &commands);
+    // After the branch, the turtle continues from the top of the first segment.
+    let p = turtle.position();
+    assert!((p - pt2(0.0, 20.0)).magnitude() < 1e-4);
+    assert_eq!(turtle.heading(), 0.0);
+    // The branch continues the trunk, while the segment after the branch begins a new line.
+    let (mut begins, mut lines) = (0, 0);
+    for event in turtle.path().iter() {
+        match event {
+            PathEvent::Begin { .. } => begins += 1,
+            PathEvent::Line { .. } => lines += 1,
+            _ => (),
+        }
+    }
+    assert_eq!(begins, 2);
+    assert_eq!(lines, 3);
+}
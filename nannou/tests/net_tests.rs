@@ -0,0 +1,67 @@
+use nannou::net::{BeatClock, ControlRequest, ControlServer, UrlScheme};
+use std::io::{Read, Write};
+use std::net::TcpStream;
+
+#[test]
+fn control_request_from_target_test() {
+    let request = ControlRequest::from_target("/play?bpm=120&name=a+b%21&flag");
+    assert_eq!(request.path(), "/play");
+    let params = request.params();
+    assert_eq!(params.get("bpm"), Some(&"120"));
+    assert_eq!(params.get("name"), Some(&"a b!"));
+    assert_eq!(params.get("flag"), Some(&""));
+    assert_eq!(params.len(), 3);
+
+    // `+` only describes a space within the query.
+    let request = ControlRequest::from_target("/a+b%20c?x=1+2");
+    assert_eq!(request.path(), "/a+b c");
+    assert_eq!(request.params().get("x"), Some(&"1 2"));
+
+    let request = ControlRequest::from_target("/");
+    assert_eq!(request.path(), "/");
+    assert!(request.params().is_empty());
+}
+
+#[test]
+fn control_server_loopback_test() {
+    let server = ControlServer::bind(0).unwrap();
+    let port = server.addr().port();
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+    write!(
+        stream,
+        "GET /play?bpm=140 HTTP/1.1\r\nHost: localhost\r\n\r\n"
+    )
+    .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK"));
+    // The request is queued before the response is written.
+    let requests: Vec<_> = server.try_iter().collect();
+    assert_eq!(requests, vec![ControlRequest::from_target("/play?bpm=140")]);
+}
+
+#[test]
+fn url_scheme_matches_test() {
+    let scheme = UrlScheme::new("nannou://");
+    assert!(scheme.matches("nannou://play?bpm=120"));
+    assert!(scheme.matches("NANNOU://play"));
+    assert!(!scheme.matches("http://nannou.cc"));
+    assert!(!scheme.matches("nannou"));
+    assert_eq!(scheme.scheme(), "nannou");
+}
+
+#[test]
+fn beat_clock_test() {
+    let mut clock = BeatClock::new(120.0);
+    let now = std::time::Instant::now();
+    let beat = clock.beat_at(now + std::time::Duration::from_secs(1));
+    assert!((beat - clock.beat_at(now) - 2.0).abs() < 1e-6);
+    assert!(clock.phase() >= 0.0 && clock.phase() < 1.0);
+    assert!(clock.is_synced());
+
+    // Changing the tempo preserves the current beat.
+    let before = clock.beat();
+    clock.set_bpm(60.0);
+    assert_eq!(clock.bpm(), 60.0);
+    assert!((clock.beat() - before).abs() < 0.01);
+}
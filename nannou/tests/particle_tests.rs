@@ -0,0 +1,22 @@
+use nannou::color::WHITE;
+use nannou::geom::{pt2, vec2};
+use nannou::particle::{Force, ParticleSystem};
+
+#[test]
+fn particle_system_len_test() {
+    let mut system = ParticleSystem::new(3);
+    assert!(system.is_empty());
+    system.emit(pt2(0.0, 0.0), vec2(1.0, 0.0), 1.0, WHITE);
+    system.emit(pt2(0.0, 0.0), vec2(0.0, 1.0), 2.0, WHITE);
+    assert_eq!(system.len(), 2);
+    system.update(1.5, &[Force::Gravity(vec2(0.0, -9.8)), Force::Drag(0.1)]);
+    assert_eq!(system.len(), 1);
+    // Emitting beyond the capacity replaces the oldest particles.
+    for _ in 0..3 {
+        system.emit(pt2(0.0, 0.0), vec2(0.0, 0.0), 1.0, WHITE);
+    }
+    assert_eq!(system.len(), 3);
+    assert_eq!(system.max_count(), 3);
+    system.update(1.0, &[]);
+    assert!(system.is_empty());
+}
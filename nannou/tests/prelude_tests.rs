@@ -0,0 +1,17 @@
+#[test]
+fn slim_prelude_test() {
+    use nannou::prelude::math::*;
+    use nannou::prelude::slim::*;
+
+    // The slim prelude exports no vector types, so these names remain free for local use.
+    #[derive(Debug, PartialEq)]
+    struct Vector2(f32, f32);
+
+    let draw = Draw::new();
+    draw.ellipse().xy(vec2(1.0, 2.0));
+    let v: NanVec2 = vec2(3.0, 4.0);
+    assert_eq!(v, nannou::geom::Vector2::new(3.0, 4.0));
+    assert_eq!(Vector2(v.x, v.y), Vector2(3.0, 4.0));
+    let m = NanMat4::from_scale(2.0);
+    assert_eq!(m, nannou::math::Matrix4::from_scale(2.0));
+}
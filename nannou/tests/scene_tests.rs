@@ -0,0 +1,24 @@
+use nannou::math::cgmath::Vector3;
+use nannou::math::{Matrix4, SquareMatrix};
+use nannou::scene::Scene;
+
+#[test]
+fn scene_world_transform_test() {
+    let mut scene = Scene::new();
+    let root = scene.add_node(None);
+    let arm = scene.add_node(Some(root));
+    let hand = scene.add_node(Some(arm));
+    assert_eq!(scene.node(arm).parent(), Some(root));
+    assert_eq!(scene.node(root).children(), &[arm]);
+    assert_eq!(scene.world_transform(hand), Matrix4::identity());
+
+    let translate = |x, y| Matrix4::from_translation(Vector3::new(x, y, 0.0));
+    scene.set_transform(root, translate(10.0, 0.0));
+    scene.set_transform(arm, translate(0.0, 5.0));
+    assert_eq!(scene.world_transform(hand), translate(10.0, 5.0));
+
+    // Changing an ancestor invalidates the cached transforms of its descendants.
+    scene.set_transform(root, translate(-10.0, 0.0));
+    assert_eq!(scene.world_transform(hand), translate(-10.0, 5.0));
+    assert_eq!(scene.world_transform(arm), translate(-10.0, 5.0));
+}
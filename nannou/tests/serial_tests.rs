@@ -0,0 +1,23 @@
+#![cfg(feature = "serial")]
+
+use nannou::serial::parse_csv_floats;
+
+#[test]
+fn parse_csv_floats_test() {
+    assert_eq!(
+        parse_csv_floats("0.5,12,-3.25"),
+        Some(vec![0.5, 12.0, -3.25])
+    );
+    // Whitespace and line endings around values are ignored.
+    assert_eq!(parse_csv_floats(" 1 , 2.5 \r\n"), Some(vec![1.0, 2.5]));
+    assert_eq!(parse_csv_floats("42"), Some(vec![42.0]));
+    assert_eq!(parse_csv_floats(""), Some(vec![]));
+    assert_eq!(parse_csv_floats("   "), Some(vec![]));
+}
+
+#[test]
+fn parse_csv_floats_invalid_test() {
+    assert_eq!(parse_csv_floats("1,two,3"), None);
+    assert_eq!(parse_csv_floats("1,,3"), None);
+    assert_eq!(parse_csv_floats("1,2,"), None);
+}
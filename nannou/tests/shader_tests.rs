@@ -0,0 +1,31 @@
+// Requires the `hotglsl` feature, e.g. `cargo test -p nannou --features hotglsl --test shader_tests`.
+#![cfg(feature = "hotglsl")]
+
+use nannou::wgpu;
+
+#[test]
+fn shader_handle_surfaces_compile_error_test() {
+    let dir = std::env::temp_dir().join("nannou_shader_tests");
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("bad.frag");
+    let glsl = "#version 450\nvoid main() {\n    undeclared_variable = 1.0;\n}\n";
+    std::fs::write(&path, glsl).unwrap();
+
+    let shader = wgpu::ShaderHandle::new(&path);
+    assert_eq!(shader.version(), 0);
+    let error = shader.error().expect("expected a compilation error");
+    assert!(error.contains("undeclared_variable"), "{}", error);
+}
+
+#[test]
+fn shader_handle_compiles_valid_shader_test() {
+    let dir = std::env::temp_dir().join("nannou_shader_tests");
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("good.frag");
+    let glsl = "#version 450\nlayout(location = 0) out vec4 f_color;\nvoid main() {\n    f_color = vec4(1.0);\n}\n";
+    std::fs::write(&path, glsl).unwrap();
+
+    let shader = wgpu::ShaderHandle::new(&path);
+    assert_eq!(shader.version(), 1);
+    assert!(shader.error().is_none());
+}
@@ -0,0 +1,59 @@
+use nannou::color::{BLACK, RED};
+use nannou::draw::{ArrowHead, Draw};
+
+#[test]
+fn svg_rect_path_test() {
+    let draw = Draw::new();
+    draw.rect().x_y(10.0, 20.0).w_h(20.0, 10.0).color(RED);
+    let svg = draw.to_svg(200, 100);
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.contains("viewBox=\"0 0 200 100\""));
+    // The origin is at the centre with the y axis flipped.
+    assert!(svg.contains("0,35 "));
+    assert!(svg.contains("20,25 "));
+    assert!(svg.contains("fill=\"#ff0000\" fill-opacity=\"1\""));
+    assert!(svg.trim_end().ends_with("</svg>"));
+}
+
+#[test]
+fn svg_background_and_stroke_test() {
+    let draw = Draw::new();
+    draw.background().color(BLACK);
+    draw.line()
+        .points([0.0, 0.0].into(), [10.0, 0.0].into())
+        .weight(2.0);
+    let svg = draw.to_svg(100, 100);
+    assert!(svg.contains("<rect width=\"100%\" height=\"100%\" fill=\"#000000\""));
+    assert!(svg.contains("fill=\"none\""));
+    assert!(svg.contains("stroke-width=\"2\""));
+}
+
+#[test]
+fn svg_arrow_head_styles_test() {
+    let draw = Draw::new();
+    draw.arrow()
+        .points([0.0, 0.0].into(), [50.0, 0.0].into())
+        .head_style(ArrowHead::Filled);
+    draw.arrow()
+        .points([0.0, 10.0].into(), [50.0, 10.0].into())
+        .head_style(ArrowHead::Open);
+    draw.arrow()
+        .points([0.0, 20.0].into(), [50.0, 20.0].into())
+        .head_style(ArrowHead::Circle);
+    let svg = draw.to_svg(100, 100);
+    // The shaft and head of each arrow are a single path.
+    assert_eq!(svg.matches("<path").count(), 3);
+    assert_eq!(svg.matches("fill=\"none\"").count(), 1);
+}
+
+#[cfg(feature = "notosans")]
+#[test]
+fn svg_text_outline_test() {
+    let draw = Draw::new();
+    draw.text("nannou").color(RED);
+    let svg = draw.to_svg(200, 100);
+    // Text is recorded as the filled outlines of its glyphs rather than as a text element.
+    assert!(!svg.contains("<text"));
+    assert!(svg.contains("fill=\"#ff0000\" fill-opacity=\"1\" fill-rule=\"nonzero\""));
+    assert!(svg.contains('Q'));
+}
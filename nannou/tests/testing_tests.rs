@@ -0,0 +1,49 @@
+use nannou::image::{DynamicImage, Rgba, RgbaImage};
+use nannou::testing;
+
+fn gradient(offset: u8) -> DynamicImage {
+    let image = RgbaImage::from_fn(32, 32, |x, y| {
+        let v = (x * 4 + y * 2) as u8;
+        Rgba([v.saturating_add(offset), v, v, 255])
+    });
+    DynamicImage::ImageRgba8(image)
+}
+
+#[test]
+fn ssim_identical_test() {
+    let image = gradient(0);
+    assert_eq!(testing::ssim(&image, &image), 1.0);
+}
+
+#[test]
+fn ssim_different_test() {
+    let (a, b) = (gradient(0), gradient(80));
+    let similarity = testing::ssim(&a, &b);
+    assert!(similarity < 1.0);
+    assert!(similarity > testing::ssim(&a, &DynamicImage::new_rgba8(32, 32)));
+}
+
+#[test]
+fn ssim_ignores_alpha_test() {
+    let a = gradient(0);
+    let mut b = a.to_rgba();
+    for pixel in b.pixels_mut() {
+        pixel[3] = 0;
+    }
+    assert_eq!(testing::ssim(&a, &DynamicImage::ImageRgba8(b)), 1.0);
+}
+
+#[test]
+fn ssim_edges_test() {
+    // The windows do not evenly divide 34 pixels, so the far edges need windows of their own.
+    let a = DynamicImage::new_rgba8(34, 34);
+    let mut b = a.to_rgba();
+    b.put_pixel(33, 33, Rgba([255, 255, 255, 255]));
+    assert!(testing::ssim(&a, &DynamicImage::ImageRgba8(b)) < 1.0);
+}
+
+#[test]
+fn diff_test() {
+    let diff = testing::diff(&gradient(0), &gradient(10));
+    assert_eq!(diff.get_pixel(0, 0), &Rgba([10, 0, 0, 255]));
+}
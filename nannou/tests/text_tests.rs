@@ -0,0 +1,85 @@
+#![cfg(feature = "notosans")]
+
+use nannou::text::{self, line, Wrap};
+
+#[test]
+fn unicode_wrap_test() {
+    let font = text::font::default_notosans();
+    let font_size = 16;
+    let string = "the quick brown fox jumps over the lazy dog";
+    let max_width = line::width("the quick brown ", &font, font_size);
+    let infos: Vec<_> =
+        line::infos_maybe_wrapped(string, &font, font_size, Some(Wrap::Unicode), max_width)
+            .collect();
+    assert!(infos.len() > 1);
+    for info in &infos {
+        assert!(info.width <= max_width);
+        // Lines should only ever begin at the start of a word.
+        let line = &string[info.start_byte..info.end_break.byte_index()];
+        assert!(!line.starts_with(' '));
+        assert!(!line.is_empty());
+    }
+    let first = &infos[0];
+    assert_eq!(&string[..first.end_break.byte_index()], "the quick brown");
+}
+
+fn median(pixel: &[u8]) -> u8 {
+    let (r, g, b) = (pixel[0], pixel[1], pixel[2]);
+    r.min(g).max(r.max(g).min(b))
+}
+
+#[test]
+fn sdf_generate_test() {
+    let font = text::font::default_notosans();
+    let bitmap = text::sdf::generate(&font, font.glyph('o').id()).expect("no outline");
+    assert!(bitmap.width > 0 && bitmap.height > 0);
+    assert_eq!(
+        bitmap.pixels.len(),
+        bitmap.width as usize * bitmap.height as usize * 4
+    );
+    // The corners of the bitmap lie outside of the glyph, while some of its pixels lie within.
+    let pixel = |x: u32, y: u32| {
+        let i = (y * bitmap.width + x) as usize * 4;
+        median(&bitmap.pixels[i..i + 4])
+    };
+    assert!(pixel(0, 0) < 128);
+    assert!(pixel(bitmap.width - 1, bitmap.height - 1) < 128);
+    assert!(bitmap.pixels.chunks(4).any(|p| median(p) > 128));
+    // The centre of the `o` lies within its counter.
+    assert!(pixel(bitmap.width / 2, bitmap.height / 2) < 128);
+
+    // Glyphs without an outline have no distance field.
+    assert!(text::sdf::generate(&font, font.glyph(' ').id()).is_none());
+}
+
+#[test]
+fn sdf_atlas_test() {
+    let font = text::font::default_notosans();
+    let font_id = text::font::id(&font).index();
+    let mut atlas = text::sdf::SdfAtlas::new(16);
+    assert!(!atlas.requires_upload);
+
+    // The atlas grows to fit the first glyph.
+    let a = atlas.glyph(font_id, &font, font.glyph('a').id()).unwrap();
+    assert!(atlas.requires_upload);
+    let (w, h) = atlas.dimensions();
+    assert!(w > 16 && w == h);
+    assert_eq!(atlas.pixels().len(), w as usize * h as usize * 4);
+    assert!(a.x + a.width <= w && a.y + a.height <= h);
+
+    // Glyphs are only generated once.
+    atlas.requires_upload = false;
+    assert_eq!(atlas.glyph(font_id, &font, font.glyph('a').id()), Some(a));
+    assert!(!atlas.requires_upload);
+
+    // Distinct glyphs do not overlap.
+    let b = atlas.glyph(font_id, &font, font.glyph('b').id()).unwrap();
+    let overlap_x = a.x < b.x + b.width && b.x < a.x + a.width;
+    let overlap_y = a.y < b.y + b.height && b.y < a.y + a.height;
+    assert!(!(overlap_x && overlap_y));
+    assert_eq!(atlas.glyph(font_id, &font, font.glyph(' ').id()), None);
+
+    let [left, top, right, bottom] = atlas.uv_rect(&b);
+    assert!(0.0 <= left && left < right && right <= 1.0);
+    assert!(0.0 <= top && top < bottom && bottom <= 1.0);
+}
@@ -0,0 +1,63 @@
+use nannou::time::{Clock, Sequence, Timer};
+use std::time::Duration;
+
+#[test]
+fn timer_from_clock_test() {
+    let clock = Clock::default();
+    let mut timer = Timer::from_clock(2.0, clock.clone());
+    assert_eq!(timer.elapsed_fraction(), 0.0);
+    assert!(!timer.finished());
+    clock.set(Duration::from_secs(1));
+    assert_eq!(timer.elapsed_fraction(), 0.5);
+    assert_eq!(timer.remaining_secs(), 1.0);
+    clock.set(Duration::from_secs(3));
+    assert_eq!(timer.elapsed_fraction(), 1.0);
+    assert!(timer.finished());
+    timer.reset();
+    assert_eq!(timer.elapsed_fraction(), 0.0);
+}
+
+#[test]
+fn sequence_test() {
+    let mut sequence = Sequence::new()
+        .wait(1.0)
+        .then(|calls: &mut Vec<u32>| calls.push(1))
+        .wait(2.0)
+        .then(|calls: &mut Vec<u32>| calls.push(2))
+        .then(|calls: &mut Vec<u32>| calls.push(3));
+    let mut calls = vec![];
+    sequence.advance(0.5, &mut calls);
+    assert!(calls.is_empty());
+    sequence.advance(0.5, &mut calls);
+    assert_eq!(calls, vec![1]);
+    sequence.advance(1.0, &mut calls);
+    assert_eq!(calls, vec![1]);
+    assert!(!sequence.finished());
+    sequence.advance(5.0, &mut calls);
+    assert_eq!(calls, vec![1, 2, 3]);
+    assert!(sequence.finished());
+    sequence.reset();
+    sequence.advance(3.0, &mut calls);
+    assert_eq!(calls, vec![1, 2, 3, 1, 2, 3]);
+}
+
+#[test]
+fn frame_times_test() {
+    use nannou::state::time::FrameTimes;
+    use std::time::Instant;
+
+    let mut times = FrameTimes::default();
+    let start = Instant::now();
+    times.record(start);
+    assert_eq!(times.fps(), 0.0);
+    assert!(times.history(10).is_empty());
+
+    for i in 1..=FrameTimes::HISTORY_LEN + 10 {
+        times.record(start + Duration::from_millis(20 * i as u64));
+    }
+    assert_eq!(times.delta(), Duration::from_millis(20));
+    assert!((times.fps() - 50.0).abs() < 1e-9);
+    assert!((times.fps_smoothed() - 50.0).abs() < 1e-9);
+    assert_eq!(times.history(5).len(), 5);
+    assert_eq!(times.history(1000).len(), FrameTimes::HISTORY_LEN);
+}
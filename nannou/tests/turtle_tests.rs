@@ -0,0 +1,21 @@
+use nannou::geom::pt2;
+use nannou::turtle::Turtle;
+
+#[test]
+fn turtle_movement_test() {
+    let mut turtle = Turtle::new();
+    turtle.forward(10.0);
+    assert!((turtle.position() - pt2(0.0, 10.0)).magnitude() < 1e-4);
+    turtle.turn_right(90.0);
+    turtle.forward(5.0);
+    assert!((turtle.position() - pt2(5.0, 10.0)).magnitude() < 1e-4);
+    turtle.turn_left(180.0);
+    assert_eq!(turtle.heading(), 270.0);
+    turtle.backward(5.0);
+    assert!((turtle.position() - pt2(10.0, 10.0)).magnitude() < 1e-4);
+    turtle.pen_up();
+    assert!(!turtle.is_pen_down());
+    turtle.home();
+    assert_eq!(turtle.position(), pt2(0.0, 0.0));
+    assert_eq!(turtle.heading(), 0.0);
+}
@@ -0,0 +1,30 @@
+use nannou::ease;
+use nannou::tween::Tween;
+
+#[test]
+fn tween_update_test() {
+    let mut tween = Tween::new(0.0, 10.0, 2.0, ease::linear);
+    assert_eq!(tween.value(), 0.0);
+    assert_eq!(tween.update(0.5), 2.5);
+    assert_eq!(tween.update(0.5), 5.0);
+    assert!(!tween.finished());
+    assert_eq!(tween.update(5.0), 10.0);
+    assert!(tween.finished());
+    tween.reset();
+    assert_eq!(tween.value(), 0.0);
+}
+
+#[test]
+fn tween_chain_test() {
+    let mut chain = Tween::new(0.0, 1.0, 1.0, ease::linear)
+        .chained(Tween::new(1.0, 3.0, 1.0, ease::linear))
+        .chained(Tween::new(3.0, 0.0, 1.0, ease::quad_in));
+    assert_eq!(chain.duration_secs(), 3.0);
+    assert_eq!(chain.update(0.5), 0.5);
+    // The remaining time of the first tween carries over into the second.
+    assert_eq!(chain.update(1.0), 2.0);
+    assert_eq!(chain.update(1.0), 2.25);
+    assert!(!chain.finished());
+    assert_eq!(chain.update(1.0), 0.0);
+    assert!(chain.finished());
+}
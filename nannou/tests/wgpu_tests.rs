@@ -0,0 +1,148 @@
+use nannou::wgpu;
+
+#[test]
+fn texture_builder_usage_test() {
+    let desc = wgpu::TextureBuilder::new().into_descriptor();
+    assert_eq!(
+        desc.usage,
+        wgpu::TextureUsage::SAMPLED | wgpu::TextureUsage::COPY_DST
+    );
+    assert_eq!(desc.format, wgpu::TextureFormat::Rgba8Unorm);
+    assert_eq!(desc.mip_level_count, 1);
+
+    let desc = wgpu::TextureBuilder::new()
+        .as_render_target()
+        .as_storage()
+        .label("target")
+        .into_descriptor();
+    assert!(desc.usage.contains(wgpu::TextureUsage::OUTPUT_ATTACHMENT));
+    assert!(desc.usage.contains(wgpu::TextureUsage::STORAGE));
+    assert!(desc.usage.contains(wgpu::TextureUsage::SAMPLED));
+    assert_eq!(desc.label, Some("target"));
+}
+
+#[test]
+fn texture_mip_level_count_test() {
+    assert_eq!(wgpu::texture_max_mip_level_count([1, 1]), 1);
+    assert_eq!(wgpu::texture_max_mip_level_count([256, 256]), 9);
+    assert_eq!(wgpu::texture_max_mip_level_count([300, 20]), 9);
+
+    let desc = wgpu::TextureBuilder::new()
+        .size([64, 32])
+        .auto_generate_mipmaps(true)
+        .into_descriptor();
+    assert_eq!(desc.mip_level_count, 7);
+    assert!(desc.usage.contains(wgpu::TextureUsage::OUTPUT_ATTACHMENT));
+}
+
+#[test]
+fn uniform_buffer_stride_test() {
+    #[repr(C)]
+    #[derive(Copy, Clone)]
+    struct Uniforms {
+        time: f32,
+        resolution: [f32; 2],
+        _pad: f32,
+    }
+    unsafe impl wgpu::Uniform for Uniforms {}
+    assert_eq!(wgpu::UniformBuffer::<Uniforms>::stride(false), 16);
+    assert_eq!(wgpu::UniformBuffer::<Uniforms>::stride(true), 256);
+    assert_eq!(wgpu::UniformBuffer::<[f32; 80]>::stride(true), 512);
+}
+
+#[test]
+fn texel_format_test() {
+    use nannou::wgpu::Texel;
+    assert_eq!(wgpu::texture_padded_bytes_per_row(0), 0);
+    assert_eq!(wgpu::texture_padded_bytes_per_row(4), 256);
+    assert_eq!(wgpu::texture_padded_bytes_per_row(256), 256);
+    assert_eq!(wgpu::texture_padded_bytes_per_row(300 * 16), 4864);
+    assert_eq!(<[u8; 4]>::TEXTURE_FORMAT, wgpu::TextureFormat::Rgba8Unorm);
+    assert_eq!(<[f32; 4]>::TEXTURE_FORMAT, wgpu::TextureFormat::Rgba32Float);
+    assert_eq!(f32::TEXTURE_FORMAT, wgpu::TextureFormat::R32Float);
+    assert_eq!(
+        wgpu::texture_format_size_bytes(<[f32; 4]>::TEXTURE_FORMAT) as usize,
+        std::mem::size_of::<[f32; 4]>()
+    );
+}
+
+// Returns `None` on systems without a supported adapter, in which case the test is skipped.
+fn device_queue_pair() -> Option<std::sync::Arc<wgpu::DeviceQueuePair>> {
+    let adapters = wgpu::AdapterMap::default();
+    let options = wgpu::RequestAdapterOptions {
+        power_preference: wgpu::DEFAULT_POWER_PREFERENCE,
+        compatible_surface: None,
+    };
+    let adapter = adapters.get_or_request(options, wgpu::DEFAULT_BACKENDS)?;
+    Some(adapter.get_or_request_device(wgpu::default_device_descriptor()))
+}
+
+#[test]
+fn buffer_pool_capacity_test() {
+    assert_eq!(
+        wgpu::BufferPool::capacity(0),
+        wgpu::BufferPool::MIN_CAPACITY
+    );
+    assert_eq!(wgpu::BufferPool::capacity(256), 256);
+    assert_eq!(wgpu::BufferPool::capacity(300), 512);
+    let pool = wgpu::BufferPool::default();
+    assert_eq!(
+        pool.max_idle_frames(),
+        wgpu::BufferPool::DEFAULT_MAX_IDLE_FRAMES
+    );
+    pool.set_max_idle_frames(1);
+    assert_eq!(pool.max_idle_frames(), 1);
+    assert_eq!(pool.idle_count(), 0);
+}
+
+#[test]
+fn buffer_pool_reuse_test() {
+    let pair = match device_queue_pair() {
+        None => return,
+        Some(pair) => pair,
+    };
+    let device = pair.device();
+    let pool = wgpu::BufferPool::default();
+    let usage = wgpu::BufferUsage::VERTEX | wgpu::BufferUsage::COPY_DST;
+    let buffer = pool.get(device, 300, usage);
+    assert_eq!(buffer.size(), 300);
+    assert_eq!(buffer.capacity(), 512);
+    assert_eq!(pool.idle_count(), 0);
+    drop(buffer);
+    assert_eq!(pool.idle_count(), 1);
+
+    // A request with the same usage and capacity re-uses the idle buffer.
+    let buffer = pool.get(device, 400, usage);
+    assert_eq!(pool.idle_count(), 0);
+    drop(buffer);
+
+    // Idle buffers are freed once they have gone unused for more than `max_idle_frames`.
+    pool.set_max_idle_frames(1);
+    pool.end_frame();
+    assert_eq!(pool.idle_count(), 1);
+    pool.end_frame();
+    assert_eq!(pool.idle_count(), 0);
+}
+
+#[test]
+fn buffer_pool_upload_test() {
+    let pair = match device_queue_pair() {
+        None => return,
+        Some(pair) => pair,
+    };
+    let device = pair.device();
+    let pool = wgpu::BufferPool::default();
+    let desc = wgpu::CommandEncoderDescriptor { label: None };
+    let mut encoder = device.create_command_encoder(&desc);
+    let buffer = pool.upload(device, &mut encoder, &[1; 64], wgpu::BufferUsage::VERTEX);
+    assert!(buffer.usage().contains(wgpu::BufferUsage::COPY_DST));
+    pair.queue().submit(&[encoder.finish()]);
+    drop(buffer);
+
+    // Once the frame has ended and its mapping has completed, the staging buffer is idle and
+    // ready to be written to again alongside the uploaded buffer.
+    pool.end_frame();
+    device.poll(wgpu::Maintain::Wait);
+    pool.end_frame();
+    assert_eq!(pool.idle_count(), 2);
+}
@@ -0,0 +1,39 @@
+#![cfg(feature = "websocket")]
+
+use nannou::net::ws::{Client, Server};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Poll the given function until it produces a value or a second has passed.
+fn poll<T, F>(mut f: F) -> Option<T>
+where
+    F: FnMut() -> Option<T>,
+{
+    let start = Instant::now();
+    while start.elapsed() < Duration::from_secs(1) {
+        if let Some(t) = f() {
+            return Some(t);
+        }
+        thread::sleep(Duration::from_millis(1));
+    }
+    None
+}
+
+#[test]
+fn ws_loopback_test() {
+    let server = Server::bind("127.0.0.1:0").unwrap();
+    let client = Client::connect(&format!("ws://{}", server.addr())).unwrap();
+
+    // Client to server.
+    assert!(client.send(b"hello"));
+    let (id, msg) = poll(|| server.try_recv()).expect("server received no message");
+    assert_eq!(msg, b"hello");
+    assert_eq!(server.clients(), vec![id]);
+
+    // Server to client.
+    assert!(server.send(id, b"world"));
+    assert_eq!(poll(|| client.try_recv()), Some(b"world".to_vec()));
+    server.broadcast(b"all");
+    assert_eq!(poll(|| client.try_recv()), Some(b"all".to_vec()));
+    assert!(!server.send(id + 1, b"nobody"));
+}
@@ -0,0 +1,204 @@
+//! Items related to detecting musical features within audio, e.g. for audio-reactive visuals.
+//!
+//! - **SpectralFlux** measures the increase in spectral energy between consecutive frames.
+//! - **OnsetDetector** detects beat onsets as peaks in the spectral flux above an adaptive
+//!   threshold.
+//!
+//! Both operate on the magnitude spectra produced by an **Fft** or **FftReceiver**. An
+//! **OnsetDetector** that polls a stream directly may be created via
+//! `FftReceiver::onset_detector`:
+//!
+//! ```ignore
+//! let fft = stream.attach_fft(1024);
+//! let mut onsets = fft.onset_detector(0.5);
+//! // Within `update`.
+//! if onsets.poll() {
+//!     model.flash = 1.0;
+//! }
+//! ```
+
+use crate::fft::FftReceiver;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// The cooldown used by detectors created via `FftReceiver::onset_detector`.
+pub const DEFAULT_COOLDOWN_SECS: f32 = 0.1;
+
+/// The longest cooldown, to which longer (including infinite) cooldowns are clamped.
+pub const MAX_COOLDOWN_SECS: f32 = 60.0 * 60.0 * 24.0;
+
+/// The number of recent flux values from which the median threshold is determined.
+pub const THRESHOLD_WINDOW: usize = 32;
+
+/// The flux below which no onset is detected, avoiding detections within near silence.
+pub const MIN_FLUX: f32 = 0.01;
+
+/// Computes the half-wave rectified spectral flux between consecutive magnitude spectra.
+///
+/// The flux is the sum of the increases in magnitude of each bin since the previous frame.
+/// Decreases are ignored, so the flux peaks as new sounds begin, making it an effective onset
+/// signal.
+#[derive(Clone, Debug, Default)]
+pub struct SpectralFlux {
+    previous: Vec<f32>,
+    flux: f32,
+}
+
+/// Detects beat onsets as peaks in the spectral flux.
+///
+/// An onset is detected when the flux exceeds a threshold derived from the median of the recent
+/// flux values, after which no further onsets are detected until the cooldown elapses.
+pub struct OnsetDetector {
+    flux: SpectralFlux,
+    history: VecDeque<f32>,
+    sensitivity: f32,
+    cooldown: Duration,
+    threshold: f32,
+    last_onset: Option<Instant>,
+    // Set when created via `FftReceiver::onset_detector`.
+    receiver: Option<FftReceiver>,
+}
+
+impl SpectralFlux {
+    /// Create a new spectral flux with no previous frame.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compute the flux between the given magnitudes and those of the previous frame.
+    ///
+    /// Returns `0.0` for the first frame, or if the number of bins differs from the previous
+    /// frame.
+    pub fn process(&mut self, magnitudes: &[f32]) -> f32 {
+        self.flux = match self.previous.len() == magnitudes.len() {
+            true => self
+                .previous
+                .iter()
+                .zip(magnitudes)
+                .map(|(&prev, &mag)| (mag - prev).max(0.0))
+                .sum(),
+            false => 0.0,
+        };
+        self.previous.clear();
+        self.previous.extend_from_slice(magnitudes);
+        self.flux
+    }
+
+    /// The flux produced by the last call to `process`.
+    pub fn flux(&self) -> f32 {
+        self.flux
+    }
+
+    /// Forget the previous frame.
+    pub fn reset(&mut self) {
+        self.previous.clear();
+        self.flux = 0.0;
+    }
+}
+
+impl OnsetDetector {
+    /// Create a new onset detector.
+    ///
+    /// The `sensitivity` is in the range `0.0..=1.0`. The flux must exceed the median of the
+    /// recent flux values by a factor of `3.0` at a sensitivity of `0.0`, falling to a factor of
+    /// `1.0` at a sensitivity of `1.0`. Following each onset, no further onsets are detected for
+    /// `cooldown_secs`, clamped to the range `0.0..=MAX_COOLDOWN_SECS`. A `NaN` cooldown is
+    /// treated as `0.0`.
+    pub fn new(sensitivity: f32, cooldown_secs: f32) -> Self {
+        OnsetDetector {
+            flux: SpectralFlux::new(),
+            history: VecDeque::with_capacity(THRESHOLD_WINDOW),
+            sensitivity: sensitivity.max(0.0).min(1.0),
+            cooldown: Duration::from_secs_f32(cooldown_secs.max(0.0).min(MAX_COOLDOWN_SECS)),
+            threshold: 0.0,
+            last_onset: None,
+            receiver: None,
+        }
+    }
+
+    /// Process the next magnitude spectrum, returning `true` if an onset is detected.
+    pub fn on_onset(&mut self, magnitudes: &[f32]) -> bool {
+        self.on_onset_at(magnitudes, Instant::now())
+    }
+
+    /// Process the next magnitude spectrum, received at the given instant.
+    pub fn on_onset_at(&mut self, magnitudes: &[f32], instant: Instant) -> bool {
+        let flux = self.flux.process(magnitudes);
+        self.threshold = median(&self.history) * (1.0 + 2.0 * (1.0 - self.sensitivity));
+        if self.history.len() == THRESHOLD_WINDOW {
+            self.history.pop_front();
+        }
+        self.history.push_back(flux);
+
+        let cooled = match self.last_onset {
+            Some(last) => instant.saturating_duration_since(last) >= self.cooldown,
+            None => true,
+        };
+        let onset = cooled && flux > MIN_FLUX && flux > self.threshold;
+        if onset {
+            self.last_onset = Some(instant);
+        }
+        onset
+    }
+
+    /// Transform the latest samples from the stream and process them, returning `true` if an
+    /// onset is detected.
+    ///
+    /// Always returns `false` unless the detector was created via
+    /// `FftReceiver::onset_detector`.
+    pub fn poll(&mut self) -> bool {
+        let mut receiver = match self.receiver.take() {
+            None => return false,
+            Some(receiver) => receiver,
+        };
+        let onset = self.on_onset(receiver.magnitudes());
+        self.receiver = Some(receiver);
+        onset
+    }
+
+    /// The flux of the last processed spectrum.
+    pub fn flux(&self) -> f32 {
+        self.flux.flux()
+    }
+
+    /// The threshold against which the flux of the last processed spectrum was compared.
+    pub fn threshold(&self) -> f32 {
+        self.threshold
+    }
+
+    /// The sensitivity in the range `0.0..=1.0`.
+    pub fn sensitivity(&self) -> f32 {
+        self.sensitivity
+    }
+
+    /// Specify the sensitivity in the range `0.0..=1.0`.
+    pub fn set_sensitivity(&mut self, sensitivity: f32) {
+        self.sensitivity = sensitivity.max(0.0).min(1.0);
+    }
+}
+
+impl FftReceiver {
+    /// Create an **OnsetDetector** receiving the spectrum of the same stream.
+    ///
+    /// The detector owns its own transform of the same size, so this receiver remains usable.
+    /// Call `OnsetDetector::poll` to process the latest samples.
+    pub fn onset_detector(&self, sensitivity: f32) -> OnsetDetector {
+        let mut detector = OnsetDetector::new(sensitivity, DEFAULT_COOLDOWN_SECS);
+        detector.receiver = Some(self.attach_sibling());
+        detector
+    }
+}
+
+// The median of the given values, or `0.0` if there are none.
+fn median(values: &VecDeque<f32>) -> f32 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    let mut sorted: Vec<f32> = values.iter().cloned().collect();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let mid = sorted.len() / 2;
+    match sorted.len() % 2 {
+        0 => (sorted[mid - 1] + sorted[mid]) * 0.5,
+        _ => sorted[mid],
+    }
+}
@@ -1,3 +1,4 @@
+use dasp_sample::{Sample, ToSample};
 use std;
 use std::ops::{Deref, DerefMut};
 
@@ -59,6 +60,44 @@ impl<S> Buffer<S> {
     }
 }
 
+impl<S> Buffer<S>
+where
+    S: Sample + ToSample<f32>,
+{
+    /// The root mean square of all samples within the buffer, a measure of its average loudness.
+    ///
+    /// Returns `0.0` for an empty buffer.
+    pub fn rms(&self) -> f32 {
+        if self.interleaved_samples.is_empty() {
+            return 0.0;
+        }
+        let sum: f32 = self
+            .interleaved_samples
+            .iter()
+            .map(|&s| {
+                let s = s.to_sample::<f32>();
+                s * s
+            })
+            .sum();
+        (sum / self.interleaved_samples.len() as f32).sqrt()
+    }
+
+    /// The greatest absolute amplitude of all samples within the buffer.
+    pub fn peak(&self) -> f32 {
+        self.interleaved_samples
+            .iter()
+            .map(|&s| s.to_sample::<f32>().abs())
+            .fold(0.0, f32::max)
+    }
+}
+
+impl Buffer<f32> {
+    /// The interleaved samples of the buffer.
+    pub fn as_f32_slice(&self) -> &[f32] {
+        &self.interleaved_samples
+    }
+}
+
 impl<S> Deref for Buffer<S> {
     type Target = [S];
     fn deref(&self) -> &Self::Target {
@@ -0,0 +1,559 @@
+//! Audio effects for processing a signal one sample at a time.
+//!
+//! - [**AudioEffect**](./trait.AudioEffect.html) - the trait implemented by all effects.
+//! - [**Reverb**](./struct.Reverb.html) - either a lightweight algorithmic Schroeder reverb or a
+//!   convolution reverb driven by a recorded impulse response.
+//! - [**Delay**](./struct.Delay.html) - a feedback delay line.
+//! - [**EffectChain**](./struct.EffectChain.html) - applies a sequence of effects in order.
+//!
+//! An **EffectChain** may be applied to the buffer within an output stream's `render` function,
+//! after the buffer has been filled:
+//!
+//! ```ignore
+//! let mut chain = EffectChain::new()
+//!     .add(Reverb::schroeder(0.8, 0.5))
+//!     .add(Delay::new(0.25, 0.4));
+//!
+//! // Within the audio render function.
+//! graph.fill(buffer);
+//! chain.process_buffer(buffer);
+//! ```
+//!
+//! Effects assume the `DEFAULT_SAMPLE_RATE` until told otherwise via
+//! `AudioEffect::set_sample_rate`. `EffectChain::process_buffer` does so automatically using the
+//! sample rate of the buffer.
+//!
+//! Each effect processes a single channel. `EffectChain::process_buffer` processes each channel of
+//! the buffer with its own copy of every effect, preserving the separation between channels.
+
+use crate::fft;
+use crate::Buffer;
+
+/// The sample rate assumed by effects until `AudioEffect::set_sample_rate` is called.
+pub const DEFAULT_SAMPLE_RATE: u32 = 44_100;
+
+/// The number of samples in each partition of a convolution reverb's impulse response.
+///
+/// The output of a convolution reverb is delayed by this many samples.
+pub const CONVOLUTION_BLOCK_SIZE: usize = 256;
+
+// The comb and allpass delay lengths in samples at 44.1 kHz, as tuned for Freeverb.
+const COMB_TUNINGS: [usize; 4] = [1116, 1188, 1277, 1356];
+const ALLPASS_TUNINGS: [usize; 2] = [556, 441];
+const ALLPASS_FEEDBACK: f32 = 0.5;
+const TUNING_SAMPLE_RATE: f32 = 44_100.0;
+
+// Scales the input to the parallel combs, whose resonance greatly amplifies the signal.
+const COMB_INPUT_GAIN: f32 = 0.03;
+
+// The default proportion of the processed signal within the output of each effect.
+const DEFAULT_REVERB_MIX: f32 = 0.3;
+const DEFAULT_DELAY_MIX: f32 = 0.5;
+
+/// An effect that processes a mono signal one sample at a time.
+pub trait AudioEffect: Send {
+    /// Process the next input sample, returning the next output sample.
+    fn process(&mut self, input: f32) -> f32;
+
+    /// Inform the effect of the rate at which samples are processed.
+    ///
+    /// Effects whose behaviour is specified in seconds use this to determine their length in
+    /// samples. The default implementation does nothing.
+    fn set_sample_rate(&mut self, _sample_rate: u32) {}
+}
+
+/// A reverb applied to a mono signal.
+///
+/// Created via either `Reverb::schroeder` or `Reverb::convolution`.
+#[derive(Clone)]
+pub struct Reverb {
+    kind: ReverbKind,
+    mix: f32,
+}
+
+/// A feedback delay line, repeating the signal after a fixed time.
+#[derive(Clone)]
+pub struct Delay {
+    time_secs: f32,
+    feedback: f32,
+    mix: f32,
+    line: Vec<f32>,
+    index: usize,
+}
+
+/// A sequence of effects applied one after another.
+///
+/// When used as an **AudioEffect**, the chain processes a single channel. `process_buffer`
+/// additionally creates a copy of each effect for every further channel of the buffer.
+#[derive(Default)]
+pub struct EffectChain {
+    effects: Vec<ChainEffect>,
+    sample_rate: Option<u32>,
+}
+
+// An effect within a chain, with one instance per processed channel.
+struct ChainEffect {
+    channels: Vec<Box<dyn AudioEffect>>,
+    // Produces a copy of the effect as it was when added to the chain.
+    new_channel: Box<dyn Fn() -> Box<dyn AudioEffect> + Send>,
+}
+
+#[derive(Clone)]
+enum ReverbKind {
+    Schroeder(Schroeder),
+    Convolution(Convolution),
+}
+
+// Four parallel damped comb filters followed by two allpass filters in series.
+#[derive(Clone)]
+struct Schroeder {
+    feedback: f32,
+    damping: f32,
+    combs: Vec<Comb>,
+    allpasses: Vec<Allpass>,
+}
+
+#[derive(Clone)]
+struct Comb {
+    line: Vec<f32>,
+    index: usize,
+    filtered: f32,
+}
+
+#[derive(Clone)]
+struct Allpass {
+    line: Vec<f32>,
+    index: usize,
+}
+
+// Uniformly partitioned overlap-save convolution.
+#[derive(Clone)]
+struct Convolution {
+    // The impulse response as given, along with its sample rate.
+    impulse_response: Vec<f32>,
+    impulse_response_rate: u32,
+    // The spectrum of each partition of the impulse response at the processing rate.
+    partitions: Vec<Spectrum>,
+    // A ring of the spectra of the most recent input blocks, one per partition. The most recent
+    // is at `newest`, with older spectra following it.
+    inputs: Vec<Spectrum>,
+    newest: usize,
+    // The previous and current blocks of input samples.
+    previous: Vec<f32>,
+    current: Vec<f32>,
+    // The output for the current block.
+    output: Vec<f32>,
+    index: usize,
+    // Reused while transforming.
+    re: Vec<f32>,
+    im: Vec<f32>,
+}
+
+#[derive(Clone)]
+struct Spectrum {
+    re: Vec<f32>,
+    im: Vec<f32>,
+}
+
+impl Reverb {
+    /// A Schroeder reverb, simulating a room via a network of delay lines.
+    ///
+    /// The `room_size` in the range `0.0..=1.0` determines the length of the tail. The `damping`
+    /// in the range `0.0..=1.0` determines how quickly high frequencies decay within the tail.
+    pub fn schroeder(room_size: f32, damping: f32) -> Self {
+        let room_size = room_size.max(0.0).min(1.0);
+        let damping = damping.max(0.0).min(1.0);
+        let mut schroeder = Schroeder {
+            feedback: 0.7 + room_size * 0.28,
+            damping: damping * 0.4,
+            combs: vec![],
+            allpasses: vec![],
+        };
+        schroeder.set_sample_rate(DEFAULT_SAMPLE_RATE);
+        Reverb {
+            kind: ReverbKind::Schroeder(schroeder),
+            mix: DEFAULT_REVERB_MIX,
+        }
+    }
+
+    /// A convolution reverb, applying the recorded response of a space to the signal.
+    ///
+    /// The `impulse_response` is a mono recording of the space sampled at the given
+    /// `sample_rate`. It is resampled if the effect is processing at a different rate. The output
+    /// is delayed by `CONVOLUTION_BLOCK_SIZE` samples.
+    pub fn convolution(impulse_response: &[f32], sample_rate: u32) -> Self {
+        let mut convolution = Convolution {
+            impulse_response: impulse_response.to_vec(),
+            impulse_response_rate: sample_rate,
+            partitions: vec![],
+            inputs: vec![],
+            newest: 0,
+            previous: vec![0.0; CONVOLUTION_BLOCK_SIZE],
+            current: vec![0.0; CONVOLUTION_BLOCK_SIZE],
+            output: vec![0.0; CONVOLUTION_BLOCK_SIZE],
+            index: 0,
+            re: vec![0.0; CONVOLUTION_BLOCK_SIZE * 2],
+            im: vec![0.0; CONVOLUTION_BLOCK_SIZE * 2],
+        };
+        convolution.set_sample_rate(DEFAULT_SAMPLE_RATE);
+        Reverb {
+            kind: ReverbKind::Convolution(convolution),
+            mix: DEFAULT_REVERB_MIX,
+        }
+    }
+
+    /// Specify the proportion of reverberated signal within the output in the range `0.0..=1.0`.
+    ///
+    /// By default this is `0.3`.
+    pub fn mix(mut self, mix: f32) -> Self {
+        self.set_mix(mix);
+        self
+    }
+
+    /// Specify the proportion of reverberated signal within the output in the range `0.0..=1.0`.
+    pub fn set_mix(&mut self, mix: f32) {
+        self.mix = mix.max(0.0).min(1.0);
+    }
+}
+
+impl Delay {
+    /// A delay repeating the signal after `time_secs`.
+    ///
+    /// The `feedback` in the range `0.0..1.0` is the amplitude of each repeat relative to the
+    /// last.
+    pub fn new(time_secs: f32, feedback: f32) -> Self {
+        let mut delay = Delay {
+            time_secs: time_secs.max(0.0),
+            feedback: feedback.max(0.0).min(0.999),
+            mix: DEFAULT_DELAY_MIX,
+            line: vec![],
+            index: 0,
+        };
+        delay.set_sample_rate(DEFAULT_SAMPLE_RATE);
+        delay
+    }
+
+    /// Specify the proportion of delayed signal within the output in the range `0.0..=1.0`.
+    ///
+    /// By default this is `0.5`.
+    pub fn mix(mut self, mix: f32) -> Self {
+        self.set_mix(mix);
+        self
+    }
+
+    /// Specify the proportion of delayed signal within the output in the range `0.0..=1.0`.
+    pub fn set_mix(&mut self, mix: f32) {
+        self.mix = mix.max(0.0).min(1.0);
+    }
+
+    /// Specify the amplitude of each repeat relative to the last in the range `0.0..1.0`.
+    pub fn set_feedback(&mut self, feedback: f32) {
+        self.feedback = feedback.max(0.0).min(0.999);
+    }
+}
+
+impl EffectChain {
+    /// An empty chain, passing the signal through unchanged.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append the given effect to the end of the chain.
+    ///
+    /// The effect is cloned for each additional channel processed via `process_buffer`.
+    pub fn add<E>(mut self, effect: E) -> Self
+    where
+        E: 'static + AudioEffect + Clone,
+    {
+        self.push(effect);
+        self
+    }
+
+    /// Append the given effect to the end of the chain.
+    ///
+    /// The effect is cloned for each additional channel processed via `process_buffer`.
+    pub fn push<E>(&mut self, effect: E)
+    where
+        E: 'static + AudioEffect + Clone,
+    {
+        let new_channel = {
+            let effect = effect.clone();
+            Box::new(move || Box::new(effect.clone()) as Box<dyn AudioEffect>)
+        };
+        let mut effect = ChainEffect {
+            channels: vec![Box::new(effect)],
+            new_channel,
+        };
+        if let Some(sample_rate) = self.sample_rate {
+            effect.set_sample_rate(sample_rate);
+        }
+        self.effects.push(effect);
+    }
+
+    /// The number of effects within the chain.
+    pub fn len(&self) -> usize {
+        self.effects.len()
+    }
+
+    /// Whether or not the chain contains any effects.
+    pub fn is_empty(&self) -> bool {
+        self.effects.is_empty()
+    }
+
+    /// Apply the chain to every frame of the given buffer.
+    ///
+    /// Each channel is processed independently as described by `process_interleaved`. The effects
+    /// are informed of the buffer's sample rate if it has changed.
+    pub fn process_buffer(&mut self, buffer: &mut Buffer) {
+        let sample_rate = buffer.sample_rate();
+        if self.sample_rate != Some(sample_rate) {
+            self.set_sample_rate(sample_rate);
+        }
+        let channels = buffer.channels();
+        self.process_interleaved(buffer, channels);
+    }
+
+    /// Apply the chain to the given interleaved samples with the given number of channels.
+    ///
+    /// Each channel is processed independently by its own copy of each effect, created upon the
+    /// first call with that many channels. The first channel shares the effects used by
+    /// `AudioEffect::process`.
+    pub fn process_interleaved(&mut self, samples: &mut [f32], channels: usize) {
+        if channels == 0 {
+            return;
+        }
+        let sample_rate = self.sample_rate.unwrap_or(DEFAULT_SAMPLE_RATE);
+        for effect in &mut self.effects {
+            effect.ensure_channels(channels, sample_rate);
+        }
+        for frame in samples.chunks_mut(channels) {
+            for (channel, sample) in frame.iter_mut().enumerate() {
+                *sample = self.effects.iter_mut().fold(*sample, |sample, effect| {
+                    effect.channels[channel].process(sample)
+                });
+            }
+        }
+    }
+}
+
+impl ChainEffect {
+    // Ensure that there is an instance of the effect for each of the given number of channels.
+    fn ensure_channels(&mut self, channels: usize, sample_rate: u32) {
+        while self.channels.len() < channels {
+            let mut effect = (self.new_channel)();
+            effect.set_sample_rate(sample_rate);
+            self.channels.push(effect);
+        }
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: u32) {
+        for effect in &mut self.channels {
+            effect.set_sample_rate(sample_rate);
+        }
+    }
+}
+
+impl Schroeder {
+    fn process(&mut self, input: f32) -> f32 {
+        let input = input * COMB_INPUT_GAIN;
+        let (feedback, damping) = (self.feedback, self.damping);
+        let mut output: f32 = self
+            .combs
+            .iter_mut()
+            .map(|comb| comb.process(input, feedback, damping))
+            .sum();
+        for allpass in &mut self.allpasses {
+            output = allpass.process(output);
+        }
+        output
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: u32) {
+        let scale = sample_rate as f32 / TUNING_SAMPLE_RATE;
+        let len = |tuning: usize| ((tuning as f32 * scale) as usize).max(1);
+        self.combs = COMB_TUNINGS
+            .iter()
+            .map(|&tuning| Comb {
+                line: vec![0.0; len(tuning)],
+                index: 0,
+                filtered: 0.0,
+            })
+            .collect();
+        self.allpasses = ALLPASS_TUNINGS
+            .iter()
+            .map(|&tuning| Allpass {
+                line: vec![0.0; len(tuning)],
+                index: 0,
+            })
+            .collect();
+    }
+}
+
+impl Comb {
+    fn process(&mut self, input: f32, feedback: f32, damping: f32) -> f32 {
+        let output = self.line[self.index];
+        self.filtered = output * (1.0 - damping) + self.filtered * damping;
+        self.line[self.index] = input + self.filtered * feedback;
+        self.index = (self.index + 1) % self.line.len();
+        output
+    }
+}
+
+impl Allpass {
+    fn process(&mut self, input: f32) -> f32 {
+        let delayed = self.line[self.index];
+        self.line[self.index] = input + delayed * ALLPASS_FEEDBACK;
+        self.index = (self.index + 1) % self.line.len();
+        delayed - input
+    }
+}
+
+impl Convolution {
+    fn process(&mut self, input: f32) -> f32 {
+        let output = self.output[self.index];
+        self.current[self.index] = input;
+        self.index += 1;
+        if self.index == CONVOLUTION_BLOCK_SIZE {
+            self.index = 0;
+            self.process_block();
+        }
+        output
+    }
+
+    // Convolve the latest block of input with the impulse response, producing the next block of
+    // output.
+    fn process_block(&mut self) {
+        let b = CONVOLUTION_BLOCK_SIZE;
+        let n = self.inputs.len();
+        if n == 0 {
+            std::mem::swap(&mut self.previous, &mut self.current);
+            return;
+        }
+
+        // Transform the previous and current blocks together, overwriting the oldest spectrum.
+        self.newest = (self.newest + n - 1) % n;
+        let x = &mut self.inputs[self.newest];
+        x.re[..b].copy_from_slice(&self.previous);
+        x.re[b..].copy_from_slice(&self.current);
+        x.im.iter_mut().for_each(|im| *im = 0.0);
+        fft::transform(&mut x.re, &mut x.im, false);
+        std::mem::swap(&mut self.previous, &mut self.current);
+
+        // Sum the products of each input spectrum with its partition of the impulse response.
+        self.re.iter_mut().for_each(|re| *re = 0.0);
+        self.im.iter_mut().for_each(|im| *im = 0.0);
+        for (age, h) in self.partitions.iter().enumerate() {
+            let x = &self.inputs[(self.newest + age) % n];
+            let sums = self.re.iter_mut().zip(self.im.iter_mut());
+            let products = x.re.iter().zip(&x.im).zip(h.re.iter().zip(&h.im));
+            for ((re, im), ((xr, xi), (hr, hi))) in sums.zip(products) {
+                *re += xr * hr - xi * hi;
+                *im += xr * hi + xi * hr;
+            }
+        }
+
+        // The first half is corrupted by circular wrapping, so only the second half is kept.
+        fft::transform(&mut self.re, &mut self.im, true);
+        self.output.copy_from_slice(&self.re[b..]);
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: u32) {
+        let b = CONVOLUTION_BLOCK_SIZE;
+        let ir = resample(
+            &self.impulse_response,
+            self.impulse_response_rate,
+            sample_rate,
+        );
+        self.partitions = ir
+            .chunks(b)
+            .map(|chunk| {
+                let mut re = vec![0.0; b * 2];
+                let mut im = vec![0.0; b * 2];
+                re[..chunk.len()].copy_from_slice(chunk);
+                fft::transform(&mut re, &mut im, false);
+                Spectrum { re, im }
+            })
+            .collect();
+        let silence = Spectrum {
+            re: vec![0.0; b * 2],
+            im: vec![0.0; b * 2],
+        };
+        self.inputs = vec![silence; self.partitions.len()];
+        self.newest = 0;
+        self.previous.iter_mut().for_each(|s| *s = 0.0);
+        self.output.iter_mut().for_each(|s| *s = 0.0);
+    }
+}
+
+impl AudioEffect for Reverb {
+    fn process(&mut self, input: f32) -> f32 {
+        let wet = match self.kind {
+            ReverbKind::Schroeder(ref mut schroeder) => schroeder.process(input),
+            ReverbKind::Convolution(ref mut convolution) => convolution.process(input),
+        };
+        input * (1.0 - self.mix) + wet * self.mix
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: u32) {
+        match self.kind {
+            ReverbKind::Schroeder(ref mut schroeder) => schroeder.set_sample_rate(sample_rate),
+            ReverbKind::Convolution(ref mut convolution) => {
+                convolution.set_sample_rate(sample_rate)
+            }
+        }
+    }
+}
+
+impl AudioEffect for Delay {
+    fn process(&mut self, input: f32) -> f32 {
+        if self.line.is_empty() {
+            return input;
+        }
+        let delayed = self.line[self.index];
+        self.line[self.index] = input + delayed * self.feedback;
+        self.index = (self.index + 1) % self.line.len();
+        input * (1.0 - self.mix) + delayed * self.mix
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: u32) {
+        let len = (self.time_secs * sample_rate as f32).round() as usize;
+        self.line = vec![0.0; len];
+        self.index = 0;
+    }
+}
+
+impl AudioEffect for EffectChain {
+    fn process(&mut self, input: f32) -> f32 {
+        self.effects
+            .iter_mut()
+            .fold(input, |sample, effect| effect.channels[0].process(sample))
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: u32) {
+        self.sample_rate = Some(sample_rate);
+        for effect in &mut self.effects {
+            effect.set_sample_rate(sample_rate);
+        }
+    }
+}
+
+// Linearly resample the given impulse response from one sample rate to another.
+//
+// The amplitude is scaled by the ratio of the rates so that the gain of the response is preserved.
+fn resample(signal: &[f32], from: u32, to: u32) -> Vec<f32> {
+    if from == to || from == 0 || signal.is_empty() {
+        return signal.to_vec();
+    }
+    let ratio = from as f64 / to as f64;
+    let len = (signal.len() as f64 / ratio).ceil() as usize;
+    (0..len)
+        .map(|i| {
+            let pos = i as f64 * ratio;
+            let j = pos as usize;
+            let t = (pos - j as f64) as f32;
+            let a = signal[j];
+            let b = signal.get(j + 1).cloned().unwrap_or(0.0);
+            (a + (b - a) * t) * ratio as f32
+        })
+        .collect()
+}
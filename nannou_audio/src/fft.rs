@@ -0,0 +1,275 @@
+//! Items related to spectral analysis of audio via the fast fourier transform.
+//!
+//! An **FftReceiver** may be attached to any **Stream** via `Stream::attach_fft`. The audio thread
+//! writes the latest samples into a shared lock-free ring buffer, while the transform itself is
+//! performed on the polling thread, e.g. within the app's `update` function. Neither thread ever
+//! blocks the other and no buffers are skipped.
+
+use dasp_sample::ToSample;
+use std::f32::consts::PI;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+
+/// A fast fourier transform of a fixed size, producing the magnitude of each frequency bin.
+///
+/// A Hann window is applied to the samples before the transform to reduce spectral leakage.
+#[derive(Clone, Debug)]
+pub struct Fft {
+    window: Vec<f32>,
+    // The sum of the window, used to normalise the magnitudes.
+    window_sum: f32,
+    re: Vec<f32>,
+    im: Vec<f32>,
+    magnitudes: Vec<f32>,
+}
+
+/// Receives the spectrum of the audio passing through the stream to which it is attached.
+///
+/// Created via `Stream::attach_fft`.
+pub struct FftReceiver {
+    tap: Arc<Tap>,
+    // The taps of the stream, allowing further receivers to be attached.
+    taps: Taps,
+    fft: Fft,
+    samples: Vec<f32>,
+    sample_rate: u32,
+}
+
+// The ring buffer of mono samples shared between an `FftReceiver` and the audio thread.
+//
+// Samples are stored as the bits of an `f32` so that they may be written and read atomically.
+pub(crate) struct Tap {
+    samples: Vec<AtomicU32>,
+    // The index at which the next sample will be written, i.e. the oldest sample.
+    next: AtomicUsize,
+    // Set to `false` when the receiver is dropped so that the audio thread may remove the tap.
+    attached: AtomicBool,
+}
+
+// Sends newly attached taps to the audio thread of a single stream.
+//
+// The lock is only ever taken when attaching a receiver, never by the audio thread.
+pub(crate) type Taps = Arc<Mutex<mpsc::Sender<Arc<Tap>>>>;
+
+// The taps owned by the audio thread of a single stream.
+pub(crate) struct Feed {
+    new_taps: mpsc::Receiver<Arc<Tap>>,
+    taps: Vec<Arc<Tap>>,
+}
+
+impl Fft {
+    /// Create a new transform of the given size.
+    ///
+    /// **Panics** if `size` is not a power of two greater than `1`.
+    pub fn new(size: usize) -> Self {
+        assert!(
+            size > 1 && size.is_power_of_two(),
+            "the FFT size must be a power of two"
+        );
+        let window: Vec<f32> = (0..size)
+            .map(|i| 0.5 - 0.5 * (2.0 * PI * i as f32 / size as f32).cos())
+            .collect();
+        let window_sum = window.iter().sum();
+        Fft {
+            window,
+            window_sum,
+            re: vec![0.0; size],
+            im: vec![0.0; size],
+            magnitudes: vec![0.0; size / 2],
+        }
+    }
+
+    /// The number of samples consumed by each transform.
+    pub fn size(&self) -> usize {
+        self.window.len()
+    }
+
+    /// Transform the given samples, returning the magnitude of each of the `size / 2` frequency
+    /// bins.
+    ///
+    /// Magnitudes are normalised so that a full scale sine wave centered on a bin produces a
+    /// magnitude of roughly `1.0`. If fewer than `size` samples are given, the remainder are
+    /// treated as silence.
+    pub fn process(&mut self, samples: &[f32]) -> &[f32] {
+        let n = self.size();
+        for i in 0..n {
+            let sample = samples.get(i).cloned().unwrap_or(0.0);
+            self.re[i] = sample * self.window[i];
+            self.im[i] = 0.0;
+        }
+
+        transform(&mut self.re, &mut self.im, false);
+
+        let scale = 2.0 / self.window_sum;
+        for (i, magnitude) in self.magnitudes.iter_mut().enumerate() {
+            let (re, im) = (self.re[i], self.im[i]);
+            *magnitude = (re * re + im * im).sqrt() * scale;
+        }
+        &self.magnitudes
+    }
+
+    /// The magnitudes produced by the last call to `process`.
+    pub fn magnitudes(&self) -> &[f32] {
+        &self.magnitudes
+    }
+}
+
+impl FftReceiver {
+    // Attach a new receiver to the given taps of a stream.
+    pub(crate) fn attach(taps: &Taps, size: usize, sample_rate: u32) -> Self {
+        let fft = Fft::new(size);
+        let tap = Arc::new(Tap {
+            samples: (0..size).map(|_| AtomicU32::new(0)).collect(),
+            next: AtomicUsize::new(0),
+            attached: AtomicBool::new(true),
+        });
+        // If the audio thread has already exited, the receiver simply yields silence.
+        taps.lock()
+            .expect("failed to acquire FFT taps lock")
+            .send(tap.clone())
+            .ok();
+        let samples = vec![0.0; size];
+        FftReceiver {
+            tap,
+            taps: taps.clone(),
+            fft,
+            samples,
+            sample_rate,
+        }
+    }
+
+    // Attach a new receiver of the same size to the same stream.
+    pub(crate) fn attach_sibling(&self) -> Self {
+        Self::attach(&self.taps, self.size(), self.sample_rate)
+    }
+
+    /// Transform the latest `size` samples from the stream, returning the magnitude of each of the
+    /// `size / 2` frequency bins.
+    ///
+    /// Multi-channel audio is mixed down to mono before the transform. If the audio thread writes
+    /// while the samples are being read, the oldest few samples may be replaced by newer ones.
+    pub fn magnitudes(&mut self) -> &[f32] {
+        let next = self.tap.next.load(Ordering::Acquire);
+        let len = self.samples.len();
+        for (i, sample) in self.samples.iter_mut().enumerate() {
+            let bits = self.tap.samples[(next + i) % len].load(Ordering::Relaxed);
+            *sample = f32::from_bits(bits);
+        }
+        self.fft.process(&self.samples)
+    }
+
+    /// The number of samples consumed by each transform.
+    pub fn size(&self) -> usize {
+        self.fft.size()
+    }
+
+    /// The center frequency of the given bin in hz.
+    pub fn bin_frequency(&self, bin: usize) -> f32 {
+        bin as f32 * self.sample_rate as f32 / self.size() as f32
+    }
+}
+
+impl Drop for FftReceiver {
+    fn drop(&mut self) {
+        // Mark the tap as detached so that the audio thread may remove it.
+        self.tap.attached.store(false, Ordering::Relaxed);
+    }
+}
+
+impl Tap {
+    // Write the given interleaved samples into the ring, mixing down to mono.
+    //
+    // Returns `false` if the receiver has been dropped.
+    fn write<S>(&self, interleaved: &[S], channels: usize) -> bool
+    where
+        S: Copy + ToSample<f32>,
+    {
+        if !self.attached.load(Ordering::Relaxed) {
+            return false;
+        }
+        // Only the audio thread writes, so the index may be advanced locally and published once.
+        let len = self.samples.len();
+        let mut next = self.next.load(Ordering::Relaxed);
+        for frame in interleaved.chunks(channels) {
+            let sum: f32 = frame.iter().map(|&s| s.to_sample_()).sum();
+            let sample = sum / channels as f32;
+            self.samples[next].store(sample.to_bits(), Ordering::Relaxed);
+            next = (next + 1) % len;
+        }
+        self.next.store(next, Ordering::Release);
+        true
+    }
+}
+
+// Perform an in-place radix-2 transform of the given complex values.
+//
+// The length must be a power of two. The inverse transform is scaled by `1 / len` so that the
+// inverse of the forward transform reproduces the input.
+pub(crate) fn transform(re: &mut [f32], im: &mut [f32], inverse: bool) {
+    let n = re.len();
+    debug_assert!(n.is_power_of_two() && im.len() == n);
+
+    // Reorder the values via bit reversal of their indices.
+    let bits = n.trailing_zeros();
+    for i in 0..n {
+        let j = i.reverse_bits() >> (std::mem::size_of::<usize>() as u32 * 8 - bits);
+        if j > i {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    // Iterative radix-2 butterflies.
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let mut len = 2;
+    while len <= n {
+        let angle = sign * 2.0 * PI / len as f32;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (sin, cos) = (angle * k as f32).sin_cos();
+                let (a, b) = (start + k, start + k + len / 2);
+                let r = re[b] * cos - im[b] * sin;
+                let i = re[b] * sin + im[b] * cos;
+                re[b] = re[a] - r;
+                im[b] = im[a] - i;
+                re[a] += r;
+                im[a] += i;
+            }
+        }
+        len *= 2;
+    }
+
+    if inverse {
+        let scale = 1.0 / n as f32;
+        for (r, i) in re.iter_mut().zip(im.iter_mut()) {
+            *r *= scale;
+            *i *= scale;
+        }
+    }
+}
+
+// Create the taps of a new stream along with the feed to be owned by its audio thread.
+pub(crate) fn taps() -> (Taps, Feed) {
+    let (tx, new_taps) = mpsc::channel();
+    let taps = Arc::new(Mutex::new(tx));
+    let feed = Feed {
+        new_taps,
+        taps: vec![],
+    };
+    (taps, feed)
+}
+
+impl Feed {
+    // Feed the given buffer of raw samples to each of the stream's taps.
+    pub(crate) fn feed(&mut self, data: &cpal::Data, channels: usize) {
+        self.taps.extend(self.new_taps.try_iter());
+        if self.taps.is_empty() {
+            return;
+        }
+        self.taps.retain(|tap| match data.sample_format() {
+            cpal::SampleFormat::U16 => tap.write(data.as_slice::<u16>().unwrap(), channels),
+            cpal::SampleFormat::I16 => tap.write(data.as_slice::<i16>().unwrap(), channels),
+            cpal::SampleFormat::F32 => tap.write(data.as_slice::<f32>().unwrap(), channels),
+        });
+    }
+}
@@ -0,0 +1,91 @@
+//! Items related to reading audio files, e.g. for playback within an output stream's `render`
+//! function.
+//!
+//! Currently, WAV files containing integer or floating point PCM are supported.
+
+use std::path::Path;
+use std::time::Duration;
+use std::{fs, io};
+use thiserror::Error;
+
+/// Audio decoded from a file into interleaved `f32` samples in the range `-1.0..=1.0`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct File {
+    samples: Vec<f32>,
+    channels: usize,
+    sample_rate: u32,
+}
+
+/// Errors that might occur while reading an audio file.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("failed to open the audio file: {0}")]
+    Io(#[from] io::Error),
+    #[error("failed to decode the WAV file: {0}")]
+    Wav(#[from] hound::Error),
+}
+
+impl File {
+    /// Read and decode the WAV file at the given path.
+    pub fn open<P>(path: P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let file = fs::File::open(path)?;
+        Self::from_reader(io::BufReader::new(file))
+    }
+
+    /// Read and decode WAV data from the given reader.
+    pub fn from_reader<R>(reader: R) -> Result<Self, Error>
+    where
+        R: io::Read,
+    {
+        let reader = hound::WavReader::new(reader)?;
+        let spec = reader.spec();
+        let samples = match spec.sample_format {
+            hound::SampleFormat::Float => reader.into_samples::<f32>().collect::<Result<_, _>>()?,
+            hound::SampleFormat::Int => {
+                let scale = 1.0 / (1u64 << (spec.bits_per_sample - 1)) as f32;
+                reader
+                    .into_samples::<i32>()
+                    .map(|s| s.map(|s| s as f32 * scale))
+                    .collect::<Result<_, _>>()?
+            }
+        };
+        Ok(File {
+            samples,
+            channels: spec.channels as usize,
+            sample_rate: spec.sample_rate,
+        })
+    }
+
+    /// The interleaved samples of every channel.
+    pub fn samples(&self) -> &[f32] {
+        &self.samples
+    }
+
+    /// An iterator yielding each frame, i.e. a sample for each channel at a single moment in time.
+    pub fn frames(&self) -> std::slice::Chunks<f32> {
+        self.samples.chunks(self.channels)
+    }
+
+    /// The number of channels.
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// The number of frames per second.
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// The number of frames.
+    pub fn len_frames(&self) -> usize {
+        self.samples.len() / self.channels
+    }
+
+    /// The duration of the audio when played back at its sample rate.
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs_f64(self.len_frames() as f64 / self.sample_rate as f64)
+    }
+}
@@ -11,13 +11,28 @@
 //! - [**Receiver**](./receiver/struct.Receiver.html) and
 //!   [**Requester**](./requester/struct.Requester.html) for buffering input and output streams that
 //!   may deliver buffers of inconsistent sizes into a stream of consistently sized buffers.
+//! - [**synth**](./synth/index.html) - oscillators, envelopes and a minimal signal graph for
+//!   synthesis within an output stream's `render` function.
+//! - [**FftReceiver**](./fft/struct.FftReceiver.html) for polling the spectrum of the audio passing
+//!   through a stream, attached via `Stream::attach_fft`.
+//! - [**OnsetDetector**](./analysis/struct.OnsetDetector.html) and
+//!   [**SpectralFlux**](./analysis/struct.SpectralFlux.html) for detecting beat onsets within the
+//!   spectrum of a stream.
+//! - [**effect**](./effect/index.html) - reverb, delay and an **EffectChain** for processing the
+//!   buffer within an output stream's `render` function.
+//! - [**File**](./file/struct.File.html) - audio decoded from a WAV file, e.g. for playback within
+//!   an output stream's `render` function.
 
 use cpal::traits::HostTrait;
 use std::marker::PhantomData;
 use std::sync::Arc;
 
+pub use self::analysis::{OnsetDetector, SpectralFlux};
 pub use self::buffer::Buffer;
 pub use self::device::{Device, Devices};
+pub use self::effect::{AudioEffect, EffectChain};
+pub use self::fft::{Fft, FftReceiver};
+pub use self::file::File;
 pub use self::receiver::Receiver;
 pub use self::requester::Requester;
 pub use self::stream::Stream;
@@ -32,11 +47,16 @@ pub use cpal::{
 };
 pub use dasp_sample;
 
+pub mod analysis;
 pub mod buffer;
 pub mod device;
+pub mod effect;
+pub mod fft;
+pub mod file;
 pub mod receiver;
 pub mod requester;
 pub mod stream;
+pub mod synth;
 
 /// The top-level audio API, for enumerating devices and spawning input/output streams.
 pub struct Host {
@@ -139,6 +159,7 @@ impl Host {
             frames_per_buffer: None,
             device_buffer_size: None,
             device: None,
+            device_name: None,
             sample_format: PhantomData,
         }
     }
@@ -1,4 +1,5 @@
 use crate::{
+    fft,
     stream::{self, DefaultErrorFn, ErrorFn},
     Buffer, Device, Receiver, Stream,
 };
@@ -77,12 +78,26 @@ impl<M, FC, FE, S> Builder<M, FC, FE, S> {
         self
     }
 
+    /// Target the input device with the given name, as produced by `Device::name`.
+    ///
+    /// Building the stream fails with `BuildError::DeviceNotFound` if no such device exists. This
+    /// is ignored if a `device` is also specified.
+    pub fn device_name(mut self, name: &str) -> Self {
+        self.builder.device_name = Some(name.to_string());
+        self
+    }
+
     pub fn frames_per_buffer(mut self, frames_per_buffer: usize) -> Self {
         assert!(frames_per_buffer > 0);
         self.builder.frames_per_buffer = Some(frames_per_buffer);
         self
     }
 
+    /// An alias for `frames_per_buffer`.
+    pub fn buffer_size(self, frames_per_buffer: usize) -> Self {
+        self.frames_per_buffer(frames_per_buffer)
+    }
+
     pub fn device_buffer_size(mut self, buffer_size: cpal::BufferSize) -> Self {
         self.builder.device_buffer_size = Some(buffer_size);
         self
@@ -107,15 +122,20 @@ impl<M, FC, FE, S> Builder<M, FC, FE, S> {
                     frames_per_buffer,
                     device_buffer_size,
                     device,
+                    device_name,
                     ..
                 },
         } = self;
 
-        let device = match device {
-            None => host
+        let device = match (device, device_name) {
+            (Some(Device { device }), _) => device,
+            (None, Some(name)) => {
+                let devices = host.input_devices().ok().into_iter().flatten();
+                super::find_device_by_name(devices, &name)?
+            }
+            (None, None) => host
                 .default_input_device()
                 .ok_or(super::BuildError::DefaultDevice)?,
-            Some(Device { device }) => device,
         };
 
         let desired = super::DesiredStreamConfig {
@@ -134,6 +154,7 @@ impl<M, FC, FE, S> Builder<M, FC, FE, S> {
         )?
         .expect("no matching supported audio input formats for the target device");
         let (update_tx, update_rx) = mpsc::channel();
+        let (fft_taps, mut fft_feed) = fft::taps();
         let model = Arc::new(Mutex::new(Some(model)));
         let model_render = model.clone();
         let model_error = model.clone();
@@ -208,6 +229,9 @@ impl<M, FC, FE, S> Builder<M, FC, FE, S> {
                 }
             }
 
+            // Feed any attached spectral analysers.
+            fft_feed.feed(data, num_channels);
+
             if let Ok(mut guard) = model_render.lock() {
                 let mut m = guard.take().unwrap();
                 m = receiver.read_buffer(m, &capture, &samples, num_channels, sample_rate);
@@ -239,6 +263,7 @@ impl<M, FC, FE, S> Builder<M, FC, FE, S> {
             shared,
             update_tx,
             cpal_config: stream_config,
+            fft_taps,
         };
         Ok(stream)
     }
@@ -1,5 +1,6 @@
+use crate::fft::{self, FftReceiver};
 use crate::Device;
-use cpal::traits::StreamTrait;
+use cpal::traits::{DeviceTrait, StreamTrait};
 use std;
 use std::any::{Any, TypeId};
 use std::marker::PhantomData;
@@ -32,6 +33,8 @@ pub struct Stream<M> {
     shared: Arc<Shared<M>>,
     /// The stream config with which the stream was created.
     cpal_config: cpal::StreamConfig,
+    /// The spectral analysers fed by the audio thread.
+    fft_taps: fft::Taps,
 }
 
 // Data shared between each `Stream` handle to a single stream.
@@ -53,6 +56,7 @@ pub struct Builder<M, S = f32> {
     pub frames_per_buffer: Option<usize>,
     pub device_buffer_size: Option<cpal::BufferSize>,
     pub device: Option<Device>,
+    pub device_name: Option<String>,
     pub(crate) sample_format: PhantomData<S>,
 }
 
@@ -61,6 +65,8 @@ pub struct Builder<M, S = f32> {
 pub enum BuildError {
     #[error("failed to get default device")]
     DefaultDevice,
+    #[error("no device found with the name \"{name}\"")]
+    DeviceNotFound { name: String },
     #[error("failed to enumerate available configs: {err}")]
     SupportedStreamConfigs {
         err: cpal::SupportedStreamConfigsError,
@@ -174,6 +180,17 @@ impl<M> Stream<M> {
     pub fn cpal_config(&self) -> &cpal::StreamConfig {
         &self.cpal_config
     }
+
+    /// Attach a spectral analyser to the stream, transforming the latest `size` samples on each
+    /// call to `FftReceiver::magnitudes`.
+    ///
+    /// For input streams the captured audio is analysed, while for output streams the rendered
+    /// audio is analysed.
+    ///
+    /// **Panics** if `size` is not a power of two greater than `1`.
+    pub fn attach_fft(&self, size: usize) -> FftReceiver {
+        FftReceiver::attach(&self.fft_taps, size, self.cpal_config.sample_rate.0)
+    }
 }
 
 impl<M> Shared<M> {
@@ -205,10 +222,12 @@ impl<M> Clone for Stream<M> {
         let update_tx = self.update_tx.clone();
         let shared = self.shared.clone();
         let cpal_config = self.cpal_config.clone();
+        let fft_taps = self.fft_taps.clone();
         Stream {
             update_tx,
             shared,
             cpal_config,
+            fft_taps,
         }
     }
 }
@@ -444,6 +463,19 @@ where
     }
 }
 
+// Find the device with the given name, if there is one.
+fn find_device_by_name<I>(devices: I, name: &str) -> Result<cpal::Device, BuildError>
+where
+    I: IntoIterator<Item = cpal::Device>,
+{
+    devices
+        .into_iter()
+        .find(|device| device.name().map(|n| n == name).unwrap_or(false))
+        .ok_or_else(|| BuildError::DeviceNotFound {
+            name: name.to_string(),
+        })
+}
+
 // The default error function used when unspecified.
 pub(crate) fn default_error_fn<M>(_: &mut M, err: cpal::StreamError) {
     eprintln!("A `StreamError` occurred: {}", err);
@@ -1,4 +1,5 @@
 use crate::{
+    fft,
     stream::{self, DefaultErrorFn, ErrorFn},
     Buffer, Device, Requester, Stream,
 };
@@ -76,12 +77,26 @@ impl<M, FR, FE, S> Builder<M, FR, FE, S> {
         self
     }
 
+    /// Target the output device with the given name, as produced by `Device::name`.
+    ///
+    /// Building the stream fails with `BuildError::DeviceNotFound` if no such device exists. This
+    /// is ignored if a `device` is also specified.
+    pub fn device_name(mut self, name: &str) -> Self {
+        self.builder.device_name = Some(name.to_string());
+        self
+    }
+
     pub fn frames_per_buffer(mut self, frames_per_buffer: usize) -> Self {
         assert!(frames_per_buffer > 0);
         self.builder.frames_per_buffer = Some(frames_per_buffer);
         self
     }
 
+    /// An alias for `frames_per_buffer`.
+    pub fn buffer_size(self, frames_per_buffer: usize) -> Self {
+        self.frames_per_buffer(frames_per_buffer)
+    }
+
     pub fn device_buffer_size(mut self, buffer_size: cpal::BufferSize) -> Self {
         self.builder.device_buffer_size = Some(buffer_size);
         self
@@ -106,15 +121,20 @@ impl<M, FR, FE, S> Builder<M, FR, FE, S> {
                     frames_per_buffer,
                     device_buffer_size,
                     device,
+                    device_name,
                     ..
                 },
         } = self;
 
-        let device = match device {
-            None => host
+        let device = match (device, device_name) {
+            (Some(Device { device }), _) => device,
+            (None, Some(name)) => {
+                let devices = host.output_devices().ok().into_iter().flatten();
+                super::find_device_by_name(devices, &name)?
+            }
+            (None, None) => host
                 .default_output_device()
                 .ok_or(super::BuildError::DefaultDevice)?,
-            Some(Device { device }) => device,
         };
 
         let desired = super::DesiredStreamConfig {
@@ -133,6 +153,7 @@ impl<M, FR, FE, S> Builder<M, FR, FE, S> {
         )?
         .expect("no matching supported audio output formats for the target device");
         let (update_tx, update_rx) = mpsc::channel();
+        let (fft_taps, mut fft_feed) = fft::taps();
         let model = Arc::new(Mutex::new(Some(model)));
         let model_render = model.clone();
         let model_error = model.clone();
@@ -214,6 +235,9 @@ impl<M, FR, FE, S> Builder<M, FR, FE, S> {
                     fill_output(output, &samples);
                 }
             }
+
+            // Feed any attached spectral analysers.
+            fft_feed.feed(data, num_channels);
         };
 
         // Wrap the user's error function.
@@ -238,6 +262,7 @@ impl<M, FR, FE, S> Builder<M, FR, FE, S> {
             shared,
             update_tx,
             cpal_config: stream_config,
+            fft_taps,
         };
         Ok(stream)
     }
@@ -0,0 +1,582 @@
+//! Simple building blocks for audio synthesis.
+//!
+//! - [**Oscillator**](./struct.Oscillator.html) - a periodic waveform generator.
+//! - [**Adsr**](./struct.Adsr.html) - an attack, decay, sustain, release envelope.
+//! - [**Graph**](./struct.Graph.html) - connects nodes such as the above and evaluates them in
+//!   topological order to produce a signal.
+//!
+//! Both **Oscillator** and **Adsr** may be used on their own as iterators yielding one sample at a
+//! time, or added to a **Graph**:
+//!
+//! ```ignore
+//! let mut graph = Graph::new();
+//! let osc = graph.add(Oscillator::new(Waveform::Saw, sample_rate).frequency(220.0));
+//! let env = graph.add(Adsr::new(sample_rate).attack(0.01).release(0.5));
+//! graph.connect(osc.output(), env.cv_input())?;
+//! graph.connect(env.output(), graph.master())?;
+//!
+//! // Upon receiving a MIDI note on message.
+//! graph.node_mut(osc).set_frequency(440.0);
+//! graph.node_mut(env).gate(true);
+//!
+//! // Within the audio render function.
+//! graph.fill(buffer);
+//! ```
+
+use crate::Buffer;
+use std::any::Any;
+use std::f32::consts::PI;
+use std::marker::PhantomData;
+use thiserror::Error;
+
+/// The shape of the waveform produced by an **Oscillator**.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Waveform {
+    Sine,
+    Square,
+    Saw,
+    Triangle,
+    /// Uniform white noise. The frequency and phase have no effect.
+    Noise,
+}
+
+/// A periodic waveform generator.
+///
+/// When added to a **Graph**, the oscillator's single input is added to its frequency in hz,
+/// allowing for frequency modulation.
+#[derive(Clone, Debug)]
+pub struct Oscillator {
+    waveform: Waveform,
+    frequency: f32,
+    amplitude: f32,
+    phase_offset: f32,
+    // The current phase in the range `0.0..1.0`.
+    phase: f32,
+    sample_rate: f32,
+    // The state of the xorshift generator used for noise.
+    noise: u32,
+}
+
+/// An attack, decay, sustain, release envelope.
+///
+/// The envelope begins its attack when the gate is opened via `gate(true)` and begins its release
+/// when the gate is closed via `gate(false)`.
+///
+/// When added to a **Graph**, the envelope's single input is multiplied by the envelope level,
+/// e.g. to shape the amplitude of an oscillator.
+#[derive(Clone, Debug)]
+pub struct Adsr {
+    attack: f32,
+    decay: f32,
+    sustain: f32,
+    release: f32,
+    sample_rate: f32,
+    stage: Stage,
+    level: f32,
+    // The change in level per sample during the release stage.
+    release_step: f32,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Stage {
+    Idle,
+    Attack,
+    Decay,
+    Sustain,
+    Release,
+}
+
+/// A unit within a **Graph** that produces a single output sample from some number of inputs.
+pub trait Node: AsAny + Send {
+    /// The number of inputs to which other nodes may be connected.
+    fn inputs(&self) -> usize;
+
+    /// Produce the next output sample.
+    ///
+    /// Each input is the sum of all connected outputs, or `0.0` if nothing is connected.
+    fn process(&mut self, inputs: &[f32]) -> f32;
+}
+
+/// Allows for downcasting `Node` trait objects to their concrete type.
+pub trait AsAny: Any {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+/// A handle to a node of type `N` within a **Graph**.
+#[derive(Debug)]
+pub struct Handle<N> {
+    index: usize,
+    node: PhantomData<N>,
+}
+
+/// The output of a node within a **Graph**.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Output {
+    node: usize,
+}
+
+/// An input of a node within a **Graph**, or the graph's master output.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Input {
+    // `None` refers to the master output.
+    node: Option<usize>,
+    index: usize,
+}
+
+/// A minimal signal graph, evaluating its nodes in topological order once per sample.
+///
+/// All outputs connected to the graph's `master` input are summed to produce the graph's signal.
+#[derive(Default)]
+pub struct Graph {
+    nodes: Vec<Slot>,
+    // The evaluation order of the nodes.
+    order: Vec<usize>,
+    // The outputs connected to the master input.
+    master: Vec<usize>,
+}
+
+// A node along with the sources of each of its inputs.
+struct Slot {
+    node: Box<dyn Node>,
+    // `(source node, input index)` for each connection into this node.
+    sources: Vec<(usize, usize)>,
+    inputs: Vec<f32>,
+    value: f32,
+}
+
+/// Errors that might occur when connecting nodes within a **Graph**.
+#[derive(Debug, Error)]
+pub enum ConnectError {
+    #[error("the input index {index} is out of range for a node with {inputs} inputs")]
+    InvalidInput { index: usize, inputs: usize },
+    #[error("the connection would introduce a cycle")]
+    Cycle,
+}
+
+impl Oscillator {
+    /// The default frequency in hz.
+    pub const DEFAULT_FREQUENCY: f32 = 440.0;
+
+    /// An oscillator with the given waveform running at the given sample rate.
+    pub fn new(waveform: Waveform, sample_rate: u32) -> Self {
+        Oscillator {
+            waveform,
+            frequency: Self::DEFAULT_FREQUENCY,
+            amplitude: 1.0,
+            phase_offset: 0.0,
+            phase: 0.0,
+            sample_rate: sample_rate as f32,
+            noise: 0x9E37_79B9,
+        }
+    }
+
+    /// A sine wave oscillator.
+    pub fn sine(sample_rate: u32) -> Self {
+        Self::new(Waveform::Sine, sample_rate)
+    }
+
+    /// Specify the frequency in hz.
+    pub fn frequency(mut self, hz: f32) -> Self {
+        self.set_frequency(hz);
+        self
+    }
+
+    /// Specify the peak amplitude.
+    pub fn amplitude(mut self, amplitude: f32) -> Self {
+        self.set_amplitude(amplitude);
+        self
+    }
+
+    /// Specify the phase offset as a fraction of a cycle, e.g. `0.25` for a quarter cycle.
+    pub fn phase_offset(mut self, offset: f32) -> Self {
+        self.phase_offset = offset;
+        self
+    }
+
+    /// Specify the waveform.
+    pub fn waveform(mut self, waveform: Waveform) -> Self {
+        self.waveform = waveform;
+        self
+    }
+
+    /// Set the frequency in hz.
+    pub fn set_frequency(&mut self, hz: f32) {
+        self.frequency = hz;
+    }
+
+    /// Set the peak amplitude.
+    pub fn set_amplitude(&mut self, amplitude: f32) {
+        self.amplitude = amplitude;
+    }
+
+    /// Restart the waveform from the beginning of its cycle.
+    pub fn reset(&mut self) {
+        self.phase = 0.0;
+    }
+
+    /// Produce the next sample, modulating the frequency by the given number of hz.
+    pub fn next_sample(&mut self, frequency_offset: f32) -> f32 {
+        let t = (self.phase + self.phase_offset).rem_euclid(1.0);
+        let value = match self.waveform {
+            Waveform::Sine => (t * 2.0 * PI).sin(),
+            Waveform::Square => {
+                if t < 0.5 {
+                    1.0
+                } else {
+                    -1.0
+                }
+            }
+            Waveform::Saw => t * 2.0 - 1.0,
+            Waveform::Triangle => 1.0 - 4.0 * ((t + 0.25).rem_euclid(1.0) - 0.5).abs(),
+            Waveform::Noise => {
+                self.noise ^= self.noise << 13;
+                self.noise ^= self.noise >> 17;
+                self.noise ^= self.noise << 5;
+                self.noise as f32 / std::u32::MAX as f32 * 2.0 - 1.0
+            }
+        };
+        let step = (self.frequency + frequency_offset) / self.sample_rate;
+        self.phase = (self.phase + step).rem_euclid(1.0);
+        value * self.amplitude
+    }
+}
+
+impl Iterator for Oscillator {
+    type Item = f32;
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.next_sample(0.0))
+    }
+}
+
+impl Adsr {
+    /// An envelope with an instant attack, decay and release and full sustain.
+    pub fn new(sample_rate: u32) -> Self {
+        Adsr {
+            attack: 0.0,
+            decay: 0.0,
+            sustain: 1.0,
+            release: 0.0,
+            sample_rate: sample_rate as f32,
+            stage: Stage::Idle,
+            level: 0.0,
+            release_step: 0.0,
+        }
+    }
+
+    /// The duration of the attack in seconds, rising from the current level to `1.0`.
+    pub fn attack(mut self, secs: f32) -> Self {
+        self.attack = secs.max(0.0);
+        self
+    }
+
+    /// The duration of the decay in seconds, falling from `1.0` to the sustain level.
+    pub fn decay(mut self, secs: f32) -> Self {
+        self.decay = secs.max(0.0);
+        self
+    }
+
+    /// The level held while the gate remains open, in the range `0.0..=1.0`.
+    pub fn sustain(mut self, level: f32) -> Self {
+        self.sustain = level.max(0.0).min(1.0);
+        self
+    }
+
+    /// The duration of the release in seconds, falling from the current level to `0.0`.
+    pub fn release(mut self, secs: f32) -> Self {
+        self.release = secs.max(0.0);
+        self
+    }
+
+    /// Open or close the gate, triggering the attack or release respectively.
+    pub fn gate(&mut self, open: bool) {
+        if open {
+            self.stage = Stage::Attack;
+        } else if self.stage != Stage::Idle {
+            self.stage = Stage::Release;
+            self.release_step = self.level / self.samples(self.release);
+        }
+    }
+
+    /// Whether or not the envelope is producing a non-zero level.
+    pub fn is_active(&self) -> bool {
+        self.stage != Stage::Idle
+    }
+
+    /// The current level of the envelope.
+    pub fn level(&self) -> f32 {
+        self.level
+    }
+
+    // The number of samples spanned by the given duration, at least one.
+    fn samples(&self, secs: f32) -> f32 {
+        (secs * self.sample_rate).max(1.0)
+    }
+
+    /// Advance the envelope by a single sample and return its level.
+    pub fn next_level(&mut self) -> f32 {
+        match self.stage {
+            Stage::Idle => self.level = 0.0,
+            Stage::Attack => {
+                self.level += 1.0 / self.samples(self.attack);
+                if self.level >= 1.0 {
+                    self.level = 1.0;
+                    self.stage = Stage::Decay;
+                }
+            }
+            Stage::Decay => {
+                self.level -= (1.0 - self.sustain) / self.samples(self.decay);
+                if self.level <= self.sustain {
+                    self.level = self.sustain;
+                    self.stage = Stage::Sustain;
+                }
+            }
+            Stage::Sustain => self.level = self.sustain,
+            Stage::Release => {
+                self.level -= self.release_step;
+                if self.level <= 0.0 {
+                    self.level = 0.0;
+                    self.stage = Stage::Idle;
+                }
+            }
+        }
+        self.level
+    }
+}
+
+impl Iterator for Adsr {
+    type Item = f32;
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.next_level())
+    }
+}
+
+impl Node for Oscillator {
+    fn inputs(&self) -> usize {
+        1
+    }
+
+    fn process(&mut self, inputs: &[f32]) -> f32 {
+        self.next_sample(inputs[0])
+    }
+}
+
+impl Node for Adsr {
+    fn inputs(&self) -> usize {
+        1
+    }
+
+    fn process(&mut self, inputs: &[f32]) -> f32 {
+        inputs[0] * self.next_level()
+    }
+}
+
+impl<T> AsAny for T
+where
+    T: Any,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+impl<N> Handle<N> {
+    /// The output of the node.
+    pub fn output(&self) -> Output {
+        Output { node: self.index }
+    }
+
+    /// The input of the node at the given index.
+    pub fn input(&self, index: usize) -> Input {
+        Input {
+            node: Some(self.index),
+            index,
+        }
+    }
+}
+
+impl Handle<Oscillator> {
+    /// The input that modulates the oscillator's frequency in hz.
+    pub fn frequency_input(&self) -> Input {
+        self.input(0)
+    }
+}
+
+impl Handle<Adsr> {
+    /// The input whose signal is shaped by the envelope.
+    pub fn cv_input(&self) -> Input {
+        self.input(0)
+    }
+}
+
+impl<N> Clone for Handle<N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<N> Copy for Handle<N> {}
+
+impl Graph {
+    /// An empty graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the given node to the graph.
+    pub fn add<N>(&mut self, node: N) -> Handle<N>
+    where
+        N: Node,
+    {
+        let index = self.nodes.len();
+        let inputs = vec![0.0; node.inputs()];
+        self.nodes.push(Slot {
+            node: Box::new(node),
+            sources: vec![],
+            inputs,
+            value: 0.0,
+        });
+        self.order.push(index);
+        Handle {
+            index,
+            node: PhantomData,
+        }
+    }
+
+    /// The graph's master output. All outputs connected to this input are summed to produce the
+    /// graph's signal.
+    pub fn master(&self) -> Input {
+        Input {
+            node: None,
+            index: 0,
+        }
+    }
+
+    /// Connect the given output to the given input.
+    ///
+    /// Multiple outputs connected to the same input are summed.
+    pub fn connect(&mut self, output: Output, input: Input) -> Result<(), ConnectError> {
+        let target = match input.node {
+            None => {
+                self.master.push(output.node);
+                return Ok(());
+            }
+            Some(target) => target,
+        };
+        let inputs = self.nodes[target].inputs.len();
+        if input.index >= inputs {
+            let index = input.index;
+            return Err(ConnectError::InvalidInput { index, inputs });
+        }
+        self.nodes[target].sources.push((output.node, input.index));
+        match self.sorted() {
+            Some(order) => {
+                self.order = order;
+                Ok(())
+            }
+            None => {
+                self.nodes[target].sources.pop();
+                Err(ConnectError::Cycle)
+            }
+        }
+    }
+
+    /// Remove all connections to the given input.
+    pub fn disconnect(&mut self, input: Input) {
+        match input.node {
+            None => self.master.clear(),
+            Some(target) => self.nodes[target]
+                .sources
+                .retain(|&(_, index)| index != input.index),
+        }
+    }
+
+    /// Borrow the node associated with the given handle.
+    pub fn node<N>(&self, handle: Handle<N>) -> &N
+    where
+        N: Node,
+    {
+        // Deref the box so that the node itself is downcast rather than the box.
+        (*self.nodes[handle.index].node)
+            .as_any()
+            .downcast_ref()
+            .expect("handle does not belong to this graph")
+    }
+
+    /// Mutably borrow the node associated with the given handle, e.g. to change its parameters.
+    pub fn node_mut<N>(&mut self, handle: Handle<N>) -> &mut N
+    where
+        N: Node,
+    {
+        (*self.nodes[handle.index].node)
+            .as_any_mut()
+            .downcast_mut()
+            .expect("handle does not belong to this graph")
+    }
+
+    /// Evaluate each node once, producing the next sample of the master output.
+    pub fn next_sample(&mut self) -> f32 {
+        for i in 0..self.order.len() {
+            let ix = self.order[i];
+            let (before, rest) = self.nodes.split_at_mut(ix);
+            let (slot, after) = rest.split_first_mut().unwrap();
+            for input in slot.inputs.iter_mut() {
+                *input = 0.0;
+            }
+            // As the nodes are sorted, all sources have already been evaluated.
+            for &(source, index) in &slot.sources {
+                let value = if source < ix {
+                    before[source].value
+                } else {
+                    after[source - ix - 1].value
+                };
+                slot.inputs[index] += value;
+            }
+            slot.value = slot.node.process(&slot.inputs);
+        }
+        self.master.iter().map(|&ix| self.nodes[ix].value).sum()
+    }
+
+    /// Fill every channel of each frame of the given buffer with the master output.
+    pub fn fill(&mut self, buffer: &mut Buffer) {
+        for frame in buffer.frames_mut() {
+            let sample = self.next_sample();
+            for channel in frame {
+                *channel = sample;
+            }
+        }
+    }
+
+    // A topological ordering of the nodes, or `None` if the graph contains a cycle.
+    fn sorted(&self) -> Option<Vec<usize>> {
+        let mut dependents = vec![vec![]; self.nodes.len()];
+        let mut remaining: Vec<usize> = self.nodes.iter().map(|s| s.sources.len()).collect();
+        for (ix, slot) in self.nodes.iter().enumerate() {
+            for &(source, _) in &slot.sources {
+                dependents[source].push(ix);
+            }
+        }
+        let mut ready: Vec<usize> = (0..self.nodes.len())
+            .filter(|&ix| remaining[ix] == 0)
+            .collect();
+        let mut order = Vec::with_capacity(self.nodes.len());
+        while let Some(ix) = ready.pop() {
+            order.push(ix);
+            for &dependent in &dependents[ix] {
+                remaining[dependent] -= 1;
+                if remaining[dependent] == 0 {
+                    ready.push(dependent);
+                }
+            }
+        }
+        if order.len() == self.nodes.len() {
+            Some(order)
+        } else {
+            None
+        }
+    }
+}
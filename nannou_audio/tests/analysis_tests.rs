@@ -0,0 +1,55 @@
+use nannou_audio::{OnsetDetector, SpectralFlux};
+use std::time::{Duration, Instant};
+
+#[test]
+fn spectral_flux_test() {
+    let mut flux = SpectralFlux::new();
+    assert_eq!(flux.process(&[0.5, 0.5, 0.5]), 0.0);
+    // Only increases in magnitude contribute.
+    assert_eq!(flux.process(&[1.0, 0.25, 0.75]), 0.75);
+    assert_eq!(flux.process(&[0.0, 0.0, 0.0]), 0.0);
+    assert_eq!(flux.flux(), 0.0);
+}
+
+#[test]
+fn onset_detector_test() {
+    let mut detector = OnsetDetector::new(0.5, 0.1);
+    let quiet = [0.1; 8];
+    let loud = [0.5; 8];
+    let louder = [0.9; 8];
+    let start = Instant::now();
+    let frame = Duration::from_millis(16);
+    let mut onsets = vec![];
+    for i in 0..40 {
+        let at = start + frame * i;
+        // A hit every ten frames, along with a louder hit that falls within the cooldown.
+        let magnitudes = match i {
+            26 => &louder,
+            i if i % 10 == 5 => &loud,
+            _ => &quiet,
+        };
+        if detector.on_onset_at(magnitudes, at) {
+            onsets.push(i);
+        }
+    }
+    assert_eq!(onsets, vec![5, 15, 25, 35]);
+}
+
+#[test]
+fn onset_detector_cooldown_clamp_test() {
+    let start = Instant::now();
+    // Infinite cooldowns are clamped rather than panicking, while `NaN` and negative cooldowns
+    // are treated as no cooldown.
+    for &(cooldown_secs, cooled) in &[
+        (std::f32::INFINITY, false),
+        (std::f32::NAN, true),
+        (-1.0, true),
+    ] {
+        let mut detector = OnsetDetector::new(0.5, cooldown_secs);
+        assert!(!detector.on_onset_at(&[0.0; 8], start));
+        assert!(detector.on_onset_at(&[0.5; 8], start));
+        // A louder hit a second later.
+        let later = start + Duration::from_secs(1);
+        assert_eq!(detector.on_onset_at(&[4.0; 8], later), cooled);
+    }
+}
@@ -0,0 +1,77 @@
+use nannou_audio::effect::{Delay, Reverb, CONVOLUTION_BLOCK_SIZE};
+use nannou_audio::{AudioEffect, EffectChain};
+
+#[test]
+fn delay_test() {
+    let mut delay = Delay::new(0.5, 0.5).mix(1.0);
+    delay.set_sample_rate(4);
+    let output: Vec<f32> = (0..7)
+        .map(|i| delay.process(if i == 0 { 1.0 } else { 0.0 }))
+        .collect();
+    assert_eq!(output, vec![0.0, 0.0, 1.0, 0.0, 0.5, 0.0, 0.25]);
+}
+
+#[test]
+fn convolution_reverb_test() {
+    let mut reverb = Reverb::convolution(&[1.0, 0.5, 0.25], 44_100).mix(1.0);
+    let output: Vec<f32> = (0..CONVOLUTION_BLOCK_SIZE * 3)
+        .map(|i| reverb.process(if i == 1 { 1.0 } else { 0.0 }))
+        .collect();
+    // The output is delayed by a single block.
+    for (i, &sample) in output.iter().enumerate() {
+        let expected = match i.checked_sub(CONVOLUTION_BLOCK_SIZE + 1) {
+            Some(0) => 1.0,
+            Some(1) => 0.5,
+            Some(2) => 0.25,
+            _ => 0.0,
+        };
+        assert!((sample - expected).abs() < 1e-4, "sample {}: {}", i, sample);
+    }
+}
+
+#[test]
+fn effect_chain_test() {
+    let mut chain = EffectChain::new();
+    assert!(chain.is_empty());
+    assert_eq!(chain.process(0.5), 0.5);
+    chain = chain
+        .add(Reverb::schroeder(0.8, 0.5))
+        .add(Delay::new(0.25, 0.4));
+    assert_eq!(chain.len(), 2);
+    // The tail of the reverb continues after the input stops.
+    chain.process(1.0);
+    let tail: f32 = (0..44_100).map(|_| chain.process(0.0).abs()).sum();
+    assert!(tail > 0.0);
+}
+
+#[test]
+fn convolution_reverb_partitions_test() {
+    // An impulse response spanning several partitions.
+    let mut impulse_response = vec![0.0; CONVOLUTION_BLOCK_SIZE * 3];
+    impulse_response[0] = 1.0;
+    impulse_response[CONVOLUTION_BLOCK_SIZE * 2 + 10] = 0.5;
+    let mut reverb = Reverb::convolution(&impulse_response, 44_100).mix(1.0);
+    let output: Vec<f32> = (0..CONVOLUTION_BLOCK_SIZE * 6)
+        .map(|i| reverb.process(if i == 0 { 1.0 } else { 0.0 }))
+        .collect();
+    for (i, &sample) in output.iter().enumerate() {
+        let expected = match i.checked_sub(CONVOLUTION_BLOCK_SIZE) {
+            Some(0) => 1.0,
+            Some(j) if j == CONVOLUTION_BLOCK_SIZE * 2 + 10 => 0.5,
+            _ => 0.0,
+        };
+        assert!((sample - expected).abs() < 1e-4, "sample {}: {}", i, sample);
+    }
+}
+
+#[test]
+fn effect_chain_interleaved_test() {
+    let mut chain = EffectChain::new().add(Delay::new(0.5, 0.0).mix(1.0));
+    chain.set_sample_rate(4);
+    // An impulse on the left channel only, followed by silence.
+    let mut samples = vec![0.0; 2 * 4];
+    samples[0] = 1.0;
+    chain.process_interleaved(&mut samples, 2);
+    // The delayed impulse remains on the left channel.
+    assert_eq!(samples, vec![0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0]);
+}
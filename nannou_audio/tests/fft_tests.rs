@@ -0,0 +1,45 @@
+use nannou_audio::Fft;
+use std::f32::consts::PI;
+
+fn sine(size: usize, bin: usize, amp: f32) -> Vec<f32> {
+    (0..size)
+        .map(|i| amp * (2.0 * PI * bin as f32 * i as f32 / size as f32).sin())
+        .collect()
+}
+
+#[test]
+fn fft_sine_magnitude_test() {
+    let size = 256;
+    let mut fft = Fft::new(size);
+    let magnitudes = fft.process(&sine(size, 16, 1.0));
+    assert_eq!(magnitudes.len(), size / 2);
+    assert!((magnitudes[16] - 1.0).abs() < 1e-3);
+    // The Hann window leaks into the neighbouring bins only.
+    for (bin, &magnitude) in magnitudes.iter().enumerate() {
+        if (bin as isize - 16).abs() > 1 {
+            assert!(magnitude < 1e-3, "bin {}: {}", bin, magnitude);
+        }
+    }
+}
+
+#[test]
+fn fft_amplitude_test() {
+    let size = 128;
+    let mut fft = Fft::new(size);
+    let magnitude = fft.process(&sine(size, 8, 0.25))[8];
+    assert!((magnitude - 0.25).abs() < 1e-3);
+    assert_eq!(fft.magnitudes()[8], magnitude);
+}
+
+#[test]
+fn fft_silence_test() {
+    let mut fft = Fft::new(64);
+    assert!(fft.process(&[]).iter().all(|&m| m == 0.0));
+    assert!(fft.process(&[0.0; 64]).iter().all(|&m| m == 0.0));
+}
+
+#[test]
+#[should_panic]
+fn fft_size_test() {
+    Fft::new(100);
+}
@@ -0,0 +1,56 @@
+use nannou_audio::File;
+use std::io::Cursor;
+use std::time::Duration;
+
+// Encode the given interleaved samples as WAV data.
+fn wav<S>(spec: hound::WavSpec, samples: &[S]) -> Vec<u8>
+where
+    S: hound::Sample + Copy,
+{
+    let mut bytes = Cursor::new(vec![]);
+    let mut writer = hound::WavWriter::new(&mut bytes, spec).unwrap();
+    for &sample in samples {
+        writer.write_sample(sample).unwrap();
+    }
+    writer.finalize().unwrap();
+    bytes.into_inner()
+}
+
+#[test]
+fn file_int_test() {
+    let spec = hound::WavSpec {
+        channels: 2,
+        sample_rate: 4,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let bytes = wav(spec, &[0i16, 16384, -16384, -32768]);
+    let file = File::from_reader(Cursor::new(bytes)).unwrap();
+    assert_eq!(file.channels(), 2);
+    assert_eq!(file.sample_rate(), 4);
+    assert_eq!(file.len_frames(), 2);
+    assert_eq!(file.duration(), Duration::from_millis(500));
+    assert_eq!(file.samples(), &[0.0, 0.5, -0.5, -1.0]);
+    let frames: Vec<&[f32]> = file.frames().collect();
+    assert_eq!(frames, vec![&[0.0, 0.5][..], &[-0.5, -1.0][..]]);
+}
+
+#[test]
+fn file_float_test() {
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: 44_100,
+        bits_per_sample: 32,
+        sample_format: hound::SampleFormat::Float,
+    };
+    let bytes = wav(spec, &[0.25f32, -0.75]);
+    let file = File::from_reader(Cursor::new(bytes)).unwrap();
+    assert_eq!(file.channels(), 1);
+    assert_eq!(file.samples(), &[0.25, -0.75]);
+}
+
+#[test]
+fn file_error_test() {
+    assert!(File::from_reader(Cursor::new(b"not a wav file".to_vec())).is_err());
+    assert!(File::open("does/not/exist.wav").is_err());
+}
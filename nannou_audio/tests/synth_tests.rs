@@ -0,0 +1,136 @@
+use nannou_audio::synth::{Adsr, ConnectError, Graph, Node, Oscillator, Waveform};
+
+// A node producing a constant value.
+struct Constant(f32);
+
+impl Node for Constant {
+    fn inputs(&self) -> usize {
+        0
+    }
+
+    fn process(&mut self, _inputs: &[f32]) -> f32 {
+        self.0
+    }
+}
+
+fn levels(env: &mut Adsr, n: usize) -> Vec<f32> {
+    env.take(n).collect()
+}
+
+#[test]
+fn adsr_stages_test() {
+    // At 8hz, each stage spans a whole number of samples with exact steps.
+    let mut env = Adsr::new(8)
+        .attack(0.5)
+        .decay(0.5)
+        .sustain(0.5)
+        .release(0.25);
+    assert!(!env.is_active());
+    assert_eq!(levels(&mut env, 2), vec![0.0, 0.0]);
+
+    env.gate(true);
+    assert!(env.is_active());
+    assert_eq!(levels(&mut env, 4), vec![0.25, 0.5, 0.75, 1.0]);
+    assert_eq!(levels(&mut env, 4), vec![0.875, 0.75, 0.625, 0.5]);
+    assert_eq!(levels(&mut env, 3), vec![0.5, 0.5, 0.5]);
+
+    env.gate(false);
+    assert_eq!(levels(&mut env, 2), vec![0.25, 0.0]);
+    assert!(!env.is_active());
+    assert_eq!(env.level(), 0.0);
+}
+
+#[test]
+fn adsr_retrigger_test() {
+    let mut env = Adsr::new(8).attack(0.5).release(0.5);
+    env.gate(true);
+    assert_eq!(levels(&mut env, 4), vec![0.25, 0.5, 0.75, 1.0]);
+    env.gate(false);
+    assert_eq!(levels(&mut env, 2), vec![0.75, 0.5]);
+    // Re-opening the gate during the release attacks from the current level.
+    env.gate(true);
+    assert_eq!(levels(&mut env, 2), vec![0.75, 1.0]);
+
+    // Closing the gate of an idle envelope has no effect.
+    let mut env = Adsr::new(8);
+    env.gate(false);
+    assert!(!env.is_active());
+}
+
+#[test]
+fn graph_mix_test() {
+    let mut graph = Graph::new();
+    assert_eq!(graph.next_sample(), 0.0);
+    let a = graph.add(Constant(0.25));
+    let b = graph.add(Constant(0.5));
+    graph.connect(a.output(), graph.master()).unwrap();
+    graph.connect(b.output(), graph.master()).unwrap();
+    assert_eq!(graph.next_sample(), 0.75);
+
+    graph.disconnect(graph.master());
+    assert_eq!(graph.next_sample(), 0.0);
+}
+
+#[test]
+fn graph_envelope_test() {
+    let mut graph = Graph::new();
+    let a = graph.add(Constant(0.25));
+    let b = graph.add(Constant(0.5));
+    let env = graph.add(Adsr::new(8));
+    // Outputs connected to the same input are summed before being shaped by the envelope.
+    graph.connect(env.output(), graph.master()).unwrap();
+    graph.connect(a.output(), env.cv_input()).unwrap();
+    graph.connect(b.output(), env.cv_input()).unwrap();
+    assert_eq!(graph.next_sample(), 0.0);
+
+    graph.node_mut(env).gate(true);
+    assert_eq!(graph.next_sample(), 0.75);
+    assert_eq!(graph.node(env).level(), 1.0);
+
+    graph.node_mut(env).gate(false);
+    assert_eq!(graph.next_sample(), 0.0);
+    assert!(!graph.node(env).is_active());
+}
+
+#[test]
+fn graph_frequency_modulation_test() {
+    // A square wave at a quarter of the sample rate alternates every two samples.
+    let mut graph = Graph::new();
+    let offset = graph.add(Constant(1.0));
+    let osc = graph.add(Oscillator::new(Waveform::Square, 8).frequency(1.0));
+    graph.connect(osc.output(), graph.master()).unwrap();
+    graph
+        .connect(offset.output(), osc.frequency_input())
+        .unwrap();
+    let samples: Vec<f32> = (0..8).map(|_| graph.next_sample()).collect();
+    let mut reference = Oscillator::new(Waveform::Square, 8).frequency(2.0);
+    let expected: Vec<f32> = (0..8).map(|_| reference.next_sample(0.0)).collect();
+    assert_eq!(samples, expected);
+}
+
+#[test]
+fn graph_connect_error_test() {
+    let mut graph = Graph::new();
+    let a = graph.add(Adsr::new(8));
+    let b = graph.add(Adsr::new(8));
+    let c = graph.add(Constant(1.0));
+    match graph.connect(a.output(), c.input(0)) {
+        Err(ConnectError::InvalidInput {
+            index: 0,
+            inputs: 0,
+        }) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    graph.connect(a.output(), b.cv_input()).unwrap();
+    match graph.connect(b.output(), a.cv_input()) {
+        Err(ConnectError::Cycle) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    // The rejected connection is not retained.
+    graph.connect(c.output(), a.cv_input()).unwrap();
+    graph.connect(b.output(), graph.master()).unwrap();
+    graph.node_mut(a).gate(true);
+    graph.node_mut(b).gate(true);
+    assert_eq!(graph.next_sample(), 1.0);
+}
@@ -0,0 +1,53 @@
+//! A cargo subcommand for working with nannou projects, run via `cargo nannou`.
+
+use std::env;
+use std::process::{self, Command};
+
+// Matches `nannou::testing::UPDATE_GOLDEN_ENV_VAR`.
+const UPDATE_GOLDEN_ENV_VAR: &str = "NANNOU_UPDATE_GOLDEN";
+
+const USAGE: &str = "\
+Usage:
+    cargo nannou golden update [<test-name>] [<cargo-test-args>...]
+
+Commands:
+    golden update    Re-render and overwrite the golden images of the matching tests.";
+
+fn main() {
+    // When run as `cargo nannou`, cargo passes the subcommand name as the first argument.
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    if args.first().map(|arg| arg == "nannou").unwrap_or(false) {
+        args.remove(0);
+    }
+
+    let code = match args.split_first() {
+        Some((cmd, rest)) if cmd == "golden" => match rest.split_first() {
+            Some((cmd, rest)) if cmd == "update" => golden_update(rest),
+            _ => usage(),
+        },
+        _ => usage(),
+    };
+    process::exit(code);
+}
+
+// Run the matching tests with golden image updates enabled.
+fn golden_update(args: &[String]) -> i32 {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let status = Command::new(cargo)
+        .arg("test")
+        .args(args)
+        .env(UPDATE_GOLDEN_ENV_VAR, "1")
+        .status();
+    match status {
+        Ok(status) => status.code().unwrap_or(1),
+        Err(e) => {
+            eprintln!("failed to run `cargo test`: {}", e);
+            1
+        }
+    }
+}
+
+fn usage() -> i32 {
+    eprintln!("{}", USAGE);
+    1
+}
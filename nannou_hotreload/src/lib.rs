@@ -0,0 +1,493 @@
+//! Live-reloading of nannou sketch code without restarting the app or losing its state.
+//!
+//! The sketch is written as a library crate with `crate-type = ["dylib"]` whose model struct is
+//! annotated with `#[nannou::reloadable]` (requires nannou's `hotreload` feature). A small binary
+//! then hands the library's crate directory to [**run**](./fn.run.html). Both the sketch and the
+//! binary depend on `nannou_hotreload`:
+//!
+//! ```ignore
+//! // sketch/src/lib.rs
+//! use nannou::prelude::*;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[nannou::reloadable]
+//! #[derive(Deserialize, Serialize)]
+//! pub struct Model {
+//!     angle: f32,
+//! }
+//!
+//! fn model(_app: &App) -> Model {
+//!     Model { angle: 0.0 }
+//! }
+//!
+//! fn update(_app: &App, model: &mut Model, _update: Update) {
+//!     model.angle += 0.01;
+//! }
+//!
+//! fn view(app: &App, model: &Model, frame: Frame) {
+//!     let draw = app.draw();
+//!     draw.background().color(BLACK);
+//!     draw.rect().w_h(100.0, 100.0).rotate(model.angle).color(PLUM);
+//!     draw.to_frame(app, &frame).unwrap();
+//! }
+//!
+//! // host/src/main.rs
+//! fn main() {
+//!     nannou_hotreload::run("../sketch");
+//! }
+//! ```
+//!
+//! In debug builds, the sketch's `src` directory is watched for changes. Upon each change the
+//! library is rebuilt via `cargo build` on a background thread while the current build keeps
+//! running. Once the build succeeds, the model is serialised to JSON using the previous build and
+//! restored using the new build during the next update. Fields that fail to deserialise, e.g. after
+//! changing their type, cause the model to be re-initialised via the sketch's `model` function.
+//! Release builds load the library once without watching.
+//!
+//! ## Sharing nannou
+//!
+//! nannou types such as the **App** and **Frame** are passed between the host and the sketch. To
+//! ensure that both sides agree on the layout of these types and share nannou's global state,
+//! `nannou_hotreload` is itself built as a Rust `dylib` containing nannou, which both the host and
+//! the sketch link to dynamically. This is also why the sketch must be a `dylib` rather than a
+//! `cdylib`, as the latter would contain a copy of nannou of its own.
+//!
+//! Upon loading each build, the host checks that the sketch links to the same copy of
+//! `nannou_hotreload` before calling any of its exports, failing with
+//! [**Error::NannouMismatch**](./enum.Error.html#variant.NannouMismatch) otherwise.
+//!
+//! ## Limitations
+//!
+//! - The host and the sketch must be members of the same workspace, be built with the same
+//!   compiler and enable the same nannou features, such that both link to the same build of
+//!   `nannou_hotreload`.
+//! - The model must be serialisable. Resources such as textures should be `#[serde(skip)]`ped and
+//!   recreated lazily.
+//! - Windows created by the sketch must not be given window-specific functions (e.g. via
+//!   `window::Builder::view`), as these are bound to the sketch's model type. All windows are
+//!   instead drawn via the sketch's `view` function.
+//! - Previous builds of the library remain loaded until the app exits, as the app may still hold
+//!   references to their code. Each build is loaded from a copy within the temporary directory,
+//!   removed once the build is replaced or, where the platform forbids removing loaded
+//!   libraries, upon exit.
+
+use libloading::Library;
+use nannou::prelude::*;
+use notify::{DebouncedEvent, RecursiveMode, Watcher};
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::Duration;
+use std::{env, fmt, fs, io, thread};
+
+/// The duration for which file changes are debounced before rebuilding.
+pub const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Errors that might occur while building or loading the sketch library.
+#[derive(Debug)]
+pub enum Error {
+    /// Failed to run cargo or copy the library.
+    Io(io::Error),
+    /// `cargo build` did not succeed.
+    Build,
+    /// The build did not produce a dynamic library. Is `crate-type = ["dylib"]` specified?
+    NoLibrary,
+    /// Failed to load the library or one of its exports. Is `#[reloadable]` applied?
+    Load(libloading::Error),
+    /// The sketch does not link to the host's copy of `nannou_hotreload`, e.g. due to differing
+    /// nannou features, and so cannot safely share nannou types with the host.
+    NannouMismatch,
+    /// Failed to watch the sketch's source for changes.
+    Watch(notify::Error),
+}
+
+type InstanceFn = extern "C" fn() -> *const u8;
+type ModelFn = fn(&App) -> *mut ();
+type RestoreFn = fn(&App, &str) -> *mut ();
+type SaveFn = unsafe fn(*mut ()) -> String;
+type DropFn = unsafe fn(*mut ());
+type UpdateFn = unsafe fn(&App, *mut (), Update);
+type ViewFn = unsafe fn(&App, *mut (), Frame);
+type EventFn = unsafe fn(&App, *mut (), Event);
+
+// A loaded build of the sketch library along with its exports.
+struct Sketch {
+    // Kept alive for as long as the exports below are in use.
+    library: Option<Library>,
+    // The copy of the library from which it was loaded.
+    path: PathBuf,
+    model: ModelFn,
+    restore: RestoreFn,
+    save: SaveFn,
+    drop: DropFn,
+    update: UpdateFn,
+    view: ViewFn,
+    event: Option<EventFn>,
+}
+
+// The model of the host app.
+struct Host {
+    crate_dir: PathBuf,
+    sketch: Sketch,
+    // The sketch's model, owned by the current build of the sketch.
+    model: *mut (),
+    // The number of builds loaded so far, used to name the copies of each build.
+    builds: usize,
+    // Previous builds, kept loaded as the app may still reference their code.
+    retired: Vec<Sketch>,
+    changes: Option<(notify::RecommendedWatcher, mpsc::Receiver<DebouncedEvent>)>,
+    // Delivers the copy of the library produced by the build in progress, if any.
+    build: Option<mpsc::Receiver<Result<PathBuf, Error>>>,
+    // Whether the source changed since the build in progress began.
+    rebuild: bool,
+}
+
+// A byte whose address identifies the copy of this library in use.
+static INSTANCE: u8 = 0;
+
+thread_local! {
+    // The crate directory given to `run`, read by the host's model function.
+    static CRATE_DIR: RefCell<Option<PathBuf>> = RefCell::new(None);
+}
+
+/// Run the sketch library within the crate at the given directory, reloading it upon changes to
+/// its source in debug builds.
+///
+/// **Panics** if the initial build of the library fails.
+pub fn run<P>(crate_dir: P)
+where
+    P: AsRef<Path>,
+{
+    let crate_dir = crate_dir.as_ref().to_path_buf();
+    CRATE_DIR.with(|dir| *dir.borrow_mut() = Some(crate_dir));
+    nannou::app(model)
+        .update(update)
+        .event(event)
+        .view(view)
+        .exit(exit)
+        .run();
+}
+
+/// The address identifying the copy of `nannou_hotreload` in use by the caller.
+///
+/// Used by the exports generated by `#[reloadable]`, allowing the host to check that the sketch
+/// shares its copy of nannou.
+#[doc(hidden)]
+pub fn instance() -> *const u8 {
+    &INSTANCE
+}
+
+fn model(app: &App) -> Host {
+    let crate_dir = CRATE_DIR
+        .with(|dir| dir.borrow_mut().take())
+        .expect("no sketch crate directory was given");
+    let sketch = build_and_copy(&crate_dir, 0).and_then(|copy| load(&copy));
+    let sketch = sketch.unwrap_or_else(|err| {
+        panic!(
+            "failed to load sketch at `{}`: {}",
+            crate_dir.display(),
+            err
+        )
+    });
+    let changes = match cfg!(debug_assertions) {
+        true => match watch(&crate_dir) {
+            Ok(changes) => Some(changes),
+            Err(err) => {
+                eprintln!("failed to watch sketch, hot-reloading disabled: {}", err);
+                None
+            }
+        },
+        false => None,
+    };
+    let model = (sketch.model)(app);
+    Host {
+        crate_dir,
+        sketch,
+        model,
+        builds: 1,
+        retired: vec![],
+        changes,
+        build: None,
+        rebuild: false,
+    }
+}
+
+fn update(app: &App, host: &mut Host, update: Update) {
+    if host.source_changed() {
+        host.rebuild = true;
+    }
+    host.poll_build(app);
+    if host.rebuild && host.build.is_none() {
+        host.start_build();
+    }
+    unsafe { (host.sketch.update)(app, host.model, update) }
+}
+
+fn event(app: &App, host: &mut Host, event: Event) {
+    // Updates are delivered via `update`.
+    if let Event::Update(_) = event {
+        return;
+    }
+    if let Some(event_fn) = host.sketch.event {
+        unsafe { event_fn(app, host.model, event) }
+    }
+}
+
+fn view(app: &App, host: &Host, frame: Frame) {
+    unsafe { (host.sketch.view)(app, host.model, frame) }
+}
+
+fn exit(_app: &App, host: Host) {
+    unsafe { (host.sketch.drop)(host.model) }
+}
+
+impl Host {
+    // Whether or not any of the sketch's source files changed since the last call.
+    fn source_changed(&self) -> bool {
+        let rx = match self.changes {
+            Some((_, ref rx)) => rx,
+            None => return false,
+        };
+        let mut changed = false;
+        for event in rx.try_iter() {
+            match event {
+                DebouncedEvent::Create(_)
+                | DebouncedEvent::Write(_)
+                | DebouncedEvent::Remove(_)
+                | DebouncedEvent::Rename(_, _) => changed = true,
+                _ => (),
+            }
+        }
+        changed
+    }
+
+    // Begin rebuilding the sketch on a background thread.
+    fn start_build(&mut self) {
+        println!("sketch changed, rebuilding...");
+        let (tx, rx) = mpsc::channel();
+        let crate_dir = self.crate_dir.clone();
+        let build = self.builds;
+        thread::Builder::new()
+            .name("nannou_hotreload-build".into())
+            .spawn(move || tx.send(build_and_copy(&crate_dir, build)).ok())
+            .expect("failed to spawn sketch build thread");
+        self.builds += 1;
+        self.build = Some(rx);
+        self.rebuild = false;
+    }
+
+    // Load the build in progress if it has completed, carrying the model across via JSON.
+    //
+    // The current build remains in use if the rebuild fails.
+    fn poll_build(&mut self, app: &App) {
+        let result = match self.build {
+            None => return,
+            Some(ref rx) => match rx.try_recv() {
+                Ok(result) => result,
+                Err(mpsc::TryRecvError::Empty) => return,
+                Err(mpsc::TryRecvError::Disconnected) => Err(Error::Build),
+            },
+        };
+        self.build = None;
+        let sketch = match result.and_then(|copy| load(&copy)) {
+            Ok(sketch) => sketch,
+            Err(err) => {
+                eprintln!("failed to reload sketch: {}", err);
+                return;
+            }
+        };
+        let json = unsafe {
+            let json = (self.sketch.save)(self.model);
+            (self.sketch.drop)(self.model);
+            json
+        };
+        self.model = (sketch.restore)(app, &json);
+        let previous = std::mem::replace(&mut self.sketch, sketch);
+        // Platforms that forbid removing loaded libraries retry upon exit.
+        let _ = fs::remove_file(&previous.path);
+        self.retired.push(previous);
+        println!("sketch reloaded");
+    }
+}
+
+impl Drop for Sketch {
+    fn drop(&mut self) {
+        // Unload the library before removing its copy.
+        self.library.take();
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+// Watch the sketch's source directory and manifest for changes.
+fn watch(
+    crate_dir: &Path,
+) -> Result<(notify::RecommendedWatcher, mpsc::Receiver<DebouncedEvent>), Error> {
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::watcher(tx, DEBOUNCE)?;
+    watcher.watch(crate_dir.join("src"), RecursiveMode::Recursive)?;
+    watcher.watch(crate_dir.join("Cargo.toml"), RecursiveMode::NonRecursive)?;
+    Ok((watcher, rx))
+}
+
+// Build the sketch library and copy it to a uniquely named file, producing the path to the copy.
+//
+// The copy ensures that the OS loads the new build rather than returning the cached library.
+fn build_and_copy(crate_dir: &Path, build: usize) -> Result<PathBuf, Error> {
+    let library = build_library(crate_dir)?;
+    let name = library
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("sketch");
+    let extension = library.extension().and_then(|s| s.to_str()).unwrap_or("");
+    let copy = env::temp_dir().join(format!(
+        "nannou_hotreload_{}_{}_{}.{}",
+        name,
+        std::process::id(),
+        build,
+        extension
+    ));
+    fs::copy(&library, &copy)?;
+    Ok(copy)
+}
+
+// Run `cargo build` for the sketch, producing the path to the built dynamic library.
+fn build_library(crate_dir: &Path) -> Result<PathBuf, Error> {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let mut command = Command::new(cargo);
+    command
+        .arg("build")
+        .arg("--lib")
+        .arg("--message-format=json")
+        .current_dir(crate_dir)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit());
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+    let output = command.output()?;
+    if !output.status.success() {
+        return Err(Error::Build);
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut library = None;
+    for line in stdout.lines() {
+        let msg: serde_json::Value = match serde_json::from_str(line) {
+            Ok(msg) => msg,
+            Err(_) => continue,
+        };
+        if msg["reason"] != "compiler-artifact" {
+            continue;
+        }
+        let is_dylib = msg["target"]["kind"]
+            .as_array()
+            .map(|kinds| kinds.iter().any(|k| k == "dylib"))
+            .unwrap_or(false);
+        if !is_dylib {
+            continue;
+        }
+        let filenames = msg["filenames"].as_array().cloned().unwrap_or_default();
+        library = filenames
+            .iter()
+            .filter_map(|f| f.as_str())
+            .map(PathBuf::from)
+            .find(|path| {
+                let ext = path.extension().and_then(|e| e.to_str());
+                ext == Some(env::consts::DLL_EXTENSION)
+            })
+            .or(library);
+    }
+    library.ok_or(Error::NoLibrary)
+}
+
+// Load the copy of the library at the given path along with the exports generated by
+// `#[reloadable]`, removing the copy upon failure.
+//
+// Fails if the library does not share this copy of nannou.
+fn load(path: &Path) -> Result<Sketch, Error> {
+    let result = load_exports(path);
+    if result.is_err() {
+        let _ = fs::remove_file(path);
+    }
+    result
+}
+
+fn load_exports(path: &Path) -> Result<Sketch, Error> {
+    let library = Library::new(path)?;
+    unsafe {
+        let sketch_instance = *library.get::<InstanceFn>(b"__nannou_hotreload_instance\0")?;
+        if sketch_instance() != instance() {
+            return Err(Error::NannouMismatch);
+        }
+        let model = *library.get::<ModelFn>(b"__nannou_hotreload_model\0")?;
+        let restore = *library.get::<RestoreFn>(b"__nannou_hotreload_restore\0")?;
+        let save = *library.get::<SaveFn>(b"__nannou_hotreload_save\0")?;
+        let drop = *library.get::<DropFn>(b"__nannou_hotreload_drop\0")?;
+        let update = *library.get::<UpdateFn>(b"__nannou_hotreload_update\0")?;
+        let view = *library.get::<ViewFn>(b"__nannou_hotreload_view\0")?;
+        let event = library
+            .get::<EventFn>(b"__nannou_hotreload_event\0")
+            .ok()
+            .map(|f| *f);
+        Ok(Sketch {
+            library: Some(library),
+            path: path.to_path_buf(),
+            model,
+            restore,
+            save,
+            drop,
+            update,
+            view,
+            event,
+        })
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<libloading::Error> for Error {
+    fn from(err: libloading::Error) -> Self {
+        Error::Load(err)
+    }
+}
+
+impl From<notify::Error> for Error {
+    fn from(err: notify::Error) -> Self {
+        Error::Watch(err)
+    }
+}
+
+impl std::error::Error for Error {
+    fn cause(&self) -> Option<&dyn std::error::Error> {
+        match *self {
+            Error::Io(ref err) => Some(err),
+            Error::Load(ref err) => Some(err),
+            Error::Watch(ref err) => Some(err),
+            Error::Build | Error::NoLibrary | Error::NannouMismatch => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Io(ref err) => err.fmt(f),
+            Error::Build => write!(f, "`cargo build` failed"),
+            Error::NoLibrary => write!(
+                f,
+                "the build produced no dynamic library, is `crate-type = [\"dylib\"]` specified?"
+            ),
+            Error::NannouMismatch => write!(
+                f,
+                "the sketch does not share the host's copy of nannou, are the same nannou \
+                 features enabled for both?"
+            ),
+            Error::Load(ref err) => write!(f, "failed to load the sketch library: {}", err),
+            Error::Watch(ref err) => write!(f, "failed to watch the sketch: {}", err),
+        }
+    }
+}
@@ -0,0 +1,245 @@
+//! The `#[reloadable]` attribute, generating the dynamic library boundary required to hot-reload a
+//! nannou sketch via [**nannou_hotreload**](https://docs.rs/nannou_hotreload).
+//!
+//! Usually accessed as `nannou::reloadable` via nannou's `hotreload` feature.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Data, DeriveInput, Ident, Token};
+
+// The sketch functions generated exports may call, with the default name of each.
+const FUNCTIONS: &[(&str, &str)] = &[("model", "model"), ("update", "update"), ("view", "view")];
+
+/// Generate the exports through which `nannou_hotreload` drives a sketch compiled as a `dylib`.
+///
+/// The attribute is applied to the sketch's model struct, which must implement
+/// `serde::Serialize` and `serde::de::DeserializeOwned` so that its state may be carried across
+/// reloads. The sketch crate must depend on `nannou_hotreload`, which provides the copy of nannou
+/// shared with the host.
+///
+/// By default the sketch's `model`, `update` and `view` functions are expected to be declared in
+/// the same module as the struct. Functions with other names may be specified as arguments, along
+/// with an optional `event` function:
+///
+/// ```ignore
+/// #[nannou::reloadable(update = step, event = event)]
+/// #[derive(Deserialize, Serialize)]
+/// struct Model {
+///     radius: f32,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn reloadable(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = match parse_args(attr.into()) {
+        Ok(args) => args,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let name = match struct_name(item.clone().into()) {
+        Some(name) => name,
+        None => {
+            let msg = "`#[reloadable]` must be applied to a model `struct`";
+            let err = syn::Error::new(proc_macro2::Span::call_site(), msg);
+            return err.to_compile_error().into();
+        }
+    };
+    let function = |key: &str| -> Option<String> {
+        args.iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.clone())
+            .or_else(|| {
+                FUNCTIONS
+                    .iter()
+                    .find(|(k, _)| *k == key)
+                    .map(|(_, v)| v.to_string())
+            })
+    };
+    let model = function("model").expect("no default model function");
+    let update = function("update").expect("no default update function");
+    let view = function("view").expect("no default view function");
+
+    // The `instance` export allows the host to check that the sketch shares its copy of nannou
+    // before calling any of the other exports.
+    let mut exports = format!(
+        r#"
+        #[doc(hidden)]
+        #[no_mangle]
+        pub extern "C" fn __nannou_hotreload_instance() -> *const u8 {{
+            nannou_hotreload::instance()
+        }}
+
+        #[doc(hidden)]
+        #[no_mangle]
+        pub fn __nannou_hotreload_model(app: &nannou::App) -> *mut () {{
+            Box::into_raw(Box::new({model}(app))) as *mut ()
+        }}
+
+        #[doc(hidden)]
+        #[no_mangle]
+        pub fn __nannou_hotreload_restore(app: &nannou::App, json: &str) -> *mut () {{
+            match nannou::serde_json::from_str::<{name}>(json) {{
+                Ok(model) => Box::into_raw(Box::new(model)) as *mut (),
+                Err(err) => {{
+                    eprintln!("failed to restore the model, re-initialising: {{}}", err);
+                    __nannou_hotreload_model(app)
+                }}
+            }}
+        }}
+
+        #[doc(hidden)]
+        #[no_mangle]
+        pub unsafe fn __nannou_hotreload_save(model: *mut ()) -> String {{
+            let model = &*(model as *const {name});
+            nannou::serde_json::to_string(model).unwrap_or_else(|err| {{
+                eprintln!("failed to save the model: {{}}", err);
+                String::new()
+            }})
+        }}
+
+        #[doc(hidden)]
+        #[no_mangle]
+        pub unsafe fn __nannou_hotreload_drop(model: *mut ()) {{
+            drop(Box::from_raw(model as *mut {name}));
+        }}
+
+        #[doc(hidden)]
+        #[no_mangle]
+        pub unsafe fn __nannou_hotreload_update(
+            app: &nannou::App,
+            model: *mut (),
+            update: nannou::event::Update,
+        ) {{
+            {update}(app, &mut *(model as *mut {name}), update);
+        }}
+
+        #[doc(hidden)]
+        #[no_mangle]
+        pub unsafe fn __nannou_hotreload_view(
+            app: &nannou::App,
+            model: *mut (),
+            frame: nannou::Frame,
+        ) {{
+            {view}(app, &*(model as *const {name}), frame);
+        }}
+        "#,
+        name = name,
+        model = model,
+        update = update,
+        view = view,
+    );
+    if let Some(event) = function("event") {
+        exports.push_str(&format!(
+            r#"
+            #[doc(hidden)]
+            #[no_mangle]
+            pub unsafe fn __nannou_hotreload_event(
+                app: &nannou::App,
+                model: *mut (),
+                event: nannou::Event,
+            ) {{
+                {event}(app, &mut *(model as *mut {name}), event);
+            }}
+            "#,
+            name = name,
+            event = event,
+        ));
+    }
+
+    let exports: TokenStream = exports.parse().expect("failed to parse generated exports");
+    let mut output = item;
+    output.extend(exports);
+    output
+}
+
+// A single argument of the form `key = ident`.
+struct Arg {
+    key: Ident,
+    value: Ident,
+}
+
+// The comma-separated arguments given to the attribute.
+struct Args(Punctuated<Arg, Token![,]>);
+
+impl Parse for Arg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key: Ident = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let value: Ident = input.parse()?;
+        Ok(Arg { key, value })
+    }
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Punctuated::parse_terminated(input).map(Args)
+    }
+}
+
+// Parse arguments of the form `key = ident, ...`.
+fn parse_args(attr: proc_macro2::TokenStream) -> syn::Result<Vec<(String, String)>> {
+    let Args(args) = syn::parse2(attr).map_err(|err| {
+        let msg = "expected arguments of the form `update = my_update`";
+        syn::Error::new(err.span(), msg)
+    })?;
+    args.into_iter()
+        .map(|Arg { key, value }| {
+            let name = key.to_string();
+            if name != "event" && !FUNCTIONS.iter().any(|(k, _)| *k == name) {
+                let msg = format!("unknown `#[reloadable]` function `{}`", name);
+                return Err(syn::Error::new(key.span(), msg));
+            }
+            Ok((name, value.to_string()))
+        })
+        .collect()
+}
+
+// The name of the struct to which the attribute was applied.
+fn struct_name(item: proc_macro2::TokenStream) -> Option<String> {
+    let input: DeriveInput = syn::parse2(item).ok()?;
+    match input.data {
+        Data::Struct(_) => Some(input.ident.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(s: &str) -> proc_macro2::TokenStream {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(parse_args(tokens("")).unwrap(), vec![]);
+        let args = parse_args(tokens("update = step, event = event,")).unwrap();
+        let expected = vec![
+            ("update".to_string(), "step".to_string()),
+            ("event".to_string(), "event".to_string()),
+        ];
+        assert_eq!(args, expected);
+    }
+
+    #[test]
+    fn test_parse_args_errors() {
+        assert!(parse_args(tokens("draw = render")).is_err());
+        assert!(parse_args(tokens("update")).is_err());
+        assert!(parse_args(tokens("update = \"step\"")).is_err());
+        assert!(parse_args(tokens("update = step view = show")).is_err());
+    }
+
+    #[test]
+    fn test_struct_name() {
+        let name = struct_name(tokens("#[derive(Serialize)] pub struct Model { a: f32 }"));
+        assert_eq!(name, Some("Model".to_string()));
+        assert_eq!(
+            struct_name(tokens("struct Unit;")),
+            Some("Unit".to_string())
+        );
+        assert_eq!(struct_name(tokens("enum Model { A }")), None);
+        assert_eq!(struct_name(tokens("fn model() {}")), None);
+    }
+}
@@ -0,0 +1,271 @@
+//! Tempo-synced timing driven by an external MIDI clock, e.g. from a DAW or DJ software.
+//!
+//! A MIDI clock source sends 24 *pulses* per quarter note along with *start*, *stop* and
+//! *continue* messages. The **Clock** measures the tempo from the interval between pulses and
+//! interpolates between them, providing a phase that is smooth enough to drive animation at
+//! normal frame rates.
+//!
+//! ```ignore
+//! struct Model {
+//!     clock: midi::Clock,
+//!     flashes: u32,
+//! }
+//!
+//! fn update(_app: &App, model: &mut Model, _update: Update) {
+//!     model.clock.on_beat(&mut model.flashes, |flashes| *flashes += 1);
+//! }
+//!
+//! fn view(app: &App, model: &Model, frame: Frame) {
+//!     let radius = 100.0 + 50.0 * (1.0 - model.clock.beat_phase()) as f32;
+//!     // ...
+//! }
+//! ```
+
+use crate::{Error, Input, InputBuilder, MidiMessage};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// The number of clock pulses per quarter note, as specified by the MIDI standard.
+pub const PULSES_PER_QUARTER_NOTE: u32 = 24;
+
+/// The number of beats per bar assumed by `bar_phase`.
+pub const BEATS_PER_BAR: u32 = 4;
+
+/// Pulse intervals longer than this are discarded when measuring the tempo, e.g. following a
+/// pause in the clock source.
+pub const MAX_PULSE_INTERVAL: Duration = Duration::from_millis(250);
+
+// The number of recent pulse intervals averaged when measuring the tempo.
+const INTERVAL_WINDOW: usize = PULSES_PER_QUARTER_NOTE as usize;
+
+// Interpolation stops just short of the next pulse so that the phase never runs ahead of it.
+const MAX_INTERPOLATION: f64 = 0.999;
+
+const CLOCK: u8 = 0xF8;
+const START: u8 = 0xFA;
+const CONTINUE: u8 = 0xFB;
+const STOP: u8 = 0xFC;
+const SONG_POSITION: u8 = 0xF2;
+
+/// A clock following the tempo and position of an external MIDI clock source.
+pub struct Clock {
+    state: Arc<Mutex<State>>,
+    // Keeps the connection open when the clock was built via `InputBuilder::build_clock`.
+    input: Option<Input<Arc<Mutex<State>>>>,
+    // The relocation and beat of the last call to `on_beat`.
+    last_beat: Option<(u64, u64)>,
+}
+
+// The state updated for each clock message.
+#[derive(Debug, Default)]
+struct State {
+    playing: bool,
+    // The position of the next pulse in pulses since the start of the song.
+    next_pulse: u64,
+    // The position of the last pulse received while playing.
+    position: Option<u64>,
+    // Incremented each time the position is reset via a start or song position message.
+    relocations: u64,
+    // The time at which the last pulse was received, whether playing or not.
+    last_pulse_at: Option<Instant>,
+    // The most recent intervals between pulses.
+    intervals: VecDeque<Duration>,
+}
+
+impl InputBuilder {
+    /// Connect to the port, following the tempo and position of the MIDI clock messages received
+    /// from it.
+    ///
+    /// The messages are timestamped upon arrival on the MIDI thread.
+    pub fn build_clock(self) -> Result<Clock, Error> {
+        let state = Arc::new(Mutex::new(State::default()));
+        let input = self.build(state.clone(), |state, msg| {
+            if let Ok(mut state) = state.lock() {
+                state.handle_message(&msg, Instant::now());
+            }
+        })?;
+        Ok(Clock {
+            state,
+            input: Some(input),
+            last_beat: None,
+        })
+    }
+}
+
+impl Clock {
+    /// A clock that is not connected to a port.
+    ///
+    /// Messages may be delivered manually via `handle_message`. See `InputBuilder::build_clock`
+    /// for a clock connected directly to a port, which measures the tempo more precisely.
+    pub fn new() -> Self {
+        Clock {
+            state: Arc::new(Mutex::new(State::default())),
+            input: None,
+            last_beat: None,
+        }
+    }
+
+    /// The name of the connected port, if any.
+    pub fn port_name(&self) -> Option<&str> {
+        self.input.as_ref().map(|input| input.port_name())
+    }
+
+    /// Update the clock with the given message, received now.
+    ///
+    /// All messages other than clock, start, stop, continue and song position are ignored.
+    pub fn handle_message(&self, msg: &MidiMessage) {
+        self.handle_message_at(msg, Instant::now());
+    }
+
+    /// Update the clock with the given message, received at the given instant.
+    pub fn handle_message_at(&self, msg: &MidiMessage, instant: Instant) {
+        self.lock().handle_message(msg, instant);
+    }
+
+    /// The tempo in beats per minute, measured from the most recent pulses.
+    ///
+    /// Returns `0.0` until at least two consecutive pulses have been received.
+    pub fn bpm(&self) -> f64 {
+        match self.lock().pulse_interval() {
+            Some(interval) => 60.0 / (interval * PULSES_PER_QUARTER_NOTE as f64),
+            None => 0.0,
+        }
+    }
+
+    /// Whether or not the clock source is playing, i.e. a start or continue message has been
+    /// received more recently than a stop message.
+    pub fn is_playing(&self) -> bool {
+        self.lock().playing
+    }
+
+    /// The position in beats since the start of the song, interpolated between pulses.
+    pub fn beats(&self) -> f64 {
+        self.beats_at(Instant::now())
+    }
+
+    /// The position in beats since the start of the song at the given instant.
+    ///
+    /// Returns `0.0` if no pulse has been received since the clock source started playing.
+    pub fn beats_at(&self, instant: Instant) -> f64 {
+        self.lock().pulses_at(instant) / PULSES_PER_QUARTER_NOTE as f64
+    }
+
+    /// The phase within the current beat in the range `0.0..1.0`.
+    pub fn beat_phase(&self) -> f64 {
+        self.beats().fract()
+    }
+
+    /// The phase within the current bar in the range `0.0..1.0`, assuming 4/4 time.
+    pub fn bar_phase(&self) -> f64 {
+        (self.beats() / BEATS_PER_BAR as f64).fract()
+    }
+
+    /// Call `handler` with the given `model` if a beat has been crossed since the last call.
+    ///
+    /// Intended to be called once per update. The handler is called at most once per call, even
+    /// if multiple beats have been crossed, and is not called while the clock source is stopped.
+    pub fn on_beat<M>(&mut self, model: &mut M, handler: fn(&mut M)) {
+        let beat = {
+            let state = self.lock();
+            match state.position {
+                Some(position) if state.playing => {
+                    (state.relocations, position / PULSES_PER_QUARTER_NOTE as u64)
+                }
+                _ => return,
+            }
+        };
+        if self.last_beat != Some(beat) {
+            self.last_beat = Some(beat);
+            handler(model);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<State> {
+        self.state
+            .lock()
+            .expect("failed to acquire clock state lock")
+    }
+}
+
+impl State {
+    fn handle_message(&mut self, msg: &MidiMessage, instant: Instant) {
+        let bytes = match *msg {
+            MidiMessage::Other(ref bytes) => bytes,
+            _ => return,
+        };
+        match bytes.first() {
+            Some(&CLOCK) => self.pulse(instant),
+            Some(&START) => {
+                self.playing = true;
+                self.next_pulse = 0;
+                self.position = None;
+                self.relocations += 1;
+            }
+            Some(&CONTINUE) => self.playing = true,
+            Some(&STOP) => self.playing = false,
+            Some(&SONG_POSITION) => {
+                // The position is measured in sixteenth notes, each of which is six pulses.
+                if let (Some(&lsb), Some(&msb)) = (bytes.get(1), bytes.get(2)) {
+                    let sixteenths = (lsb & 0x7F) as u64 | ((msb & 0x7F) as u64) << 7;
+                    self.next_pulse = sixteenths * 6;
+                    self.position = None;
+                    self.relocations += 1;
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn pulse(&mut self, instant: Instant) {
+        if let Some(last) = self.last_pulse_at {
+            let interval = instant.saturating_duration_since(last);
+            if interval <= MAX_PULSE_INTERVAL {
+                if self.intervals.len() == INTERVAL_WINDOW {
+                    self.intervals.pop_front();
+                }
+                self.intervals.push_back(interval);
+            } else {
+                self.intervals.clear();
+            }
+        }
+        self.last_pulse_at = Some(instant);
+        if self.playing {
+            self.position = Some(self.next_pulse);
+            self.next_pulse += 1;
+        }
+    }
+
+    // The mean interval between pulses in seconds.
+    fn pulse_interval(&self) -> Option<f64> {
+        if self.intervals.is_empty() {
+            return None;
+        }
+        let total: Duration = self.intervals.iter().sum();
+        Some(total.as_secs_f64() / self.intervals.len() as f64)
+    }
+
+    // The interpolated position in pulses at the given instant.
+    fn pulses_at(&self, instant: Instant) -> f64 {
+        let position = match self.position {
+            None => return 0.0,
+            Some(position) => position as f64,
+        };
+        if !self.playing {
+            return position;
+        }
+        match (self.last_pulse_at, self.pulse_interval()) {
+            (Some(at), Some(interval)) => {
+                let elapsed = instant.saturating_duration_since(at).as_secs_f64();
+                position + (elapsed / interval).min(MAX_INTERPOLATION)
+            }
+            _ => position,
+        }
+    }
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,148 @@
+//! Items related to receiving MIDI messages from an input port.
+
+use crate::{find_port, Error, MidiMessage, CLIENT_NAME};
+use std::sync::mpsc;
+
+/// A type used for configuring and connecting to a MIDI input port.
+#[derive(Clone, Debug, Default)]
+pub struct InputBuilder {
+    port: Option<String>,
+    channel: Option<u8>,
+}
+
+/// A connection to a MIDI input port.
+///
+/// Messages are delivered to the handler on the MIDI thread as they arrive. The connection is
+/// closed when the `Input` is dropped.
+pub struct Input<M> {
+    connection: midir::MidiInputConnection<Handler<M>>,
+    port_name: String,
+}
+
+/// A connection to a MIDI input port that queues incoming messages.
+///
+/// Unlike `Input`, messages are not handled on the MIDI thread. Instead they are stored until
+/// collected via `try_iter` or `dispatch`, e.g. within the app's `update` function.
+pub struct Receiver {
+    input: Input<mpsc::Sender<MidiMessage>>,
+    rx: mpsc::Receiver<MidiMessage>,
+}
+
+// The state owned by the MIDI thread.
+struct Handler<M> {
+    model: M,
+    handler: fn(&mut M, MidiMessage),
+    channel: Option<u8>,
+}
+
+impl InputBuilder {
+    /// Begin building a connection to a MIDI input port.
+    ///
+    /// By default the first available port is used and messages on all channels are delivered.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Connect to the port with the given name.
+    ///
+    /// If no port has exactly this name, the first port whose name contains it is used. This is
+    /// useful as some platforms decorate device names, e.g. with a client number.
+    pub fn port(mut self, name: &str) -> Self {
+        self.port = Some(name.to_string());
+        self
+    }
+
+    /// Only deliver messages sent on the given channel in the range `0..16`.
+    ///
+    /// Messages that do not belong to a channel, e.g. `SysEx`, are always delivered.
+    pub fn channel(mut self, channel: u8) -> Self {
+        self.channel = Some(channel);
+        self
+    }
+
+    /// Connect to the port, calling `handler` with the given `model` for each message received.
+    ///
+    /// The handler is called on the MIDI thread. See `build_receiver` for handling messages on
+    /// the main thread instead.
+    pub fn build<M>(self, model: M, handler: fn(&mut M, MidiMessage)) -> Result<Input<M>, Error>
+    where
+        M: 'static + Send,
+    {
+        let midi_in = midir::MidiInput::new(CLIENT_NAME)?;
+        let ports = midi_in.ports();
+        let mut names = Vec::with_capacity(ports.len());
+        for port in &ports {
+            names.push(midi_in.port_name(port)?);
+        }
+        let ix = find_port(&names, self.port.as_ref().map(|s| &s[..]))?;
+        let port_name = names.swap_remove(ix);
+        let data = Handler {
+            model,
+            handler,
+            channel: self.channel,
+        };
+        let connection = midi_in
+            .connect(&ports[ix], CLIENT_NAME, receive, data)
+            .map_err(|err| Error::Connect(err.to_string()))?;
+        Ok(Input {
+            connection,
+            port_name,
+        })
+    }
+
+    /// Connect to the port, queueing received messages so that they may be handled on the
+    /// current thread.
+    pub fn build_receiver(self) -> Result<Receiver, Error> {
+        let (tx, rx) = mpsc::channel();
+        let input = self.build(tx, |tx, msg| {
+            tx.send(msg).ok();
+        })?;
+        Ok(Receiver { input, rx })
+    }
+}
+
+impl<M> Input<M> {
+    /// The name of the connected port.
+    pub fn port_name(&self) -> &str {
+        &self.port_name
+    }
+
+    /// Close the connection, returning the model.
+    pub fn close(self) -> M {
+        let (_midi_in, data) = self.connection.close();
+        data.model
+    }
+}
+
+impl Receiver {
+    /// The name of the connected port.
+    pub fn port_name(&self) -> &str {
+        self.input.port_name()
+    }
+
+    /// An iterator yielding all pending messages without blocking.
+    pub fn try_iter(&self) -> mpsc::TryIter<MidiMessage> {
+        self.rx.try_iter()
+    }
+
+    /// Call `handler` with the given `model` for each pending message.
+    pub fn dispatch<M>(&self, model: &mut M, handler: fn(&mut M, MidiMessage)) {
+        for msg in self.try_iter() {
+            handler(model, msg);
+        }
+    }
+}
+
+// Called on the MIDI thread for each message received.
+fn receive<M>(_timestamp: u64, bytes: &[u8], data: &mut Handler<M>) {
+    let msg = match MidiMessage::from_bytes(bytes) {
+        Some(msg) => msg,
+        None => return,
+    };
+    if let (Some(channel), Some(msg_channel)) = (data.channel, msg.channel()) {
+        if channel != msg_channel {
+            return;
+        }
+    }
+    (data.handler)(&mut data.model, msg);
+}
@@ -0,0 +1,286 @@
+//! A simple API for sending and receiving MIDI messages to and from hardware controllers, DAWs and other software.
+//!
+//! - [**InputBuilder**](./input/struct.InputBuilder.html) for connecting to a MIDI input port.
+//! - [**Output**](./output/struct.Output.html) for sending messages to a MIDI output port, along
+//!   with [**VirtualOutput**](./output/struct.VirtualOutput.html) for creating a port to which
+//!   other software may connect on platforms that support it.
+//! - [**MidiMessage**](./enum.MidiMessage.html) describing the messages delivered to the handler.
+//! - [**Clock**](./clock/struct.Clock.html) for following the tempo and position of an external
+//!   MIDI clock source.
+//! - [**available_ports**](./fn.available_ports.html) and
+//!   [**available_output_ports**](./fn.available_output_ports.html) for discovering the names of
+//!   connected devices at runtime.
+//! - [**note_to_freq**](./fn.note_to_freq.html) and [**note_name**](./fn.note_name.html) utilities
+//!   for working with MIDI note numbers.
+
+pub use self::clock::Clock;
+pub use self::input::{Input, InputBuilder, Receiver};
+pub use self::output::Output;
+#[cfg(unix)]
+pub use self::output::VirtualOutput;
+pub use midir;
+use thiserror::Error;
+
+pub mod clock;
+pub mod input;
+pub mod output;
+
+/// The name with which nannou registers itself as a client of the platform's MIDI API.
+pub const CLIENT_NAME: &str = "nannou";
+
+/// The frequency of the note `A4` (MIDI note `69`) in hz.
+pub const A4_FREQ: f32 = 440.0;
+
+/// The MIDI note number of the note `A4`.
+pub const A4_NOTE: u8 = 69;
+
+/// A single MIDI message.
+///
+/// Channels are in the range `0..16`, while notes, velocities and controller values are in the
+/// range `0..128`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MidiMessage {
+    /// A key was pressed.
+    ///
+    /// A note on with a velocity of `0` is delivered as a `NoteOff`.
+    NoteOn { channel: u8, note: u8, velocity: u8 },
+    /// A key was released.
+    NoteOff { channel: u8, note: u8, velocity: u8 },
+    /// The pressure of a single held key changed.
+    PolyPressure { channel: u8, note: u8, pressure: u8 },
+    /// A knob, fader, pedal or other controller changed.
+    ControlChange {
+        channel: u8,
+        controller: u8,
+        value: u8,
+    },
+    /// A new program, or patch, was selected.
+    ProgramChange { channel: u8, program: u8 },
+    /// The pressure applied to all held keys changed.
+    ChannelPressure { channel: u8, pressure: u8 },
+    /// The pitch bend wheel moved.
+    ///
+    /// The value is in the range `-1.0..=1.0` where `0.0` is the center.
+    PitchBend { channel: u8, value: f32 },
+    /// A system exclusive message, excluding the leading `0xF0` and trailing `0xF7` bytes.
+    SysEx(Vec<u8>),
+    /// Any other message, e.g. system common or realtime messages, as raw bytes.
+    Other(Vec<u8>),
+}
+
+/// Errors that might occur while connecting to or communicating with a MIDI port.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("failed to initialise the MIDI client: {0}")]
+    Init(#[from] midir::InitError),
+    #[error("failed to retrieve MIDI port info: {0}")]
+    PortInfo(#[from] midir::PortInfoError),
+    #[error("no MIDI ports are available")]
+    NoPorts,
+    #[error("no MIDI port matching \"{name}\" was found")]
+    PortNotFound { name: String },
+    #[error("failed to connect to the MIDI port: {0}")]
+    Connect(String),
+    #[error("failed to send the MIDI message: {0}")]
+    Send(#[from] midir::SendError),
+}
+
+const NOTE_NAMES: [&str; 128] = [
+    "C-1", "C#-1", "D-1", "D#-1", "E-1", "F-1", "F#-1", "G-1", "G#-1", "A-1", "A#-1", "B-1", "C0",
+    "C#0", "D0", "D#0", "E0", "F0", "F#0", "G0", "G#0", "A0", "A#0", "B0", "C1", "C#1", "D1",
+    "D#1", "E1", "F1", "F#1", "G1", "G#1", "A1", "A#1", "B1", "C2", "C#2", "D2", "D#2", "E2", "F2",
+    "F#2", "G2", "G#2", "A2", "A#2", "B2", "C3", "C#3", "D3", "D#3", "E3", "F3", "F#3", "G3",
+    "G#3", "A3", "A#3", "B3", "C4", "C#4", "D4", "D#4", "E4", "F4", "F#4", "G4", "G#4", "A4",
+    "A#4", "B4", "C5", "C#5", "D5", "D#5", "E5", "F5", "F#5", "G5", "G#5", "A5", "A#5", "B5", "C6",
+    "C#6", "D6", "D#6", "E6", "F6", "F#6", "G6", "G#6", "A6", "A#6", "B6", "C7", "C#7", "D7",
+    "D#7", "E7", "F7", "F#7", "G7", "G#7", "A7", "A#7", "B7", "C8", "C#8", "D8", "D#8", "E8", "F8",
+    "F#8", "G8", "G#8", "A8", "A#8", "B8", "C9", "C#9", "D9", "D#9", "E9", "F9", "F#9", "G9",
+];
+
+impl MidiMessage {
+    /// Parse a single message from the given raw bytes.
+    ///
+    /// Returns `None` if the bytes do not begin with a status byte or if the message is
+    /// truncated.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let status = *bytes.first()?;
+        if status < 0x80 {
+            return None;
+        }
+        let channel = status & 0x0F;
+        let data = |i: usize| bytes.get(i).map(|&b| b & 0x7F);
+        let msg = match status & 0xF0 {
+            0x80 => MidiMessage::NoteOff {
+                channel,
+                note: data(1)?,
+                velocity: data(2)?,
+            },
+            0x90 => {
+                let (note, velocity) = (data(1)?, data(2)?);
+                if velocity == 0 {
+                    MidiMessage::NoteOff {
+                        channel,
+                        note,
+                        velocity,
+                    }
+                } else {
+                    MidiMessage::NoteOn {
+                        channel,
+                        note,
+                        velocity,
+                    }
+                }
+            }
+            0xA0 => MidiMessage::PolyPressure {
+                channel,
+                note: data(1)?,
+                pressure: data(2)?,
+            },
+            0xB0 => MidiMessage::ControlChange {
+                channel,
+                controller: data(1)?,
+                value: data(2)?,
+            },
+            0xC0 => MidiMessage::ProgramChange {
+                channel,
+                program: data(1)?,
+            },
+            0xD0 => MidiMessage::ChannelPressure {
+                channel,
+                pressure: data(1)?,
+            },
+            0xE0 => {
+                let raw = data(1)? as u16 | (data(2)? as u16) << 7;
+                let value = (raw as f32 - 8192.0) / 8192.0;
+                MidiMessage::PitchBend { channel, value }
+            }
+            _ if status == 0xF0 => {
+                let end = bytes.len() - (bytes.last() == Some(&0xF7)) as usize;
+                MidiMessage::SysEx(bytes[1..end.max(1)].to_vec())
+            }
+            _ => MidiMessage::Other(bytes.to_vec()),
+        };
+        Some(msg)
+    }
+
+    /// Encode the message as raw bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match *self {
+            MidiMessage::NoteOn {
+                channel,
+                note,
+                velocity,
+            } => vec![0x90 | channel & 0x0F, note & 0x7F, velocity & 0x7F],
+            MidiMessage::NoteOff {
+                channel,
+                note,
+                velocity,
+            } => vec![0x80 | channel & 0x0F, note & 0x7F, velocity & 0x7F],
+            MidiMessage::PolyPressure {
+                channel,
+                note,
+                pressure,
+            } => vec![0xA0 | channel & 0x0F, note & 0x7F, pressure & 0x7F],
+            MidiMessage::ControlChange {
+                channel,
+                controller,
+                value,
+            } => vec![0xB0 | channel & 0x0F, controller & 0x7F, value & 0x7F],
+            MidiMessage::ProgramChange { channel, program } => {
+                vec![0xC0 | channel & 0x0F, program & 0x7F]
+            }
+            MidiMessage::ChannelPressure { channel, pressure } => {
+                vec![0xD0 | channel & 0x0F, pressure & 0x7F]
+            }
+            MidiMessage::PitchBend { channel, value } => {
+                let raw = (value.max(-1.0).min(1.0) * 8192.0 + 8192.0).round() as u16;
+                let raw = raw.min(0x3FFF);
+                vec![0xE0 | channel & 0x0F, (raw & 0x7F) as u8, (raw >> 7) as u8]
+            }
+            MidiMessage::SysEx(ref data) => {
+                let mut bytes = Vec::with_capacity(data.len() + 2);
+                bytes.push(0xF0);
+                bytes.extend(data.iter().map(|&b| b & 0x7F));
+                bytes.push(0xF7);
+                bytes
+            }
+            MidiMessage::Other(ref bytes) => bytes.clone(),
+        }
+    }
+
+    /// The channel on which the message was sent, if it is a channel message.
+    pub fn channel(&self) -> Option<u8> {
+        match *self {
+            MidiMessage::NoteOn { channel, .. }
+            | MidiMessage::NoteOff { channel, .. }
+            | MidiMessage::PolyPressure { channel, .. }
+            | MidiMessage::ControlChange { channel, .. }
+            | MidiMessage::ProgramChange { channel, .. }
+            | MidiMessage::ChannelPressure { channel, .. }
+            | MidiMessage::PitchBend { channel, .. } => Some(channel),
+            MidiMessage::SysEx(_) | MidiMessage::Other(_) => None,
+        }
+    }
+}
+
+/// The names of all MIDI input ports currently available on the system.
+///
+/// Returns an empty list if the platform's MIDI API is unavailable.
+pub fn available_ports() -> Vec<String> {
+    let midi_in = match midir::MidiInput::new(CLIENT_NAME) {
+        Ok(midi_in) => midi_in,
+        Err(_) => return vec![],
+    };
+    midi_in
+        .ports()
+        .iter()
+        .filter_map(|port| midi_in.port_name(port).ok())
+        .collect()
+}
+
+/// The names of all MIDI output ports currently available on the system.
+///
+/// Returns an empty list if the platform's MIDI API is unavailable.
+pub fn available_output_ports() -> Vec<String> {
+    let midi_out = match midir::MidiOutput::new(CLIENT_NAME) {
+        Ok(midi_out) => midi_out,
+        Err(_) => return vec![],
+    };
+    midi_out
+        .ports()
+        .iter()
+        .filter_map(|port| midi_out.port_name(port).ok())
+        .collect()
+}
+
+/// The frequency in hz of the given MIDI note number in twelve-tone equal temperament, where
+/// note `69` is `A4` at 440hz.
+pub fn note_to_freq(note: u8) -> f32 {
+    A4_FREQ * 2.0f32.powf((note as f32 - A4_NOTE as f32) / 12.0)
+}
+
+/// The name of the given MIDI note number using sharps and scientific pitch notation, e.g. `60` is
+/// `"C4"` and `61` is `"C#4"`.
+///
+/// Values above `127` are masked to their lower 7 bits.
+pub fn note_name(note: u8) -> &'static str {
+    NOTE_NAMES[(note & 0x7F) as usize]
+}
+
+// Find the index of the port with the given name within the list of port names.
+//
+// An exact match is preferred, falling back to the first name containing `name`. If no name is
+// given, the first port is selected.
+pub(crate) fn find_port(names: &[String], name: Option<&str>) -> Result<usize, Error> {
+    match name {
+        None if names.is_empty() => Err(Error::NoPorts),
+        None => Ok(0),
+        Some(name) => names
+            .iter()
+            .position(|n| n == name)
+            .or_else(|| names.iter().position(|n| n.contains(name)))
+            .ok_or_else(|| Error::PortNotFound {
+                name: name.to_string(),
+            }),
+    }
+}
@@ -0,0 +1,125 @@
+//! Items related to sending MIDI messages to an output port.
+
+use crate::{find_port, Error, MidiMessage, CLIENT_NAME};
+
+/// A connection to a MIDI output port, e.g. a hardware synth or a DAW.
+///
+/// The `Output` is `Send` and may be stored within the app's model. The connection is closed when
+/// the `Output` is dropped.
+pub struct Output {
+    connection: midir::MidiOutputConnection,
+    port_name: String,
+}
+
+/// A virtual MIDI output port to which other software, e.g. a DAW, may connect.
+///
+/// Only available on platforms that support virtual ports, i.e. macOS via CoreMIDI and Linux via
+/// ALSA. Dereferences to an `Output` for sending messages.
+#[cfg(unix)]
+pub struct VirtualOutput {
+    output: Output,
+}
+
+impl Output {
+    /// Connect to the output port with the given name.
+    ///
+    /// If no port has exactly this name, the first port whose name contains it is used.
+    pub fn open(port_name: &str) -> Result<Self, Error> {
+        let midi_out = midir::MidiOutput::new(CLIENT_NAME)?;
+        let ports = midi_out.ports();
+        let mut names = Vec::with_capacity(ports.len());
+        for port in &ports {
+            names.push(midi_out.port_name(port)?);
+        }
+        let ix = find_port(&names, Some(port_name))?;
+        let port_name = names.swap_remove(ix);
+        let connection = midi_out
+            .connect(&ports[ix], CLIENT_NAME)
+            .map_err(|err| Error::Connect(err.to_string()))?;
+        Ok(Output {
+            connection,
+            port_name,
+        })
+    }
+
+    /// The name of the connected port.
+    pub fn port_name(&self) -> &str {
+        &self.port_name
+    }
+
+    /// Send the given message.
+    pub fn send(&mut self, msg: &MidiMessage) -> Result<(), Error> {
+        self.send_raw(&msg.to_bytes())
+    }
+
+    /// Begin playing the given note.
+    pub fn send_note_on(&mut self, channel: u8, note: u8, velocity: u8) -> Result<(), Error> {
+        self.send(&MidiMessage::NoteOn {
+            channel,
+            note,
+            velocity,
+        })
+    }
+
+    /// Stop playing the given note.
+    pub fn send_note_off(&mut self, channel: u8, note: u8) -> Result<(), Error> {
+        self.send(&MidiMessage::NoteOff {
+            channel,
+            note,
+            velocity: 0,
+        })
+    }
+
+    /// Set the value of the given controller.
+    pub fn send_cc(&mut self, channel: u8, controller: u8, value: u8) -> Result<(), Error> {
+        self.send(&MidiMessage::ControlChange {
+            channel,
+            controller,
+            value,
+        })
+    }
+
+    /// Set the pitch bend, where `value` is in the range `-1.0..=1.0` and `0.0` is the center.
+    pub fn send_pitch_bend(&mut self, channel: u8, value: f32) -> Result<(), Error> {
+        self.send(&MidiMessage::PitchBend { channel, value })
+    }
+
+    /// Send the given raw bytes as-is.
+    pub fn send_raw(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        self.connection.send(bytes)?;
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+impl VirtualOutput {
+    /// Create a virtual output port with the given name.
+    pub fn new(name: &str) -> Result<Self, Error> {
+        use midir::os::unix::VirtualOutput as _;
+        let midi_out = midir::MidiOutput::new(CLIENT_NAME)?;
+        let connection = midi_out
+            .create_virtual(name)
+            .map_err(|err| Error::Connect(err.to_string()))?;
+        let port_name = name.to_string();
+        let output = Output {
+            connection,
+            port_name,
+        };
+        Ok(VirtualOutput { output })
+    }
+}
+
+#[cfg(unix)]
+impl std::ops::Deref for VirtualOutput {
+    type Target = Output;
+    fn deref(&self) -> &Self::Target {
+        &self.output
+    }
+}
+
+#[cfg(unix)]
+impl std::ops::DerefMut for VirtualOutput {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.output
+    }
+}
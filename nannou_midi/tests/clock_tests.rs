@@ -0,0 +1,31 @@
+use nannou_midi::{Clock, MidiMessage};
+use std::time::{Duration, Instant};
+
+#[test]
+fn midi_clock_test() {
+    let clock = Clock::new();
+    let msg = |byte: u8| MidiMessage::from_bytes(&[byte]).unwrap();
+    let start = Instant::now();
+    let pulse_interval = Duration::from_micros(20_833); // 120 bpm
+
+    clock.handle_message_at(&msg(0xFA), start);
+    assert!(clock.is_playing());
+    assert_eq!(clock.beats_at(start), 0.0);
+    for i in 0..36 {
+        clock.handle_message_at(&msg(0xF8), start + pulse_interval * i);
+    }
+    assert!((clock.bpm() - 120.0).abs() < 0.01);
+
+    // The last pulse was at position 35, with half a pulse of interpolation.
+    let at = start + pulse_interval * 35 + pulse_interval / 2;
+    let beats = clock.beats_at(at);
+    assert!((beats - 35.5 / 24.0).abs() < 1e-3);
+
+    // Interpolation never runs ahead of the next pulse.
+    let late = start + pulse_interval * 40;
+    assert!(clock.beats_at(late) < 36.0 / 24.0);
+
+    clock.handle_message_at(&msg(0xFC), late);
+    assert!(!clock.is_playing());
+    assert_eq!(clock.beats_at(late), 35.0 / 24.0);
+}
@@ -1,5 +1,12 @@
 //! Tools for working with OSC. [**sender()**](./fn.sender.html) creates an OSC sender,
 //! [**receiver(port)**](./fn.receiver.html) creates an OSC receiver.
+//!
+//! A receiver may be turned into a [**Router**](./router/struct.Router.html) via
+//! `Receiver::routed` in order to dispatch messages to handlers by address pattern.
+//!
+//! Message arguments are described by [**Type**](./enum.Type.html), which covers the full OSC 1.1
+//! type tag list: the standard `Int`, `Float`, `String` and `Blob` along with the extended
+//! `Long`, `Double`, `Time`, `Char`, `Color`, `Midi`, `Bool`, `Nil` and `Inf` types.
 
 pub use rosc;
 
@@ -13,14 +20,20 @@ pub use self::rosc::{
     decoder, encoder, OscBundle as Bundle, OscColor as Color, OscError as Error,
     OscMessage as Message, OscMidiMessage as MidiMessage, OscType as Type,
 };
+pub use self::router::{Args, Router};
 pub use self::send::Sender;
 
 use std;
 use std::net::{Ipv4Addr, SocketAddr};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 pub mod recv;
+pub mod router;
 pub mod send;
 
+// The number of seconds between the NTP epoch (1900) and the UNIX epoch (1970).
+const NTP_UNIX_OFFSET_SECS: u64 = 2_208_988_800;
+
 /// Indicates that a `Sender` is not currently connected to a target address, and that the target
 /// address will have to be supplied manually when sending packets.
 pub struct Unconnected;
@@ -30,12 +43,98 @@ pub struct Connected {
     addr: SocketAddr,
 }
 
-/// An *OSC packet* can contain an OSC message or a bundle of nested packets which is called an
-/// *OSC bundle*.
+/// An *OSC packet* can contain an OSC message or a bundle of packets which is called an *OSC
+/// bundle*.
+///
+/// Bundles may be nested, in which case each nested bundle keeps its own time tag.
 #[derive(Clone, Debug, PartialEq)]
 pub enum Packet {
     Message(Message),
-    Bundle(Bundle),
+    Bundle {
+        /// The time at which the bundle's content should take effect.
+        timetag: TimeTag,
+        /// The messages and nested bundles within the bundle.
+        content: Vec<Packet>,
+    },
+}
+
+/// An OSC time tag describing an absolute time as an NTP timestamp.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TimeTag {
+    /// Seconds since midnight on January 1st, 1900.
+    pub seconds: u32,
+    /// Fractions of a second in units of `1 / 2^32` seconds.
+    pub fractional: u32,
+}
+
+impl TimeTag {
+    /// The special time tag indicating that a bundle should take effect immediately.
+    pub const IMMEDIATELY: Self = TimeTag {
+        seconds: 0,
+        fractional: 1,
+    };
+
+    /// Create a time tag from the given system time.
+    ///
+    /// Times before the NTP epoch are clamped to the epoch.
+    pub fn from_system_time(time: SystemTime) -> Self {
+        let since_ntp_epoch = match time.duration_since(UNIX_EPOCH) {
+            Ok(since_unix) => since_unix + Duration::from_secs(NTP_UNIX_OFFSET_SECS),
+            Err(err) => Duration::from_secs(NTP_UNIX_OFFSET_SECS)
+                .checked_sub(err.duration())
+                .unwrap_or_default(),
+        };
+        let seconds = since_ntp_epoch.as_secs() as u32;
+        let fractional = ((since_ntp_epoch.subsec_nanos() as u64) << 32) / 1_000_000_000;
+        TimeTag {
+            seconds,
+            fractional: fractional as u32,
+        }
+    }
+
+    /// A time tag describing the current time.
+    pub fn now() -> Self {
+        Self::from_system_time(SystemTime::now())
+    }
+
+    /// A time tag describing the given duration from now.
+    pub fn from_now(duration: Duration) -> Self {
+        Self::from_system_time(SystemTime::now() + duration)
+    }
+
+    /// Whether or not this is the special `IMMEDIATELY` time tag.
+    pub fn is_immediately(&self) -> bool {
+        *self == Self::IMMEDIATELY
+    }
+
+    /// The system time described by the time tag.
+    pub fn to_system_time(&self) -> SystemTime {
+        let nanos = (self.fractional as u64 * 1_000_000_000) >> 32;
+        let since_ntp_epoch = Duration::new(self.seconds as u64, nanos as u32);
+        let ntp_epoch = UNIX_EPOCH - Duration::from_secs(NTP_UNIX_OFFSET_SECS);
+        ntp_epoch + since_ntp_epoch
+    }
+}
+
+impl Default for TimeTag {
+    fn default() -> Self {
+        Self::IMMEDIATELY
+    }
+}
+
+impl From<(u32, u32)> for TimeTag {
+    fn from((seconds, fractional): (u32, u32)) -> Self {
+        TimeTag {
+            seconds,
+            fractional,
+        }
+    }
+}
+
+impl Into<Type> for TimeTag {
+    fn into(self) -> Type {
+        Type::Time(self.seconds, self.fractional)
+    }
 }
 
 impl From<Message> for Packet {
@@ -46,7 +145,15 @@ impl From<Message> for Packet {
 
 impl From<Bundle> for Packet {
     fn from(bundle: Bundle) -> Self {
-        Packet::Bundle(bundle)
+        let timetag = match bundle.timetag {
+            Type::Time(seconds, fractional) => TimeTag {
+                seconds,
+                fractional,
+            },
+            _ => TimeTag::IMMEDIATELY,
+        };
+        let content = bundle.content.into_iter().map(Packet::from).collect();
+        Packet::Bundle { timetag, content }
     }
 }
 
@@ -72,19 +179,31 @@ impl Into<rosc::OscPacket> for Packet {
     fn into(self) -> rosc::OscPacket {
         match self {
             Packet::Message(msg) => rosc::OscPacket::Message(msg),
-            Packet::Bundle(bundle) => rosc::OscPacket::Bundle(bundle),
+            Packet::Bundle { timetag, content } => {
+                let timetag = timetag.into();
+                let content = content.into_iter().map(Into::into).collect();
+                rosc::OscPacket::Bundle(Bundle { timetag, content })
+            }
         }
     }
 }
 
 impl Packet {
-    /// A recursive function that unfolds the packet into the end of the given buffer of messages.
+    /// Create a bundle of the given messages that should take effect at the given time.
+    pub fn bundle(timetag: TimeTag, messages: Vec<Message>) -> Self {
+        let content = messages.into_iter().map(Packet::Message).collect();
+        Packet::Bundle { timetag, content }
+    }
+
+    /// Unfold the packet into the end of the given buffer of messages.
+    ///
+    /// The messages of nested bundles are unfolded in order, discarding their time tags.
     pub fn unfold(self, msgs: &mut Vec<Message>) {
         match self {
             Packet::Message(msg) => msgs.push(msg),
-            Packet::Bundle(bundle) => {
-                for packet in bundle.content {
-                    Packet::unfold(packet.into(), msgs);
+            Packet::Bundle { content, .. } => {
+                for packet in content {
+                    packet.unfold(msgs);
                 }
             }
         }
@@ -0,0 +1,288 @@
+//! Items related to dispatching received OSC messages to handlers by address pattern.
+
+use super::{Connected, Message, Packet, Receiver, TimeTag, Type, Unconnected};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::time::SystemTime;
+
+/// The address and arguments of a message delivered to a `Router` handler.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Args<'a> {
+    /// The address to which the message was sent.
+    pub addr: &'a str,
+    /// The message arguments.
+    pub args: &'a [Type],
+}
+
+/// Dispatches the messages received by a `Receiver` to the handlers whose pattern matches their
+/// address.
+///
+/// Created via `Receiver::routed`. Call `update` regularly, e.g. within the app's `update`
+/// function, to receive and dispatch pending packets.
+pub struct Router<M = Unconnected> {
+    receiver: Receiver<M>,
+    methods: Vec<(String, fn(Args))>,
+    schedule_bundles: bool,
+    scheduled: BinaryHeap<Scheduled>,
+    // The latest time given to `update`.
+    time: f64,
+    // Incremented for each scheduled bundle so that bundles due at the same time keep their order.
+    next_id: u64,
+}
+
+// A bundle awaiting dispatch.
+struct Scheduled {
+    due: f64,
+    id: u64,
+    content: Vec<Packet>,
+}
+
+impl<M> Receiver<M> {
+    /// Convert the receiver into a `Router` for registering address pattern handlers.
+    pub fn routed(self) -> Router<M> {
+        Router {
+            receiver: self,
+            methods: vec![],
+            schedule_bundles: false,
+            scheduled: BinaryHeap::new(),
+            time: 0.0,
+            next_id: 0,
+        }
+    }
+}
+
+impl<M> Router<M> {
+    /// Call `handler` for each message whose address matches the given pattern.
+    ///
+    /// Patterns support the wildcards described by the OSC spec:
+    ///
+    /// - `?` matches any single character.
+    /// - `*` matches any sequence of zero or more characters.
+    /// - `[abc]` matches any of the listed characters, `[a-z]` any in the range and `[!abc]` any
+    ///   character not listed.
+    /// - `{foo,bar}` matches any of the comma separated strings.
+    ///
+    /// Wildcards never match the `/` separating address parts.
+    pub fn add_method(mut self, pattern: &str, handler: fn(Args)) -> Self {
+        self.methods.push((pattern.to_string(), handler));
+        self
+    }
+
+    /// Whether or not to delay the messages of bundles until the time described by their time
+    /// tag.
+    ///
+    /// By default this is `false` and bundle contents are dispatched as soon as they are received.
+    pub fn schedule_bundles(mut self, schedule: bool) -> Self {
+        self.schedule_bundles = schedule;
+        self
+    }
+
+    /// The inner receiver.
+    pub fn receiver(&self) -> &Receiver<M> {
+        &self.receiver
+    }
+
+    /// The number of scheduled bundles awaiting dispatch.
+    pub fn scheduled_len(&self) -> usize {
+        self.scheduled.len()
+    }
+
+    /// Dispatch the given packet as though it had been received.
+    ///
+    /// If bundle scheduling is enabled and the packet is a bundle with a future time tag, its
+    /// content is queued until an `update` at or after that time. Nested bundles are scheduled by
+    /// their own time tag once the enclosing bundle is dispatched.
+    pub fn dispatch(&mut self, packet: Packet) {
+        match packet {
+            Packet::Message(msg) => self.dispatch_message(&msg),
+            Packet::Bundle { timetag, content } => {
+                let delay = if self.schedule_bundles && !timetag.is_immediately() {
+                    delay_secs(timetag)
+                } else {
+                    0.0
+                };
+                if delay > 0.0 {
+                    let due = self.time + delay;
+                    let id = self.next_id;
+                    self.next_id += 1;
+                    self.scheduled.push(Scheduled { due, id, content });
+                } else {
+                    for packet in content {
+                        self.dispatch(packet);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Call each handler whose pattern matches the address of the given message.
+    pub fn dispatch_message(&self, msg: &Message) {
+        let args = match msg.args {
+            Some(ref args) => &args[..],
+            None => &[],
+        };
+        let args = Args {
+            addr: &msg.addr,
+            args,
+        };
+        for &(ref pattern, handler) in &self.methods {
+            if matches(pattern, &msg.addr) {
+                handler(args);
+            }
+        }
+    }
+
+    /// Dispatch all scheduled bundles that are due at the given time in seconds.
+    ///
+    /// This is called by `update`, but may be useful when dispatching packets manually.
+    pub fn flush(&mut self, time: f64) {
+        self.time = time;
+        while self
+            .scheduled
+            .peek()
+            .map(|s| s.due <= time)
+            .unwrap_or(false)
+        {
+            let scheduled = self.scheduled.pop().expect("no scheduled bundle");
+            for packet in scheduled.content {
+                self.dispatch(packet);
+            }
+        }
+    }
+}
+
+impl Router<Unconnected> {
+    /// Dispatch all pending packets, followed by any scheduled bundles that are now due.
+    ///
+    /// `time` is the time in seconds against which bundles are scheduled, e.g. `app.time`.
+    pub fn update(&mut self, time: f32) {
+        self.time = time as f64;
+        let packets: Vec<_> = self.receiver.try_iter().map(|(packet, _)| packet).collect();
+        for packet in packets {
+            self.dispatch(packet);
+        }
+        self.flush(time as f64);
+    }
+}
+
+impl Router<Connected> {
+    /// Dispatch all pending packets, followed by any scheduled bundles that are now due.
+    ///
+    /// `time` is the time in seconds against which bundles are scheduled, e.g. `app.time`.
+    pub fn update(&mut self, time: f32) {
+        self.time = time as f64;
+        let packets: Vec<_> = self.receiver.try_iter().collect();
+        for packet in packets {
+            self.dispatch(packet);
+        }
+        self.flush(time as f64);
+    }
+}
+
+impl PartialEq for Scheduled {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Scheduled {}
+
+impl PartialOrd for Scheduled {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Scheduled {
+    // Reversed so that the `BinaryHeap` yields the earliest bundle first.
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .due
+            .partial_cmp(&self.due)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| other.id.cmp(&self.id))
+    }
+}
+
+/// Whether or not the given OSC address pattern matches the given address.
+///
+/// See `Router::add_method` for the supported wildcards.
+pub fn matches(pattern: &str, addr: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let addr: Vec<char> = addr.chars().collect();
+    matches_chars(&pattern, &addr)
+}
+
+fn matches_chars(pattern: &[char], addr: &[char]) -> bool {
+    let (&p, rest) = match pattern.split_first() {
+        None => return addr.is_empty(),
+        Some(split) => split,
+    };
+    match p {
+        '*' => {
+            // Try each possible length of the run, stopping at the end of the address part.
+            let max = addr.iter().position(|&c| c == '/').unwrap_or(addr.len());
+            (0..=max).any(|n| matches_chars(rest, &addr[n..]))
+        }
+        '?' => match addr.split_first() {
+            Some((&c, addr)) if c != '/' => matches_chars(rest, addr),
+            _ => false,
+        },
+        '[' => {
+            let end = match rest.iter().position(|&c| c == ']') {
+                Some(end) => end,
+                None => return false,
+            };
+            let c = match addr.split_first() {
+                Some((&c, _)) if c != '/' => c,
+                _ => return false,
+            };
+            if matches_set(&rest[..end], c) {
+                matches_chars(&rest[end + 1..], &addr[1..])
+            } else {
+                false
+            }
+        }
+        '{' => {
+            let end = match rest.iter().position(|&c| c == '}') {
+                Some(end) => end,
+                None => return false,
+            };
+            rest[..end].split(|&c| c == ',').any(|alt| {
+                addr.starts_with(alt) && matches_chars(&rest[end + 1..], &addr[alt.len()..])
+            })
+        }
+        p => match addr.split_first() {
+            Some((&c, addr)) if c == p => matches_chars(rest, addr),
+            _ => false,
+        },
+    }
+}
+
+// Whether the given character is within the contents of a `[...]` set.
+fn matches_set(set: &[char], c: char) -> bool {
+    let (negate, set) = match set.split_first() {
+        Some((&'!', set)) => (true, set),
+        _ => (false, set),
+    };
+    let mut found = false;
+    let mut i = 0;
+    while i < set.len() {
+        if i + 2 < set.len() && set[i + 1] == '-' {
+            found |= set[i] <= c && c <= set[i + 2];
+            i += 3;
+        } else {
+            found |= set[i] == c;
+            i += 1;
+        }
+    }
+    found != negate
+}
+
+// The number of seconds from now until the given time tag, or `0.0` if it has passed.
+fn delay_secs(timetag: TimeTag) -> f64 {
+    match timetag.to_system_time().duration_since(SystemTime::now()) {
+        Ok(delay) => delay.as_secs_f64(),
+        Err(_) => 0.0,
+    }
+}
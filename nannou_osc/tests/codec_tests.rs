@@ -0,0 +1,72 @@
+use nannou_osc as osc;
+use nannou_osc::{Color, Packet, TimeTag, Type};
+
+fn round_trip(args: Vec<Type>) -> Vec<Type> {
+    let packet: Packet = osc::msg("/test", args).into();
+    let bytes = osc::encode(packet).unwrap();
+    assert_eq!(bytes.len() % 4, 0);
+    match osc::decode(&bytes).unwrap() {
+        Packet::Message(msg) => msg.args.unwrap_or_default(),
+        Packet::Bundle { .. } => panic!("expected a message"),
+    }
+}
+
+#[test]
+fn extended_types_round_trip() {
+    let color = Color {
+        red: 255,
+        green: 128,
+        blue: 0,
+        alpha: 64,
+    };
+    let args = vec![
+        Type::Int(-7),
+        Type::Float(0.5),
+        Type::String("nannou".into()),
+        Type::Bool(true),
+        Type::Time(1, 2),
+        Type::Color(color),
+        Type::Blob(vec![1, 2, 3, 4, 5]),
+        Type::Double(std::f64::consts::PI),
+        Type::Long(i64::min_value()),
+        Type::Char('n'),
+    ];
+    assert_eq!(round_trip(args.clone()), args);
+}
+
+#[test]
+fn color_and_blob_encoding() {
+    let color = Color {
+        red: 1,
+        green: 2,
+        blue: 3,
+        alpha: 4,
+    };
+    let args = vec![Type::Color(color), Type::Blob(vec![9, 8, 7])];
+    let bytes = osc::encode(osc::msg("/a", args).into()).unwrap();
+    let expected: &[u8] = &[
+        b'/', b'a', 0, 0, // Address.
+        b',', b'r', b'b', 0, // Type tags.
+        1, 2, 3, 4, // Color.
+        0, 0, 0, 3, 9, 8, 7, 0, // Blob length prefix, data and padding.
+    ];
+    assert_eq!(bytes, expected);
+}
+
+#[test]
+fn nested_bundle_round_trip() {
+    let inner = Packet::bundle(TimeTag::from((10, 0)), vec![osc::msg("/b", vec![])]);
+    let outer = Packet::Bundle {
+        timetag: TimeTag::from((5, 0)),
+        content: vec![osc::msg("/a", vec![]).into(), inner.clone()],
+    };
+    let bytes = osc::encode(outer.clone()).unwrap();
+    let decoded = osc::decode(&bytes).unwrap();
+    assert_eq!(decoded, outer);
+    match decoded {
+        Packet::Bundle { content, .. } => assert_eq!(content[1], inner),
+        Packet::Message(_) => panic!("expected a bundle"),
+    }
+    let addrs: Vec<_> = outer.into_msgs().into_iter().map(|msg| msg.addr).collect();
+    assert_eq!(addrs, ["/a", "/b"]);
+}
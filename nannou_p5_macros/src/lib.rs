@@ -0,0 +1,121 @@
+//! The `#[use_p5_compat]` attribute, providing the implicit draw context required by the p5.js
+//! compatibility functions within [**nannou**](https://docs.rs/nannou)'s `compat::p5` module.
+//!
+//! Usually accessed as `nannou::compat::p5::use_p5_compat` via nannou's `p5` feature.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{FnArg, Ident, ItemFn, Pat};
+
+/// Provide an implicit draw context to the p5.js compatibility functions called within a `view`.
+///
+/// The attribute is applied to a sketch's `view` function. The first argument must be the `&App`
+/// and the last argument must be the `Frame`, as is the case for both `app` and `sketch` views.
+/// Before the body runs, the app's **Draw** is installed as the implicit draw context. Once the
+/// body completes, including via an early `return`, the context is removed and the drawing is
+/// submitted to the frame. If the body panics, the context is removed without submitting.
+///
+/// As the frame is used after the body, the body may borrow but not move the frame.
+///
+/// ```ignore
+/// use nannou::compat::p5::*;
+///
+/// #[use_p5_compat]
+/// fn view(app: &App, frame: Frame) {
+///     background(30.0, 30.0, 40.0);
+///     fill(220.0, 80.0, 80.0, 255.0);
+///     noStroke();
+///     ellipse(0.0, 0.0, 100.0, 100.0);
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn use_p5_compat(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        let attr = proc_macro2::TokenStream::from(attr);
+        let msg = "`#[use_p5_compat]` does not accept any arguments";
+        return syn::Error::new(attr.span(), msg).to_compile_error().into();
+    }
+    let item = match syn::parse::<ItemFn>(item) {
+        Ok(item) => item,
+        Err(err) => {
+            let msg = "`#[use_p5_compat]` must be applied to a `view` function";
+            return syn::Error::new(err.span(), msg).to_compile_error().into();
+        }
+    };
+    let (app, frame) = match view_args(&item) {
+        Ok(args) => args,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = item;
+    let output = quote! {
+        #(#attrs)*
+        #vis #sig {
+            let __p5_guard = nannou::compat::p5::ViewGuard::new(#app, &#frame);
+            #block
+        }
+    };
+    output.into()
+}
+
+// The names of the `&App` and `Frame` arguments of the given `view` function.
+fn view_args(item: &ItemFn) -> syn::Result<(Ident, Ident)> {
+    let inputs = &item.sig.inputs;
+    if inputs.len() < 2 {
+        let msg = "`#[use_p5_compat]` expects a `view` function of the form \
+                   `fn view(app: &App, .., frame: Frame)`";
+        return Err(syn::Error::new(item.sig.span(), msg));
+    }
+    let name = |arg: &FnArg| match *arg {
+        FnArg::Typed(ref pat_type) => match *pat_type.pat {
+            Pat::Ident(ref pat_ident) => Ok(pat_ident.ident.clone()),
+            _ => {
+                let msg = "the `&App` and `Frame` arguments of `view` must be named";
+                Err(syn::Error::new(pat_type.pat.span(), msg))
+            }
+        },
+        FnArg::Receiver(ref receiver) => {
+            let msg = "`#[use_p5_compat]` cannot be applied to methods";
+            Err(syn::Error::new(receiver.span(), msg))
+        }
+    };
+    let app = name(&inputs[0])?;
+    let frame = name(&inputs[inputs.len() - 1])?;
+    Ok((app, frame))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::view_args;
+    use syn::ItemFn;
+
+    fn view_arg_names(item: &str) -> syn::Result<(String, String)> {
+        let item: ItemFn = syn::parse_str(item).expect("failed to parse test function");
+        view_args(&item).map(|(app, frame)| (app.to_string(), frame.to_string()))
+    }
+
+    #[test]
+    fn test_view_args() {
+        let names = view_arg_names("fn view(app: &App, frame: Frame) {}").unwrap();
+        assert_eq!(names, ("app".to_string(), "frame".to_string()));
+        // Arrows and nested generics within argument types are not mistaken for angle brackets.
+        let names = view_arg_names(
+            "fn view(a: &App, f: &dyn Fn(u8) -> u8, m: &Vec<Vec<u8>>, mut fr: Frame) {}",
+        )
+        .unwrap();
+        assert_eq!(names, ("a".to_string(), "fr".to_string()));
+    }
+
+    #[test]
+    fn test_view_args_errors() {
+        assert!(view_arg_names("fn view(frame: Frame) {}").is_err());
+        assert!(view_arg_names("fn view(_: &App, frame: Frame) {}").is_err());
+    }
+}